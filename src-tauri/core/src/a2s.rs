@@ -0,0 +1,280 @@
+// A2S - Steam's server query protocol over UDP: A2S_INFO for the name, map and player
+// count, A2S_PLAYER for who's on. Servers may answer with a challenge first, which is sent
+// back with the request, and split long answers over several packets.
+
+use crate::games::GameConfig;
+use crate::server::{lifecycle, Server};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::net::UdpSocket;
+
+const SINGLE: i32 = -1;
+const SPLIT: i32 = -2;
+const CHALLENGE: u8 = 0x41;
+const INFO_REQUEST: u8 = 0x54;
+const INFO_RESPONSE: u8 = 0x49;
+const PLAYER_REQUEST: u8 = 0x55;
+const PLAYER_RESPONSE: u8 = 0x44;
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Where a game answers Steam queries
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SteamQuery {
+    /// The variable holding the query port, None when it's the game port
+    #[serde(default)]
+    pub port_var: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum A2sError {
+    #[error("Query failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The server didn't answer the query")]
+    Timeout,
+    #[error("Unexpected query answer: {0}")]
+    Protocol(String),
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ServerInfo {
+    pub name: String,
+    pub map: String,
+    /// The game's name as the server reports it
+    pub game: String,
+    pub players: u8,
+    pub max_players: u8,
+    pub bots: u8,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub score: i32,
+    /// Seconds connected
+    pub duration: f32,
+}
+
+/// Reads the little-endian fields and null-terminated strings of an answer
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], A2sError> {
+        if self.0.len() < n {
+            return Err(A2sError::Protocol("answer cut short".to_string()));
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, A2sError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, A2sError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32, A2sError> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, A2sError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, A2sError> {
+        let end = self.0.iter().position(|&b| b == 0).ok_or_else(|| A2sError::Protocol("unterminated string".to_string()))?;
+        let text = String::from_utf8_lossy(&self.0[..end]).into_owned();
+        self.0 = &self.0[end + 1..];
+        Ok(text)
+    }
+}
+
+fn parse_info(payload: &[u8]) -> Result<ServerInfo, A2sError> {
+    let mut r = Reader(payload);
+    let _protocol = r.u8()?;
+    let name = r.string()?;
+    let map = r.string()?;
+    let _folder = r.string()?;
+    let game = r.string()?;
+    let _app_id = r.u16()?;
+    let players = r.u8()?;
+    let max_players = r.u8()?;
+    let bots = r.u8()?;
+    let _server_type = r.u8()?;
+    let _environment = r.u8()?;
+    let _visibility = r.u8()?;
+    let _vac = r.u8()?;
+    // Older servers end before the version
+    let version = r.string().unwrap_or_default();
+    Ok(ServerInfo { name, map, game, players, max_players, bots, version })
+}
+
+fn parse_players(payload: &[u8]) -> Result<Vec<Player>, A2sError> {
+    let mut r = Reader(payload);
+    let count = r.u8()?;
+    let mut players = Vec::with_capacity(count.into());
+    for _ in 0..count {
+        let _index = r.u8()?;
+        let name = r.string()?;
+        let score = r.i32()?;
+        let duration = r.f32()?;
+        players.push(Player { name, score, duration });
+    }
+    // Players still joining have no name yet
+    players.retain(|p| !p.name.is_empty());
+    Ok(players)
+}
+
+/// An answer's kind and payload, once its packets are all in
+async fn receive(socket: &UdpSocket) -> Result<(u8, Vec<u8>), A2sError> {
+    let mut parts = BTreeMap::new();
+    let mut buf = vec![0u8; 1400];
+    loop {
+        let len = tokio::time::timeout(TIMEOUT, socket.recv(&mut buf)).await.map_err(|_| A2sError::Timeout)??;
+        let mut r = Reader(&buf[..len]);
+        match r.i32()? {
+            SINGLE => {
+                let kind = r.u8()?;
+                return Ok((kind, r.0.to_vec()));
+            }
+            SPLIT => {
+                let id = r.i32()?;
+                if id < 0 {
+                    return Err(A2sError::Protocol("compressed answers aren't supported".to_string()));
+                }
+                let total = r.u8()?;
+                let number = r.u8()?;
+                let _size = r.u16()?;
+                parts.insert(number, r.0.to_vec());
+                if parts.len() == total as usize {
+                    let joined: Vec<u8> = parts.into_values().flatten().collect();
+                    let mut r = Reader(&joined);
+                    if r.i32()? != SINGLE {
+                        return Err(A2sError::Protocol("bad split answer".to_string()));
+                    }
+                    let kind = r.u8()?;
+                    return Ok((kind, r.0.to_vec()));
+                }
+            }
+            header => return Err(A2sError::Protocol(format!("header {}", header))),
+        }
+    }
+}
+
+/// Send a request, answering a challenge when the server asks for one
+async fn request(addr: SocketAddr, kind: u8, payload: &[u8], expected: u8) -> Result<Vec<u8>, A2sError> {
+    let socket = UdpSocket::bind(if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" }).await?;
+    socket.connect(addr).await?;
+    let packet = |challenge: Option<&[u8]>| {
+        let mut bytes = SINGLE.to_le_bytes().to_vec();
+        bytes.push(kind);
+        bytes.extend_from_slice(payload);
+        bytes.extend_from_slice(challenge.unwrap_or(&[]));
+        bytes
+    };
+    // A2S_PLAYER asks for a challenge with -1 in its place
+    let first = if kind == PLAYER_REQUEST { packet(Some(&SINGLE.to_le_bytes())) } else { packet(None) };
+    socket.send(&first).await?;
+    for _ in 0..3 {
+        let (answer, body) = receive(&socket).await?;
+        match answer {
+            CHALLENGE if body.len() >= 4 => {
+                socket.send(&packet(Some(&body[..4]))).await?;
+            }
+            kind if kind == expected => return Ok(body),
+            other => return Err(A2sError::Protocol(format!("answer type {:#x}", other))),
+        }
+    }
+    Err(A2sError::Protocol("too many challenges".to_string()))
+}
+
+pub async fn info(addr: SocketAddr) -> Result<ServerInfo, A2sError> {
+    parse_info(&request(addr, INFO_REQUEST, b"Source Engine Query\0", INFO_RESPONSE).await?)
+}
+
+pub async fn players(addr: SocketAddr) -> Result<Vec<Player>, A2sError> {
+    parse_players(&request(addr, PLAYER_REQUEST, &[], PLAYER_RESPONSE).await?)
+}
+
+/// The port a server answers Steam queries on, None when its game doesn't
+pub fn query_port(server: &Server, game: &GameConfig) -> Option<u16> {
+    let query = game.steam_query.as_ref()?;
+    let Some(var) = &query.port_var else { return Some(server.port) };
+    let extra_ports = lifecycle::extra_ports(server, Some(game));
+    let env = lifecycle::container_env(game, server.memory_mb, server.port, &server.config, &server.env, &extra_ports);
+    env.get(var)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info_payload() -> Vec<u8> {
+        let mut p = vec![17];
+        for s in ["My Rust Server", "Procedural Map", "rust", "Rust"] {
+            p.extend_from_slice(s.as_bytes());
+            p.push(0);
+        }
+        p.extend_from_slice(&252490u32.to_le_bytes()[..2]);
+        p.extend_from_slice(&[3, 50, 0, b'd', b'l', 0, 1]);
+        p.extend_from_slice(b"2577\0");
+        p
+    }
+
+    #[test]
+    fn test_parse_info() {
+        let info = parse_info(&info_payload()).unwrap();
+        assert_eq!(info.name, "My Rust Server");
+        assert_eq!(info.map, "Procedural Map");
+        assert_eq!((info.players, info.max_players, info.bots), (3, 50, 0));
+        assert_eq!(info.version, "2577");
+        assert!(parse_info(&info_payload()[..20]).is_err());
+    }
+
+    #[test]
+    fn test_parse_players_skips_unnamed() {
+        let mut p = vec![2];
+        p.extend_from_slice(&[0, b'a', b'l', b'i', 0]);
+        p.extend_from_slice(&5i32.to_le_bytes());
+        p.extend_from_slice(&61.5f32.to_le_bytes());
+        p.extend_from_slice(&[0, 0]);
+        p.extend_from_slice(&0i32.to_le_bytes());
+        p.extend_from_slice(&1.0f32.to_le_bytes());
+        assert_eq!(parse_players(&p).unwrap(), vec![Player { name: "ali".into(), score: 5, duration: 61.5 }]);
+    }
+
+    #[tokio::test]
+    async fn test_challenge_and_split_answer() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1400];
+            let (_, client) = server.recv_from(&mut buf).await.unwrap();
+            let mut challenge = SINGLE.to_le_bytes().to_vec();
+            challenge.extend_from_slice(&[CHALLENGE, 9, 8, 7, 6]);
+            server.send_to(&challenge, client).await.unwrap();
+
+            let (len, client) = server.recv_from(&mut buf).await.unwrap();
+            assert_eq!(&buf[len - 4..len], &[9, 8, 7, 6]);
+            let mut whole = SINGLE.to_le_bytes().to_vec();
+            whole.push(INFO_RESPONSE);
+            whole.extend_from_slice(&info_payload());
+            let (first, second) = whole.split_at(10);
+            for (number, part) in [(1u8, second), (0, first)] {
+                let mut packet = SPLIT.to_le_bytes().to_vec();
+                packet.extend_from_slice(&7i32.to_le_bytes());
+                packet.extend_from_slice(&[2, number]);
+                packet.extend_from_slice(&1248u16.to_le_bytes());
+                packet.extend_from_slice(part);
+                server.send_to(&packet, client).await.unwrap();
+            }
+        });
+        assert_eq!(info(addr).await.unwrap().name, "My Rust Server");
+    }
+}
@@ -0,0 +1,83 @@
+// Atomic file writes - write to a temp file next to the target and rename it
+// into place, so a crash mid-write never leaves a half-written file behind
+
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Replace `path` with `contents` in one step
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp_path = sibling(path, "tmp");
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        std::fs::remove_file(&temp_path).ok();
+    }
+    result
+}
+
+/// Like `write_atomic`, keeping the previous version as `<file>.bak`
+pub fn write_with_backup(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    if path.exists() {
+        std::fs::copy(path, backup_path(path))?;
+    }
+    write_atomic(path, contents)
+}
+
+/// Parse a JSON file, falling back to its `.bak` if the file is missing or damaged
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let parse = |p: &Path| {
+        std::fs::read_to_string(p)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    };
+    parse(path).or_else(|| {
+        let backup = backup_path(path);
+        let value = parse(&backup);
+        if value.is_some() {
+            tracing::warn!("{} is unreadable, using {}", path.display(), backup.display());
+        }
+        value
+    })
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, "bak")
+}
+
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(extension);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_with_backup_keeps_previous_version() {
+        let dir = std::env::temp_dir().join(format!("sw-atomic-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("settings.json");
+
+        write_with_backup(&path, r#"{"a": 1}"#).unwrap();
+        write_with_backup(&path, r#"{"a": 2}"#).unwrap();
+        assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), r#"{"a": 1}"#);
+        assert!(!sibling(&path, "tmp").exists());
+
+        // A damaged file falls back to the backup
+        std::fs::write(&path, "{\"a\": ").unwrap();
+        let value: serde_json::Value = read_json(&path).unwrap();
+        assert_eq!(value["a"], 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,196 @@
+// Cron expressions - the five fields of a crontab line (minute, hour, day of month, month,
+// day of week) with lists, ranges, steps and month and weekday names, plus the @hourly,
+// @daily, @weekly and @monthly shorthands. Times are matched in whatever timezone they're
+// given in.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike};
+use std::fmt;
+
+/// How far ahead a next run is looked for - an expression like "0 0 30 2 *" never runs
+const SEARCH_DAYS: i64 = 5 * 366;
+
+const MONTHS: &[&str] = &["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAYS: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cron {
+    expression: String,
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days: Vec<bool>,
+    months: Vec<bool>,
+    /// Sunday is 0 (and 7 when parsing)
+    weekdays: Vec<bool>,
+    /// Whether the day of month and day of week fields were both restricted - a day then
+    /// matches when either does, as in crontab
+    either_day: bool,
+}
+
+impl Cron {
+    pub fn parse(expression: &str) -> Result<Cron, String> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "'{}' needs five fields: minute, hour, day of month, month and day of week",
+                expression.trim()
+            ));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7, WEEKDAYS).map_err(|e| format!("Day of week: {}", e))?;
+        // 7 is another way to write Sunday
+        weekdays[0] |= weekdays[7];
+        weekdays.truncate(7);
+        Ok(Cron {
+            expression: expression.trim().to_string(),
+            minutes: parse_field(minute, 0, 59, &[]).map_err(|e| format!("Minute: {}", e))?,
+            hours: parse_field(hour, 0, 23, &[]).map_err(|e| format!("Hour: {}", e))?,
+            days: parse_field(day, 1, 31, &[]).map_err(|e| format!("Day of month: {}", e))?,
+            months: parse_field(month, 1, 12, MONTHS).map_err(|e| format!("Month: {}", e))?,
+            weekdays,
+            either_day: !day.starts_with('*') && !weekday.starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !self.months[date.month() as usize] {
+            return false;
+        }
+        let day = self.days[date.day() as usize];
+        let weekday = self.weekdays[date.weekday().num_days_from_sunday() as usize];
+        if self.either_day {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+
+    /// The first time after `after` the expression matches, in its timezone. Local times a
+    /// clock change skips are passed over.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        for offset in 0..SEARCH_DAYS {
+            let date = start.date() + Duration::days(offset);
+            if !self.matches_day(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|h| self.hours[*h as usize]) {
+                for minute in (0..60).filter(|m| self.minutes[*m as usize]) {
+                    let Some(time) = date.and_hms_opt(hour, minute, 0) else { continue };
+                    if time < start {
+                        continue;
+                    }
+                    if let Some(time) = tz.from_local_datetime(&time).earliest() {
+                        if time > *after {
+                            return Some(time);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// One field as a table of which values match, indexed by value
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<Vec<bool>, String> {
+    let mut matches = vec![false; max as usize + 1];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("'{}' isn't a step", step))?;
+                if step == 0 {
+                    return Err("a step can't be 0".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (from, to) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((from, to)) => (value(from, min, max, names)?, value(to, min, max, names)?),
+                // A start with a step runs to the end, like "5/15"
+                None if step > 1 => (value(range, min, max, names)?, max),
+                None => {
+                    let single = value(range, min, max, names)?;
+                    (single, single)
+                }
+            },
+        };
+        if from > to {
+            return Err(format!("{} comes after {}", from, to));
+        }
+        for v in (from..=to).step_by(step as usize) {
+            matches[v as usize] = true;
+        }
+    }
+    Ok(matches)
+}
+
+fn value(text: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let lower = text.to_ascii_lowercase();
+    // Names count from the field's first value: jan is 1, sun is 0
+    if let Some(index) = names.iter().position(|name| *name == lower) {
+        return Ok(index as u32 + min);
+    }
+    let number: u32 = text.parse().map_err(|_| format!("'{}' isn't a number", text))?;
+    if number < min || number > max {
+        return Err(format!("{} isn't between {} and {}", number, min, max));
+    }
+    Ok(number)
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_next_runs() {
+        // Friday October 16th 2026, 20:00
+        let now = at(2026, 10, 16, 20, 0);
+        let next = |expression: &str| Cron::parse(expression).unwrap().next_after(&now);
+
+        assert_eq!(next("*/15 * * * *"), Some(at(2026, 10, 16, 20, 15)));
+        assert_eq!(next("0 4 * * *"), Some(at(2026, 10, 17, 4, 0)));
+        assert_eq!(next("30 18 * * mon-fri"), Some(at(2026, 10, 19, 18, 30)));
+        assert_eq!(next("0 6 * * 0"), next("0 6 * * 7"));
+        assert_eq!(next("0 6 * * 0"), Some(at(2026, 10, 18, 6, 0)));
+        assert_eq!(next("@monthly"), Some(at(2026, 11, 1, 0, 0)));
+        assert_eq!(next("0 12 1 jan *"), Some(at(2027, 1, 1, 12, 0)));
+        assert_eq!(next("0 0,12 * * *"), Some(at(2026, 10, 17, 0, 0)));
+        assert_eq!(next("5/20 20 * * *"), Some(at(2026, 10, 16, 20, 5)));
+        // Either day field matches when both are set: the 1st or any Monday
+        assert_eq!(next("0 9 1 * mon"), Some(at(2026, 10, 19, 9, 0)));
+        // February never has a 30th
+        assert_eq!(next("0 0 30 2 *"), None);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(Cron::parse("* * * *").is_err());
+        assert!(Cron::parse("60 * * * *").unwrap_err().starts_with("Minute"));
+        assert!(Cron::parse("0 0 * * funday").unwrap_err().starts_with("Day of week"));
+        assert!(Cron::parse("*/0 * * * *").is_err());
+        assert!(Cron::parse("0 20-4 * * *").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+// Docker connection - the endpoint DockerManager talks to. A socket (found automatically
+// unless one is set), or a remote daemon over TCP with optional TLS, and the API timeout.
+// A remote host that sleeps can be woken with Wake-on-LAN.
+
+use super::socket;
+use super::wake::WakeOnLan;
+use bollard::Docker;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::RwLock;
+
+lazy_static::lazy_static! {
+    static ref CONNECTION: RwLock<DockerConnection> = RwLock::new(DockerConnection::default());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DockerConnection {
+    /// Socket to use instead of the one found automatically
+    #[serde(default)]
+    pub socket: Option<String>,
+    /// tcp://, http:// or https:// address of a Docker daemon, used instead of a socket
+    #[serde(default)]
+    pub url: Option<String>,
+    /// TLS files for the daemon at `url` - ca.pem, cert.pem and key.pem as `docker`
+    /// reads them from DOCKER_CERT_PATH. All three or none.
+    #[serde(default)]
+    pub tls_ca: Option<String>,
+    #[serde(default)]
+    pub tls_cert: Option<String>,
+    #[serde(default)]
+    pub tls_key: Option<String>,
+    /// How long a Docker API call may take
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Wake the host at `url` before a scheduled start, or from Settings
+    #[serde(default)]
+    pub wake: Option<WakeOnLan>,
+}
+
+/// Same as bollard's own default
+fn default_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for DockerConnection {
+    fn default() -> Self {
+        Self {
+            socket: None,
+            url: None,
+            tls_ca: None,
+            tls_cert: None,
+            tls_key: None,
+            timeout_secs: default_timeout_secs(),
+            wake: None,
+        }
+    }
+}
+
+fn set(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+impl DockerConnection {
+    /// Whether the TLS files are set
+    pub fn uses_tls(&self) -> bool {
+        set(&self.tls_ca).is_some() || set(&self.tls_cert).is_some() || set(&self.tls_key).is_some()
+    }
+
+    /// Why these settings can't work, if they can't
+    pub fn validate(&self) -> Result<(), String> {
+        if self.timeout_secs == 0 {
+            return Err("The Docker timeout must be at least one second".into());
+        }
+        if let Some(url) = set(&self.url) {
+            if !["tcp://", "http://", "https://"].iter().any(|scheme| url.starts_with(scheme)) {
+                return Err("The Docker address must start with tcp://, http:// or https://".into());
+            }
+            if url.starts_with("https://") && !self.uses_tls() {
+                return Err("An https:// Docker address needs the TLS certificate files".into());
+            }
+        } else if self.uses_tls() {
+            return Err("TLS certificates are only used with a Docker address".into());
+        } else if self.wake.is_some() {
+            return Err("Wake-on-LAN is for a Docker host on another machine - set its address".into());
+        }
+        if let Some(wake) = &self.wake {
+            wake.validate()?;
+        }
+        if self.uses_tls() {
+            for (name, value) in [("CA", &self.tls_ca), ("certificate", &self.tls_cert), ("key", &self.tls_key)] {
+                match set(value) {
+                    None => return Err(format!("The TLS {} file is missing", name)),
+                    Some(path) if !Path::new(path).is_file() => {
+                        return Err(format!("The TLS {} file {} doesn't exist", name, path))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The socket or address this connects to, None for Docker's defaults (DOCKER_HOST or
+    /// the standard socket)
+    pub fn endpoint(&self) -> Option<String> {
+        set(&self.url)
+            .or(set(&self.socket))
+            .map(str::to_string)
+            .or_else(socket::resolve)
+    }
+
+    /// A client for this endpoint. Nothing is sent until the first call.
+    pub fn connect(&self) -> Result<Docker, bollard::errors::Error> {
+        let timeout = self.timeout_secs;
+        let version = bollard::API_DEFAULT_VERSION;
+        if let Some(url) = set(&self.url) {
+            return match (set(&self.tls_ca), set(&self.tls_cert), set(&self.tls_key)) {
+                (Some(ca), Some(cert), Some(key)) => {
+                    Docker::connect_with_ssl(url, Path::new(key), Path::new(cert), Path::new(ca), timeout, version)
+                }
+                _ => Docker::connect_with_http(url, timeout, version),
+            };
+        }
+        match self.endpoint() {
+            Some(path) => Docker::connect_with_socket(&path, timeout, version),
+            None => Ok(Docker::connect_with_local_defaults()?.with_timeout(std::time::Duration::from_secs(timeout))),
+        }
+    }
+}
+
+/// Use these settings for every DockerManager from now on
+pub fn configure(connection: DockerConnection) {
+    *CONNECTION.write().unwrap() = connection;
+}
+
+/// The settings DockerManager::new connects with
+pub fn current() -> DockerConnection {
+    CONNECTION.read().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_connection() {
+        assert!(DockerConnection::default().validate().is_ok());
+
+        let remote = DockerConnection {
+            url: Some("tcp://192.168.1.20:2375".into()),
+            ..Default::default()
+        };
+        assert!(remote.validate().is_ok());
+        assert_eq!(remote.endpoint().as_deref(), Some("tcp://192.168.1.20:2375"));
+
+        let bad_scheme = DockerConnection { url: Some("192.168.1.20:2375".into()), ..Default::default() };
+        assert!(bad_scheme.validate().is_err());
+
+        let half_tls = DockerConnection {
+            url: Some("https://docker.example:2376".into()),
+            tls_ca: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        };
+        assert!(half_tls.validate().unwrap_err().contains("TLS"));
+
+        let socket = DockerConnection { socket: Some("/tmp/colima.sock".into()), ..Default::default() };
+        assert_eq!(socket.endpoint().as_deref(), Some("/tmp/colima.sock"));
+
+        let no_timeout = DockerConnection { timeout_secs: 0, ..Default::default() };
+        assert!(no_timeout.validate().is_err());
+
+        let wake: WakeOnLan = serde_json::from_value(serde_json::json!({ "mac": "00:11:22:aa:bb:cc" })).unwrap();
+        let local_wake = DockerConnection { wake: Some(wake.clone()), ..Default::default() };
+        assert!(local_wake.validate().unwrap_err().contains("Wake-on-LAN"));
+        assert!(DockerConnection { wake: Some(wake), ..remote }.validate().is_ok());
+    }
+}
@@ -0,0 +1,66 @@
+// Container events - Docker's events stream, narrowed to containers starting and stopping
+
+use super::{DockerError, DockerManager};
+use crate::server::ServerStatus;
+use bollard::system::EventsOptions;
+use futures_util::stream::{Stream, StreamExt};
+use std::collections::HashMap;
+
+/// The actions that change a container's status
+const ACTIONS: &[&str] = &["start", "unpause", "die", "pause", "destroy"];
+
+#[derive(Debug, Clone)]
+pub struct ContainerEvent {
+    pub container_id: String,
+    pub name: String,
+    pub status: ServerStatus,
+}
+
+/// The status a container action leaves it in, as get_container_status would report it
+pub fn action_status(action: &str) -> Option<ServerStatus> {
+    match action {
+        "start" | "unpause" => Some(ServerStatus::Running),
+        "die" | "pause" | "destroy" => Some(ServerStatus::Stopped),
+        _ => None,
+    }
+}
+
+impl DockerManager {
+    /// Containers whose name starts with `prefix` starting and stopping, from now on.
+    /// Ends with an error or None when Docker goes away.
+    pub fn container_events<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Stream<Item = Result<ContainerEvent, DockerError>> + 'a {
+        let filters = HashMap::from([
+            ("type".to_string(), vec!["container".to_string()]),
+            ("event".to_string(), ACTIONS.iter().map(|a| a.to_string()).collect()),
+        ]);
+        let options = EventsOptions::<String> { filters, ..Default::default() };
+        self.client().events(Some(options)).filter_map(move |message| async move {
+            let message = match message {
+                Ok(message) => message,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let status = action_status(message.action.as_deref()?)?;
+            let actor = message.actor?;
+            let name = actor.attributes?.remove("name")?;
+            if !name.starts_with(prefix) {
+                return None;
+            }
+            Some(Ok(ContainerEvent { container_id: actor.id?, name, status }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_status() {
+        assert_eq!(action_status("start"), Some(ServerStatus::Running));
+        assert_eq!(action_status("die"), Some(ServerStatus::Stopped));
+        assert_eq!(action_status("exec_start: sh"), None);
+    }
+}
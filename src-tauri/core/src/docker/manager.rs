@@ -0,0 +1,1263 @@
+// Docker Manager - Handles all Docker operations
+
+use super::connection::{self, DockerConnection};
+use super::platform;
+use crate::server::ServerStatus;
+use bollard::container::{
+    AttachContainerOptions, AttachContainerResults,
+    Config, CreateContainerOptions, KillContainerOptions, ListContainersOptions,
+    LogOutput, LogsOptions, RemoveContainerOptions, StartContainerOptions,
+    StatsOptions, StopContainerOptions, TopOptions,
+};
+use bollard::image::CreateImageOptions;
+use bollard::models::{ContainerStateStatusEnum, HostConfig, PortBinding, PortMap};
+use bollard::Docker;
+use futures_util::stream::StreamExt;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use crate::games::{PortConfig, PortProtocol};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize)]
+pub struct DockerInfo {
+    pub version: String,
+    pub api_version: String,
+    pub os: String,
+    pub arch: String,
+    pub containers_running: u64,
+    pub containers_total: u64,
+    pub images: u64,
+    /// Memory containers can use - the VM's on Docker Desktop
+    pub memory_bytes: u64,
+}
+
+#[derive(Error, Debug)]
+#[allow(dead_code)]
+pub enum DockerError {
+    #[error("Docker connection error: {0}")]
+    ConnectionError(#[from] bollard::errors::Error),
+
+    #[error("Container not found: {0}")]
+    ContainerNotFound(String),
+
+    #[error("Image pull failed: {0}")]
+    ImagePullFailed(String),
+
+    #[error("Attach failed: {0}")]
+    AttachFailed(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerStats {
+    pub cpu_percent: f64,
+    pub memory_usage_mb: f64,
+    pub memory_limit_mb: f64,
+    pub memory_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerProcess {
+    pub pid: String,
+    pub user: String,
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+    pub rss_kb: u64,
+    pub command: String,
+}
+
+/// Extra settings for a run_script container
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOptions {
+    /// Environment variables as KEY=value
+    pub env: Vec<String>,
+    /// Keep stdin open so prompts can be answered with send_stdin
+    pub stdin: bool,
+    /// Network to run on instead of the default bridge
+    pub network: Option<String>,
+}
+
+/// What a game server's container is created from
+#[derive(Debug, Clone)]
+pub struct ContainerSpec<'a> {
+    /// Named serverwave-<name>
+    pub name: &'a str,
+    pub image: &'a str,
+    pub port: u16,
+    pub data_path: &'a Path,
+    pub env: &'a HashMap<String, String>,
+    pub extra_ports: &'a [PortConfig],
+    /// Where the data folder is mounted, /data when None
+    pub volume_path: Option<&'a str>,
+    pub memory_mb: Option<u32>,
+    pub startup_command: Option<&'a str>,
+}
+
+/// A container port published on the host
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PublishedPort {
+    pub container_port: u16,
+    pub host_port: u16,
+    /// "tcp" or "udp"
+    pub protocol: String,
+    pub host_ip: String,
+}
+
+/// The ports a game container publishes: the main port over TCP and UDP, then the extra
+/// ports over their own protocols, each on the same port of the host (local-only ones on
+/// its loopback address)
+pub fn published_ports(port: u16, extra_ports: &[PortConfig]) -> Vec<PublishedPort> {
+    let publish = |port: u16, protocol: &str, local_only: bool| PublishedPort {
+        container_port: port,
+        host_port: port,
+        protocol: protocol.to_string(),
+        host_ip: if local_only { "127.0.0.1" } else { "0.0.0.0" }.to_string(),
+    };
+    let mut ports = vec![publish(port, "tcp", false), publish(port, "udp", false)];
+    for extra in extra_ports {
+        let protocols: &[&str] = match extra.protocol {
+            PortProtocol::Tcp => &["tcp"],
+            PortProtocol::Udp => &["udp"],
+            PortProtocol::Both => &["tcp", "udp"],
+        };
+        ports.extend(protocols.iter().map(|protocol| publish(extra.container_port, protocol, extra.local_only)));
+    }
+    ports
+}
+
+/// Docker's port bindings (`"25565/tcp" -> [0.0.0.0:25565]`) as published ports, sorted.
+/// Exposed ports with no binding on the host are left out.
+pub fn bound_ports(bindings: &PortMap) -> Vec<PublishedPort> {
+    let mut ports = Vec::new();
+    for (key, bound) in bindings {
+        let (port, protocol) = key.split_once('/').unwrap_or((key.as_str(), "tcp"));
+        let Ok(container_port) = port.parse() else { continue };
+        for binding in bound.iter().flatten() {
+            let Some(host_port) = binding.host_port.as_deref().and_then(|p| p.parse().ok()) else { continue };
+            let host_ip = binding.host_ip.clone().filter(|ip| !ip.is_empty()).unwrap_or_else(|| "0.0.0.0".to_string());
+            ports.push(PublishedPort { container_port, host_port, protocol: protocol.to_string(), host_ip });
+        }
+    }
+    ports.sort_by(|a, b| {
+        (a.container_port, &a.protocol, &a.host_ip).cmp(&(b.container_port, &b.protocol, &b.host_ip))
+    });
+    ports
+}
+
+/// The command a game container runs for a startup line - from the volume folder, in bash
+pub fn container_command(volume_path: &str, startup: &str) -> Option<Vec<String>> {
+    if startup.is_empty() {
+        return None;
+    }
+    let full_cmd = format!("cd {} && exec {}", volume_path, startup);
+    Some(vec!["/bin/bash".to_string(), "-c".to_string(), full_cmd])
+}
+
+/// The mounts of a game container: its data folder, and the persistent machine-id in it
+pub fn container_binds(data_path: &Path, volume_path: &str) -> Vec<String> {
+    // Forward slashes for Docker on Windows
+    let data_path_str = data_path.to_string_lossy().replace('\\', "/");
+    vec![
+        format!("{}:{}", data_path_str, volume_path),
+        format!("{}/.machine-id:/etc/machine-id:ro", data_path_str),
+    ]
+}
+
+pub struct DockerManager {
+    docker: Docker,
+}
+
+impl DockerManager {
+    /// Create a new Docker manager instance, connected as the settings say - see connection::configure
+    pub async fn new() -> Result<Self, DockerError> {
+        Self::with_connection(&connection::current()).await
+    }
+
+    /// A Docker manager for other connection settings, e.g. to try them before saving
+    pub async fn with_connection(connection: &DockerConnection) -> Result<Self, DockerError> {
+        Ok(Self { docker: connection.connect()? })
+    }
+
+    /// A Docker manager on a client that's already connected, e.g. one shared between commands
+    pub fn from_client(docker: Docker) -> Self {
+        Self { docker }
+    }
+
+    /// Get the underlying Docker client (for advanced operations)
+    pub fn client(&self) -> &Docker {
+        &self.docker
+    }
+
+    /// Ping Docker to check if it's running
+    pub async fn ping(&self) -> Result<(), DockerError> {
+        self.docker.ping().await?;
+        Ok(())
+    }
+
+    /// Get Docker system information
+    pub async fn get_info(&self) -> Result<DockerInfo, DockerError> {
+        let info = self.docker.info().await?;
+        let version = self.docker.version().await?;
+
+        Ok(DockerInfo {
+            version: version.version.unwrap_or_default(),
+            api_version: version.api_version.unwrap_or_default(),
+            os: info.operating_system.unwrap_or_default(),
+            arch: info.architecture.unwrap_or_default(),
+            containers_running: info.containers_running.unwrap_or(0) as u64,
+            containers_total: info.containers.unwrap_or(0) as u64,
+            images: info.images.unwrap_or(0) as u64,
+            memory_bytes: info.mem_total.unwrap_or(0).max(0) as u64,
+        })
+    }
+
+    /// The platform the Docker host runs, like linux/arm64. Looked up once per process.
+    pub async fn host_platform(&self) -> Option<String> {
+        if let Some(platform) = platform::cached_host() {
+            return Some(platform);
+        }
+        let architecture = self.docker.info().await.ok()?.architecture?;
+        let platform = platform::from_architecture(&architecture)?;
+        platform::set_host(platform);
+        Some(platform.to_string())
+    }
+
+    /// Platforms an image is published for, asked from its registry
+    pub async fn image_platforms(&self, image: &str) -> Result<Vec<String>, DockerError> {
+        let inspect = self.docker.inspect_registry_image(image, None).await?;
+        Ok(inspect
+            .platforms
+            .iter()
+            .filter_map(|p| {
+                let os = p.os.as_deref()?;
+                let architecture = p.architecture.as_deref()?;
+                Some(platform::platform_string(os, architecture, p.variant.as_deref()))
+            })
+            .collect())
+    }
+
+    async fn pull_platform(&self, image: &str, platform: &str) -> Result<(), DockerError> {
+        let options = Some(CreateImageOptions {
+            from_image: image,
+            platform,
+            ..Default::default()
+        });
+
+        let mut stream = self.docker.create_image(options, None, None);
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(info) => {
+                    if let Some(status) = info.status {
+                        tracing::debug!("Pulling {}: {}", image, status);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to pull image {}: {}", image, e);
+                    return Err(DockerError::ImagePullFailed(e.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Pull a Docker image for the host's platform. An image without a build for it is
+    /// pulled for linux/amd64 instead, to run under emulation - see platform::emulated.
+    pub async fn pull_image(&self, image: &str) -> Result<(), DockerError> {
+        tracing::info!("Pulling image: {}", image);
+        let host = self.host_platform().await;
+
+        let mut pulled = host.clone().unwrap_or_default();
+        if let Err(e) = self.pull_platform(image, &pulled).await {
+            let retry = host.as_deref().is_some_and(|h| h != platform::AMD64)
+                && platform::is_missing_platform(&e.to_string());
+            if !retry {
+                return Err(e);
+            }
+            tracing::warn!("{} has no {} build, pulling {} to run under emulation", image, pulled, platform::AMD64);
+            pulled = platform::AMD64.to_string();
+            self.pull_platform(image, &pulled).await?;
+        }
+        if !pulled.is_empty() {
+            platform::record_pull(image, &pulled);
+        }
+
+        tracing::info!("Successfully pulled image: {}", image);
+        Ok(())
+    }
+
+    /// The local id of an image tag, None when it hasn't been pulled
+    pub async fn image_id(&self, image: &str) -> Option<String> {
+        self.docker.inspect_image(image).await.ok().and_then(|info| info.id)
+    }
+
+    /// The id of the image a container was created from
+    pub async fn container_image_id(&self, container_id: &str) -> Option<String> {
+        self.docker.inspect_container(container_id, None).await.ok().and_then(|info| info.image)
+    }
+
+    /// The value a container was created with for an environment variable
+    pub async fn container_env_var(&self, container_id: &str, name: &str) -> Option<String> {
+        let info = self.docker.inspect_container(container_id, None).await.ok()?;
+        let prefix = format!("{}=", name);
+        info.config?.env?.into_iter().find_map(|entry| entry.strip_prefix(&prefix).map(str::to_string))
+    }
+
+    /// Size on disk of a pulled image, None when it hasn't been pulled
+    pub async fn image_size(&self, image: &str) -> Option<u64> {
+        let info = self.docker.inspect_image(image).await.ok()?;
+        Some(info.size.unwrap_or(0).max(0) as u64)
+    }
+
+    /// Remove an image tag. Not forced - Docker refuses while a container still uses it.
+    pub async fn remove_image(&self, image: &str) -> Result<(), DockerError> {
+        tracing::info!("Removing image: {}", image);
+        self.docker
+            .remove_image(image, Some(bollard::image::RemoveImageOptions { force: false, noprune: false }), None)
+            .await?;
+        Ok(())
+    }
+
+    /// Create a new container
+    pub async fn create_container(&self, spec: &ContainerSpec<'_>) -> Result<String, DockerError> {
+        let ContainerSpec { name, image, port, data_path, env, extra_ports, volume_path, memory_mb, startup_command } = *spec;
+        // Ensure image is available
+        self.pull_image(image).await?;
+
+        // Build environment variables
+        let env_vars: Vec<String> = env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        tracing::debug!("Environment variables: {:?}", env_vars);
+
+        // Build port bindings
+        let mut port_bindings = HashMap::new();
+        let mut exposed_ports = HashMap::new();
+        for published in published_ports(port, extra_ports) {
+            let port_key = format!("{}/{}", published.container_port, published.protocol);
+            port_bindings.insert(
+                port_key.clone(),
+                Some(vec![PortBinding {
+                    host_ip: Some(published.host_ip),
+                    host_port: Some(published.host_port.to_string()),
+                }]),
+            );
+            exposed_ports.insert(port_key, HashMap::new());
+        }
+        for extra in extra_ports {
+            let desc = extra.description.as_deref().unwrap_or("extra port");
+            tracing::info!("Added extra port: {} ({:?}) - {}", extra.container_port, extra.protocol, desc);
+        }
+
+        let container_volume_path = volume_path.unwrap_or("/data");
+        let binds = container_binds(data_path, container_volume_path);
+        tracing::info!("Volume mount: {}", binds[0]);
+
+        // Create a persistent machine-id file for hardware identification (needed by Hytale)
+        let machine_id_path = data_path.join(".machine-id");
+        if !machine_id_path.exists() {
+            let machine_id = format!("{}\n", Uuid::new_v4().to_string().replace("-", ""));
+            if let Err(e) = std::fs::write(&machine_id_path, &machine_id) {
+                tracing::warn!("Failed to create machine-id file: {}", e);
+            } else {
+                tracing::info!("Created machine-id file: {}", machine_id.trim());
+            }
+        }
+
+        // Calculate memory limit in bytes (Docker expects bytes)
+        let memory_limit = memory_mb.map(|mb| (mb as i64) * 1024 * 1024);
+        if let Some(mb) = memory_mb {
+            tracing::info!("Container memory limit: {} MB", mb);
+        }
+
+        let host_config = HostConfig {
+            port_bindings: Some(port_bindings),
+            binds: Some(binds),
+            memory: memory_limit,
+            memory_swap: memory_limit, // Same as memory to disable swap
+            restart_policy: Some(bollard::models::RestartPolicy {
+                name: Some(bollard::models::RestartPolicyNameEnum::NO),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Build the container command if startup_command provided
+        let cmd = startup_command.and_then(|startup| container_command(container_volume_path, startup));
+        if let Some(cmd) = &cmd {
+            tracing::info!("Container command: {}", cmd.join(" "));
+        }
+
+        let config = Config {
+            image: Some(image.to_string()),
+            env: Some(env_vars),
+            exposed_ports: Some(exposed_ports),
+            host_config: Some(host_config),
+            cmd,
+            tty: Some(true),
+            open_stdin: Some(true),
+            attach_stdin: Some(true),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+
+        let container_name = format!("serverwave-{}", name);
+        let platform = platform::pulled(image);
+        let options = Some(CreateContainerOptions {
+            name: container_name.as_str(),
+            platform: platform.as_deref(),
+        });
+
+        tracing::info!("Creating container: {}", container_name);
+        let response = self.docker.create_container(options, config).await?;
+        tracing::info!("Container created with ID: {}", response.id);
+
+        Ok(response.id)
+    }
+
+    /// Create a helper container that shares the network namespace of another container,
+    /// so it can reach the game server on localhost. Any existing container with the same
+    /// name is replaced.
+    pub async fn create_sidecar_container(
+        &self,
+        name: &str,
+        image: &str,
+        cmd: Vec<String>,
+        env: Vec<String>,
+        network_container_id: &str,
+    ) -> Result<String, DockerError> {
+        self.pull_image(image).await?;
+
+        let _ = self.docker.remove_container(
+            name,
+            Some(RemoveContainerOptions { force: true, ..Default::default() }),
+        ).await;
+
+        let host_config = HostConfig {
+            network_mode: Some(format!("container:{}", network_container_id)),
+            restart_policy: Some(bollard::models::RestartPolicy {
+                name: Some(bollard::models::RestartPolicyNameEnum::NO),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let config = Config {
+            image: Some(image.to_string()),
+            cmd: Some(cmd),
+            env: Some(env),
+            host_config: Some(host_config),
+            ..Default::default()
+        };
+
+        let platform = platform::pulled(image);
+
+        let options = Some(CreateContainerOptions {
+            name,
+            platform: platform.as_deref(),
+        });
+
+        tracing::info!("Creating sidecar container: {}", name);
+        let response = self.docker.create_container(options, config).await?;
+        Ok(response.id)
+    }
+
+    /// Create a service container (a database, ...) on a network, with one folder mounted
+    /// for its data. Any existing container with the same name is replaced.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_service_container(
+        &self,
+        name: &str,
+        image: &str,
+        cmd: Option<Vec<String>>,
+        env: Vec<String>,
+        data_bind: String,
+        network: &str,
+        memory_mb: u32,
+    ) -> Result<String, DockerError> {
+        self.pull_image(image).await?;
+
+        let _ = self.docker.remove_container(
+            name,
+            Some(RemoveContainerOptions { force: true, ..Default::default() }),
+        ).await;
+
+        let memory_limit = (memory_mb as i64) * 1024 * 1024;
+        let host_config = HostConfig {
+            binds: Some(vec![data_bind]),
+            network_mode: Some(network.to_string()),
+            memory: Some(memory_limit),
+            memory_swap: Some(memory_limit),
+            restart_policy: Some(bollard::models::RestartPolicy {
+                name: Some(bollard::models::RestartPolicyNameEnum::NO),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let config = Config {
+            image: Some(image.to_string()),
+            cmd,
+            env: Some(env),
+            host_config: Some(host_config),
+            ..Default::default()
+        };
+
+        let platform = platform::pulled(image);
+        let options = Some(CreateContainerOptions {
+            name,
+            platform: platform.as_deref(),
+        });
+
+        tracing::info!("Creating service container: {}", name);
+        let response = self.docker.create_container(options, config).await?;
+        Ok(response.id)
+    }
+
+    /// Create a bridge network if it doesn't exist yet
+    pub async fn ensure_network(&self, name: &str) -> Result<(), DockerError> {
+        use bollard::network::{CreateNetworkOptions, InspectNetworkOptions};
+
+        if self.docker.inspect_network(name, None::<InspectNetworkOptions<String>>).await.is_ok() {
+            return Ok(());
+        }
+
+        tracing::info!("Creating network: {}", name);
+        self.docker.create_network(CreateNetworkOptions {
+            name,
+            driver: "bridge",
+            ..Default::default()
+        }).await?;
+        Ok(())
+    }
+
+    /// Create a bridge network with no route out of it if it doesn't exist yet - its members
+    /// only reach each other
+    pub async fn ensure_internal_network(&self, name: &str) -> Result<(), DockerError> {
+        use bollard::network::{CreateNetworkOptions, InspectNetworkOptions};
+
+        if self.docker.inspect_network(name, None::<InspectNetworkOptions<String>>).await.is_ok() {
+            return Ok(());
+        }
+
+        tracing::info!("Creating internal network: {}", name);
+        self.docker.create_network(CreateNetworkOptions {
+            name,
+            driver: "bridge",
+            internal: true,
+            ..Default::default()
+        }).await?;
+        Ok(())
+    }
+
+    /// Attach a container to a network so other members can reach it by name
+    pub async fn connect_to_network(&self, network: &str, container_id: &str) -> Result<(), DockerError> {
+        use bollard::network::ConnectNetworkOptions;
+
+        self.docker.connect_network(network, ConnectNetworkOptions {
+            container: container_id,
+            endpoint_config: Default::default(),
+        }).await?;
+        Ok(())
+    }
+
+    /// Whether a container was created with stdin kept open, so it can be written to
+    pub async fn container_stdin_open(&self, container_id: &str) -> bool {
+        let info = self.docker.inspect_container(container_id, None).await.ok();
+        info.and_then(|info| info.config?.open_stdin).unwrap_or(false)
+    }
+
+    /// The networks a container is attached to
+    pub async fn container_networks(&self, container_id: &str) -> Vec<String> {
+        self.docker
+            .inspect_container(container_id, None)
+            .await
+            .ok()
+            .and_then(|info| info.network_settings?.networks)
+            .map(|networks| networks.into_keys().collect())
+            .unwrap_or_default()
+    }
+
+    /// The ports a container publishes - what Docker bound while it runs, what it's set up
+    /// to bind while it's stopped
+    pub async fn container_ports(&self, container_id: &str) -> Result<Vec<PublishedPort>, DockerError> {
+        let info = self.docker.inspect_container(container_id, None).await?;
+        let live = info.network_settings.and_then(|n| n.ports).map(|ports| bound_ports(&ports));
+        match live {
+            Some(ports) if !ports.is_empty() => Ok(ports),
+            _ => Ok(info.host_config.and_then(|h| h.port_bindings).map(|ports| bound_ports(&ports)).unwrap_or_default()),
+        }
+    }
+
+    /// The running container publishing a host port, by name
+    pub async fn container_on_port(&self, port: u16) -> Option<String> {
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions::<String>::default()))
+            .await
+            .ok()?;
+        containers
+            .into_iter()
+            .find(|c| c.ports.iter().flatten().any(|p| p.public_port == Some(port)))
+            .and_then(|c| c.names?.into_iter().next())
+            .map(|name| name.trim_start_matches('/').to_string())
+    }
+
+    /// Remove a network (fails while containers are still attached)
+    pub async fn remove_network(&self, name: &str) -> Result<(), DockerError> {
+        self.docker.remove_network(name).await?;
+        Ok(())
+    }
+
+    /// Start a container
+    pub async fn start_container(&self, container_id: &str) -> Result<(), DockerError> {
+        tracing::info!("Starting container: {}", container_id);
+        self.docker
+            .start_container(container_id, None::<StartContainerOptions<String>>)
+            .await?;
+        tracing::info!("Container start command sent: {}", container_id);
+        Ok(())
+    }
+
+    /// Stop a container
+    pub async fn stop_container(&self, container_id: &str) -> Result<(), DockerError> {
+        tracing::info!("Stopping container: {}", container_id);
+        let options = Some(StopContainerOptions { t: 30 });
+        self.docker.stop_container(container_id, options).await?;
+        Ok(())
+    }
+
+    /// Send a signal (SIGINT, SIGTERM, SIGKILL, ...) to the container's main process
+    pub async fn kill_container(&self, container_id: &str, signal: &str) -> Result<(), DockerError> {
+        tracing::info!("Sending {} to container: {}", signal, container_id);
+        self.docker
+            .kill_container(container_id, Some(KillContainerOptions { signal }))
+            .await?;
+        Ok(())
+    }
+
+    /// Wait up to `timeout` for the container to exit - false when it's still running
+    pub async fn wait_for_exit(&self, container_id: &str, timeout: std::time::Duration) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            match self.get_container_status(container_id).await {
+                Ok(ServerStatus::Running | ServerStatus::Starting | ServerStatus::Stopping) => {}
+                _ => return true,
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+
+    /// Remove a container
+    pub async fn remove_container(&self, container_id: &str) -> Result<(), DockerError> {
+        tracing::info!("Removing container: {}", container_id);
+        let options = Some(RemoveContainerOptions {
+            force: true,
+            v: false,
+            ..Default::default()
+        });
+        self.docker.remove_container(container_id, options).await?;
+        Ok(())
+    }
+
+    /// Get container status by inspecting it directly
+    pub async fn get_container_status(
+        &self,
+        container_id: &str,
+    ) -> Result<ServerStatus, DockerError> {
+        // Use inspect for more accurate status
+        match self.docker.inspect_container(container_id, None).await {
+            Ok(info) => {
+                if let Some(state) = info.state {
+                    let status = state.status;
+                    tracing::debug!("Container {} status: {:?}", container_id, status);
+                    
+                    return Ok(match status {
+                        Some(ContainerStateStatusEnum::RUNNING) => ServerStatus::Running,
+                        Some(ContainerStateStatusEnum::CREATED) => ServerStatus::Stopped,
+                        Some(ContainerStateStatusEnum::RESTARTING) => ServerStatus::Starting,
+                        Some(ContainerStateStatusEnum::PAUSED) => ServerStatus::Stopped,
+                        Some(ContainerStateStatusEnum::REMOVING) => ServerStatus::Stopping,
+                        Some(ContainerStateStatusEnum::EXITED) => ServerStatus::Stopped,
+                        Some(ContainerStateStatusEnum::DEAD) => ServerStatus::Error,
+                        None | Some(ContainerStateStatusEnum::EMPTY) => ServerStatus::Stopped,
+                    });
+                }
+                Ok(ServerStatus::Stopped)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to inspect container {}: {}", container_id, e);
+                Ok(ServerStatus::Stopped)
+            }
+        }
+    }
+
+    /// Get container stats (CPU, memory)
+    pub async fn get_container_stats(
+        &self,
+        container_id: &str,
+    ) -> Result<ContainerStats, DockerError> {
+        let options = Some(StatsOptions {
+            stream: false,
+            one_shot: true,
+        });
+
+        let mut stream = self.docker.stats(container_id, options);
+
+        if let Some(Ok(stats)) = stream.next().await {
+            // Calculate CPU percentage
+            let cpu_delta = stats.cpu_stats.cpu_usage.total_usage as f64
+                - stats.precpu_stats.cpu_usage.total_usage as f64;
+            let system_delta = stats.cpu_stats.system_cpu_usage.unwrap_or(0) as f64
+                - stats.precpu_stats.system_cpu_usage.unwrap_or(0) as f64;
+            let num_cpus = stats.cpu_stats.online_cpus.unwrap_or(1) as f64;
+
+            let cpu_percent = if system_delta > 0.0 && cpu_delta > 0.0 {
+                (cpu_delta / system_delta) * num_cpus * 100.0
+            } else {
+                0.0
+            };
+
+            // Calculate memory
+            let memory_usage = stats.memory_stats.usage.unwrap_or(0) as f64 / 1024.0 / 1024.0;
+            let memory_limit = stats.memory_stats.limit.unwrap_or(1) as f64 / 1024.0 / 1024.0;
+            let memory_percent = if memory_limit > 0.0 {
+                (memory_usage / memory_limit) * 100.0
+            } else {
+                0.0
+            };
+
+            return Ok(ContainerStats {
+                cpu_percent,
+                memory_usage_mb: memory_usage,
+                memory_limit_mb: memory_limit,
+                memory_percent,
+            });
+        }
+
+        Ok(ContainerStats {
+            cpu_percent: 0.0,
+            memory_usage_mb: 0.0,
+            memory_limit_mb: 0.0,
+            memory_percent: 0.0,
+        })
+    }
+
+    /// List processes running inside a container (docker top), busiest first
+    pub async fn get_container_processes(
+        &self,
+        container_id: &str,
+    ) -> Result<Vec<ContainerProcess>, DockerError> {
+        let options = Some(TopOptions { ps_args: "aux" });
+        let top = self.docker.top_processes(container_id, options).await?;
+
+        let titles = top.titles.unwrap_or_default();
+        let column = |names: &[&str]| {
+            titles.iter().position(|t| names.iter().any(|n| t.eq_ignore_ascii_case(n)))
+        };
+        let pid_col = column(&["PID"]);
+        let user_col = column(&["USER", "UID"]);
+        let cpu_col = column(&["%CPU", "C"]);
+        let mem_col = column(&["%MEM"]);
+        let rss_col = column(&["RSS"]);
+        let cmd_col = column(&["COMMAND", "CMD"]);
+
+        let mut processes: Vec<ContainerProcess> = top
+            .processes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|row| {
+                let field = |col: Option<usize>| {
+                    col.and_then(|i| row.get(i)).cloned().unwrap_or_default()
+                };
+                ContainerProcess {
+                    pid: field(pid_col),
+                    user: field(user_col),
+                    cpu_percent: field(cpu_col).parse().unwrap_or(0.0),
+                    memory_percent: field(mem_col).parse().unwrap_or(0.0),
+                    rss_kb: field(rss_col).parse().unwrap_or(0),
+                    command: field(cmd_col),
+                }
+            })
+            .collect();
+
+        processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+        Ok(processes)
+    }
+
+    /// Send input to container's stdin using attach
+    pub async fn send_stdin(&self, container_id: &str, input: &str) -> Result<(), DockerError> {
+        use tokio::io::AsyncWriteExt;
+        
+        tracing::info!("Sending stdin to container {}: {}", container_id, input);
+        
+        let options = AttachContainerOptions::<String> {
+            stdin: Some(true),
+            stdout: Some(false),
+            stderr: Some(false),
+            stream: Some(true),
+            logs: Some(false),
+            ..Default::default()
+        };
+        
+        match self.docker.attach_container(container_id, Some(options)).await {
+            Ok(AttachContainerResults { input: mut stdin_writer, .. }) => {
+                // Write the command followed by newline
+                let data = format!("{}\n", input);
+                stdin_writer.write_all(data.as_bytes()).await
+                    .map_err(|e| DockerError::AttachFailed(format!("Failed to write to stdin: {}", e)))?;
+                stdin_writer.flush().await
+                    .map_err(|e| DockerError::AttachFailed(format!("Failed to flush stdin: {}", e)))?;
+                tracing::info!("Successfully sent command to container stdin");
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to attach to container: {}", e);
+                Err(DockerError::AttachFailed(e.to_string()))
+            }
+        }
+    }
+
+    /// Get container logs (non-streaming, for initial load)
+    pub async fn get_logs(
+        &self,
+        container_id: &str,
+        lines: u32,
+    ) -> Result<Vec<String>, DockerError> {
+        let options = Some(LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            tail: lines.to_string(),
+            timestamps: false,
+            ..Default::default()
+        });
+
+        let mut stream = self.docker.logs(container_id, options);
+        let mut logs = Vec::new();
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(output) => {
+                    let line = match output {
+                        LogOutput::StdOut { message } => {
+                            String::from_utf8_lossy(&message).to_string()
+                        }
+                        LogOutput::StdErr { message } => {
+                            String::from_utf8_lossy(&message).to_string()
+                        }
+                        LogOutput::Console { message } => {
+                            String::from_utf8_lossy(&message).to_string()
+                        }
+                        LogOutput::StdIn { message } => {
+                            String::from_utf8_lossy(&message).to_string()
+                        }
+                    };
+                    for l in line.lines() {
+                        if !l.trim().is_empty() {
+                            logs.push(l.to_string());
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Error reading log: {}", e);
+                }
+            }
+        }
+
+        Ok(logs)
+    }
+
+    /// Get the timestamp of the most recent log line written by a container
+    pub async fn get_last_log_time(
+        &self,
+        container_id: &str,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, DockerError> {
+        let options = Some(LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            tail: "1".to_string(),
+            timestamps: true,
+            ..Default::default()
+        });
+
+        let mut stream = self.docker.logs(container_id, options);
+        let mut last = None;
+
+        while let Some(result) = stream.next().await {
+            let output = result?;
+            let text = String::from_utf8_lossy(&output.into_bytes()).to_string();
+            // Docker prefixes each line with an RFC3339Nano timestamp
+            if let Some(ts) = text.split_whitespace().next() {
+                if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(ts) {
+                    last = Some(parsed.with_timezone(&chrono::Utc));
+                }
+            }
+        }
+
+        Ok(last)
+    }
+
+    /// Execute a command inside a running container with streaming output
+    #[allow(dead_code)]
+    pub async fn exec_command<F>(
+        &self,
+        container_id: &str,
+        cmd: Vec<&str>,
+        working_dir: Option<&str>,
+        mut on_output: F,
+    ) -> Result<i64, DockerError>
+    where
+        F: FnMut(String),
+    {
+        use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
+        
+        let cmd_strings: Vec<String> = cmd.iter().map(|s| s.to_string()).collect();
+        tracing::info!("Executing in container {}: {:?}", container_id, cmd_strings);
+        
+        let exec_options = CreateExecOptions {
+            cmd: Some(cmd_strings),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            tty: Some(false), // Don't use tty for proper streaming
+            working_dir: working_dir.map(|s| s.to_string()),
+            ..Default::default()
+        };
+
+        let exec = self.docker
+            .create_exec(container_id, exec_options)
+            .await
+            .map_err(|e| DockerError::AttachFailed(e.to_string()))?;
+
+        let start_options = Some(StartExecOptions {
+            detach: false,
+            tty: false,
+            ..Default::default()
+        });
+
+        match self.docker.start_exec(&exec.id, start_options).await {
+            Ok(StartExecResults::Attached { output: mut stream, .. }) => {
+                tracing::info!("Exec attached, streaming output...");
+                while let Some(result) = stream.next().await {
+                    match result {
+                        Ok(output) => {
+                            let text = match output {
+                                LogOutput::StdOut { message } => String::from_utf8_lossy(&message).to_string(),
+                                LogOutput::StdErr { message } => String::from_utf8_lossy(&message).to_string(),
+                                _ => String::new(),
+                            };
+                            for line in text.lines() {
+                                if !line.is_empty() {
+                                    tracing::debug!("Exec output: {}", line);
+                                    on_output(line.to_string());
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Exec output error: {}", e);
+                        }
+                    }
+                }
+                tracing::info!("Exec stream finished");
+            }
+            Ok(StartExecResults::Detached) => {
+                tracing::warn!("Exec started in detached mode unexpectedly");
+            }
+            Err(e) => return Err(DockerError::AttachFailed(e.to_string())),
+        }
+
+        // Get exit code
+        let inspect = self.docker.inspect_exec(&exec.id).await
+            .map_err(|e| DockerError::AttachFailed(e.to_string()))?;
+        let exit_code = inspect.exit_code.unwrap_or(-1);
+        tracing::info!("Exec finished with exit code: {}", exit_code);
+        Ok(exit_code)
+    }
+
+    /// Run a script in a temporary container with streaming output
+    /// Creates a one-off container, runs the script, streams output, then removes the container
+    /// Returns (exit_code, container_id)
+    /// on_container_created is called with the container_id after creation but before starting
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_script<F, C>(
+        &self,
+        image: &str,
+        data_path: &std::path::Path,
+        volume_path: &str,
+        script: &str,
+        options: ScriptOptions,
+        on_container_created: C,
+        mut on_output: F,
+    ) -> Result<(i64, String), DockerError>
+    where
+        F: FnMut(String),
+        C: FnOnce(&str),
+    {
+        use base64::Engine;
+        
+        tracing::info!("Running install script in temporary container");
+        
+        // Ensure image is available
+        self.pull_image(image).await?;
+        
+        // Build volume mount
+        let data_path_str = data_path.to_string_lossy().replace('\\', "/");
+        let data_mount = format!("{}:{}", data_path_str, volume_path);
+        
+        // Create a persistent machine-id file for hardware identification (needed by Hytale)
+        let machine_id_path = data_path.join(".machine-id");
+        if !machine_id_path.exists() {
+            // Format: 32 hex chars with newline (standard machine-id format)
+            let machine_id = format!("{}\n", Uuid::new_v4().to_string().replace("-", ""));
+            if let Err(e) = std::fs::write(&machine_id_path, &machine_id) {
+                tracing::warn!("Failed to create machine-id file: {}", e);
+            } else {
+                tracing::info!("Created machine-id file: {}", machine_id.trim());
+            }
+        }
+        let machine_id_mount = format!("{}/.machine-id:/etc/machine-id:ro", data_path_str);
+        
+        // Encode script to base64 to avoid shell escaping issues
+        let encoded_script = base64::engine::general_purpose::STANDARD.encode(script);
+        
+        // Command: decode script, save to file, execute it
+        let cmd = format!(
+            "echo '{}' | base64 -d > /tmp/install.sh && chmod +x /tmp/install.sh && exec /tmp/install.sh",
+            encoded_script
+        );
+        
+        let mut binds = vec![data_mount, machine_id_mount];
+        let mut env = options.env;
+        
+        // SteamCMD scripts share one SteamCMD and a copy of each game between servers
+        if super::steam_cache::wanted_by(script) {
+            match super::steam_cache::prepare() {
+                Ok(dir) => {
+                    let dir = dir.to_string_lossy().replace('\\', "/");
+                    binds.push(format!("{}:{}", dir, super::steam_cache::MOUNT_PATH));
+                    env.push(format!("{}={}", super::steam_cache::ENV_VAR, super::steam_cache::MOUNT_PATH));
+                }
+                Err(e) => tracing::warn!("SteamCMD cache unavailable, installing without it: {}", e),
+            }
+        }
+        
+        let host_config = HostConfig {
+            binds: Some(binds),
+            network_mode: options.network,
+            ..Default::default()
+        };
+        
+        let container_name = format!("serverwave-install-{}", &Uuid::new_v4().to_string()[..8]);
+        
+        let config = Config {
+            image: Some(image.to_string()),
+            cmd: Some(vec!["/bin/sh".to_string(), "-c".to_string(), cmd]),
+            host_config: Some(host_config),
+            env: Some(env),
+            working_dir: Some(volume_path.to_string()),
+            tty: Some(false),
+            open_stdin: Some(options.stdin),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+        
+        let platform = platform::pulled(image);
+        
+        let options = Some(CreateContainerOptions {
+            name: container_name.as_str(),
+            platform: platform.as_deref(),
+        });
+        
+        tracing::info!("Creating temporary install container: {}", container_name);
+        let container = self.docker.create_container(options, config).await?;
+        let container_id = container.id.clone();
+        
+        // Notify caller of container ID so they can save it for log recovery
+        on_container_created(&container_id);
+        
+        tracing::info!("Starting install container: {}", container_id);
+        
+        // Start the container
+        self.docker.start_container(&container_id, None::<StartContainerOptions<String>>).await?;
+        
+        // Stream logs with follow=true
+        let log_options = LogsOptions::<String> {
+            follow: true,
+            stdout: true,
+            stderr: true,
+            timestamps: false,
+            ..Default::default()
+        };
+        
+        let mut log_stream = self.docker.logs(&container_id, Some(log_options));
+        
+        loop {
+            // Use timeout to periodically check if container is still running
+            match tokio::time::timeout(
+                tokio::time::Duration::from_secs(1),
+                log_stream.next()
+            ).await {
+                Ok(Some(Ok(output))) => {
+                    let text = match output {
+                        LogOutput::StdOut { message } => String::from_utf8_lossy(&message).to_string(),
+                        LogOutput::StdErr { message } => String::from_utf8_lossy(&message).to_string(),
+                        LogOutput::Console { message } => String::from_utf8_lossy(&message).to_string(),
+                        _ => String::new(),
+                    };
+                    for line in text.lines() {
+                        if !line.is_empty() {
+                            on_output(line.to_string());
+                        }
+                    }
+                }
+                Ok(Some(Err(e))) => {
+                    tracing::warn!("Log stream error: {}", e);
+                    break;
+                }
+                Ok(None) => {
+                    tracing::info!("Log stream ended");
+                    break;
+                }
+                Err(_) => {
+                    // Timeout - check if container is still running
+                    match self.docker.inspect_container(&container_id, None).await {
+                        Ok(info) => {
+                            if let Some(state) = info.state {
+                                if state.running != Some(true) {
+                                    tracing::info!("Install container stopped");
+                                    break;
+                                }
+                            }
+                        }
+                        Err(_) => {
+                            tracing::info!("Container not found, assuming finished");
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        
+        // Log stream ended, container should be stopped now
+        // Get exit code by inspecting the container
+        let exit_code = match self.docker.inspect_container(&container_id, None).await {
+            Ok(info) => {
+                info.state
+                    .and_then(|s| s.exit_code)
+                    .unwrap_or(-1)
+            }
+            Err(e) => {
+                tracing::error!("Failed to inspect container for exit code: {}", e);
+                -1
+            }
+        };
+        
+        tracing::info!("Install container finished with exit code: {}", exit_code);
+        
+        // Don't remove the container yet - keep it for log retrieval
+        // It will be removed when install completes or server is deleted
+        
+        Ok((exit_code, container_id))
+    }
+
+    /// Run a script in a throwaway container without mounts or network and collect its output
+    /// Returns (exit_code, output lines)
+    pub async fn run_isolated(&self, image: &str, script: &str) -> Result<(i64, Vec<String>), DockerError> {
+        use base64::Engine;
+        
+        self.pull_image(image).await?;
+        
+        let encoded_script = base64::engine::general_purpose::STANDARD.encode(script);
+        let cmd = format!(
+            "echo '{}' | base64 -d > /tmp/run.sh && exec /bin/sh /tmp/run.sh",
+            encoded_script
+        );
+        let config = Config {
+            image: Some(image.to_string()),
+            cmd: Some(vec!["/bin/sh".to_string(), "-c".to_string(), cmd]),
+            host_config: Some(HostConfig {
+                network_mode: Some("none".to_string()),
+                ..Default::default()
+            }),
+            tty: Some(false),
+            ..Default::default()
+        };
+        let container_name = format!("serverwave-check-{}", &Uuid::new_v4().to_string()[..8]);
+        let platform = platform::pulled(image);
+        let options = Some(CreateContainerOptions {
+            name: container_name.as_str(),
+            platform: platform.as_deref(),
+        });
+        let container_id = self.docker.create_container(options, config).await?.id;
+        
+        let result = async {
+            self.docker.start_container(&container_id, None::<StartContainerOptions<String>>).await?;
+            
+            let log_options = LogsOptions::<String> {
+                follow: true,
+                stdout: true,
+                stderr: true,
+                ..Default::default()
+            };
+            let mut log_stream = self.docker.logs(&container_id, Some(log_options));
+            let mut lines = Vec::new();
+            while let Some(output) = log_stream.next().await {
+                let text = match output? {
+                    LogOutput::StdOut { message } | LogOutput::StdErr { message } | LogOutput::Console { message } => {
+                        String::from_utf8_lossy(&message).to_string()
+                    }
+                    _ => continue,
+                };
+                lines.extend(text.lines().map(str::to_string));
+            }
+            
+            let exit_code = self.docker.inspect_container(&container_id, None).await?
+                .state
+                .and_then(|s| s.exit_code)
+                .unwrap_or(-1);
+            Ok((exit_code, lines))
+        }
+        .await;
+        
+        let _ = self.docker.remove_container(
+            &container_id,
+            Some(RemoveContainerOptions { force: true, ..Default::default() })
+        ).await;
+        result
+    }
+
+    /// Remove install container after installation completes
+    pub async fn remove_install_container(&self, container_id: &str) -> Result<(), DockerError> {
+        let _ = self.docker.remove_container(
+            container_id,
+            Some(RemoveContainerOptions { force: true, ..Default::default() })
+        ).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bound_ports() {
+        let binding = |ip: &str, port: &str| PortBinding { host_ip: Some(ip.to_string()), host_port: Some(port.to_string()) };
+        let bindings: PortMap = HashMap::from([
+            ("27015/udp".to_string(), Some(vec![binding("", "27015"), binding("::", "27015")])),
+            ("25575/tcp".to_string(), Some(vec![binding("127.0.0.1", "35575")])),
+            ("8080/tcp".to_string(), None),
+        ]);
+        let ports = bound_ports(&bindings);
+        assert_eq!(ports.len(), 3);
+        assert_eq!(
+            ports[0],
+            PublishedPort { container_port: 25575, host_port: 35575, protocol: "tcp".into(), host_ip: "127.0.0.1".into() }
+        );
+        assert_eq!(ports[1].host_ip, "0.0.0.0");
+        assert_eq!(ports[2].host_ip, "::");
+    }
+}
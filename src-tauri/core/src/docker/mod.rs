@@ -0,0 +1,15 @@
+// Docker management module
+
+pub mod connection;
+mod events;
+mod manager;
+pub mod platform;
+pub mod socket;
+pub mod steam_cache;
+pub mod wake;
+
+pub use events::ContainerEvent;
+pub use manager::{
+    container_binds, container_command, published_ports, ContainerProcess, ContainerStats, DockerError, DockerInfo, DockerManager,
+    ContainerSpec, PublishedPort, ScriptOptions,
+};
@@ -0,0 +1,80 @@
+// Platforms - the architecture the Docker host runs, and which platform each image was pulled
+// for, so an arm64 host (Apple Silicon, Raspberry Pi) gets arm64 images where they exist and
+// falls back to amd64 under emulation where they don't
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The platform game images are always built for
+pub const AMD64: &str = "linux/amd64";
+
+lazy_static::lazy_static! {
+    static ref HOST: Mutex<Option<String>> = Mutex::new(None);
+    /// The platform each image was pulled for, by image
+    static ref PULLED: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+/// Docker's architecture name (`docker info`) as a platform
+pub fn from_architecture(architecture: &str) -> Option<&'static str> {
+    match architecture {
+        "x86_64" | "amd64" => Some(AMD64),
+        "aarch64" | "arm64" => Some("linux/arm64"),
+        "armv7l" | "armhf" | "arm" => Some("linux/arm/v7"),
+        _ => None,
+    }
+}
+
+/// A registry platform (`architecture` plus optional `variant`) as a platform string
+pub fn platform_string(os: &str, architecture: &str, variant: Option<&str>) -> String {
+    match variant.filter(|v| !v.is_empty()) {
+        Some(variant) => format!("{}/{}/{}", os, architecture, variant),
+        None => format!("{}/{}", os, architecture),
+    }
+}
+
+/// Whether a pull failed because the image has no build for the requested platform
+pub fn is_missing_platform(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("no matching manifest") || error.contains("does not provide the specified platform")
+}
+
+pub(crate) fn cached_host() -> Option<String> {
+    HOST.lock().unwrap().clone()
+}
+
+pub(crate) fn set_host(platform: &str) {
+    *HOST.lock().unwrap() = Some(platform.to_string());
+}
+
+pub(crate) fn record_pull(image: &str, platform: &str) {
+    PULLED.lock().unwrap().insert(image.to_string(), platform.to_string());
+}
+
+/// The platform `image` was pulled for, when this process pulled it
+pub fn pulled(image: &str) -> Option<String> {
+    PULLED.lock().unwrap().get(image).cloned()
+}
+
+/// The platform `image` runs under emulation as, None when it runs natively or the host
+/// platform isn't known
+pub fn emulated(image: &str) -> Option<String> {
+    let platform = pulled(image)?;
+    let host = cached_host()?;
+    (platform != host).then_some(platform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_names() {
+        assert_eq!(from_architecture("x86_64"), Some(AMD64));
+        assert_eq!(from_architecture("aarch64"), Some("linux/arm64"));
+        assert_eq!(from_architecture("riscv64"), None);
+        assert_eq!(platform_string("linux", "arm", Some("v7")), "linux/arm/v7");
+        assert_eq!(platform_string("linux", "arm64", Some("")), "linux/arm64");
+        assert!(is_missing_platform("no matching manifest for linux/arm64/v8 in the manifest list entries"));
+        assert!(!is_missing_platform("pull access denied"));
+    }
+}
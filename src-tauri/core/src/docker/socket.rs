@@ -0,0 +1,98 @@
+// Docker socket - which socket to talk to when it isn't the default one. Colima, OrbStack,
+// Rancher Desktop and rootless Docker/Podman all put theirs somewhere else, so with no
+// DOCKER_HOST set the default connection finds nothing and Docker looks "not available".
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A container runtime socket found on this machine
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DetectedSocket {
+    /// The runtime it most likely belongs to, like "Colima"
+    pub runtime: String,
+    pub path: String,
+}
+
+/// Where runtimes put their socket, most common first
+fn candidates() -> Vec<(&'static str, PathBuf)> {
+    let mut candidates = Vec::new();
+    if !cfg!(unix) {
+        return candidates;
+    }
+    candidates.push(("Docker", PathBuf::from("/var/run/docker.sock")));
+
+    if let Some(home) = directories::UserDirs::new().map(|d| d.home_dir().to_path_buf()) {
+        candidates.push(("Docker Desktop", home.join(".docker/run/docker.sock")));
+        candidates.push(("Docker Desktop", home.join(".docker/desktop/docker.sock")));
+        candidates.push(("OrbStack", home.join(".orbstack/run/docker.sock")));
+        candidates.push(("Colima", home.join(".colima/default/docker.sock")));
+        candidates.push(("Colima", home.join(".colima/docker.sock")));
+        candidates.push(("Rancher Desktop", home.join(".rd/docker.sock")));
+        candidates.push(("Podman", home.join(".local/share/containers/podman/machine/podman.sock")));
+        candidates.push(("Podman", home.join(".local/share/containers/podman/machine/qemu/podman.sock")));
+        candidates.push((
+            "Podman",
+            home.join(".local/share/containers/podman/machine/podman-machine-default/podman.sock"),
+        ));
+    }
+
+    if let Some(runtime_dir) = runtime_dir() {
+        candidates.push(("Rootless Docker", runtime_dir.join("docker.sock")));
+        candidates.push(("Podman", runtime_dir.join("podman/podman.sock")));
+    }
+    candidates.push(("Podman", PathBuf::from("/run/podman/podman.sock")));
+    candidates
+}
+
+/// The user's runtime dir, where rootless Docker and Podman keep their sockets
+fn runtime_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    user_run_dir()
+}
+
+#[cfg(unix)]
+fn user_run_dir() -> Option<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+    let home = directories::UserDirs::new()?.home_dir().to_path_buf();
+    let uid = std::fs::metadata(home).ok()?.uid();
+    let dir = PathBuf::from(format!("/run/user/{}", uid));
+    dir.exists().then_some(dir)
+}
+
+#[cfg(not(unix))]
+fn user_run_dir() -> Option<PathBuf> {
+    None
+}
+
+/// The sockets that exist on this machine
+pub fn detect() -> Vec<DetectedSocket> {
+    let mut found: Vec<DetectedSocket> = Vec::new();
+    for (runtime, path) in candidates() {
+        // /var/run is often a link to /run - list each socket once
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        if !path.exists() {
+            continue;
+        }
+        let path = path.to_string_lossy().to_string();
+        if found.iter().all(|s| s.path != path) {
+            found.push(DetectedSocket { runtime: runtime.to_string(), path });
+        }
+    }
+    found
+}
+
+/// The socket to connect to when none is set, None for Docker's own defaults - which
+/// honour DOCKER_HOST and find a socket in the standard place
+pub fn resolve() -> Option<String> {
+    if std::env::var_os("DOCKER_HOST").is_some_and(|h| !h.is_empty()) {
+        return None;
+    }
+    // Podman's own variable, for when only Podman is set up
+    if let Some(host) = std::env::var("CONTAINER_HOST").ok().filter(|h| h.starts_with("unix://")) {
+        return Some(host);
+    }
+    detect().into_iter().next().map(|s| s.path)
+}
+
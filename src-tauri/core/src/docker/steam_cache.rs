@@ -0,0 +1,106 @@
+// SteamCMD cache - one SteamCMD installation and a seed copy of each game's files, shared by
+// every install container that runs a SteamCMD script
+
+use std::path::PathBuf;
+
+/// Where the cache is mounted inside install containers
+pub const MOUNT_PATH: &str = "/serverwave/steamcmd";
+/// Set in install containers so scripts can find the cache
+pub const ENV_VAR: &str = "STEAMCMD_CACHE";
+const HELPERS_FILE: &str = "serverwave.sh";
+
+/// Shell functions install scripts source from the cache:
+/// - steamcmd_setup <server path>: copy the shared, already self-updated SteamCMD into
+///   <server path>/steamcmd, downloading it once. It and steamcmd_store hold a lock so
+///   concurrent installs don't write the shared copy at the same time.
+/// - steamcmd_seed <server path> <app id>: start a server that has no copy of the game yet
+///   from the cached one, so app_update only downloads what changed since
+/// - steamcmd_store <server path> <app id>: write SteamCMD's updates back and keep the first
+///   fresh install of a game as the seed for the next server
+const HELPERS: &str = r#"# Written by Serverwave Anywhere - changes are overwritten
+STEAMCMD_URL=https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+
+steamcmd_lock() {
+    exec 9>"${STEAMCMD_CACHE}/.lock"
+    flock 9 2>/dev/null || true
+}
+
+steamcmd_unlock() {
+    exec 9>&-
+}
+
+steamcmd_setup() {
+    steamcmd_lock
+    mkdir -p "${STEAMCMD_CACHE}/steamcmd" "$1/steamcmd" "$1/steamapps"
+    if [ ! -f "${STEAMCMD_CACHE}/steamcmd/steamcmd.sh" ]; then
+        echo "[Serverwave] Downloading SteamCMD into the shared cache..."
+        curl -sSL "${STEAMCMD_URL}" | tar -xz -C "${STEAMCMD_CACHE}/steamcmd"
+    fi
+    cp -a "${STEAMCMD_CACHE}/steamcmd/." "$1/steamcmd/"
+    steamcmd_unlock
+}
+
+steamcmd_seed() {
+    STEAMCMD_FRESH=0
+    [ -f "$1/steamapps/appmanifest_$2.acf" ] && return 0
+    STEAMCMD_FRESH=1
+    if [ -d "${STEAMCMD_CACHE}/apps/$2" ]; then
+        echo "[Serverwave] Copying cached game files..."
+        cp -a "${STEAMCMD_CACHE}/apps/$2/." "$1/"
+    fi
+}
+
+steamcmd_store() {
+    steamcmd_lock
+    cp -a "$1/steamcmd/." "${STEAMCMD_CACHE}/steamcmd/"
+    if [ "${STEAMCMD_FRESH}" = "1" ] && [ ! -d "${STEAMCMD_CACHE}/apps/$2" ] && [ -f "$1/steamapps/appmanifest_$2.acf" ]; then
+        echo "[Serverwave] Keeping a copy of the game files for the next server..."
+        mkdir -p "${STEAMCMD_CACHE}/apps/.$2"
+        tar -C "$1" --exclude=./steamcmd --exclude=./.machine-id -cf - . | tar -C "${STEAMCMD_CACHE}/apps/.$2" -xf - \
+            && mv "${STEAMCMD_CACHE}/apps/.$2" "${STEAMCMD_CACHE}/apps/$2" \
+            || rm -rf "${STEAMCMD_CACHE}/apps/.$2"
+    fi
+    steamcmd_unlock
+}
+"#;
+
+pub fn host_dir() -> PathBuf {
+    crate::paths::cache_dir().join("steamcmd")
+}
+
+/// Create the cache folder with the current helpers, returning the folder to mount
+pub fn prepare() -> std::io::Result<PathBuf> {
+    let dir = host_dir();
+    std::fs::create_dir_all(&dir)?;
+    crate::atomic::write_atomic(&dir.join(HELPERS_FILE), HELPERS)?;
+    Ok(dir)
+}
+
+/// Whether a script uses SteamCMD and should get the cache
+pub fn wanted_by(script: &str) -> bool {
+    script.contains("steamcmd")
+}
+
+/// Size of the cache on disk
+pub fn size_bytes() -> u64 {
+    fn walk(path: &std::path::Path) -> u64 {
+        let Ok(entries) = std::fs::read_dir(path) else { return 0 };
+        entries
+            .flatten()
+            .map(|e| match e.file_type() {
+                Ok(t) if t.is_dir() => walk(&e.path()),
+                _ => e.metadata().map(|m| m.len()).unwrap_or(0),
+            })
+            .sum()
+    }
+    walk(&host_dir())
+}
+
+/// Remove everything but the helpers - the next install downloads SteamCMD again
+pub fn clear() -> std::io::Result<()> {
+    match std::fs::remove_dir_all(host_dir()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
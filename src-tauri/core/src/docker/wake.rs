@@ -0,0 +1,103 @@
+// Wake-on-LAN - powering on a Docker host that sleeps between sessions. The magic packet is
+// six 0xFF bytes and the host's MAC address sixteen times, broadcast over UDP.
+
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, UdpSocket};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WakeOnLan {
+    /// The host's network card, like 00:11:22:aa:bb:cc
+    pub mac: String,
+    /// Where the packet goes - the whole local network unless the host is on another subnet
+    #[serde(default = "default_broadcast")]
+    pub broadcast: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// How long the host may take to boot before Docker answers
+    #[serde(default = "default_boot_secs")]
+    pub boot_secs: u64,
+}
+
+fn default_broadcast() -> String {
+    "255.255.255.255".to_string()
+}
+
+fn default_port() -> u16 {
+    9
+}
+
+fn default_boot_secs() -> u64 {
+    180
+}
+
+impl WakeOnLan {
+    pub fn validate(&self) -> Result<(), String> {
+        parse_mac(&self.mac)?;
+        self.broadcast
+            .trim()
+            .parse::<Ipv4Addr>()
+            .map_err(|_| format!("{} isn't an IPv4 broadcast address", self.broadcast))?;
+        if self.boot_secs == 0 {
+            return Err("Give the host at least a second to boot".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn magic_packet(&self) -> Result<Vec<u8>, String> {
+        let mac = parse_mac(&self.mac)?;
+        let mut packet = vec![0xFF; 6];
+        for _ in 0..16 {
+            packet.extend_from_slice(&mac);
+        }
+        Ok(packet)
+    }
+
+    /// Broadcast the magic packet once
+    pub fn send(&self) -> Result<(), String> {
+        let packet = self.magic_packet()?;
+        let broadcast: Ipv4Addr = self.broadcast.trim().parse().map_err(|_| "Invalid broadcast address".to_string())?;
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(|e| e.to_string())?;
+        socket.set_broadcast(true).map_err(|e| e.to_string())?;
+        socket
+            .send_to(&packet, (broadcast, self.port))
+            .map_err(|e| format!("Couldn't send the wake packet: {}", e))?;
+        Ok(())
+    }
+}
+
+/// A MAC address written with colons, dashes or nothing between the bytes
+fn parse_mac(mac: &str) -> Result<[u8; 6], String> {
+    let invalid = || format!("{} isn't a MAC address", mac.trim());
+    let hex: String = mac.trim().chars().filter(|c| !matches!(c, ':' | '-' | '.')).collect();
+    if hex.len() != 12 {
+        return Err(invalid());
+    }
+    let mut bytes = [0; 6];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2).ok_or_else(invalid)?, 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magic_packet() {
+        let wake = WakeOnLan {
+            mac: "00:11:22:AA:bb:cc".to_string(),
+            broadcast: default_broadcast(),
+            port: default_port(),
+            boot_secs: default_boot_secs(),
+        };
+        let packet = wake.magic_packet().unwrap();
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert_eq!(&packet[96..], &[0x00, 0x11, 0x22, 0xAA, 0xBB, 0xCC]);
+
+        assert_eq!(parse_mac("00-11-22-aa-bb-cc"), parse_mac("001122aabbcc"));
+        assert!(parse_mac("00:11:22:aa:bb").is_err());
+        assert!(parse_mac("00:11:22:aa:bb:zz").is_err());
+    }
+}
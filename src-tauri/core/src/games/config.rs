@@ -1,3 +1,7 @@
+use super::java;
+use super::player_log::PlayerLogRules;
+use crate::a2s::SteamQuery;
+use crate::rcon::RconSettings;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -31,7 +35,13 @@ pub struct GameConfig {
     pub description: String,
     pub docker_image: String,
     pub startup: String,
+    /// Typed into the console to stop the server, for StopMethod::Command. `^C` means SIGINT.
     pub stop_command: String,
+    #[serde(default)]
+    pub stop: StopConfig,
+    /// Seconds a started server gets to answer on its port - heavy games take minutes to load
+    #[serde(default = "default_start_timeout")]
+    pub start_timeout_secs: u32,
     pub variables: Vec<Variable>,
     pub ports: Vec<PortConfig>,
     pub volume_path: String,
@@ -50,6 +60,49 @@ pub struct GameConfig {
     pub is_custom: bool,
     #[serde(default = "default_console")]
     pub console: bool,
+    /// How players join, shown next to the shareable address.
+    /// Supports {{ADDRESS}}, {{IP}} and {{PORT}} placeholders.
+    #[serde(default)]
+    pub join_instructions: Option<String>,
+    /// Buttons on the server page - see GameAction
+    #[serde(default)]
+    pub actions: Vec<GameAction>,
+    /// How the console reports players joining and leaving, for session tracking
+    #[serde(default)]
+    pub player_log: Option<PlayerLogRules>,
+    /// Variable the server reads a Steam Game Server Login Token from, for games that won't
+    /// list publicly without one (CS2, Source games). The assigned token is set at start.
+    #[serde(default)]
+    pub gslt_var: Option<String>,
+    /// Folders in the data folder that hold the worlds or saves, for the file manager's
+    /// shortcuts. Minecraft Java's come from level-name instead.
+    #[serde(default)]
+    pub save_folders: Vec<String>,
+    /// Images a server can be switched to with its IMAGE_VARIANT setting, like another Java
+    /// for Minecraft. Servers that don't pick one run `docker_image`.
+    #[serde(default)]
+    pub image_variants: Vec<ImageVariant>,
+    /// Where the game takes Source RCON, for commands the console can't take
+    #[serde(default)]
+    pub rcon: Option<RconSettings>,
+    /// Where the game answers Steam (A2S) queries, for its player count
+    #[serde(default)]
+    pub steam_query: Option<SteamQuery>,
+    /// Where install scripts may connect, None for anywhere
+    #[serde(default)]
+    pub install_network: Option<InstallNetwork>,
+}
+
+/// The setting holding a server's image variant - empty for the game's own image
+pub const IMAGE_VARIANT_VAR: &str = "IMAGE_VARIANT";
+
+/// An image a game's servers can run on instead of its own
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImageVariant {
+    /// What IMAGE_VARIANT is set to
+    pub id: String,
+    pub label: String,
+    pub image: String,
 }
 
 fn default_console() -> bool {
@@ -72,6 +125,31 @@ pub struct Variable {
     pub field_type: FieldType,
 }
 
+/// An environment variable a server sets on its own, beyond the game's variables - for
+/// image options the game definition doesn't list
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnvVar {
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+impl EnvVar {
+    /// What's wrong with the name, if anything - letters, digits and underscores, not
+    /// starting with a digit
+    pub fn validate_key(key: &str) -> Result<(), String> {
+        let mut chars = key.chars();
+        match chars.next() {
+            None => Err("An environment variable needs a name".to_string()),
+            Some(c) if c.is_ascii_digit() => Err(format!("{} can't start with a digit", key)),
+            _ if !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+                Err(format!("{} may only have letters, digits and underscores", key))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum SystemMapping {
@@ -105,6 +183,197 @@ pub struct PortConfig {
     pub description: Option<String>,
     #[serde(default)]
     pub env_var: Option<String>,
+    /// Published on this machine only (127.0.0.1), for admin interfaces the app talks to
+    /// itself - never forwarded, opened in the firewall or shown to players
+    #[serde(default)]
+    pub local_only: bool,
+}
+
+/// Where an install script may connect. Restricted installs run on a network of their own
+/// that only reaches the internet through a proxy letting the allowed domains through.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum InstallNetwork {
+    Open,
+    Restricted {
+        /// Each one lets its subdomains through too. None at all leaves the install offline.
+        allowed_domains: Vec<String>,
+    },
+}
+
+impl InstallNetwork {
+    /// Check the allowed domains are plain host names, returning them trimmed and lowercased
+    pub fn validate(&self) -> Result<InstallNetwork, String> {
+        let InstallNetwork::Restricted { allowed_domains } = self else { return Ok(InstallNetwork::Open) };
+        let mut domains = Vec::new();
+        for domain in allowed_domains {
+            let domain = domain.trim().trim_start_matches("*.").trim_end_matches('.').to_lowercase();
+            let valid = !domain.is_empty()
+                && domain.split('.').all(|label| {
+                    !label.is_empty()
+                        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                        && !label.starts_with('-')
+                        && !label.ends_with('-')
+                });
+            if !valid {
+                return Err(format!("\"{}\" isn't a domain name", domain));
+            }
+            if !domains.contains(&domain) {
+                domains.push(domain);
+            }
+        }
+        Ok(InstallNetwork::Restricted { allowed_domains: domains })
+    }
+}
+
+/// How a server of this game is asked to stop
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StopMethod {
+    /// Type the game's stop command into the console
+    #[default]
+    Command,
+    /// What Ctrl+C in a terminal sends
+    Sigint,
+    Sigterm,
+}
+
+/// Asking the server to stop, then killing it when it hasn't exited within the timeout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StopConfig {
+    #[serde(default)]
+    pub method: StopMethod,
+    /// Seconds to wait for the server to save and exit before it's killed
+    #[serde(default = "default_stop_timeout")]
+    pub timeout_secs: u32,
+}
+
+impl Default for StopConfig {
+    fn default() -> Self {
+        Self {
+            method: StopMethod::default(),
+            timeout_secs: default_stop_timeout(),
+        }
+    }
+}
+
+fn default_stop_timeout() -> u32 {
+    30
+}
+
+pub const DEFAULT_START_TIMEOUT_SECS: u32 = 120;
+
+fn default_start_timeout() -> u32 {
+    DEFAULT_START_TIMEOUT_SECS
+}
+
+impl GameConfig {
+    /// The stop method that applies: a `^C` stop command is a SIGINT, and with no stop
+    /// command there's nothing to type, so the server gets a SIGTERM
+    pub fn stop_method(&self) -> StopMethod {
+        match (self.stop.method, self.stop_command.trim()) {
+            (StopMethod::Command, "^C") => StopMethod::Sigint,
+            (StopMethod::Command, "") => StopMethod::Sigterm,
+            (method, _) => method,
+        }
+    }
+
+    /// The variant a server's config picks, None for the game's own image
+    pub fn image_variant(&self, config: &HashMap<String, String>) -> Option<&ImageVariant> {
+        let id = config.get(IMAGE_VARIANT_VAR)?;
+        self.image_variants.iter().find(|variant| variant.id == *id)
+    }
+
+    /// The image a server with this config runs instead of the game's own: the variant it
+    /// picked, or else for Minecraft the Java its version needs. None for the game's image.
+    pub fn select_image(&self, config: &HashMap<String, String>) -> Option<String> {
+        match self.image_variant(config) {
+            Some(variant) => Some(variant.image.clone()).filter(|image| *image != self.docker_image),
+            None => java::select_image(self, config),
+        }
+    }
+
+    pub fn validate_image_variants(&self) -> Result<(), String> {
+        for (i, variant) in self.image_variants.iter().enumerate() {
+            if variant.id.trim().is_empty() || variant.image.trim().is_empty() {
+                return Err("Each image variant needs an ID and an image".to_string());
+            }
+            if variant.image.chars().any(char::is_whitespace) {
+                return Err(format!("'{}' isn't an image name", variant.image));
+            }
+            if self.image_variants[..i].iter().any(|other| other.id == variant.id) {
+                return Err(format!("Two image variants are called '{}'", variant.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// List IMAGE_VARIANT with the game's settings while it has variants, so servers pick one
+    /// like any other setting
+    pub fn sync_variant_variable(&mut self) {
+        self.variables.retain(|v| v.env != IMAGE_VARIANT_VAR);
+        if self.image_variants.is_empty() {
+            return;
+        }
+        let mut options = vec![SelectOption { value: String::new(), label: "Default".to_string() }];
+        options.extend(self.image_variants.iter().map(|variant| SelectOption {
+            value: variant.id.clone(),
+            label: variant.label.clone(),
+        }));
+        self.variables.push(Variable {
+            env: IMAGE_VARIANT_VAR.to_string(),
+            name: "Image".to_string(),
+            description: "The image the server runs on. Default is the game's own, or for Minecraft the Java its version needs.".to_string(),
+            default: String::new(),
+            system_mapping: None,
+            user_editable: true,
+            options: Some(options),
+            field_type: FieldType::Select,
+        });
+    }
+}
+
+/// A named button for a server of this game, running a console command or a shell script
+/// in the container. Both support {{VAR}} placeholders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameAction {
+    /// Used to run it, unique within the game
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Sent to the server console
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Run with `sh -c` in the volume folder, for what the console can't do
+    #[serde(default)]
+    pub script: Option<String>,
+    /// Ask before running it
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+impl GameAction {
+    /// The action with its placeholders filled in from the server's environment
+    pub fn resolve(&self, env: &HashMap<String, String>) -> GameAction {
+        let fill = |text: &Option<String>| text.as_ref().map(|t| resolve_startup(t, env));
+        GameAction {
+            command: fill(&self.command),
+            script: fill(&self.script),
+            ..self.clone()
+        }
+    }
+}
+
+fn console_action(id: &str, label: &str, command: &str) -> GameAction {
+    GameAction {
+        id: id.to_string(),
+        label: label.to_string(),
+        description: None,
+        command: Some(command.to_string()),
+        script: None,
+        confirm: false,
+    }
 }
 
 /// Configuration for modifying config files with variable substitution
@@ -144,6 +413,8 @@ impl Default for GameConfig {
             docker_image: "".to_string(),
             startup: "".to_string(),
             stop_command: "".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
             variables: Vec::new(),
             ports: Vec::new(),
             volume_path: "/data".to_string(),
@@ -156,12 +427,20 @@ impl Default for GameConfig {
             config_files: Vec::new(),
             is_custom: true,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: Vec::new(),
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: None,
+            actions: Vec::new(),
         }
     }
 }
 
 // Resolve startup command by replacing {{VAR}} placeholders
-#[allow(dead_code)]
 pub fn resolve_startup(startup: &str, variables: &HashMap<String, String>) -> String {
     let mut result = startup.to_string();
     for (key, value) in variables {
@@ -176,6 +455,7 @@ pub fn build_env_vars(
     ram_mb: u32,
     port: u16,
     user_overrides: &HashMap<String, String>,
+    custom_env: &[EnvVar],
 ) -> HashMap<String, String> {
     let mut env = HashMap::new();
     
@@ -191,6 +471,11 @@ pub fn build_env_vars(
         };
         env.insert(var.env.clone(), value);
     }
+
+    // The game's own variables win - they're set through the server's settings
+    for var in custom_env {
+        env.entry(var.key.clone()).or_insert_with(|| var.value.clone());
+    }
     
     env
 }
@@ -215,6 +500,8 @@ pub fn get_builtin_games() -> Vec<GameConfig> {
             docker_image: "ghcr.io/serverwavehost/game-images:java_21".to_string(),
             startup: "java -Dcom.mojang.eula.agree=true -Xms128M -Xmx{{SERVER_MEMORY}}M -Dterminal.jline=false -Dterminal.ansi=true -jar {{SERVER_JARFILE}}".to_string(),
             stop_command: "stop".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
             variables: vec![
                 Variable {
                     env: "SERVER_MEMORY".to_string(),
@@ -335,9 +622,30 @@ pub fn get_builtin_games() -> Vec<GameConfig> {
                     ]),
                     field_type: FieldType::Select,
                 },
+                Variable {
+                    env: "RCON_PORT".to_string(),
+                    name: "RCON Port".to_string(),
+                    description: "Port of RCON, which takes the console's commands - reachable from this machine only".to_string(),
+                    default: "25575".to_string(),
+                    system_mapping: None,
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
+                Variable {
+                    env: "RCON_PASSWORD".to_string(),
+                    name: "RCON Password".to_string(),
+                    description: "Generated for each server".to_string(),
+                    default: "".to_string(),
+                    system_mapping: None,
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Password,
+                },
             ],
             ports: vec![
-                PortConfig { container_port: 25565, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None },
+                PortConfig { container_port: 25565, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None, local_only: false },
+                PortConfig { container_port: 25575, protocol: PortProtocol::Tcp, description: Some("RCON".to_string()), env_var: Some("RCON_PORT".to_string()), local_only: true },
             ],
             volume_path: "/mnt/server".to_string(),
             min_ram_mb: 1024,
@@ -407,6 +715,8 @@ fi
 # Download the jar
 echo "[Serverwave] Downloading Paper..."
 curl -L --progress-bar -o "${SERVER_JARFILE}" "${DOWNLOAD_URL}"
+echo "[Serverwave] Installed Minecraft version: ${MINECRAFT_VERSION}"
+echo "[Serverwave] Installed build: ${BUILD_NUMBER}"
 
 # Download server.properties if it doesn't exist
 if [ ! -f server.properties ]; then
@@ -498,6 +808,303 @@ echo "[Serverwave] Paper ${MINECRAFT_VERSION} build ${BUILD_NUMBER} installed su
             ],
             is_custom: false,
             console: true,
+            player_log: Some(PlayerLogRules::new(&["]: {player} joined the game"], &["]: {player} left the game"])),
+            gslt_var: None,
+            save_folders: Vec::new(),
+            image_variants: java::variants("ghcr.io/serverwavehost/game-images:java_21"),
+            rcon: Some(RconSettings { port_var: "RCON_PORT".to_string(), password_var: "RCON_PASSWORD".to_string() }),
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Minecraft, open Multiplayer → Add Server and enter {{ADDRESS}}.".to_string()),
+            actions: vec![
+                console_action("save", "Save World", "save-all"),
+                console_action("restart-warning", "Broadcast Restart Warning", "say Server restarting in 5 minutes"),
+                console_action("whitelist-on", "Whitelist On", "whitelist on"),
+                console_action("whitelist-off", "Whitelist Off", "whitelist off"),
+            ],
+        },
+
+        GameConfig {
+            game_type: GameType::new("minecraft-modded"),
+            name: "Minecraft Modded".to_string(),
+            description: "Minecraft Java with Forge, NeoForge or Fabric, for mods and modpacks.".to_string(),
+            docker_image: "ghcr.io/serverwavehost/game-images:java_21".to_string(),
+            startup: "java -Xms128M -Xmx{{SERVER_MEMORY}}M -Dterminal.jline=false -Dterminal.ansi=true $(cat unix_args.txt) nogui".to_string(),
+            stop_command: "stop".to_string(),
+            stop: StopConfig {
+                timeout_secs: 90,
+                ..StopConfig::default()
+            },
+            start_timeout_secs: 600,
+            variables: vec![
+                Variable {
+                    env: "SERVER_MEMORY".to_string(),
+                    name: "Memory".to_string(),
+                    description: "RAM allocation in MB".to_string(),
+                    default: "4096".to_string(),
+                    system_mapping: Some(SystemMapping::Ram),
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
+                Variable {
+                    env: "LOADER".to_string(),
+                    name: "Mod Loader".to_string(),
+                    description: "The loader the mods are made for. Update the server after changing it.".to_string(),
+                    default: "neoforge".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: Some(vec![
+                        SelectOption { value: "neoforge".to_string(), label: "NeoForge".to_string() },
+                        SelectOption { value: "forge".to_string(), label: "Forge".to_string() },
+                        SelectOption { value: "fabric".to_string(), label: "Fabric".to_string() },
+                    ]),
+                    field_type: FieldType::Select,
+                },
+                Variable {
+                    env: "MINECRAFT_VERSION".to_string(),
+                    name: "Minecraft Version".to_string(),
+                    description: "The Minecraft version the mods are for, like 1.20.1".to_string(),
+                    default: "1.21.1".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: None,
+                    field_type: FieldType::Text,
+                },
+                Variable {
+                    env: "LOADER_VERSION".to_string(),
+                    name: "Loader Version".to_string(),
+                    description: "The loader's own version. Leave at latest for the newest one for the Minecraft version.".to_string(),
+                    default: "latest".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: None,
+                    field_type: FieldType::Text,
+                },
+                Variable {
+                    env: "MC_DIFFICULTY".to_string(),
+                    name: "Difficulty".to_string(),
+                    description: "Game difficulty level".to_string(),
+                    default: "normal".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: Some(vec![
+                        SelectOption { value: "peaceful".to_string(), label: "Peaceful".to_string() },
+                        SelectOption { value: "easy".to_string(), label: "Easy".to_string() },
+                        SelectOption { value: "normal".to_string(), label: "Normal".to_string() },
+                        SelectOption { value: "hard".to_string(), label: "Hard".to_string() },
+                    ]),
+                    field_type: FieldType::Select,
+                },
+                Variable {
+                    env: "MC_MAXPLAYERS".to_string(),
+                    name: "Max Players".to_string(),
+                    description: "Maximum number of players".to_string(),
+                    default: "20".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
+                Variable {
+                    env: "MC_ONLINEMODE".to_string(),
+                    name: "Online Mode".to_string(),
+                    description: "Verify players with Minecraft account database".to_string(),
+                    default: "true".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: Some(vec![
+                        SelectOption { value: "true".to_string(), label: "Yes (Recommended)".to_string() },
+                        SelectOption { value: "false".to_string(), label: "No (Cracked)".to_string() },
+                    ]),
+                    field_type: FieldType::Select,
+                },
+                Variable {
+                    env: "MC_WHITELIST".to_string(),
+                    name: "Whitelist".to_string(),
+                    description: "Enable whitelist for private servers".to_string(),
+                    default: "false".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: Some(vec![
+                        SelectOption { value: "true".to_string(), label: "Enabled".to_string() },
+                        SelectOption { value: "false".to_string(), label: "Disabled".to_string() },
+                    ]),
+                    field_type: FieldType::Select,
+                },
+                Variable {
+                    env: "MC_FLIGHT".to_string(),
+                    name: "Allow Flight".to_string(),
+                    description: "Allow flight in Survival mode - many modded items fly, and the server kicks them otherwise".to_string(),
+                    default: "true".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: Some(vec![
+                        SelectOption { value: "true".to_string(), label: "Allowed".to_string() },
+                        SelectOption { value: "false".to_string(), label: "Not Allowed".to_string() },
+                    ]),
+                    field_type: FieldType::Select,
+                },
+            ],
+            ports: vec![
+                PortConfig { container_port: 25565, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None, local_only: false },
+            ],
+            volume_path: "/mnt/server".to_string(),
+            min_ram_mb: 2048,
+            recommended_ram_mb: 6144,
+            icon: "⚒️".to_string(),
+            logo_url: Some("https://img.icons8.com/color/96/minecraft-grass-cube.png".to_string()),
+            install_script: Some(r#"#!/bin/sh
+# Modded Minecraft Installation Script
+# Runs the Forge, NeoForge or Fabric installer in the server's Java image, then puts the
+# arguments the loader starts with in unix_args.txt so one startup line fits all of them.
+# The startup expands the file itself - Java 8, which old Forge needs, has no @argfiles.
+set -e
+
+LOADER="${LOADER:-neoforge}"
+MINECRAFT_VERSION="${MINECRAFT_VERSION:-1.21.1}"
+LOADER_VERSION="${LOADER_VERSION:-latest}"
+# A pinned or rolled-back server asks for the loader build it had
+if [ -n "$SERVERWAVE_TARGET_BUILD" ]; then
+    LOADER_VERSION="$SERVERWAVE_TARGET_BUILD"
+fi
+
+if ! command -v curl >/dev/null 2>&1; then
+    echo "[Serverwave] curl is missing from the install image"
+    exit 1
+fi
+
+# The JSON APIs are read without jq - the Java images don't have it
+first_field() {
+    grep -o "\"$1\":\"[^\"]*\"" | head -n 1 | cut -d '"' -f 4
+}
+
+echo "[Serverwave] Installing ${LOADER} for Minecraft ${MINECRAFT_VERSION}..."
+
+# Left over from an earlier install or another loader - the installers recreate them
+rm -rf libraries unix_args.txt user_jvm_args.txt run.sh run.bat .fabric fabric-server-launch.jar fabric-server-launcher.properties
+rm -f forge-*.jar neoforge-*.jar installer.jar installer.jar.log
+
+case "$LOADER" in
+    fabric)
+        if [ "$LOADER_VERSION" = "latest" ]; then
+            LOADER_VERSION=$(curl -sf "https://meta.fabricmc.net/v2/versions/loader/${MINECRAFT_VERSION}" | first_field version)
+        fi
+        INSTALLER_VERSION=$(curl -sf "https://meta.fabricmc.net/v2/versions/installer" | first_field version)
+        if [ -z "$LOADER_VERSION" ] || [ -z "$INSTALLER_VERSION" ]; then
+            echo "[Serverwave] Fabric doesn't support Minecraft ${MINECRAFT_VERSION}"
+            exit 1
+        fi
+        curl -fL --progress-bar -o installer.jar \
+            "https://maven.fabricmc.net/net/fabricmc/fabric-installer/${INSTALLER_VERSION}/fabric-installer-${INSTALLER_VERSION}.jar"
+        java -jar installer.jar server -mcversion "${MINECRAFT_VERSION}" -loader "${LOADER_VERSION}" -downloadMinecraft
+        echo "-jar fabric-server-launch.jar" > unix_args.txt
+        ;;
+    forge)
+        if [ "$LOADER_VERSION" = "latest" ]; then
+            PROMOTIONS=$(curl -sf "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json")
+            LOADER_VERSION=$(echo "$PROMOTIONS" | first_field "${MINECRAFT_VERSION}-recommended")
+            if [ -z "$LOADER_VERSION" ]; then
+                LOADER_VERSION=$(echo "$PROMOTIONS" | first_field "${MINECRAFT_VERSION}-latest")
+            fi
+        fi
+        if [ -z "$LOADER_VERSION" ]; then
+            echo "[Serverwave] Forge doesn't support Minecraft ${MINECRAFT_VERSION}"
+            exit 1
+        fi
+        FORGE_VERSION="${MINECRAFT_VERSION}-${LOADER_VERSION}"
+        curl -fL --progress-bar -o installer.jar \
+            "https://maven.minecraftforge.net/net/minecraftforge/forge/${FORGE_VERSION}/forge-${FORGE_VERSION}-installer.jar"
+        java -jar installer.jar --installServer
+        ARGS="libraries/net/minecraftforge/forge/${FORGE_VERSION}/unix_args.txt"
+        if [ -f "$ARGS" ]; then
+            # 1.17 and later start through run.sh, which reads this file
+            cp "$ARGS" unix_args.txt
+        else
+            # Older versions have one jar to run
+            JAR=$(ls forge-"${FORGE_VERSION}"*.jar | grep -v installer | head -n 1)
+            echo "-jar ${JAR}" > unix_args.txt
+        fi
+        ;;
+    neoforge)
+        # NeoForge numbers follow Minecraft's: 1.21.1 is 21.1.x, 1.21 is 21.0.x
+        MINOR=$(echo "$MINECRAFT_VERSION" | cut -d . -f 2)
+        PATCH=$(echo "$MINECRAFT_VERSION" | cut -d . -f 3)
+        PREFIX="${MINOR}.${PATCH:-0}."
+        if [ "$LOADER_VERSION" = "latest" ]; then
+            LOADER_VERSION=$(curl -sf "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge" \
+                | grep -o '"[0-9][^"]*"' | tr -d '"' | grep "^${PREFIX}" | grep -v beta | tail -n 1)
+        fi
+        if [ -z "$LOADER_VERSION" ]; then
+            echo "[Serverwave] NeoForge doesn't support Minecraft ${MINECRAFT_VERSION} - it starts at 1.20.2, use Forge for older versions"
+            exit 1
+        fi
+        curl -fL --progress-bar -o installer.jar \
+            "https://maven.neoforged.net/releases/net/neoforged/neoforge/${LOADER_VERSION}/neoforge-${LOADER_VERSION}-installer.jar"
+        java -jar installer.jar --installServer
+        cp "libraries/net/neoforged/neoforge/${LOADER_VERSION}/unix_args.txt" unix_args.txt
+        ;;
+    *)
+        echo "[Serverwave] Unknown loader ${LOADER} - use forge, neoforge or fabric"
+        exit 1
+        ;;
+esac
+
+rm -f installer.jar installer.jar.log
+mkdir -p mods
+
+if [ ! -f server.properties ]; then
+    echo "[Serverwave] Creating default server.properties..."
+    cat > server.properties << 'EOF'
+motd=A Serverwave Anywhere Server
+server-port=25565
+difficulty=normal
+max-players=20
+online-mode=true
+white-list=false
+allow-flight=true
+EOF
+fi
+
+echo "[Serverwave] Accepting EULA..."
+echo "eula=true" > eula.txt
+
+echo "[Serverwave] Installed Minecraft version: ${MINECRAFT_VERSION}"
+echo "[Serverwave] Installed build: ${LOADER_VERSION}"
+echo "[Serverwave] ${LOADER} ${LOADER_VERSION} for Minecraft ${MINECRAFT_VERSION} installed successfully!"
+"#.to_string()),
+            install_image: None,
+            config_files: vec![
+                ConfigFile {
+                    path: "server.properties".to_string(),
+                    format: ConfigFileFormat::Properties,
+                    variables: {
+                        let mut m = HashMap::new();
+                        m.insert("difficulty".to_string(), "{{MC_DIFFICULTY}}".to_string());
+                        m.insert("max-players".to_string(), "{{MC_MAXPLAYERS}}".to_string());
+                        m.insert("online-mode".to_string(), "{{MC_ONLINEMODE}}".to_string());
+                        m.insert("white-list".to_string(), "{{MC_WHITELIST}}".to_string());
+                        m.insert("allow-flight".to_string(), "{{MC_FLIGHT}}".to_string());
+                        m
+                    },
+                },
+            ],
+            is_custom: false,
+            console: true,
+            player_log: Some(PlayerLogRules::new(&["]: {player} joined the game"], &["]: {player} left the game"])),
+            gslt_var: None,
+            save_folders: Vec::new(),
+            image_variants: java::variants("ghcr.io/serverwavehost/game-images:java_21"),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Minecraft with the same mods installed, open Multiplayer → Add Server and enter {{ADDRESS}}.".to_string()),
+            actions: vec![
+                console_action("save", "Save World", "save-all"),
+                console_action("restart-warning", "Broadcast Restart Warning", "say Server restarting in 5 minutes"),
+                console_action("whitelist-on", "Whitelist On", "whitelist on"),
+                console_action("whitelist-off", "Whitelist Off", "whitelist off"),
+            ],
         },
 
         GameConfig {
@@ -507,6 +1114,11 @@ echo "[Serverwave] Paper ${MINECRAFT_VERSION} build ${BUILD_NUMBER} installed su
             docker_image: "ghcr.io/serverwavehost/game-images:wine_latest".to_string(),
             startup: "wine ./SonsOfTheForestDS.exe -userdatapath \"/home/container/serverconfig\" -dedicatedserver.IpAddress \"0.0.0.0\" -dedicatedserver.GamePort \"{{SERVER_PORT}}\" -dedicatedserver.QueryPort \"{{QUERY_PORT}}\" -dedicatedserver.BlobSyncPort \"{{SYNC_PORT}}\" -dedicatedserver.SkipNetworkAccessibilityTest \"{{SKIP_TESTS}}\"".to_string(),
             stop_command: "^C".to_string(),
+            stop: StopConfig {
+                timeout_secs: 60,
+                ..StopConfig::default()
+            },
+            start_timeout_secs: 300,
             variables: vec![
                 Variable {
                     env: "SRCDS_APPID".to_string(),
@@ -687,18 +1299,21 @@ echo "[Serverwave] Paper ${MINECRAFT_VERSION} build ${BUILD_NUMBER} installed su
                     protocol: PortProtocol::Both,
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
                 },
                 PortConfig {
                     container_port: 27016,
                     protocol: PortProtocol::Both,
                     description: Some("Query port".to_string()),
                     env_var: Some("QUERY_PORT".to_string()),
+                    local_only: false,
                 },
                 PortConfig {
                     container_port: 9700,
                     protocol: PortProtocol::Both,
                     description: Some("Blob sync port".to_string()),
                     env_var: Some("SYNC_PORT".to_string()),
+                    local_only: false,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -717,22 +1332,34 @@ echo "[Serverwave] Starting Sons of the Forest installation..."
 SERVER_PATH=/home/container
 SRCDS_APPID=2465200
 
-# Download and setup steamcmd
-cd /tmp
-mkdir -p "${SERVER_PATH}/steamcmd"
-curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
-tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
-mkdir -p "${SERVER_PATH}/steamapps"
+# Set up steamcmd - from the shared cache when the app mounts one
+mkdir -p "${SERVER_PATH}/steamcmd" "${SERVER_PATH}/steamapps"
+if [ -n "${STEAMCMD_CACHE}" ] && [ -f "${STEAMCMD_CACHE}/serverwave.sh" ]; then
+    . "${STEAMCMD_CACHE}/serverwave.sh"
+    steamcmd_setup "${SERVER_PATH}"
+    steamcmd_seed "${SERVER_PATH}" "${SRCDS_APPID}"
+else
+    steamcmd_store() { :; }
+    cd /tmp
+    curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+    tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+fi
 cd "${SERVER_PATH}/steamcmd"
 
 chown -R root:root "${SERVER_PATH}"
 export HOME="${SERVER_PATH}"
 
-echo "[Serverwave] Logging into Steam..."
-./steamcmd.sh +login anonymous +quit
+# A retried install skips SteamCMD once it has reported the game fully installed
+if [ "${STEAMCMD_DONE}" = "1" ]; then
+    echo "[Serverwave] Game files already downloaded, skipping SteamCMD..."
+else
+    echo "[Serverwave] Logging into Steam..."
+    ./steamcmd.sh +login anonymous +quit
 
-echo "[Serverwave] Installing Sons of the Forest dedicated server (Windows)..."
-./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +@sSteamCmdForcePlatformType windows +app_update ${SRCDS_APPID} validate +quit
+    echo "[Serverwave] Installing Sons of the Forest dedicated server (Windows)..."
+    ./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +@sSteamCmdForcePlatformType windows +app_update ${SRCDS_APPID} validate +quit
+    steamcmd_store "${SERVER_PATH}" "${SRCDS_APPID}"
+fi
 
 # Set up Steam libraries
 echo "[Serverwave] Setting up Steam libraries..."
@@ -777,6 +1404,15 @@ echo "[Serverwave] Sons of the Forest installed successfully!"
             ],
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: vec!["serverconfig/Saves".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: Some(SteamQuery { port_var: Some("QUERY_PORT".to_string()) }),
+            install_network: None,
+            join_instructions: Some("In Sons of the Forest, open Multiplayer → Join → Dedicated, then filter by {{IP}} or search for the server name.".to_string()),
+            actions: Vec::new(),
         },
 
         GameConfig {
@@ -786,6 +1422,11 @@ echo "[Serverwave] Sons of the Forest installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:rust_latest".to_string(),
             startup: "./RustDedicated -batchmode +server.port {{SERVER_PORT}} +server.queryport {{SERVER_PORT}} +server.identity \"rust\" +rcon.ip 0.0.0.0 +rcon.port {{RCON_PORT}} +rcon.web true +server.hostname \"{{HOSTNAME}}\" +server.level \"{{LEVEL}}\" +server.description \"{{DESCRIPTION}}\" +server.url \"{{SERVER_URL}}\" +server.headerimage \"{{SERVER_IMG}}\" +server.maxplayers {{MAX_PLAYERS}} +rcon.password \"{{RCON_PASS}}\" +server.saveinterval {{SAVEINTERVAL}} +server.worldsize {{WORLD_SIZE}} +server.seed {{WORLD_SEED}} {{ADDITIONAL_ARGS}}".to_string(),
             stop_command: "quit".to_string(),
+            stop: StopConfig {
+                timeout_secs: 120,
+                ..StopConfig::default()
+            },
+            start_timeout_secs: 600,
             variables: vec![
                 Variable {
                     env: "SRCDS_APPID".to_string(),
@@ -961,12 +1602,14 @@ echo "[Serverwave] Sons of the Forest installed successfully!"
                     protocol: PortProtocol::Both,
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
                 },
                 PortConfig {
                     container_port: 28016,
                     protocol: PortProtocol::Both,
                     description: Some("RCON port".to_string()),
                     env_var: Some("RCON_PORT".to_string()),
+                    local_only: false,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -985,22 +1628,34 @@ echo "[Serverwave] Starting Rust installation..."
 SERVER_PATH=/home/container
 SRCDS_APPID=258550
 
-# Download and setup steamcmd
-cd /tmp
-mkdir -p "${SERVER_PATH}/steamcmd"
-curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
-tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
-mkdir -p "${SERVER_PATH}/steamapps"
+# Set up steamcmd - from the shared cache when the app mounts one
+mkdir -p "${SERVER_PATH}/steamcmd" "${SERVER_PATH}/steamapps"
+if [ -n "${STEAMCMD_CACHE}" ] && [ -f "${STEAMCMD_CACHE}/serverwave.sh" ]; then
+    . "${STEAMCMD_CACHE}/serverwave.sh"
+    steamcmd_setup "${SERVER_PATH}"
+    steamcmd_seed "${SERVER_PATH}" "${SRCDS_APPID}"
+else
+    steamcmd_store() { :; }
+    cd /tmp
+    curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+    tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+fi
 cd "${SERVER_PATH}/steamcmd"
 
 chown -R root:root "${SERVER_PATH}"
 export HOME="${SERVER_PATH}"
 
-echo "[Serverwave] Logging into Steam..."
-./steamcmd.sh +login anonymous +quit
+# A retried install skips SteamCMD once it has reported the game fully installed
+if [ "${STEAMCMD_DONE}" = "1" ]; then
+    echo "[Serverwave] Game files already downloaded, skipping SteamCMD..."
+else
+    echo "[Serverwave] Logging into Steam..."
+    ./steamcmd.sh +login anonymous +quit
 
-echo "[Serverwave] Installing Rust dedicated server..."
-./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +app_update ${SRCDS_APPID} validate +quit
+    echo "[Serverwave] Installing Rust dedicated server..."
+    ./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +app_update ${SRCDS_APPID} validate +quit
+    steamcmd_store "${SERVER_PATH}" "${SRCDS_APPID}"
+fi
 
 # Set up Steam libraries
 echo "[Serverwave] Setting up Steam libraries..."
@@ -1022,6 +1677,18 @@ echo "[Serverwave] Rust installed successfully!"
             config_files: Vec::new(),
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: vec!["server/rust".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: Some(SteamQuery::default()),
+            install_network: None,
+            join_instructions: Some("Press F1 in Rust to open the console and run: client.connect {{ADDRESS}}".to_string()),
+            actions: vec![
+                console_action("save", "Save World", "server.save"),
+                console_action("restart-warning", "Broadcast Restart Warning", "say \"Server restarting in 5 minutes\""),
+            ],
         },
 
         GameConfig {
@@ -1031,6 +1698,8 @@ echo "[Serverwave] Rust installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:debian".to_string(),
             startup: "./{{SERVER_BINARY}}".to_string(),
             stop_command: "stop".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
             variables: vec![
                 Variable {
                     env: "SERVER_BINARY".to_string(),
@@ -1054,7 +1723,7 @@ echo "[Serverwave] Rust installed successfully!"
                 },
             ],
             ports: vec![
-                PortConfig { container_port: 19133, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None },
+                PortConfig { container_port: 19133, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None, local_only: false },
             ],
             volume_path: "/mnt/server".to_string(),
             min_ram_mb: 512,
@@ -1129,6 +1798,19 @@ echo "[Serverwave] Minecraft Bedrock installed successfully!"
             ],
             is_custom: false,
             console: true,
+            player_log: Some(PlayerLogRules::new(&["Player connected: {player}, xuid"], &["Player disconnected: {player}, xuid"])),
+            gslt_var: None,
+            save_folders: vec!["worlds".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Minecraft Bedrock, open Play → Servers → Add Server and enter {{IP}} as the address and {{PORT}} as the port.".to_string()),
+            actions: vec![
+                console_action("restart-warning", "Broadcast Restart Warning", "say Server restarting in 5 minutes"),
+                console_action("allowlist-on", "Allowlist On", "allowlist on"),
+                console_action("allowlist-off", "Allowlist Off", "allowlist off"),
+            ],
         },
 
         GameConfig {
@@ -1138,6 +1820,8 @@ echo "[Serverwave] Minecraft Bedrock installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:debian".to_string(),
             startup: "./TerrariaServer.bin.x86_64 -config serverconfig.txt".to_string(),
             stop_command: "exit".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
             variables: vec![
                 Variable {
                     env: "WORLD_NAME".to_string(),
@@ -1210,7 +1894,7 @@ echo "[Serverwave] Minecraft Bedrock installed successfully!"
                 },
             ],
             ports: vec![
-                PortConfig { container_port: 7777, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None },
+                PortConfig { container_port: 7777, protocol: PortProtocol::Both, description: Some("Game port".to_string()), env_var: None, local_only: false },
             ],
             volume_path: "/home/container".to_string(),
             min_ram_mb: 512,
@@ -1293,6 +1977,18 @@ echo "[Serverwave] Terraria installed successfully!"
             ],
             is_custom: false,
             console: true,
+            player_log: Some(PlayerLogRules::new(&["{player} has joined."], &["{player} has left."])),
+            gslt_var: None,
+            save_folders: vec!["saves/Worlds".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Terraria, choose Multiplayer → Join via IP, enter {{IP}} and then port {{PORT}}.".to_string()),
+            actions: vec![
+                console_action("save", "Save World", "save"),
+                console_action("restart-warning", "Broadcast Restart Warning", "say Server restarting in 5 minutes"),
+            ],
         },
 
         GameConfig {
@@ -1302,6 +1998,8 @@ echo "[Serverwave] Terraria installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:java_25".to_string(),
             startup: "java -XX:+UnlockExperimentalVMOptions -XX:AOTCache=Server/HytaleServer.aot -Xms128M -Xmx{{SERVER_MEMORY}}M -XX:+UseG1GC -XX:MaxGCPauseMillis=200 -XX:G1HeapRegionSize=8M -XX:G1NewSizePercent=30 -XX:G1ReservePercent=20 -XX:InitiatingHeapOccupancyPercent=15 -XX:+UseStringDeduplication -XX:+AlwaysPreTouch -XX:MaxMetaspaceSize=512M -XX:+UseGCOverheadLimit -XX:+ExplicitGCInvokesConcurrent -jar {{SERVER_JARFILE}} --assets {{ASSETS_PATH}} {{EXTRA_ARGS}}".to_string(),
             stop_command: "stop".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
             variables: vec![
                 Variable {
                     env: "SERVER_MEMORY".to_string(),
@@ -1390,6 +2088,7 @@ echo "[Serverwave] Terraria installed successfully!"
                     protocol: PortProtocol::Both, 
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -1419,7 +2118,7 @@ unzip -o hytale-downloader.zip
 # Make executable and run (this will prompt for OAuth if needed)
 chmod +x hytale-downloader-linux-amd64
 echo "[Serverwave] Running Hytale downloader (OAuth authentication may be required)..."
-echo "[Serverwave] Check the popup if authentication is needed!"
+echo "[Serverwave] If it asks you to sign in, the link shows up in the app"
 ./hytale-downloader-linux-amd64
 
 # Find and extract the downloaded version zip
@@ -1456,6 +2155,15 @@ echo "[Serverwave] Hytale server installed successfully!"
             ],
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: vec!["universe".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Hytale, open Servers → Add Server and enter {{ADDRESS}}.".to_string()),
+            actions: Vec::new(),
         },
 
         GameConfig {
@@ -1465,6 +2173,8 @@ echo "[Serverwave] Hytale server installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:steamcmd_debian".to_string(),
             startup: "/home/container/Pal/Binaries/Linux/PalServer-Linux-Shipping Pal -port={{SERVER_PORT}} -players={{MAX_PLAYERS}} -useperfthreads -NoAsyncLoadingThread -UseMultithreadForDS -servername=\"{{SRV_NAME}}\" -serverpassword=\"{{SRV_PASSWORD}}\" -adminpassword=\"{{ADMIN_PASSWORD}}\"".to_string(),
             stop_command: "^C".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: 180,
             variables: vec![
                 Variable {
                     env: "SRCDS_APPID".to_string(),
@@ -1526,6 +2236,26 @@ echo "[Serverwave] Hytale server installed successfully!"
                     options: None,
                     field_type: FieldType::Password,
                 },
+                Variable {
+                    env: "REST_API_PORT".to_string(),
+                    name: "REST API Port".to_string(),
+                    description: "Port of the admin REST API the app uses for players, announcements and saves - reachable from this machine only".to_string(),
+                    default: "8212".to_string(),
+                    system_mapping: None,
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
+                Variable {
+                    env: "RCON_PORT".to_string(),
+                    name: "RCON Port".to_string(),
+                    description: "Port of RCON, which takes the console's commands - reachable from this machine only".to_string(),
+                    default: "25575".to_string(),
+                    system_mapping: None,
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
                 Variable {
                     env: "AUTO_UPDATE".to_string(),
                     name: "Auto Update".to_string(),
@@ -1546,6 +2276,21 @@ echo "[Serverwave] Hytale server installed successfully!"
                     protocol: PortProtocol::Both,
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
+                },
+                PortConfig {
+                    container_port: 8212,
+                    protocol: PortProtocol::Tcp,
+                    description: Some("REST API".to_string()),
+                    env_var: Some("REST_API_PORT".to_string()),
+                    local_only: true,
+                },
+                PortConfig {
+                    container_port: 25575,
+                    protocol: PortProtocol::Tcp,
+                    description: Some("RCON".to_string()),
+                    env_var: Some("RCON_PORT".to_string()),
+                    local_only: true,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -1564,22 +2309,34 @@ echo "[Serverwave] Starting Palworld installation..."
 SERVER_PATH=/home/container
 SRCDS_APPID=2394010
 
-# Download and setup steamcmd
-cd /tmp
-mkdir -p ${SERVER_PATH}/steamcmd
-curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
-tar -xzvf steamcmd.tar.gz -C ${SERVER_PATH}/steamcmd
-mkdir -p ${SERVER_PATH}/steamapps
-cd ${SERVER_PATH}/steamcmd
+# Set up steamcmd - from the shared cache when the app mounts one
+mkdir -p "${SERVER_PATH}/steamcmd" "${SERVER_PATH}/steamapps"
+if [ -n "${STEAMCMD_CACHE}" ] && [ -f "${STEAMCMD_CACHE}/serverwave.sh" ]; then
+    . "${STEAMCMD_CACHE}/serverwave.sh"
+    steamcmd_setup "${SERVER_PATH}"
+    steamcmd_seed "${SERVER_PATH}" "${SRCDS_APPID}"
+else
+    steamcmd_store() { :; }
+    cd /tmp
+    curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+    tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+fi
+cd "${SERVER_PATH}/steamcmd"
 
 chown -R root:root ${SERVER_PATH}
 export HOME=${SERVER_PATH}
 
-echo "[Serverwave] Logging into Steam..."
-./steamcmd.sh +login anonymous +quit
+# A retried install skips SteamCMD once it has reported the game fully installed
+if [ "${STEAMCMD_DONE}" = "1" ]; then
+    echo "[Serverwave] Game files already downloaded, skipping SteamCMD..."
+else
+    echo "[Serverwave] Logging into Steam..."
+    ./steamcmd.sh +login anonymous +quit
 
-echo "[Serverwave] Installing Palworld dedicated server..."
-./steamcmd.sh +force_install_dir ${SERVER_PATH} +login anonymous +app_update ${SRCDS_APPID} validate +quit
+    echo "[Serverwave] Installing Palworld dedicated server..."
+    ./steamcmd.sh +force_install_dir ${SERVER_PATH} +login anonymous +app_update ${SRCDS_APPID} validate +quit
+    steamcmd_store "${SERVER_PATH}" "${SRCDS_APPID}"
+fi
 
 # Set up Steam libraries
 echo "[Serverwave] Setting up Steam libraries..."
@@ -1617,6 +2374,15 @@ echo "[Serverwave] Palworld installed successfully!"
             ],
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: vec!["Pal/Saved/SaveGames".to_string()],
+            image_variants: Vec::new(),
+            rcon: Some(RconSettings { port_var: "RCON_PORT".to_string(), password_var: "ADMIN_PASSWORD".to_string() }),
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Palworld, choose Join Multiplayer Game and enter {{ADDRESS}} in the box at the bottom of the server list.".to_string()),
+            actions: Vec::new(),
         },
 
         GameConfig {
@@ -1626,6 +2392,11 @@ echo "[Serverwave] Palworld installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:steamcmd_debian".to_string(),
             startup: "Engine/Binaries/Linux/*-Linux-Shipping FactoryGame -Port={{SERVER_PORT}} -ReliablePort={{RELIABLE_PORT}}".to_string(),
             stop_command: "^C".to_string(),
+            stop: StopConfig {
+                timeout_secs: 60,
+                ..StopConfig::default()
+            },
+            start_timeout_secs: 300,
             variables: vec![
                 Variable {
                     env: "SRCDS_APPID".to_string(),
@@ -1717,12 +2488,14 @@ echo "[Serverwave] Palworld installed successfully!"
                     protocol: PortProtocol::Both,
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
                 },
                 PortConfig {
                     container_port: 8888,
                     protocol: PortProtocol::Both,
                     description: Some("Reliable port".to_string()),
                     env_var: Some("RELIABLE_PORT".to_string()),
+                    local_only: false,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -1741,21 +2514,34 @@ echo "[Serverwave] Starting Satisfactory installation..."
 SERVER_PATH=/home/container
 SRCDS_APPID=1690800
 
-# Download and setup steamcmd
-cd /tmp
-curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
-mkdir -p "${SERVER_PATH}/steamcmd"
-tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+# Set up steamcmd - from the shared cache when the app mounts one
+mkdir -p "${SERVER_PATH}/steamcmd" "${SERVER_PATH}/steamapps"
+if [ -n "${STEAMCMD_CACHE}" ] && [ -f "${STEAMCMD_CACHE}/serverwave.sh" ]; then
+    . "${STEAMCMD_CACHE}/serverwave.sh"
+    steamcmd_setup "${SERVER_PATH}"
+    steamcmd_seed "${SERVER_PATH}" "${SRCDS_APPID}"
+else
+    steamcmd_store() { :; }
+    cd /tmp
+    curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+    tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+fi
 cd "${SERVER_PATH}/steamcmd"
 
 chown -R root:root "${SERVER_PATH}"
 export HOME="${SERVER_PATH}"
 
-echo "[Serverwave] Logging into Steam..."
-./steamcmd.sh +login anonymous +quit
+# A retried install skips SteamCMD once it has reported the game fully installed
+if [ "${STEAMCMD_DONE}" = "1" ]; then
+    echo "[Serverwave] Game files already downloaded, skipping SteamCMD..."
+else
+    echo "[Serverwave] Logging into Steam..."
+    ./steamcmd.sh +login anonymous +quit
 
-echo "[Serverwave] Installing Satisfactory dedicated server..."
-./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +app_update ${SRCDS_APPID} validate +exit
+    echo "[Serverwave] Installing Satisfactory dedicated server..."
+    ./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +app_update ${SRCDS_APPID} validate +exit
+    steamcmd_store "${SERVER_PATH}" "${SRCDS_APPID}"
+fi
 
 # Set up Steam libraries
 echo "[Serverwave] Setting up Steam libraries..."
@@ -1814,6 +2600,15 @@ echo "[Serverwave] Satisfactory installed successfully!"
             ],
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: vec![".config/Epic/FactoryGame/Saved/SaveGames".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Satisfactory, open Server Manager → Add Server and enter {{IP}} with port {{PORT}}.".to_string()),
+            actions: Vec::new(),
         },
 
         GameConfig {
@@ -1823,6 +2618,11 @@ echo "[Serverwave] Satisfactory installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:steamcmd_debian".to_string(),
             startup: "export PATH=\"./jre64/bin:$PATH\" ; export LD_LIBRARY_PATH=\"./linux64:./natives:.:./jre64/lib/amd64:${LD_LIBRARY_PATH}\" ; ./ProjectZomboid64 -port {{SERVER_PORT}} -udpport {{UDP_PORT}} -cachedir=/home/container/.cache -servername \"{{SERVER_NAME}}\" -adminusername {{ADMIN_USER}} -adminpassword \"{{ADMIN_PASSWORD}}\"".to_string(),
             stop_command: "^C".to_string(),
+            stop: StopConfig {
+                timeout_secs: 60,
+                ..StopConfig::default()
+            },
+            start_timeout_secs: 300,
             variables: vec![
                 Variable {
                     env: "SRCDS_APPID".to_string(),
@@ -1914,12 +2714,14 @@ echo "[Serverwave] Satisfactory installed successfully!"
                     protocol: PortProtocol::Both,
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
                 },
                 PortConfig {
                     container_port: 16262,
                     protocol: PortProtocol::Both,
                     description: Some("UDP port".to_string()),
                     env_var: Some("UDP_PORT".to_string()),
+                    local_only: false,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -1938,22 +2740,34 @@ echo "[Serverwave] Starting Project Zomboid installation..."
 SERVER_PATH=/home/container
 SRCDS_APPID=380870
 
-# Download and setup steamcmd
-cd /tmp
-mkdir -p "${SERVER_PATH}/steamcmd"
-curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
-tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
-mkdir -p "${SERVER_PATH}/steamapps"
+# Set up steamcmd - from the shared cache when the app mounts one
+mkdir -p "${SERVER_PATH}/steamcmd" "${SERVER_PATH}/steamapps"
+if [ -n "${STEAMCMD_CACHE}" ] && [ -f "${STEAMCMD_CACHE}/serverwave.sh" ]; then
+    . "${STEAMCMD_CACHE}/serverwave.sh"
+    steamcmd_setup "${SERVER_PATH}"
+    steamcmd_seed "${SERVER_PATH}" "${SRCDS_APPID}"
+else
+    steamcmd_store() { :; }
+    cd /tmp
+    curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+    tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+fi
 cd "${SERVER_PATH}/steamcmd"
 
 chown -R root:root "${SERVER_PATH}"
 export HOME="${SERVER_PATH}"
 
-echo "[Serverwave] Logging into Steam..."
-./steamcmd.sh +login anonymous +quit
+# A retried install skips SteamCMD once it has reported the game fully installed
+if [ "${STEAMCMD_DONE}" = "1" ]; then
+    echo "[Serverwave] Game files already downloaded, skipping SteamCMD..."
+else
+    echo "[Serverwave] Logging into Steam..."
+    ./steamcmd.sh +login anonymous +quit
 
-echo "[Serverwave] Installing Project Zomboid dedicated server..."
-./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +app_update ${SRCDS_APPID} validate +quit
+    echo "[Serverwave] Installing Project Zomboid dedicated server..."
+    ./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +app_update ${SRCDS_APPID} validate +quit
+    steamcmd_store "${SERVER_PATH}" "${SRCDS_APPID}"
+fi
 
 # Set up Steam libraries
 echo "[Serverwave] Setting up Steam libraries..."
@@ -1973,6 +2787,15 @@ echo "[Serverwave] Project Zomboid installed successfully!"
             config_files: Vec::new(),
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: vec![".cache/Saves".to_string()],
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: Some(SteamQuery::default()),
+            install_network: None,
+            join_instructions: Some("In Project Zomboid, choose Join → Add to favorites with IP {{IP}} and port {{PORT}}.".to_string()),
+            actions: Vec::new(),
         },
 
         GameConfig {
@@ -1982,6 +2805,8 @@ echo "[Serverwave] Project Zomboid installed successfully!"
             docker_image: "ghcr.io/serverwavehost/game-images:wine_latest".to_string(),
             startup: "wine ./StarRuptureServerEOS.exe -Log -port={{SERVER_PORT}} -QueryPort={{QUERY_PORT}} -ServerName=\"{{SRV_NAME}}\" MaxPlayers={{MAX_PLAYERS}}".to_string(),
             stop_command: "^C".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
             variables: vec![
                 Variable {
                     env: "SRCDS_APPID".to_string(),
@@ -2103,12 +2928,14 @@ echo "[Serverwave] Project Zomboid installed successfully!"
                     protocol: PortProtocol::Both,
                     description: Some("Game port".to_string()),
                     env_var: Some("SERVER_PORT".to_string()),
+                    local_only: false,
                 },
                 PortConfig {
                     container_port: 27015,
                     protocol: PortProtocol::Both,
                     description: Some("Query port".to_string()),
                     env_var: Some("QUERY_PORT".to_string()),
+                    local_only: false,
                 },
             ],
             volume_path: "/home/container".to_string(),
@@ -2127,22 +2954,34 @@ echo "[Serverwave] Starting StarRupture installation..."
 SERVER_PATH=/home/container
 SRCDS_APPID=3809400
 
-# Download and setup steamcmd
-cd /tmp
-mkdir -p "${SERVER_PATH}/steamcmd"
-curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
-tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
-mkdir -p "${SERVER_PATH}/steamapps"
+# Set up steamcmd - from the shared cache when the app mounts one
+mkdir -p "${SERVER_PATH}/steamcmd" "${SERVER_PATH}/steamapps"
+if [ -n "${STEAMCMD_CACHE}" ] && [ -f "${STEAMCMD_CACHE}/serverwave.sh" ]; then
+    . "${STEAMCMD_CACHE}/serverwave.sh"
+    steamcmd_setup "${SERVER_PATH}"
+    steamcmd_seed "${SERVER_PATH}" "${SRCDS_APPID}"
+else
+    steamcmd_store() { :; }
+    cd /tmp
+    curl -sSL -o steamcmd.tar.gz https://steamcdn-a.akamaihd.net/client/installer/steamcmd_linux.tar.gz
+    tar -xzvf steamcmd.tar.gz -C "${SERVER_PATH}/steamcmd"
+fi
 cd "${SERVER_PATH}/steamcmd"
 
 chown -R root:root "${SERVER_PATH}"
 export HOME="${SERVER_PATH}"
 
-echo "[Serverwave] Logging into Steam..."
-./steamcmd.sh +login anonymous +quit
+# A retried install skips SteamCMD once it has reported the game fully installed
+if [ "${STEAMCMD_DONE}" = "1" ]; then
+    echo "[Serverwave] Game files already downloaded, skipping SteamCMD..."
+else
+    echo "[Serverwave] Logging into Steam..."
+    ./steamcmd.sh +login anonymous +quit
 
-echo "[Serverwave] Installing StarRupture dedicated server (Windows)..."
-./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +@sSteamCmdForcePlatformType windows +app_update ${SRCDS_APPID} validate +quit
+    echo "[Serverwave] Installing StarRupture dedicated server (Windows)..."
+    ./steamcmd.sh +force_install_dir "${SERVER_PATH}" +login anonymous +@sSteamCmdForcePlatformType windows +app_update ${SRCDS_APPID} validate +quit
+    steamcmd_store "${SERVER_PATH}" "${SRCDS_APPID}"
+fi
 
 # Set up Steam libraries
 echo "[Serverwave] Setting up Steam libraries..."
@@ -2158,6 +2997,136 @@ echo "[Serverwave] StarRupture installed successfully!"
             config_files: Vec::new(),
             is_custom: false,
             console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: Vec::new(),
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In StarRupture, open the server browser and direct-connect to {{ADDRESS}}.".to_string()),
+            actions: Vec::new(),
+        },
+
+        GameConfig {
+            game_type: GameType::new("velocity"),
+            name: "Velocity Proxy".to_string(),
+            description: "Modern Minecraft proxy for linking several Paper servers into one network.".to_string(),
+            docker_image: "ghcr.io/serverwavehost/game-images:java_21".to_string(),
+            startup: "java -Xms128M -Xmx{{SERVER_MEMORY}}M -XX:+UseG1GC -XX:G1HeapRegionSize=4M -XX:+UnlockExperimentalVMOptions -XX:+ParallelRefProcEnabled -XX:+AlwaysPreTouch -jar {{SERVER_JARFILE}} --port {{SERVER_PORT}}".to_string(),
+            stop_command: "end".to_string(),
+            stop: StopConfig::default(),
+            start_timeout_secs: DEFAULT_START_TIMEOUT_SECS,
+            variables: vec![
+                Variable {
+                    env: "SERVER_MEMORY".to_string(),
+                    name: "Memory".to_string(),
+                    description: "RAM allocation in MB".to_string(),
+                    default: "512".to_string(),
+                    system_mapping: Some(SystemMapping::Ram),
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
+                Variable {
+                    env: "SERVER_PORT".to_string(),
+                    name: "Server Port".to_string(),
+                    description: "Port players connect to".to_string(),
+                    default: "25577".to_string(),
+                    system_mapping: Some(SystemMapping::Port),
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Number,
+                },
+                Variable {
+                    env: "SERVER_JARFILE".to_string(),
+                    name: "Server JAR File".to_string(),
+                    description: "The name of the proxy jarfile".to_string(),
+                    default: "velocity.jar".to_string(),
+                    system_mapping: None,
+                    user_editable: false,
+                    options: None,
+                    field_type: FieldType::Text,
+                },
+                Variable {
+                    env: "VELOCITY_VERSION".to_string(),
+                    name: "Velocity Version".to_string(),
+                    description: "The version of Velocity. Leave at latest for newest version.".to_string(),
+                    default: "latest".to_string(),
+                    system_mapping: None,
+                    user_editable: true,
+                    options: None,
+                    field_type: FieldType::Text,
+                },
+            ],
+            ports: vec![
+                PortConfig { container_port: 25577, protocol: PortProtocol::Tcp, description: Some("Proxy port".to_string()), env_var: Some("SERVER_PORT".to_string()), local_only: false },
+            ],
+            volume_path: "/mnt/server".to_string(),
+            min_ram_mb: 512,
+            recommended_ram_mb: 1024,
+            icon: "🔀".to_string(),
+            logo_url: None,
+            install_script: Some(r#"#!/bin/sh
+# Velocity Installation Script
+set -e
+
+echo "[Serverwave] Installing required tools..."
+apk add --no-cache curl jq
+
+PROJECT=velocity
+SERVER_JARFILE="${SERVER_JARFILE:-velocity.jar}"
+VELOCITY_VERSION="${VELOCITY_VERSION:-latest}"
+
+if [ "$VELOCITY_VERSION" = "latest" ]; then
+    echo "[Serverwave] Fetching latest Velocity version..."
+    VELOCITY_VERSION=$(curl -s https://api.papermc.io/v2/projects/${PROJECT} | jq -r '.versions[-1]')
+fi
+echo "[Serverwave] Using version: ${VELOCITY_VERSION}"
+
+BUILD_NUMBER=$(curl -s https://api.papermc.io/v2/projects/${PROJECT}/versions/${VELOCITY_VERSION} | jq -r '.builds[-1]')
+if [ -z "$BUILD_NUMBER" ] || [ "$BUILD_NUMBER" = "null" ]; then
+    echo "[Serverwave] Version ${VELOCITY_VERSION} not found"
+    exit 1
+fi
+
+JAR_NAME=${PROJECT}-${VELOCITY_VERSION}-${BUILD_NUMBER}.jar
+DOWNLOAD_URL="https://api.papermc.io/v2/projects/${PROJECT}/versions/${VELOCITY_VERSION}/builds/${BUILD_NUMBER}/downloads/${JAR_NAME}"
+
+echo "[Serverwave] Downloading ${JAR_NAME}..."
+curl -L --progress-bar -o "${SERVER_JARFILE}" "${DOWNLOAD_URL}"
+
+echo "[Serverwave] Velocity ${VELOCITY_VERSION} build ${BUILD_NUMBER} installed successfully!"
+"#.to_string()),
+            install_image: Some("alpine:latest".to_string()),
+            config_files: Vec::new(),
+            is_custom: false,
+            console: true,
+            player_log: None,
+            gslt_var: None,
+            save_folders: Vec::new(),
+            image_variants: Vec::new(),
+            rcon: None,
+            steam_query: None,
+            install_network: None,
+            join_instructions: Some("In Minecraft, open Multiplayer → Add Server and enter {{ADDRESS}}.".to_string()),
+            actions: Vec::new(),
         },
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_allowed_domains() {
+        let network = InstallNetwork::Restricted { allowed_domains: vec![" *.GitHub.com ".into(), "github.com".into()] };
+        assert_eq!(
+            network.validate(),
+            Ok(InstallNetwork::Restricted { allowed_domains: vec!["github.com".into()] })
+        );
+        let bad = InstallNetwork::Restricted { allowed_domains: vec!["https://github.com".into()] };
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,612 @@
+// Config file processor - handles reading/writing config files with variable substitution
+// TODO: Integrate with server startup to process config files
+#![allow(dead_code)]
+
+use crate::games::{ConfigFile, ConfigFileFormat};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Apply variable substitutions to a config file
+/// Returns Ok(true) if file was modified, Ok(false) if file doesn't exist
+pub fn apply_config_variables(
+    base_path: &Path,
+    config_file: &ConfigFile,
+    variables: &HashMap<String, String>,
+) -> Result<bool, String> {
+    let file_path = base_path.join(&config_file.path);
+    
+    if !file_path.exists() {
+        tracing::debug!("Config file doesn't exist yet: {:?}", file_path);
+        return Ok(false);
+    }
+    
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read config file {:?}: {}", file_path, e))?;
+    
+    let new_content = render_config(&content, config_file, variables)?;
+    
+    if new_content != content {
+        crate::atomic::write_atomic(&file_path, &new_content)
+            .map_err(|e| format!("Failed to write config file {:?}: {}", file_path, e))?;
+        tracing::info!("Updated config file: {:?}", file_path);
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// What applying the variables would change in one config file
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigFileDiff {
+    pub path: String,
+    /// False until the game has written the file - it's left alone until then
+    pub exists: bool,
+    /// Unified diff from the file as it is to the file with the variables applied, empty
+    /// when nothing would change
+    pub diff: String,
+    /// The file couldn't be read or isn't valid for its format
+    pub error: Option<String>,
+}
+
+/// Dry run of apply_config_variables over a game's config files - nothing is written
+pub fn preview_config_variables(
+    base_path: &Path,
+    config_files: &[ConfigFile],
+    variables: &HashMap<String, String>,
+) -> Vec<ConfigFileDiff> {
+    config_files
+        .iter()
+        .map(|config_file| {
+            let file_path = base_path.join(&config_file.path);
+            let mut preview = ConfigFileDiff {
+                path: config_file.path.clone(),
+                exists: file_path.exists(),
+                diff: String::new(),
+                error: None,
+            };
+            if !preview.exists {
+                return preview;
+            }
+            let rendered = std::fs::read_to_string(&file_path)
+                .map_err(|e| format!("Failed to read config file {:?}: {}", file_path, e))
+                .and_then(|content| {
+                    render_config(&content, config_file, variables).map(|new| (content, new))
+                });
+            match rendered {
+                Ok((content, new_content)) => {
+                    preview.diff = unified_diff(&config_file.path, &content, &new_content)
+                }
+                Err(e) => preview.error = Some(e),
+            }
+            preview
+        })
+        .collect()
+}
+
+/// The reverse of apply_config_variables: the values a config file has for the keys mapped
+/// to a single variable, by variable name. Keys the file doesn't have are left out.
+pub fn read_config_variables(base_path: &Path, config_file: &ConfigFile) -> HashMap<String, String> {
+    let Ok(content) = std::fs::read_to_string(base_path.join(&config_file.path)) else {
+        return HashMap::new();
+    };
+    let json: Option<serde_json::Value> = match config_file.format {
+        ConfigFileFormat::Json => serde_json::from_str(&content).ok(),
+        _ => None,
+    };
+    config_file
+        .variables
+        .iter()
+        .filter_map(|(config_key, template)| {
+            // Fixed values and templates with text around the variable can't be read back
+            let var_name = template.strip_prefix("{{")?.strip_suffix("}}")?;
+            if var_name.contains("{{") {
+                return None;
+            }
+            let value = match config_file.format {
+                ConfigFileFormat::Properties => read_line_value(&content, None, config_key, '='),
+                ConfigFileFormat::Ini => {
+                    let (section, key) = match config_key.split_once('/') {
+                        Some((section, key)) => (Some(section), key),
+                        None => (None, config_key.as_str()),
+                    };
+                    read_line_value(&content, Some(section), key, '=')
+                }
+                ConfigFileFormat::Yaml => read_line_value(&content, None, config_key, ':')
+                    .map(|v| v.trim_matches(|c| c == '"' || c == '\'').to_string()),
+                ConfigFileFormat::Json => {
+                    let value = config_key
+                        .split('.')
+                        .try_fold(json.as_ref()?, |value, key| value.get(key))?;
+                    Some(match value {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                }
+            }?;
+            Some((var_name.to_string(), value))
+        })
+        .collect()
+}
+
+/// The value of the first `key<separator>value` line, the way the apply functions find it.
+/// `section` is Some for INI files: Some(None) matches the key in any section.
+fn read_line_value(content: &str, section: Option<Option<&str>>, key: &str, separator: char) -> Option<String> {
+    let mut current_section: Option<&str> = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if section.is_some() && trimmed.starts_with('[') && trimmed.ends_with(']') {
+            current_section = Some(&trimmed[1..trimmed.len() - 1]);
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') || (section.is_some() && trimmed.starts_with(';')) {
+            continue;
+        }
+        let Some((line_key, value)) = trimmed.split_once(separator) else { continue };
+        let section_matches = match section {
+            Some(Some(target)) => current_section == Some(target),
+            _ => true,
+        };
+        if section_matches && line_key.trim() == key {
+            return Some(value.trim().to_string());
+        }
+    }
+    None
+}
+
+/// The file's content with the variables applied
+fn render_config(
+    content: &str,
+    config_file: &ConfigFile,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut new_content = match config_file.format {
+        ConfigFileFormat::Properties => {
+            apply_properties_variables(content, &config_file.variables, variables)?
+        }
+        ConfigFileFormat::Ini => {
+            apply_ini_variables(content, &config_file.variables, variables)?
+        }
+        ConfigFileFormat::Json => {
+            apply_json_variables(content, &config_file.variables, variables)?
+        }
+        ConfigFileFormat::Yaml => {
+            apply_yaml_variables(content, &config_file.variables, variables)?
+        }
+    };
+    // The line-based formats rejoin lines without the final newline - keep it
+    if content.ends_with('\n') && !new_content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    Ok(new_content)
+}
+
+/// Unchanged lines shown around each change in a diff
+const DIFF_CONTEXT: usize = 3;
+/// Past this many line pairs the changed part is shown as replaced outright
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffLine {
+    Same,
+    Removed,
+    Added,
+}
+
+/// The lines of `old` and `new` in diff order, each marked kept, removed or added
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffLine, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut lines: Vec<(DiffLine, &str)> = old[..prefix].iter().map(|l| (DiffLine::Same, *l)).collect();
+    if a.len() * b.len() > MAX_DIFF_CELLS {
+        lines.extend(a.iter().map(|l| (DiffLine::Removed, *l)));
+        lines.extend(b.iter().map(|l| (DiffLine::Added, *l)));
+    } else {
+        // Longest common subsequence of a[i..] and b[j..], for every i and j
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                lines.push((DiffLine::Same, a[i]));
+                i += 1;
+                j += 1;
+            } else if i < a.len() && (j == b.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                lines.push((DiffLine::Removed, a[i]));
+                i += 1;
+            } else {
+                lines.push((DiffLine::Added, b[j]));
+                j += 1;
+            }
+        }
+    }
+    lines.extend(old[old.len() - suffix..].iter().map(|l| (DiffLine::Same, *l)));
+    lines
+}
+
+/// A unified diff (as `diff -u` prints it) from `old` to `new`, empty when they match
+fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let lines = diff_lines(&old_lines, &new_lines);
+    let changes: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (kind, _))| *kind != DiffLine::Same)
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let count = |lines: &[(DiffLine, &str)], skip: DiffLine| lines.iter().filter(|(kind, _)| *kind != skip).count();
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let mut c = 0;
+    while c < changes.len() {
+        // Changes closer than twice the context share a hunk
+        let start = changes[c].saturating_sub(DIFF_CONTEXT);
+        let mut last = changes[c];
+        while c + 1 < changes.len() && changes[c + 1] - last <= 2 * DIFF_CONTEXT {
+            c += 1;
+            last = changes[c];
+        }
+        c += 1;
+        let end = (last + DIFF_CONTEXT + 1).min(lines.len());
+
+        let hunk = &lines[start..end];
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(count(&lines[..start], DiffLine::Added), count(hunk, DiffLine::Added)),
+            hunk_range(count(&lines[..start], DiffLine::Removed), count(hunk, DiffLine::Removed)),
+        ));
+        for (kind, line) in hunk {
+            let marker = match kind {
+                DiffLine::Same => ' ',
+                DiffLine::Removed => '-',
+                DiffLine::Added => '+',
+            };
+            out.push(marker);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// A hunk header's "start,count" for one side; an empty side points at the line before it
+fn hunk_range(lines_before: usize, count: usize) -> String {
+    let start = if count == 0 { lines_before } else { lines_before + 1 };
+    if count == 1 {
+        start.to_string()
+    } else {
+        format!("{},{}", start, count)
+    }
+}
+
+/// Apply variables to a Properties file (key=value format, no sections)
+fn apply_properties_variables(
+    content: &str,
+    mappings: &HashMap<String, String>,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    
+    for (config_key, var_template) in mappings {
+        // Extract variable name from {{VAR_NAME}}
+        let var_name = var_template
+            .trim_start_matches("{{")
+            .trim_end_matches("}}")
+            .to_string();
+        
+        if let Some(value) = variables.get(&var_name) {
+            // Find and replace the line with this key
+            for line in &mut lines {
+                let trimmed = line.trim();
+                if trimmed.starts_with('#') || trimmed.is_empty() {
+                    continue;
+                }
+                
+                if let Some(eq_pos) = trimmed.find('=') {
+                    let key = trimmed[..eq_pos].trim();
+                    if key == config_key {
+                        *line = format!("{}={}", config_key, value);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(lines.join("\n"))
+}
+
+/// Apply variables to an INI file (with [section] headers)
+fn apply_ini_variables(
+    content: &str,
+    mappings: &HashMap<String, String>,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    
+    for (config_key, var_template) in mappings {
+        // Extract variable name from {{VAR_NAME}}
+        let var_name = var_template
+            .trim_start_matches("{{")
+            .trim_end_matches("}}")
+            .to_string();
+        
+        if let Some(value) = variables.get(&var_name) {
+            // config_key can be either "key" or "section/key"
+            let (target_section, target_key) = if config_key.contains('/') {
+                let parts: Vec<&str> = config_key.splitn(2, '/').collect();
+                (Some(parts[0]), parts[1])
+            } else {
+                (None, config_key.as_str())
+            };
+            
+            let mut current_section: Option<String> = None;
+            let mut found = false;
+            
+            for line in &mut lines {
+                let trimmed = line.trim();
+                
+                // Track current section
+                if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                    current_section = Some(trimmed[1..trimmed.len()-1].to_string());
+                    continue;
+                }
+                
+                // Skip comments and empty lines
+                if trimmed.starts_with('#') || trimmed.starts_with(';') || trimmed.is_empty() {
+                    continue;
+                }
+                
+                // Check if this line matches our key (and section if specified)
+                if let Some(eq_pos) = trimmed.find('=') {
+                    let key = trimmed[..eq_pos].trim();
+                    
+                    let section_matches = match (&target_section, &current_section) {
+                        (Some(ts), Some(cs)) => ts == cs,
+                        (None, _) => true, // No section specified, match any
+                        _ => false,
+                    };
+                    
+                    if section_matches && key == target_key {
+                        *line = format!("{}={}", target_key, value);
+                        found = true;
+                        break;
+                    }
+                }
+            }
+            
+            if !found {
+                tracing::debug!("INI key not found: {} (will be added on first run)", config_key);
+            }
+        }
+    }
+    
+    Ok(lines.join("\n"))
+}
+
+/// Apply variables to a JSON file
+fn apply_json_variables(
+    content: &str,
+    mappings: &HashMap<String, String>,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    let mut json: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("Invalid JSON: {}", e))?;
+    
+    for (config_key, var_template) in mappings {
+        let var_name = var_template
+            .trim_start_matches("{{")
+            .trim_end_matches("}}")
+            .to_string();
+        
+        if let Some(value) = variables.get(&var_name) {
+            // Support nested keys with dot notation: "server.maxPlayers"
+            let keys: Vec<&str> = config_key.split('.').collect();
+            set_json_value(&mut json, &keys, value);
+        }
+    }
+    
+    serde_json::to_string_pretty(&json)
+        .map_err(|e| format!("Failed to serialize JSON: {}", e))
+}
+
+fn set_json_value(json: &mut serde_json::Value, keys: &[&str], value: &str) {
+    if keys.is_empty() {
+        return;
+    }
+    
+    if keys.len() == 1 {
+        if let serde_json::Value::Object(map) = json {
+            // Try to preserve the original type
+            if let Some(existing) = map.get(keys[0]) {
+                let new_value = match existing {
+                    serde_json::Value::Number(_) => {
+                        if let Ok(n) = value.parse::<i64>() {
+                            serde_json::Value::Number(n.into())
+                        } else if let Ok(n) = value.parse::<f64>() {
+                            serde_json::Number::from_f64(n)
+                                .map(serde_json::Value::Number)
+                                .unwrap_or_else(|| serde_json::Value::String(value.to_string()))
+                        } else {
+                            serde_json::Value::String(value.to_string())
+                        }
+                    }
+                    serde_json::Value::Bool(_) => {
+                        serde_json::Value::Bool(value == "true" || value == "1")
+                    }
+                    _ => serde_json::Value::String(value.to_string()),
+                };
+                map.insert(keys[0].to_string(), new_value);
+            } else {
+                map.insert(keys[0].to_string(), serde_json::Value::String(value.to_string()));
+            }
+        }
+    } else {
+        if let serde_json::Value::Object(map) = json {
+            if let Some(nested) = map.get_mut(keys[0]) {
+                set_json_value(nested, &keys[1..], value);
+            }
+        }
+    }
+}
+
+/// Apply variables to a YAML file
+fn apply_yaml_variables(
+    content: &str,
+    mappings: &HashMap<String, String>,
+    variables: &HashMap<String, String>,
+) -> Result<String, String> {
+    // For YAML, we do simple line-based replacement to preserve formatting
+    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    
+    for (config_key, var_template) in mappings {
+        let var_name = var_template
+            .trim_start_matches("{{")
+            .trim_end_matches("}}")
+            .to_string();
+        
+        if let Some(value) = variables.get(&var_name) {
+            // Simple key: value matching
+            for line in &mut lines {
+                let trimmed = line.trim();
+                if trimmed.starts_with('#') || trimmed.is_empty() {
+                    continue;
+                }
+                
+                if let Some(colon_pos) = trimmed.find(':') {
+                    let key = trimmed[..colon_pos].trim();
+                    if key == config_key {
+                        // Preserve indentation
+                        let indent = line.len() - line.trim_start().len();
+                        let indent_str: String = line.chars().take(indent).collect();
+                        *line = format!("{}{}: {}", indent_str, config_key, value);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_properties_replacement() {
+        let content = "# Comment\nmax-players=20\ndifficulty=normal\n";
+        let mut mappings = HashMap::new();
+        mappings.insert("max-players".to_string(), "{{MAX_PLAYERS}}".to_string());
+        
+        let mut variables = HashMap::new();
+        variables.insert("MAX_PLAYERS".to_string(), "50".to_string());
+        
+        let result = apply_properties_variables(content, &mappings, &variables).unwrap();
+        assert!(result.contains("max-players=50"));
+    }
+    
+    #[test]
+    fn test_ini_replacement() {
+        let content = "[Server]\nMaxPlayers=20\nViewDistance=12\n";
+        let mut mappings = HashMap::new();
+        mappings.insert("Server/MaxPlayers".to_string(), "{{HT_MAXPLAYERS}}".to_string());
+        
+        let mut variables = HashMap::new();
+        variables.insert("HT_MAXPLAYERS".to_string(), "100".to_string());
+        
+        let result = apply_ini_variables(content, &mappings, &variables).unwrap();
+        assert!(result.contains("MaxPlayers=100"));
+    }
+    
+    #[test]
+    fn test_json_replacement() {
+        let content = r#"{"MaxPlayers": 20, "ViewDistance": 12}"#;
+        let mut mappings = HashMap::new();
+        mappings.insert("MaxPlayers".to_string(), "{{HT_MAXPLAYERS}}".to_string());
+        
+        let mut variables = HashMap::new();
+        variables.insert("HT_MAXPLAYERS".to_string(), "100".to_string());
+        
+        let result = apply_json_variables(content, &mappings, &variables).unwrap();
+        assert!(result.contains("100"));
+    }
+    
+    #[test]
+    fn test_reads_variables_back() {
+        let dir = std::env::temp_dir().join(format!("sw-config-read-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("server.properties"), "# Minecraft\nmax-players=12\nmotd=Hello = world\n").unwrap();
+        std::fs::write(dir.join("Game.ini"), "[Other]\nMaxPlayers=1\n[Server]\nMaxPlayers=32\n").unwrap();
+
+        let properties = ConfigFile {
+            path: "server.properties".to_string(),
+            format: ConfigFileFormat::Properties,
+            variables: HashMap::from([
+                ("max-players".to_string(), "{{MAX_PLAYERS}}".to_string()),
+                ("motd".to_string(), "{{MOTD}}".to_string()),
+                ("enable-query".to_string(), "true".to_string()),
+                ("level-name".to_string(), "{{LEVEL}}".to_string()),
+            ]),
+        };
+        let values = read_config_variables(&dir, &properties);
+        assert_eq!(values.get("MAX_PLAYERS").map(String::as_str), Some("12"));
+        assert_eq!(values.get("MOTD").map(String::as_str), Some("Hello = world"));
+        assert_eq!(values.len(), 2);
+
+        let ini = ConfigFile {
+            path: "Game.ini".to_string(),
+            format: ConfigFileFormat::Ini,
+            variables: HashMap::from([("Server/MaxPlayers".to_string(), "{{MAX_PLAYERS}}".to_string())]),
+        };
+        assert_eq!(read_config_variables(&dir, &ini).get("MAX_PLAYERS").map(String::as_str), Some("32"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_preview_diff() {
+        let old = "# Comment\nmotd=Hi\nmax-players=20\ndifficulty=normal\npvp=true\nview-distance=10\nsimulation-distance=10\nspawn-protection=16\nwhite-list=false\n";
+        let config_file = ConfigFile {
+            path: "server.properties".to_string(),
+            format: ConfigFileFormat::Properties,
+            variables: HashMap::from([
+                ("max-players".to_string(), "{{MAX_PLAYERS}}".to_string()),
+                ("white-list".to_string(), "{{WHITELIST}}".to_string()),
+            ]),
+        };
+        let variables = HashMap::from([
+            ("MAX_PLAYERS".to_string(), "50".to_string()),
+            ("WHITELIST".to_string(), "false".to_string()),
+        ]);
+
+        let new = render_config(old, &config_file, &variables).unwrap();
+        assert!(new.ends_with('\n'));
+        assert_eq!(
+            unified_diff("server.properties", old, &new),
+            "--- a/server.properties\n+++ b/server.properties\n@@ -1,6 +1,6 @@\n # Comment\n motd=Hi\n-max-players=20\n+max-players=50\n difficulty=normal\n pvp=true\n view-distance=10\n"
+        );
+        assert_eq!(unified_diff("server.properties", old, old), "");
+    }
+}
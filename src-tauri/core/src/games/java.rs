@@ -0,0 +1,132 @@
+// Java runtime - the Java a Minecraft version needs, and the java_<n> image tag that has it,
+// so old versions don't start on a Java too new for them and new ones on one too old
+
+use super::{GameConfig, ImageVariant};
+use std::collections::HashMap;
+
+/// The variable Minecraft games keep their version in
+const VERSION_VAR: &str = "MINECRAFT_VERSION";
+const TAG_PREFIX: &str = ":java_";
+/// The Javas there are images for, offered as image variants
+const JAVA_VERSIONS: &[u32] = &[8, 11, 16, 17, 21, 25];
+
+/// The Java a Minecraft version runs on. None for "latest" and versions it can't read,
+/// which stay on the game's own image.
+pub fn required_java(minecraft_version: &str) -> Option<u32> {
+    let mut parts = minecraft_version.trim().split(['.', '-']).map(|p| p.parse::<u32>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().and_then(Result::ok).unwrap_or(0);
+    let patch = parts.next().and_then(Result::ok).unwrap_or(0);
+    // Year-based versions (26.1 and later) need Java 25
+    if major >= 26 {
+        return Some(25);
+    }
+    if major != 1 {
+        return None;
+    }
+    Some(match (minor, patch) {
+        (0..=16, _) => 8,
+        (17, _) => 16,
+        (18..=19, _) | (20, 0..=4) => 17,
+        _ => 21,
+    })
+}
+
+/// `image` with its java_<n> tag swapped for `java`, None when it isn't tagged that way
+pub fn image_with_java(image: &str, java: u32) -> Option<String> {
+    let (repository, tag) = image.rsplit_once(TAG_PREFIX)?;
+    tag.parse::<u32>().ok()?;
+    Some(format!("{}{}{}", repository, TAG_PREFIX, java))
+}
+
+/// The image this game needs for a Minecraft version when it isn't the game's own image
+pub fn image_for_version(game: &GameConfig, minecraft_version: &str) -> Option<String> {
+    let java = required_java(minecraft_version)?;
+    image_with_java(&game.docker_image, java).filter(|image| *image != game.docker_image)
+}
+
+/// A variant for each Java there's an image for, for a game whose image is tagged java_<n>
+pub fn variants(image: &str) -> Vec<ImageVariant> {
+    JAVA_VERSIONS
+        .iter()
+        .filter_map(|java| {
+            Some(ImageVariant {
+                id: format!("java_{}", java),
+                label: format!("Java {}", java),
+                image: image_with_java(image, *java)?,
+            })
+        })
+        .collect()
+}
+
+/// The image a server of this game should use for the Minecraft version in its config.
+/// None when the game has no version setting or its own image fits.
+pub fn select_image(game: &GameConfig, config: &HashMap<String, String>) -> Option<String> {
+    game.variables.iter().find(|v| v.env == VERSION_VAR)?;
+    image_for_version(game, config.get(VERSION_VAR)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::{Variable, IMAGE_VARIANT_VAR};
+
+    #[test]
+    fn test_java_follows_minecraft_version() {
+        assert_eq!(required_java("1.8.9"), Some(8));
+        assert_eq!(required_java("1.16.5"), Some(8));
+        assert_eq!(required_java("1.17.1"), Some(16));
+        assert_eq!(required_java("1.18"), Some(17));
+        assert_eq!(required_java("1.20.4"), Some(17));
+        assert_eq!(required_java("1.20.5"), Some(21));
+        assert_eq!(required_java("1.21.4"), Some(21));
+        assert_eq!(required_java("26.1"), Some(25));
+        assert_eq!(required_java("latest"), None);
+
+        assert_eq!(
+            image_with_java("ghcr.io/serverwavehost/game-images:java_21", 8).as_deref(),
+            Some("ghcr.io/serverwavehost/game-images:java_8")
+        );
+        assert_eq!(image_with_java("ghcr.io/serverwavehost/game-images:debian", 8), None);
+    }
+
+    #[test]
+    fn test_picked_variant_wins_over_version() {
+        let mut game = GameConfig {
+            docker_image: "ghcr.io/serverwavehost/game-images:java_21".to_string(),
+            image_variants: variants("ghcr.io/serverwavehost/game-images:java_21"),
+            ..Default::default()
+        };
+        game.variables.push(Variable {
+            env: VERSION_VAR.to_string(),
+            name: "Minecraft Version".to_string(),
+            description: String::new(),
+            default: "latest".to_string(),
+            system_mapping: None,
+            user_editable: true,
+            options: None,
+            field_type: Default::default(),
+        });
+        game.sync_variant_variable();
+        game.sync_variant_variable();
+        let picker: Vec<_> = game.variables.iter().filter(|v| v.env == IMAGE_VARIANT_VAR).collect();
+        assert_eq!(picker.len(), 1);
+        assert_eq!(picker[0].options.as_ref().map(Vec::len), Some(JAVA_VERSIONS.len() + 1));
+
+        let config = |pairs: &[(&str, &str)]| pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        // Without a pick the version decides; a picked variant wins, and one that's the
+        // game's own image means no override
+        assert_eq!(
+            game.select_image(&config(&[(VERSION_VAR, "1.12.2")])).as_deref(),
+            Some("ghcr.io/serverwavehost/game-images:java_8")
+        );
+        assert_eq!(
+            game.select_image(&config(&[(VERSION_VAR, "1.12.2"), (IMAGE_VARIANT_VAR, "java_11")])).as_deref(),
+            Some("ghcr.io/serverwavehost/game-images:java_11")
+        );
+        assert_eq!(game.select_image(&config(&[(VERSION_VAR, "1.12.2"), (IMAGE_VARIANT_VAR, "java_21")])), None);
+        assert!(game.validate_image_variants().is_ok());
+        game.image_variants.push(game.image_variants[0].clone());
+        assert!(game.validate_image_variants().is_err());
+    }
+}
@@ -0,0 +1,110 @@
+// Install script lint - a syntax check with the script's own shell, plus shellcheck when the
+// image has it, run in a throwaway container before anyone waits through a full install
+
+use serde::Serialize;
+
+const SYNTAX_OK: &str = "@@serverwave-syntax-ok";
+const SYNTAX_FAILED: &str = "@@serverwave-syntax-failed";
+const SHELLCHECK: &str = "@@serverwave-shellcheck";
+const NO_SHELLCHECK: &str = "@@serverwave-no-shellcheck";
+const SHELL: &str = "@@serverwave-interpreter";
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ScriptLint {
+    /// The interpreter that checked the syntax, from the shebang line
+    pub shell: String,
+    pub syntax_ok: bool,
+    /// What the syntax check printed
+    pub errors: Vec<String>,
+    /// shellcheck's findings, None when the image doesn't have shellcheck
+    pub shellcheck: Option<Vec<String>>,
+}
+
+/// The script run in the image. The install script itself is written to /tmp/install.sh
+/// from base64, the same way installs get it.
+pub fn checker(script: &str) -> String {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(script);
+    format!(
+        r#"echo '{encoded}' | base64 -d > /tmp/install.sh
+shell=$(sed -n '1s|^#![[:space:]]*\([^[:space:]]*\).*|\1|p' /tmp/install.sh)
+case "$shell" in
+    */env) shell=$(sed -n '1s|^#![[:space:]]*[^[:space:]]*[[:space:]]*\([^[:space:]]*\).*|\1|p' /tmp/install.sh) ;;
+esac
+command -v "$shell" >/dev/null 2>&1 || shell=/bin/sh
+echo "{SHELL} $shell"
+if "$shell" -n /tmp/install.sh 2>&1; then echo "{SYNTAX_OK}"; else echo "{SYNTAX_FAILED}"; fi
+if command -v shellcheck >/dev/null 2>&1; then
+    echo "{SHELLCHECK}"
+    shellcheck -f gcc -s "$(basename "$shell")" /tmp/install.sh 2>&1
+else
+    echo "{NO_SHELLCHECK}"
+fi
+exit 0
+"#
+    )
+}
+
+/// Read the checker's output back
+pub fn parse(lines: &[String]) -> Result<ScriptLint, String> {
+    let mut lint = ScriptLint {
+        shell: "/bin/sh".to_string(),
+        syntax_ok: false,
+        errors: Vec::new(),
+        shellcheck: None,
+    };
+    let mut finished_syntax = false;
+    let mut in_shellcheck = false;
+    for line in lines {
+        let line = line.trim_end();
+        if let Some(shell) = line.strip_prefix(SHELL) {
+            lint.shell = shell.trim().to_string();
+        } else if line == SYNTAX_OK || line == SYNTAX_FAILED {
+            lint.syntax_ok = line == SYNTAX_OK;
+            finished_syntax = true;
+        } else if line == SHELLCHECK {
+            in_shellcheck = true;
+            lint.shellcheck = Some(Vec::new());
+        } else if line == NO_SHELLCHECK {
+            in_shellcheck = false;
+        } else if in_shellcheck {
+            if let Some(findings) = lint.shellcheck.as_mut() {
+                // Point at the script rather than the temp file it was checked as
+                findings.push(line.replace("/tmp/install.sh:", "line "));
+            }
+        } else if !finished_syntax && !line.is_empty() {
+            lint.errors.push(line.replace("/tmp/install.sh: ", ""));
+        }
+    }
+    if !finished_syntax {
+        return Err(format!("The image couldn't check the script: {}", lines.join("\n")));
+    }
+    Ok(lint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_syntax_errors_and_shellcheck() {
+        let lines: Vec<String> = [
+            "@@serverwave-interpreter /bin/bash",
+            "/tmp/install.sh: line 12: syntax error near unexpected token `fi'",
+            "@@serverwave-syntax-failed",
+            "@@serverwave-shellcheck",
+            "/tmp/install.sh:4:6: warning: Quote this to prevent word splitting. [SC2046]",
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let lint = parse(&lines).unwrap();
+        assert_eq!(lint.shell, "/bin/bash");
+        assert!(!lint.syntax_ok);
+        assert_eq!(lint.errors, vec!["line 12: syntax error near unexpected token `fi'".to_string()]);
+        assert_eq!(lint.shellcheck.unwrap()[0], "line 4:6: warning: Quote this to prevent word splitting. [SC2046]");
+
+        assert!(parse(&["exec format error".to_string()]).is_err());
+        assert!(checker("#!/bin/sh\necho hi\n").contains("base64 -d"));
+    }
+}
@@ -10,6 +10,12 @@ pub struct GamesManager {
     custom_games_path: PathBuf,
 }
 
+impl Default for GamesManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GamesManager {
     pub fn new() -> Self {
         let custom_games_path = get_games_config_path();
@@ -29,11 +35,9 @@ impl GamesManager {
         }
 
         // Load custom games
-        if let Ok(content) = std::fs::read_to_string(&self.custom_games_path) {
-            if let Ok(custom_games) = serde_json::from_str::<Vec<GameConfig>>(&content) {
-                for game in custom_games {
-                    self.custom_games.insert(game.game_type.0.clone(), game);
-                }
+        if let Some(custom_games) = crate::atomic::read_json::<Vec<GameConfig>>(&self.custom_games_path) {
+            for game in custom_games {
+                self.custom_games.insert(game.game_type.0.clone(), game);
             }
         }
     }
@@ -54,6 +58,9 @@ impl GamesManager {
         }
         
         // Sort: built-in first, then custom, alphabetically
+        for game in &mut games {
+            game.sync_variant_variable();
+        }
         games.sort_by(|a, b| {
             match (a.is_custom, b.is_custom) {
                 (false, true) => std::cmp::Ordering::Less,
@@ -67,10 +74,9 @@ impl GamesManager {
 
     pub fn get_game(&self, game_type: &GameType) -> Option<GameConfig> {
         // Custom games take precedence (overrides)
-        if let Some(game) = self.custom_games.get(&game_type.0) {
-            return Some(game.clone());
-        }
-        self.builtin_games.get(&game_type.0).cloned()
+        let mut game = self.custom_games.get(&game_type.0).or_else(|| self.builtin_games.get(&game_type.0))?.clone();
+        game.sync_variant_variable();
+        Some(game)
     }
 
     pub fn add_game(&mut self, mut game: GameConfig) -> Result<(), String> {
@@ -80,6 +86,10 @@ impl GamesManager {
         if game.docker_image.is_empty() {
             return Err("Docker image cannot be empty".to_string());
         }
+        if let Some(rules) = &game.player_log {
+            rules.validate()?;
+        }
+        game.validate_image_variants()?;
 
         // Always mark as custom when adding
         game.is_custom = true;
@@ -95,6 +105,10 @@ impl GamesManager {
         if !exists {
             return Err("Game not found".to_string());
         }
+        if let Some(rules) = &game.player_log {
+            rules.validate()?;
+        }
+        game.validate_image_variants()?;
         
         // Always save as custom (this creates an override for built-in games)
         game.is_custom = true;
@@ -124,6 +138,11 @@ impl GamesManager {
         serde_json::to_string_pretty(game).map_err(|e| e.to_string())
     }
 
+    /// Custom games and overrides of built-in ones
+    pub fn get_custom_games(&self) -> Vec<GameConfig> {
+        self.custom_games.values().cloned().collect()
+    }
+
     pub fn export_all_custom_games(&self) -> Result<String, String> {
         let custom_games: Vec<_> = self.custom_games.values().cloned().collect();
         serde_json::to_string_pretty(&custom_games).map_err(|e| e.to_string())
@@ -143,6 +162,10 @@ impl GamesManager {
         if game.docker_image.is_empty() {
             return Err("Docker image cannot be empty".to_string());
         }
+        if let Some(rules) = &game.player_log {
+            rules.validate()?;
+        }
+        game.validate_image_variants()?;
 
         self.custom_games.insert(game.game_type.0.clone(), game.clone());
         self.save_custom_games()?;
@@ -156,7 +179,7 @@ impl GamesManager {
         let mut imported = Vec::new();
         for mut game in games {
             game.is_custom = true;
-            if !game.game_type.0.is_empty() && !game.docker_image.is_empty() {
+            if !game.game_type.0.is_empty() && !game.docker_image.is_empty() && game.validate_image_variants().is_ok() {
                 self.custom_games.insert(game.game_type.0.clone(), game.clone());
                 imported.push(game);
             }
@@ -172,12 +195,7 @@ impl GamesManager {
         let content = serde_json::to_string_pretty(&custom_games)
             .map_err(|e| e.to_string())?;
 
-        // Ensure directory exists
-        if let Some(parent) = self.custom_games_path.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-
-        std::fs::write(&self.custom_games_path, content)
+        crate::atomic::write_with_backup(&self.custom_games_path, content)
             .map_err(|e| e.to_string())
     }
 
@@ -195,10 +213,5 @@ impl GamesManager {
 }
 
 fn get_games_config_path() -> PathBuf {
-    directories::UserDirs::new()
-        .map(|d| d.home_dir().to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("ServerWaveAnywhere")
-        .join("games")
-        .join("custom_games.json")
+    crate::paths::app_root().join("games").join("custom_games.json")
 }
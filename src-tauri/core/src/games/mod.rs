@@ -0,0 +1,15 @@
+mod config;
+mod config_processor;
+pub mod java;
+pub mod lint;
+mod manager;
+mod player_log;
+
+pub use config::{
+    build_env_vars, ConfigFile, ConfigFileFormat, EnvVar, FieldType, GameAction, GameConfig, GameType, ImageVariant,
+    InstallNetwork, PortConfig, PortProtocol, SelectOption, StopConfig, StopMethod, SystemMapping, Variable, DEFAULT_START_TIMEOUT_SECS,
+    IMAGE_VARIANT_VAR,
+};
+pub use config_processor::{preview_config_variables, read_config_variables, ConfigFileDiff};
+pub use manager::GamesManager;
+pub use player_log::{PlayerLogEvent, PlayerLogRules};
@@ -0,0 +1,108 @@
+// Player log rules - how a game's console says someone joined or left, so play sessions can
+// be tracked for games without a query protocol
+
+use serde::{Deserialize, Serialize};
+
+/// Stands for the player's name in a pattern
+const PLAYER: &str = "{player}";
+/// Longer than any game allows - anything more is a chat line or a false match
+const MAX_NAME_LEN: usize = 64;
+
+/// Console line patterns with a `{player}` placeholder, like `]: {player} joined the game`.
+/// The text before the placeholder is looked for first, then the text after it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerLogRules {
+    #[serde(default)]
+    pub joined: Vec<String>,
+    #[serde(default)]
+    pub left: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerLogEvent {
+    Joined(String),
+    Left(String),
+}
+
+impl PlayerLogRules {
+    pub fn new(joined: &[&str], left: &[&str]) -> Self {
+        Self {
+            joined: joined.iter().map(|p| p.to_string()).collect(),
+            left: left.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self.joined.iter().chain(&self.left).find(|p| p.matches(PLAYER).count() != 1) {
+            Some(pattern) => Err(format!("Player log pattern \"{}\" needs {} exactly once", pattern, PLAYER)),
+            None => Ok(()),
+        }
+    }
+
+    /// The join or leave a console line reports, if any
+    pub fn parse(&self, line: &str) -> Option<PlayerLogEvent> {
+        let line = strip_ansi(line);
+        let find = |patterns: &[String]| patterns.iter().find_map(|p| match_pattern(p, &line));
+        find(&self.joined)
+            .map(PlayerLogEvent::Joined)
+            .or_else(|| find(&self.left).map(PlayerLogEvent::Left))
+    }
+}
+
+fn match_pattern(pattern: &str, line: &str) -> Option<String> {
+    let (before, after) = pattern.split_once(PLAYER)?;
+    let start = if before.is_empty() { 0 } else { line.find(before)? + before.len() };
+    let rest = &line[start..];
+    let name = if after.is_empty() { rest } else { &rest[..rest.find(after)?] };
+    let name = name.trim_matches(|c: char| c.is_whitespace() || c == ':');
+    (!name.is_empty() && name.len() <= MAX_NAME_LEN).then(|| name.to_string())
+}
+
+/// The line without terminal color codes, which some consoles wrap names in
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // ESC [ ... final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_joins_and_leaves() {
+        let minecraft = PlayerLogRules::new(&["]: {player} joined the game"], &["]: {player} left the game"]);
+        assert_eq!(
+            minecraft.parse("[12:00:01] [Server thread/INFO]: Steve joined the game"),
+            Some(PlayerLogEvent::Joined("Steve".to_string()))
+        );
+        assert_eq!(
+            minecraft.parse("\u{1b}[33m[12:30:00 INFO]: Alex left the game\u{1b}[0m"),
+            Some(PlayerLogEvent::Left("Alex".to_string()))
+        );
+        assert_eq!(minecraft.parse("[12:00:00 INFO]: Done (3.2s)!"), None);
+
+        let bedrock = PlayerLogRules::new(&["Player connected: {player}, xuid"], &["Player disconnected: {player}, xuid"]);
+        assert_eq!(
+            bedrock.parse("[2026-10-16 12:00:00:000 INFO] Player connected: Some One, xuid: 2535400000000000"),
+            Some(PlayerLogEvent::Joined("Some One".to_string()))
+        );
+
+        let terraria = PlayerLogRules::new(&["{player} has joined."], &["{player} has left."]);
+        assert_eq!(terraria.parse(": Steve has left."), Some(PlayerLogEvent::Left("Steve".to_string())));
+        assert!(PlayerLogRules::new(&["joined the game"], &[]).validate().is_err());
+        assert!(terraria.validate().is_ok());
+    }
+}
@@ -0,0 +1,13 @@
+// Serverwave Anywhere Core
+// Docker access, game definitions and the server lifecycle, independent of the UI
+
+pub mod a2s;
+pub mod atomic;
+pub mod cron;
+pub mod docker;
+pub mod games;
+pub mod paths;
+pub mod ports;
+pub mod rcon;
+pub mod server;
+pub mod slp;
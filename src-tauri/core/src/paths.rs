@@ -0,0 +1,76 @@
+// Data locations - app files live under ~/ServerWaveAnywhere, server data and
+// configs under the data directory, which defaults to the same folder but can be moved
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+lazy_static::lazy_static! {
+    static ref DATA_ROOT_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+}
+
+/// Tests that point the data root at a temp dir hold this so they don't overlap
+#[cfg(test)]
+pub(crate) static TEST_ROOT_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Folder for app files - settings, games, users, secrets. Never moves.
+pub fn app_root() -> PathBuf {
+    directories::UserDirs::new()
+        .map(|d| d.home_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ServerWaveAnywhere")
+}
+
+/// Application log files
+pub fn logs_dir() -> PathBuf {
+    app_root().join("logs")
+}
+
+/// Root folder for servers and their configs
+pub fn data_root() -> PathBuf {
+    DATA_ROOT_OVERRIDE
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(app_root)
+}
+
+/// Point the data directory somewhere else, or back to the default with None
+pub fn set_data_root(path: Option<PathBuf>) {
+    *DATA_ROOT_OVERRIDE.write().unwrap() = path;
+}
+
+/// Server data, one folder per game and server id
+pub fn servers_dir() -> PathBuf {
+    data_root().join("servers")
+}
+
+/// Per-server JSON configs
+pub fn config_dir() -> PathBuf {
+    data_root().join("config")
+}
+
+/// Data of deleted servers that was put aside rather than deleted
+pub fn trash_dir() -> PathBuf {
+    data_root().join("trash")
+}
+
+/// Downloads shared between servers. Kept under the data directory so files can be
+/// hard-linked into server folders.
+pub fn cache_dir() -> PathBuf {
+    data_root().join("cache")
+}
+
+/// Copy a directory tree, creating `to` as needed
+pub fn copy_dir_all(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
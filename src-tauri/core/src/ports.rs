@@ -0,0 +1,161 @@
+// Host port allocation - which ports belong to which server
+
+use serde::Serialize;
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+lazy_static::lazy_static! {
+    /// Held while a port is picked and the server using it is saved, so two
+    /// concurrent creates can't be handed the same port
+    pub static ref ALLOCATION_LOCK: Mutex<()> = Mutex::new(());
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortAllocation {
+    pub port: u16,
+    pub server_id: String,
+    pub server_name: String,
+    pub description: Option<String>,
+}
+
+/// A port was requested that another server (or the same one, twice) already holds
+#[derive(Error, Debug, Clone)]
+#[error("Port {port} is already used by server '{server_name}'")]
+pub struct PortConflict {
+    pub port: u16,
+    pub server_id: String,
+    pub server_name: String,
+}
+
+/// What Docker says when a container can't publish a port something else holds: its own
+/// "already allocated", the proxy's bind error, and Docker Desktop's and Windows' wordings
+const BIND_FAILURES: &[&str] = &[
+    "port is already allocated",
+    "address already in use",
+    "ports are not available",
+    "only one usage of each socket address",
+];
+
+/// The host port a container failed to start on, from Docker's error message
+/// ("Bind for 0.0.0.0:25565 failed: port is already allocated"). None for other errors.
+pub fn bind_failure_port(message: &str) -> Option<u16> {
+    let lower = message.to_lowercase();
+    if !BIND_FAILURES.iter().any(|failure| lower.contains(failure)) {
+        return None;
+    }
+    // The first address:port - 0.0.0.0:25565, [::]:25565 or :::25565
+    message.match_indices(':').find_map(|(index, _)| {
+        let before = message[..index].chars().last()?;
+        if !(before.is_ascii_digit() || before == ':' || before == ']') {
+            return None;
+        }
+        let digits: String = message[index + 1..].chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok().filter(|port| *port != 0)
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct PortAllocator {
+    range_start: u16,
+    range_end: u16,
+    allocations: Vec<PortAllocation>,
+}
+
+impl PortAllocator {
+    pub fn new(range_start: u16, range_end: u16) -> Self {
+        Self {
+            range_start,
+            range_end,
+            allocations: Vec::new(),
+        }
+    }
+
+    pub fn allocations(&self) -> &[PortAllocation] {
+        &self.allocations
+    }
+
+    pub fn owner(&self, port: u16) -> Option<&PortAllocation> {
+        self.allocations.iter().find(|a| a.port == port)
+    }
+
+    /// Record a port as used by a server, refusing ports another server already holds
+    pub fn assign(
+        &mut self,
+        server_id: &str,
+        server_name: &str,
+        port: u16,
+        description: Option<String>,
+    ) -> Result<(), PortConflict> {
+        if let Some(owner) = self.owner(port) {
+            return Err(PortConflict {
+                port,
+                server_id: owner.server_id.clone(),
+                server_name: owner.server_name.clone(),
+            });
+        }
+        self.allocations.push(PortAllocation {
+            port,
+            server_id: server_id.to_string(),
+            server_name: server_name.to_string(),
+            description,
+        });
+        Ok(())
+    }
+
+    /// Lowest free port in range at or above `preferred`, wrapping around to the start
+    /// of the range. The same allocations always give the same answer.
+    pub fn next_free(&self, preferred: u16) -> Option<u16> {
+        let start = preferred.clamp(self.range_start, self.range_end);
+        (start..=self.range_end)
+            .chain(self.range_start..start)
+            .find(|port| self.owner(*port).is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_free_prefers_default_port() {
+        let mut allocator = PortAllocator::new(1024, 49151);
+        assert_eq!(allocator.next_free(25565), Some(25565));
+
+        allocator.assign("a", "A", 25565, None).unwrap();
+        allocator.assign("b", "B", 25566, None).unwrap();
+        assert_eq!(allocator.next_free(25565), Some(25567));
+    }
+
+    #[test]
+    fn test_next_free_wraps_within_range() {
+        let mut allocator = PortAllocator::new(30000, 30002);
+        allocator.assign("a", "A", 30001, None).unwrap();
+        allocator.assign("a", "A", 30002, None).unwrap();
+        assert_eq!(allocator.next_free(30001), Some(30000));
+        assert_eq!(allocator.next_free(7777), Some(30000));
+
+        allocator.assign("b", "B", 30000, None).unwrap();
+        assert_eq!(allocator.next_free(30000), None);
+    }
+
+    #[test]
+    fn test_bind_failure_port() {
+        let allocated = "driver failed programming external connectivity on endpoint serverwave-ab12cd34 (f00d): \
+                         Bind for 0.0.0.0:25565 failed: port is already allocated";
+        assert_eq!(bind_failure_port(allocated), Some(25565));
+        let desktop = "Ports are not available: exposing port UDP 0.0.0.0:7777 -> 0.0.0.0:0: listen udp 0.0.0.0:7777: \
+                       bind: address already in use";
+        assert_eq!(bind_failure_port(desktop), Some(7777));
+        assert_eq!(bind_failure_port("Bind for :::27015 failed: port is already allocated"), Some(27015));
+        assert_eq!(bind_failure_port("No such image: itzg/minecraft-server:latest"), None);
+    }
+
+    #[test]
+    fn test_assign_rejects_double_assignment() {
+        let mut allocator = PortAllocator::new(1024, 49151);
+        allocator.assign("a", "Survival", 27015, None).unwrap();
+        let err = allocator.assign("b", "Creative", 27015, None).unwrap_err();
+        assert_eq!(err.server_id, "a");
+        assert!(err.to_string().contains("Survival"));
+    }
+}
@@ -0,0 +1,254 @@
+// RCON - the Source remote console protocol, which Minecraft speaks too. A command goes out
+// as one packet; its reply can span several, so an empty packet is sent after it and the
+// reply ends where the server answers that one.
+
+use crate::games::GameConfig;
+use crate::server::{lifecycle, Server};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const AUTH: i32 = 3;
+const AUTH_RESPONSE: i32 = 2;
+const EXEC_COMMAND: i32 = 2;
+const RESPONSE_VALUE: i32 = 0;
+/// The largest packet servers accept, size field excluded
+const MAX_PACKET: usize = 4096;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long the rest of a reply may take once part of it came - for servers that never
+/// answer the empty packet
+const TRAILER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where a game takes RCON: the variables holding its port and password
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RconSettings {
+    pub port_var: String,
+    pub password_var: String,
+}
+
+#[derive(Debug, Error)]
+pub enum RconError {
+    #[error("RCON connection failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("RCON password was rejected")]
+    AuthFailed,
+    #[error("RCON server didn't answer in time")]
+    Timeout,
+    #[error("RCON: {0}")]
+    Protocol(String),
+}
+
+#[derive(Debug, PartialEq)]
+struct Packet {
+    id: i32,
+    kind: i32,
+    body: String,
+}
+
+fn encode(id: i32, kind: i32, body: &str) -> Vec<u8> {
+    let size = (4 + 4 + body.len() + 2) as i32;
+    let mut bytes = Vec::with_capacity(size as usize + 4);
+    bytes.extend_from_slice(&size.to_le_bytes());
+    bytes.extend_from_slice(&id.to_le_bytes());
+    bytes.extend_from_slice(&kind.to_le_bytes());
+    bytes.extend_from_slice(body.as_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+    bytes
+}
+
+async fn read_packet(reader: &mut (impl AsyncRead + Unpin)) -> Result<Packet, RconError> {
+    let size = reader.read_i32_le().await?;
+    if !(10..=MAX_PACKET as i32 + 10).contains(&size) {
+        return Err(RconError::Protocol(format!("bad packet size {}", size)));
+    }
+    let mut rest = vec![0; size as usize];
+    reader.read_exact(&mut rest).await?;
+    let id = i32::from_le_bytes(rest[0..4].try_into().unwrap());
+    let kind = i32::from_le_bytes(rest[4..8].try_into().unwrap());
+    let body = &rest[8..];
+    let body = body.strip_suffix(&[0, 0]).or_else(|| body.strip_suffix(&[0])).unwrap_or(body);
+    Ok(Packet { id, kind, body: String::from_utf8_lossy(body).into_owned() })
+}
+
+/// A logged-in RCON connection
+pub struct RconClient<S = TcpStream> {
+    stream: S,
+    next_id: i32,
+}
+
+impl RconClient<TcpStream> {
+    pub async fn connect(host: &str, port: u16, password: &str) -> Result<Self, RconError> {
+        let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host, port)))
+            .await
+            .map_err(|_| RconError::Timeout)??;
+        RconClient::login(stream, password).await
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> RconClient<S> {
+    async fn login(stream: S, password: &str) -> Result<Self, RconError> {
+        let mut client = RconClient { stream, next_id: 1 };
+        let id = client.send(AUTH, password).await?;
+        // Source servers send an empty response value before the auth response
+        loop {
+            let packet = client.read(REPLY_TIMEOUT).await?;
+            if packet.kind != AUTH_RESPONSE {
+                continue;
+            }
+            if packet.id == -1 {
+                return Err(RconError::AuthFailed);
+            }
+            if packet.id == id {
+                return Ok(client);
+            }
+        }
+    }
+
+    /// Run a command and return the server's reply
+    pub async fn exec(&mut self, command: &str) -> Result<String, RconError> {
+        if command.len() > MAX_PACKET - 10 {
+            return Err(RconError::Protocol("command too long".to_string()));
+        }
+        let id = self.send(EXEC_COMMAND, command).await?;
+        let end = self.send(RESPONSE_VALUE, "").await?;
+        let mut reply = String::new();
+        let mut timeout = REPLY_TIMEOUT;
+        loop {
+            let packet = match self.read(timeout).await {
+                Err(RconError::Timeout) if timeout == TRAILER_TIMEOUT => return Ok(reply),
+                result => result?,
+            };
+            if packet.id == end {
+                return Ok(reply);
+            }
+            if packet.id == id {
+                reply.push_str(&packet.body);
+                timeout = TRAILER_TIMEOUT;
+            }
+        }
+    }
+
+    async fn send(&mut self, kind: i32, body: &str) -> Result<i32, RconError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.stream.write_all(&encode(id, kind, body)).await?;
+        Ok(id)
+    }
+
+    async fn read(&mut self, timeout: Duration) -> Result<Packet, RconError> {
+        tokio::time::timeout(timeout, read_packet(&mut self.stream))
+            .await
+            .map_err(|_| RconError::Timeout)?
+    }
+}
+
+/// The RCON port and password a server's container gets, None when the game has no RCON or
+/// the server wasn't given a port for it
+pub fn server_settings(server: &Server, game: &GameConfig) -> Option<(u16, String)> {
+    let settings = game.rcon.as_ref()?;
+    let extra_ports = lifecycle::extra_ports(server, Some(game));
+    let env = lifecycle::container_env(game, server.memory_mb, server.port, &server.config, &server.env, &extra_ports);
+    let port = env.get(&settings.port_var)?.parse().ok()?;
+    Some((port, env.get(&settings.password_var).cloned().unwrap_or_default()))
+}
+
+/// Give a server a password of its own when its game's RCON password defaults to blank, so
+/// RCON is never open without one. Returns whether `config` changed.
+pub fn ensure_password(config: &mut HashMap<String, String>, game: &GameConfig) -> bool {
+    let Some(settings) = &game.rcon else { return false };
+    let blank_default = game
+        .variables
+        .iter()
+        .any(|v| v.env == settings.password_var && v.default.is_empty());
+    if !blank_default || config.get(&settings.password_var).is_some_and(|p| !p.is_empty()) {
+        return false;
+    }
+    config.insert(settings.password_var.clone(), uuid::Uuid::new_v4().simple().to_string());
+    true
+}
+
+/// Log in to a server's RCON on this machine. Nothing has reached the game's console when
+/// this fails.
+pub async fn connect(server: &Server, game: &GameConfig) -> Result<RconClient, RconError> {
+    let (port, password) = server_settings(server, game)
+        .ok_or_else(|| RconError::Protocol(format!("{} has no RCON port", server.name)))?;
+    RconClient::connect("127.0.0.1", port, &password).await
+}
+
+/// Log in to a server's RCON on this machine and run one command
+pub async fn send(server: &Server, game: &GameConfig, command: &str) -> Result<String, RconError> {
+    connect(server, game).await?.exec(command).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let bytes = encode(7, EXEC_COMMAND, "list");
+        assert_eq!(&bytes[0..4], &14i32.to_le_bytes());
+        assert_eq!(&bytes[4..8], &7i32.to_le_bytes());
+        assert_eq!(&bytes[12..], b"list\0\0");
+    }
+
+    #[tokio::test]
+    async fn test_login_and_split_reply() {
+        let (client_end, mut server_end) = tokio::io::duplex(MAX_PACKET * 2);
+        let server = tokio::spawn(async move {
+            let auth = read_packet(&mut server_end).await.unwrap();
+            assert_eq!((auth.kind, auth.body.as_str()), (AUTH, "secret"));
+            server_end.write_all(&encode(auth.id, RESPONSE_VALUE, "")).await.unwrap();
+            server_end.write_all(&encode(auth.id, AUTH_RESPONSE, "")).await.unwrap();
+
+            let command = read_packet(&mut server_end).await.unwrap();
+            let end = read_packet(&mut server_end).await.unwrap();
+            assert_eq!(command.body, "list");
+            server_end.write_all(&encode(command.id, RESPONSE_VALUE, "There are 2 ")).await.unwrap();
+            server_end.write_all(&encode(command.id, RESPONSE_VALUE, "players online")).await.unwrap();
+            server_end.write_all(&encode(end.id, RESPONSE_VALUE, "")).await.unwrap();
+
+            assert!(read_packet(&mut server_end).await.is_err(), "client hung up");
+        });
+
+        let mut client = RconClient::login(client_end, "secret").await.unwrap();
+        assert_eq!(client.exec("list").await.unwrap(), "There are 2 players online");
+        drop(client);
+        server.await.unwrap();
+    }
+
+    #[test]
+    fn test_ensure_password_only_fills_blank_defaults() {
+        let mut game: GameConfig = serde_json::from_value(serde_json::json!({
+            "game_type": "mc", "name": "MC", "description": "", "docker_image": "img", "startup": "",
+            "stop_command": "stop", "ports": [], "volume_path": "/data", "min_ram_mb": 1024,
+            "recommended_ram_mb": 2048, "icon": "", "is_custom": true, "console": true,
+            "variables": [{"env": "RCON_PASSWORD", "name": "RCON Password", "description": "", "default": ""}],
+            "rcon": {"port_var": "RCON_PORT", "password_var": "RCON_PASSWORD"}
+        }))
+        .unwrap();
+        let mut config = HashMap::new();
+        assert!(ensure_password(&mut config, &game));
+        let generated = config["RCON_PASSWORD"].clone();
+        assert_eq!(generated.len(), 32);
+        assert!(!ensure_password(&mut config, &game), "kept once set");
+        assert_eq!(config["RCON_PASSWORD"], generated);
+
+        game.variables[0].default = "ChangeMe".to_string();
+        assert!(!ensure_password(&mut HashMap::new(), &game));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_password() {
+        let (client_end, mut server_end) = tokio::io::duplex(MAX_PACKET);
+        tokio::spawn(async move {
+            read_packet(&mut server_end).await.unwrap();
+            server_end.write_all(&encode(-1, AUTH_RESPONSE, "")).await.unwrap();
+        });
+        assert!(matches!(RconClient::login(client_end, "nope").await, Err(RconError::AuthFailed)));
+    }
+}
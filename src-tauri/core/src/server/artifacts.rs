@@ -0,0 +1,156 @@
+// Install artifacts - the files a server's startup line runs: the jar after -jar, the program
+// it starts with and the argument files it reads. Checked after an install, so one the
+// install didn't leave behind is reported by name instead of as bash's "No such file or
+// directory" on the first start.
+
+use super::lifecycle::{container_env, extra_ports, resolve_startup_command};
+use super::model::Server;
+use crate::games::GameConfig;
+use std::path::Path;
+
+/// Programs that run the file named after them
+const LAUNCHERS: &[&str] = &["wine", "wine64", "box64", "exec"];
+
+/// The files in the data folder a startup line needs, relative to it. Lines that change
+/// folder or still hold variables are only checked as far as they can be read.
+pub fn expected(startup: &str, volume_path: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    for command in startup.split([';', '|', '&']) {
+        let words = split_words(command);
+        let mut words = words.iter().map(String::as_str).peekable();
+        match words.peek() {
+            Some(&"cd") | Some(&"pushd") => return files,
+            Some(&"export") | Some(&"echo") | None => continue,
+            _ => {}
+        }
+        let mut program = words.next().unwrap_or_default();
+        if LAUNCHERS.contains(&program) {
+            program = words.next().unwrap_or_default();
+        }
+        if program == "java" {
+            while let Some(word) = words.next() {
+                if word == "-jar" {
+                    files.extend(words.next().and_then(|jar| data_file(jar, volume_path)));
+                } else if let Some(args_file) = word.strip_prefix('@') {
+                    files.extend(data_file(args_file, volume_path));
+                }
+            }
+        } else if program.contains('/') {
+            files.extend(data_file(program, volume_path));
+        }
+        // Arguments read from a file - $(cat unix_args.txt)
+        if let Some((_, rest)) = command.split_once("$(cat ") {
+            let args_file = rest.split(')').next().unwrap_or_default().trim();
+            files.extend(data_file(args_file, volume_path));
+        }
+    }
+    files.dedup();
+    files
+}
+
+/// The startup files a server's data folder is missing
+pub fn missing(server: &Server, game: &GameConfig) -> Vec<String> {
+    let env = container_env(
+        game,
+        server.memory_mb,
+        server.port,
+        &server.config,
+        &server.env,
+        &extra_ports(server, Some(game)),
+    );
+    let Some(startup) = resolve_startup_command(game, &env) else { return Vec::new() };
+    expected(&startup, &game.volume_path)
+        .into_iter()
+        .filter(|file| !exists(&server.data_path, file))
+        .collect()
+}
+
+/// Words as bash splits them, without their quotes
+fn split_words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote = None;
+    for c in command.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, _) if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            _ => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// A path in the container as one relative to the data folder, None when it's elsewhere or
+/// not known until bash runs
+fn data_file(path: &str, volume_path: &str) -> Option<String> {
+    if path.is_empty() || path.contains("{{") || path.contains('$') || path.contains('`') {
+        return None;
+    }
+    let relative = match path.strip_prefix(volume_path.trim_end_matches('/')) {
+        Some(rest) => rest.strip_prefix('/')?,
+        None if path.starts_with('/') => return None,
+        None => path.trim_start_matches("./"),
+    };
+    Some(relative.to_string()).filter(|r| !r.is_empty())
+}
+
+/// Whether a file exists, with a `*` in its name matching anything
+fn exists(data_path: &Path, file: &str) -> bool {
+    let path = data_path.join(file);
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let Some((prefix, suffix)) = name.split_once('*') else { return path.exists() };
+    let Some(Ok(entries)) = path.parent().map(std::fs::read_dir) else { return false };
+    entries.flatten().any(|entry| {
+        let candidate = entry.file_name().to_string_lossy().to_string();
+        candidate.len() >= prefix.len() + suffix.len() && candidate.starts_with(prefix) && candidate.ends_with(suffix)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_startup_files() {
+        let volume = "/home/container";
+        assert_eq!(expected("java -Xms128M -Xmx2048M -jar server.jar nogui", volume), ["server.jar"]);
+        assert_eq!(expected("java -Xmx4G $(cat unix_args.txt) nogui", volume), ["unix_args.txt"]);
+        assert_eq!(expected("java @user_jvm_args.txt @libraries/forge/unix_args.txt", volume), [
+            "user_jvm_args.txt",
+            "libraries/forge/unix_args.txt"
+        ]);
+        assert_eq!(expected("wine ./SonsOfTheForestDS.exe -userdatapath \"/home/container/serverconfig\"", volume), [
+            "SonsOfTheForestDS.exe"
+        ]);
+        assert_eq!(
+            expected("/home/container/Pal/Binaries/Linux/PalServer-Linux-Shipping Pal -port=8211", volume),
+            ["Pal/Binaries/Linux/PalServer-Linux-Shipping"]
+        );
+        assert_eq!(
+            expected("export PATH=\"./jre64/bin:$PATH\" ; ./ProjectZomboid64 -port 16261", volume),
+            ["ProjectZomboid64"]
+        );
+        assert!(expected("cd server && ./start.sh", volume).is_empty());
+        assert!(expected("bash start.sh", volume).is_empty());
+    }
+
+    #[test]
+    fn test_glob_in_file_name() {
+        let dir = std::env::temp_dir().join(format!("serverwave-artifacts-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("Engine/Binaries/Linux")).unwrap();
+        std::fs::write(dir.join("Engine/Binaries/Linux/FactoryServer-Linux-Shipping"), "").unwrap();
+
+        assert!(exists(&dir, "Engine/Binaries/Linux/*-Linux-Shipping"));
+        assert!(!exists(&dir, "Engine/Binaries/Linux/*-Win64-Shipping"));
+        assert!(!exists(&dir, "FactoryServer.sh"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
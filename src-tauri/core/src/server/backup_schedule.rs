@@ -0,0 +1,109 @@
+// Backup schedule - when a server is backed up automatically, and how many of its backups
+// are kept. Saved with the server so it survives restarts.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Shortest time between interval backups
+const MIN_INTERVAL_HOURS: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum BackupTiming {
+    /// Every this many hours after the last backup
+    Interval { hours: u32 },
+    /// At a local time of day on these days, every day when empty
+    Daily {
+        #[serde(default)]
+        days: Vec<Weekday>,
+        hour: u32,
+        minute: u32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupSchedule {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub timing: BackupTiming,
+    /// Backups kept, the retention count from Settings when None
+    #[serde(default)]
+    pub keep_count: Option<u32>,
+    /// Oldest backups are removed past this many MB in total - the newest always stays
+    #[serde(default)]
+    pub max_total_mb: Option<u64>,
+    #[serde(default)]
+    pub last_run: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl BackupSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.timing {
+            BackupTiming::Interval { hours } if *hours < MIN_INTERVAL_HOURS => {
+                Err(format!("Back up at most every {} hour", MIN_INTERVAL_HOURS))
+            }
+            BackupTiming::Daily { hour, minute, .. } if *hour > 23 || *minute > 59 => {
+                Err(format!("{:02}:{:02} isn't a valid time", hour, minute))
+            }
+            _ if self.keep_count == Some(0) => Err("Keep at least one backup".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// The next backup after `now`, in its timezone. An interval counts from the last backup,
+    /// so one the app wasn't running for is due straight away.
+    pub fn next_from<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = now.timezone();
+        match &self.timing {
+            BackupTiming::Interval { hours } => {
+                let from = self.last_run.map(|t| t.with_timezone(&tz)).unwrap_or_else(|| now.clone());
+                Some(from + Duration::hours((*hours).max(MIN_INTERVAL_HOURS).into()))
+            }
+            BackupTiming::Daily { days, hour, minute } => next_daily(now, days, *hour, *minute),
+        }
+    }
+}
+
+/// The first `hour`:`minute` after `now` on one of `days`, any day when empty
+pub(super) fn next_daily<Tz: TimeZone>(now: &DateTime<Tz>, days: &[Weekday], hour: u32, minute: u32) -> Option<DateTime<Tz>> {
+    let tz = now.timezone();
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+    (0..=7)
+        .filter_map(|offset| now.date_naive().checked_add_signed(Duration::days(offset)))
+        .filter(|date| days.is_empty() || days.contains(&date.weekday()))
+        .filter_map(|date| tz.from_local_datetime(&date.and_time(time)).earliest())
+        .find(|at| at > now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(timing: BackupTiming) -> BackupSchedule {
+        BackupSchedule { enabled: true, timing, keep_count: None, max_total_mb: None, last_run: None, next_run: None }
+    }
+
+    #[test]
+    fn test_next_backup() {
+        // Friday October 16th 2026
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+
+        let mut every_six = schedule(BackupTiming::Interval { hours: 6 });
+        assert_eq!(every_six.next_from(&now), Some(Utc.with_ymd_and_hms(2026, 10, 16, 18, 0, 0).unwrap()));
+        every_six.last_run = Some(Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap());
+        assert!(every_six.next_from(&now).unwrap() < now);
+
+        let nightly = schedule(BackupTiming::Daily { days: vec![], hour: 4, minute: 30 });
+        assert_eq!(nightly.next_from(&now), Some(Utc.with_ymd_and_hms(2026, 10, 17, 4, 30, 0).unwrap()));
+
+        let weekends = schedule(BackupTiming::Daily { days: vec![Weekday::Sun], hour: 12, minute: 0 });
+        assert_eq!(weekends.next_from(&now), Some(Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()));
+        assert!(schedule(BackupTiming::Interval { hours: 0 }).validate().is_err());
+    }
+}
@@ -0,0 +1,66 @@
+// Backup targets - storage off this computer a server's backups are copied to. The password
+// or secret key isn't saved here but in the app's secret storage.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum BackupStorage {
+    /// Amazon S3 or a service with the same API - Backblaze B2, Cloudflare R2, MinIO, ...
+    S3 {
+        /// https://s3.eu-central-1.amazonaws.com, https://s3.us-west-004.backblazeb2.com, ...
+        endpoint: String,
+        region: String,
+        bucket: String,
+        access_key_id: String,
+        /// Address the bucket as endpoint/bucket rather than bucket.endpoint, for MinIO and
+        /// other self-hosted stores
+        #[serde(default)]
+        path_style: bool,
+    },
+    /// A WebDAV folder - Nextcloud, ownCloud, most NAS
+    WebDav { url: String, username: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupTarget {
+    pub id: String,
+    pub name: String,
+    pub storage: BackupStorage,
+    /// Folder in the bucket or share the backups go under, in a folder per server
+    #[serde(default)]
+    pub prefix: String,
+    /// Copy every new backup of the server here once it's made
+    #[serde(default)]
+    pub upload_new: bool,
+}
+
+impl BackupTarget {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Give the target a name".to_string());
+        }
+        let url = match &self.storage {
+            BackupStorage::S3 { endpoint, bucket, access_key_id, .. } => {
+                if bucket.trim().is_empty() || access_key_id.trim().is_empty() {
+                    return Err("A bucket and an access key ID are needed".to_string());
+                }
+                endpoint
+            }
+            BackupStorage::WebDav { url, .. } => url,
+        };
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(format!("{} isn't an http(s) address", url));
+        }
+        Ok(())
+    }
+
+    /// Where a server's backups go, without a leading or trailing slash
+    pub fn folder(&self, server_id: &str) -> String {
+        let prefix = self.prefix.trim_matches('/');
+        match prefix.is_empty() {
+            true => server_id.to_string(),
+            false => format!("{}/{}", prefix, server_id),
+        }
+    }
+}
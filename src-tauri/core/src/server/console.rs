@@ -0,0 +1,129 @@
+// Console bridges - the ways a command can reach a game's console. Most games read the
+// container's stdin, but some images run the game in a screen or tmux session, or ship a
+// helper like mc-send-to-console or rcon-cli. A running container is probed once for what
+// it has, and the result is kept on the server.
+
+use crate::docker::DockerManager;
+use serde::{Deserialize, Serialize};
+
+/// Prints a line per bridge found. Images without a shell fail it, leaving stdin.
+const PROBE_SCRIPT: &str = r#"
+command -v mc-send-to-console >/dev/null 2>&1 && echo mc-send-to-console
+command -v rcon-cli >/dev/null 2>&1 && echo rcon-cli
+command -v screen >/dev/null 2>&1 && screen -ls 2>/dev/null | awk '$1 ~ /^[0-9]+\./ { print "screen " $1 }'
+command -v tmux >/dev/null 2>&1 && tmux list-sessions -F 'tmux #{session_name}' 2>/dev/null
+true
+"#;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConsoleBridge {
+    /// A screen session the game runs in, sent keystrokes with `screen -X stuff`
+    Screen { session: String },
+    Tmux { session: String },
+    /// The container's stdin, read by its main process
+    Stdin,
+    /// The Minecraft images' helper, which writes to the server's console pipe
+    McSendToConsole,
+    RconCli,
+}
+
+impl std::fmt::Display for ConsoleBridge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsoleBridge::Screen { session } => write!(f, "screen session {}", session),
+            ConsoleBridge::Tmux { session } => write!(f, "tmux session {}", session),
+            ConsoleBridge::Stdin => write!(f, "stdin"),
+            ConsoleBridge::McSendToConsole => write!(f, "mc-send-to-console"),
+            ConsoleBridge::RconCli => write!(f, "rcon-cli"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsoleProbe {
+    /// The container probed - a new one is probed again
+    pub container_id: String,
+    /// Tried in this order until one takes the command
+    pub bridges: Vec<ConsoleBridge>,
+}
+
+/// The bridges in the probe script's output, best first: a session the game runs in, then
+/// stdin when the container keeps it open, then the helpers
+pub fn parse_probe(lines: &[String], stdin_open: bool) -> Vec<ConsoleBridge> {
+    let mut sessions = Vec::new();
+    let mut helpers = Vec::new();
+    for line in lines {
+        match line.trim().split_once(' ') {
+            Some(("screen", session)) => sessions.push(ConsoleBridge::Screen { session: session.to_string() }),
+            Some(("tmux", session)) => sessions.push(ConsoleBridge::Tmux { session: session.to_string() }),
+            _ => match line.trim() {
+                "mc-send-to-console" => helpers.push(ConsoleBridge::McSendToConsole),
+                "rcon-cli" => helpers.push(ConsoleBridge::RconCli),
+                _ => {}
+            },
+        }
+    }
+    if stdin_open {
+        sessions.push(ConsoleBridge::Stdin);
+    }
+    sessions.extend(helpers);
+    sessions
+}
+
+/// Find the bridges a running container has
+pub async fn probe(docker: &DockerManager, container_id: &str) -> ConsoleProbe {
+    let mut lines = Vec::new();
+    if let Err(e) = docker.exec_command(container_id, vec!["sh", "-c", PROBE_SCRIPT], None, |line| lines.push(line)).await {
+        tracing::debug!("Couldn't probe {} for a console: {}", container_id, e);
+    }
+    let stdin_open = docker.container_stdin_open(container_id).await;
+    ConsoleProbe { container_id: container_id.to_string(), bridges: parse_probe(&lines, stdin_open) }
+}
+
+/// Send a command through one bridge, returning what it printed
+pub async fn send(docker: &DockerManager, container_id: &str, bridge: &ConsoleBridge, command: &str) -> Result<String, String> {
+    let keys = format!("{}\r", command);
+    let cmd = match bridge {
+        ConsoleBridge::Stdin => {
+            docker.send_stdin(container_id, command).await.map_err(|e| e.to_string())?;
+            return Ok("Command sent".to_string());
+        }
+        ConsoleBridge::Screen { session } => vec!["screen", "-S", session, "-p", "0", "-X", "stuff", &keys],
+        ConsoleBridge::Tmux { session } => vec!["tmux", "send-keys", "-t", session, command, "Enter"],
+        ConsoleBridge::McSendToConsole => vec!["mc-send-to-console", command],
+        ConsoleBridge::RconCli => vec!["rcon-cli", command],
+    };
+    let mut output = Vec::new();
+    let exit_code = docker
+        .exec_command(container_id, cmd, None, |line| output.push(line))
+        .await
+        .map_err(|e| e.to_string())?;
+    match exit_code {
+        0 => Ok(output.join("\n")),
+        code if output.is_empty() => Err(format!("exited with code {}", code)),
+        _ => Err(output.join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_probe_orders_sessions_first() {
+        let lines = ["rcon-cli", "screen 412.console", "mc-send-to-console", "tmux game", "garbage"]
+            .map(String::from);
+        assert_eq!(
+            parse_probe(&lines, true),
+            vec![
+                ConsoleBridge::Screen { session: "412.console".into() },
+                ConsoleBridge::Tmux { session: "game".into() },
+                ConsoleBridge::Stdin,
+                ConsoleBridge::RconCli,
+                ConsoleBridge::McSendToConsole,
+            ]
+        );
+        assert!(parse_probe(&[], false).is_empty());
+    }
+}
@@ -0,0 +1,550 @@
+// Server lifecycle - container create/start/stop/remove shared by every frontend
+
+use super::model::{Server, ServerStatus};
+use super::store::StoreError;
+use crate::docker::{
+    container_binds, container_command, published_ports, ContainerSpec, DockerError, DockerManager, PublishedPort,
+};
+use crate::games::{build_env_vars, EnvVar, GameConfig, PortConfig, PortProtocol, StopMethod, DEFAULT_START_TIMEOUT_SECS};
+use bollard::models::HealthStatusEnum;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Seconds a server gets to exit after a fallback SIGTERM or the final SIGKILL, and to stop
+/// at all when there's no game to say how
+const STOP_GRACE_SECS: u32 = 10;
+/// Without a health check or a TCP port to knock on, staying up this long counts as started
+const START_SETTLE_SECS: u64 = 5;
+const READY_POLL: Duration = Duration::from_secs(1);
+
+#[derive(Error, Debug)]
+pub enum LifecycleError {
+    #[error(transparent)]
+    Docker(#[from] DockerError),
+
+    #[error(transparent)]
+    Store(#[from] StoreError),
+
+    #[error("No container ID")]
+    NoContainer,
+
+    #[error("Container failed to start")]
+    FailedToStart,
+
+    #[error("The new container isn't on network {0}")]
+    NetworkLost(String),
+}
+
+impl From<LifecycleError> for String {
+    fn from(e: LifecycleError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Extra ports (query, RCON, ...) a server publishes besides its game port
+pub fn extra_ports(server: &Server, game: Option<&GameConfig>) -> Vec<PortConfig> {
+    if !server.extra_ports.is_empty() {
+        return server.extra_ports.clone();
+    }
+    game.map(|g| g.ports.iter().skip(1).cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Container environment: the game's variables, the server's own ones and any ports
+/// allocated to variables
+pub fn container_env(
+    game: &GameConfig,
+    memory_mb: u32,
+    port: u16,
+    user_config: &HashMap<String, String>,
+    custom_env: &[EnvVar],
+    extra_ports: &[PortConfig],
+) -> HashMap<String, String> {
+    let mut env = build_env_vars(game, memory_mb, port, user_config, custom_env);
+    for extra in extra_ports {
+        if let Some(var) = extra.env_var.as_ref().filter(|v| !v.is_empty()) {
+            env.insert(var.clone(), extra.container_port.to_string());
+        }
+    }
+    env
+}
+
+/// Why a server's own environment variables can't be used: bad or repeated names, or
+/// names the game or its ports already set
+pub fn validate_custom_env(game: &GameConfig, extra_ports: &[PortConfig], env: &[EnvVar]) -> Result<(), String> {
+    for (index, var) in env.iter().enumerate() {
+        EnvVar::validate_key(&var.key)?;
+        if env[..index].iter().any(|other| other.key == var.key) {
+            return Err(format!("{} is set twice", var.key));
+        }
+        if game.variables.iter().any(|v| v.env == var.key) {
+            return Err(format!("{} is one of {}'s settings - change it there", var.key, game.name));
+        }
+        if extra_ports.iter().any(|p| p.env_var.as_deref() == Some(var.key.as_str())) {
+            return Err(format!("{} holds one of the server's ports", var.key));
+        }
+    }
+    Ok(())
+}
+
+/// The game's startup command with `{{VAR}}` placeholders filled from the environment
+pub fn resolve_startup_command(game: &GameConfig, env: &HashMap<String, String>) -> Option<String> {
+    if game.startup.is_empty() {
+        return None;
+    }
+    let mut startup = game.startup.clone();
+    for (key, value) in env {
+        startup = startup.replace(&format!("{{{{{}}}}}", key), value);
+    }
+    Some(startup)
+}
+
+/// The image a server's container runs - its own override or the game's
+pub fn image<'a>(server: &'a Server, game: &'a GameConfig) -> &'a str {
+    server.docker_image.as_deref().unwrap_or(&game.docker_image)
+}
+
+/// What a server's container gets when it's created from the current config
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupPreview {
+    pub image: String,
+    /// The startup line with its variables filled in
+    pub startup: Option<String>,
+    /// The command the container runs
+    pub command: Option<Vec<String>>,
+    pub env: BTreeMap<String, String>,
+    pub ports: Vec<PublishedPort>,
+    pub binds: Vec<String>,
+    pub memory_mb: u32,
+    /// The existing container was made from older settings and is recreated on the next start
+    pub container_outdated: bool,
+    /// Placeholders left unfilled and values that break the shell quoting
+    pub warnings: Vec<String>,
+}
+
+/// Everything create_container would give the server's container, without creating it
+pub fn preview(server: &Server, game: &GameConfig) -> StartupPreview {
+    let extra_ports = extra_ports(server, Some(game));
+    let env = container_env(game, server.memory_mb, server.port, &server.config, &server.env, &extra_ports);
+    let startup = resolve_startup_command(game, &env);
+
+    let mut warnings = startup_warnings(&game.startup, &env);
+    if let Some(startup) = &startup {
+        for name in placeholders(startup) {
+            warnings.push(format!("{{{{{}}}}} isn't set by any variable and is passed as it is", name));
+        }
+    }
+
+    StartupPreview {
+        image: image(server, game).to_string(),
+        command: startup.as_deref().and_then(|s| container_command(&game.volume_path, s)),
+        startup,
+        env: env.into_iter().collect(),
+        ports: published_ports(server.port, &extra_ports),
+        binds: container_binds(&server.data_path, &game.volume_path),
+        memory_mb: server.memory_mb,
+        container_outdated: server.config_outdated,
+        warnings,
+    }
+}
+
+/// The `{{VAR}}` placeholders in a startup line, with the quote they're inside of
+fn placeholders_quoted(startup: &str) -> Vec<(String, Option<char>)> {
+    let mut found = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut rest = startup;
+    while let Some(c) = rest.chars().next() {
+        if !escaped && quote != Some('\'') && rest.starts_with("{{") {
+            if let Some(end) = rest.find("}}") {
+                found.push((rest[2..end].to_string(), quote));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && quote != Some('\'') {
+            escaped = true;
+        } else if c == '"' || c == '\'' {
+            match quote {
+                None => quote = Some(c),
+                Some(q) if q == c => quote = None,
+                Some(_) => {}
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    found
+}
+
+fn placeholders(startup: &str) -> Vec<String> {
+    placeholders_quoted(startup).into_iter().map(|(name, _)| name).collect()
+}
+
+/// Values that won't reach the game as one argument once bash reads the startup line
+fn startup_warnings(template: &str, env: &HashMap<String, String>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (name, quote) in placeholders_quoted(template) {
+        let Some(value) = env.get(&name) else { continue };
+        let problem = match quote {
+            None if value.is_empty() => Some("is empty, so the argument disappears - quote it"),
+            None if value.chars().any(|c| c.is_whitespace() || "\"'`$\\;&|<>()*?".contains(c)) => {
+                Some("has spaces or shell characters but isn't quoted")
+            }
+            Some('"') if value.chars().any(|c| "\"`$\\".contains(c)) => {
+                Some("has \", `, $ or \\, which bash reads inside double quotes")
+            }
+            Some('\'') if value.contains('\'') => Some("has a ' inside single quotes"),
+            _ => None,
+        };
+        if let Some(problem) = problem {
+            warnings.push(format!("{} ({:?}) {}", name, value, problem));
+        }
+    }
+    warnings
+}
+
+/// Create (but don't start) the game container for a server
+pub async fn create_container(
+    docker: &DockerManager,
+    server: &Server,
+    game: &GameConfig,
+) -> Result<String, DockerError> {
+    create_container_with(docker, server, game, &HashMap::new()).await
+}
+
+/// `create_container` with variables that aren't saved with the server, like secrets
+pub async fn create_container_with(
+    docker: &DockerManager,
+    server: &Server,
+    game: &GameConfig,
+    secret_env: &HashMap<String, String>,
+) -> Result<String, DockerError> {
+    let extra_ports = extra_ports(server, Some(game));
+    let mut env = container_env(game, server.memory_mb, server.port, &server.config, &server.env, &extra_ports);
+    env.extend(secret_env.iter().map(|(key, value)| (key.clone(), value.clone())));
+    let startup_command = resolve_startup_command(game, &env);
+
+    docker
+        .create_container(&ContainerSpec {
+            name: &server.id,
+            image: image(server, game),
+            port: server.port,
+            data_path: &server.data_path,
+            env: &env,
+            extra_ports: &extra_ports,
+            volume_path: Some(&game.volume_path),
+            memory_mb: Some(server.memory_mb),
+            startup_command: startup_command.as_deref(),
+        })
+        .await
+}
+
+/// Replace the server's container with one built from its current config, plus `secret_env`.
+/// The data folder is mounted again, so nothing in it is lost, and the new container joins
+/// the server's shared network again. The caller saves the new container id.
+pub async fn recreate_container(
+    docker: &DockerManager,
+    server: &mut Server,
+    game: &GameConfig,
+    secret_env: &HashMap<String, String>,
+) -> Result<(), LifecycleError> {
+    // Already gone is fine - there's nothing to replace then
+    if let Some(container_id) = server.container_id.take() {
+        docker.remove_container(&container_id).await.ok();
+    }
+    let container_id = create_container_with(docker, server, game, secret_env).await?;
+    server.container_id = Some(container_id.clone());
+    // The proxy reaches its backends on this network - nothing else puts them back on it
+    if let Some(network) = &server.network {
+        docker.connect_to_network(network, &container_id).await?;
+        if !docker.container_networks(&container_id).await.contains(network) {
+            return Err(LifecycleError::NetworkLost(network.clone()));
+        }
+    }
+    server.config_outdated = false;
+    Ok(())
+}
+
+/// Start the container. The server is Starting until `wait_until_ready` sees it come up;
+/// the caller saves the status.
+pub async fn start(docker: &DockerManager, server: &mut Server) -> Result<(), LifecycleError> {
+    let container_id = server.container_id.clone().ok_or(LifecycleError::NoContainer)?;
+
+    docker.start_container(&container_id).await?;
+
+    let status = docker.get_container_status(&container_id).await?;
+    if status == ServerStatus::Stopped || status == ServerStatus::Error {
+        return Err(LifecycleError::FailedToStart);
+    }
+
+    server.status = ServerStatus::Starting;
+    Ok(())
+}
+
+/// How a start went, given the game's start timeout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Readiness {
+    /// Answered on its port, passed its image's health check, or stayed up
+    Ready,
+    /// Still running, but not answering by the timeout
+    TimedOut,
+    /// Exited while starting, with its exit code
+    Exited(i64),
+}
+
+/// Wait for a started server to be ready: its image's health check passing when it has one,
+/// else its port taking connections when the game speaks TCP, else staying up for a moment
+pub async fn wait_until_ready(docker: &DockerManager, server: &Server, game: Option<&GameConfig>) -> Readiness {
+    let Some(container_id) = &server.container_id else {
+        return Readiness::Exited(0);
+    };
+    let timeout = game.map(|g| g.start_timeout_secs).unwrap_or(DEFAULT_START_TIMEOUT_SECS);
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout.into());
+    let settled = tokio::time::Instant::now() + Duration::from_secs(START_SETTLE_SECS.min(timeout.into()));
+    let tcp = game.and_then(|g| g.ports.first()).is_some_and(|p| p.protocol != PortProtocol::Udp);
+
+    loop {
+        let state = docker
+            .client()
+            .inspect_container(container_id, None)
+            .await
+            .ok()
+            .and_then(|info| info.state)
+            .unwrap_or_default();
+        if state.running != Some(true) {
+            return Readiness::Exited(state.exit_code.unwrap_or(0));
+        }
+
+        let ready = match state.health.and_then(|h| h.status) {
+            Some(HealthStatusEnum::HEALTHY) => true,
+            Some(HealthStatusEnum::STARTING | HealthStatusEnum::UNHEALTHY) => false,
+            _ if tcp => accepts_tcp(server.port).await,
+            _ => tokio::time::Instant::now() >= settled,
+        };
+        if ready {
+            return Readiness::Ready;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Readiness::TimedOut;
+        }
+        tokio::time::sleep(READY_POLL).await;
+    }
+}
+
+async fn accepts_tcp(port: u16) -> bool {
+    let connect = tokio::net::TcpStream::connect(("127.0.0.1", port));
+    matches!(tokio::time::timeout(Duration::from_secs(2), connect).await, Ok(Ok(_)))
+}
+
+/// How a stop went
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopOutcome {
+    /// Exited once asked
+    Exited,
+    /// Didn't exit within the game's stop timeout, nor after a SIGTERM, and was killed
+    Killed,
+}
+
+/// Ask the server to stop the game's way (stop command, SIGINT or SIGTERM) and wait for it
+/// to exit. One still running after the timeout gets a SIGTERM, then a SIGKILL. The caller
+/// saves the Stopped status.
+pub async fn stop(
+    docker: &DockerManager,
+    server: &mut Server,
+    game: Option<&GameConfig>,
+) -> Result<StopOutcome, LifecycleError> {
+    let container_id = match &server.container_id {
+        Some(id) => id.clone(),
+        None => return Ok(StopOutcome::Exited),
+    };
+    let method = game.map(GameConfig::stop_method).unwrap_or(StopMethod::Sigterm);
+    let timeout = Duration::from_secs(game.map(|g| g.stop.timeout_secs).unwrap_or(STOP_GRACE_SECS).into());
+
+    let asked = match (method, game) {
+        (StopMethod::Command, Some(game)) => {
+            tracing::info!("Sending stop command: {}", game.stop_command);
+            docker.send_stdin(&container_id, &game.stop_command).await
+        }
+        (StopMethod::Sigint, _) => docker.kill_container(&container_id, "SIGINT").await,
+        _ => docker.kill_container(&container_id, "SIGTERM").await,
+    };
+    if let Err(e) = &asked {
+        tracing::warn!("Couldn't ask server {} to stop: {}", server.id, e);
+    }
+
+    let mut outcome = StopOutcome::Exited;
+    if !docker.wait_for_exit(&container_id, timeout).await {
+        let signalled = method == StopMethod::Sigterm && asked.is_ok();
+        if !signalled {
+            tracing::warn!("Server {} is still running after {:?} - sending SIGTERM", server.id, timeout);
+            docker.kill_container(&container_id, "SIGTERM").await.ok();
+        }
+        if signalled || !docker.wait_for_exit(&container_id, Duration::from_secs(STOP_GRACE_SECS.into())).await {
+            tracing::warn!("Server {} didn't exit - killing it", server.id);
+            docker.kill_container(&container_id, "SIGKILL").await?;
+            docker.wait_for_exit(&container_id, Duration::from_secs(STOP_GRACE_SECS.into())).await;
+            outcome = StopOutcome::Killed;
+        }
+    }
+
+    server.status = ServerStatus::Stopped;
+    Ok(outcome)
+}
+
+/// Remove the server's containers, and optionally its data. The caller deletes its config.
+pub async fn remove(docker: &DockerManager, server: &Server, delete_data: bool) -> Result<(), LifecycleError> {
+    if let Some(container_id) = &server.container_id {
+        docker.stop_container(container_id).await.ok();
+        docker.remove_container(container_id).await.ok();
+    }
+
+    if let Some(install_container_id) = &server.install_container_id {
+        docker.remove_install_container(install_container_id).await.ok();
+    }
+
+    // A folder imported in place from outside the data directory belongs to the user
+    if delete_data && server.data_path.starts_with(crate::paths::servers_dir()) && server.data_path.exists() {
+        std::fs::remove_dir_all(&server.data_path).ok();
+    }
+    Ok(())
+}
+
+/// Live status from Docker. Installing is left alone - the install flow manages it.
+pub async fn current_status(docker: &DockerManager, server: &Server) -> Result<ServerStatus, DockerError> {
+    if server.status == ServerStatus::Installing {
+        return Ok(ServerStatus::Installing);
+    }
+    let status = match &server.container_id {
+        Some(container_id) => docker.get_container_status(container_id).await?,
+        None => ServerStatus::Stopped,
+    };
+    Ok(kept_status(server, status))
+}
+
+/// The container's status, except that a crash-looping server stays so until it runs again
+pub fn kept_status(server: &Server, status: ServerStatus) -> ServerStatus {
+    if server.status == ServerStatus::CrashLooping && status != ServerStatus::Running {
+        ServerStatus::CrashLooping
+    } else {
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::PortProtocol;
+
+    fn test_game() -> GameConfig {
+        serde_json::from_str(
+            r#"{
+                "game_type": "rust",
+                "name": "Rust",
+                "description": "",
+                "docker_image": "example/rust",
+                "startup": "./RustDedicated +server.port {{SERVER_PORT}} +rcon.port {{RCON_PORT}}",
+                "stop_command": "quit",
+                "variables": [
+                    { "env": "SERVER_PORT", "name": "Port", "description": "", "default": "28015", "system_mapping": "port" },
+                    { "env": "RCON_PORT", "name": "RCON", "description": "", "default": "28016" }
+                ],
+                "ports": [
+                    { "container_port": 28015, "protocol": "udp" },
+                    { "container_port": 28016, "protocol": "tcp", "env_var": "RCON_PORT" }
+                ],
+                "volume_path": "/home/container",
+                "min_ram_mb": 4096,
+                "recommended_ram_mb": 8192,
+                "icon": ""
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_container_env_uses_allocated_extra_ports() {
+        let game = test_game();
+        let extra = vec![PortConfig {
+            container_port: 28026,
+            protocol: PortProtocol::Tcp,
+            description: None,
+            env_var: Some("RCON_PORT".to_string()),
+            local_only: false,
+        }];
+
+        let env = container_env(&game, 8192, 28025, &HashMap::new(), &[], &extra);
+        assert_eq!(env.get("SERVER_PORT").map(String::as_str), Some("28025"));
+        assert_eq!(env.get("RCON_PORT").map(String::as_str), Some("28026"));
+    }
+
+    #[test]
+    fn test_stop_method() {
+        let mut game = test_game();
+        assert_eq!(game.stop_method(), StopMethod::Command);
+        assert_eq!(game.stop.timeout_secs, 30);
+        assert_eq!(game.start_timeout_secs, DEFAULT_START_TIMEOUT_SECS);
+        // Written to stdin, "^C" would just be text - it means Ctrl+C
+        game.stop_command = "^C".to_string();
+        assert_eq!(game.stop_method(), StopMethod::Sigint);
+        game.stop_command = String::new();
+        assert_eq!(game.stop_method(), StopMethod::Sigterm);
+        game.stop.method = StopMethod::Sigint;
+        assert_eq!(game.stop_method(), StopMethod::Sigint);
+    }
+
+    #[test]
+    fn test_resolve_startup_command() {
+        let game = test_game();
+        let env = container_env(&game, 8192, 28015, &HashMap::new(), &[], &[]);
+        assert_eq!(
+            resolve_startup_command(&game, &env).as_deref(),
+            Some("./RustDedicated +server.port 28015 +rcon.port 28016")
+        );
+    }
+
+    #[test]
+    fn test_custom_env() {
+        let game = test_game();
+        let env = |pairs: &[(&str, &str)]| -> Vec<EnvVar> {
+            pairs.iter().map(|(k, v)| EnvVar { key: k.to_string(), value: v.to_string() }).collect()
+        };
+
+        let custom = env(&[("JAVA_TOOL_OPTIONS", "-XX:+UseZGC"), ("SERVER_PORT", "1")]);
+        let built = container_env(&game, 8192, 28015, &HashMap::new(), &custom, &[]);
+        assert_eq!(built.get("JAVA_TOOL_OPTIONS").map(String::as_str), Some("-XX:+UseZGC"));
+        // The game's variable wins
+        assert_eq!(built.get("SERVER_PORT").map(String::as_str), Some("28015"));
+
+        assert!(validate_custom_env(&game, &[], &env(&[("TZ", "Europe/Madrid")])).is_ok());
+        assert!(validate_custom_env(&game, &[], &env(&[("SERVER_PORT", "1")])).is_err());
+        assert!(validate_custom_env(&game, &[], &env(&[("TZ", "a"), ("TZ", "b")])).is_err());
+        assert!(validate_custom_env(&game, &[], &env(&[("1BAD", "")])).is_err());
+        assert!(validate_custom_env(&game, &[], &env(&[("BAD-NAME", "")])).is_err());
+    }
+
+    #[test]
+    fn test_startup_warnings() {
+        let env: HashMap<String, String> = [
+            ("NAME", "My Server"),
+            ("MOTD", "say \"hi\""),
+            ("EMPTY", ""),
+            ("PORT", "28015"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let quoted = startup_warnings(r#"./run +name "{{NAME}}" +port {{PORT}} '{{MOTD}}'"#, &env);
+        assert!(quoted.is_empty(), "{:?}", quoted);
+
+        let warnings = startup_warnings(r#"./run +name {{NAME}} +motd "{{MOTD}}" {{EMPTY}}"#, &env);
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings[0].starts_with("NAME"));
+
+        assert_eq!(placeholders("./run {{MISSING}} +port 28015"), vec!["MISSING".to_string()]);
+    }
+}
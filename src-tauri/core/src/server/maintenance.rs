@@ -0,0 +1,122 @@
+// Maintenance policy - when automation (scheduled wipes, automatic updates, ...) may stop or
+// change a server: never in quiet hours, and only inside a maintenance window when any are set
+
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// How far ahead the next allowed time is looked for - every weekly window repeats within it
+const LOOKAHEAD_DAYS: i64 = 8;
+
+/// A stretch of local time on some days of the week. An end before the start runs past
+/// midnight into the next day; an end equal to the start covers the whole day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Days the window starts on, every day when empty
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    pub start_hour: u32,
+    pub start_minute: u32,
+    pub end_hour: u32,
+    pub end_minute: u32,
+}
+
+impl TimeWindow {
+    fn starts_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start_hour > 23 || self.end_hour > 23 || self.start_minute > 59 || self.end_minute > 59 {
+            return Err(format!(
+                "{:02}:{:02}-{:02}:{:02} isn't a valid time range",
+                self.start_hour, self.start_minute, self.end_hour, self.end_minute
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, time: NaiveDateTime) -> bool {
+        let minute = time.hour() * 60 + time.minute();
+        let start = self.start_hour * 60 + self.start_minute;
+        let end = self.end_hour * 60 + self.end_minute;
+        let day = time.weekday();
+        if start == end {
+            self.starts_on(day)
+        } else if start < end {
+            self.starts_on(day) && (start..end).contains(&minute)
+        } else {
+            (self.starts_on(day) && minute >= start) || (self.starts_on(day.pred()) && minute < end)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MaintenancePolicy {
+    /// Times automation never touches the server, like peak play hours
+    #[serde(default)]
+    pub quiet_hours: Vec<TimeWindow>,
+    /// When set, automation only runs inside one of these
+    #[serde(default)]
+    pub windows: Vec<TimeWindow>,
+}
+
+impl MaintenancePolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        self.quiet_hours.iter().chain(&self.windows).try_for_each(TimeWindow::validate)
+    }
+
+    /// Whether automation may run at this local time
+    pub fn allows(&self, time: NaiveDateTime) -> bool {
+        !self.quiet_hours.iter().any(|w| w.contains(time))
+            && (self.windows.is_empty() || self.windows.iter().any(|w| w.contains(time)))
+    }
+
+    /// The first whole minute from `from` that automation may run, None if it never may
+    pub fn next_allowed(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.allows(from) {
+            return Some(from);
+        }
+        let start = from.with_second(0)?.with_nanosecond(0)?;
+        (1..=LOOKAHEAD_DAYS * 24 * 60)
+            .map(|minutes| start + Duration::minutes(minutes))
+            .find(|time| self.allows(*time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // October 2026: the 16th is a Friday
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn window(days: Vec<Weekday>, start: (u32, u32), end: (u32, u32)) -> TimeWindow {
+        TimeWindow { days, start_hour: start.0, start_minute: start.1, end_hour: end.0, end_minute: end.1 }
+    }
+
+    #[test]
+    fn test_quiet_hours_and_windows() {
+        // Quiet every evening 18:00-23:00, maintenance from 03:00 to 05:00
+        let policy = MaintenancePolicy {
+            quiet_hours: vec![window(vec![], (18, 0), (23, 0))],
+            windows: vec![window(vec![], (3, 0), (5, 0))],
+        };
+        assert!(policy.allows(at(16, 4, 30)));
+        assert!(!policy.allows(at(16, 12, 0)));
+        assert!(!policy.allows(at(16, 20, 0)));
+        assert_eq!(policy.next_allowed(at(16, 20, 15)), Some(at(17, 3, 0)));
+
+        // Past midnight on the weekend only: Friday 22:00 to Saturday 02:00 is quiet
+        let weekend = MaintenancePolicy {
+            quiet_hours: vec![window(vec![Weekday::Fri, Weekday::Sat], (22, 0), (2, 0))],
+            windows: vec![],
+        };
+        assert!(!weekend.allows(at(17, 1, 0)));
+        assert!(weekend.allows(at(16, 1, 0)));
+        assert_eq!(weekend.next_allowed(at(17, 23, 0)), Some(at(18, 2, 0)));
+        assert!(MaintenancePolicy::default().allows(at(16, 20, 0)));
+    }
+}
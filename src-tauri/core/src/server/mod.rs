@@ -0,0 +1,30 @@
+// Server module - the persisted server model, its store, registry and lifecycle
+
+pub mod artifacts;
+mod backup_schedule;
+mod backup_target;
+pub mod console;
+pub mod lifecycle;
+mod maintenance;
+mod model;
+mod registry;
+pub mod relocate;
+pub mod sessions;
+mod start_schedule;
+mod store;
+pub mod upgrade;
+
+pub use backup_schedule::{BackupSchedule, BackupTiming};
+pub use backup_target::{BackupStorage, BackupTarget};
+pub use console::{ConsoleBridge, ConsoleProbe};
+pub use lifecycle::LifecycleError;
+pub use maintenance::{MaintenancePolicy, TimeWindow};
+pub use registry::ServerConfigs;
+pub use model::{DatabaseKind, DatabaseSidecar, GameVersion, InstallState, Server, ServerStatus, TunnelConfig, TunnelProvider};
+pub use sessions::{PlayerSession, SessionRange};
+pub use start_schedule::StartSchedule;
+pub use store::{
+    calculate_dir_size, close_database, delete_server_config, get_database_path,
+    get_servers_config_dir, get_servers_dir, load_all_server_configs, load_server_config,
+    save_server_config, StoreError,
+};
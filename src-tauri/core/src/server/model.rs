@@ -0,0 +1,284 @@
+// Server model - what gets saved to config/<id>.json
+
+use super::{BackupSchedule, BackupTarget, ConsoleProbe, MaintenancePolicy, StartSchedule};
+use crate::games::{EnvVar, GameType, InstallNetwork, PortConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Server {
+    pub id: String,
+    pub name: String,
+    pub game_type: GameType,
+    pub status: ServerStatus,
+    pub container_id: Option<String>,
+    pub port: u16,
+    pub memory_mb: u32,
+    pub data_path: PathBuf,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub config: HashMap<String, String>,
+    #[serde(default)]
+    pub installed: bool,
+    #[serde(default)]
+    pub install_container_id: Option<String>,
+    #[serde(default)]
+    pub upnp_enabled: bool,
+    #[serde(default)]
+    pub tunnel: Option<TunnelConfig>,
+    /// Whether Windows Firewall rules were created for this server's ports
+    #[serde(default)]
+    pub firewall_rules: bool,
+    /// Docker network shared with other servers (proxy networks)
+    #[serde(default)]
+    pub network: Option<String>,
+    /// Extra host ports allocated at creation (query, RCON, ...). Empty for older
+    /// servers, which use the game's default extra ports.
+    #[serde(default)]
+    pub extra_ports: Vec<PortConfig>,
+    /// How the last failed install went, None once an install succeeds
+    #[serde(default)]
+    pub install_state: Option<InstallState>,
+    /// Game builds installs left behind, oldest first
+    #[serde(default)]
+    pub versions: Vec<GameVersion>,
+    /// Build installs target instead of the latest, and that update checks leave alone
+    #[serde(default)]
+    pub pinned_version: Option<GameVersion>,
+    /// The config changed since the container was created - its environment and startup
+    /// command are rebuilt on the next start
+    #[serde(default)]
+    pub config_outdated: bool,
+    /// Image used instead of the game's, like the Java runtime an older Minecraft version needs
+    #[serde(default)]
+    pub docker_image: Option<String>,
+    /// Extra environment variables for the game and install containers
+    #[serde(default)]
+    pub env: Vec<EnvVar>,
+    /// When automation may touch this server, None to follow the global policy
+    #[serde(default)]
+    pub maintenance: Option<MaintenancePolicy>,
+    /// Start the server again when it crashes, until it keeps crashing
+    #[serde(default)]
+    pub auto_restart: bool,
+    /// Database container that starts and stops with the server
+    #[serde(default)]
+    pub database: Option<DatabaseSidecar>,
+    /// Automatic backups, None when the server is only backed up by hand
+    #[serde(default)]
+    pub backup_schedule: Option<BackupSchedule>,
+    /// Storage off this computer the server's backups can be copied to
+    #[serde(default)]
+    pub backup_targets: Vec<BackupTarget>,
+    /// Started at a time of day, waking the Docker host first when it sleeps
+    #[serde(default)]
+    pub start_schedule: Option<StartSchedule>,
+    /// How commands reach the console of the running container, found when it's attached
+    #[serde(default)]
+    pub console: Option<ConsoleProbe>,
+    /// Where its install scripts may connect, None to follow the game
+    #[serde(default)]
+    pub install_network: Option<InstallNetwork>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Server {
+    /// A stopped Minecraft server named Survival on port 25565, for tests to adjust. Built the
+    /// way an old config loads, so new fields take their defaults here too.
+    pub fn test_fixture(id: &str) -> Self {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": "Survival",
+            "game_type": "minecraft-java",
+            "status": "stopped",
+            "container_id": null,
+            "port": 25565,
+            "memory_mb": 4096,
+            "data_path": format!("/tmp/{}", id),
+            "created_at": "2024-05-01T12:00:00Z",
+            "config": {}
+        }))
+        .unwrap()
+    }
+}
+
+/// A database for the server's plugins, in a container of its own next to the server's
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSidecar {
+    pub kind: DatabaseKind,
+    #[serde(default)]
+    pub container_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseKind {
+    Mariadb,
+    Mysql,
+    Redis,
+}
+
+impl DatabaseKind {
+    pub fn image(&self) -> &'static str {
+        match self {
+            DatabaseKind::Mariadb => "mariadb:11",
+            DatabaseKind::Mysql => "mysql:8.4",
+            DatabaseKind::Redis => "redis:7-alpine",
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        match self {
+            DatabaseKind::Mariadb | DatabaseKind::Mysql => 3306,
+            DatabaseKind::Redis => 6379,
+        }
+    }
+
+    /// Where the image keeps its data
+    pub fn data_path(&self) -> &'static str {
+        match self {
+            DatabaseKind::Mariadb | DatabaseKind::Mysql => "/var/lib/mysql",
+            DatabaseKind::Redis => "/data",
+        }
+    }
+}
+
+/// A game build an install put in place
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameVersion {
+    /// Steam build id or Paper build number
+    pub build: String,
+    /// The Minecraft version a Paper build belongs to
+    #[serde(default)]
+    pub minecraft_version: Option<String>,
+    pub installed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl GameVersion {
+    pub fn same_build(&self, other: &GameVersion) -> bool {
+        self.build == other.build && self.minecraft_version == other.minecraft_version
+    }
+
+    pub fn label(&self) -> String {
+        match &self.minecraft_version {
+            Some(version) => format!("{} build {}", version, self.build),
+            None => format!("build {}", self.build),
+        }
+    }
+}
+
+/// What a failed install got through, so the next attempt can pick up where it stopped
+/// instead of starting over
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallState {
+    /// Failed attempts in a row
+    pub attempts: u32,
+    /// The last progress message the script printed before it failed
+    pub failed_step: Option<String>,
+    /// SteamCMD reported the game fully installed - a retry doesn't need to run it again
+    pub steamcmd_done: bool,
+    pub exit_code: Option<i64>,
+    #[serde(default)]
+    pub failed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl InstallState {
+    /// Follow install output for progress messages and SteamCMD finishing
+    pub fn observe(&mut self, line: &str) {
+        if let Some(step) = line.strip_prefix("[Serverwave] ") {
+            self.failed_step = Some(step.trim().to_string());
+        }
+        if line.contains("Success! App '") && line.contains("fully installed") {
+            self.steamcmd_done = true;
+        }
+    }
+
+    /// Environment telling the install script what a previous attempt already did
+    pub fn resume_env(&self) -> Vec<String> {
+        let mut env = vec!["SERVERWAVE_RESUME=1".to_string()];
+        if self.steamcmd_done {
+            env.push("STEAMCMD_DONE=1".to_string());
+        }
+        env
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerStatus {
+    Stopped,
+    Starting,
+    Installing,
+    Running,
+    Stopping,
+    Error,
+    /// Crashed too often in a row to be restarted again - stays until it's started by hand
+    CrashLooping,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelProvider {
+    Ngrok,
+    Cloudflared,
+}
+
+/// Per-server tunnel settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelConfig {
+    pub provider: TunnelProvider,
+    /// ngrok authtoken or Cloudflare tunnel token. Kept in the keychain - only present when
+    /// it's being set, and in configs written by older versions.
+    #[serde(default, skip_serializing)]
+    pub token: String,
+    /// Whether the keychain holds a token for it
+    #[serde(default)]
+    pub has_token: bool,
+    #[serde(default)]
+    pub enabled: bool,
+    /// Public hostname routed to this server (Cloudflare tunnels are configured in the dashboard)
+    #[serde(default)]
+    pub hostname: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_config_gets_defaults() {
+        let json = r#"{
+            "id": "ab12cd34",
+            "name": "Survival",
+            "game_type": "minecraft-java",
+            "status": "stopped",
+            "container_id": null,
+            "port": 25565,
+            "memory_mb": 4096,
+            "data_path": "/tmp/ab12cd34",
+            "created_at": "2024-05-01T12:00:00Z",
+            "config": {}
+        }"#;
+
+        let server: Server = serde_json::from_str(json).unwrap();
+        assert!(!server.installed);
+        assert!(!server.upnp_enabled);
+        assert!(server.tunnel.is_none());
+        assert!(server.extra_ports.is_empty());
+        assert!(server.install_state.is_none());
+    }
+
+    #[test]
+    fn test_install_state_follows_steps_and_steamcmd() {
+        let mut state = InstallState::default();
+        state.observe("[Serverwave] Installing Rust dedicated server...");
+        state.observe("Update state (0x61) downloading, progress: 12.50");
+        assert_eq!(state.failed_step.as_deref(), Some("Installing Rust dedicated server..."));
+        assert_eq!(state.resume_env(), vec!["SERVERWAVE_RESUME=1".to_string()]);
+
+        state.observe("Success! App '258550' fully installed.");
+        assert!(state.steamcmd_done);
+        assert!(state.resume_env().contains(&"STEAMCMD_DONE=1".to_string()));
+    }
+}
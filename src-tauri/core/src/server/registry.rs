@@ -0,0 +1,127 @@
+// Server registry - the saved server configs held in memory, with a lock per server so
+// changes from different places (a start, a settings edit, an install finishing) are
+// applied one after another to the latest config instead of overwriting each other.
+
+use super::model::Server;
+use super::store::{delete_server_config, get_database_path, load_server_config, save_server_config, StoreError};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Default)]
+pub struct ServerConfigs {
+    /// Configs read so far, and the database they came from - dropped when the data directory moves
+    cache: Mutex<(PathBuf, HashMap<String, Server>)>,
+    locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl ServerConfigs {
+    /// The latest saved config
+    pub fn get(&self, server_id: &str) -> Result<Server, StoreError> {
+        if let Some(server) = self.cache().1.get(server_id) {
+            return Ok(server.clone());
+        }
+        let server = load_server_config(server_id)?;
+        // An update that finished meanwhile already holds the newer config
+        Ok(self.cache().1.entry(server.id.clone()).or_insert(server).clone())
+    }
+
+    /// Change the latest config and save it. Changes to the same server wait for each other,
+    /// so `change` should only touch the fields it means to.
+    pub fn update(&self, server_id: &str, change: impl FnOnce(&mut Server)) -> Result<Server, StoreError> {
+        self.try_update(server_id, |server| {
+            change(server);
+            Ok::<_, StoreError>(())
+        })
+    }
+
+    /// `update` with a change that can refuse - nothing is saved when it does
+    pub fn try_update<E: From<StoreError>>(
+        &self,
+        server_id: &str,
+        change: impl FnOnce(&mut Server) -> Result<(), E>,
+    ) -> Result<Server, E> {
+        let lock = self.lock_for(server_id);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut server = self.get(server_id)?;
+        change(&mut server)?;
+        save_server_config(&server)?;
+        self.cache().1.insert(server.id.clone(), server.clone());
+        Ok(server)
+    }
+
+    /// Save a whole config - a new server, or one replaced by an import
+    pub fn insert(&self, server: &Server) -> Result<(), StoreError> {
+        let lock = self.lock_for(&server.id);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        save_server_config(server)?;
+        self.cache().1.insert(server.id.clone(), server.clone());
+        Ok(())
+    }
+
+    /// Delete a server's config
+    pub fn remove(&self, server_id: &str) -> Result<(), StoreError> {
+        let lock = self.lock_for(server_id);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        delete_server_config(server_id)?;
+        self.cache().1.remove(server_id);
+        Ok(())
+    }
+
+    fn cache(&self) -> MutexGuard<'_, (PathBuf, HashMap<String, Server>)> {
+        let mut cache = self.cache.lock().unwrap();
+        let database = get_database_path();
+        if cache.0 != database {
+            *cache = (database, HashMap::new());
+        }
+        cache
+    }
+
+    fn lock_for(&self, server_id: &str) -> Arc<Mutex<()>> {
+        self.locks.lock().unwrap().entry(server_id.to_string()).or_default().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths;
+    use crate::server::store::close_database;
+
+    #[test]
+    fn test_concurrent_updates_are_not_lost() {
+        let _guard = paths::TEST_ROOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("sw-registry-{}", uuid::Uuid::new_v4()));
+        paths::set_data_root(Some(root.clone()));
+
+        let configs = Arc::new(ServerConfigs::default());
+        let server = Server::test_fixture("race0001");
+        configs.insert(&server).unwrap();
+
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let configs = configs.clone();
+                std::thread::spawn(move || {
+                    configs
+                        .update("race0001", |s| {
+                            s.config.insert(format!("KEY_{}", i), i.to_string());
+                        })
+                        .unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(configs.get("race0001").unwrap().config.len(), 8);
+        assert_eq!(load_server_config("race0001").unwrap().config.len(), 8);
+
+        configs.remove("race0001").unwrap();
+        assert!(matches!(configs.get("race0001"), Err(StoreError::NotFound(_))));
+
+        close_database();
+        paths::set_data_root(None);
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,158 @@
+// Data directory relocation - moves servers and configs to a new root and
+// rewrites the data paths stored in each server config
+
+use super::store::{close_database, load_all_server_configs, save_server_config, StoreError};
+use crate::paths;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Folders that move with the data directory
+const MOVED_DIRS: &[&str] = &["servers", "config"];
+
+#[derive(Error, Debug)]
+pub enum RelocateError {
+    #[error("'{0}' must be an absolute path")]
+    NotAbsolute(PathBuf),
+
+    #[error("'{0}' is already the data directory")]
+    SameDirectory(PathBuf),
+
+    #[error("The new data directory can't be inside the current one")]
+    Nested,
+
+    #[error("'{0}' already contains server data")]
+    NotEmpty(PathBuf),
+
+    #[error("Failed to move '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error(transparent)]
+    Store(#[from] StoreError),
+}
+
+impl From<RelocateError> for String {
+    fn from(e: RelocateError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Check that data can be moved to `new_root` without overwriting anything
+pub fn check_target(new_root: &Path) -> Result<(), RelocateError> {
+    let current = paths::data_root();
+    if !new_root.is_absolute() {
+        return Err(RelocateError::NotAbsolute(new_root.to_path_buf()));
+    }
+    if new_root == current {
+        return Err(RelocateError::SameDirectory(new_root.to_path_buf()));
+    }
+    if new_root.starts_with(&current) {
+        return Err(RelocateError::Nested);
+    }
+    for dir in MOVED_DIRS {
+        let target = new_root.join(dir);
+        let has_content = std::fs::read_dir(&target)
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false);
+        if has_content {
+            return Err(RelocateError::NotEmpty(target));
+        }
+    }
+    Ok(())
+}
+
+/// Move servers and configs to `new_root` and switch the data root over.
+///
+/// Each folder is renamed when both roots are on the same drive, otherwise it is
+/// copied and the original removed only once everything has been copied. If a
+/// copy fails the partial copy is removed and the old data is left untouched.
+pub fn relocate(new_root: &Path) -> Result<(), RelocateError> {
+    check_target(new_root)?;
+    let old_root = paths::data_root();
+    let old_servers = paths::servers_dir();
+
+    std::fs::create_dir_all(new_root).map_err(|source| RelocateError::Io {
+        path: new_root.to_path_buf(),
+        source,
+    })?;
+
+    // The database lives in config/ - it has to be closed to move
+    close_database();
+
+    let mut copied = Vec::new();
+    for dir in MOVED_DIRS {
+        let from = old_root.join(dir);
+        let to = new_root.join(dir);
+        if !from.exists() {
+            continue;
+        }
+        // An empty target folder from check_target is fine to replace
+        std::fs::remove_dir(&to).ok();
+        if std::fs::rename(&from, &to).is_ok() {
+            continue;
+        }
+        if let Err(source) = paths::copy_dir_all(&from, &to) {
+            for partial in copied.iter().chain(std::iter::once(&to)) {
+                std::fs::remove_dir_all(partial).ok();
+            }
+            return Err(RelocateError::Io { path: from, source });
+        }
+        copied.push(to);
+    }
+
+    paths::set_data_root(Some(new_root.to_path_buf()));
+
+    let new_servers = paths::servers_dir();
+    for mut server in load_all_server_configs() {
+        if let Ok(relative) = server.data_path.strip_prefix(&old_servers) {
+            server.data_path = new_servers.join(relative);
+            save_server_config(&server)?;
+        }
+    }
+
+    // Only now that the new copy is complete and in use
+    for dir in MOVED_DIRS {
+        let old = old_root.join(dir);
+        if old.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&old) {
+                tracing::warn!("Could not remove old data at {}: {}", old.display(), e);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::load_server_config;
+
+    #[test]
+    fn test_relocate_moves_data_and_rewrites_paths() {
+        let _guard = paths::TEST_ROOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let base = std::env::temp_dir().join(format!("sw-relocate-{}", uuid::Uuid::new_v4()));
+        let old_root = base.join("old");
+        let new_root = base.join("new");
+        paths::set_data_root(Some(old_root.clone()));
+
+        let data_path = paths::servers_dir().join("minecraft-java").join("ab12cd34");
+        std::fs::create_dir_all(&data_path).unwrap();
+        std::fs::write(data_path.join("server.properties"), "motd=hi").unwrap();
+        let server = crate::server::Server { data_path: data_path.clone(), ..crate::server::Server::test_fixture("ab12cd34") };
+        save_server_config(&server).unwrap();
+
+        relocate(&new_root).unwrap();
+
+        let moved = load_server_config("ab12cd34").unwrap();
+        assert!(moved.data_path.starts_with(&new_root));
+        assert!(moved.data_path.join("server.properties").exists());
+        assert!(!old_root.join("servers").exists());
+        assert!(matches!(relocate(&new_root), Err(RelocateError::SameDirectory(_))));
+
+        paths::set_data_root(None);
+        std::fs::remove_dir_all(&base).ok();
+    }
+}
@@ -0,0 +1,160 @@
+// Player sessions - who played when, from the joins and leaves a game's console reports.
+// Times are the log lines' own, so lines read again after a reconnect change nothing.
+
+use super::store::{with_database, StoreError};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct PlayerSession {
+    pub player: String,
+    pub joined_at: DateTime<Utc>,
+    /// None while they're still on
+    pub left_at: Option<DateTime<Utc>>,
+    /// Up to now for a session still going
+    pub duration_secs: i64,
+}
+
+/// Sessions overlapping this stretch are returned; an open end is unbounded
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SessionRange {
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Stored with a fixed width so the text sorts like the time
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_time(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// Start a session. One still open for the player missed its leave and ends here.
+pub fn record_join(server_id: &str, player: &str, at: DateTime<Utc>) -> Result<(), StoreError> {
+    let at = timestamp(at);
+    with_database(|connection| {
+        let known: Option<i64> = connection
+            .query_row(
+                "SELECT id FROM player_sessions WHERE server_id = ?1 AND player = ?2 AND joined_at = ?3",
+                params![server_id, player, at],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if known.is_some() {
+            return Ok(());
+        }
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "UPDATE player_sessions SET left_at = ?3
+             WHERE server_id = ?1 AND player = ?2 AND left_at IS NULL AND joined_at < ?3",
+            params![server_id, player, at],
+        )?;
+        transaction.execute(
+            "INSERT INTO player_sessions (server_id, player, joined_at) VALUES (?1, ?2, ?3)",
+            params![server_id, player, at],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    })
+}
+
+pub fn record_leave(server_id: &str, player: &str, at: DateTime<Utc>) -> Result<(), StoreError> {
+    with_database(|connection| {
+        connection.execute(
+            "UPDATE player_sessions SET left_at = ?3
+             WHERE server_id = ?1 AND player = ?2 AND left_at IS NULL AND joined_at <= ?3",
+            params![server_id, player, timestamp(at)],
+        )?;
+        Ok(())
+    })
+}
+
+/// End every open session - the server stopped, so everyone left
+pub fn end_sessions(server_id: &str, at: DateTime<Utc>) -> Result<(), StoreError> {
+    with_database(|connection| {
+        connection.execute(
+            "UPDATE player_sessions SET left_at = ?2 WHERE server_id = ?1 AND left_at IS NULL",
+            params![server_id, timestamp(at)],
+        )?;
+        Ok(())
+    })
+}
+
+/// The server's sessions in the range, newest first
+pub fn load_sessions(server_id: &str, range: &SessionRange) -> Result<Vec<PlayerSession>, StoreError> {
+    let from = range.from.map(timestamp);
+    let to = range.to.map(timestamp);
+    let rows = with_database(|connection| {
+        let mut statement = connection.prepare(
+            "SELECT player, joined_at, left_at FROM player_sessions
+             WHERE server_id = ?1
+               AND (?2 IS NULL OR left_at IS NULL OR left_at >= ?2)
+               AND (?3 IS NULL OR joined_at <= ?3)
+             ORDER BY joined_at DESC",
+        )?;
+        let rows = statement
+            .query_map(params![server_id, from, to], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    })?;
+
+    let now = Utc::now();
+    Ok(rows
+        .into_iter()
+        .filter_map(|(player, joined_at, left_at)| {
+            let joined_at = parse_time(&joined_at)?;
+            let left_at = left_at.as_deref().and_then(parse_time);
+            Some(PlayerSession {
+                duration_secs: (left_at.unwrap_or(now) - joined_at).num_seconds().max(0),
+                player,
+                joined_at,
+                left_at,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths;
+    use crate::server::close_database;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        format!("2026-10-16T12:{:02}:00Z", minute).parse().unwrap()
+    }
+
+    #[test]
+    fn test_sessions_from_joins_and_leaves() {
+        let _guard = paths::TEST_ROOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("sw-sessions-{}", uuid::Uuid::new_v4()));
+        paths::set_data_root(Some(root.clone()));
+
+        record_join("ab12cd34", "Steve", at(0)).unwrap();
+        record_join("ab12cd34", "Alex", at(5)).unwrap();
+        record_leave("ab12cd34", "Steve", at(30)).unwrap();
+        // The same lines read again after a reconnect
+        record_join("ab12cd34", "Steve", at(0)).unwrap();
+        record_leave("ab12cd34", "Steve", at(30)).unwrap();
+        record_join("ab12cd34", "Steve", at(40)).unwrap();
+        end_sessions("ab12cd34", at(50)).unwrap();
+
+        let all = load_sessions("ab12cd34", &SessionRange::default()).unwrap();
+        let summary: Vec<_> = all.iter().map(|s| (s.player.as_str(), s.duration_secs)).collect();
+        assert_eq!(summary, [("Steve", 600), ("Alex", 2700), ("Steve", 1800)]);
+
+        let late = SessionRange { from: Some(at(35)), to: None };
+        assert_eq!(load_sessions("ab12cd34", &late).unwrap().len(), 2);
+
+        close_database();
+        paths::set_data_root(None);
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
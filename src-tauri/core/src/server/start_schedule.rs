@@ -0,0 +1,66 @@
+// Start schedule - a server started at a time of day, like an evening play session. When the
+// Docker host sleeps, it's woken first.
+
+use super::backup_schedule::next_daily;
+use chrono::{DateTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartSchedule {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Days it starts on, every day when empty
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    /// Local time
+    pub hour: u32,
+    pub minute: u32,
+    #[serde(default)]
+    pub last_run: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl StartSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.hour > 23 || self.minute > 59 {
+            return Err(format!("{:02}:{:02} isn't a valid time", self.hour, self.minute));
+        }
+        Ok(())
+    }
+
+    /// The next start after `now`, in its timezone
+    pub fn next_from<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        next_daily(now, &self.days, self.hour, self.minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_start() {
+        // Friday October 16th 2026
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 20, 0, 0).unwrap();
+        let schedule = |days: Vec<Weekday>| StartSchedule {
+            enabled: true,
+            days,
+            hour: 19,
+            minute: 30,
+            last_run: None,
+            next_run: None,
+        };
+        // Past today's time, so tomorrow
+        assert_eq!(schedule(vec![]).next_from(&now), Some(Utc.with_ymd_and_hms(2026, 10, 17, 19, 30, 0).unwrap()));
+        assert_eq!(
+            schedule(vec![Weekday::Fri]).next_from(&now),
+            Some(Utc.with_ymd_and_hms(2026, 10, 23, 19, 30, 0).unwrap())
+        );
+        assert!(StartSchedule { hour: 24, ..schedule(vec![]) }.validate().is_err());
+    }
+}
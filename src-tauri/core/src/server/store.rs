@@ -0,0 +1,355 @@
+// Server store - servers (and their player sessions) in an SQLite database in the config
+// dir, with versioned schema migrations. Older versions kept one JSON file per server;
+// those are imported the first time the database is opened. Configs saved by an older
+// version are upgraded to this one's shape at the same time.
+
+use super::model::Server;
+use super::upgrade::{self, CONFIG_VERSION};
+use crate::paths;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use thiserror::Error;
+
+const DATABASE_FILE: &str = "servers.db";
+
+/// Schema migrations, applied in order. The database's `user_version` is the
+/// number already applied - only ever append to this list.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE servers (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        game_type TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX servers_game_type ON servers (game_type);",
+    "CREATE TABLE player_sessions (
+        id INTEGER PRIMARY KEY,
+        server_id TEXT NOT NULL,
+        player TEXT NOT NULL,
+        joined_at TEXT NOT NULL,
+        left_at TEXT,
+        UNIQUE (server_id, player, joined_at)
+    );
+    CREATE INDEX player_sessions_server ON player_sessions (server_id, joined_at);",
+    "ALTER TABLE servers ADD COLUMN config_version INTEGER NOT NULL DEFAULT 0;",
+];
+
+lazy_static::lazy_static! {
+    /// Open connection and the path it was opened at - reopened when the data directory moves
+    static ref DATABASE: Mutex<Option<(PathBuf, Connection)>> = Mutex::new(None);
+}
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("Server '{0}' not found")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid server config: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+}
+
+impl From<StoreError> for String {
+    fn from(e: StoreError) -> Self {
+        e.to_string()
+    }
+}
+
+pub fn get_servers_dir() -> PathBuf {
+    paths::servers_dir()
+}
+
+pub fn get_servers_config_dir() -> PathBuf {
+    paths::config_dir()
+}
+
+pub fn get_database_path() -> PathBuf {
+    get_servers_config_dir().join(DATABASE_FILE)
+}
+
+/// Run `f` with the store's connection, opening (and migrating) it on first use
+pub(super) fn with_database<T>(f: impl FnOnce(&mut Connection) -> Result<T, StoreError>) -> Result<T, StoreError> {
+    let path = get_database_path();
+    let mut database = DATABASE.lock().unwrap_or_else(|e| e.into_inner());
+    let stale = database.as_ref().map(|(open_path, _)| open_path != &path).unwrap_or(true);
+    if stale {
+        *database = None;
+        *database = Some((path.clone(), open_database(&path)?));
+    }
+    let (_, connection) = database.as_mut().expect("database was just opened");
+    f(connection)
+}
+
+/// Close the connection so the database file can be moved
+pub fn close_database() {
+    *DATABASE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn open_database(path: &Path) -> Result<Connection, StoreError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut connection = Connection::open(path)?;
+    connection.busy_timeout(Duration::from_secs(5))?;
+    connection.pragma_update(None, "synchronous", "FULL")?;
+    migrate(&mut connection)?;
+    import_legacy_json(&mut connection)?;
+    upgrade_configs(&mut connection)?;
+    Ok(connection)
+}
+
+fn migrate(connection: &mut Connection) -> Result<(), StoreError> {
+    let applied: usize = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.pragma_update(None, "user_version", index + 1)?;
+        transaction.commit()?;
+        tracing::info!("Applied server store migration {}", index + 1);
+    }
+    Ok(())
+}
+
+/// Move servers from the old one-JSON-file-per-server layout into the database.
+/// Imported files are kept in `imported-json/` rather than deleted.
+fn import_legacy_json(connection: &mut Connection) -> Result<(), StoreError> {
+    let config_dir = get_servers_config_dir();
+    let files: Vec<PathBuf> = match std::fs::read_dir(&config_dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().map(|e| e == "json").unwrap_or(false))
+            .collect(),
+        Err(_) => return Ok(()),
+    };
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut imported = Vec::new();
+    let transaction = connection.transaction()?;
+    for path in files {
+        let id = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let server = std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|content| {
+            let mut config = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+            upgrade::upgrade(&mut config, 0, &id)?;
+            serde_json::from_value::<Server>(config).map_err(|e| e.to_string())
+        });
+        match server {
+            Ok(server) => {
+                upsert(&transaction, &server)?;
+                imported.push(path);
+            }
+            Err(e) => tracing::warn!("Skipping unreadable server config {}: {}", path.display(), e),
+        }
+    }
+    transaction.commit()?;
+
+    let backup_dir = config_dir.join("imported-json");
+    std::fs::create_dir_all(&backup_dir)?;
+    for path in &imported {
+        if let Some(name) = path.file_name() {
+            std::fs::rename(path, backup_dir.join(name))?;
+        }
+    }
+    tracing::info!("Imported {} server configs into the database", imported.len());
+    Ok(())
+}
+
+/// Run the config upgrades on servers saved by an older version. What a changed config
+/// looked like is kept in `upgraded-json/`, and one that still can't be read is left as it
+/// was for a later version to try.
+fn upgrade_configs(connection: &mut Connection) -> Result<(), StoreError> {
+    let rows = {
+        let mut statement = connection.prepare("SELECT id, config_version, data FROM servers WHERE config_version < ?1")?;
+        let rows = statement
+            .query_map([CONFIG_VERSION], |row| Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?, row.get::<_, String>(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let backup_dir = get_servers_config_dir().join("upgraded-json");
+    let mut upgraded = 0;
+    let transaction = connection.transaction()?;
+    for (id, version, data) in rows {
+        let server = serde_json::from_str(&data).map_err(|e| e.to_string()).and_then(|mut config| {
+            upgrade::upgrade(&mut config, version, &id)?;
+            serde_json::from_value::<Server>(config).map_err(|e| e.to_string())
+        });
+        match server {
+            Ok(server) => {
+                if serde_json::to_string(&server)? != data {
+                    std::fs::create_dir_all(&backup_dir)?;
+                    std::fs::write(backup_dir.join(format!("{}-v{}.json", id, version)), &data)?;
+                    upgraded += 1;
+                }
+                upsert(&transaction, &server)?;
+            }
+            Err(e) => tracing::warn!("Server {} can't be upgraded from config version {}: {}", id, version, e),
+        }
+    }
+    transaction.commit()?;
+    if upgraded > 0 {
+        tracing::info!("Upgraded {} server configs to version {}", upgraded, CONFIG_VERSION);
+    }
+    Ok(())
+}
+
+fn upsert(connection: &Connection, server: &Server) -> Result<(), StoreError> {
+    connection.execute(
+        "INSERT INTO servers (id, name, game_type, created_at, data, config_version) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT (id) DO UPDATE SET
+            name = excluded.name,
+            game_type = excluded.game_type,
+            created_at = excluded.created_at,
+            data = excluded.data,
+            config_version = excluded.config_version",
+        params![
+            server.id,
+            server.name,
+            server.game_type.to_string(),
+            server.created_at.to_rfc3339(),
+            serde_json::to_string(server)?,
+            CONFIG_VERSION,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn save_server_config(server: &Server) -> Result<(), StoreError> {
+    with_database(|connection| upsert(connection, server))
+}
+
+pub fn load_server_config(server_id: &str) -> Result<Server, StoreError> {
+    let data: Option<String> = with_database(|connection| {
+        Ok(connection
+            .query_row("SELECT data FROM servers WHERE id = ?1", [server_id], |row| row.get(0))
+            .optional()?)
+    })?;
+    match data {
+        Some(data) => Ok(serde_json::from_str(&data)?),
+        None => Err(StoreError::NotFound(server_id.to_string())),
+    }
+}
+
+pub fn delete_server_config(server_id: &str) -> Result<(), StoreError> {
+    with_database(|connection| {
+        connection.execute("DELETE FROM servers WHERE id = ?1", [server_id])?;
+        connection.execute("DELETE FROM player_sessions WHERE server_id = ?1", [server_id])?;
+        Ok(())
+    })
+}
+
+/// Every saved server, newest first, skipping rows that fail to parse
+pub fn load_all_server_configs() -> Vec<Server> {
+    let rows = with_database(|connection| {
+        let mut statement = connection.prepare("SELECT id, data FROM servers ORDER BY created_at DESC")?;
+        let rows = statement
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    });
+
+    match rows {
+        Ok(rows) => rows
+            .into_iter()
+            .filter_map(|(id, data)| match serde_json::from_str(&data) {
+                Ok(server) => Some(server),
+                Err(e) => {
+                    tracing::warn!("Skipping unreadable server {}: {}", id, e);
+                    None
+                }
+            })
+            .collect(),
+        Err(e) => {
+            tracing::error!("Failed to load servers: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn calculate_dir_size(path: &Path) -> Result<u64, std::io::Error> {
+    let mut total = 0;
+
+    if path.is_file() {
+        return Ok(std::fs::metadata(path)?.len());
+    }
+
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_file() {
+            total += std::fs::metadata(&path)?.len();
+        } else if path.is_dir() {
+            total += calculate_dir_size(&path).unwrap_or(0);
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_server(id: &str, created_at: &str) -> Server {
+        Server { created_at: created_at.parse().unwrap(), ..Server::test_fixture(id) }
+    }
+
+    #[test]
+    fn test_imports_legacy_json_and_round_trips() {
+        let _guard = paths::TEST_ROOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("sw-store-{}", uuid::Uuid::new_v4()));
+        paths::set_data_root(Some(root.clone()));
+
+        let legacy = test_server("old00001", "2024-01-01T00:00:00Z");
+        std::fs::create_dir_all(get_servers_config_dir()).unwrap();
+        std::fs::write(
+            get_servers_config_dir().join("old00001.json"),
+            serde_json::to_string(&legacy).unwrap(),
+        )
+        .unwrap();
+
+        let mut newer = test_server("new00002", "2024-06-01T00:00:00Z");
+        save_server_config(&newer).unwrap();
+        newer.name = "Creative".to_string();
+        save_server_config(&newer).unwrap();
+
+        let all = load_all_server_configs();
+        assert_eq!(all.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["new00002", "old00001"]);
+        assert_eq!(load_server_config("new00002").unwrap().name, "Creative");
+        assert!(get_servers_config_dir().join("imported-json").join("old00001.json").exists());
+
+        delete_server_config("old00001").unwrap();
+        assert!(matches!(load_server_config("old00001"), Err(StoreError::NotFound(_))));
+
+        // A row an older version wrote in a shape this one can't read is upgraded on the next open
+        with_database(|connection| {
+            connection.execute(
+                "UPDATE servers SET config_version = 0, data = json_set(data, '$.port', '25566', '$.status', 'Stopped')
+                 WHERE id = 'new00002'",
+                [],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(load_server_config("new00002").is_err());
+        close_database();
+        assert_eq!(load_server_config("new00002").unwrap().port, 25566);
+        assert!(get_servers_config_dir().join("upgraded-json").join("new00002-v0.json").exists());
+
+        close_database();
+        paths::set_data_root(None);
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,158 @@
+// Config upgrades - servers are saved as JSON, and one saved by an older version (or edited by
+// hand) may be in a shape this version can't read. Each step takes a config one version
+// further; the store runs the steps a saved server is behind on when the database is opened.
+
+use crate::paths;
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
+type Step = fn(&mut Map<String, Value>);
+
+/// Applied in order - only ever append. A config saved by this version is at `CONFIG_VERSION`.
+const STEPS: &[Step] = &[fill_required_fields, normalize_values, rewrite_data_path];
+
+pub const CONFIG_VERSION: u32 = STEPS.len() as u32;
+
+/// Values a server can't do without, and what an older config gets when it has none
+const DEFAULT_MEMORY_MB: u32 = 2048;
+const DEFAULT_STATUS: &str = "stopped";
+const STATUSES: &[&str] = &["stopped", "starting", "installing", "running", "stopping", "error", "crashlooping"];
+
+/// Bring a saved config from `version` up to `CONFIG_VERSION`. `id` is the one it's stored
+/// under, for a config that lost its own.
+pub fn upgrade(config: &mut Value, version: u32, id: &str) -> Result<(), String> {
+    let map = config.as_object_mut().ok_or("A server config must be a JSON object")?;
+    if !map.get("id").is_some_and(Value::is_string) {
+        map.insert("id".to_string(), Value::String(id.to_string()));
+    }
+    for step in STEPS.iter().skip(version as usize) {
+        step(map);
+    }
+    Ok(())
+}
+
+/// 1 - the fields every version needed but a hand-made or cut-short config may lack
+fn fill_required_fields(map: &mut Map<String, Value>) {
+    let id = map["id"].clone();
+    let named = map.get("name").and_then(Value::as_str).is_some_and(|name| !name.trim().is_empty());
+    if !named {
+        map.insert("name".to_string(), id);
+    }
+    map.entry("status").or_insert_with(|| Value::String(DEFAULT_STATUS.to_string()));
+    map.entry("memory_mb").or_insert_with(|| Value::from(DEFAULT_MEMORY_MB));
+    map.entry("created_at").or_insert_with(|| Value::String(chrono::Utc::now().to_rfc3339()));
+    if !map.get("config").is_some_and(Value::is_object) {
+        map.insert("config".to_string(), Value::Object(Map::new()));
+    }
+    if !map.contains_key("data_path") {
+        if let (Some(game), Some(id)) = (map.get("game_type").and_then(Value::as_str), map["id"].as_str()) {
+            let path = paths::servers_dir().join(game).join(id);
+            map.insert("data_path".to_string(), Value::String(path.to_string_lossy().into_owned()));
+        }
+    }
+}
+
+/// 2 - values written as the wrong type: numbers as strings, settings as numbers, statuses
+/// in another case, and nulls where a list or flag now has a default
+fn normalize_values(map: &mut Map<String, Value>) {
+    // A missing field gets its default, which a null doesn't
+    map.retain(|_, value| !value.is_null());
+
+    for key in ["port", "memory_mb"] {
+        if let Some(number) = map.get(key).and_then(Value::as_str).and_then(|s| s.trim().parse::<u64>().ok()) {
+            map.insert(key.to_string(), Value::from(number));
+        }
+    }
+
+    // The status is only a snapshot - the real one is read from Docker
+    let status = map
+        .get("status")
+        .and_then(Value::as_str)
+        .map(|s| s.to_lowercase().replace(['_', '-'], ""))
+        .filter(|s| STATUSES.contains(&s.as_str()))
+        .unwrap_or_else(|| DEFAULT_STATUS.to_string());
+    map.insert("status".to_string(), Value::String(status));
+
+    if let Some(Value::Object(config)) = map.get_mut("config") {
+        for value in config.values_mut() {
+            let text = match &*value {
+                Value::String(_) => continue,
+                Value::Null => String::new(),
+                other => other.to_string(),
+            };
+            *value = Value::String(text);
+        }
+    }
+}
+
+/// 3 - data paths as older versions or other machines wrote them: relative to the data
+/// directory, with Windows' \\?\ prefix, or somewhere the server's folder no longer is
+fn rewrite_data_path(map: &mut Map<String, Value>) {
+    let Some(path) = map.get("data_path").and_then(Value::as_str) else { return };
+    let mut path = PathBuf::from(path.strip_prefix(r"\\?\").unwrap_or(path));
+    if path.is_relative() {
+        path = paths::data_root().join(path);
+    }
+    if !path.exists() {
+        if let (Some(game), Some(id)) = (map.get("game_type").and_then(Value::as_str), map["id"].as_str()) {
+            let expected = paths::servers_dir().join(game).join(id);
+            if expected.is_dir() {
+                path = expected;
+            }
+        }
+    }
+    map.insert("data_path".to_string(), Value::String(path.to_string_lossy().into_owned()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{Server, ServerStatus};
+
+    #[test]
+    fn test_old_config_becomes_loadable() {
+        let _guard = paths::TEST_ROOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let root = std::env::temp_dir().join(format!("sw-upgrade-{}", uuid::Uuid::new_v4()));
+        paths::set_data_root(Some(root.clone()));
+        let moved = paths::servers_dir().join("minecraft-java").join("ab12cd34");
+        std::fs::create_dir_all(&moved).unwrap();
+
+        let mut config = serde_json::json!({
+            "game_type": "minecraft-java",
+            "status": "Running",
+            "container_id": null,
+            "port": "25565",
+            "data_path": "/old/disk/servers/minecraft-java/ab12cd34",
+            "created_at": "2024-05-01T12:00:00Z",
+            "config": { "MC_MAXPLAYERS": 20, "MC_PVP": true, "MC_MOTD": null },
+            "extra_ports": null,
+            "auto_restart": null
+        });
+        assert!(serde_json::from_value::<Server>(config.clone()).is_err());
+
+        upgrade(&mut config, 0, "ab12cd34").unwrap();
+        let server: Server = serde_json::from_value(config).unwrap();
+        assert_eq!(server.id, "ab12cd34");
+        assert_eq!(server.name, "ab12cd34");
+        assert_eq!(server.status, ServerStatus::Running);
+        assert_eq!(server.port, 25565);
+        assert_eq!(server.memory_mb, DEFAULT_MEMORY_MB);
+        assert_eq!(server.data_path, moved);
+        assert_eq!(server.config.get("MC_MAXPLAYERS").map(String::as_str), Some("20"));
+        assert_eq!(server.config.get("MC_PVP").map(String::as_str), Some("true"));
+        assert_eq!(server.config.get("MC_MOTD").map(String::as_str), Some(""));
+        assert!(server.extra_ports.is_empty());
+
+        // Relative paths are under the data directory; configs already current are left alone
+        let mut relative = serde_json::json!({ "game_type": "terraria", "data_path": "servers/terraria/ef56ab78", "status": "bogus" });
+        upgrade(&mut relative, 0, "ef56ab78").unwrap();
+        assert_eq!(relative["data_path"], root.join("servers/terraria/ef56ab78").to_string_lossy().as_ref());
+        assert_eq!(relative["status"], "stopped");
+        let mut current = serde_json::json!({ "id": "ef56ab78", "status": "Bogus" });
+        upgrade(&mut current, CONFIG_VERSION, "ef56ab78").unwrap();
+        assert_eq!(current["status"], "Bogus");
+
+        paths::set_data_root(None);
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,341 @@
+// Server List Ping - what Minecraft clients ask a server for its entry in the server list:
+// the MOTD, version and player count. Java servers answer a status request over TCP with
+// JSON; Bedrock servers answer RakNet's unconnected ping over UDP with a line of fields.
+
+use crate::server::Server;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+const TIMEOUT: Duration = Duration::from_secs(3);
+/// Servers answer status requests from clients of any version
+const ANY_PROTOCOL: i32 = -1;
+const STATUS_STATE: i32 = 1;
+/// Room for a status with a favicon
+const MAX_STATUS: usize = 256 * 1024;
+const UNCONNECTED_PING: u8 = 0x01;
+const UNCONNECTED_PONG: u8 = 0x1c;
+const RAKNET_MAGIC: [u8; 16] = [
+    0x00, 0xff, 0xff, 0x00, 0xfe, 0xfe, 0xfe, 0xfe, 0xfd, 0xfd, 0xfd, 0xfd, 0x12, 0x34, 0x56, 0x78,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Edition {
+    Java,
+    Bedrock,
+}
+
+impl Edition {
+    /// The edition a server runs, None when it isn't Minecraft
+    pub fn of(server: &Server) -> Option<Edition> {
+        let game_type = server.game_type.0.to_lowercase();
+        if !game_type.contains("minecraft") {
+            None
+        } else if game_type.contains("bedrock") {
+            Some(Edition::Bedrock)
+        } else {
+            Some(Edition::Java)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ServerPing {
+    /// Without its colour codes
+    pub motd: String,
+    pub version: String,
+    pub players: u32,
+    pub max_players: u32,
+    /// Some of who's on - Java servers list up to a dozen, Bedrock servers nobody
+    pub player_names: Vec<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum PingError {
+    #[error("Ping failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The server didn't answer the ping")]
+    Timeout,
+    #[error("Unexpected ping answer: {0}")]
+    Protocol(String),
+}
+
+#[derive(Deserialize)]
+struct Status {
+    #[serde(default)]
+    version: Option<StatusVersion>,
+    #[serde(default)]
+    players: Option<StatusPlayers>,
+    #[serde(default)]
+    description: Value,
+}
+
+#[derive(Deserialize)]
+struct StatusVersion {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct StatusPlayers {
+    max: u32,
+    online: u32,
+    #[serde(default)]
+    sample: Vec<SamplePlayer>,
+}
+
+#[derive(Deserialize)]
+struct SamplePlayer {
+    name: String,
+}
+
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+async fn read_varint(reader: &mut (impl AsyncRead + Unpin)) -> Result<i32, PingError> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let byte = reader.read_u8().await?;
+        value |= ((byte & 0x7f) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(PingError::Protocol("VarInt too long".to_string()))
+}
+
+fn packet(id: i32, data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    write_varint(&mut body, id);
+    body.extend_from_slice(data);
+    let mut framed = Vec::new();
+    write_varint(&mut framed, body.len() as i32);
+    framed.extend(body);
+    framed
+}
+
+/// The handshake switching the connection to status, followed by the status request
+fn status_request(host: &str, port: u16) -> Vec<u8> {
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, ANY_PROTOCOL);
+    write_varint(&mut handshake, host.len() as i32);
+    handshake.extend_from_slice(host.as_bytes());
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, STATUS_STATE);
+    let mut request = packet(0x00, &handshake);
+    request.extend(packet(0x00, &[]));
+    request
+}
+
+/// Send the status request and return the JSON the server answers with
+async fn exchange_status<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S, host: &str, port: u16) -> Result<String, PingError> {
+    stream.write_all(&status_request(host, port)).await?;
+    let length = read_varint(stream).await?;
+    if length <= 0 || length as usize > MAX_STATUS {
+        return Err(PingError::Protocol(format!("packet length {}", length)));
+    }
+    let mut body = vec![0; length as usize];
+    stream.read_exact(&mut body).await?;
+    let mut body = body.as_slice();
+    let id = read_varint(&mut body).await?;
+    if id != 0x00 {
+        return Err(PingError::Protocol(format!("packet id {:#x}", id)));
+    }
+    let json_length = read_varint(&mut body).await?;
+    let json = usize::try_from(json_length)
+        .ok()
+        .and_then(|len| body.get(..len))
+        .ok_or_else(|| PingError::Protocol("status cut short".to_string()))?;
+    Ok(String::from_utf8_lossy(json).into_owned())
+}
+
+/// The text of a chat component - a string, a list of them, or an object with its own
+/// text and extra parts
+fn chat_text(component: &Value) -> String {
+    match component {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts.iter().map(chat_text).collect(),
+        Value::Object(fields) => {
+            let mut text = fields.get("text").map(chat_text).unwrap_or_default();
+            if let Some(extra) = fields.get("extra") {
+                text.push_str(&chat_text(extra));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// Drop the § colour and style codes
+fn strip_codes(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            plain.push(c);
+        }
+    }
+    plain.lines().map(str::trim).collect::<Vec<_>>().join("\n")
+}
+
+fn parse_status(json: &str) -> Result<ServerPing, PingError> {
+    let status: Status = serde_json::from_str(json).map_err(|e| PingError::Protocol(e.to_string()))?;
+    let (players, max_players, sample) = match status.players {
+        Some(p) => (p.online, p.max, p.sample),
+        None => (0, 0, Vec::new()),
+    };
+    Ok(ServerPing {
+        motd: strip_codes(&chat_text(&status.description)),
+        version: status.version.map(|v| strip_codes(&v.name)).unwrap_or_default(),
+        players,
+        max_players,
+        player_names: sample.into_iter().map(|p| p.name).filter(|name| !name.is_empty()).collect(),
+    })
+}
+
+fn unconnected_ping() -> Vec<u8> {
+    let mut ping = vec![UNCONNECTED_PING];
+    ping.extend_from_slice(&chrono::Utc::now().timestamp_millis().to_be_bytes());
+    ping.extend_from_slice(&RAKNET_MAGIC);
+    ping.extend_from_slice(&rand_guid().to_be_bytes());
+    ping
+}
+
+/// Any id does for the client's GUID - it only has to differ between clients
+fn rand_guid() -> i64 {
+    uuid::Uuid::new_v4().as_u64_pair().0 as i64
+}
+
+/// The pong's fields: MCPE;MOTD;protocol;version;players;max players;server id;world;...
+fn parse_pong(pong: &[u8]) -> Result<ServerPing, PingError> {
+    // id, time, server GUID and magic come before the text
+    const TEXT_AT: usize = 1 + 8 + 8 + 16;
+    if pong.first() != Some(&UNCONNECTED_PONG) || pong.len() < TEXT_AT + 2 {
+        return Err(PingError::Protocol("not an unconnected pong".to_string()));
+    }
+    let length = u16::from_be_bytes([pong[TEXT_AT], pong[TEXT_AT + 1]]) as usize;
+    let text = pong
+        .get(TEXT_AT + 2..TEXT_AT + 2 + length)
+        .ok_or_else(|| PingError::Protocol("pong cut short".to_string()))?;
+    let text = String::from_utf8_lossy(text);
+    let fields: Vec<&str> = text.split(';').collect();
+    if fields.len() < 6 {
+        return Err(PingError::Protocol(format!("pong \"{}\"", text)));
+    }
+    let number = |field: &str| field.trim().parse().map_err(|_| PingError::Protocol(format!("player count \"{}\"", field)));
+    Ok(ServerPing {
+        motd: strip_codes(fields[1]),
+        version: fields[3].to_string(),
+        players: number(fields[4])?,
+        max_players: number(fields[5])?,
+        player_names: Vec::new(),
+    })
+}
+
+pub async fn java(addr: SocketAddr) -> Result<ServerPing, PingError> {
+    let json = tokio::time::timeout(TIMEOUT, async {
+        let mut stream = TcpStream::connect(addr).await?;
+        exchange_status(&mut stream, &addr.ip().to_string(), addr.port()).await
+    })
+    .await
+    .map_err(|_| PingError::Timeout)??;
+    parse_status(&json)
+}
+
+pub async fn bedrock(addr: SocketAddr) -> Result<ServerPing, PingError> {
+    let socket = UdpSocket::bind(if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" }).await?;
+    socket.connect(addr).await?;
+    socket.send(&unconnected_ping()).await?;
+    let mut buf = vec![0u8; 1500];
+    let len = tokio::time::timeout(TIMEOUT, socket.recv(&mut buf)).await.map_err(|_| PingError::Timeout)??;
+    parse_pong(&buf[..len])
+}
+
+/// Ping a server the way its edition answers
+pub async fn ping(edition: Edition, addr: SocketAddr) -> Result<ServerPing, PingError> {
+    match edition {
+        Edition::Java => java(addr).await,
+        Edition::Bedrock => bedrock(addr).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        for (value, bytes) in [(0, vec![0x00]), (300, vec![0xac, 0x02]), (-1, vec![0xff, 0xff, 0xff, 0xff, 0x0f])] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, value);
+            assert_eq!(buf, bytes);
+        }
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let json = r#"{
+            "version": {"name": "Paper 1.21.1", "protocol": 767},
+            "players": {"max": 20, "online": 2, "sample": [{"name": "Steve", "id": "x"}, {"name": "Alex", "id": "y"}]},
+            "description": {"text": "§aWelcome ", "extra": [{"text": "home"}, "!"]},
+            "favicon": "data:image/png;base64,AAAA"
+        }"#;
+        let ping = parse_status(json).unwrap();
+        assert_eq!(ping.motd, "Welcome home!");
+        assert_eq!(ping.version, "Paper 1.21.1");
+        assert_eq!((ping.players, ping.max_players), (2, 20));
+        assert_eq!(ping.player_names, vec!["Steve", "Alex"]);
+        assert_eq!(parse_status(r#"{"description": "A server"}"#).unwrap().motd, "A server");
+    }
+
+    #[test]
+    fn test_parse_pong() {
+        let text = b"MCPE;\xc2\xa7bDedicated Server;712;1.21.20;3;10;1234;Bedrock level;Survival;1;19132;19133;";
+        let mut pong = vec![UNCONNECTED_PONG];
+        pong.extend_from_slice(&[0; 16]);
+        pong.extend_from_slice(&RAKNET_MAGIC);
+        pong.extend_from_slice(&(text.len() as u16).to_be_bytes());
+        pong.extend_from_slice(text);
+        let ping = parse_pong(&pong).unwrap();
+        assert_eq!(ping.motd, "Dedicated Server");
+        assert_eq!(ping.version, "1.21.20");
+        assert_eq!((ping.players, ping.max_players), (3, 10));
+        assert!(parse_pong(&pong[..30]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_status_exchange() {
+        let (mut client, mut server) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            let handshake_length = read_varint(&mut server).await.unwrap();
+            let mut handshake = vec![0; handshake_length as usize];
+            server.read_exact(&mut handshake).await.unwrap();
+            assert_eq!(&handshake[handshake.len() - 3..], &[0x63, 0xdd, STATUS_STATE as u8]);
+            let mut request = [0; 2];
+            server.read_exact(&mut request).await.unwrap();
+            assert_eq!(request, [1, 0x00]);
+
+            let json = br#"{"description":"hi"}"#;
+            let mut body = Vec::new();
+            write_varint(&mut body, json.len() as i32);
+            body.extend_from_slice(json);
+            server.write_all(&packet(0x00, &body)).await.unwrap();
+        });
+        assert_eq!(exchange_status(&mut client, "127.0.0.1", 25565).await.unwrap(), r#"{"description":"hi"}"#);
+    }
+}
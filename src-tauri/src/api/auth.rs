@@ -0,0 +1,65 @@
+// API token - shared secret remote clients send as a bearer token
+
+use crate::security::{secrets, users, Caller};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const TOKEN_KEY: &str = "api-token";
+
+/// Load the API token from the keychain, generating one on first use
+pub async fn load_or_create_token() -> Result<String, String> {
+    if let Some(token) = secrets::get_secret(TOKEN_KEY).await {
+        return Ok(token);
+    }
+
+    // Older versions kept the token in a plain file - move it over
+    let legacy_path = get_legacy_token_path();
+    if let Some(token) = std::fs::read_to_string(&legacy_path)
+        .ok()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+    {
+        secrets::set_secret(TOKEN_KEY, &token).await?;
+        std::fs::remove_file(&legacy_path).ok();
+        tracing::info!("Moved API token from {} to the keychain", legacy_path.display());
+        return Ok(token);
+    }
+
+    let token = generate_token();
+    secrets::set_secret(TOKEN_KEY, &token).await?;
+    tracing::info!("Generated a new API token");
+    Ok(token)
+}
+
+pub fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// Work out who a token belongs to - the app's own token is an admin
+pub async fn authenticate(api_token: &str, provided: &str) -> Option<Caller> {
+    if provided.is_empty() {
+        return None;
+    }
+    if token_matches(api_token, provided) {
+        return Some(Caller::Admin);
+    }
+    users::users_with_tokens()
+        .await
+        .into_iter()
+        .find(|(_, token)| token_matches(token, provided))
+        .map(|(user, _)| Caller::User(user))
+}
+
+/// Compare without short-circuiting so timing doesn't leak the token
+pub fn token_matches(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn get_legacy_token_path() -> PathBuf {
+    serverwave_core::paths::app_root().join("api-token")
+}
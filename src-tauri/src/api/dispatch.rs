@@ -0,0 +1,524 @@
+// Command dispatcher - runs Tauri commands by name for the remote API
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::ServerState;
+use crate::commands::{
+    actions, backups, content, dashboard, database, diagnostics, docker, files, games, health, hooks, import, minecraft, network, onboarding,
+    palworld, players, proxy, remote, schedules, security, server, settings, state, steam, users, wipes,
+};
+use crate::security::{with_caller, Caller, Permission};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::error::AppError;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug)]
+pub enum DispatchError {
+    UnknownCommand(String),
+    InvalidArgs(String),
+    Forbidden(String),
+    Command(AppError),
+}
+
+impl std::fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DispatchError::UnknownCommand(name) => write!(f, "Unknown command '{}'", name),
+            DispatchError::Forbidden(name) => write!(f, "Not allowed to run '{}'", name),
+            DispatchError::InvalidArgs(e) => write!(f, "{}", e),
+            DispatchError::Command(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl DispatchError {
+    /// `{"error": message, "kind": ...}` plus the command error's own fields
+    pub fn to_json(&self) -> Value {
+        let mut body = match self {
+            DispatchError::Command(e) => serde_json::to_value(e).unwrap_or_else(|_| json!({})),
+            DispatchError::UnknownCommand(_) => json!({ "kind": "UnknownCommand" }),
+            DispatchError::InvalidArgs(_) => json!({ "kind": "InvalidArgs" }),
+            DispatchError::Forbidden(_) => json!({ "kind": "Forbidden" }),
+        };
+        body["error"] = Value::String(self.to_string());
+        body
+    }
+}
+
+/// Arguments as the frontend sends them to `invoke` - an object with camelCase keys
+struct Args(Map<String, Value>);
+
+impl Args {
+    fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T, DispatchError> {
+        let value = self.0.get(key).cloned().unwrap_or(Value::Null);
+        serde_json::from_value(value)
+            .map_err(|e| DispatchError::InvalidArgs(format!("Invalid argument '{}': {}", key, e)))
+    }
+}
+
+fn reply<T: Serialize>(result: Result<T, AppError>) -> Result<Value, DispatchError> {
+    let value = result.map_err(DispatchError::Command)?;
+    serde_json::to_value(value).map_err(|e| DispatchError::Command(e.into()))
+}
+
+/// What a command needs. Server-scoped commands are checked against their `serverId`.
+pub(crate) fn required_permission(command: &str) -> Permission {
+    match command {
+        "list_servers" | "get_server_status" | "get_server_stats" | "get_server_processes"
+        | "get_server_logs" | "attach_server" | "detach_server" | "get_server_disk_usage"
+        | "check_needs_install" | "get_server_health" | "get_connection_info" | "get_server_network"
+        | "check_port_reachable" | "get_tunnel_status" | "list_available_games"
+        | "get_game_config" | "check_docker_status" | "get_docker_info" | "get_lock_status"
+        | "get_current_user" | "get_install_queue" | "check_image_platform"
+        | "detect_docker_sockets" | "get_wipe_schedule"
+        | "list_game_actions" | "run_onboarding_checks" | "get_player_sessions" | "query_server"
+        | "get_startup_reconciliation" | "get_disk_space" | "get_palworld_status"
+        | "get_crash_report" | "get_dashboard_summary" | "list_backups" | "list_schedules" => Permission::View,
+        "start_server" | "stop_server" => Permission::Control,
+        "send_command" | "send_rcon_command" | "run_game_action" | "palworld_announce" | "palworld_kick" | "palworld_ban"
+        | "palworld_unban" | "palworld_save" => Permission::Console,
+        _ => Permission::Manage,
+    }
+}
+
+/// Run a command by its Tauri name with the same arguments the frontend would pass.
+/// Callers are already token-authenticated, so the app lock doesn't apply.
+pub async fn dispatch(
+    app: &AppHandle,
+    caller: Caller,
+    command: &str,
+    args: Value,
+) -> Result<Value, DispatchError> {
+    let server_id = args.get("serverId").and_then(|v| v.as_str());
+    if !caller.can(required_permission(command), server_id) {
+        return Err(DispatchError::Forbidden(command.to_string()));
+    }
+    with_caller(caller, run_command(app, command, args)).await
+}
+
+async fn run_command(app: &AppHandle, command: &str, args: Value) -> Result<Value, DispatchError> {
+    let args = match args {
+        Value::Object(map) => Args(map),
+        Value::Null => Args(Map::new()),
+        _ => return Err(DispatchError::InvalidArgs("Arguments must be a JSON object".to_string())),
+    };
+    let app = app.clone();
+    let servers = || app.state::<ServerState>();
+    let games = || app.state::<GamesState>();
+    let dockers = || app.state::<DockerState>();
+
+    match command {
+        // Servers
+        "create_server" => reply(server::create_server(args.get("request")?, servers(), games(), dockers()).await),
+        "start_server" => reply(
+            server::start_server(
+                args.get("serverId")?,
+                args.get("force")?,
+                args.get("movePort")?,
+                app.clone(),
+                servers(),
+                games(),
+                dockers(),
+            )
+            .await,
+        ),
+        "stop_server" => reply(
+            server::stop_server(args.get("serverId")?, app.clone(), servers(), games(), dockers()).await,
+        ),
+        "delete_server" => reply(
+            server::delete_server(
+                args.get("serverId")?,
+                args.get("confirmName")?,
+                args.get("deleteData")?,
+                args.get("trash")?,
+                servers(),
+                games(),
+                dockers(),
+            )
+            .await,
+        ),
+        "list_servers" => reply(server::list_servers(dockers()).await),
+        "get_dashboard_summary" => reply(dashboard::get_dashboard_summary(dockers()).await),
+        "get_server_status" => reply(server::get_server_status(args.get("serverId")?, dockers()).await),
+        "send_command" => {
+            reply(server::send_command(args.get("serverId")?, args.get("command")?, games(), dockers()).await)
+        }
+        "send_rcon_command" => {
+            reply(server::send_rcon_command(args.get("serverId")?, args.get("command")?, games()).await)
+        }
+        "get_server_stats" => reply(server::get_server_stats(args.get("serverId")?, dockers()).await),
+        "get_server_processes" => reply(server::get_server_processes(args.get("serverId")?, dockers()).await),
+        "get_server_logs" => {
+            reply(server::get_server_logs(args.get("serverId")?, args.get("lines")?, dockers()).await)
+        }
+        "attach_server" => {
+            reply(server::attach_server(args.get("serverId")?, app.clone(), servers(), dockers()).await)
+        }
+        "detach_server" => reply(server::detach_server(args.get("serverId")?, servers()).await),
+        "update_server_config" => {
+            reply(server::update_server_config(args.get("serverId")?, args.get("config")?, servers(), games()).await)
+        }
+        "update_server_env" => reply(server::update_server_env(args.get("serverId")?, args.get("env")?, servers(), games()).await),
+        "update_server_install_network" => reply(
+            server::update_server_install_network(args.get("serverId")?, args.get("installNetwork")?, servers()).await,
+        ),
+        "update_server_maintenance" => {
+            reply(server::update_server_maintenance(args.get("serverId")?, args.get("maintenance")?, servers()).await)
+        }
+        "set_server_auto_restart" => {
+            reply(server::set_server_auto_restart(args.get("serverId")?, args.get("enabled")?, servers()).await)
+        }
+        "set_start_schedule" => {
+            reply(server::set_start_schedule(args.get("serverId")?, args.get("schedule")?, servers()).await)
+        }
+        "get_crash_report" => reply(server::get_crash_report(args.get("serverId")?).await),
+        "apply_config" => reply(server::apply_config(args.get("serverId")?, app.clone(), servers(), games(), dockers()).await),
+        "preview_startup" => reply(server::preview_startup(args.get("serverId")?, games()).await),
+        "preview_config_changes" => reply(server::preview_config_changes(args.get("serverId")?, games()).await),
+        "list_backups" => reply(backups::list_backups(args.get("serverId")?).await),
+        "create_backup" => reply(backups::create_backup(args.get("serverId")?, args.get("note")?, app.clone()).await),
+        "restore_backup" => {
+            reply(backups::restore_backup(args.get("serverId")?, args.get("backupId")?, app.clone()).await)
+        }
+        "delete_backup" => reply(backups::delete_backup(args.get("serverId")?, args.get("backupId")?).await),
+        "set_backup_schedule" => {
+            reply(backups::set_backup_schedule(args.get("serverId")?, args.get("schedule")?, servers()).await)
+        }
+        "set_backup_target" => reply(
+            backups::set_backup_target(args.get("serverId")?, args.get("target")?, args.get("secret")?, servers()).await,
+        ),
+        "remove_backup_target" => {
+            reply(backups::remove_backup_target(args.get("serverId")?, args.get("targetId")?, servers()).await)
+        }
+        "test_backup_target" => {
+            reply(backups::test_backup_target(args.get("serverId")?, args.get("targetId")?).await)
+        }
+        "push_backup" => reply(
+            backups::push_backup(args.get("serverId")?, args.get("backupId")?, args.get("targetId")?, app.clone()).await,
+        ),
+        "list_remote_backups" => {
+            reply(backups::list_remote_backups(args.get("serverId")?, args.get("targetId")?).await)
+        }
+        "pull_backup" => reply(
+            backups::pull_backup(args.get("serverId")?, args.get("targetId")?, args.get("backupId")?, app.clone()).await,
+        ),
+        "get_server_disk_usage" => reply(server::get_server_disk_usage(args.get("serverId")?).await),
+        "get_player_sessions" => {
+            reply(players::get_player_sessions(args.get("serverId")?, args.get("range")?).await)
+        }
+        "query_server" => reply(players::query_server(args.get("serverId")?, games()).await),
+        "run_install_script" => reply(
+            server::run_install_script(args.get("serverId")?, app.clone(), servers(), games()).await,
+        ),
+        "retry_install" => reply(
+            server::retry_install(args.get("serverId")?, app.clone(), servers(), games()).await,
+        ),
+        "reinstall_server" => reply(
+            server::reinstall_server(args.get("serverId")?, app.clone(), servers(), games(), dockers()).await,
+        ),
+        "update_server_game" => reply(
+            server::update_server_game(args.get("serverId")?, app.clone(), servers(), games(), dockers()).await,
+        ),
+        "check_game_updates" => reply(server::check_game_updates(args.get("serverId")?).await),
+        "get_pending_updates" => reply(server::get_pending_updates().await),
+        "get_install_queue" => reply(server::get_install_queue().await),
+        "pin_game_version" => reply(server::pin_game_version(args.get("serverId")?, args.get("build")?, servers()).await),
+        "rollback_game_version" => reply(
+            server::rollback_game_version(args.get("serverId")?, args.get("build")?, app.clone(), servers(), games())
+                .await,
+        ),
+        "check_needs_install" => {
+            reply(server::check_needs_install(args.get("serverId")?, games()).await)
+        }
+        "get_server_health" => reply(health::get_server_health(args.get("serverId")?, games(), dockers()).await),
+
+        // Networking
+        "set_server_upnp" => {
+            reply(network::set_server_upnp(args.get("serverId")?, args.get("enabled")?, servers()).await)
+        }
+        "test_port_mapping" => reply(network::test_port_mapping(args.get("serverId")?).await),
+        "open_firewall_ports" => {
+            reply(network::open_firewall_ports(args.get("serverId")?, servers(), games()).await)
+        }
+        "close_firewall_ports" => reply(network::close_firewall_ports(args.get("serverId")?, servers()).await),
+        "get_connection_info" => reply(
+            network::get_connection_info(args.get("serverId")?, args.get("refresh")?, games(), dockers()).await,
+        ),
+        "get_server_network" => reply(network::get_server_network(args.get("serverId")?, games(), dockers()).await),
+        "check_port_reachable" => {
+            reply(network::check_port_reachable(args.get("serverId")?, games(), dockers()).await)
+        }
+        "set_server_tunnel" => reply(
+            network::set_server_tunnel(args.get("serverId")?, args.get("tunnel")?, servers(), games(), dockers()).await,
+        ),
+        "get_tunnel_status" => reply(network::get_tunnel_status(args.get("serverId")?, dockers()).await),
+        "get_port_allocations" => reply(network::get_port_allocations(games()).await),
+        "create_proxy_network" => {
+            reply(proxy::create_proxy_network(args.get("request")?, servers(), games(), dockers()).await)
+        }
+        "list_proxy_networks" => reply(proxy::list_proxy_networks().await),
+        "delete_proxy_network" => reply(proxy::delete_proxy_network(args.get("networkId")?, servers(), dockers()).await),
+
+        // Settings and Docker
+        "get_app_settings" => reply(settings::get_app_settings().await),
+        "get_disk_space" => reply(settings::get_disk_space(app.clone()).await),
+        "update_app_settings" => {
+            reply(settings::update_app_settings(args.get("settings")?, app.clone()).await)
+        }
+        "set_data_directory" => {
+            reply(settings::set_data_directory(args.get("path")?, app.clone(), games()).await)
+        }
+        "export_app_state" => reply(state::export_app_state(args.get("dest")?, games()).await),
+        "import_app_state" => reply(state::import_app_state(args.get("path")?, app.clone(), games()).await),
+        "check_docker_status" => reply(docker::check_docker_status(dockers()).await),
+        "get_docker_info" => reply(docker::get_docker_info(dockers()).await),
+        "run_onboarding_checks" => reply(onboarding::run_onboarding_checks(games()).await),
+        "detect_docker_sockets" => reply(docker::detect_docker_sockets().await),
+        "test_docker_connection" => reply(docker::test_docker_connection(args.get("connection")?).await),
+        "wake_docker_host" => reply(docker::wake_docker_host(app.clone()).await),
+        "refresh_game_images" => reply(docker::refresh_game_images(app.clone()).await),
+        "get_stale_images" => reply(docker::get_stale_images().await),
+        "get_install_images" => reply(docker::get_install_images(app.clone()).await),
+        "clean_install_images" => reply(docker::clean_install_images(app.clone()).await),
+        "get_startup_reconciliation" => reply(docker::get_startup_reconciliation().await),
+        "check_image_platform" => reply(docker::check_image_platform(args.get("image")?, dockers()).await),
+        "recreate_server_container" => {
+            reply(docker::recreate_server_container(args.get("serverId")?, app.clone()).await)
+        }
+
+        // Games
+        "list_available_games" => reply(games::list_available_games(games()).await),
+        "get_game_config" => reply(games::get_game_config(args.get("gameType")?, games()).await),
+        "add_custom_game" => reply(games::add_custom_game(args.get("game")?, app.clone(), games()).await),
+        "update_game" => reply(games::update_game(args.get("game")?, app.clone(), games()).await),
+        "lint_install_script" => reply(games::lint_install_script(args.get("script")?, args.get("image")?, dockers()).await),
+        "delete_game" => reply(games::delete_game(args.get("gameType")?, app.clone(), games()).await),
+        "export_game" => reply(games::export_game(args.get("gameType")?, games()).await),
+        "export_all_custom_games" => reply(games::export_all_custom_games(games()).await),
+        "import_game" => reply(games::import_game(args.get("json")?, app.clone(), games()).await),
+        "import_games" => reply(games::import_games(args.get("json")?, app.clone(), games()).await),
+        "reset_games_to_defaults" => reply(games::reset_games_to_defaults(games()).await),
+        "get_games_config_path" => reply(Ok(games::get_games_config_path())),
+        "get_game_sync_status" => reply(games::get_game_sync_status().await),
+        "pull_game_library" => reply(games::pull_game_library(app.clone()).await),
+        "push_game_library" => reply(games::push_game_library(app.clone()).await),
+        "set_game_sync_token" => reply(games::set_game_sync_token(args.get("token")?).await),
+
+        // Diagnostics
+        "export_diagnostics" => reply(diagnostics::export_diagnostics(games(), dockers()).await),
+        "get_app_logs" => {
+            reply(diagnostics::get_app_logs(args.get("filter")?, args.get("lines")?).await)
+        }
+
+        // Content
+        "search_modrinth" => reply(content::search_modrinth(args.get("query")?, args.get("serverId")?).await),
+        "get_modrinth_versions" => reply(
+            content::get_modrinth_versions(args.get("serverId")?, args.get("project")?).await,
+        ),
+        "install_mod" => reply(
+            content::install_mod(args.get("serverId")?, args.get("project")?, args.get("version")?).await,
+        ),
+        "check_mod_updates" => reply(content::check_mod_updates(args.get("serverId")?).await),
+        "list_installed_content" => reply(content::list_installed_content(args.get("serverId")?).await),
+        "update_all_content" => reply(content::update_all_content(args.get("serverId")?).await),
+        "search_curseforge" => reply(
+            content::search_curseforge(args.get("query")?, args.get("serverId")?, args.get("modpacks")?).await,
+        ),
+        "install_curseforge_mod" => reply(
+            content::install_curseforge_mod(args.get("serverId")?, args.get("modId")?, args.get("fileId")?).await,
+        ),
+        "install_curseforge_modpack" => reply(
+            content::install_curseforge_modpack(
+                args.get("serverId")?,
+                args.get("modId")?,
+                args.get("fileId")?,
+                app.clone(),
+            )
+            .await,
+        ),
+        "get_server_versions" => reply(content::get_server_versions(args.get("flavor")?).await),
+        "get_content_cache" => reply(content::get_content_cache().await),
+        "clear_content_cache" => reply(content::clear_content_cache().await),
+        "change_server_version" => reply(
+            content::change_server_version(
+                args.get("serverId")?,
+                args.get("flavor")?,
+                args.get("version")?,
+                app.clone(),
+                games(),
+            )
+            .await,
+        ),
+
+        // Minecraft
+        "list_worlds" => reply(minecraft::list_worlds(args.get("serverId")?).await),
+        "set_active_world" => reply(minecraft::set_active_world(args.get("serverId")?, args.get("name")?, dockers()).await),
+        "import_world" => reply(
+            minecraft::import_world(args.get("serverId")?, args.get("path")?, args.get("name")?, dockers()).await,
+        ),
+        "delete_world" => reply(
+            minecraft::delete_world(args.get("serverId")?, args.get("name")?, args.get("archive")?, dockers()).await,
+        ),
+        "list_players" => reply(minecraft::list_players(args.get("serverId")?, args.get("list")?).await),
+        "add_player" => reply(
+            minecraft::add_player(args.get("serverId")?, args.get("list")?, args.get("name")?, args.get("reason")?, dockers())
+                .await,
+        ),
+        "remove_player" => reply(
+            minecraft::remove_player(args.get("serverId")?, args.get("list")?, args.get("name")?, dockers()).await,
+        ),
+        "list_datapacks" => reply(minecraft::list_datapacks(args.get("serverId")?).await),
+        "install_datapack" => reply(minecraft::install_datapack(args.get("serverId")?, args.get("source")?).await),
+        "remove_datapack" => reply(minecraft::remove_datapack(args.get("serverId")?, args.get("name")?).await),
+        "get_resource_pack" => reply(minecraft::get_resource_pack(args.get("serverId")?).await),
+        "set_resource_pack" => reply(
+            minecraft::set_resource_pack(args.get("serverId")?, args.get("url")?, args.get("required")?).await,
+        ),
+        "host_resource_pack" => reply(
+            minecraft::host_resource_pack(
+                args.get("serverId")?,
+                args.get("path")?,
+                args.get("publicUrl")?,
+                args.get("required")?,
+            )
+            .await,
+        ),
+        "clear_resource_pack" => reply(minecraft::clear_resource_pack(args.get("serverId")?).await),
+
+        // Game actions
+        "list_game_actions" => reply(actions::list_game_actions(args.get("serverId")?, games()).await),
+        "run_game_action" => reply(
+            actions::run_game_action(args.get("serverId")?, args.get("action")?, app.clone(), games()).await,
+        ),
+
+        // Rust wipes
+        "get_wipe_schedule" => reply(wipes::get_wipe_schedule(args.get("serverId")?).await),
+        "set_wipe_schedule" => reply(wipes::set_wipe_schedule(args.get("serverId")?, args.get("schedule")?).await),
+        "wipe_rust_server" => reply(
+            wipes::wipe_rust_server(
+                args.get("serverId")?,
+                args.get("kind")?,
+                args.get("newSeed")?,
+                args.get("hostnameDate")?,
+                app.clone(),
+            )
+            .await,
+        ),
+
+        // Scheduled tasks
+        "add_schedule" => reply(schedules::add_schedule(args.get("serverId")?, args.get("schedule")?).await),
+        "list_schedules" => reply(schedules::list_schedules(args.get("serverId")?).await),
+        "remove_schedule" => reply(schedules::remove_schedule(args.get("scheduleId")?).await),
+        "run_schedule_now" => reply(schedules::run_schedule_now(args.get("scheduleId")?, app.clone()).await),
+
+        // Palworld
+        "get_palworld_status" => reply(palworld::get_palworld_status(args.get("serverId")?, games()).await),
+        "palworld_announce" => reply(
+            palworld::palworld_announce(args.get("serverId")?, args.get("message")?, games()).await,
+        ),
+        "palworld_kick" => reply(
+            palworld::palworld_kick(args.get("serverId")?, args.get("userId")?, args.get("message")?, games()).await,
+        ),
+        "palworld_ban" => reply(
+            palworld::palworld_ban(args.get("serverId")?, args.get("userId")?, args.get("message")?, games()).await,
+        ),
+        "palworld_unban" => reply(palworld::palworld_unban(args.get("serverId")?, args.get("userId")?, games()).await),
+        "palworld_save" => reply(palworld::palworld_save(args.get("serverId")?, games()).await),
+
+        // Database sidecars
+        "get_database" => reply(database::get_database(args.get("serverId")?, dockers()).await),
+        "add_database" => reply(database::add_database(args.get("serverId")?, args.get("kind")?, servers(), dockers()).await),
+        "remove_database" => reply(
+            database::remove_database(args.get("serverId")?, args.get("deleteData")?, servers(), dockers()).await,
+        ),
+
+        // Steam
+        "get_steam_account" => reply(steam::get_steam_account().await),
+        "set_steam_account" => reply(steam::set_steam_account(args.get("username")?, args.get("password")?).await),
+        "clear_steam_account" => reply(steam::clear_steam_account().await),
+        "submit_steam_guard_code" => reply(
+            steam::submit_steam_guard_code(args.get("serverId")?, args.get("code")?, dockers()).await,
+        ),
+        "list_server_tokens" => reply(steam::list_server_tokens().await),
+        "add_server_token" => reply(steam::add_server_token(args.get("label")?, args.get("token")?).await),
+        "remove_server_token" => reply(steam::remove_server_token(args.get("tokenId")?).await),
+        "assign_server_token" => reply(
+            steam::assign_server_token(args.get("tokenId")?, args.get("serverId")?).await,
+        ),
+        "check_server_token" => reply(steam::check_server_token(args.get("tokenId")?).await),
+        "set_steam_web_api_key" => reply(steam::set_steam_web_api_key(args.get("key")?).await),
+
+        // Hooks
+        "list_hooks" => reply(hooks::list_hooks().await),
+        "save_hook" => reply(hooks::save_hook(args.get("hook")?).await),
+        "delete_hook" => reply(hooks::delete_hook(args.get("hookId")?).await),
+        "test_hook" => reply(hooks::test_hook(args.get("hookId")?, args.get("serverId")?, app.clone()).await),
+
+        // Import
+        "list_pterodactyl_servers" => reply(
+            import::list_pterodactyl_servers(args.get("panelUrl")?, args.get("apiKey")?).await,
+        ),
+        "import_pterodactyl_server" => reply(
+            import::import_pterodactyl_server(args.get("request")?, app.clone(), servers(), games()).await,
+        ),
+        "scan_local_server" => {
+            reply(import::scan_local_server(args.get("path")?, args.get("gameType")?, games()).await)
+        }
+        "import_local_server" => reply(import::import_local_server(args.get("request")?, app.clone(), servers(), games()).await),
+        "export_server" => {
+            reply(import::export_server(args.get("serverId")?, args.get("dest")?, app.clone(), games()).await)
+        }
+        "import_server" => {
+            reply(import::import_server(args.get("path")?, args.get("name")?, app.clone(), servers(), games()).await)
+        }
+
+        // Security
+        "get_lock_status" => reply(security::get_lock_status().await),
+        "set_app_password" => reply(
+            security::set_app_password(args.get("currentPassword")?, args.get("newPassword")?).await,
+        ),
+        "unlock_app" => reply(security::unlock_app(args.get("password")?).await),
+        "lock_app" => reply(security::lock_app().await),
+        "get_api_token" => reply(security::get_api_token().await),
+        "get_audit_journal" => reply(security::get_audit_journal().await),
+
+        // Users
+        "list_users" => reply(users::list_users().await),
+        "add_user" => reply(
+            users::add_user(args.get("name")?, args.get("role")?, args.get("servers")?, args.get("readOnly")?).await,
+        ),
+        "update_user" => reply(
+            users::update_user(args.get("userId")?, args.get("role")?, args.get("servers")?, args.get("readOnly")?).await,
+        ),
+        "remove_user" => reply(users::remove_user(args.get("userId")?).await),
+        "get_current_user" => reply(users::get_current_user().await),
+        "get_remote_access" => reply(remote::get_remote_access().await),
+        "create_pairing_code" => reply(remote::create_pairing_code().await),
+        "regenerate_api_certificate" => reply(remote::regenerate_api_certificate().await),
+
+        // Files
+        "list_directory" => reply(files::list_directory(args.get("path")?).await),
+        "read_file_text" => reply(files::read_file_text(args.get("path")?).await),
+        "write_file_text" => {
+            reply(files::write_file_text(args.get("path")?, args.get("content")?).await)
+        }
+        "create_file" => reply(files::create_file(args.get("path")?, args.get("content")?).await),
+        "create_directory" => reply(files::create_directory(args.get("path")?).await),
+        "delete_path" => reply(files::delete_path(args.get("path")?).await),
+        "rename_path" => {
+            reply(files::rename_path(args.get("oldPath")?, args.get("newName")?).await)
+        }
+        "move_path" => {
+            reply(files::move_path(args.get("source")?, args.get("destinationDir")?).await)
+        }
+        "copy_path" => {
+            reply(files::copy_path(args.get("source")?, args.get("destinationDir")?).await)
+        }
+        "get_file_info" => reply(files::get_file_info(args.get("path")?).await),
+        "get_server_roots" => reply(files::get_server_roots(args.get("serverId")?, games()).await),
+
+        _ => Err(DispatchError::UnknownCommand(command.to_string())),
+    }
+}
@@ -0,0 +1,195 @@
+// HTTP server - REST/JSON access to the dispatcher
+
+use super::auth;
+use super::dispatch::{dispatch, DispatchError};
+use super::pairing;
+use super::tls::{self, TlsSource};
+use super::ws::{self, ApiEvent};
+use crate::security::Caller;
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use tokio::sync::broadcast;
+
+pub const DEFAULT_API_PORT: u16 = 7450;
+
+lazy_static::lazy_static! {
+    /// Where the API is listening, once it is
+    static ref LISTENING: Mutex<Option<SocketAddr>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiOptions {
+    pub bind: SocketAddr,
+    /// None serves plain HTTP
+    pub tls: Option<TlsSource>,
+}
+
+impl ApiOptions {
+    /// Listen on every interface so other machines can connect (agent mode), over HTTPS
+    pub fn public() -> Self {
+        Self {
+            bind: SocketAddr::from(([0, 0, 0, 0], DEFAULT_API_PORT)),
+            tls: Some(TlsSource::SelfSigned),
+        }
+    }
+}
+
+impl Default for ApiOptions {
+    fn default() -> Self {
+        Self {
+            bind: SocketAddr::from(([127, 0, 0, 1], DEFAULT_API_PORT)),
+            tls: None,
+        }
+    }
+}
+
+/// The address the API listens on, if it's running
+pub fn listening() -> Option<SocketAddr> {
+    *LISTENING.lock().unwrap()
+}
+
+#[derive(Clone)]
+pub(super) struct ApiState {
+    pub app: AppHandle,
+    pub token: Arc<String>,
+    pub events: broadcast::Sender<ApiEvent>,
+}
+
+/// Serve the API until the app exits
+pub async fn serve(app: AppHandle, options: ApiOptions) -> Result<(), String> {
+    let token = auth::load_or_create_token().await?;
+    let state = ApiState {
+        events: ws::forward_app_events(&app),
+        app,
+        token: Arc::new(token),
+    };
+
+    let protected = Router::new()
+        .route("/api/v1/invoke/:command", post(invoke))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    let router = Router::new()
+        .route("/api/v1/health", get(health))
+        .route("/api/v1/pair", post(pair))
+        .route("/api/v1/ws", get(ws::upgrade))
+        .route("/packs/:server_id/:file", get(resource_pack))
+        .merge(protected)
+        .with_state(state);
+
+    let Some(source) = options.tls else {
+        let listener = tokio::net::TcpListener::bind(options.bind)
+            .await
+            .map_err(|e| format!("Failed to bind API on {}: {}", options.bind, e))?;
+        if !options.bind.ip().is_loopback() {
+            tracing::warn!("API reachable from other machines over plain HTTP - tokens are sent unencrypted");
+        }
+        tracing::info!("API listening on http://{}", options.bind);
+        *LISTENING.lock().unwrap() = Some(options.bind);
+        return axum::serve(listener, router).await.map_err(|e| e.to_string());
+    };
+
+    let renewable = matches!(source, TlsSource::Files { .. });
+    let config = tls::load(source).await?;
+    if renewable {
+        tls::spawn_reloader();
+    }
+    let identity = tls::identity().ok_or("The API certificate wasn't loaded")?;
+    tracing::info!(
+        "API listening on https://{} (certificate fingerprint {})",
+        options.bind,
+        identity.fingerprint
+    );
+    *LISTENING.lock().unwrap() = Some(options.bind);
+    axum_server::bind_rustls(options.bind, config)
+        .serve(router.into_make_service())
+        .await
+        .map_err(|e| format!("API on {} stopped: {}", options.bind, e))
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+#[derive(Deserialize)]
+struct PairRequest {
+    code: String,
+    #[serde(default)]
+    name: String,
+}
+
+/// Trade a pairing code for a token - the only call besides health that needs none
+async fn pair(Json(request): Json<PairRequest>) -> Response {
+    match pairing::redeem(&request.code, &request.name).await {
+        Ok(paired) => Json(paired).into_response(),
+        Err(e) => error_response(StatusCode::UNAUTHORIZED, &e),
+    }
+}
+
+/// Resource packs hosted for Minecraft clients, which can't send a token
+async fn resource_pack(Path((server_id, file)): Path<(String, String)>) -> Response {
+    let Some(path) = serverwave_core::server::load_server_config(&server_id)
+        .ok()
+        .and_then(|server| crate::minecraft::packs::hosted_pack(&server.data_path, &file))
+    else {
+        return error_response(StatusCode::NOT_FOUND, "No such resource pack");
+    };
+    match tokio::fs::read(&path).await {
+        Ok(bytes) => ([(header::CONTENT_TYPE, "application/zip")], bytes).into_response(),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+/// Resolve the bearer token to a caller and hand it to the handler
+async fn require_token(State(state): State<ApiState>, mut request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or("");
+
+    match auth::authenticate(&state.token, provided).await {
+        Some(caller) => {
+            request.extensions_mut().insert(caller);
+            next.run(request).await
+        }
+        None => error_response(StatusCode::UNAUTHORIZED, "Missing or invalid API token"),
+    }
+}
+
+async fn invoke(
+    State(state): State<ApiState>,
+    Extension(caller): Extension<Caller>,
+    Path(command): Path<String>,
+    body: Option<Json<Value>>,
+) -> Response {
+    let args = body.map(|Json(v)| v).unwrap_or(Value::Null);
+    match dispatch(&state.app, caller, &command, args).await {
+        Ok(value) => Json(value).into_response(),
+        Err(e) => {
+            let status = match e {
+                DispatchError::UnknownCommand(_) => StatusCode::NOT_FOUND,
+                DispatchError::InvalidArgs(_) => StatusCode::BAD_REQUEST,
+                DispatchError::Forbidden(_) => StatusCode::FORBIDDEN,
+                DispatchError::Command(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            };
+            (status, Json(e.to_json())).into_response()
+        }
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
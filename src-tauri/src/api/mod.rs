@@ -0,0 +1,10 @@
+// Remote API - the command surface over HTTP for headless mode
+
+pub mod auth;
+pub mod dispatch;
+pub mod http;
+pub mod pairing;
+pub mod tls;
+pub mod ws;
+
+pub use http::{serve, ApiOptions};
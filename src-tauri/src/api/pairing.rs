@@ -0,0 +1,140 @@
+// Pairing - a one-time code shown on this machine that a remote app trades for its own admin
+// token, so nobody has to copy the API token around. Codes expire after a few minutes and all
+// of them are dropped after a few wrong guesses.
+
+use super::auth;
+use super::tls::{self, ApiIdentity};
+use crate::security::users::{self, User};
+use crate::security::Role;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+const CODE_TTL_MINUTES: i64 = 10;
+const CODE_LENGTH: usize = 8;
+/// Wrong codes allowed before every pending code is dropped
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+/// No 0/O or 1/I, so codes read out loud or typed from a screen come out right
+const ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingCode {
+    pub code: String,
+    pub expires_at: DateTime<Utc>,
+    /// For comparing with what the remote app shows after pairing
+    pub identity: Option<ApiIdentity>,
+}
+
+/// What a paired client gets back
+#[derive(Debug, Clone, Serialize)]
+pub struct Paired {
+    pub token: String,
+    pub user: User,
+}
+
+#[derive(Default)]
+struct Pending {
+    codes: Vec<(String, DateTime<Utc>)>,
+    failed: u32,
+}
+
+lazy_static::lazy_static! {
+    static ref PENDING: Mutex<Pending> = Mutex::new(Pending::default());
+}
+
+fn generate_code() -> String {
+    let mut bytes = [0u8; CODE_LENGTH];
+    getrandom::getrandom(&mut bytes).expect("the OS has no random source");
+    // 32 symbols divide 256 evenly, so every symbol is equally likely
+    bytes
+        .iter()
+        .map(|b| ALPHABET[*b as usize % ALPHABET.len()] as char)
+        .collect()
+}
+
+/// Codes are shown as ABCD-EFGH; accept them typed any way
+fn normalize(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+pub fn create_code() -> PairingCode {
+    let code = generate_code();
+    let expires_at = Utc::now() + chrono::Duration::minutes(CODE_TTL_MINUTES);
+    let mut pending = PENDING.lock().unwrap();
+    pending.codes.retain(|(_, expires)| *expires > Utc::now());
+    pending.codes.push((code.clone(), expires_at));
+    pending.failed = 0;
+
+    PairingCode {
+        code: format!("{}-{}", &code[..CODE_LENGTH / 2], &code[CODE_LENGTH / 2..]),
+        expires_at,
+        identity: tls::identity(),
+    }
+}
+
+/// Use up a code, making an admin user for the client that sent it
+pub async fn redeem(code: &str, client_name: &str) -> Result<Paired, String> {
+    let code = normalize(code);
+    {
+        let mut pending = PENDING.lock().unwrap();
+        pending.codes.retain(|(_, expires)| *expires > Utc::now());
+        match pending.codes.iter().position(|(c, _)| auth::token_matches(c, &code)) {
+            Some(index) => {
+                pending.codes.remove(index);
+            }
+            None => {
+                pending.failed += 1;
+                if pending.failed >= MAX_FAILED_ATTEMPTS {
+                    pending.codes.clear();
+                    tracing::warn!("Too many wrong pairing codes - dropped the pending ones");
+                }
+                return Err("Wrong or expired pairing code".to_string());
+            }
+        }
+    }
+
+    let mut all = users::load_users();
+    let base = match client_name.trim() {
+        "" => "Paired app".to_string(),
+        name => name.chars().take(64).collect(),
+    };
+    let mut name = base.clone();
+    let mut n = 2;
+    while all.iter().any(|u| u.name.eq_ignore_ascii_case(&name)) {
+        name = format!("{} ({})", base, n);
+        n += 1;
+    }
+
+    let user = User {
+        id: Uuid::new_v4().to_string()[..8].to_string(),
+        name,
+        role: Role::Admin,
+        servers: Vec::new(),
+        read_only: false,
+        created_at: Utc::now(),
+    };
+    let token = auth::generate_token();
+    users::set_user_token(&user.id, &token).await?;
+    all.push(user.clone());
+    users::save_users(&all)?;
+    tracing::info!("Paired remote app '{}'", user.name);
+
+    Ok(Paired { token, user })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_readable() {
+        let code = generate_code();
+        assert_eq!(code.len(), CODE_LENGTH);
+        assert!(code.bytes().all(|b| ALPHABET.contains(&b)));
+        assert_eq!(normalize(" abcd-efgh "), "ABCDEFGH");
+    }
+}
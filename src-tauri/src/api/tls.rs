@@ -0,0 +1,193 @@
+// API certificate - HTTPS for the remote API. By default a self-signed certificate is made
+// on first use (its private key kept in the keychain) and paired clients pin its fingerprint.
+// Users with a domain can point the API at certificate files instead, e.g. from certbot,
+// which are picked up again when they're renewed.
+
+use crate::security::secrets;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::ServerConfig;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+const KEY_SECRET: &str = "api-tls-key";
+const RELOAD_INTERVAL: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Where the API's certificate comes from
+#[derive(Debug, Clone)]
+pub enum TlsSource {
+    SelfSigned,
+    /// PEM files holding the certificate chain and its private key
+    Files { cert: PathBuf, key: PathBuf },
+}
+
+/// The certificate the API is serving, for the settings page and pairing
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiIdentity {
+    /// SHA-256 of the certificate, as colon-separated hex
+    pub fingerprint: String,
+    pub self_signed: bool,
+}
+
+struct Active {
+    source: TlsSource,
+    config: RustlsConfig,
+    identity: ApiIdentity,
+    /// When the certificate files were last changed, to notice renewals
+    modified: Option<SystemTime>,
+}
+
+lazy_static::lazy_static! {
+    static ref ACTIVE: Mutex<Option<Active>> = Mutex::new(None);
+}
+
+pub fn fingerprint(cert: &[u8]) -> String {
+    Sha256::digest(cert)
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn get_cert_path() -> PathBuf {
+    serverwave_core::paths::app_root().join("api-tls").join("cert.pem")
+}
+
+/// The TLS config to serve with, set up once when the API starts
+pub async fn load(source: TlsSource) -> Result<RustlsConfig, String> {
+    let (server_config, identity) = server_config(&source).await?;
+    let config = RustlsConfig::from_config(server_config);
+    *ACTIVE.lock().unwrap() = Some(Active {
+        modified: files_modified(&source),
+        source,
+        config: config.clone(),
+        identity,
+    });
+    Ok(config)
+}
+
+/// The certificate being served, if the API is on with HTTPS
+pub fn identity() -> Option<ApiIdentity> {
+    ACTIVE.lock().unwrap().as_ref().map(|a| a.identity.clone())
+}
+
+/// Replace the self-signed certificate. Paired clients have to pair again.
+pub async fn regenerate() -> Result<ApiIdentity, String> {
+    let config = match ACTIVE.lock().unwrap().as_ref() {
+        Some(Active { source: TlsSource::SelfSigned, config, .. }) => config.clone(),
+        Some(_) => return Err("The API uses certificate files - renew those instead".to_string()),
+        None => return Err("The API isn't serving HTTPS".to_string()),
+    };
+    std::fs::remove_file(get_cert_path()).ok();
+    secrets::delete_secret(KEY_SECRET).await;
+    let (server_config, identity) = server_config(&TlsSource::SelfSigned).await?;
+    config.reload_from_config(server_config);
+    if let Some(active) = ACTIVE.lock().unwrap().as_mut() {
+        active.identity = identity.clone();
+    }
+    tracing::info!("New API certificate, fingerprint {}", identity.fingerprint);
+    Ok(identity)
+}
+
+/// Reload certificate files when they change, so a renewed certificate is served
+pub(super) fn spawn_reloader() {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(RELOAD_INTERVAL).await;
+            let Some((source, config, modified)) = ACTIVE
+                .lock()
+                .unwrap()
+                .as_ref()
+                .filter(|a| matches!(a.source, TlsSource::Files { .. }))
+                .map(|a| (a.source.clone(), a.config.clone(), a.modified))
+            else {
+                return;
+            };
+            let now_modified = files_modified(&source);
+            if now_modified == modified {
+                continue;
+            }
+            match server_config(&source).await {
+                Ok((server_config, identity)) => {
+                    config.reload_from_config(server_config);
+                    tracing::info!("Reloaded the API certificate, fingerprint {}", identity.fingerprint);
+                    if let Some(active) = ACTIVE.lock().unwrap().as_mut() {
+                        active.identity = identity;
+                        active.modified = now_modified;
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to reload the API certificate: {}", e),
+            }
+        }
+    });
+}
+
+fn files_modified(source: &TlsSource) -> Option<SystemTime> {
+    match source {
+        TlsSource::SelfSigned => None,
+        TlsSource::Files { cert, key } => {
+            let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            modified(cert).max(modified(key))
+        }
+    }
+}
+
+async fn server_config(source: &TlsSource) -> Result<(Arc<ServerConfig>, ApiIdentity), String> {
+    let (cert_pem, key_pem) = match source {
+        TlsSource::SelfSigned => load_or_create_self_signed().await?,
+        TlsSource::Files { cert, key } => (
+            std::fs::read(cert).map_err(|e| format!("Failed to read {}: {}", cert.display(), e))?,
+            std::fs::read(key).map_err(|e| format!("Failed to read {}: {}", key.display(), e))?,
+        ),
+    };
+
+    let chain = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid certificate: {}", e))?;
+    let first = chain.first().ok_or("The certificate file holds no certificate")?;
+    let identity = ApiIdentity {
+        fingerprint: fingerprint(first),
+        self_signed: matches!(source, TlsSource::SelfSigned),
+    };
+    let key = PrivateKeyDer::from_pem_slice(&key_pem).map_err(|e| format!("Invalid private key: {}", e))?;
+
+    let mut config = ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_no_client_auth()
+        .with_single_cert(chain, key)
+        .map_err(|e| format!("The certificate doesn't match its key: {}", e))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok((Arc::new(config), identity))
+}
+
+/// The self-signed certificate and key, made on first use
+async fn load_or_create_self_signed() -> Result<(Vec<u8>, Vec<u8>), String> {
+    let cert_path = get_cert_path();
+    if let (Ok(cert), Some(key)) = (std::fs::read(&cert_path), secrets::get_secret(KEY_SECRET).await) {
+        return Ok((cert, key.into_bytes()));
+    }
+
+    let mut names = vec!["localhost".to_string()];
+    if let Some(host) = sysinfo::System::host_name() {
+        names.push(host);
+    }
+    let mut params = rcgen::CertificateParams::new(names).map_err(|e| e.to_string())?;
+    params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, "Serverwave Anywhere");
+    let key = rcgen::KeyPair::generate().map_err(|e| e.to_string())?;
+    let cert = params.self_signed(&key).map_err(|e| e.to_string())?;
+
+    secrets::set_secret(KEY_SECRET, &key.serialize_pem()).await?;
+    if let Some(parent) = cert_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&cert_path, cert.pem()).map_err(|e| e.to_string())?;
+    tracing::info!("Generated a self-signed API certificate");
+    Ok((cert.pem().into_bytes(), key.serialize_pem().into_bytes()))
+}
@@ -0,0 +1,244 @@
+// WebSocket channel - live events plus command calls for remote frontends
+//
+// Client messages:
+//   {"type": "invoke", "id": 1, "command": "start_server", "args": {"serverId": "..."}}
+//   {"type": "subscribe", "serverIds": ["..."]}   (null subscribes to every server)
+// Server messages:
+//   {"type": "result", "id": 1, "ok": true, "data": ...} / {"type": "result", "id": 1, "ok": false, "error": "...", "kind": "..."}
+//   {"type": "event", "event": "server-event" | "settings-changed", "payload": {...}}
+//   (server-event payloads are the tagged `ServerEvent`s from events.rs)
+
+use super::auth;
+use super::dispatch::dispatch;
+use super::http::ApiState;
+use crate::commands::docker::DockerState;
+use crate::events::{ServerEvent, SERVER_EVENT, STATE_CHANGED_EVENT};
+use crate::security::{Caller, Permission};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use serverwave_core::server::{lifecycle, load_all_server_configs, ServerStatus};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager};
+use tokio::sync::{broadcast, mpsc};
+
+/// App events relayed to WebSocket clients as-is
+const FORWARDED_EVENTS: &[&str] = &[SERVER_EVENT, STATE_CHANGED_EVENT, "settings-changed"];
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiEvent {
+    pub event: String,
+    pub payload: Value,
+}
+
+impl ApiEvent {
+    fn server_id(&self) -> Option<&str> {
+        self.payload.get("server_id").and_then(|v| v.as_str())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ClientMessage {
+    Invoke {
+        id: Value,
+        command: String,
+        #[serde(default)]
+        args: Value,
+    },
+    Subscribe {
+        #[serde(rename = "serverIds")]
+        server_ids: Option<Vec<String>>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct WsQuery {
+    token: Option<String>,
+}
+
+/// Relay app events into a broadcast channel every socket can subscribe to
+pub(super) fn forward_app_events(app: &AppHandle) -> broadcast::Sender<ApiEvent> {
+    let (tx, _) = broadcast::channel(1024);
+    for name in FORWARDED_EVENTS {
+        let tx = tx.clone();
+        app.listen_any(*name, move |event| {
+            let payload = serde_json::from_str(event.payload()).unwrap_or(Value::Null);
+            let _ = tx.send(ApiEvent {
+                event: name.to_string(),
+                payload,
+            });
+        });
+    }
+    tx
+}
+
+/// Browsers can't set headers on a WebSocket, so the token comes as `?token=`
+pub(super) async fn upgrade(
+    State(state): State<ApiState>,
+    Query(query): Query<WsQuery>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let provided = query.token.unwrap_or_default();
+    match auth::authenticate(&state.token, &provided).await {
+        Some(caller) => ws.on_upgrade(move |socket| handle_socket(socket, state, caller)),
+        None => (StatusCode::UNAUTHORIZED, "Missing or invalid API token").into_response(),
+    }
+}
+
+/// Which servers a connection wants events for; None means all of them
+type Subscription = Option<HashSet<String>>;
+
+/// Subscribed to and allowed to see. App-wide events (no server) go to admins regardless.
+fn wants(subscription: &Subscription, caller: &Caller, server_id: Option<&str>) -> bool {
+    match (subscription, server_id) {
+        (_, None) => caller.can(Permission::Manage, None),
+        (None, Some(id)) => caller.can(Permission::View, Some(id)),
+        (Some(ids), Some(id)) => ids.contains(id) && caller.can(Permission::View, Some(id)),
+    }
+}
+
+async fn handle_socket(socket: WebSocket, state: ApiState, caller: Caller) {
+    let (mut sink, mut stream) = socket.split();
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel::<Value>();
+    let (sub_tx, sub_rx) = tokio::sync::watch::channel::<Subscription>(Some(HashSet::new()));
+
+    let writer = tokio::spawn(async move {
+        while let Some(message) = out_rx.recv().await {
+            if sink.send(Message::Text(message.to_string())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let relay = {
+        let out_tx = out_tx.clone();
+        let sub_rx = sub_rx.clone();
+        let mut events = state.events.subscribe();
+        let caller = caller.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if wants(&sub_rx.borrow(), &caller, event.server_id()) {
+                            let _ = out_tx.send(json!({ "type": "event", "event": event.event, "payload": event.payload }));
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("WebSocket client lagged, dropped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    };
+
+    let poller = tokio::spawn(poll_servers(state.app.clone(), out_tx.clone(), sub_rx, caller.clone()));
+
+    while let Some(Ok(message)) = stream.next().await {
+        let text = match message {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        match serde_json::from_str::<ClientMessage>(&text) {
+            Ok(ClientMessage::Invoke { id, command, args }) => {
+                let app = state.app.clone();
+                let caller = caller.clone();
+                let out_tx = out_tx.clone();
+                tokio::spawn(async move {
+                    let reply = match dispatch(&app, caller, &command, args).await {
+                        Ok(data) => json!({ "type": "result", "id": id, "ok": true, "data": data }),
+                        Err(e) => {
+                            let mut reply = e.to_json();
+                            reply["type"] = json!("result");
+                            reply["id"] = id;
+                            reply["ok"] = json!(false);
+                            reply
+                        }
+                    };
+                    let _ = out_tx.send(reply);
+                });
+            }
+            Ok(ClientMessage::Subscribe { server_ids }) => {
+                let _ = sub_tx.send(server_ids.map(|ids| ids.into_iter().collect()));
+            }
+            Err(e) => {
+                let _ = out_tx.send(json!({ "type": "error", "error": format!("Invalid message: {}", e) }));
+            }
+        }
+    }
+
+    relay.abort();
+    poller.abort();
+    writer.abort();
+}
+
+/// Docker doesn't push status or stats, so poll them for subscribed servers and
+/// report status only when it changes
+async fn poll_servers(
+    app: AppHandle,
+    out_tx: mpsc::UnboundedSender<Value>,
+    sub_rx: tokio::sync::watch::Receiver<Subscription>,
+    caller: Caller,
+) {
+    let mut last_status: HashMap<String, ServerStatus> = HashMap::new();
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        let subscription = sub_rx.borrow().clone();
+        if matches!(&subscription, Some(ids) if ids.is_empty()) {
+            continue;
+        }
+        let docker = match app.state::<DockerState>().manager() {
+            Ok(docker) => docker,
+            Err(_) => continue,
+        };
+
+        for server in load_all_server_configs() {
+            if !wants(&subscription, &caller, Some(&server.id)) {
+                continue;
+            }
+            let status = lifecycle::current_status(&docker, &server)
+                .await
+                .unwrap_or(ServerStatus::Error);
+
+            if last_status.get(&server.id) != Some(&status) {
+                last_status.insert(server.id.clone(), status.clone());
+                if send_event(&out_tx, ServerEvent::status(&server.id, status.clone())).is_err() {
+                    return;
+                }
+            }
+
+            if status != ServerStatus::Running {
+                continue;
+            }
+            if let Some(container_id) = &server.container_id {
+                if let Ok(stats) = docker.get_container_stats(container_id).await {
+                    let event = ServerEvent::Stats {
+                        server_id: server.id.clone(),
+                        stats,
+                    };
+                    if send_event(&out_tx, event).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn send_event(
+    out_tx: &mpsc::UnboundedSender<Value>,
+    event: ServerEvent,
+) -> Result<(), mpsc::error::SendError<Value>> {
+    out_tx.send(json!({ "type": "event", "event": SERVER_EVENT, "payload": event }))
+}
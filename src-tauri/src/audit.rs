@@ -0,0 +1,104 @@
+// Audit journal - a record of what was done that can't be taken back, oldest first, in
+// audit.json in the config folder. A deleted server leaves a tombstone here: what it was,
+// who deleted it and what became of its files.
+
+use crate::security::{current_caller, Caller};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use serverwave_core::server::Server;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Older entries are dropped past this many
+const MAX_ENTRIES: usize = 1000;
+
+/// Held while audit.json is read and written back, so two deletions don't drop an entry
+static FILE: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEntry {
+    ServerDeleted(Tombstone),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFate {
+    /// Left where it was
+    Kept,
+    /// Moved to the trash folder
+    Trashed,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tombstone {
+    pub server_id: String,
+    pub name: String,
+    pub game_type: String,
+    pub deleted_at: DateTime<Utc>,
+    /// The user who deleted it, or how - the desktop app or the admin token
+    pub deleted_by: String,
+    pub data_path: PathBuf,
+    /// The size of the data folder when it was deleted
+    pub data_bytes: u64,
+    pub data: DataFate,
+    /// Where the data went, when it was trashed
+    #[serde(default)]
+    pub trash_path: Option<PathBuf>,
+}
+
+impl Tombstone {
+    pub fn new(server: &Server, data_bytes: u64, data: DataFate, trash_path: Option<&Path>) -> Self {
+        let deleted_by = match current_caller() {
+            None => "Desktop app".to_string(),
+            Some(Caller::Admin) => "Admin API token".to_string(),
+            Some(Caller::User(user)) => user.name,
+        };
+        Tombstone {
+            server_id: server.id.clone(),
+            name: server.name.clone(),
+            game_type: server.game_type.to_string(),
+            deleted_at: Utc::now(),
+            deleted_by,
+            data_path: server.data_path.clone(),
+            data_bytes,
+            data,
+            trash_path: trash_path.map(Path::to_path_buf),
+        }
+    }
+}
+
+pub fn load_journal() -> Vec<AuditEntry> {
+    atomic::read_json(&get_journal_path()).unwrap_or_default()
+}
+
+pub fn record(entry: AuditEntry) -> Result<(), String> {
+    let _file = FILE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut journal = load_journal();
+    journal.push(entry);
+    let excess = journal.len().saturating_sub(MAX_ENTRIES);
+    journal.drain(..excess);
+    let content = serde_json::to_string_pretty(&journal).map_err(|e| e.to_string())?;
+    atomic::write_with_backup(&get_journal_path(), content).map_err(|e| e.to_string())
+}
+
+/// Move a deleted server's data folder, and its database's, into a folder of their own in
+/// the trash. Returns that folder.
+pub fn move_to_trash(server: &Server, database_dir: &Path) -> Result<PathBuf, String> {
+    let folder = serverwave_core::paths::trash_dir()
+        .join(format!("{}-{}", server.id, Utc::now().format("%Y%m%d-%H%M%S")));
+    std::fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+    for (from, name) in [(server.data_path.as_path(), "data"), (database_dir, "database")] {
+        if from.exists() {
+            std::fs::rename(from, folder.join(name))
+                .map_err(|e| format!("Couldn't move {} to the trash: {}", from.display(), e))?;
+        }
+    }
+    Ok(folder)
+}
+
+fn get_journal_path() -> PathBuf {
+    serverwave_core::paths::config_dir().join("audit.json")
+}
@@ -0,0 +1,362 @@
+// Backup targets - copying a server's backups to storage off this computer and back: an S3
+// bucket (Amazon, Backblaze B2, Cloudflare R2, MinIO, ...) or a WebDAV folder. A backup is
+// its zip and its JSON file next to each other in the target's folder for the server, the
+// JSON written last so a half-copied zip isn't listed. Pulled backups land with the local
+// ones. Transfers send BackupTransfer events as they go.
+
+mod s3;
+mod webdav;
+
+use crate::backups::{self, Backup};
+use crate::disk_guard::{self, Activity};
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent, TransferDirection};
+use crate::security::secrets;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use futures_util::stream;
+use serde::Serialize;
+use serverwave_core::atomic;
+use serverwave_core::server::{load_server_config, BackupStorage, BackupTarget, Server};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Read size for uploads
+const CHUNK_SIZE: usize = 256 * 1024;
+/// Time between progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteBackup {
+    pub id: String,
+    pub size_bytes: u64,
+    /// From the id, None for one that wasn't named by this app
+    pub created_at: Option<DateTime<Utc>>,
+    /// Whether it's also in the server's local backups
+    pub local: bool,
+}
+
+/// A file in a target's folder
+struct RemoteFile {
+    name: String,
+    size: u64,
+}
+
+enum Store {
+    S3(s3::Bucket),
+    WebDav(webdav::Share),
+}
+
+impl Store {
+    async fn put(&self, path: &str, body: reqwest::Body, len: u64) -> Result<(), String> {
+        match self {
+            Store::S3(bucket) => bucket.put(path, body, len).await,
+            Store::WebDav(share) => share.put(path, body, len).await,
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<reqwest::Response, String> {
+        match self {
+            Store::S3(bucket) => bucket.get(path).await,
+            Store::WebDav(share) => share.get(path).await,
+        }
+    }
+
+    async fn list(&self, folder: &str) -> Result<Vec<RemoteFile>, String> {
+        match self {
+            Store::S3(bucket) => bucket.list(folder).await,
+            Store::WebDav(share) => share.list(folder).await,
+        }
+    }
+}
+
+fn secret_key(server_id: &str, target_id: &str) -> String {
+    format!("backup-target-{}-{}", server_id, target_id)
+}
+
+/// Keep a target's secret key or password in the keychain
+pub async fn save_secret(server_id: &str, target_id: &str, secret: &str) -> Result<(), AppError> {
+    Ok(secrets::set_secret(&secret_key(server_id, target_id), secret).await?)
+}
+
+pub async fn forget_secret(server_id: &str, target_id: &str) {
+    secrets::delete_secret(&secret_key(server_id, target_id)).await;
+}
+
+/// Forget the secrets of a deleted server's targets - the copies on them stay
+pub async fn forget(server: &Server) {
+    for target in &server.backup_targets {
+        forget_secret(&server.id, &target.id).await;
+    }
+}
+
+async fn open(server_id: &str, target_id: &str) -> Result<(BackupTarget, Store), AppError> {
+    let target = load_server_config(server_id)?
+        .backup_targets
+        .into_iter()
+        .find(|t| t.id == target_id)
+        .ok_or_else(|| AppError::InvalidInput(format!("No backup target '{}' for this server", target_id)))?;
+    let secret = secrets::get_secret(&secret_key(server_id, target_id)).await.unwrap_or_default();
+    let store = match &target.storage {
+        BackupStorage::S3 { endpoint, region, bucket, access_key_id, path_style } => Store::S3(s3::Bucket {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            region: region.clone(),
+            bucket: bucket.clone(),
+            access_key_id: access_key_id.clone(),
+            secret_access_key: secret,
+            path_style: *path_style,
+        }),
+        BackupStorage::WebDav { url, username } => Store::WebDav(webdav::Share {
+            url: url.trim_end_matches('/').to_string(),
+            username: username.clone(),
+            password: secret,
+        }),
+    };
+    Ok((target, store))
+}
+
+/// Sends a transfer's BackupTransfer events, a few a second at most
+struct Progress {
+    app: AppHandle,
+    server_id: String,
+    backup_id: String,
+    target_id: String,
+    direction: TransferDirection,
+    done: u64,
+    total: u64,
+    last: Instant,
+}
+
+impl Progress {
+    fn new(app: &AppHandle, backup: &Backup, target: &BackupTarget, direction: TransferDirection, total: u64) -> Self {
+        Self {
+            app: app.clone(),
+            server_id: backup.server_id.clone(),
+            backup_id: backup.id.clone(),
+            target_id: target.id.clone(),
+            direction,
+            done: 0,
+            total,
+            last: Instant::now(),
+        }
+    }
+
+    fn advance(&mut self, bytes: u64) {
+        self.done += bytes;
+        if self.done >= self.total || self.last.elapsed() >= PROGRESS_INTERVAL {
+            self.last = Instant::now();
+            events::emit(&self.app, ServerEvent::BackupTransfer {
+                server_id: self.server_id.clone(),
+                backup_id: self.backup_id.clone(),
+                target_id: self.target_id.clone(),
+                direction: self.direction,
+                done_bytes: self.done,
+                total_bytes: self.total,
+            });
+        }
+    }
+}
+
+/// A file as a request body, counting what's read
+fn upload_body(file: tokio::fs::File, progress: Progress) -> reqwest::Body {
+    let chunks = stream::unfold(Some((file, progress)), |state| async move {
+        let (mut file, mut progress) = state?;
+        let mut buffer = vec![0; CHUNK_SIZE];
+        match file.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(read) => {
+                buffer.truncate(read);
+                progress.advance(read as u64);
+                Some((Ok(buffer), Some((file, progress))))
+            }
+            Err(e) => Some((Err(e), None)),
+        }
+    });
+    reqwest::Body::wrap_stream(chunks)
+}
+
+/// Backup ids end up in file names
+fn check_backup_id(backup_id: &str) -> Result<(), AppError> {
+    match !backup_id.is_empty() && backup_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        true => Ok(()),
+        false => Err(AppError::InvalidInput(format!("'{}' isn't a backup id", backup_id))),
+    }
+}
+
+/// Copy a local backup to a target
+pub async fn push(app: &AppHandle, server_id: &str, backup_id: &str, target_id: &str) -> Result<(), AppError> {
+    let backup = backups::find(server_id, backup_id)?;
+    let (target, store) = open(server_id, target_id).await?;
+    let folder = target.folder(server_id);
+
+    let file = tokio::fs::File::open(&backup.path).await?;
+    let size = file.metadata().await?.len();
+    let progress = Progress::new(app, &backup, &target, TransferDirection::Upload, size);
+    let to = |e: String| format!("Copying the backup to {} failed: {}", target.name, e);
+    store
+        .put(&format!("{}/{}.zip", folder, backup.id), upload_body(file, progress), size)
+        .await
+        .map_err(to)?;
+    let details = serde_json::to_vec_pretty(&backup).map_err(|e| e.to_string())?;
+    let len = details.len() as u64;
+    store.put(&format!("{}/{}.json", folder, backup.id), details.into(), len).await.map_err(to)?;
+
+    events::emit(app, ServerEvent::log(server_id, format!("[Serverwave] Copied backup {} to {}", backup.id, target.name)));
+    Ok(())
+}
+
+/// The backups of the server on a target, newest first
+pub async fn list(server_id: &str, target_id: &str) -> Result<Vec<RemoteBackup>, AppError> {
+    let (target, store) = open(server_id, target_id).await?;
+    let files = store
+        .list(&target.folder(server_id))
+        .await
+        .map_err(|e| format!("Couldn't list the backups on {}: {}", target.name, e))?;
+
+    let local = backups::list(server_id);
+    let mut remote: Vec<RemoteBackup> = files
+        .iter()
+        .filter_map(|file| Some((file.name.strip_suffix(".zip")?, file.size)))
+        .filter(|(id, _)| files.iter().any(|f| f.name == format!("{}.json", id)))
+        .map(|(id, size_bytes)| RemoteBackup {
+            id: id.to_string(),
+            size_bytes,
+            created_at: NaiveDateTime::parse_from_str(id, "%Y%m%d-%H%M%S")
+                .ok()
+                .and_then(|t| Local.from_local_datetime(&t).earliest())
+                .map(|t| t.with_timezone(&Utc)),
+            local: local.iter().any(|b| b.id == id),
+        })
+        .collect();
+    remote.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.id.cmp(&a.id)));
+    Ok(remote)
+}
+
+/// Download a backup from a target into the server's local backups, where it can be restored
+pub async fn pull(app: &AppHandle, server_id: &str, target_id: &str, backup_id: &str) -> Result<Backup, AppError> {
+    check_backup_id(backup_id)?;
+    if backups::find(server_id, backup_id).is_ok() {
+        return Err(AppError::InvalidInput("This backup is already on this computer".into()));
+    }
+    disk_guard::ensure_room(Activity::Backup).await?;
+    let (target, store) = open(server_id, target_id).await?;
+    let folder = target.folder(server_id);
+    let from = |e: String| format!("Copying the backup from {} failed: {}", target.name, e);
+
+    let details = store.get(&format!("{}/{}.json", folder, backup_id)).await.map_err(from)?;
+    let details = details.bytes().await.map_err(|e| from(e.to_string()))?;
+    let mut backup: Backup =
+        serde_json::from_slice(&details).map_err(|e| from(format!("its details can't be read: {}", e)))?;
+    backup.id = backup_id.to_string();
+    backup.server_id = server_id.to_string();
+
+    let dir = backups::get_server_dir(server_id);
+    std::fs::create_dir_all(&dir)?;
+    let zip_path = dir.join(format!("{}.zip", backup_id));
+    let partial = dir.join(format!("{}.zip.part", backup_id));
+    let mut response = store.get(&format!("{}/{}.zip", folder, backup_id)).await.map_err(from)?;
+    let mut progress = Progress::new(app, &backup, &target, TransferDirection::Download, backup.size_bytes);
+    let mut file = tokio::fs::File::create(&partial).await?;
+    let copied: Result<(), String> = async {
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+            progress.advance(chunk.len() as u64);
+        }
+        file.flush().await.map_err(|e| e.to_string())
+    }
+    .await;
+    drop(file);
+    if let Err(e) = copied {
+        let _ = std::fs::remove_file(&partial);
+        return Err(from(e).into());
+    }
+    std::fs::rename(&partial, &zip_path)?;
+
+    backup.path = zip_path.to_string_lossy().to_string();
+    backup.size_bytes = std::fs::metadata(&zip_path)?.len();
+    let content = serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())?;
+    atomic::write_atomic(&backups::get_meta_path(server_id, backup_id), content)?;
+    events::emit(app, ServerEvent::log(server_id, format!("[Serverwave] Copied backup {} from {}", backup_id, target.name)));
+    Ok(backup)
+}
+
+/// Whether the target can be reached with its credentials
+pub async fn test(server_id: &str, target_id: &str) -> Result<(), AppError> {
+    list(server_id, target_id).await.map(|_| ())
+}
+
+/// Copy a new backup to the server's targets that take every backup, in the background
+pub fn upload_new(app: &AppHandle, backup: &Backup) {
+    let Ok(server) = load_server_config(&backup.server_id) else { return };
+    for target in server.backup_targets.into_iter().filter(|t| t.upload_new) {
+        let (app, backup) = (app.clone(), backup.clone());
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = push(&app, &backup.server_id, &backup.id, &target.id).await {
+                events::emit(&app, ServerEvent::alert(&backup.server_id, AlertLevel::Error, e.to_string()));
+            }
+        });
+    }
+}
+
+/// A response, or the error the server gave - S3 and WebDAV servers explain it in XML
+async fn check(response: reqwest::Response) -> Result<reqwest::Response, String> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    match xml_elements(&body, "Message").into_iter().chain(xml_elements(&body, "message")).next() {
+        Some(message) if !message.trim().is_empty() => Err(format!("{} ({})", message.trim(), status)),
+        _ => Err(status.to_string()),
+    }
+}
+
+/// The content of each element named `name` whatever its namespace prefix - enough for the
+/// listings S3 and WebDAV answer with
+fn xml_elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('>') else { break };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        let tag_name = tag.split_whitespace().next().unwrap_or_default();
+        if tag.ends_with('/') || tag_name.rsplit(':').next() != Some(name) {
+            continue;
+        }
+        let close = format!("</{}>", tag_name);
+        if let Some(stop) = rest.find(&close) {
+            found.push(&rest[..stop]);
+            rest = &rest[stop + close.len()..];
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml_elements_ignore_namespaces() {
+        let s3 = "<?xml version=\"1.0\"?><ListBucketResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+            <Contents><Key>backups/ab12cd34/20261016-040000.zip</Key><Size>1048576</Size></Contents>\
+            <Contents><Key>backups/ab12cd34/20261016-040000.json</Key><Size>312</Size></Contents>\
+            <IsTruncated>false</IsTruncated></ListBucketResult>";
+        let contents = xml_elements(s3, "Contents");
+        assert_eq!(contents.len(), 2);
+        assert_eq!(xml_elements(contents[0], "Key"), ["backups/ab12cd34/20261016-040000.zip"]);
+        assert_eq!(xml_elements(s3, "IsTruncated"), ["false"]);
+
+        let webdav = "<d:multistatus xmlns:d=\"DAV:\"><d:response><d:href>/dav/ab12cd34/</d:href>\
+            <d:propstat><d:prop><d:getcontentlength/></d:prop></d:propstat></d:response>\
+            <d:response><d:href>/dav/ab12cd34/20261016-040000.zip</d:href>\
+            <d:propstat><d:prop><d:getcontentlength>1048576</d:getcontentlength></d:prop></d:propstat></d:response>\
+            </d:multistatus>";
+        let responses = xml_elements(webdav, "response");
+        assert_eq!(responses.len(), 2);
+        assert!(xml_elements(responses[0], "getcontentlength").is_empty());
+        assert_eq!(xml_elements(responses[1], "getcontentlength"), ["1048576"]);
+    }
+}
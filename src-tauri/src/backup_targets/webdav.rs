@@ -0,0 +1,96 @@
+// WebDAV - a folder on Nextcloud, ownCloud, a NAS or any other WebDAV server, with basic
+// auth. Folders are made as they're needed.
+
+use super::{check, xml_elements, RemoteFile};
+use crate::content::http_client;
+use reqwest::{Method, RequestBuilder, StatusCode};
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:getcontentlength/></d:prop></d:propfind>"#;
+
+pub struct Share {
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl Share {
+    pub async fn put(&self, path: &str, body: reqwest::Body, len: u64) -> Result<(), String> {
+        if let Some((folder, _)) = path.rsplit_once('/') {
+            self.make_folders(folder).await?;
+        }
+        let request = self.request(Method::PUT, path).header(reqwest::header::CONTENT_LENGTH, len).body(body);
+        check(request.send().await.map_err(|e| e.to_string())?).await?;
+        Ok(())
+    }
+
+    pub async fn get(&self, path: &str) -> Result<reqwest::Response, String> {
+        check(self.request(Method::GET, path).send().await.map_err(|e| e.to_string())?).await
+    }
+
+    /// The files in a folder, none when it isn't there yet
+    pub async fn list(&self, folder: &str) -> Result<Vec<RemoteFile>, String> {
+        let response = self
+            .request(propfind(), &format!("{}/", folder))
+            .header("Depth", "1")
+            .header(reqwest::header::CONTENT_TYPE, "application/xml")
+            .body(PROPFIND_BODY)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        let body = check(response).await?.text().await.map_err(|e| e.to_string())?;
+
+        Ok(xml_elements(&body, "response")
+            .into_iter()
+            .filter_map(|entry| {
+                let href = xml_elements(entry, "href").first()?.trim().to_string();
+                // The folder itself ends with a slash
+                let name = href.rsplit('/').next().filter(|n| !n.is_empty())?;
+                let size = xml_elements(entry, "getcontentlength").first().and_then(|s| s.trim().parse().ok());
+                Some(RemoteFile { name: name.to_string(), size: size.unwrap_or(0) })
+            })
+            .collect())
+    }
+
+    /// MKCOL each folder down to `folder` - a 405 is one that's already there
+    async fn make_folders(&self, folder: &str) -> Result<(), String> {
+        let mut path = String::new();
+        for part in folder.split('/').filter(|p| !p.is_empty()) {
+            path.push_str(part);
+            path.push('/');
+            let response = self.request(mkcol(), &path).send().await.map_err(|e| e.to_string())?;
+            if response.status() != StatusCode::METHOD_NOT_ALLOWED {
+                check(response).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let path: Vec<String> = path.split('/').map(encode_segment).collect();
+        http_client()
+            .request(method, format!("{}/{}", self.url, path.join("/")))
+            .basic_auth(&self.username, Some(&self.password))
+    }
+}
+
+fn propfind() -> Method {
+    Method::from_bytes(b"PROPFIND").expect("PROPFIND is a valid method")
+}
+
+fn mkcol() -> Method {
+    Method::from_bytes(b"MKCOL").expect("MKCOL is a valid method")
+}
+
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
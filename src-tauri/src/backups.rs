@@ -0,0 +1,404 @@
+// Backups - a zip of a server's data folder, kept in a folder per server under the backups
+// directory (Settings, or `backups` in the data directory) with a JSON file of its details
+// next to it. Past the retention count (the server's schedule's, else Settings') or the
+// schedule's total size the oldest are removed. Restoring puts the zip's files in place of
+// the data folder, stopping the server for it if it runs. Servers with a backup schedule
+// are backed up by `spawn_scheduler`. Copies off this computer are in `backup_targets`.
+
+use crate::backup_targets;
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::{console_command, start_server, stop_server, ServerState};
+use crate::commands::settings::load_app_settings;
+use crate::disk_guard::{self, Activity};
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::maintenance::{self, Window};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serverwave_core::server::{lifecycle, load_all_server_configs, load_server_config, ServerStatus};
+use serverwave_core::{atomic, paths};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// The game action that makes a running server write its world to disk
+const SAVE_ACTION: &str = "save";
+/// Time the save gets before the files are read
+const SAVE_WAIT: Duration = Duration::from_secs(5);
+const TICK: Duration = Duration::from_secs(60);
+/// Note on the backups the schedule makes
+const SCHEDULED_NOTE: &str = "Scheduled";
+
+lazy_static::lazy_static! {
+    /// Servers being backed up or restored right now
+    static ref BUSY: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub id: String,
+    pub server_id: String,
+    pub server_name: String,
+    pub game_type: String,
+    /// The zip
+    pub path: String,
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Marks a server busy until dropped, so a backup and a restore can't overlap
+struct BusyGuard(String);
+
+impl BusyGuard {
+    fn take(server_id: &str) -> Result<Self, AppError> {
+        if !BUSY.lock().unwrap().insert(server_id.to_string()) {
+            return Err(AppError::InvalidInput("A backup or restore of this server is already running".into()));
+        }
+        Ok(Self(server_id.to_string()))
+    }
+}
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        BUSY.lock().unwrap().remove(&self.0);
+    }
+}
+
+pub fn backups_dir() -> PathBuf {
+    match load_app_settings().backups.directory.filter(|d| !d.trim().is_empty()) {
+        Some(directory) => PathBuf::from(directory),
+        None => paths::data_root().join("backups"),
+    }
+}
+
+pub(crate) fn get_server_dir(server_id: &str) -> PathBuf {
+    backups_dir().join(server_id)
+}
+
+pub(crate) fn get_meta_path(server_id: &str, backup_id: &str) -> PathBuf {
+    get_server_dir(server_id).join(format!("{}.json", backup_id))
+}
+
+/// A server's backups, newest first
+pub fn list(server_id: &str) -> Vec<Backup> {
+    let Ok(entries) = std::fs::read_dir(get_server_dir(server_id)) else { return Vec::new() };
+    let mut backups: Vec<Backup> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|p| atomic::read_json(&p))
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    backups
+}
+
+pub fn find(server_id: &str, backup_id: &str) -> Result<Backup, AppError> {
+    list(server_id)
+        .into_iter()
+        .find(|b| b.id == backup_id)
+        .ok_or_else(|| AppError::InvalidInput(format!("No backup '{}' for this server", backup_id)))
+}
+
+/// Zip a server's data folder. A running server is asked to save first, when its game can.
+pub async fn create(app: &AppHandle, server_id: &str, note: Option<String>) -> Result<Backup, AppError> {
+    let server = load_server_config(server_id)?;
+    if server.status == ServerStatus::Installing {
+        return Err(AppError::InvalidInput("Wait for the install to finish before backing up".into()));
+    }
+    disk_guard::ensure_room(Activity::Backup).await?;
+    let _busy = BusyGuard::take(server_id)?;
+
+    let docker = app.state::<DockerState>().manager()?;
+    if lifecycle::current_status(&docker, &server).await? == ServerStatus::Running {
+        let game = app.state::<GamesState>().manager.lock().await.get_game(&server.game_type);
+        let save = game.and_then(|g| g.actions.into_iter().find(|a| a.id == SAVE_ACTION)).and_then(|a| a.command);
+        if let Some(command) = save {
+            events::emit(app, ServerEvent::log(server_id, "[Serverwave] Saving the world for the backup..."));
+            if console_command(&docker, server_id, &command).await.is_ok() {
+                tokio::time::sleep(SAVE_WAIT).await;
+            }
+        }
+    }
+
+    let created_at = Utc::now();
+    let id = created_at.with_timezone(&Local).format("%Y%m%d-%H%M%S").to_string();
+    let dir = get_server_dir(server_id);
+    std::fs::create_dir_all(&dir)?;
+    let zip_path = dir.join(format!("{}.zip", id));
+    events::emit(app, ServerEvent::log(server_id, "[Serverwave] Backing up the server files..."));
+    let (source, target) = (server.data_path.clone(), zip_path.clone());
+    if let Err(e) = tokio::task::spawn_blocking(move || zip_dir(&source, &target)).await? {
+        let _ = std::fs::remove_file(&zip_path);
+        return Err(format!("Backup failed: {}", e).into());
+    }
+
+    let backup = Backup {
+        id,
+        server_id: server_id.to_string(),
+        server_name: server.name.clone(),
+        game_type: server.game_type.to_string(),
+        path: zip_path.to_string_lossy().to_string(),
+        size_bytes: std::fs::metadata(&zip_path)?.len(),
+        note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+        created_at,
+    };
+    let content = serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())?;
+    atomic::write_atomic(&get_meta_path(server_id, &backup.id), content)?;
+    prune(server_id);
+
+    events::emit(app, ServerEvent::BackupDone {
+        server_id: server_id.to_string(),
+        path: backup.path.clone(),
+        size_bytes: backup.size_bytes,
+    });
+    backup_targets::upload_new(app, &backup);
+    Ok(backup)
+}
+
+/// Remove the oldest backups past the retention count or total size
+fn prune(server_id: &str) {
+    let schedule = load_server_config(server_id).ok().and_then(|s| s.backup_schedule);
+    let keep = schedule
+        .as_ref()
+        .and_then(|s| s.keep_count)
+        .unwrap_or(load_app_settings().backups.retention_count)
+        .max(1) as usize;
+    let max_bytes = schedule.and_then(|s| s.max_total_mb).map(|mb| mb * 1024 * 1024);
+    for old in past_retention(list(server_id), keep, max_bytes) {
+        if let Err(e) = delete(&old) {
+            tracing::warn!("Failed to remove old backup {} of {}: {}", old.id, server_id, e);
+        }
+    }
+}
+
+/// The backups (newest first) past `keep` of them or `max_bytes` in total - the newest stays
+fn past_retention(backups: Vec<Backup>, keep: usize, max_bytes: Option<u64>) -> Vec<Backup> {
+    let mut total = 0;
+    backups
+        .into_iter()
+        .enumerate()
+        .filter(|(index, backup)| {
+            total += backup.size_bytes;
+            *index > 0 && (*index >= keep || max_bytes.is_some_and(|max| total > max))
+        })
+        .map(|(_, backup)| backup)
+        .collect()
+}
+
+pub fn delete(backup: &Backup) -> Result<(), AppError> {
+    let zip_path = PathBuf::from(&backup.path);
+    if zip_path.exists() {
+        std::fs::remove_file(&zip_path)?;
+    }
+    std::fs::remove_file(get_meta_path(&backup.server_id, &backup.id))?;
+    Ok(())
+}
+
+/// Put a backup's files in place of the server's. A running server is stopped for it and
+/// started again after.
+pub async fn restore(app: &AppHandle, server_id: &str, backup_id: &str) -> Result<(), AppError> {
+    let backup = find(server_id, backup_id)?;
+    let server = load_server_config(server_id)?;
+    let docker = app.state::<DockerState>().manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if status == ServerStatus::Installing {
+        return Err(AppError::InvalidInput("Wait for the install to finish before restoring".into()));
+    }
+    let _busy = BusyGuard::take(server_id)?;
+
+    let was_running = matches!(status, ServerStatus::Running | ServerStatus::Starting);
+    if was_running {
+        events::emit(app, ServerEvent::log(server_id, "[Serverwave] Stopping to restore the backup..."));
+        stop_server(server_id.to_string(), app.clone(), app.state(), app.state(), app.state()).await?;
+    }
+
+    let (zip_path, data_path) = (PathBuf::from(&backup.path), server.data_path.clone());
+    tokio::task::spawn_blocking(move || replace_with_zip(&zip_path, &data_path)).await??;
+    events::emit(app, ServerEvent::log(
+        server_id,
+        format!("[Serverwave] Restored the backup from {}", backup.created_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
+    ));
+
+    if was_running {
+        start_server(server_id.to_string(), Some(true), None, app.clone(), app.state(), app.state(), app.state()).await?;
+    }
+    Ok(())
+}
+
+/// Run the scheduled backups that are due, once a minute
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            run_due(&app).await;
+        }
+    });
+}
+
+async fn run_due(app: &AppHandle) {
+    for server in load_all_server_configs() {
+        let Some(due) = server.backup_schedule.as_ref().filter(|s| s.enabled).and_then(|s| s.next_run) else { continue };
+        let now = Utc::now();
+        // Left for after the install - it's due again on the next tick
+        if due > now || server.status == ServerStatus::Installing {
+            continue;
+        }
+        // The backup may stop or pause the server, so it keeps to the maintenance window
+        let held = match maintenance::window(&server) {
+            Window::Open => None,
+            Window::Closed { opens } => Some(opens),
+        };
+        match held {
+            Some(opens) => maintenance::report_held(app, &server.id, "Scheduled backup", opens),
+            None => {
+                tracing::info!("Running scheduled backup of {}", server.id);
+                if let Err(e) = create(app, &server.id, Some(SCHEDULED_NOTE.to_string())).await {
+                    events::emit(app, ServerEvent::alert(&server.id, AlertLevel::Error, format!("Scheduled backup failed: {}", e)));
+                }
+            }
+        }
+        // A failed backup waits for the next time too, rather than retrying every minute
+        let updated = app.state::<ServerState>().configs.update(&server.id, |s| {
+            if let Some(schedule) = s.backup_schedule.as_mut() {
+                match held {
+                    Some(Some(opens)) => schedule.next_run = Some(opens),
+                    _ => {
+                        schedule.last_run = Some(now);
+                        schedule.next_run = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+                    }
+                }
+            }
+        });
+        if let Err(e) = updated {
+            tracing::warn!("Failed to save the backup schedule of {}: {}", server.id, e);
+        }
+    }
+}
+
+/// Unpack next to the data folder and swap the two, so a broken zip leaves the files as they were
+fn replace_with_zip(zip_path: &Path, data_path: &Path) -> Result<(), String> {
+    let sibling = |suffix: &str| {
+        let name = data_path.file_name().unwrap_or_default().to_string_lossy();
+        data_path.with_file_name(format!(".{}-{}", name, suffix))
+    };
+    let staging = sibling("restore");
+    let previous = sibling("previous");
+    for dir in [&staging, &previous] {
+        if dir.exists() {
+            std::fs::remove_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let file = std::fs::File::open(zip_path).map_err(|e| format!("Can't open the backup: {}", e))?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    if let Err(e) = zip.extract(&staging) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(format!("Couldn't unpack the backup: {}", e));
+    }
+
+    if data_path.exists() {
+        std::fs::rename(data_path, &previous).map_err(|e| e.to_string())?;
+    }
+    if let Err(e) = std::fs::rename(&staging, data_path) {
+        let _ = std::fs::rename(&previous, data_path);
+        return Err(e.to_string());
+    }
+    std::fs::remove_dir_all(&previous).ok();
+    Ok(())
+}
+
+fn zip_dir(source: &Path, path: &Path) -> Result<(), String> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path).map_err(|e| e.to_string())?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+    if source.exists() {
+        add_dir(&mut zip, source, source, options, "")?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Add the files under `dir` as their path from `base`, after `prefix`
+pub(crate) fn add_dir(
+    zip: &mut zip::ZipWriter<std::fs::File>,
+    base: &Path,
+    dir: &Path,
+    options: zip::write::SimpleFileOptions,
+    prefix: &str,
+) -> Result<(), String> {
+    for entry in std::fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let name = format!(
+            "{}{}",
+            prefix,
+            path.strip_prefix(base).map_err(|e| e.to_string())?.to_string_lossy().replace('\\', "/")
+        );
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else { continue };
+        // Start scripts have to stay executable
+        #[cfg(unix)]
+        let options = {
+            use std::os::unix::fs::PermissionsExt;
+            options.unix_permissions(metadata.permissions().mode())
+        };
+        if metadata.is_dir() {
+            zip.add_directory(name, options).map_err(|e| e.to_string())?;
+            add_dir(zip, base, &path, options, prefix)?;
+        } else if metadata.is_file() {
+            zip.start_file(name, options).map_err(|e| e.to_string())?;
+            let mut file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+            std::io::copy(&mut file, zip).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_by_count_and_size() {
+        let backup = |id: &str, size_mb: u64| Backup {
+            id: id.to_string(),
+            server_id: "ab12cd34".to_string(),
+            server_name: "Survival".to_string(),
+            game_type: "minecraft-java".to_string(),
+            path: String::new(),
+            size_bytes: size_mb * 1024 * 1024,
+            note: None,
+            created_at: Utc::now(),
+        };
+        let ids = |backups: Vec<Backup>| backups.into_iter().map(|b| b.id).collect::<Vec<_>>();
+        let backups = vec![backup("c", 300), backup("b", 300), backup("a", 300)];
+
+        assert_eq!(ids(past_retention(backups.clone(), 2, None)), ["a"]);
+        assert_eq!(ids(past_retention(backups.clone(), 10, Some(700 * 1024 * 1024))), ["a"]);
+        // The newest is kept even when it alone is over the size
+        assert_eq!(ids(past_retention(backups, 10, Some(100 * 1024 * 1024))), ["b", "a"]);
+    }
+
+    #[test]
+    fn test_restore_replaces_the_data_folder() {
+        let dir = std::env::temp_dir().join(format!("serverwave-backup-{}", uuid::Uuid::new_v4()));
+        let data = dir.join("data");
+        std::fs::create_dir_all(data.join("world")).unwrap();
+        std::fs::write(data.join("world").join("level.dat"), b"before").unwrap();
+        let zip_path = dir.join("backup.zip");
+        zip_dir(&data, &zip_path).unwrap();
+
+        std::fs::write(data.join("world").join("level.dat"), b"after").unwrap();
+        std::fs::write(data.join("new.txt"), b"added later").unwrap();
+        replace_with_zip(&zip_path, &data).unwrap();
+
+        assert_eq!(std::fs::read(data.join("world").join("level.dat")).unwrap(), b"before");
+        assert!(!data.join("new.txt").exists());
+        assert!(!dir.join(".data-previous").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,120 @@
+// Host capacity - whether a server's memory limit fits in what the host has free and next to
+// the limits of the servers already running, checked before a start so one oversized server
+// doesn't push the whole machine out of memory
+
+use crate::docker::DockerManager;
+use serde::Serialize;
+use serverwave_core::server::{load_all_server_configs, Server, ServerStatus};
+
+const MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Capacity {
+    /// The memory limit of the server being started
+    pub requested_mb: u64,
+    /// Memory the host has free right now
+    pub available_mb: u64,
+    /// Memory containers can use - the Docker VM's on Docker Desktop
+    pub total_mb: u64,
+    /// Memory limits of the other servers that are running
+    pub committed_mb: u64,
+    /// Names of those servers
+    pub running: Vec<String>,
+}
+
+impl Capacity {
+    pub fn fits(&self) -> bool {
+        self.requested_mb <= self.available_mb && self.committed_mb + self.requested_mb <= self.total_mb
+    }
+
+    pub fn message(&self) -> String {
+        if self.requested_mb > self.available_mb {
+            format!(
+                "The server may use up to {} MB but only {} MB of memory is free",
+                self.requested_mb, self.available_mb
+            )
+        } else {
+            format!(
+                "The server may use up to {} MB and the {} running server(s) up to {} MB, more than the {} MB there is",
+                self.requested_mb,
+                self.running.len(),
+                self.committed_mb,
+                self.total_mb
+            )
+        }
+    }
+}
+
+/// Free and total memory of the host, in MB
+fn host_memory() -> (u64, u64) {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    (system.available_memory() / MB, system.total_memory() / MB)
+}
+
+/// Free memory on the host (None when it can't be read) and the memory containers can use
+/// (0 when unknown), in MB
+pub async fn memory(docker: &DockerManager) -> (Option<u64>, u64) {
+    let (available_mb, host_total_mb) = tokio::task::spawn_blocking(host_memory).await.unwrap_or((0, 0));
+    // Docker Desktop runs containers in a VM that has less memory than the host
+    let total_mb = match docker.get_info().await.map(|info| info.memory_bytes / MB) {
+        Ok(docker_mb) if docker_mb > 0 && (host_total_mb == 0 || docker_mb < host_total_mb) => docker_mb,
+        _ => host_total_mb,
+    };
+    ((host_total_mb > 0).then_some(available_mb), total_mb)
+}
+
+/// How `server` would fit next to the other managed servers that are running
+pub async fn check(docker: &DockerManager, server: &Server) -> Capacity {
+    let (available_mb, total_mb) = memory(docker).await;
+
+    let others: Vec<Server> = load_all_server_configs()
+        .into_iter()
+        .filter(|s| s.id != server.id && s.container_id.is_some())
+        .collect();
+    let statuses = futures_util::future::join_all(others.iter().map(|s| async move {
+        let container_id = s.container_id.as_deref().unwrap_or_default();
+        docker.get_container_status(container_id).await.unwrap_or(ServerStatus::Stopped)
+    }))
+    .await;
+    let running: Vec<&Server> = others
+        .iter()
+        .zip(statuses)
+        .filter(|(_, status)| matches!(status, ServerStatus::Running | ServerStatus::Starting))
+        .map(|(s, _)| s)
+        .collect();
+
+    Capacity {
+        requested_mb: u64::from(server.memory_mb),
+        // Unknown when the host can't be read - don't block starts on that
+        available_mb: available_mb.unwrap_or(u64::MAX),
+        total_mb: if total_mb == 0 { u64::MAX } else { total_mb },
+        committed_mb: running.iter().map(|s| u64::from(s.memory_mb)).sum(),
+        running: running.iter().map(|s| s.name.clone()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fits_needs_free_memory_and_room_next_to_running_servers() {
+        let mut capacity = Capacity {
+            requested_mb: 16384,
+            available_mb: 12000,
+            total_mb: 16384,
+            committed_mb: 0,
+            running: Vec::new(),
+        };
+        assert!(!capacity.fits());
+        assert!(capacity.message().contains("only 12000 MB"));
+
+        capacity.requested_mb = 4096;
+        assert!(capacity.fits());
+        capacity.committed_mb = 14336;
+        capacity.running = vec!["Rust".to_string()];
+        assert!(!capacity.fits());
+        assert!(capacity.message().contains("1 running server(s) up to 14336 MB"));
+    }
+}
@@ -0,0 +1,75 @@
+// Game action commands - the buttons a game declares for its servers (GameConfig::actions)
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::console_command;
+use crate::error::AppError;
+use crate::events;
+use crate::games::{GameAction, GameConfig};
+use serverwave_core::server::{lifecycle, load_server_config, Server, ServerStatus};
+use tauri::{AppHandle, Manager, State};
+
+async fn server_and_game(server_id: &str, games_state: &State<'_, GamesState>) -> Result<(Server, GameConfig), AppError> {
+    let server = load_server_config(server_id)?;
+    let game = games_state.manager.lock().await.get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+    Ok((server, game))
+}
+
+/// The actions a server's game declares
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_game_actions(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+) -> Result<Vec<GameAction>, AppError> {
+    let (_, game) = server_and_game(&server_id, &games_state).await?;
+    Ok(game.actions)
+}
+
+/// Run one of the server's game actions. The server has to be running; a script's output
+/// goes to the console and is returned.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn run_game_action(
+    server_id: String,
+    action: String,
+    app: AppHandle,
+    games_state: State<'_, GamesState>,
+) -> Result<String, AppError> {
+    let (server, game) = server_and_game(&server_id, &games_state).await?;
+    let declared = game.actions.iter().find(|a| a.id == action)
+        .ok_or_else(|| AppError::InvalidInput(format!("{} has no action '{}'", game.name, action)))?;
+
+    let docker = app.state::<DockerState>().manager()?;
+    if lifecycle::current_status(&docker, &server).await? != ServerStatus::Running {
+        return Err(AppError::InvalidInput(format!("Start the server to run '{}'", declared.label)));
+    }
+    let env = lifecycle::container_env(
+        &game,
+        server.memory_mb,
+        server.port,
+        &server.config,
+        &server.env,
+        &lifecycle::extra_ports(&server, Some(&game)),
+    );
+    let resolved = declared.resolve(&env);
+    tracing::info!("Running action '{}' on {}", resolved.id, server_id);
+
+    let mut output = Vec::new();
+    if let Some(command) = resolved.command.as_deref().filter(|c| !c.is_empty()) {
+        output.push(console_command(&docker, &server_id, command).await?);
+    }
+    if let Some(script) = resolved.script.as_deref().filter(|s| !s.is_empty()) {
+        let container_id = server.container_id.as_deref().ok_or("No container ID")?;
+        let console = events::LogBatch::new(&app, &server_id);
+        let exit_code = docker
+            .exec_command(container_id, vec!["sh", "-c", script], Some(&game.volume_path), |line| {
+                console.push(line.clone());
+                output.push(line);
+            })
+            .await?;
+        if exit_code != 0 {
+            return Err(format!("'{}' failed with exit code {}", resolved.label, exit_code).into());
+        }
+    }
+    Ok(output.join("\n"))
+}
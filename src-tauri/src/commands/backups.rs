@@ -0,0 +1,138 @@
+// Backup commands - back up a server's files, list, restore or delete its backups, and copy
+// them to and from backup targets off this computer
+
+use crate::backup_targets::{self, RemoteBackup};
+use crate::backups::{self, Backup};
+use crate::commands::server::{ServerResponse, ServerState};
+use crate::error::AppError;
+use chrono::{Local, Utc};
+use serverwave_core::server::{BackupSchedule, BackupTarget};
+use tauri::{AppHandle, State};
+
+/// A server's backups, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_backups(server_id: String) -> Result<Vec<Backup>, AppError> {
+    Ok(backups::list(&server_id))
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn create_backup(server_id: String, note: Option<String>, app: AppHandle) -> Result<Backup, AppError> {
+    backups::create(&app, &server_id, note).await
+}
+
+/// Replace the server's files with a backup's, stopping and restarting it if it runs
+#[tauri::command(rename_all = "camelCase")]
+pub async fn restore_backup(server_id: String, backup_id: String, app: AppHandle) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
+    backups::restore(&app, &server_id, &backup_id).await
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_backup(server_id: String, backup_id: String) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
+    backups::delete(&backups::find(&server_id, &backup_id)?)
+}
+
+/// Back the server up on a schedule, or None to stop. The schedule's retention applies to all
+/// of the server's backups.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_backup_schedule(
+    server_id: String,
+    schedule: Option<BackupSchedule>,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    if let Some(schedule) = &schedule {
+        schedule.validate().map_err(AppError::InvalidInput)?;
+    }
+    let server = state.configs.update(&server_id, |s| {
+        let last_run = s.backup_schedule.as_ref().and_then(|old| old.last_run);
+        s.backup_schedule = schedule.map(|mut schedule| {
+            schedule.last_run = last_run;
+            schedule.next_run = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+            schedule
+        });
+    })?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Add or change an off-machine backup target - a new one when its id is empty. The secret key
+/// or password goes to the keychain, and is kept as it was when None.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_backup_target(
+    server_id: String,
+    mut target: BackupTarget,
+    secret: Option<String>,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    target.validate().map_err(AppError::InvalidInput)?;
+    if target.id.is_empty() {
+        target.id = uuid::Uuid::new_v4().to_string()[..8].to_string();
+    }
+    if let Some(secret) = secret.filter(|s| !s.is_empty()) {
+        backup_targets::save_secret(&server_id, &target.id, &secret).await?;
+    }
+    let server = state.configs.update(&server_id, |s| {
+        match s.backup_targets.iter_mut().find(|t| t.id == target.id) {
+            Some(existing) => *existing = target,
+            None => s.backup_targets.push(target),
+        }
+    })?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Stop using a backup target. The backups already on it stay there.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_backup_target(
+    server_id: String,
+    target_id: String,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    crate::security::ensure_unlocked().await?;
+    let server = state.configs.update(&server_id, |s| s.backup_targets.retain(|t| t.id != target_id))?;
+    backup_targets::forget_secret(&server_id, &target_id).await;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Check a target can be reached with its credentials
+#[tauri::command(rename_all = "camelCase")]
+pub async fn test_backup_target(server_id: String, target_id: String) -> Result<(), AppError> {
+    backup_targets::test(&server_id, &target_id).await
+}
+
+/// Copy a backup to a target, sending BackupTransfer events as it goes
+#[tauri::command(rename_all = "camelCase")]
+pub async fn push_backup(server_id: String, backup_id: String, target_id: String, app: AppHandle) -> Result<(), AppError> {
+    backup_targets::push(&app, &server_id, &backup_id, &target_id).await
+}
+
+/// The server's backups on a target, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_remote_backups(server_id: String, target_id: String) -> Result<Vec<RemoteBackup>, AppError> {
+    backup_targets::list(&server_id, &target_id).await
+}
+
+/// Copy a backup from a target into the server's local backups, so it can be restored
+#[tauri::command(rename_all = "camelCase")]
+pub async fn pull_backup(
+    server_id: String,
+    target_id: String,
+    backup_id: String,
+    app: AppHandle,
+) -> Result<Backup, AppError> {
+    backup_targets::pull(&app, &server_id, &target_id, &backup_id).await
+}
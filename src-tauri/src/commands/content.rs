@@ -0,0 +1,757 @@
+// Content commands - find, install and update Minecraft mods, plugins and modpacks
+// from Modrinth and CurseForge
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::ServerState;
+use crate::commands::settings::load_app_settings;
+use crate::content::curseforge::{self, CurseForge, Manifest};
+use crate::content::dependencies;
+use crate::content::loader::{ContentTarget, Loader};
+use crate::content::modrinth::{self, SearchHit, Version};
+use crate::content::server_jar::{self, Flavor};
+use crate::content::inventory::{self, ScannedJar};
+use crate::content::cache::{self, CacheUsage};
+use crate::content::{download_file, download_immutable, extract_zip, zip_root};
+use crate::docker::ScriptOptions;
+use serverwave_core::docker::steam_cache;
+use crate::error::AppError;
+use crate::events::{self, ServerEvent};
+use crate::games::java;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use serverwave_core::server::{lifecycle, load_server_config, ServerStatus};
+use tauri::{AppHandle, Manager, State};
+
+const SEARCH_LIMIT: u32 = 20;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledMod {
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    pub file_name: String,
+    /// Older jars of the same project that were removed
+    pub replaced: Vec<String>,
+    /// Jars of required dependencies installed with it
+    pub dependencies: Vec<String>,
+    /// Dependencies that couldn't be installed and installed mods that clash with it
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModUpdate {
+    pub file_name: String,
+    pub project_id: String,
+    pub current_version: Option<String>,
+    pub latest_version_id: String,
+    pub latest_version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModpackInstall {
+    pub name: String,
+    pub version: String,
+    pub minecraft_version: Option<String>,
+    /// Loader the pack was built for, e.g. "forge-47.2.0"
+    pub loader: Option<String>,
+    pub files: usize,
+    /// Mods whose authors only allow downloads from curseforge.com - these go in mods/ by hand
+    pub manual_downloads: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionChange {
+    pub flavor: Flavor,
+    pub minecraft_version: String,
+    pub build: String,
+    /// What SERVER_JARFILE points at now
+    pub jar_file: String,
+    /// The previous jar, kept next to it as <jar>.old
+    pub backup: Option<String>,
+}
+
+/// A jar in plugins/ or mods/ and what Modrinth or CurseForge know about it
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledContent {
+    pub file_name: String,
+    pub size_bytes: u64,
+    /// "modrinth" or "curseforge", None when neither knows the file
+    pub source: Option<String>,
+    pub project_id: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateOutcome {
+    Updated,
+    UpToDate,
+    /// Neither source knows the file, so it can't be updated
+    Unknown,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentUpdateResult {
+    pub file_name: String,
+    pub outcome: UpdateOutcome,
+    pub message: Option<String>,
+    pub new_file: Option<String>,
+}
+
+/// A newer file that fits the server
+struct Replacement {
+    version: String,
+    file_name: String,
+    /// None when the author only allows downloads from the website
+    url: Option<String>,
+    sha1: Option<String>,
+}
+
+struct Identified {
+    jar: ScannedJar,
+    source: Option<&'static str>,
+    project_id: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+    replacement: Option<Replacement>,
+}
+
+async fn target_for(server_id: &str) -> Result<ContentTarget, AppError> {
+    let server = load_server_config(server_id)?;
+    Ok(ContentTarget::for_server(&server)?)
+}
+
+/// Search Modrinth - with a server, only what its loader and Minecraft version can run
+#[tauri::command(rename_all = "camelCase")]
+pub async fn search_modrinth(query: String, server_id: Option<String>) -> Result<Vec<SearchHit>, AppError> {
+    let target = match server_id {
+        Some(id) => Some(target_for(&id).await?),
+        None => None,
+    };
+    Ok(modrinth::search(&query, target.as_ref(), SEARCH_LIMIT).await?)
+}
+
+/// Versions of a project the server can run, newest first
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_modrinth_versions(server_id: String, project: String) -> Result<Vec<Version>, AppError> {
+    let target = target_for(&server_id).await?;
+    Ok(modrinth::versions(&project, &target).await?)
+}
+
+/// Download a project's jar into plugins/ or mods/, replacing any older version of it.
+/// `version` is a version id or number; the newest compatible one when left out.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn install_mod(
+    server_id: String,
+    project: String,
+    version: Option<String>,
+) -> Result<InstalledMod, AppError> {
+    let target = target_for(&server_id).await?;
+    let versions = modrinth::versions(&project, &target).await?;
+    let chosen = match &version {
+        Some(wanted) => versions
+            .iter()
+            .find(|v| &v.id == wanted || &v.version_number == wanted)
+            .ok_or_else(|| {
+                AppError::InvalidInput(format!("Version '{}' of '{}' doesn't support this server", wanted, project))
+            })?,
+        None => versions.first().ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "'{}' has no version for {:?} {}",
+                project,
+                target.loader,
+                target.game_version.as_deref().unwrap_or("")
+            ))
+        })?,
+    };
+    let file = chosen.primary_file().ok_or("That version has no files")?;
+
+    let scan_target = target.clone();
+    let existing = tokio::task::spawn_blocking(move || inventory::scan(&scan_target)).await?;
+    let hashes: Vec<String> = existing.iter().map(|jar| jar.sha1.clone()).collect();
+    // Only used to clean up old versions - install anyway if Modrinth doesn't answer
+    let known = modrinth::identify(&hashes).await.unwrap_or_default();
+
+    let path = modrinth::download(file, &target.dir).await?;
+    tracing::info!("Installed {} {} on {}", project, chosen.version_number, server_id);
+
+    let mut replaced = Vec::new();
+    for jar in existing {
+        let same_project = known.get(&jar.sha1).is_some_and(|v| v.project_id == chosen.project_id);
+        if same_project && jar.path != path {
+            std::fs::remove_file(&jar.path)?;
+            replaced.push(jar.file_name());
+        }
+    }
+
+    let installed: HashSet<String> = known.values().map(|v| v.project_id.clone()).collect();
+    let resolution = dependencies::resolve_modrinth(chosen, &target, &installed).await;
+    for warning in &resolution.warnings {
+        tracing::warn!("{} on {}: {}", project, server_id, warning);
+    }
+
+    Ok(InstalledMod {
+        project_id: chosen.project_id.clone(),
+        version_id: chosen.id.clone(),
+        version_number: chosen.version_number.clone(),
+        file_name: file.filename.clone(),
+        replaced,
+        dependencies: resolution.installed,
+        warnings: resolution.warnings,
+    })
+}
+
+/// Installed Modrinth jars that have a newer version for the server's loader and Minecraft version
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_mod_updates(server_id: String) -> Result<Vec<ModUpdate>, AppError> {
+    let target = target_for(&server_id).await?;
+    let scan_target = target.clone();
+    let jars = tokio::task::spawn_blocking(move || inventory::scan(&scan_target)).await?;
+    let hashes: Vec<String> = jars.iter().map(|jar| jar.sha1.clone()).collect();
+    let current = modrinth::identify(&hashes).await?;
+    let latest = modrinth::latest_for(&hashes, &target).await?;
+
+    let updates = jars
+        .into_iter()
+        .filter_map(|jar| {
+            let newest = latest.get(&jar.sha1)?;
+            let newest_hash = newest.primary_file()?.hashes.get("sha1")?;
+            if newest_hash.eq_ignore_ascii_case(&jar.sha1) {
+                return None;
+            }
+            Some(ModUpdate {
+                file_name: jar.file_name(),
+                project_id: newest.project_id.clone(),
+                current_version: current.get(&jar.sha1).map(|v| v.version_number.clone()),
+                latest_version_id: newest.id.clone(),
+                latest_version: newest.version_number.clone(),
+            })
+        })
+        .collect();
+    Ok(updates)
+}
+
+fn curseforge() -> Result<CurseForge, AppError> {
+    Ok(CurseForge::new(load_app_settings().curseforge_api_key.as_deref())?)
+}
+
+/// Search CurseForge mods (or plugins, for Paper servers) - or modpacks when `modpacks` is set
+#[tauri::command(rename_all = "camelCase")]
+pub async fn search_curseforge(
+    query: String,
+    server_id: Option<String>,
+    modpacks: Option<bool>,
+) -> Result<Vec<curseforge::Project>, AppError> {
+    let target = match server_id {
+        Some(id) => Some(target_for(&id).await?),
+        None => None,
+    };
+    let class_id = match (modpacks.unwrap_or(false), target.as_ref().map(|t| t.loader)) {
+        (true, _) => curseforge::CLASS_MODPACKS,
+        (false, Some(Loader::Paper)) => curseforge::CLASS_PLUGINS,
+        (false, _) => curseforge::CLASS_MODS,
+    };
+    Ok(curseforge()?.search(&query, class_id, target.as_ref()).await?)
+}
+
+/// Download a CurseForge mod's jar into plugins/ or mods/ - the newest file that fits
+/// the server unless `file_id` picks one
+#[tauri::command(rename_all = "camelCase")]
+pub async fn install_curseforge_mod(
+    server_id: String,
+    mod_id: u64,
+    file_id: Option<u64>,
+) -> Result<InstalledMod, AppError> {
+    let target = target_for(&server_id).await?;
+    let client = curseforge()?;
+    let file = match file_id {
+        Some(file_id) => {
+            let file = client.file(mod_id, file_id).await?;
+            if !file.fits(&target) {
+                return Err(AppError::InvalidInput(format!("{} doesn't support this server", file.display_name)));
+            }
+            file
+        }
+        None => client
+            .files(mod_id, Some(&target))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::InvalidInput("No file of this mod supports this server".into()))?,
+    };
+    let url = file.download_url.as_deref().ok_or_else(|| {
+        AppError::InvalidInput(format!(
+            "The author only allows downloads from the website: {}",
+            file.website_url()
+        ))
+    })?;
+    download_file(url, &file.file_name, file.sha1(), &target.dir).await?;
+    tracing::info!("Installed CurseForge file {} on {}", file.file_name, server_id);
+
+    let scan_target = target.clone();
+    let fingerprints: Vec<u32> = tokio::task::spawn_blocking(move || inventory::scan(&scan_target))
+        .await?
+        .iter()
+        .map(|jar| jar.fingerprint)
+        .collect();
+    // Only used to skip dependencies that are already there
+    let installed: HashSet<u64> = client
+        .identify(&fingerprints)
+        .await
+        .unwrap_or_default()
+        .values()
+        .map(|f| f.mod_id)
+        .collect();
+    let resolution = dependencies::resolve_curseforge(&client, &file, &target, &installed).await;
+    for warning in &resolution.warnings {
+        tracing::warn!("CurseForge mod {} on {}: {}", mod_id, server_id, warning);
+    }
+
+    Ok(InstalledMod {
+        project_id: mod_id.to_string(),
+        version_id: file.id.to_string(),
+        version_number: file.display_name.clone(),
+        file_name: file.file_name.clone(),
+        replaced: Vec::new(),
+        dependencies: resolution.installed,
+        warnings: resolution.warnings,
+    })
+}
+
+/// Put a CurseForge modpack's server files into the data folder - the author's server
+/// pack when there is one, otherwise the pack's mods plus its overrides
+#[tauri::command(rename_all = "camelCase")]
+pub async fn install_curseforge_modpack(
+    server_id: String,
+    mod_id: u64,
+    file_id: Option<u64>,
+    app: AppHandle,
+) -> Result<ModpackInstall, AppError> {
+    let server = load_server_config(&server_id)?;
+    let docker = app.state::<DockerState>().manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if matches!(status, ServerStatus::Running | ServerStatus::Starting | ServerStatus::Installing) {
+        return Err(AppError::InvalidInput("Stop the server before installing a modpack".into()));
+    }
+
+    let client = curseforge()?;
+    let pack = match file_id {
+        Some(file_id) => client.file(mod_id, file_id).await?,
+        None => client
+            .files(mod_id, None)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or("The modpack has no files")?,
+    };
+    let log = |line: String| events::emit(&app, ServerEvent::log(&server_id, format!("[Modpack] {}", line)));
+    let staging = std::env::temp_dir().join(format!("serverwave-modpack-{}", server.id));
+    let data_path = server.data_path.clone();
+
+    let result = match pack.server_pack_file_id {
+        Some(server_pack_id) => {
+            let server_pack = client.file(mod_id, server_pack_id).await?;
+            let url = server_pack.download_url.as_deref().ok_or("The server pack can't be downloaded")?;
+            log(format!("Downloading server pack {}", server_pack.file_name));
+            let archive = download_file(url, &server_pack.file_name, server_pack.sha1(), &staging).await?;
+            let files = tokio::task::spawn_blocking(move || {
+                let root = zip_root(&archive)?.unwrap_or_default();
+                extract_zip(&archive, &root, &data_path)
+            })
+            .await??;
+            let game_version = pack.game_versions.iter().find(|v| v.starts_with(|c: char| c.is_ascii_digit()));
+            let loader = pack
+                .game_versions
+                .iter()
+                .find(|v| ["Forge", "NeoForge", "Fabric", "Quilt"].contains(&v.as_str()));
+            ModpackInstall {
+                name: pack.display_name.clone(),
+                version: pack.display_name.clone(),
+                minecraft_version: game_version.cloned(),
+                loader: loader.map(|l| l.to_lowercase()),
+                files,
+                manual_downloads: Vec::new(),
+            }
+        }
+        None => {
+            let url = pack.download_url.as_deref().ok_or("The modpack can't be downloaded")?;
+            log(format!("Downloading {}", pack.file_name));
+            let archive = download_file(url, &pack.file_name, pack.sha1(), &staging).await?;
+            let manifest_path = archive.clone();
+            let manifest = tokio::task::spawn_blocking(move || Manifest::read(&manifest_path)).await??;
+
+            let file_ids: Vec<u64> = manifest.files.iter().filter(|f| f.required).map(|f| f.file_id).collect();
+            let mods = client.files_by_id(&file_ids).await?;
+            let mods_dir = data_path.join("mods");
+            let mut files = 0;
+            let mut manual_downloads = Vec::new();
+            for (i, file) in mods.iter().enumerate() {
+                if file.client_only() {
+                    continue;
+                }
+                match file.download_url.as_deref() {
+                    Some(url) => {
+                        log(format!("({}/{}) {}", i + 1, mods.len(), file.file_name));
+                        download_file(url, &file.file_name, file.sha1(), &mods_dir).await?;
+                        files += 1;
+                    }
+                    None => manual_downloads.push(format!("{} ({})", file.display_name, file.website_url())),
+                }
+            }
+
+            let overrides = manifest.overrides.clone();
+            files += tokio::task::spawn_blocking(move || extract_zip(&archive, &overrides, &data_path)).await??;
+            ModpackInstall {
+                name: manifest.name.clone(),
+                version: manifest.version.clone(),
+                minecraft_version: Some(manifest.minecraft.version.clone()),
+                loader: manifest.primary_loader().map(str::to_string),
+                files,
+                manual_downloads,
+            }
+        }
+    };
+    let _ = std::fs::remove_dir_all(&staging);
+
+    if let Some(version) = &result.minecraft_version {
+        app.state::<ServerState>().configs.update(&server_id, |server| {
+            if server.config.contains_key("MINECRAFT_VERSION") {
+                server.config.insert("MINECRAFT_VERSION".to_string(), version.clone());
+                server.config_outdated = server.container_id.is_some();
+            }
+        })?;
+    }
+    log(format!("Installed {} {} ({} files)", result.name, result.version, result.files));
+    for name in &result.manual_downloads {
+        log(format!("Download by hand into mods/: {}", name));
+    }
+    Ok(result)
+}
+
+/// Minecraft versions a server flavor can be switched to, newest first
+#[tauri::command]
+pub async fn get_server_versions(flavor: Flavor) -> Result<Vec<String>, AppError> {
+    Ok(server_jar::versions(flavor).await?)
+}
+
+/// Switch a Minecraft server to another version or loader by swapping only the server jar.
+/// Worlds, configs, plugins and mods stay; the old jar is kept as <jar>.old.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn change_server_version(
+    server_id: String,
+    flavor: Flavor,
+    version: String,
+    app: AppHandle,
+    games_state: State<'_, GamesState>,
+) -> Result<VersionChange, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game_type = server.game_type.0.to_lowercase();
+    if !game_type.contains("minecraft") || game_type.contains("bedrock") {
+        return Err(AppError::InvalidInput("Only Minecraft Java servers can switch versions".into()));
+    }
+    let version = version.trim().to_string();
+    if version.is_empty() {
+        return Err(AppError::InvalidInput("Pick a Minecraft version".into()));
+    }
+    let docker = app.state::<DockerState>().manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if matches!(status, ServerStatus::Running | ServerStatus::Starting | ServerStatus::Installing) {
+        return Err(AppError::InvalidInput("Stop the server before changing its version".into()));
+    }
+
+    let game = games_state
+        .manager
+        .lock()
+        .await
+        .get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+    // A picked image variant stays; otherwise the version may need another Java
+    let runtime_image = match game.image_variant(&server.config) {
+        Some(_) => server.docker_image.clone(),
+        None => java::image_for_version(&game, &version),
+    };
+
+    let log = |line: String| events::emit(&app, ServerEvent::log(&server_id, format!("[Version] {}", line)));
+    let jar = server_jar::resolve(flavor, &version).await?;
+    let data_path = server.data_path.clone();
+
+    // A jar named after an old Forge install would be misleading for anything else
+    let current = server.config.get("SERVER_JARFILE").cloned().unwrap_or_else(|| "server.jar".to_string());
+    let mut jar_file = match flavor {
+        Flavor::Forge => current.clone(),
+        _ if current.starts_with("forge-") => "server.jar".to_string(),
+        _ => current.clone(),
+    };
+
+    let backup = if data_path.join(&current).is_file() {
+        let backup = format!("{}.old", current);
+        std::fs::copy(data_path.join(&current), data_path.join(&backup))?;
+        log(format!("Kept the current jar as {}", backup));
+        Some(backup)
+    } else {
+        None
+    };
+
+    for removed in server_jar::clear_loader_files(&data_path, flavor.loader()) {
+        log(format!("Removed {} from the previous loader", removed));
+    }
+
+    log(format!("Downloading {} {} ({})", flavor.name(), version, jar.build));
+    if flavor == Flavor::Forge {
+        const INSTALLER: &str = "forge-installer.jar";
+        // The installer runs on the Java the new version needs
+        let image = runtime_image.clone().unwrap_or_else(|| game.docker_image.clone());
+        let volume_path = game.volume_path.clone();
+        download_immutable(&jar.url, INSTALLER, &data_path).await?;
+        log("Running the Forge installer".to_string());
+        let script = format!(
+            "java -jar {0} --installServer; code=$?; rm -f {0} {0}.log; exit $code",
+            INSTALLER
+        );
+        let (exit_code, container_id) = docker
+            .run_script(&image, &data_path, &volume_path, &script, ScriptOptions::default(), |_| {}, log)
+            .await?;
+        docker.remove_install_container(&container_id).await.ok();
+        if exit_code != 0 {
+            return Err(AppError::Other(format!("The Forge installer failed with exit code {}", exit_code)));
+        }
+        jar_file = server_jar::forge_server_jar(&data_path, &version, &jar.build).ok_or_else(|| {
+            AppError::Other(format!(
+                "Forge {} for {} only starts through run.sh, which this server's startup command doesn't use",
+                jar.build, version
+            ))
+        })?;
+    } else {
+        download_immutable(&jar.url, &jar_file, &data_path).await?;
+    }
+
+    if server.docker_image != runtime_image {
+        let image = runtime_image.as_deref().unwrap_or(&game.docker_image);
+        log(format!("Switching the server to {}", image));
+    }
+    app.state::<ServerState>().configs.update(&server_id, |server| {
+        if server.config.contains_key("SERVER_JARFILE") {
+            server.config.insert("SERVER_JARFILE".to_string(), jar_file.clone());
+        }
+        if server.config.contains_key("MINECRAFT_VERSION") {
+            server.config.insert("MINECRAFT_VERSION".to_string(), version.clone());
+        }
+        if flavor == Flavor::Paper && server.config.contains_key("BUILD_NUMBER") {
+            server.config.insert("BUILD_NUMBER".to_string(), jar.build.clone());
+        }
+        server.docker_image = runtime_image;
+        // The startup command names the jar
+        server.config_outdated = server.container_id.is_some();
+    })?;
+
+    log(format!("{} {} is installed as {}", flavor.name(), version, jar_file));
+    Ok(VersionChange {
+        flavor,
+        minecraft_version: version,
+        build: jar.build,
+        jar_file,
+        backup,
+    })
+}
+
+/// Scan the content folder and look every jar up - Modrinth by sha1 first, then CurseForge
+/// by fingerprint when there's an API key. A source that can't be reached just leaves its
+/// files unknown.
+async fn identify_installed(target: &ContentTarget) -> Result<Vec<Identified>, AppError> {
+    let scan_target = target.clone();
+    let jars = tokio::task::spawn_blocking(move || inventory::scan(&scan_target)).await?;
+
+    let hashes: Vec<String> = jars.iter().map(|jar| jar.sha1.clone()).collect();
+    let modrinth_current = modrinth::identify(&hashes).await.unwrap_or_else(|e| {
+        tracing::warn!("Modrinth lookup failed: {}", e);
+        HashMap::new()
+    });
+    let modrinth_latest = modrinth::latest_for(&hashes, target).await.unwrap_or_default();
+    let mut project_ids: Vec<String> = modrinth_current.values().map(|v| v.project_id.clone()).collect();
+    project_ids.sort();
+    project_ids.dedup();
+    let modrinth_names = modrinth::project_names(&project_ids).await.unwrap_or_default();
+
+    let unknown: Vec<u32> = jars
+        .iter()
+        .filter(|jar| !modrinth_current.contains_key(&jar.sha1))
+        .map(|jar| jar.fingerprint)
+        .collect();
+    let client = CurseForge::new(load_app_settings().curseforge_api_key.as_deref()).ok();
+    let mut cf_current = HashMap::new();
+    let mut cf_latest = HashMap::new();
+    let mut cf_names = HashMap::new();
+    if let Some(client) = client.as_ref().filter(|_| !unknown.is_empty()) {
+        cf_current = client.identify(&unknown).await.unwrap_or_else(|e| {
+            tracing::warn!("CurseForge lookup failed: {}", e);
+            HashMap::new()
+        });
+        let mod_ids: Vec<u64> = cf_current.values().map(|f| f.mod_id).collect();
+        for mod_id in &mod_ids {
+            if let Ok(files) = client.files(*mod_id, Some(target)).await {
+                if let Some(newest) = files.into_iter().next() {
+                    cf_latest.insert(*mod_id, newest);
+                }
+            }
+        }
+        cf_names = client.project_names(&mod_ids).await.unwrap_or_default();
+    }
+
+    let identified = jars
+        .into_iter()
+        .map(|jar| {
+            if let Some(current) = modrinth_current.get(&jar.sha1) {
+                let replacement = modrinth_latest
+                    .get(&jar.sha1)
+                    .and_then(|newest| Some((newest, newest.primary_file()?)))
+                    .filter(|(_, file)| file.hashes.get("sha1").is_none_or(|h| !h.eq_ignore_ascii_case(&jar.sha1)))
+                    .map(|(newest, file)| Replacement {
+                        version: newest.version_number.clone(),
+                        file_name: file.filename.clone(),
+                        url: Some(file.url.clone()),
+                        sha1: file.hashes.get("sha1").cloned(),
+                    });
+                return Identified {
+                    source: Some("modrinth"),
+                    project_id: Some(current.project_id.clone()),
+                    name: modrinth_names.get(&current.project_id).cloned(),
+                    version: Some(current.version_number.clone()),
+                    replacement,
+                    jar,
+                };
+            }
+            if let Some(current) = cf_current.get(&jar.fingerprint) {
+                let replacement = cf_latest
+                    .get(&current.mod_id)
+                    .filter(|newest| newest.id != current.id && newest.file_date > current.file_date)
+                    .map(|newest| Replacement {
+                        version: newest.display_name.clone(),
+                        file_name: newest.file_name.clone(),
+                        url: newest.download_url.clone(),
+                        sha1: newest.sha1().map(str::to_string),
+                    });
+                return Identified {
+                    source: Some("curseforge"),
+                    project_id: Some(current.mod_id.to_string()),
+                    name: cf_names.get(&current.mod_id).cloned(),
+                    version: Some(current.display_name.clone()),
+                    replacement,
+                    jar,
+                };
+            }
+            Identified {
+                source: None,
+                project_id: None,
+                name: None,
+                version: None,
+                replacement: None,
+                jar,
+            }
+        })
+        .collect();
+    Ok(identified)
+}
+
+/// Every jar in the server's plugins/ or mods/ folder, identified where possible, with
+/// whether a newer version fits the server
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_installed_content(server_id: String) -> Result<Vec<InstalledContent>, AppError> {
+    let target = target_for(&server_id).await?;
+    let identified = identify_installed(&target).await?;
+    Ok(identified
+        .into_iter()
+        .map(|item| InstalledContent {
+            file_name: item.jar.file_name(),
+            size_bytes: item.jar.size,
+            source: item.source.map(str::to_string),
+            project_id: item.project_id,
+            name: item.name,
+            version: item.version,
+            update_available: item.replacement.is_some(),
+            latest_version: item.replacement.map(|r| r.version),
+        })
+        .collect())
+}
+
+/// Update every identified jar that has a newer compatible version. Each jar gets its own
+/// result - one failed download doesn't stop the rest.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_all_content(server_id: String) -> Result<Vec<ContentUpdateResult>, AppError> {
+    let target = target_for(&server_id).await?;
+    let identified = identify_installed(&target).await?;
+
+    let mut results = Vec::new();
+    for item in identified {
+        let file_name = item.jar.file_name();
+        let result = match (&item.source, item.replacement) {
+            (None, _) => ContentUpdateResult {
+                file_name,
+                outcome: UpdateOutcome::Unknown,
+                message: None,
+                new_file: None,
+            },
+            (Some(_), None) => ContentUpdateResult {
+                file_name,
+                outcome: UpdateOutcome::UpToDate,
+                message: item.version,
+                new_file: None,
+            },
+            (Some(_), Some(replacement)) => match replace_jar(&item.jar, &replacement, &target).await {
+                Ok(()) => {
+                    tracing::info!("Updated {} to {} on {}", file_name, replacement.version, server_id);
+                    ContentUpdateResult {
+                        file_name,
+                        outcome: UpdateOutcome::Updated,
+                        message: Some(replacement.version),
+                        new_file: Some(replacement.file_name),
+                    }
+                }
+                Err(e) => ContentUpdateResult {
+                    file_name,
+                    outcome: UpdateOutcome::Failed,
+                    message: Some(e),
+                    new_file: None,
+                },
+            },
+        };
+        results.push(result);
+    }
+    Ok(results)
+}
+
+async fn replace_jar(jar: &ScannedJar, replacement: &Replacement, target: &ContentTarget) -> Result<(), String> {
+    let url = replacement
+        .url
+        .as_deref()
+        .ok_or("The author only allows downloads from the website")?;
+    let path = download_file(url, &replacement.file_name, replacement.sha1.as_deref(), &target.dir).await?;
+    if path != jar.path {
+        std::fs::remove_file(&jar.path).map_err(|e| format!("Installed the update but couldn't remove the old jar: {}", e))?;
+    }
+    Ok(())
+}
+
+/// How much the shared download cache holds
+#[tauri::command]
+pub async fn get_content_cache() -> Result<CacheUsage, AppError> {
+    Ok(tokio::task::spawn_blocking(|| CacheUsage {
+        steamcmd_bytes: steam_cache::size_bytes(),
+        ..cache::usage()
+    })
+    .await?)
+}
+
+#[tauri::command]
+pub async fn clear_content_cache() -> Result<(), AppError> {
+    tokio::task::spawn_blocking(|| {
+        cache::clear()?;
+        steam_cache::clear().map_err(|e| format!("Failed to clear the SteamCMD cache: {}", e))
+    })
+    .await??;
+    Ok(())
+}
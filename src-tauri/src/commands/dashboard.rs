@@ -0,0 +1,84 @@
+// Dashboard command - what the home screen shows, gathered in one call
+
+use crate::capacity;
+use crate::commands::docker::DockerState;
+use crate::commands::server::list_servers;
+use crate::disk_guard;
+use crate::error::AppError;
+use crate::events::{self, RecentEvent};
+use crate::security::{can_access, Permission};
+use crate::updates::{self, UpdateCheck};
+use serde::Serialize;
+use serverwave_core::paths;
+use serverwave_core::server::{calculate_dir_size, ServerStatus};
+use std::collections::HashMap;
+use tauri::State;
+
+/// Events listed on the dashboard
+const RECENT_EVENTS: usize = 10;
+
+#[derive(Debug, Serialize)]
+pub struct DashboardSummary {
+    pub servers: usize,
+    pub by_status: HashMap<ServerStatus, usize>,
+    /// Memory limits of all servers, and of the ones running
+    pub allocated_memory_mb: u64,
+    pub running_memory_mb: u64,
+    /// Memory containers can use - the Docker VM's on Docker Desktop. 0 when unknown.
+    pub host_memory_mb: u64,
+    pub host_free_memory_mb: Option<u64>,
+    /// Space the servers' folders take up
+    pub disk_used_bytes: u64,
+    /// Free space on the disk servers are on
+    pub disk_free_bytes: Option<u64>,
+    pub recent_events: Vec<RecentEvent>,
+    pub pending_updates: Vec<UpdateCheck>,
+}
+
+/// Server counts, memory, disk, recent events and pending updates for the home screen
+#[tauri::command]
+pub async fn get_dashboard_summary(docker_state: State<'_, DockerState>) -> Result<DashboardSummary, AppError> {
+    let docker = docker_state.manager()?;
+    let servers = list_servers(docker_state).await?;
+
+    let mut by_status = HashMap::new();
+    for server in &servers {
+        *by_status.entry(server.status.clone()).or_insert(0) += 1;
+    }
+    let running_memory_mb = servers
+        .iter()
+        .filter(|s| matches!(s.status, ServerStatus::Running | ServerStatus::Starting))
+        .map(|s| u64::from(s.memory_mb))
+        .sum();
+    let (host_free_memory_mb, host_memory_mb) = capacity::memory(&docker).await;
+
+    let data_paths: Vec<_> = servers.iter().map(|s| s.data_path.clone()).collect();
+    let (disk_used_bytes, disk_free_bytes) = tokio::task::spawn_blocking(move || {
+        let used = data_paths.iter().filter(|p| p.exists()).map(|p| calculate_dir_size(p).unwrap_or(0)).sum();
+        (used, disk_guard::free_space(&paths::servers_dir()))
+    })
+    .await?;
+
+    let recent_events = events::recent()
+        .into_iter()
+        .filter(|e| can_access(Permission::View, Some(e.event.server_id())))
+        .take(RECENT_EVENTS)
+        .collect();
+    let pending_updates = updates::pending()
+        .into_iter()
+        .filter(|u| can_access(Permission::View, Some(&u.server_id)))
+        .collect();
+
+    Ok(DashboardSummary {
+        servers: servers.len(),
+        by_status,
+        allocated_memory_mb: servers.iter().map(|s| u64::from(s.memory_mb)).sum(),
+        running_memory_mb,
+        host_memory_mb,
+        host_free_memory_mb,
+        disk_used_bytes,
+        disk_free_bytes,
+        recent_events,
+        pending_updates,
+    })
+}
@@ -0,0 +1,47 @@
+// Database commands - the MariaDB, MySQL or Redis sidecar of a server
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::ServerState;
+use crate::database::{self, DatabaseInfo};
+use crate::error::AppError;
+use serverwave_core::server::{load_server_config, DatabaseKind};
+use tauri::State;
+
+/// The server's database and how to reach it, if it has one
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_database(server_id: String, docker_state: State<'_, DockerState>) -> Result<Option<DatabaseInfo>, AppError> {
+    let server = load_server_config(&server_id)?;
+    let docker = docker_state.manager()?;
+    Ok(database::info(&docker, &server).await?)
+}
+
+/// Give the server a database. The server gets its variables on the next start.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn add_database(
+    server_id: String,
+    kind: DatabaseKind,
+    state: State<'_, ServerState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<Option<DatabaseInfo>, AppError> {
+    let server = state.configs.get(&server_id)?;
+    let docker = docker_state.manager()?;
+    let sidecar = database::add(&docker, &server, kind).await?;
+    let server = state.configs.update(&server_id, |s| s.database = Some(sidecar))?;
+    Ok(database::info(&docker, &server).await?)
+}
+
+/// Remove the server's database container, and with `delete_data` everything in it
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_database(
+    server_id: String,
+    delete_data: bool,
+    state: State<'_, ServerState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
+    let server = state.configs.get(&server_id)?;
+    let docker = docker_state.manager()?;
+    database::remove(&docker, &server, delete_data).await?;
+    state.configs.update(&server_id, |s| s.database = None)?;
+    Ok(())
+}
@@ -0,0 +1,225 @@
+// Diagnostics commands - one zip with everything a bug report needs
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::load_all_server_configs;
+use crate::commands::settings::load_app_settings;
+use crate::error::AppError;
+use crate::games::FieldType;
+use crate::logging;
+use serde::Serialize;
+use serde_json::{json, Value};
+use serverwave_core::paths;
+use std::io::Write;
+use std::path::Path;
+use tauri::State;
+
+/// Container log lines included per server
+const CONTAINER_LOG_LINES: u32 = 300;
+/// Newest app log files included
+const APP_LOG_FILES: usize = 3;
+/// App log lines returned when the caller doesn't ask for a number
+const DEFAULT_LOG_LINES: u32 = 500;
+
+const REDACTED: &str = "[redacted]";
+
+#[derive(Debug, Serialize)]
+struct EnvironmentCheck {
+    name: &'static str,
+    ok: bool,
+    detail: String,
+}
+
+/// Write a diagnostics zip and return its path. Server variables that look like
+/// secrets are redacted; nothing is sent anywhere.
+#[tauri::command]
+pub async fn export_diagnostics(games_state: State<'_, GamesState>, docker_state: State<'_, DockerState>) -> Result<String, AppError> {
+    crate::security::ensure_unlocked().await?;
+
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut checks = Vec::new();
+
+    let docker = docker_state.manager();
+    match &docker {
+        Ok(docker) => match docker.get_info().await {
+            Ok(info) => {
+                checks.push(EnvironmentCheck {
+                    name: "docker",
+                    ok: true,
+                    detail: format!("Docker {} ({} {})", info.version, info.os, info.arch),
+                });
+                files.push(("docker.json".to_string(), serde_json::to_vec_pretty(&info)?));
+            }
+            Err(e) => checks.push(EnvironmentCheck {
+                name: "docker",
+                ok: false,
+                detail: e.to_string(),
+            }),
+        },
+        Err(e) => checks.push(EnvironmentCheck {
+            name: "docker",
+            ok: false,
+            detail: e.to_string(),
+        }),
+    }
+
+    let secret_vars: Vec<String> = {
+        let manager = games_state.manager.lock().await;
+        manager
+            .get_all_games()
+            .iter()
+            .flat_map(|g| g.variables.iter())
+            .filter(|v| v.field_type == FieldType::Password)
+            .map(|v| v.env.clone())
+            .collect()
+    };
+
+    let servers = load_all_server_configs();
+    for server in &servers {
+        let mut config = serde_json::to_value(server)?;
+        redact_server(&mut config, &secret_vars);
+        files.push((
+            format!("servers/{}.json", server.id),
+            serde_json::to_vec_pretty(&config)?,
+        ));
+
+        if let (Ok(docker), Some(container_id)) = (&docker, &server.container_id) {
+            let logs = docker
+                .get_logs(container_id, CONTAINER_LOG_LINES)
+                .await
+                .unwrap_or_else(|e| vec![format!("Failed to read logs: {}", e)]);
+            files.push((format!("servers/{}.log", server.id), logs.join("\n").into_bytes()));
+        }
+    }
+
+    let mut settings = serde_json::to_value(load_app_settings())?;
+    if !settings["notifications"]["webhook_url"].is_null() {
+        settings["notifications"]["webhook_url"] = json!(REDACTED);
+    }
+    if !settings["curseforge_api_key"].is_null() {
+        settings["curseforge_api_key"] = json!(REDACTED);
+    }
+    files.push(("settings.json".to_string(), serde_json::to_vec_pretty(&settings)?));
+
+    checks.push(writable_check("data directory", &paths::data_root()));
+    checks.push(writable_check("app directory", &paths::app_root()));
+
+    let log_files = logging::log_files();
+    for path in log_files.iter().skip(log_files.len().saturating_sub(APP_LOG_FILES)) {
+        if let (Some(name), Ok(content)) = (path.file_name(), std::fs::read(path)) {
+            files.push((format!("logs/{}", name.to_string_lossy()), content));
+        }
+    }
+
+    let summary = json!({
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "created_at": chrono::Utc::now(),
+        "app_root": paths::app_root(),
+        "data_root": paths::data_root(),
+        "server_count": servers.len(),
+        "checks": checks,
+    });
+    files.push(("summary.json".to_string(), serde_json::to_vec_pretty(&summary)?));
+
+    let dir = paths::app_root().join("diagnostics");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "serverwave-diagnostics-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    let zip_path = path.clone();
+    tokio::task::spawn_blocking(move || write_zip(&zip_path, files)).await??;
+
+    tracing::info!("Wrote diagnostics bundle to {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Recent lines from the app's own log, optionally only those containing `filter`
+/// (e.g. "ERROR", "docker" or a server id)
+#[tauri::command]
+pub async fn get_app_logs(filter: Option<String>, lines: Option<u32>) -> Result<Vec<String>, AppError> {
+    let lines = lines.unwrap_or(DEFAULT_LOG_LINES) as usize;
+    Ok(tokio::task::spawn_blocking(move || logging::read_logs(filter.as_deref(), lines)).await?)
+}
+
+fn write_zip(path: &Path, files: Vec<(String, Vec<u8>)>) -> Result<(), AppError> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in files {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(&content)?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Blank out tunnel tokens and any variable that is a password field or is named like a secret
+fn redact_server(config: &mut Value, secret_vars: &[String]) {
+    if let Some(token) = config.pointer_mut("/tunnel/token") {
+        *token = json!(REDACTED);
+    }
+    if let Some(vars) = config.get_mut("config").and_then(Value::as_object_mut) {
+        for (key, value) in vars.iter_mut() {
+            if secret_vars.contains(key) || looks_secret(key) {
+                *value = json!(REDACTED);
+            }
+        }
+    }
+}
+
+fn looks_secret(key: &str) -> bool {
+    let key = key.to_uppercase();
+    ["PASS", "TOKEN", "SECRET", "KEY", "GSLT", "AUTH"]
+        .iter()
+        .any(|marker| key.contains(marker))
+}
+
+/// Create `dir` if needed and write a file in it
+pub(crate) fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(".diagnostics-probe");
+    let result = std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&probe, b"ok"));
+    std::fs::remove_file(&probe).ok();
+    result
+}
+
+fn writable_check(name: &'static str, dir: &Path) -> EnvironmentCheck {
+    let result = probe_writable(dir);
+    EnvironmentCheck {
+        name,
+        ok: result.is_ok(),
+        detail: match result {
+            Ok(()) => format!("{} is writable", dir.display()),
+            Err(e) => format!("{}: {}", dir.display(), e),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_secret_looking_variables() {
+        let mut config = json!({
+            "id": "ab12cd34",
+            "config": {
+                "SERVER_PASSWORD": "hunter2",
+                "RCON_PASS": "hunter3",
+                "ADMIN_LIST": "alice",
+                "MC_MAXPLAYERS": "20"
+            },
+            "tunnel": { "provider": "ngrok", "token": "abc", "enabled": true }
+        });
+        redact_server(&mut config, &["ADMIN_LIST".to_string()]);
+
+        assert_eq!(config["config"]["SERVER_PASSWORD"], REDACTED);
+        assert_eq!(config["config"]["RCON_PASS"], REDACTED);
+        assert_eq!(config["config"]["ADMIN_LIST"], REDACTED);
+        assert_eq!(config["config"]["MC_MAXPLAYERS"], "20");
+        assert_eq!(config["tunnel"]["token"], REDACTED);
+    }
+}
@@ -1,53 +1,187 @@
 // Docker-related commands
 
-use crate::docker::DockerManager;
+use crate::docker::connection::{self, DockerConnection};
+use crate::docker::socket::{self, DetectedSocket};
+use crate::docker::{DockerError, DockerInfo, DockerManager};
+use crate::error::AppError;
+use crate::images::{self, ImageCleanup, ImageRefresh, InstallImage, StaleImage};
+use crate::reconcile::{self, Reconciliation};
+use crate::security::{can_access, Permission};
+use crate::wake;
 use serde::Serialize;
+use serverwave_core::server::Server;
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+/// The Docker client commands share, so each one doesn't connect again. Made on first use,
+/// and again after the connection settings change or Docker stopped answering.
+#[derive(Default)]
+pub struct DockerState {
+    client: Mutex<Option<(DockerConnection, bollard::Docker)>>,
+}
+
+impl DockerState {
+    /// A manager on the shared client, connecting first if there's none for the current settings
+    pub fn manager(&self) -> Result<DockerManager, DockerError> {
+        let connection = connection::current();
+        let mut client = self.client.lock().unwrap();
+        if let Some((connected, docker)) = client.as_ref() {
+            if *connected == connection {
+                return Ok(DockerManager::from_client(docker.clone()));
+            }
+        }
+        let docker = connection.connect()?;
+        *client = Some((connection, docker.clone()));
+        Ok(DockerManager::from_client(docker))
+    }
+
+    /// Drop the client so the next command connects again, e.g. after Docker restarted
+    pub fn reset(&self) {
+        *self.client.lock().unwrap() = None;
+    }
+}
 
 #[derive(Debug, Serialize)]
 pub struct DockerStatus {
     pub available: bool,
     pub running: bool,
     pub error: Option<String>,
+    /// The socket or address connected to, None for Docker's default (or DOCKER_HOST)
+    pub endpoint: Option<String>,
+    /// Docker version, when it answered
+    pub version: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct DockerInfo {
-    pub version: String,
-    pub api_version: String,
-    pub os: String,
-    pub arch: String,
-    pub containers_running: u64,
-    pub containers_total: u64,
-    pub images: u64,
+pub(crate) async fn status_of(connection: &DockerConnection) -> DockerStatus {
+    status_with(DockerManager::with_connection(connection).await, connection.endpoint()).await
 }
 
-/// Check if Docker is available and running
-#[tauri::command]
-pub async fn check_docker_status() -> Result<DockerStatus, String> {
-    match DockerManager::new().await {
+async fn status_with(docker: Result<DockerManager, DockerError>, endpoint: Option<String>) -> DockerStatus {
+    match docker {
         Ok(docker) => match docker.ping().await {
-            Ok(_) => Ok(DockerStatus {
+            Ok(_) => DockerStatus {
                 available: true,
                 running: true,
                 error: None,
-            }),
-            Err(e) => Ok(DockerStatus {
+                endpoint,
+                version: docker.get_info().await.ok().map(|info| info.version),
+            },
+            Err(e) => DockerStatus {
                 available: true,
                 running: false,
                 error: Some(format!("Docker not responding: {}", e)),
-            }),
+                endpoint,
+                version: None,
+            },
         },
-        Err(e) => Ok(DockerStatus {
+        Err(e) => DockerStatus {
             available: false,
             running: false,
             error: Some(format!("Docker not available: {}", e)),
-        }),
+            endpoint,
+            version: None,
+        },
+    }
+}
+
+/// Check if Docker is available and running
+#[tauri::command]
+pub async fn check_docker_status(docker_state: State<'_, DockerState>) -> Result<DockerStatus, AppError> {
+    let status = status_with(docker_state.manager(), connection::current().endpoint()).await;
+    if !status.running {
+        docker_state.reset();
     }
+    Ok(status)
+}
+
+/// Try connection settings before saving them
+#[tauri::command]
+pub async fn test_docker_connection(connection: DockerConnection) -> Result<DockerStatus, AppError> {
+    connection.validate().map_err(AppError::InvalidInput)?;
+    Ok(status_of(&connection).await)
+}
+
+/// Send the Docker host a Wake-on-LAN packet and wait until Docker on it answers
+#[tauri::command]
+pub async fn wake_docker_host(app: AppHandle) -> Result<DockerStatus, AppError> {
+    wake::wake_host(&app).await?;
+    let connection = connection::current();
+    Ok(status_of(&connection).await)
+}
+
+/// Container runtime sockets on this machine (Docker, Colima, OrbStack, Podman...),
+/// to pick one in settings when the automatic choice is the wrong one
+#[tauri::command]
+pub async fn detect_docker_sockets() -> Result<Vec<DetectedSocket>, AppError> {
+    Ok(socket::detect())
 }
 
 /// Get Docker system information
 #[tauri::command]
-pub async fn get_docker_info() -> Result<DockerInfo, String> {
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    docker.get_info().await.map_err(|e| e.to_string())
+pub async fn get_docker_info(docker_state: State<'_, DockerState>) -> Result<DockerInfo, AppError> {
+    let docker = docker_state.manager()?;
+    docker.get_info().await.map_err(AppError::from)
+}
+
+/// Pull newer versions of the game images servers use and list the stale containers
+#[tauri::command]
+pub async fn refresh_game_images(app: AppHandle) -> Result<ImageRefresh, AppError> {
+    Ok(images::refresh(&app).await?)
+}
+
+/// Servers whose container was created from an older pull of its image
+#[tauri::command]
+pub async fn get_stale_images() -> Result<Vec<StaleImage>, AppError> {
+    Ok(images::stale())
+}
+
+/// The images install scripts run in and the servers that still need each one
+#[tauri::command]
+pub async fn get_install_images(app: AppHandle) -> Result<Vec<InstallImage>, AppError> {
+    Ok(images::install_images(&app).await?)
+}
+
+/// Remove the pulled install images no server needs anymore
+#[tauri::command]
+pub async fn clean_install_images(app: AppHandle) -> Result<ImageCleanup, AppError> {
+    crate::security::ensure_unlocked().await?;
+    Ok(images::clean_install_images(&app).await?)
+}
+
+/// What the startup check found had changed while the app was closed, once it ran
+#[tauri::command]
+pub async fn get_startup_reconciliation() -> Result<Option<Reconciliation>, AppError> {
+    Ok(reconcile::last().map(|mut result| {
+        result.servers.retain(|s| can_access(Permission::View, Some(&s.server_id)));
+        result
+    }))
+}
+
+/// Recreate a server's container from the current pull of its image
+#[tauri::command(rename_all = "camelCase")]
+pub async fn recreate_server_container(server_id: String, app: AppHandle) -> Result<Server, AppError> {
+    images::recreate(&app, &server_id).await
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImagePlatform {
+    /// The platform the Docker host runs, None when Docker couldn't tell
+    pub host: Option<String>,
+    /// Platforms the image is published for
+    pub platforms: Vec<String>,
+    /// Whether one of them is the host's - false means it runs under emulation
+    pub native: bool,
+}
+
+/// Whether an image has a build for the Docker host's platform
+#[tauri::command]
+pub async fn check_image_platform(image: String, docker_state: State<'_, DockerState>) -> Result<ImagePlatform, AppError> {
+    let docker = docker_state.manager()?;
+    let host = docker.host_platform().await;
+    let platforms = docker.image_platforms(&image).await?;
+    let native = match &host {
+        Some(host) => platforms.iter().any(|p| p == host || p.starts_with(&format!("{}/", host))),
+        None => true,
+    };
+    Ok(ImagePlatform { host, platforms, native })
 }
@@ -1,8 +1,14 @@
 // File manager commands for browsing, editing, and managing server files
 
+use crate::commands::games::GamesState;
+use crate::error::AppError;
+use crate::minecraft::worlds;
 use serde::{Deserialize, Serialize};
+use serverwave_core::games::GameConfig;
+use serverwave_core::server::load_server_config;
 use std::fs;
 use std::path::{Path, PathBuf};
+use tauri::State;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -21,26 +27,47 @@ pub struct DirectoryContents {
     pub entries: Vec<FileEntry>,
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RootKind {
+    Data,
+    Config,
+    Save,
+    Logs,
+}
+
+/// A place in a server's files worth jumping to
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerRoot {
+    pub kind: RootKind,
+    pub label: String,
+    pub path: String,
+    pub is_dir: bool,
+}
+
+/// Folders servers commonly write their logs to, relative to the data folder
+const LOG_FOLDERS: &[&str] = &["logs"];
+
 /// List contents of a directory
 #[tauri::command]
-pub async fn list_directory(path: String) -> Result<DirectoryContents, String> {
+pub async fn list_directory(path: String) -> Result<DirectoryContents, AppError> {
     let dir_path = PathBuf::from(&path);
     
     if !dir_path.exists() {
-        return Err(format!("Directory does not exist: {}", path));
+        return Err(format!("Directory does not exist: {}", path).into());
     }
     
     if !dir_path.is_dir() {
-        return Err(format!("Path is not a directory: {}", path));
+        return Err(format!("Path is not a directory: {}", path).into());
     }
     
     let mut entries = Vec::new();
     
-    let read_dir = fs::read_dir(&dir_path).map_err(|e| e.to_string())?;
+    let read_dir = fs::read_dir(&dir_path)?;
     
     for entry in read_dir {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let metadata = entry.metadata().map_err(|e| e.to_string())?;
+        let entry = entry?;
+        let metadata = entry.metadata()?;
         let file_name = entry.file_name().to_string_lossy().to_string();
         
         // Skip hidden files (starting with .)
@@ -90,97 +117,98 @@ pub async fn list_directory(path: String) -> Result<DirectoryContents, String> {
 
 /// Read file contents as text
 #[tauri::command]
-pub async fn read_file_text(path: String) -> Result<String, String> {
+pub async fn read_file_text(path: String) -> Result<String, AppError> {
     let file_path = PathBuf::from(&path);
     
     if !file_path.exists() {
-        return Err(format!("File does not exist: {}", path));
+        return Err(format!("File does not exist: {}", path).into());
     }
     
     if !file_path.is_file() {
-        return Err(format!("Path is not a file: {}", path));
+        return Err(format!("Path is not a file: {}", path).into());
     }
     
     // Check file size (limit to 5MB for text editing)
-    let metadata = fs::metadata(&file_path).map_err(|e| e.to_string())?;
+    let metadata = fs::metadata(&file_path)?;
     if metadata.len() > 5 * 1024 * 1024 {
-        return Err("File is too large to edit (max 5MB)".to_string());
+        return Err(AppError::InvalidInput("File is too large to edit (max 5MB)".into()));
     }
     
-    fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {}", e))
+    fs::read_to_string(&file_path).map_err(|e| AppError::Other(format!("Failed to read file: {}", e)))
 }
 
 /// Write text content to a file
 #[tauri::command]
-pub async fn write_file_text(path: String, content: String) -> Result<(), String> {
+pub async fn write_file_text(path: String, content: String) -> Result<(), AppError> {
     let file_path = PathBuf::from(&path);
     
     // Ensure parent directory exists
     if let Some(parent) = file_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        fs::create_dir_all(parent)?;
     }
     
-    fs::write(&file_path, content).map_err(|e| format!("Failed to write file: {}", e))
+    serverwave_core::atomic::write_atomic(&file_path, content).map_err(|e| AppError::Other(format!("Failed to write file: {}", e)))
 }
 
 /// Create a new file
 #[tauri::command]
-pub async fn create_file(path: String, content: Option<String>) -> Result<(), String> {
+pub async fn create_file(path: String, content: Option<String>) -> Result<(), AppError> {
     let file_path = PathBuf::from(&path);
     
     if file_path.exists() {
-        return Err(format!("File already exists: {}", path));
+        return Err(format!("File already exists: {}", path).into());
     }
     
     // Ensure parent directory exists
     if let Some(parent) = file_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        fs::create_dir_all(parent)?;
     }
     
     fs::write(&file_path, content.unwrap_or_default())
-        .map_err(|e| format!("Failed to create file: {}", e))
+        .map_err(|e| AppError::Other(format!("Failed to create file: {}", e)))
 }
 
 /// Create a new directory
 #[tauri::command]
-pub async fn create_directory(path: String) -> Result<(), String> {
+pub async fn create_directory(path: String) -> Result<(), AppError> {
     let dir_path = PathBuf::from(&path);
     
     if dir_path.exists() {
-        return Err(format!("Directory already exists: {}", path));
+        return Err(format!("Directory already exists: {}", path).into());
     }
     
-    fs::create_dir_all(&dir_path).map_err(|e| format!("Failed to create directory: {}", e))
+    fs::create_dir_all(&dir_path).map_err(|e| AppError::Other(format!("Failed to create directory: {}", e)))
 }
 
 /// Delete a file or directory
 #[tauri::command]
-pub async fn delete_path(path: String) -> Result<(), String> {
+pub async fn delete_path(path: String) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
     let target_path = PathBuf::from(&path);
     
     if !target_path.exists() {
-        return Err(format!("Path does not exist: {}", path));
+        return Err(format!("Path does not exist: {}", path).into());
     }
     
     if target_path.is_dir() {
-        fs::remove_dir_all(&target_path).map_err(|e| format!("Failed to delete directory: {}", e))
+        fs::remove_dir_all(&target_path).map_err(|e| AppError::Other(format!("Failed to delete directory: {}", e)))
     } else {
-        fs::remove_file(&target_path).map_err(|e| format!("Failed to delete file: {}", e))
+        fs::remove_file(&target_path).map_err(|e| AppError::Other(format!("Failed to delete file: {}", e)))
     }
 }
 
 /// Rename a file or directory
 #[tauri::command]
-pub async fn rename_path(old_path: String, new_name: String) -> Result<String, String> {
+pub async fn rename_path(old_path: String, new_name: String) -> Result<String, AppError> {
     let old = PathBuf::from(&old_path);
     
     if !old.exists() {
-        return Err(format!("Path does not exist: {}", old_path));
+        return Err(format!("Path does not exist: {}", old_path).into());
     }
     
     // Validate new name (no path separators allowed)
     if new_name.contains('/') || new_name.contains('\\') {
-        return Err("Invalid name: cannot contain path separators".to_string());
+        return Err(AppError::InvalidInput("Invalid name: cannot contain path separators".into()));
     }
     
     let new = old.parent()
@@ -188,26 +216,26 @@ pub async fn rename_path(old_path: String, new_name: String) -> Result<String, S
         .join(&new_name);
     
     if new.exists() {
-        return Err(format!("A file or folder with that name already exists: {}", new_name));
+        return Err(AppError::InvalidInput(format!("A file or folder with that name already exists: {}", new_name)));
     }
     
-    fs::rename(&old, &new).map_err(|e| format!("Failed to rename: {}", e))?;
+    fs::rename(&old, &new).map_err(|e| AppError::Other(format!("Failed to rename: {}", e)))?;
     
     Ok(new.to_string_lossy().to_string())
 }
 
 /// Move a file or directory to a new location
 #[tauri::command]
-pub async fn move_path(source: String, destination_dir: String) -> Result<String, String> {
+pub async fn move_path(source: String, destination_dir: String) -> Result<String, AppError> {
     let src = PathBuf::from(&source);
     let dest_dir = PathBuf::from(&destination_dir);
     
     if !src.exists() {
-        return Err(format!("Source does not exist: {}", source));
+        return Err(format!("Source does not exist: {}", source).into());
     }
     
     if !dest_dir.is_dir() {
-        return Err(format!("Destination is not a directory: {}", destination_dir));
+        return Err(format!("Destination is not a directory: {}", destination_dir).into());
     }
     
     let file_name = src.file_name()
@@ -216,26 +244,26 @@ pub async fn move_path(source: String, destination_dir: String) -> Result<String
     let dest = dest_dir.join(file_name);
     
     if dest.exists() {
-        return Err(format!("Destination already exists: {}", dest.display()));
+        return Err(format!("Destination already exists: {}", dest.display()).into());
     }
     
-    fs::rename(&src, &dest).map_err(|e| format!("Failed to move: {}", e))?;
+    fs::rename(&src, &dest).map_err(|e| AppError::Other(format!("Failed to move: {}", e)))?;
     
     Ok(dest.to_string_lossy().to_string())
 }
 
 /// Copy a file or directory
 #[tauri::command]
-pub async fn copy_path(source: String, destination_dir: String) -> Result<String, String> {
+pub async fn copy_path(source: String, destination_dir: String) -> Result<String, AppError> {
     let src = PathBuf::from(&source);
     let dest_dir = PathBuf::from(&destination_dir);
     
     if !src.exists() {
-        return Err(format!("Source does not exist: {}", source));
+        return Err(format!("Source does not exist: {}", source).into());
     }
     
     if !dest_dir.is_dir() {
-        return Err(format!("Destination is not a directory: {}", destination_dir));
+        return Err(format!("Destination is not a directory: {}", destination_dir).into());
     }
     
     let file_name = src.file_name()
@@ -244,30 +272,30 @@ pub async fn copy_path(source: String, destination_dir: String) -> Result<String
     let dest = dest_dir.join(file_name);
     
     if dest.exists() {
-        return Err(format!("Destination already exists: {}", dest.display()));
+        return Err(format!("Destination already exists: {}", dest.display()).into());
     }
     
     if src.is_dir() {
         copy_dir_recursive(&src, &dest)?;
     } else {
-        fs::copy(&src, &dest).map_err(|e| format!("Failed to copy: {}", e))?;
+        fs::copy(&src, &dest).map_err(|e| AppError::Other(format!("Failed to copy: {}", e)))?;
     }
     
     Ok(dest.to_string_lossy().to_string())
 }
 
-fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
-    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(dest)?;
     
-    for entry in fs::read_dir(src).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
         let entry_path = entry.path();
         let dest_path = dest.join(entry.file_name());
         
         if entry_path.is_dir() {
             copy_dir_recursive(&entry_path, &dest_path)?;
         } else {
-            fs::copy(&entry_path, &dest_path).map_err(|e| e.to_string())?;
+            fs::copy(&entry_path, &dest_path)?;
         }
     }
     
@@ -276,14 +304,14 @@ fn copy_dir_recursive(src: &Path, dest: &Path) -> Result<(), String> {
 
 /// Get file info
 #[tauri::command]
-pub async fn get_file_info(path: String) -> Result<FileEntry, String> {
+pub async fn get_file_info(path: String) -> Result<FileEntry, AppError> {
     let file_path = PathBuf::from(&path);
     
     if !file_path.exists() {
-        return Err(format!("Path does not exist: {}", path));
+        return Err(format!("Path does not exist: {}", path).into());
     }
     
-    let metadata = fs::metadata(&file_path).map_err(|e| e.to_string())?;
+    let metadata = fs::metadata(&file_path)?;
     let file_name = file_path.file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_default();
@@ -307,3 +335,79 @@ pub async fn get_file_info(path: String) -> Result<FileEntry, String> {
         extension,
     })
 }
+
+/// The data folder plus whichever of the game's config files, save folders and log folders
+/// exist in it
+fn server_roots(data_path: &Path, game: Option<&GameConfig>) -> Vec<ServerRoot> {
+    let root = |kind, label: &str, path: &Path| ServerRoot {
+        kind,
+        label: label.to_string(),
+        path: path.to_string_lossy().to_string(),
+        is_dir: path.is_dir(),
+    };
+    let mut roots = vec![root(RootKind::Data, "Server files", data_path)];
+
+    let config_files = game.map(|g| g.config_files.as_slice()).unwrap_or_default();
+    for file in config_files {
+        let path = data_path.join(&file.path);
+        if path.is_file() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            roots.push(root(RootKind::Config, &name, &path));
+        }
+    }
+
+    let mut save_folders = game.map(|g| g.save_folders.clone()).unwrap_or_default();
+    // Minecraft Java keeps its world wherever level-name says
+    if save_folders.is_empty() && data_path.join("server.properties").is_file() {
+        save_folders.push(worlds::active_world(data_path));
+    }
+    for folder in &save_folders {
+        let path = data_path.join(folder);
+        if path.is_dir() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            roots.push(root(RootKind::Save, &name, &path));
+        }
+    }
+
+    for folder in LOG_FOLDERS {
+        let path = data_path.join(folder);
+        if path.is_dir() {
+            roots.push(root(RootKind::Logs, "Logs", &path));
+        }
+    }
+    roots
+}
+
+/// Where a server's config files, worlds and logs are, for the file manager to jump to
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_server_roots(server_id: String, games_state: State<'_, GamesState>) -> Result<Vec<ServerRoot>, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game = games_state.manager.lock().await.get_game(&server.game_type);
+    Ok(tokio::task::spawn_blocking(move || server_roots(&server.data_path, game.as_ref())).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serverwave_core::games::{ConfigFile, ConfigFileFormat};
+
+    #[test]
+    fn test_server_roots_skip_missing_locations() {
+        let dir = std::env::temp_dir().join(format!("serverwave-roots-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("saves").join("Worlds")).unwrap();
+        std::fs::write(dir.join("serverconfig.txt"), "").unwrap();
+        let game = GameConfig {
+            config_files: vec![
+                ConfigFile { path: "serverconfig.txt".into(), format: ConfigFileFormat::Properties, variables: Default::default() },
+                ConfigFile { path: "missing.ini".into(), format: ConfigFileFormat::Ini, variables: Default::default() },
+            ],
+            save_folders: vec!["saves/Worlds".into()],
+            ..Default::default()
+        };
+
+        let roots = server_roots(&dir, Some(&game));
+        let kinds: Vec<_> = roots.iter().map(|r| (r.kind.clone(), r.label.as_str())).collect();
+        assert_eq!(kinds, [(RootKind::Data, "Server files"), (RootKind::Config, "serverconfig.txt"), (RootKind::Save, "Worlds")]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,13 @@
 // Game-related commands
 
+use crate::commands::docker::DockerState;
+use crate::error::AppError;
+use crate::game_sync::{self, GameSyncReport, GameSyncStatus};
+use crate::games::lint::{self, ScriptLint};
 use crate::games::{GameConfig, GameType, GamesManager};
+use crate::security::secrets;
 use std::sync::Arc;
-use tauri::State;
+use tauri::{AppHandle, State};
 use tokio::sync::Mutex;
 
 pub struct GamesState {
@@ -19,7 +24,7 @@ impl Default for GamesState {
 
 /// List all available games
 #[tauri::command]
-pub async fn list_available_games(state: State<'_, GamesState>) -> Result<Vec<GameConfig>, String> {
+pub async fn list_available_games(state: State<'_, GamesState>) -> Result<Vec<GameConfig>, AppError> {
     let manager = state.manager.lock().await;
     Ok(manager.get_all_games())
 }
@@ -29,7 +34,7 @@ pub async fn list_available_games(state: State<'_, GamesState>) -> Result<Vec<Ga
 pub async fn get_game_config(
     game_type: String,
     state: State<'_, GamesState>,
-) -> Result<Option<GameConfig>, String> {
+) -> Result<Option<GameConfig>, AppError> {
     let manager = state.manager.lock().await;
     Ok(manager.get_game(&GameType::new(&game_type)))
 }
@@ -38,23 +43,40 @@ pub async fn get_game_config(
 #[tauri::command]
 pub async fn add_custom_game(
     game: GameConfig,
+    app: AppHandle,
     state: State<'_, GamesState>,
-) -> Result<GameConfig, String> {
+) -> Result<GameConfig, AppError> {
     let mut manager = state.manager.lock().await;
     let mut game = game;
     game.is_custom = true;
     manager.add_game(game.clone())?;
+    game_sync::push_on_save(&app, vec![game.clone()], Vec::new());
     Ok(game)
 }
 
+/// Check an install script's syntax in the image it would run in, without running it
+#[tauri::command]
+pub async fn lint_install_script(script: String, image: String, docker_state: State<'_, DockerState>) -> Result<ScriptLint, AppError> {
+    if script.trim().is_empty() || image.trim().is_empty() {
+        return Err(AppError::InvalidInput("Both a script and an image are needed".into()));
+    }
+    let docker = docker_state.manager()?;
+    let (_, output) = docker.run_isolated(image.trim(), &lint::checker(&script)).await?;
+    Ok(lint::parse(&output)?)
+}
+
 /// Update an existing game
 #[tauri::command]
 pub async fn update_game(
     game: GameConfig,
+    app: AppHandle,
     state: State<'_, GamesState>,
-) -> Result<GameConfig, String> {
+) -> Result<GameConfig, AppError> {
     let mut manager = state.manager.lock().await;
     manager.update_game(game.clone())?;
+    if let Some(saved) = manager.get_game(&game.game_type) {
+        game_sync::push_on_save(&app, vec![saved], Vec::new());
+    }
     Ok(game)
 }
 
@@ -62,10 +84,15 @@ pub async fn update_game(
 #[tauri::command(rename_all = "camelCase")]
 pub async fn delete_game(
     game_type: String,
+    app: AppHandle,
     state: State<'_, GamesState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
     let mut manager = state.manager.lock().await;
-    manager.delete_game(&GameType::new(&game_type))
+    let game_type = GameType::new(&game_type);
+    manager.delete_game(&game_type)?;
+    game_sync::push_on_save(&app, Vec::new(), vec![game_type]);
+    Ok(())
 }
 
 /// Export a game definition as JSON
@@ -73,57 +100,88 @@ pub async fn delete_game(
 pub async fn export_game(
     game_type: String,
     state: State<'_, GamesState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let manager = state.manager.lock().await;
-    manager.export_game(&GameType::new(&game_type))
+    Ok(manager.export_game(&GameType::new(&game_type))?)
 }
 
 /// Export all custom games as JSON
 #[tauri::command]
 pub async fn export_all_custom_games(
     state: State<'_, GamesState>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let manager = state.manager.lock().await;
-    manager.export_all_custom_games()
+    Ok(manager.export_all_custom_games()?)
 }
 
 /// Import a game from JSON
 #[tauri::command]
 pub async fn import_game(
     json: String,
+    app: AppHandle,
     state: State<'_, GamesState>,
-) -> Result<GameConfig, String> {
+) -> Result<GameConfig, AppError> {
     let mut manager = state.manager.lock().await;
-    manager.import_game(&json)
+    let game = manager.import_game(&json)?;
+    game_sync::push_on_save(&app, vec![game.clone()], Vec::new());
+    Ok(game)
 }
 
 /// Import multiple games from JSON
 #[tauri::command]
 pub async fn import_games(
     json: String,
+    app: AppHandle,
     state: State<'_, GamesState>,
-) -> Result<Vec<GameConfig>, String> {
+) -> Result<Vec<GameConfig>, AppError> {
     let mut manager = state.manager.lock().await;
-    manager.import_games(&json)
+    let games = manager.import_games(&json)?;
+    game_sync::push_on_save(&app, games.clone(), Vec::new());
+    Ok(games)
 }
 
 /// Reset games to defaults (removes all custom games)
 #[tauri::command]
 pub async fn reset_games_to_defaults(
     state: State<'_, GamesState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
     let mut manager = state.manager.lock().await;
-    manager.reset_to_defaults()
+    Ok(manager.reset_to_defaults()?)
+}
+
+/// The shared game library's repository and how the last pull or push went
+#[tauri::command]
+pub async fn get_game_sync_status() -> Result<GameSyncStatus, AppError> {
+    Ok(game_sync::status().await)
+}
+
+/// Bring in the games from the shared library - its version wins over local edits
+#[tauri::command]
+pub async fn pull_game_library(app: AppHandle) -> Result<GameSyncReport, AppError> {
+    Ok(game_sync::pull(&app).await)
+}
+
+/// Send every custom game to the shared library
+#[tauri::command]
+pub async fn push_game_library(app: AppHandle) -> Result<GameSyncReport, AppError> {
+    Ok(game_sync::push_all(&app).await)
+}
+
+/// Store an access token for an https library repository, or remove it with None
+#[tauri::command]
+pub async fn set_game_sync_token(token: Option<String>) -> Result<(), AppError> {
+    match token.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(token) => secrets::set_secret(game_sync::TOKEN_KEY, token).await?,
+        None => secrets::delete_secret(game_sync::TOKEN_KEY).await,
+    }
+    Ok(())
 }
 
 /// Get the path to the games config folder (creates it if it doesn't exist)
 #[tauri::command]
 pub fn get_games_config_path() -> String {
-    let path = directories::UserDirs::new()
-        .map(|d| d.home_dir().to_path_buf())
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("ServerWaveAnywhere")
-        .join("games");
+    let path = serverwave_core::paths::app_root().join("games");
     
     // Create directory if it doesn't exist
     if !path.exists() {
@@ -0,0 +1,140 @@
+// Server health commands - aggregate container, network, log and resource signals
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::{load_server_config, ServerStatus};
+use crate::docker::ContainerStats;
+use crate::error::AppError;
+use crate::games::PortProtocol;
+use crate::network::reachability::probe_local_tcp;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::State;
+
+/// Memory usage (percent of the container limit) above which a server is degraded
+const MEMORY_PRESSURE_PERCENT: f64 = 95.0;
+/// Minutes without log output after which an unreachable server is considered hung
+const STALE_LOG_MINUTES: i64 = 10;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthVerdict {
+    Healthy,
+    Degraded,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerHealth {
+    pub server_id: String,
+    pub verdict: HealthVerdict,
+    pub reasons: Vec<String>,
+    pub status: ServerStatus,
+    /// None when the game port can't be probed (UDP-only games)
+    pub query_reachable: Option<bool>,
+    pub last_log_at: Option<DateTime<Utc>>,
+    pub stats: Option<ContainerStats>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Get a single health verdict for a server (healthy/degraded/down + reasons)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_server_health(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerHealth, AppError> {
+    let server = load_server_config(&server_id)?;
+
+    let mut health = ServerHealth {
+        server_id: server_id.clone(),
+        verdict: HealthVerdict::Healthy,
+        reasons: Vec::new(),
+        status: server.status.clone(),
+        query_reachable: None,
+        last_log_at: None,
+        stats: None,
+        checked_at: Utc::now(),
+    };
+
+    if server.status == ServerStatus::Installing {
+        health.verdict = HealthVerdict::Degraded;
+        health.reasons.push("Server is installing".to_string());
+        return Ok(health);
+    }
+
+    let docker = match docker_state.manager() {
+        Ok(d) => d,
+        Err(e) => {
+            health.verdict = HealthVerdict::Down;
+            health.reasons.push(format!("Docker not available: {}", e));
+            return Ok(health);
+        }
+    };
+
+    let container_id = match &server.container_id {
+        Some(id) => id.clone(),
+        None => {
+            health.verdict = HealthVerdict::Down;
+            health.reasons.push("Server has no container".to_string());
+            return Ok(health);
+        }
+    };
+
+    health.status = docker
+        .get_container_status(&container_id)
+        .await?;
+    health.last_log_at = docker.get_last_log_time(&container_id).await.unwrap_or(None);
+
+    if health.status != ServerStatus::Running {
+        health.verdict = HealthVerdict::Down;
+        let status = format!("{:?}", health.status).to_lowercase();
+        health.reasons.push(format!("Container is {}", status));
+        return Ok(health);
+    }
+
+    // Probe the game port if it speaks TCP
+    let protocol = {
+        let games_manager = games_state.manager.lock().await;
+        games_manager
+            .get_game(&server.game_type)
+            .and_then(|g| g.ports.first().map(|p| p.protocol.clone()))
+    };
+    if protocol != Some(PortProtocol::Udp) {
+        let reachable = probe_local_tcp(server.port).await;
+        health.query_reachable = Some(reachable);
+        if !reachable {
+            let stale = health
+                .last_log_at
+                .map(|t| Utc::now() - t > chrono::Duration::minutes(STALE_LOG_MINUTES))
+                .unwrap_or(true);
+            if stale {
+                health.verdict = HealthVerdict::Down;
+                health.reasons.push(format!(
+                    "Port {} not accepting connections and no log output for {}+ minutes",
+                    server.port, STALE_LOG_MINUTES
+                ));
+            } else {
+                health.verdict = HealthVerdict::Degraded;
+                health
+                    .reasons
+                    .push(format!("Port {} not accepting connections yet", server.port));
+            }
+        }
+    }
+
+    if let Ok(stats) = docker.get_container_stats(&container_id).await {
+        if stats.memory_percent >= MEMORY_PRESSURE_PERCENT {
+            if health.verdict == HealthVerdict::Healthy {
+                health.verdict = HealthVerdict::Degraded;
+            }
+            health.reasons.push(format!(
+                "Memory usage at {:.0}% of the {:.0} MB limit",
+                stats.memory_percent, stats.memory_limit_mb
+            ));
+        }
+        health.stats = Some(stats);
+    }
+
+    Ok(health)
+}
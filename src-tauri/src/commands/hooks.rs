@@ -0,0 +1,75 @@
+// Hook commands - manage the automation hooks that run on server events
+
+use crate::error::AppError;
+use crate::events::{AlertLevel, ServerEvent};
+use crate::hooks::{self, Hook, HookAction};
+use crate::security::ensure_unlocked;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+#[tauri::command]
+pub async fn list_hooks() -> Result<Vec<Hook>, AppError> {
+    Ok(hooks::load_hooks())
+}
+
+/// Add a hook, or replace the one with the same id
+#[tauri::command]
+pub async fn save_hook(mut hook: Hook) -> Result<Hook, AppError> {
+    // Script hooks run programs on this machine
+    ensure_unlocked().await?;
+    validate(&hook)?;
+
+    let mut all = hooks::load_hooks();
+    if hook.id.is_empty() {
+        hook.id = Uuid::new_v4().to_string()[..8].to_string();
+    }
+    match all.iter_mut().find(|h| h.id == hook.id) {
+        Some(existing) => *existing = hook.clone(),
+        None => all.push(hook.clone()),
+    }
+    hooks::save_hooks(&all)?;
+    Ok(hook)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_hook(hook_id: String) -> Result<(), AppError> {
+    ensure_unlocked().await?;
+    let mut all = hooks::load_hooks();
+    let before = all.len();
+    all.retain(|h| h.id != hook_id);
+    if all.len() == before {
+        return Err(format!("Hook '{}' not found", hook_id).into());
+    }
+    hooks::save_hooks(&all)?;
+    Ok(())
+}
+
+/// Run a hook's action now with a test alert, whatever its trigger
+#[tauri::command(rename_all = "camelCase")]
+pub async fn test_hook(hook_id: String, server_id: String, app: AppHandle) -> Result<(), AppError> {
+    let hook = hooks::load_hooks()
+        .into_iter()
+        .find(|h| h.id == hook_id)
+        .ok_or_else(|| format!("Hook '{}' not found", hook_id))?;
+    let event = ServerEvent::alert(&server_id, AlertLevel::Warning, "Test from Serverwave Anywhere");
+    hooks::run(&app, &hook, hook.trigger, &event).await?;
+    Ok(())
+}
+
+fn validate(hook: &Hook) -> Result<(), AppError> {
+    if hook.name.trim().is_empty() {
+        return Err(AppError::InvalidInput("Hook name is required".into()));
+    }
+    match &hook.action {
+        HookAction::Command { command } if command.trim().is_empty() => {
+            Err(AppError::InvalidInput("Command is required".into()))
+        }
+        HookAction::Http { url } if !url.starts_with("http://") && !url.starts_with("https://") => {
+            Err(AppError::InvalidInput("Hook URL must start with http:// or https://".into()))
+        }
+        HookAction::Script { path, .. } if !std::path::Path::new(path).is_absolute() => {
+            Err(AppError::InvalidInput("Script path must be absolute".into()))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,607 @@
+// Import commands - recreate servers from a Pterodactyl panel, take over a server folder
+// that was run by hand on this machine, or move a server between machines as one archive
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::network::build_port_allocator;
+use crate::commands::server::{create_server, CreateServerRequest, Server, ServerResponse, ServerState};
+use crate::disk_guard::{self, Activity};
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::games::{GameConfig, GameType};
+use crate::hooks;
+use crate::import::local::{self, LocalScan};
+use crate::import::portable::{self, ServerManifest, ARCHIVE_FORMAT};
+use crate::import::pterodactyl::{self, PanelClient, PanelServer};
+use crate::import::sftp::{self, SftpLogin};
+use crate::wipes;
+use chrono::{Local, Utc};
+use serverwave_core::paths;
+use serverwave_core::server::{calculate_dir_size, lifecycle, load_server_config, ServerStatus};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PterodactylImportRequest {
+    pub panel_url: String,
+    pub api_key: String,
+    /// Short server id shown in the panel URL
+    pub identifier: String,
+    /// Defaults to `<panel username>.<identifier>`
+    #[serde(default)]
+    pub sftp_username: Option<String>,
+    /// The panel account password
+    pub sftp_password: String,
+    /// Name for the local copy, defaults to the panel's name
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub open_firewall: bool,
+}
+
+/// Servers the API key can see, for picking what to import
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_pterodactyl_servers(
+    panel_url: String,
+    api_key: String,
+) -> Result<Vec<PanelServer>, AppError> {
+    let client = PanelClient::new(&panel_url, &api_key)?;
+    Ok(client.list_servers().await?)
+}
+
+/// Rebuild a panel server's egg as a game, create the server and copy its files over SFTP
+#[tauri::command]
+pub async fn import_pterodactyl_server(
+    request: PterodactylImportRequest,
+    app: AppHandle,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    let client = PanelClient::new(&request.panel_url, &request.api_key)?;
+    let panel_server = client.get_server(&request.identifier).await?;
+    let startup = client.startup(&request.identifier).await?;
+    tracing::info!("Importing Pterodactyl server {} ({})", panel_server.name, panel_server.identifier);
+
+    let username = match request.sftp_username.filter(|u| !u.trim().is_empty()) {
+        Some(username) => username,
+        None => format!("{}.{}", client.account_username().await?, panel_server.identifier),
+    };
+    let login = SftpLogin {
+        host: panel_server.sftp_host.clone(),
+        port: panel_server.sftp_port,
+        username,
+        password: request.sftp_password,
+    };
+
+    // Log in before creating anything so a wrong password leaves nothing behind
+    let sftp = tokio::task::spawn_blocking(move || sftp::connect(&login)).await??;
+
+    let game_type = {
+        let mut manager = games_state.manager.lock().await;
+        let mut game_type = pterodactyl::game_type_for(&panel_server);
+        // Servers of the same egg share a game unless they run a different image
+        if let Some(existing) = manager.get_game(&game_type) {
+            if existing.docker_image != panel_server.docker_image {
+                game_type.0 = format!("{}-{}", game_type.0, panel_server.identifier);
+            }
+        }
+        if manager.get_game(&game_type).is_none() {
+            let game = pterodactyl::egg_to_game(game_type.clone(), &panel_server, &startup);
+            manager.add_game(game)?;
+        }
+        game_type
+    };
+
+    let response = create_server(
+        CreateServerRequest {
+            name: request.name.unwrap_or_else(|| panel_server.name.clone()),
+            game_type,
+            port: None,
+            config: Some(pterodactyl::server_config(&startup)),
+            memory_mb: panel_server.memory_mb,
+            open_firewall: request.open_firewall,
+        },
+        state.clone(),
+        games_state,
+        app.state(),
+    )
+    .await?;
+    let server = response.server.ok_or("Server was not created")?;
+
+    let server_id = server.id.clone();
+    let data_path = server.data_path.clone();
+    let log_app = app.clone();
+    let downloaded = tokio::task::spawn_blocking(move || {
+        let mut last_report = Instant::now();
+        sftp::download_all(&sftp, &data_path, &mut |progress| {
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                events::emit(&log_app, ServerEvent::log(
+                    &server_id,
+                    format!(
+                        "[Import] {} files, {:.1} MB copied ({})",
+                        progress.files,
+                        progress.bytes as f64 / 1_048_576.0,
+                        progress.current
+                    ),
+                ));
+            }
+        })
+    })
+    .await?;
+
+    let progress = match downloaded {
+        Ok(progress) => progress,
+        Err(e) => {
+            tracing::error!("Import of {} failed, removing the partial copy: {}", server.id, e);
+            events::emit(&app, ServerEvent::alert(
+                &server.id,
+                AlertLevel::Error,
+                format!("Import failed: {}", e),
+            ));
+            if let Ok(docker) = app.state::<DockerState>().manager() {
+                let _ = lifecycle::remove(&docker, &server, true).await;
+                state.configs.remove(&server.id).ok();
+            }
+            return Err(e.into());
+        }
+    };
+
+    events::emit(&app, ServerEvent::log(
+        &server.id,
+        format!(
+            "[Import] Done - {} files, {:.1} MB",
+            progress.files,
+            progress.bytes as f64 / 1_048_576.0
+        ),
+    ));
+
+    // The files came from a working server, so there's nothing to install
+    let server = state.configs.update(&server.id, |s| s.installed = true)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalImportMode {
+    /// Copy the folder into the data directory and leave the original alone
+    Copy,
+    /// Run the server from the folder where it is. Deleting the server keeps the folder.
+    InPlace,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalImportRequest {
+    pub path: String,
+    pub name: String,
+    pub game_type: GameType,
+    pub mode: LocalImportMode,
+    /// Defaults to the port in the folder's config, when it's free
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub memory_mb: Option<u32>,
+    /// Variables to set, over the ones read from the folder
+    #[serde(default)]
+    pub config: HashMap<String, String>,
+    #[serde(default)]
+    pub open_firewall: bool,
+}
+
+/// A server folder outside the app, checked before importing it
+fn local_folder(path: &str) -> Result<PathBuf, AppError> {
+    let dir = PathBuf::from(path.trim());
+    if !dir.is_absolute() || !dir.is_dir() {
+        return Err(AppError::InvalidInput(format!("{} isn't a folder", dir.display())));
+    }
+    let dir = std::fs::canonicalize(&dir)?;
+    let servers_dir = std::fs::canonicalize(paths::servers_dir()).unwrap_or_else(|_| paths::servers_dir());
+    if dir.starts_with(&servers_dir) || servers_dir.starts_with(&dir) {
+        return Err(AppError::InvalidInput("That folder is part of the app's own servers folder".into()));
+    }
+    Ok(dir)
+}
+
+/// Work out which game a server folder is for and what its settings are, to fill in the
+/// import wizard. `game_type` reads the folder as that game instead of guessing.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn scan_local_server(
+    path: String,
+    game_type: Option<GameType>,
+    games_state: State<'_, GamesState>,
+) -> Result<LocalScan, AppError> {
+    let dir = local_folder(&path)?;
+    let game = {
+        let manager = games_state.manager.lock().await;
+        game_type
+            .or_else(|| local::detect_game_type(&dir))
+            .and_then(|game_type| manager.get_game(&game_type))
+    };
+    let game_type = game.as_ref().map(|g| g.game_type.clone());
+
+    let scan_dir = dir.clone();
+    let (config, size_bytes) = tokio::task::spawn_blocking(move || {
+        let config = game.map(|g| local::read_config(&scan_dir, &g)).unwrap_or_default();
+        (config, calculate_dir_size(&scan_dir).unwrap_or(0))
+    })
+    .await?;
+    Ok(LocalScan {
+        path: dir.to_string_lossy().to_string(),
+        game_type,
+        config,
+        port: local::read_port(&dir),
+        size_bytes,
+    })
+}
+
+/// Create a managed server from a folder that was run by hand, with the settings read from
+/// it - copied into the data directory, or run where it is. Nothing is installed, since
+/// the files already work.
+#[tauri::command]
+pub async fn import_local_server(
+    request: LocalImportRequest,
+    app: AppHandle,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    let source = local_folder(&request.path)?;
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Give the server a name".into()));
+    }
+    let (game, port) = {
+        let manager = games_state.manager.lock().await;
+        let game = manager
+            .get_game(&request.game_type)
+            .ok_or_else(|| AppError::GameNotFound(request.game_type.to_string()))?;
+        // The folder's own port when it's free, so nothing has to change
+        let allocator = build_port_allocator(&manager);
+        let port = request
+            .port
+            .or_else(|| local::read_port(&source).filter(|port| allocator.owner(*port).is_none()));
+        (game, port)
+    };
+
+    let mut config = {
+        let (source, game) = (source.clone(), game.clone());
+        tokio::task::spawn_blocking(move || local::read_config(&source, &game)).await?
+    };
+    config.extend(request.config);
+    tracing::info!("Importing {} as a {} server ({:?})", source.display(), game.game_type, request.mode);
+
+    let response = create_server(
+        CreateServerRequest {
+            name,
+            game_type: request.game_type.clone(),
+            port,
+            config: Some(config),
+            memory_mb: request.memory_mb,
+            open_firewall: request.open_firewall,
+        },
+        state.clone(),
+        games_state,
+        app.state(),
+    )
+    .await?;
+    let mut server = response.server.ok_or("Server was not created")?;
+
+    let result = adopt_folder(&app, &mut server, &source, request.mode, &game).await;
+    if let Err(e) = result {
+        tracing::error!("Import of {} failed, removing the server: {}", source.display(), e);
+        if let Ok(docker) = app.state::<DockerState>().manager() {
+            // Only the copy goes - an in-place folder is outside the data directory and kept
+            let _ = lifecycle::remove(&docker, &server, true).await;
+            state.configs.remove(&server.id).ok();
+        }
+        return Err(e);
+    }
+
+    // The files came from a working server, so there's nothing to install
+    let server = state.configs.update(&server.id, |s| {
+        s.installed = true;
+        s.data_path = server.data_path.clone();
+        s.container_id = server.container_id.clone();
+        s.config_outdated = server.config_outdated;
+    })?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Put the folder's files behind a newly created server and make them start its way
+async fn adopt_folder(
+    app: &AppHandle,
+    server: &mut Server,
+    source: &Path,
+    mode: LocalImportMode,
+    game: &GameConfig,
+) -> Result<(), AppError> {
+    match mode {
+        LocalImportMode::Copy => {
+            events::emit(app, ServerEvent::log(&server.id, format!("[Import] Copying {}...", source.display())));
+            let (from, to) = (source.to_path_buf(), server.data_path.clone());
+            tokio::task::spawn_blocking(move || paths::copy_dir_all(&from, &to)).await??;
+        }
+        LocalImportMode::InPlace => {
+            // The container was made for the new, empty folder - point it at this one
+            std::fs::remove_dir_all(&server.data_path).ok();
+            server.data_path = source.to_path_buf();
+            let docker = app.state::<DockerState>().manager()?;
+            lifecycle::recreate_container(&docker, server, game, &HashMap::new()).await?;
+        }
+    }
+
+    let (dir, game_type, port) = (server.data_path.clone(), server.game_type.clone(), server.port);
+    let changes = tokio::task::spawn_blocking(move || local::prepare(&dir, &game_type, port)).await??;
+    for change in changes {
+        events::emit(app, ServerEvent::log(&server.id, format!("[Import] {}", change)));
+    }
+    events::emit(app, ServerEvent::log(
+        &server.id,
+        format!("[Import] Done - {} now runs {}", server.name, server.data_path.display()),
+    ));
+    Ok(())
+}
+
+/// Bundle a stopped server's settings and files into one zip another machine can import, and
+/// return its path - `dest`, or a new file in the app folder. Its game comes along when it's
+/// a custom one, and so do its wipe schedule and its own hooks.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn export_server(
+    server_id: String,
+    dest: Option<String>,
+    app: AppHandle,
+    games_state: State<'_, GamesState>,
+) -> Result<String, AppError> {
+    crate::security::ensure_unlocked().await?;
+    let server = load_server_config(&server_id)?;
+    let docker = app.state::<DockerState>().manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if !matches!(status, ServerStatus::Stopped | ServerStatus::Error | ServerStatus::CrashLooping) {
+        return Err(AppError::InvalidInput("Stop the server before exporting it".into()));
+    }
+    disk_guard::ensure_room(Activity::Backup).await?;
+
+    let game = games_state.manager.lock().await.get_game(&server.game_type);
+    let manifest = ServerManifest {
+        format: ARCHIVE_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: Utc::now(),
+        server: without_credentials(&server, game.as_ref()),
+        game: game.filter(|g| g.is_custom),
+        wipe_schedule: wipes::load_schedules().into_iter().find(|s| s.server_id == server_id),
+        hooks: hooks::load_hooks()
+            .into_iter()
+            .filter(|h| h.server_id.as_deref() == Some(server_id.as_str()))
+            .collect(),
+    };
+
+    let path = match dest.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(dest) => PathBuf::from(dest),
+        None => paths::app_root().join("exports").join(format!(
+            "{}-{}.zip",
+            file_name_for(&server.name),
+            Local::now().format("%Y%m%d-%H%M%S")
+        )),
+    };
+    if path.is_dir() {
+        return Err(AppError::InvalidInput(format!("{} is a folder - give a file name", path.display())));
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut left_out = Vec::new();
+    if server.tunnel.is_some() {
+        left_out.push("its tunnel");
+    }
+    if !server.backup_targets.is_empty() {
+        left_out.push("its backup targets");
+    }
+    if !left_out.is_empty() {
+        events::emit(&app, ServerEvent::log(&server_id, format!("[Export] Left out, as they hold credentials: {}", left_out.join(", "))));
+    }
+    events::emit(&app, ServerEvent::log(&server_id, format!("[Export] Writing {}...", path.display())));
+    let (data_path, archive) = (server.data_path.clone(), path.clone());
+    tokio::task::spawn_blocking(move || portable::write(&manifest, &data_path, &archive))
+        .await?
+        .map_err(|e| format!("Export failed: {}", e))?;
+    events::emit(&app, ServerEvent::log(&server_id, format!("[Export] Done - {}", path.display())));
+
+    tracing::info!("Exported server {} to {}", server_id, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// The server as an archive carries it, without the settings that hold credentials - its
+/// tunnel, its backup targets and its RCON password, which the import generates anew
+fn without_credentials(server: &Server, game: Option<&GameConfig>) -> Server {
+    let mut server = server.clone();
+    server.tunnel = None;
+    server.backup_targets.clear();
+    if let Some(rcon) = game.and_then(|g| g.rcon.as_ref()) {
+        server.config.remove(&rcon.password_var);
+    }
+    server
+}
+
+/// A server name as part of a file name
+fn file_name_for(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let name = name.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+    match name.is_empty() {
+        true => "server".to_string(),
+        false => name,
+    }
+}
+
+/// Create a server from an archive exported by `export_server`, with its files and settings.
+/// Ports another server here already has are swapped for free ones, and the container is
+/// made for this machine. `name` renames it.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn import_server(
+    path: String,
+    name: Option<String>,
+    app: AppHandle,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    crate::security::ensure_unlocked().await?;
+    let archive = PathBuf::from(path.trim());
+    let manifest = {
+        let archive = archive.clone();
+        tokio::task::spawn_blocking(move || portable::read_manifest(&archive)).await??
+    };
+    if manifest.format > ARCHIVE_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "The archive was exported by a newer version ({}) - update the app first",
+            manifest.app_version
+        )));
+    }
+    disk_guard::ensure_room(Activity::Install).await?;
+    let exported = &manifest.server;
+
+    let (game, port, config) = {
+        let mut manager = games_state.manager.lock().await;
+        if manager.get_game(&exported.game_type).is_none() {
+            let game = manifest.game.clone().ok_or_else(|| AppError::GameNotFound(exported.game_type.to_string()))?;
+            manager.add_game(game)?;
+        }
+        let game = manager
+            .get_game(&exported.game_type)
+            .ok_or_else(|| AppError::GameNotFound(exported.game_type.to_string()))?;
+        let allocator = build_port_allocator(&manager);
+        let free = |port: u16| allocator.owner(port).is_none();
+        let mut config = exported.config.clone();
+        // Extra ports another server has are left out, so new ones are found
+        for var in game.ports.iter().skip(1).filter_map(|p| p.env_var.as_ref()).filter(|v| !v.is_empty()) {
+            if config.get(var).and_then(|v| v.parse().ok()).is_some_and(|port| !free(port)) {
+                config.remove(var);
+            }
+        }
+        (game, Some(exported.port).filter(|port| free(*port)), config)
+    };
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()).unwrap_or_else(|| exported.name.clone());
+    tracing::info!("Importing {} from {}", name, archive.display());
+
+    let response = create_server(
+        CreateServerRequest {
+            name,
+            game_type: exported.game_type.clone(),
+            port,
+            config: Some(config),
+            memory_mb: Some(exported.memory_mb),
+            open_firewall: false,
+        },
+        state.clone(),
+        games_state,
+        app.state(),
+    )
+    .await?;
+    let mut server = response.server.ok_or("Server was not created")?;
+
+    if let Err(e) = adopt_archive(&app, &mut server, &archive, &manifest, &game).await {
+        tracing::error!("Import of {} failed, removing the server: {}", archive.display(), e);
+        if let Ok(docker) = app.state::<DockerState>().manager() {
+            let _ = lifecycle::remove(&docker, &server, true).await;
+            state.configs.remove(&server.id).ok();
+        }
+        return Err(e);
+    }
+    let server = state.configs.update(&server.id, |s| *s = server.clone())?;
+
+    if let Some(mut schedule) = manifest.wipe_schedule {
+        schedule.server_id = server.id.clone();
+        schedule.next_wipe = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+        let mut schedules = wipes::load_schedules();
+        schedules.push(schedule);
+        wipes::save_schedules(&schedules)?;
+    }
+    if !manifest.hooks.is_empty() {
+        let mut all_hooks = hooks::load_hooks();
+        all_hooks.extend(manifest.hooks.into_iter().map(|mut hook| {
+            hook.id = uuid::Uuid::new_v4().to_string()[..8].to_string();
+            hook.server_id = Some(server.id.clone());
+            hook
+        }));
+        hooks::save_hooks(&all_hooks)?;
+    }
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Put an archive's files behind a newly created server, take over the settings that aren't
+/// tied to the other machine and rebuild the container with them
+async fn adopt_archive(
+    app: &AppHandle,
+    server: &mut Server,
+    archive: &Path,
+    manifest: &ServerManifest,
+    game: &GameConfig,
+) -> Result<(), AppError> {
+    events::emit(app, ServerEvent::log(&server.id, format!("[Import] Unpacking {}...", archive.display())));
+    let (from, to) = (archive.to_path_buf(), server.data_path.clone());
+    tokio::task::spawn_blocking(move || portable::unpack_data(&from, &to)).await??;
+
+    let exported = &manifest.server;
+    server.installed = exported.installed;
+    server.versions = exported.versions.clone();
+    server.pinned_version = exported.pinned_version.clone();
+    server.docker_image = exported.docker_image.clone();
+    server.env = exported.env.clone();
+    server.maintenance = exported.maintenance.clone();
+    server.auto_restart = exported.auto_restart;
+    server.backup_schedule = exported.backup_schedule.clone().map(|mut schedule| {
+        schedule.next_run = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+        schedule
+    });
+    server.start_schedule = exported.start_schedule.clone().map(|mut schedule| {
+        schedule.next_run = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+        schedule
+    });
+    let docker = app.state::<DockerState>().manager()?;
+    lifecycle::recreate_container(&docker, server, game, &HashMap::new()).await?;
+
+    let mut left_behind = Vec::new();
+    if exported.database.is_some() {
+        left_behind.push("its database");
+    }
+    if exported.tunnel.is_some() {
+        left_behind.push("its tunnel");
+    }
+    if !exported.backup_targets.is_empty() {
+        left_behind.push("its backup targets");
+    }
+    if !left_behind.is_empty() {
+        events::emit(app, ServerEvent::log(
+            &server.id,
+            format!("[Import] Not moved, set up again here if needed: {}", left_behind.join(", ")),
+        ));
+    }
+    events::emit(app, ServerEvent::log(
+        &server.id,
+        format!("[Import] Done - {} from {}", server.name, manifest.exported_at.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
+    ));
+    Ok(())
+}
@@ -0,0 +1,10 @@
+// Link commands - hand launch links over to the frontend once it's ready
+
+use crate::deep_link;
+use crate::error::AppError;
+
+/// Server id from the `serverwave://server/<id>` link the app was started with, once
+#[tauri::command]
+pub async fn take_pending_link() -> Result<Option<String>, AppError> {
+    Ok(deep_link::take_pending_server())
+}
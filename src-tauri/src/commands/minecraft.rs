@@ -0,0 +1,285 @@
+// Minecraft commands - worlds, player lists and packs in a server's data folder
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::console_command;
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use crate::minecraft::packs::{self, Datapack, ResourcePack};
+use crate::minecraft::players::{self, PlayerEntry, PlayerList};
+use crate::minecraft::properties;
+use crate::minecraft::worlds::{self, World};
+use serverwave_core::server::{lifecycle, load_server_config, Server, ServerStatus};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::State;
+
+/// How long a running server gets to write its list after a console command
+const LIST_SAVE_DELAY: Duration = Duration::from_millis(1500);
+
+fn is_bedrock(server: &Server) -> bool {
+    server.game_type.0.to_lowercase().contains("bedrock")
+}
+
+fn minecraft_server(server_id: &str) -> Result<Server, AppError> {
+    let server = load_server_config(server_id)?;
+    if !server.game_type.0.to_lowercase().contains("minecraft") || is_bedrock(&server) {
+        return Err(AppError::InvalidInput(format!("'{}' isn't a Minecraft Java server", server.name)));
+    }
+    Ok(server)
+}
+
+/// Worlds are only touched while the server is down - a running server would write over them
+async fn stopped_minecraft_server(docker: &DockerManager, server_id: &str, action: &str) -> Result<Server, AppError> {
+    let server = minecraft_server(server_id)?;
+    let status = lifecycle::current_status(docker, &server).await?;
+    if matches!(status, ServerStatus::Running | ServerStatus::Starting | ServerStatus::Installing) {
+        return Err(AppError::InvalidInput(format!("Stop the server before {}", action)));
+    }
+    Ok(server)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_worlds(server_id: String) -> Result<Vec<World>, AppError> {
+    let server = minecraft_server(&server_id)?;
+    Ok(tokio::task::spawn_blocking(move || worlds::list(&server.data_path)).await?)
+}
+
+/// Make `name` the world the server loads on its next start
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_active_world(
+    server_id: String,
+    name: String,
+    docker_state: State<'_, DockerState>,
+) -> Result<(), AppError> {
+    let server = stopped_minecraft_server(&docker_state.manager()?, &server_id, "switching worlds").await?;
+    worlds::set_active(&server.data_path, &name)?;
+    tracing::info!("Server {} now loads world '{}'", server_id, name);
+    Ok(())
+}
+
+/// Bring in a world folder or zip from this computer under a new name
+#[tauri::command(rename_all = "camelCase")]
+pub async fn import_world(
+    server_id: String,
+    path: String,
+    name: String,
+    docker_state: State<'_, DockerState>,
+) -> Result<World, AppError> {
+    let server = stopped_minecraft_server(&docker_state.manager()?, &server_id, "importing a world").await?;
+    let source = PathBuf::from(path);
+    let name = name.trim().to_string();
+    let world = tokio::task::spawn_blocking(move || worlds::import(&server.data_path, &source, &name)).await??;
+    tracing::info!("Imported world '{}' into server {}", world.name, server_id);
+    Ok(world)
+}
+
+/// Delete a world, zipping it into world-archives/ first when `archive` is set. Returns the
+/// archive's path.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_world(
+    server_id: String,
+    name: String,
+    archive: bool,
+    docker_state: State<'_, DockerState>,
+) -> Result<Option<String>, AppError> {
+    crate::security::ensure_unlocked().await?;
+    let server = stopped_minecraft_server(&docker_state.manager()?, &server_id, "deleting a world").await?;
+    let archived = tokio::task::spawn_blocking(move || worlds::delete(&server.data_path, &name, archive)).await??;
+    Ok(archived.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// A Minecraft server whose edition the list belongs to
+fn server_for_list(server_id: &str, list: PlayerList) -> Result<Server, AppError> {
+    let server = load_server_config(server_id)?;
+    if !server.game_type.0.to_lowercase().contains("minecraft") {
+        return Err(AppError::InvalidInput(format!("'{}' isn't a Minecraft server", server.name)));
+    }
+    if list.bedrock() != is_bedrock(&server) {
+        return Err(AppError::InvalidInput(format!("'{}' doesn't use {}", server.name, list.file())));
+    }
+    Ok(server)
+}
+
+/// Whether list changes go through the console. A running server keeps its lists in memory
+/// and would write over edits made to the files underneath it.
+async fn use_console(docker: &DockerManager, server: &Server) -> Result<bool, AppError> {
+    match lifecycle::current_status(docker, server).await? {
+        ServerStatus::Running => Ok(true),
+        ServerStatus::Starting | ServerStatus::Installing => Err(AppError::InvalidInput(
+            "Wait for the server to finish starting".into(),
+        )),
+        _ => Ok(false),
+    }
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_players(server_id: String, list: PlayerList) -> Result<Vec<PlayerEntry>, AppError> {
+    let server = server_for_list(&server_id, list)?;
+    Ok(players::read(&server.data_path, list)?)
+}
+
+/// Whitelist, op or ban a player by name. Returns the list as it is afterwards.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn add_player(
+    server_id: String,
+    list: PlayerList,
+    name: String,
+    reason: Option<String>,
+    docker_state: State<'_, DockerState>,
+) -> Result<Vec<PlayerEntry>, AppError> {
+    let server = server_for_list(&server_id, list)?;
+    let name = name.trim().to_string();
+    players::validate_name(&name, list.bedrock())?;
+
+    let docker = docker_state.manager()?;
+    if use_console(&docker, &server).await? {
+        console_command(&docker, &server_id, &list.add_command(&name, reason.as_deref())).await?;
+        tokio::time::sleep(LIST_SAVE_DELAY).await;
+    } else {
+        let profile = if list.bedrock() {
+            None
+        } else if properties::get(&server.data_path, "online-mode").as_deref() == Some("false") {
+            // Offline-mode UUIDs are derived by the server - let it add the player
+            return Err(AppError::InvalidInput(
+                "This server runs in offline mode - start it and add the player then".into(),
+            ));
+        } else {
+            Some(players::lookup_profile(&name).await?)
+        };
+        players::add(&server.data_path, list, &name, profile.as_ref(), reason.as_deref())?;
+    }
+    Ok(players::read(&server.data_path, list)?)
+}
+
+/// Take a player off a list - unwhitelist, deop or pardon. Returns the list as it is afterwards.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_player(
+    server_id: String,
+    list: PlayerList,
+    name: String,
+    docker_state: State<'_, DockerState>,
+) -> Result<Vec<PlayerEntry>, AppError> {
+    let server = server_for_list(&server_id, list)?;
+    let docker = docker_state.manager()?;
+    if use_console(&docker, &server).await? {
+        console_command(&docker, &server_id, &list.remove_command(&name)).await?;
+        tokio::time::sleep(LIST_SAVE_DELAY).await;
+    } else if !players::remove(&server.data_path, list, &name)? {
+        return Err(AppError::InvalidInput(format!("{} isn't in {}", name, list.file())));
+    }
+    Ok(players::read(&server.data_path, list)?)
+}
+
+/// A local path, or a URL that's downloaded into a scratch folder first. The folder comes
+/// back too so it can be cleaned up.
+async fn fetch_source(source: &str) -> Result<(PathBuf, Option<PathBuf>), AppError> {
+    let source = source.trim();
+    if !(source.starts_with("https://") || source.starts_with("http://")) {
+        return Ok((PathBuf::from(source), None));
+    }
+    let scratch = std::env::temp_dir().join(format!("serverwave-pack-{}", uuid::Uuid::new_v4()));
+    let file_name = source
+        .split(['?', '#'])
+        .next()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|name| name.ends_with(".zip"))
+        .unwrap_or("pack.zip");
+    let path = crate::content::download_file(source, file_name, None, &scratch).await?;
+    Ok((path, Some(scratch)))
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_datapacks(server_id: String) -> Result<Vec<Datapack>, AppError> {
+    let server = minecraft_server(&server_id)?;
+    Ok(packs::list_datapacks(&server.data_path))
+}
+
+/// Add a datapack zip or folder - a path on this computer or a download URL - to the active
+/// world. It loads on the next start or /reload.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn install_datapack(server_id: String, source: String) -> Result<Datapack, AppError> {
+    let server = minecraft_server(&server_id)?;
+    let (path, scratch) = fetch_source(&source).await?;
+    let data_path = server.data_path.clone();
+    let result = tokio::task::spawn_blocking(move || packs::install_datapack(&data_path, &path)).await?;
+    if let Some(scratch) = scratch {
+        let _ = std::fs::remove_dir_all(scratch);
+    }
+    Ok(result?)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_datapack(server_id: String, name: String) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
+    let server = minecraft_server(&server_id)?;
+    Ok(packs::remove_datapack(&server.data_path, &name)?)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_resource_pack(server_id: String) -> Result<Option<ResourcePack>, AppError> {
+    let server = minecraft_server(&server_id)?;
+    Ok(packs::resource_pack(&server.data_path))
+}
+
+async fn hash_pack(path: &Path) -> Result<String, AppError> {
+    let path = path.to_path_buf();
+    Ok(tokio::task::spawn_blocking(move || packs::sha1_file(&path)).await??)
+}
+
+/// Point players at a resource pack someone else hosts. The pack is downloaded once to work
+/// out the SHA1 clients check it against.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_resource_pack(server_id: String, url: String, required: bool) -> Result<ResourcePack, AppError> {
+    let server = minecraft_server(&server_id)?;
+    let url = url.trim().to_string();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(AppError::InvalidInput("The resource pack needs an http(s) URL players can reach".into()));
+    }
+    let (path, scratch) = fetch_source(&url).await?;
+    let sha1 = hash_pack(&path).await;
+    if let Some(scratch) = scratch {
+        let _ = std::fs::remove_dir_all(scratch);
+    }
+    let pack = ResourcePack { url, sha1: sha1?, required };
+    packs::set_resource_pack(&server.data_path, &pack)?;
+    Ok(pack)
+}
+
+/// Serve a resource pack from this computer through the API at
+/// <public_url>/packs/<server id>/<file>. The API has to be reachable by players.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn host_resource_pack(
+    server_id: String,
+    path: String,
+    public_url: String,
+    required: bool,
+) -> Result<ResourcePack, AppError> {
+    let server = minecraft_server(&server_id)?;
+    let source = PathBuf::from(path.trim());
+    let file_name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .filter(|n| n.ends_with(".zip"))
+        .ok_or_else(|| AppError::InvalidInput("Pick the resource pack's .zip".into()))?;
+    let base = public_url.trim().trim_end_matches('/');
+    if !(base.starts_with("https://") || base.starts_with("http://")) {
+        return Err(AppError::InvalidInput("The public URL needs to start with http(s)://".into()));
+    }
+
+    let hosted = server.data_path.join(packs::HOSTED_DIR);
+    std::fs::create_dir_all(&hosted)?;
+    std::fs::copy(&source, hosted.join(&file_name))?;
+    let pack = ResourcePack {
+        url: format!("{}/packs/{}/{}", base, server.id, file_name),
+        sha1: hash_pack(&hosted.join(&file_name)).await?,
+        required,
+    };
+    packs::set_resource_pack(&server.data_path, &pack)?;
+    Ok(pack)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn clear_resource_pack(server_id: String) -> Result<(), AppError> {
+    let server = minecraft_server(&server_id)?;
+    Ok(packs::clear_resource_pack(&server.data_path)?)
+}
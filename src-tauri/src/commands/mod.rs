@@ -4,3 +4,28 @@ pub mod server;
 pub mod docker;
 pub mod games;
 pub mod files;
+pub mod health;
+pub mod network;
+pub mod proxy;
+pub mod settings;
+pub mod state;
+pub mod nodes;
+pub mod security;
+pub mod users;
+pub mod import;
+pub mod diagnostics;
+pub mod links;
+pub mod hooks;
+pub mod content;
+pub mod minecraft;
+pub mod steam;
+pub mod wipes;
+pub mod palworld;
+pub mod database;
+pub mod actions;
+pub mod onboarding;
+pub mod players;
+pub mod remote;
+pub mod dashboard;
+pub mod backups;
+pub mod schedules;
@@ -0,0 +1,557 @@
+// Network commands - router port forwarding
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::{
+    load_all_server_configs, load_server_config, Server, ServerResponse, ServerState,
+    ServerStatus,
+};
+use crate::commands::settings::load_app_settings;
+use crate::docker::{published_ports, DockerManager, PublishedPort};
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::games::{GameConfig, GamesManager, PortConfig, PortProtocol};
+use crate::network::{firewall, get_public_ip, reachability, tunnel, upnp, MappingProtocol, PortAllocation, PortAllocator, PortMapping, PortMappingResult, TunnelConfig, TunnelStatus};
+use serde::Serialize;
+use serverwave_core::server::lifecycle;
+use tauri::{AppHandle, State};
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReachabilityVerdict {
+    /// Reachable from the internet - friends can join
+    Joinable,
+    /// Running locally but blocked by NAT or a firewall
+    NotReachable,
+    ServerDown,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortReachability {
+    pub port: u16,
+    pub protocol: PortProtocol,
+    pub public_ip: Option<String>,
+    pub server_up: bool,
+    pub reachable: Option<bool>,
+    pub verdict: ReachabilityVerdict,
+    pub guidance: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionPort {
+    pub port: u16,
+    pub protocol: PortProtocol,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionInfo {
+    pub public_ip: Option<String>,
+    pub local_ip: Option<String>,
+    pub port: u16,
+    /// Ready-to-share `ip:port` for players outside the LAN
+    pub address: Option<String>,
+    pub lan_address: Option<String>,
+    pub tunnel_address: Option<String>,
+    pub extra_ports: Vec<ConnectionPort>,
+    pub instructions: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkPort {
+    #[serde(flatten)]
+    pub port: PublishedPort,
+    /// "Game" for the game port, otherwise what the game calls it, like "Query" or "RCON"
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerNetwork {
+    /// Whether the ports were read from the container. Without one they're the ports it
+    /// will publish when created.
+    pub from_container: bool,
+    pub ports: Vec<NetworkPort>,
+    /// Docker networks the container is on, like a database's
+    pub networks: Vec<String>,
+}
+
+/// Enable or disable automatic router port forwarding (UPnP/NAT-PMP) for a server
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_server_upnp(
+    server_id: String,
+    enabled: bool,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    let server = state.configs.update(&server_id, |s| s.upnp_enabled = enabled)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Create inbound Windows Firewall rules for all of a server's ports
+#[tauri::command(rename_all = "camelCase")]
+pub async fn open_firewall_ports(
+    server_id: String,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    let server = state.configs.get(&server_id)?;
+    let mappings = port_mappings_for(&server, &games_state).await;
+    firewall::add_rules(&server.id, &mappings).await?;
+
+    let server = state.configs.update(&server_id, |s| s.firewall_rules = true)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Remove the Windows Firewall rules created for a server
+#[tauri::command(rename_all = "camelCase")]
+pub async fn close_firewall_ports(
+    server_id: String,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    let server = state.configs.get(&server_id)?;
+    firewall::remove_rules(&server.id).await?;
+
+    let server = state.configs.update(&server_id, |s| s.firewall_rules = false)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Check whether the router accepts port mappings by forwarding the server's port briefly
+#[tauri::command(rename_all = "camelCase")]
+pub async fn test_port_mapping(server_id: String) -> Result<PortMappingResult, AppError> {
+    let server = load_server_config(&server_id)?;
+    Ok(upnp::test_mapping(server.port).await)
+}
+
+/// Get a shareable address and join instructions for a server
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_connection_info(
+    server_id: String,
+    refresh: Option<bool>,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ConnectionInfo, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game = {
+        let games_manager = games_state.manager.lock().await;
+        games_manager.get_game(&server.game_type)
+    };
+
+    let (public_ip, error) = match get_public_ip(refresh.unwrap_or(false)).await {
+        Ok(ip) => (Some(ip.to_string()), None),
+        Err(e) => (None, Some(e)),
+    };
+    let local_ip = upnp::local_ip().await.map(|ip| ip.to_string());
+
+    let tunnel_address = match &server.tunnel {
+        Some(config) if config.enabled => match docker_state.manager() {
+            Ok(docker) => tunnel::tunnel_status(&docker, &server.id, config).await.public_address,
+            Err(_) => None,
+        },
+        _ => None,
+    };
+
+    let address = public_ip.as_ref().map(|ip| format_address(ip, server.port));
+    let lan_address = local_ip.as_ref().map(|ip| format_address(ip, server.port));
+
+    let extra_ports = lifecycle::extra_ports(&server, game.as_ref())
+        .into_iter()
+        .filter(|p| !p.local_only)
+        .map(|p| ConnectionPort {
+            port: p.container_port,
+            protocol: p.protocol,
+            description: p.description,
+        })
+        .collect();
+
+    // Prefer the tunnel address when one is active, it works without port forwarding
+    let share_address = tunnel_address.clone().or_else(|| address.clone());
+    let instructions = game
+        .as_ref()
+        .and_then(|g| g.join_instructions.clone())
+        .zip(share_address)
+        .map(|(template, share)| {
+            let (ip, port) = share
+                .rsplit_once(':')
+                .map(|(ip, port)| (ip.to_string(), port.to_string()))
+                .unwrap_or((share.clone(), server.port.to_string()));
+            template
+                .replace("{{ADDRESS}}", &share)
+                .replace("{{IP}}", &ip)
+                .replace("{{PORT}}", &port)
+        });
+
+    Ok(ConnectionInfo {
+        public_ip,
+        local_ip,
+        port: server.port,
+        address,
+        lan_address,
+        tunnel_address,
+        extra_ports,
+        instructions,
+        error,
+    })
+}
+
+/// Every port a server's container publishes, with its protocol and host address
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_server_network(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerNetwork, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game = games_state.manager.lock().await.get_game(&server.game_type);
+    let extra_ports = lifecycle::extra_ports(&server, game.as_ref());
+    let docker = docker_state.manager()?;
+
+    let (from_container, ports, networks) = match &server.container_id {
+        Some(container_id) => match docker.container_ports(container_id).await {
+            Ok(ports) => (true, ports, docker.container_networks(container_id).await),
+            Err(e) => {
+                tracing::debug!("Couldn't inspect {}: {}", container_id, e);
+                (false, published_ports(server.port, &extra_ports), Vec::new())
+            }
+        },
+        None => (false, published_ports(server.port, &extra_ports), Vec::new()),
+    };
+
+    let ports = ports
+        .into_iter()
+        .map(|port| {
+            let description = if port.container_port == server.port {
+                Some("Game".to_string())
+            } else {
+                extra_ports.iter().find(|p| p.container_port == port.container_port).and_then(|p| p.description.clone())
+            };
+            NetworkPort { port, description }
+        })
+        .collect();
+    Ok(ServerNetwork { from_container, ports, networks })
+}
+
+fn format_address(ip: &str, port: u16) -> String {
+    if ip.contains(':') {
+        format!("[{}]:{}", ip, port)
+    } else {
+        format!("{}:{}", ip, port)
+    }
+}
+
+/// Check whether the game port can be reached from the internet, not just whether the
+/// server is running, and explain what to fix when it can't
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_port_reachable(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<PortReachability, AppError> {
+    let server = load_server_config(&server_id)?;
+    let protocol = {
+        let games_manager = games_state.manager.lock().await;
+        games_manager
+            .get_game(&server.game_type)
+            .and_then(|g| g.ports.first().map(|p| p.protocol.clone()))
+            .unwrap_or(PortProtocol::Both)
+    };
+
+    let mut result = PortReachability {
+        port: server.port,
+        protocol: protocol.clone(),
+        public_ip: None,
+        server_up: false,
+        reachable: None,
+        verdict: ReachabilityVerdict::Unknown,
+        guidance: Vec::new(),
+    };
+
+    let running = match (&server.container_id, docker_state.manager()) {
+        (Some(container_id), Ok(docker)) => {
+            docker.get_container_status(container_id).await.ok() == Some(ServerStatus::Running)
+        }
+        _ => false,
+    };
+    result.server_up = running
+        && (protocol == PortProtocol::Udp || reachability::probe_local_tcp(server.port).await);
+
+    if !result.server_up {
+        result.verdict = ReachabilityVerdict::ServerDown;
+        result.guidance.push(if running {
+            format!("The server is running but not listening on port {} yet. Wait for it to finish starting.", server.port)
+        } else {
+            "Start the server first - the port can only be checked while it's running.".to_string()
+        });
+        return Ok(result);
+    }
+
+    let public_ip = match get_public_ip(false).await {
+        Ok(ip) => ip,
+        Err(e) => {
+            result.guidance.push(e);
+            return Ok(result);
+        }
+    };
+    result.public_ip = Some(public_ip.to_string());
+
+    if protocol == PortProtocol::Udp {
+        result.guidance.push(format!(
+            "Port {} uses UDP, which can't be checked from outside. Make sure UDP {} is forwarded on your router.",
+            server.port, server.port
+        ));
+        return Ok(result);
+    }
+
+    match reachability::check_external_tcp(public_ip, server.port).await {
+        Ok(true) => {
+            result.reachable = Some(true);
+            result.verdict = ReachabilityVerdict::Joinable;
+        }
+        Ok(false) => {
+            result.reachable = Some(false);
+            result.verdict = ReachabilityVerdict::NotReachable;
+            let lan_ip = upnp::local_ip().await;
+            let lan = lan_ip.map(|ip| ip.to_string()).unwrap_or_else(|| "this PC".to_string());
+            result.guidance.push(format!(
+                "Forward port {} ({}) on your router to {}, or enable automatic port forwarding (UPnP) for this server.",
+                server.port,
+                protocol_label(&protocol),
+                lan
+            ));
+            result.guidance.push(format!(
+                "Allow inbound connections on port {} in your firewall (Windows Defender Firewall, ufw, etc.).",
+                server.port
+            ));
+            if lan_ip.map(|ip| !reachability::is_private_ip(&ip)).unwrap_or(false) {
+                result.guidance.push("This PC has a public address, so a firewall is the most likely cause.".to_string());
+            } else {
+                result.guidance.push(
+                    "If your ISP uses carrier-grade NAT, forwarding won't work - use a tunnel (ngrok or Cloudflare) instead."
+                        .to_string(),
+                );
+            }
+        }
+        Err(e) => result.guidance.push(e),
+    }
+
+    Ok(result)
+}
+
+fn protocol_label(protocol: &PortProtocol) -> &'static str {
+    match protocol {
+        PortProtocol::Tcp => "TCP",
+        PortProtocol::Udp => "UDP",
+        PortProtocol::Both => "TCP and UDP",
+    }
+}
+
+/// Configure (or remove, with `None`) the ngrok/Cloudflare tunnel for a server
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_server_tunnel(
+    server_id: String,
+    tunnel: Option<TunnelConfig>,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
+    let server = state.configs.get(&server_id)?;
+    let mut tunnel = tunnel;
+
+    if let Some(config) = &tunnel {
+        // The stored token is kept when none is given
+        let has_token = server.tunnel.as_ref().is_some_and(|t| t.has_token);
+        if config.token.trim().is_empty() && !has_token {
+            return Err(AppError::InvalidInput("Tunnel token cannot be empty".into()));
+        }
+        let games_manager = games_state.manager.lock().await;
+        let protocol = games_manager
+            .get_game(&server.game_type)
+            .and_then(|g| g.ports.first().map(|p| p.protocol.clone()));
+        if protocol == Some(PortProtocol::Udp) {
+            return Err(AppError::InvalidInput("Tunnels only support TCP-based games".into()));
+        }
+    } else {
+        // Tear down a running sidecar when the tunnel is removed
+        if let Ok(docker) = docker_state.manager() {
+            tunnel::stop_tunnel(&docker, &server_id).await;
+        }
+        tunnel::delete_token(&server_id).await;
+    }
+    if let Some(config) = &mut tunnel {
+        let token = std::mem::take(&mut config.token);
+        if !token.trim().is_empty() {
+            tunnel::save_token(&server_id, token.trim()).await?;
+        }
+        config.has_token = true;
+    }
+
+    let server = state.configs.update(&server_id, |s| s.tunnel = tunnel)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Get the state and public address of a server's tunnel
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_tunnel_status(server_id: String, docker_state: State<'_, DockerState>) -> Result<Option<TunnelStatus>, AppError> {
+    let server = load_server_config(&server_id)?;
+    let config = match &server.tunnel {
+        Some(config) => config,
+        None => return Ok(None),
+    };
+
+    let docker = docker_state.manager()?;
+    Ok(Some(tunnel::tunnel_status(&docker, &server_id, config).await))
+}
+
+/// Start the tunnel sidecar for a server if one is configured and enabled
+pub(crate) async fn start_server_tunnel(docker: &DockerManager, app: &AppHandle, server: &Server) {
+    let (config, container_id) = match (&server.tunnel, &server.container_id) {
+        (Some(config), Some(container_id)) if config.enabled => (config, container_id),
+        _ => return,
+    };
+
+    let Some(token) = tunnel::load_token(&server.id).await else {
+        events::emit(app, ServerEvent::alert(&server.id, AlertLevel::Warning, "The tunnel has no token - set it again in the network tab"));
+        return;
+    };
+    let event = match tunnel::start_tunnel(docker, &server.id, container_id, server.port, config, &token).await {
+        Ok(_) => ServerEvent::log(&server.id, format!("[Serverwave] Started {:?} tunnel", config.provider)),
+        Err(e) => ServerEvent::alert(
+            &server.id,
+            AlertLevel::Warning,
+            format!("Failed to start tunnel: {}", e),
+        ),
+    };
+    events::emit(app, event);
+}
+
+/// All host ports a server publishes, split per protocol
+pub(crate) fn server_port_mappings(server: &Server, game: Option<&GameConfig>) -> Vec<PortMapping> {
+    let mut mappings = vec![
+        PortMapping { port: server.port, protocol: MappingProtocol::Tcp },
+        PortMapping { port: server.port, protocol: MappingProtocol::Udp },
+    ];
+    mappings.extend(server_port_mappings_extra(&lifecycle::extra_ports(server, game)));
+    mappings
+}
+
+/// Extra ports split per protocol
+pub(crate) fn server_port_mappings_extra(extra_ports: &[PortConfig]) -> Vec<PortMapping> {
+    let mut mappings = Vec::new();
+    for extra in extra_ports.iter().filter(|p| !p.local_only) {
+        let protocols = match extra.protocol {
+            PortProtocol::Tcp => vec![MappingProtocol::Tcp],
+            PortProtocol::Udp => vec![MappingProtocol::Udp],
+            PortProtocol::Both => vec![MappingProtocol::Tcp, MappingProtocol::Udp],
+        };
+        for protocol in protocols {
+            mappings.push(PortMapping { port: extra.container_port, protocol });
+        }
+    }
+    mappings
+}
+
+/// Collect the port mappings for a server using its game definition
+pub(crate) async fn port_mappings_for(
+    server: &Server,
+    games_state: &State<'_, GamesState>,
+) -> Vec<PortMapping> {
+    let games_manager = games_state.manager.lock().await;
+    let game = games_manager.get_game(&server.game_type);
+    server_port_mappings(server, game.as_ref())
+}
+
+/// Request router mappings in the background and report the outcome to the console
+pub(crate) fn forward_ports(app: &AppHandle, server: &Server, mappings: Vec<PortMapping>) {
+    let app = app.clone();
+    let server_id = server.id.clone();
+    let description = format!("Serverwave {}", server.name);
+
+    tokio::spawn(async move {
+        let result = upnp::map_ports(&mappings, &description).await;
+
+        if !result.mapped.is_empty() {
+            let ports: Vec<String> = result
+                .mapped
+                .iter()
+                .map(|m| format!("{}/{:?}", m.port, m.protocol).to_lowercase())
+                .collect();
+            events::emit(&app, ServerEvent::log(
+                &server_id,
+                format!(
+                    "[Serverwave] Router forwarded ports {} (external IP: {})",
+                    ports.join(", "),
+                    result.external_ip.as_deref().unwrap_or("unknown")
+                ),
+            ));
+        }
+        for error in &result.errors {
+            events::emit(&app, ServerEvent::alert(
+                &server_id,
+                AlertLevel::Warning,
+                format!("Port forwarding failed: {}", error),
+            ));
+        }
+    });
+}
+
+/// Remove router mappings in the background
+pub(crate) fn unforward_ports(mappings: Vec<PortMapping>) {
+    tokio::spawn(async move {
+        upnp::unmap_ports(&mappings).await;
+    });
+}
+
+/// Build the port allocator from every saved server, using the configured port range
+pub(crate) fn build_port_allocator(games_manager: &GamesManager) -> PortAllocator {
+    let settings = load_app_settings();
+    let mut allocator = PortAllocator::new(settings.port_range_start, settings.port_range_end);
+
+    let mut servers = load_all_server_configs();
+    servers.sort_by_key(|s| s.created_at);
+    for server in servers {
+        let game = games_manager.get_game(&server.game_type);
+        let ports = std::iter::once((server.port, Some("Game port".to_string()))).chain(
+            lifecycle::extra_ports(&server, game.as_ref())
+                .into_iter()
+                .map(|p| (p.container_port, p.description)),
+        );
+        for (port, description) in ports {
+            // Older servers may already overlap; keep the first owner and move on
+            if let Err(e) = allocator.assign(&server.id, &server.name, port, description) {
+                tracing::warn!("{}", e);
+            }
+        }
+    }
+    allocator
+}
+
+/// Every host port currently assigned to a server
+#[tauri::command]
+pub async fn get_port_allocations(
+    games_state: State<'_, GamesState>,
+) -> Result<Vec<PortAllocation>, AppError> {
+    let games_manager = games_state.manager.lock().await;
+    let mut allocations = build_port_allocator(&games_manager).allocations().to_vec();
+    allocations.sort_by_key(|a| a.port);
+    Ok(allocations)
+}
@@ -0,0 +1,164 @@
+// Node commands - manage remote agents and their servers
+
+use crate::error::AppError;
+use crate::nodes::{client, registry, relay, Node};
+use crate::security::ensure_unlocked;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Running event relays, one per node
+pub struct NodesState {
+    pub relays: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+impl Default for NodesState {
+    fn default() -> Self {
+        Self {
+            relays: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// A node as shown to the frontend - never includes the token
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeInfo {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// The pinned certificate, to compare with the one the agent shows
+    pub fingerprint: Option<String>,
+    pub online: bool,
+    pub version: Option<String>,
+    pub error: Option<String>,
+}
+
+async fn node_info(node: &Node) -> NodeInfo {
+    let (version, error) = match client::health(node).await {
+        Ok(version) => (Some(version), None),
+        Err(e) => (None, Some(e)),
+    };
+    NodeInfo {
+        id: node.id.clone(),
+        name: node.name.clone(),
+        url: node.url.clone(),
+        fingerprint: node.fingerprint.clone(),
+        online: version.is_some(),
+        version,
+        error,
+    }
+}
+
+/// Connect the event relays for all saved nodes (called once at startup)
+pub async fn start_relays(app: &AppHandle, state: &NodesState) {
+    let mut relays = state.relays.lock().await;
+    for node in registry::load_nodes().await {
+        let id = node.id.clone();
+        relays.insert(id, relay::spawn_relay(app.clone(), node));
+    }
+}
+
+/// List saved agents with their reachability
+#[tauri::command]
+pub async fn list_nodes() -> Result<Vec<NodeInfo>, AppError> {
+    let nodes = registry::load_nodes().await;
+    let infos = futures_util::future::join_all(nodes.iter().map(node_info)).await;
+    Ok(infos)
+}
+
+/// Add an agent after checking that it answers and accepts the token
+#[tauri::command]
+pub async fn add_node(
+    name: String,
+    url: String,
+    token: String,
+    app: AppHandle,
+    state: State<'_, NodesState>,
+) -> Result<NodeInfo, AppError> {
+    let node = Node {
+        id: Uuid::new_v4().to_string()[..8].to_string(),
+        name,
+        url: url.trim().trim_end_matches('/').to_string(),
+        token: token.trim().to_string(),
+        fingerprint: None,
+        added_at: chrono::Utc::now(),
+    };
+
+    client::health(&node).await?;
+    client::invoke(&node, "check_docker_status", Value::Null).await?;
+    save_node(node, app, state).await
+}
+
+/// Add an agent with the pairing code it shows, pinning the certificate it answered with
+#[tauri::command]
+pub async fn pair_node(
+    name: String,
+    url: String,
+    code: String,
+    app: AppHandle,
+    state: State<'_, NodesState>,
+) -> Result<NodeInfo, AppError> {
+    let url = url.trim().trim_end_matches('/').to_string();
+    if registry::load_nodes().await.iter().any(|n| n.url == url) {
+        return Err(AppError::InvalidInput(format!("An agent at {} is already added", url)));
+    }
+    let host = sysinfo::System::host_name().unwrap_or_else(|| "Serverwave Anywhere".to_string());
+    let pairing = client::pair(&url, &code, &host).await?;
+    let node = Node {
+        id: Uuid::new_v4().to_string()[..8].to_string(),
+        name,
+        url,
+        token: pairing.token,
+        fingerprint: pairing.fingerprint,
+        added_at: chrono::Utc::now(),
+    };
+    save_node(node, app, state).await
+}
+
+async fn save_node(node: Node, app: AppHandle, state: State<'_, NodesState>) -> Result<NodeInfo, AppError> {
+    let mut nodes = registry::load_nodes().await;
+    if nodes.iter().any(|n| n.url == node.url) {
+        return Err(AppError::InvalidInput(format!("An agent at {} is already added", node.url)));
+    }
+    nodes.push(node.clone());
+    registry::save_nodes(&nodes).await?;
+
+    state
+        .relays
+        .lock()
+        .await
+        .insert(node.id.clone(), relay::spawn_relay(app, node.clone()));
+
+    Ok(node_info(&node).await)
+}
+
+/// Forget an agent. Its servers keep running on the remote machine.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_node(node_id: String, state: State<'_, NodesState>) -> Result<(), AppError> {
+    ensure_unlocked().await?;
+    let mut nodes = registry::load_nodes().await;
+    let before = nodes.len();
+    nodes.retain(|n| n.id != node_id);
+    if nodes.len() == before {
+        return Err(format!("Node '{}' not found", node_id).into());
+    }
+    registry::save_nodes(&nodes).await?;
+    registry::delete_node_token(&node_id).await;
+
+    if let Some(handle) = state.relays.lock().await.remove(&node_id) {
+        handle.abort();
+    }
+    Ok(())
+}
+
+/// Run any command on an agent - same name and arguments as the local command
+#[tauri::command(rename_all = "camelCase")]
+pub async fn node_invoke(node_id: String, command: String, args: Option<Value>) -> Result<Value, AppError> {
+    let node = registry::find_node(&node_id).await?;
+    Ok(client::invoke(&node, &command, args.unwrap_or(Value::Null)).await?)
+}
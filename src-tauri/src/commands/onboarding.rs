@@ -0,0 +1,349 @@
+// Onboarding commands - the setup checklist, from real probes of this machine: Docker,
+// virtualization, disk space, write access and whether registries and Steam are reachable
+
+use crate::commands::diagnostics::probe_writable;
+use crate::commands::docker::status_of;
+use crate::commands::games::GamesState;
+use crate::disk_guard::free_space;
+use crate::docker::connection;
+use crate::error::AppError;
+use serde::Serialize;
+use serverwave_core::paths;
+use std::time::Duration;
+use tauri::State;
+
+const GB: u64 = 1024 * 1024 * 1024;
+/// Less free space than this and a couple of servers fill the disk
+const LOW_DISK_GB: u64 = 20;
+/// Less than this isn't enough for one game image and its files
+const MIN_DISK_GB: u64 = 5;
+const REACH_TIMEOUT: Duration = Duration::from_secs(10);
+const DOCKER_HUB: &str = "registry-1.docker.io";
+const STEAM_URL: &str = "https://api.steampowered.com/ISteamWebAPIUtil/GetServerInfo/v1/";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but may cause trouble later
+    Warning,
+    Failed,
+    /// Couldn't be checked because an earlier check failed, or doesn't apply here
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OnboardingCheck {
+    pub id: String,
+    pub label: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about it, when it isn't Ok
+    pub fix: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OnboardingChecks {
+    /// Nothing failed - servers can be created
+    pub ready: bool,
+    pub checks: Vec<OnboardingCheck>,
+}
+
+fn check(
+    id: &str,
+    label: &str,
+    status: CheckStatus,
+    detail: impl Into<String>,
+    fix: Option<&str>,
+) -> OnboardingCheck {
+    OnboardingCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        status,
+        detail: detail.into(),
+        fix: fix.map(str::to_string),
+    }
+}
+
+/// Probe everything the app needs before the first server, in the order it's set up
+#[tauri::command]
+pub async fn run_onboarding_checks(
+    games_state: State<'_, GamesState>,
+) -> Result<OnboardingChecks, AppError> {
+    let mut checks = Vec::new();
+
+    let docker = status_of(&connection::current()).await;
+    let endpoint = docker.endpoint.as_deref().unwrap_or("the default socket");
+    checks.push(if docker.available {
+        check("docker_installed", "Docker installed", CheckStatus::Ok, format!("Found at {}", endpoint), None)
+    } else {
+        check(
+            "docker_installed",
+            "Docker installed",
+            CheckStatus::Failed,
+            docker.error.clone().unwrap_or_default(),
+            Some("Install Docker Desktop (Windows, macOS) or Docker Engine (Linux), or set where Docker is in Settings"),
+        )
+    });
+    checks.push(match (docker.available, docker.running) {
+        (_, true) => check(
+            "docker_running",
+            "Docker running",
+            CheckStatus::Ok,
+            format!(
+                "Docker {}",
+                docker.version.as_deref().unwrap_or("is answering")
+            ),
+            None,
+        ),
+        (true, false) => check(
+            "docker_running",
+            "Docker running",
+            CheckStatus::Failed,
+            docker.error.clone().unwrap_or_default(),
+            Some("Start Docker and wait until it says it's running"),
+        ),
+        (false, false) => check(
+            "docker_running",
+            "Docker running",
+            CheckStatus::Skipped,
+            "Docker isn't installed",
+            None,
+        ),
+    });
+
+    checks.push(virtualization_check(docker.running).await);
+    checks.push(tokio::task::spawn_blocking(disk_check).await?);
+
+    let servers_dir = paths::servers_dir();
+    checks.push(match probe_writable(&servers_dir) {
+        Ok(()) => check(
+            "servers_writable",
+            "Servers folder writable",
+            CheckStatus::Ok,
+            servers_dir.display().to_string(),
+            None,
+        ),
+        Err(e) => check(
+            "servers_writable",
+            "Servers folder writable",
+            CheckStatus::Failed,
+            format!("{}: {}", servers_dir.display(), e),
+            Some("Pick a data location you can write to in Settings, or fix the folder's permissions"),
+        ),
+    });
+
+    let images: Vec<String> = {
+        let manager = games_state.manager.lock().await;
+        manager
+            .get_all_games()
+            .into_iter()
+            .map(|g| g.docker_image)
+            .collect()
+    };
+    let mut hosts: Vec<String> = images.iter().map(|image| registry_host(image)).collect();
+    hosts.sort();
+    hosts.dedup();
+
+    let client = reqwest::Client::builder()
+        .timeout(REACH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let registry_urls: Vec<String> = hosts
+        .iter()
+        .map(|host| format!("https://{}/v2/", host))
+        .collect();
+    let (registries, steam) = tokio::join!(
+        futures_util::future::join_all(registry_urls.iter().map(|url| reachable(&client, url))),
+        reachable(&client, STEAM_URL),
+    );
+    for (host, result) in hosts.iter().zip(registries) {
+        checks.push(match result {
+            Ok(()) => check(
+                &format!("registry:{}", host),
+                &format!("Can reach {}", host),
+                CheckStatus::Ok,
+                "Game images can be downloaded",
+                None,
+            ),
+            Err(e) => check(
+                &format!("registry:{}", host),
+                &format!("Can reach {}", host),
+                CheckStatus::Failed,
+                e,
+                Some(
+                    "Check the internet connection, proxy or firewall - game images come from here",
+                ),
+            ),
+        });
+    }
+    checks.push(match steam {
+        Ok(()) => check("steam", "Can reach Steam", CheckStatus::Ok, "Steam games can be installed", None),
+        Err(e) => check(
+            "steam",
+            "Can reach Steam",
+            CheckStatus::Warning,
+            e,
+            Some("Only games installed through SteamCMD need this - check the firewall if you want one"),
+        ),
+    });
+
+    Ok(OnboardingChecks {
+        ready: checks.iter().all(|c| c.status != CheckStatus::Failed),
+        checks,
+    })
+}
+
+/// The registry an image is pulled from - Docker Hub unless the name starts with a host
+fn registry_host(image: &str) -> String {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            first.to_string()
+        }
+        _ => DOCKER_HUB.to_string(),
+    }
+}
+
+/// Any HTTP answer counts - registries answer 401 until you log in
+async fn reachable(client: &reqwest::Client, url: &str) -> Result<(), String> {
+    client.get(url).send().await.map(|_| ()).map_err(|e| {
+        if e.is_timeout() {
+            "Timed out".to_string()
+        } else {
+            e.to_string()
+        }
+    })
+}
+
+/// Free space on the disk the data directory is on
+fn disk_check() -> OnboardingCheck {
+    let root = paths::data_root();
+    match free_space(&root) {
+        Some(free) => {
+            let free_gb = free / GB;
+            let detail = format!("{} GB free for {}", free_gb, root.display());
+            if free_gb < MIN_DISK_GB {
+                check(
+                    "disk_space",
+                    "Disk space",
+                    CheckStatus::Failed,
+                    detail,
+                    Some("Free up space, or move the data location to a bigger drive in Settings"),
+                )
+            } else if free_gb < LOW_DISK_GB {
+                check(
+                    "disk_space",
+                    "Disk space",
+                    CheckStatus::Warning,
+                    detail,
+                    Some("Most game servers need 5-30 GB each - a bigger drive is safer"),
+                )
+            } else {
+                check("disk_space", "Disk space", CheckStatus::Ok, detail, None)
+            }
+        }
+        None => check(
+            "disk_space",
+            "Disk space",
+            CheckStatus::Skipped,
+            format!("No disk found for {}", root.display()),
+            None,
+        ),
+    }
+}
+
+/// Docker Desktop needs hardware virtualization; Docker on Linux doesn't. When Docker is
+/// already running there's nothing to check.
+async fn virtualization_check(docker_running: bool) -> OnboardingCheck {
+    let label = "Virtualization enabled";
+    if docker_running {
+        return check(
+            "virtualization",
+            label,
+            CheckStatus::Ok,
+            "Docker is running",
+            None,
+        );
+    }
+    match virtualization_enabled().await {
+        Some(true) => check(
+            "virtualization",
+            label,
+            CheckStatus::Ok,
+            "Supported and enabled",
+            None,
+        ),
+        Some(false) => check(
+            "virtualization",
+            label,
+            CheckStatus::Failed,
+            "The CPU's virtualization (VT-x / AMD-V) is off or missing",
+            Some("Turn on Intel VT-x or AMD SVM in the BIOS/UEFI settings, then restart"),
+        ),
+        None if cfg!(target_os = "linux") => check(
+            "virtualization",
+            label,
+            CheckStatus::Skipped,
+            "Docker runs natively on Linux",
+            None,
+        ),
+        None => check(
+            "virtualization",
+            label,
+            CheckStatus::Skipped,
+            "Couldn't be checked",
+            None,
+        ),
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn virtualization_enabled() -> Option<bool> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    // With Hyper-V running, the firmware flag reads false even though virtualization works
+    let script = "$c = Get-CimInstance Win32_ComputerSystem; $p = Get-CimInstance Win32_Processor | Select-Object -First 1; \
+                  if ($c.HypervisorPresent -or $p.VirtualizationFirmwareEnabled) { 'yes' } else { 'no' }";
+    let output = tokio::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .await
+        .ok()?;
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "macos")]
+async fn virtualization_enabled() -> Option<bool> {
+    let output = tokio::process::Command::new("sysctl")
+        .args(["-n", "kern.hv_support"])
+        .output()
+        .await
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).trim() == "1")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+async fn virtualization_enabled() -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_host() {
+        assert_eq!(
+            registry_host("ghcr.io/serverwavehost/game-images:java_21"),
+            "ghcr.io"
+        );
+        assert_eq!(registry_host("itzg/minecraft-server"), DOCKER_HUB);
+        assert_eq!(registry_host("debian:bookworm"), DOCKER_HUB);
+        assert_eq!(registry_host("localhost:5000/game"), "localhost:5000");
+    }
+}
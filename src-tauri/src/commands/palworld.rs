@@ -0,0 +1,82 @@
+// Palworld commands - the admin REST API of a running Palworld server
+
+use crate::commands::games::GamesState;
+use crate::error::AppError;
+use crate::palworld::{self, Metrics, Player, RestApi, ServerInfo};
+use serde::Serialize;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PalworldStatus {
+    pub info: ServerInfo,
+    pub metrics: Metrics,
+    pub players: Vec<Player>,
+}
+
+async fn rest_api(server_id: &str, games_state: &State<'_, GamesState>) -> Result<RestApi, AppError> {
+    let server = palworld::palworld_server(server_id)?;
+    let game = games_state.manager.lock().await.get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+    RestApi::new(&server, &game)
+}
+
+/// The server's version, performance and who's online
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_palworld_status(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+) -> Result<PalworldStatus, AppError> {
+    let api = rest_api(&server_id, &games_state).await?;
+    let (info, metrics, players) = tokio::try_join!(api.info(), api.metrics(), api.players())?;
+    Ok(PalworldStatus { info, metrics, players })
+}
+
+/// Show a message to everyone on the server
+#[tauri::command(rename_all = "camelCase")]
+pub async fn palworld_announce(
+    server_id: String,
+    message: String,
+    games_state: State<'_, GamesState>,
+) -> Result<(), AppError> {
+    if message.trim().is_empty() {
+        return Err(AppError::InvalidInput("Enter a message to announce".into()));
+    }
+    rest_api(&server_id, &games_state).await?.announce(&message).await
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn palworld_kick(
+    server_id: String,
+    user_id: String,
+    message: Option<String>,
+    games_state: State<'_, GamesState>,
+) -> Result<(), AppError> {
+    let api = rest_api(&server_id, &games_state).await?;
+    api.kick(&user_id, message.as_deref().unwrap_or("You were kicked")).await
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn palworld_ban(
+    server_id: String,
+    user_id: String,
+    message: Option<String>,
+    games_state: State<'_, GamesState>,
+) -> Result<(), AppError> {
+    let api = rest_api(&server_id, &games_state).await?;
+    api.ban(&user_id, message.as_deref().unwrap_or("You were banned")).await
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn palworld_unban(
+    server_id: String,
+    user_id: String,
+    games_state: State<'_, GamesState>,
+) -> Result<(), AppError> {
+    rest_api(&server_id, &games_state).await?.unban(&user_id).await
+}
+
+/// Save the world now rather than at the next autosave
+#[tauri::command(rename_all = "camelCase")]
+pub async fn palworld_save(server_id: String, games_state: State<'_, GamesState>) -> Result<(), AppError> {
+    rest_api(&server_id, &games_state).await?.save().await
+}
@@ -0,0 +1,91 @@
+// Player commands - who played when, from the sessions recorded off the console for games
+// whose definition has player log rules, and who's on now, from the game's own query
+
+use crate::commands::games::GamesState;
+use crate::error::AppError;
+use crate::palworld::{RestApi, PALWORLD_GAME};
+use serde::Serialize;
+use serverwave_core::{a2s, slp};
+use serverwave_core::server::{load_server_config, sessions, PlayerSession, ServerStatus, SessionRange};
+use std::net::{Ipv4Addr, SocketAddr};
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerQuery {
+    /// The name the server lists itself under
+    pub name: String,
+    pub map: Option<String>,
+    pub players: u32,
+    pub max_players: u32,
+    /// Who's on, when the server says
+    pub player_names: Vec<String>,
+}
+
+/// The server's player sessions overlapping `range`, newest first. Leave out `range` (or
+/// either end) for everything.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_player_sessions(
+    server_id: String,
+    range: Option<SessionRange>,
+) -> Result<Vec<PlayerSession>, AppError> {
+    load_server_config(&server_id)?;
+    Ok(sessions::load_sessions(&server_id, &range.unwrap_or_default())?)
+}
+
+/// Ask a running server who's on - over Steam's query protocol, a Minecraft server list
+/// ping, or Palworld's REST API
+#[tauri::command(rename_all = "camelCase")]
+pub async fn query_server(server_id: String, games_state: State<'_, GamesState>) -> Result<ServerQuery, AppError> {
+    let server = load_server_config(&server_id)?;
+    if server.status != ServerStatus::Running {
+        return Err(AppError::InvalidInput("The server isn't running".into()));
+    }
+    if let Some(edition) = slp::Edition::of(&server) {
+        let ping = slp::ping(edition, SocketAddr::from((Ipv4Addr::LOCALHOST, server.port))).await?;
+        return Ok(ServerQuery {
+            name: ping.motd,
+            map: None,
+            players: ping.players,
+            max_players: ping.max_players,
+            player_names: ping.player_names,
+        });
+    }
+    let game = games_state
+        .manager
+        .lock()
+        .await
+        .get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+
+    if server.game_type.0 == PALWORLD_GAME {
+        let api = RestApi::new(&server, &game)?;
+        let (info, metrics, players) = tokio::try_join!(api.info(), api.metrics(), api.players())?;
+        return Ok(ServerQuery {
+            name: info.servername,
+            map: None,
+            players: metrics.currentplayernum,
+            max_players: metrics.maxplayernum,
+            player_names: players.into_iter().map(|p| p.name).collect(),
+        });
+    }
+
+    let port = a2s::query_port(&server, &game)
+        .ok_or_else(|| AppError::InvalidInput(format!("{} doesn't answer player queries", game.name)))?;
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let info = a2s::info(addr).await?;
+    // Some servers hide their player list - the count is still right
+    let player_names = match a2s::players(addr).await {
+        Ok(players) => players.into_iter().map(|p| p.name).collect(),
+        Err(e) => {
+            tracing::debug!("No player list from {}: {}", server_id, e);
+            Vec::new()
+        }
+    };
+    Ok(ServerQuery {
+        name: info.name,
+        map: Some(info.map).filter(|m| !m.is_empty()),
+        players: info.players.saturating_sub(info.bots).into(),
+        max_players: info.max_players.into(),
+        player_names,
+    })
+}
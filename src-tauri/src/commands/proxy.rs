@@ -0,0 +1,335 @@
+// Proxy network commands - a Velocity proxy in front of several Paper servers
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::network::build_port_allocator;
+use crate::commands::server::{
+    create_server, get_servers_config_dir, CreateServerRequest, Server, ServerState,
+};
+use serverwave_core::server::ServerConfigs;
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use crate::games::GameType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::State;
+use uuid::Uuid;
+
+const PROXY_GAME: &str = "velocity";
+const BACKEND_GAME: &str = "minecraft-java";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyNetwork {
+    pub id: String,
+    pub name: String,
+    /// Docker network shared by the proxy and its backends
+    pub docker_network: String,
+    pub proxy_server_id: String,
+    pub backend_server_ids: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BackendRequest {
+    pub name: String,
+    pub memory_mb: Option<u32>,
+    pub config: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProxyNetworkRequest {
+    pub name: String,
+    pub proxy_port: Option<u16>,
+    pub proxy_memory_mb: Option<u32>,
+    pub backends: Vec<BackendRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProxyNetworkResponse {
+    pub network: ProxyNetwork,
+    pub servers: Vec<Server>,
+}
+
+/// Create a Velocity proxy plus backend Paper servers on a shared Docker network,
+/// with modern forwarding configured on both sides
+#[tauri::command]
+pub async fn create_proxy_network(
+    request: CreateProxyNetworkRequest,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ProxyNetworkResponse, AppError> {
+    if request.backends.is_empty() {
+        return Err(AppError::InvalidInput("A proxy network needs at least one backend server".into()));
+    }
+
+    let docker = docker_state.manager()?;
+    let network_id = Uuid::new_v4().to_string()[..8].to_string();
+    let docker_network = format!("serverwave-net-{}", network_id);
+    docker.ensure_network(&docker_network).await?;
+
+    let forwarding_secret = Uuid::new_v4().simple().to_string();
+    // Players expect the proxy on the default Minecraft port, not Velocity's own default
+    let proxy_port = match request.proxy_port {
+        Some(port) => port,
+        None => {
+            let games_manager = games_state.manager.lock().await;
+            build_port_allocator(&games_manager)
+                .next_free(25565)
+                .ok_or("No free port left in the configured port range")?
+        }
+    };
+
+    // Create the proxy first so the backends' automatic ports skip it
+    let response = create_server(
+        CreateServerRequest {
+            name: format!("{} Proxy", request.name),
+            game_type: GameType::new(PROXY_GAME),
+            port: Some(proxy_port),
+            config: None,
+            memory_mb: request.proxy_memory_mb,
+            open_firewall: false,
+        },
+        state.clone(),
+        games_state.clone(),
+        docker_state.clone(),
+    )
+    .await?;
+    let proxy = response.server.ok_or("Proxy server was not created")?;
+
+    let mut backends = Vec::new();
+    for backend in request.backends {
+        let mut config = backend.config.unwrap_or_default();
+        config.insert("MC_ONLINEMODE".to_string(), "false".to_string());
+
+        let response = create_server(
+            CreateServerRequest {
+                name: backend.name,
+                game_type: GameType::new(BACKEND_GAME),
+                port: None,
+                config: Some(config),
+                memory_mb: backend.memory_mb,
+                open_firewall: false,
+            },
+            state.clone(),
+            games_state.clone(),
+            docker_state.clone(),
+        )
+        .await?;
+        let server = response.server.ok_or("Backend server was not created")?;
+
+        write_backend_config(&server.data_path, server.port, &forwarding_secret)?;
+        join_network(&docker, &state.configs, &docker_network, &server).await?;
+        backends.push(server);
+    }
+
+    write_velocity_config(&proxy.data_path, proxy_port, &forwarding_secret, &backends)?;
+    join_network(&docker, &state.configs, &docker_network, &proxy).await?;
+
+    let network = ProxyNetwork {
+        id: network_id,
+        name: request.name,
+        docker_network,
+        proxy_server_id: proxy.id.clone(),
+        backend_server_ids: backends.iter().map(|s| s.id.clone()).collect(),
+        created_at: chrono::Utc::now(),
+    };
+    save_proxy_network(&network)?;
+
+    let mut servers = vec![proxy];
+    servers.extend(backends);
+
+    Ok(ProxyNetworkResponse { network, servers })
+}
+
+/// List all proxy networks
+#[tauri::command]
+pub async fn list_proxy_networks() -> Result<Vec<ProxyNetwork>, AppError> {
+    let dir = get_networks_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut networks = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map(|e| e == "json").unwrap_or(false) {
+            let content = std::fs::read_to_string(&path)?;
+            let network: ProxyNetwork = serde_json::from_str(&content)?;
+            networks.push(network);
+        }
+    }
+
+    networks.sort_by_key(|n| std::cmp::Reverse(n.created_at));
+    Ok(networks)
+}
+
+/// Delete a proxy network record and its Docker network. The servers themselves are kept.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn delete_proxy_network(network_id: String, state: State<'_, ServerState>, docker_state: State<'_, DockerState>) -> Result<(), AppError> {
+    crate::security::ensure_unlocked().await?;
+    let path = get_network_path(&network_id);
+    let content = std::fs::read_to_string(&path)?;
+    let network: ProxyNetwork = serde_json::from_str(&content)?;
+
+    let docker = docker_state.manager()?;
+    for server_id in std::iter::once(&network.proxy_server_id).chain(&network.backend_server_ids) {
+        if let Ok(server) = state.configs.get(server_id) {
+            if let Some(container_id) = &server.container_id {
+                let _ = docker
+                    .client()
+                    .disconnect_network(
+                        &network.docker_network,
+                        bollard::network::DisconnectNetworkOptions {
+                            container: container_id.as_str(),
+                            force: true,
+                        },
+                    )
+                    .await;
+            }
+            state.configs.update(server_id, |s| s.network = None)?;
+        }
+    }
+    docker.remove_network(&network.docker_network).await.ok();
+
+    std::fs::remove_file(path).map_err(AppError::from)
+}
+
+async fn join_network(
+    docker: &DockerManager,
+    configs: &ServerConfigs,
+    network: &str,
+    server: &Server,
+) -> Result<(), AppError> {
+    let container_id = server.container_id.as_deref().ok_or("No container ID")?;
+    docker
+        .connect_to_network(network, container_id)
+        .await?;
+
+    configs.update(&server.id, |s| s.network = Some(network.to_string()))?;
+    Ok(())
+}
+
+/// Pre-seed server.properties and Paper's velocity settings before the first start.
+/// The install script keeps an existing server.properties and Paper fills in the rest.
+fn write_backend_config(data_path: &Path, port: u16, secret: &str) -> Result<(), AppError> {
+    let properties = format!(
+        "#Minecraft server properties\nserver-port={}\nonline-mode=false\nmotd=A Serverwave Anywhere Server\n",
+        port
+    );
+    std::fs::write(data_path.join("server.properties"), properties)?;
+
+    let config_dir = data_path.join("config");
+    std::fs::create_dir_all(&config_dir)?;
+    let paper_global = format!(
+        "proxies:\n  velocity:\n    enabled: true\n    online-mode: true\n    secret: '{}'\n",
+        secret
+    );
+    std::fs::write(config_dir.join("paper-global.yml"), paper_global).map_err(AppError::from)
+}
+
+fn write_velocity_config(
+    data_path: &Path,
+    port: u16,
+    secret: &str,
+    backends: &[Server],
+) -> Result<(), AppError> {
+    let names = backend_keys(backends);
+
+    let mut servers = String::new();
+    for (key, backend) in names.iter().zip(backends) {
+        servers.push_str(&format!("{} = \"serverwave-{}:{}\"\n", key, backend.id, backend.port));
+    }
+    let try_list: Vec<String> = names.iter().map(|n| format!("\"{}\"", n)).collect();
+
+    let config = format!(
+        r#"config-version = "2.7"
+bind = "0.0.0.0:{port}"
+motd = "<#09add3>A Serverwave Anywhere Network"
+show-max-players = 500
+online-mode = true
+force-key-authentication = true
+player-info-forwarding-mode = "modern"
+forwarding-secret-file = "forwarding.secret"
+
+[servers]
+{servers}try = [{try_list}]
+
+[forced-hosts]
+"#,
+        port = port,
+        servers = servers,
+        try_list = try_list.join(", "),
+    );
+
+    std::fs::write(data_path.join("velocity.toml"), config)?;
+    std::fs::write(data_path.join("forwarding.secret"), secret).map_err(AppError::from)
+}
+
+/// TOML-safe server key derived from a display name
+fn backend_key(name: &str) -> String {
+    let key: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let key = key.trim_matches('-').to_string();
+    if key.is_empty() {
+        "server".to_string()
+    } else {
+        key
+    }
+}
+
+/// A key per backend, adding the server's id to the ones whose names make a key that's
+/// taken so none overwrites another in [servers]
+fn backend_keys(backends: &[Server]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::with_capacity(backends.len());
+    for backend in backends {
+        let mut key = backend_key(&backend.name);
+        if keys.contains(&key) {
+            key = format!("{}-{}", key, backend_key(&backend.id));
+        }
+        keys.push(key);
+    }
+    keys
+}
+
+fn get_networks_dir() -> PathBuf {
+    get_servers_config_dir().join("networks")
+}
+
+fn get_network_path(network_id: &str) -> PathBuf {
+    get_networks_dir().join(format!("{}.json", network_id))
+}
+
+fn save_proxy_network(network: &ProxyNetwork) -> Result<(), AppError> {
+    let content = serde_json::to_string_pretty(network)?;
+    serverwave_core::atomic::write_atomic(&get_network_path(&network.id), content).map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(id: &str, name: &str) -> Server {
+        Server { name: name.to_string(), port: 25566, ..Server::test_fixture(id) }
+    }
+
+    #[test]
+    fn test_velocity_config_keeps_colliding_names_apart() {
+        let dir = std::env::temp_dir().join(format!("serverwave-velocity-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let backends = [backend("aa11", "Lobby"), backend("bb22", "lobby!"), backend("cc33", "Lobby 2"), backend("dd44", "LOBBY")];
+
+        write_velocity_config(&dir, 25565, "secret", &backends).unwrap();
+        let config = std::fs::read_to_string(dir.join("velocity.toml")).unwrap();
+        assert!(config.contains("lobby = \"serverwave-aa11:25566\"\n"));
+        assert!(config.contains("lobby-bb22 = \"serverwave-bb22:25566\"\n"));
+        assert!(config.contains("lobby-2 = \"serverwave-cc33:25566\"\n"));
+        assert!(config.contains("lobby-dd44 = \"serverwave-dd44:25566\"\n"));
+        assert!(config.contains("try = [\"lobby\", \"lobby-bb22\", \"lobby-2\", \"lobby-dd44\"]"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+// Remote access commands - the API's certificate and pairing codes for remote apps
+
+use crate::api::pairing::{self, PairingCode};
+use crate::api::tls::{self, ApiIdentity};
+use crate::error::AppError;
+use crate::security::ensure_unlocked;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteAccess {
+    /// Where the API listens, None when it's off
+    pub listening: Option<String>,
+    /// The certificate served, None over plain HTTP
+    pub identity: Option<ApiIdentity>,
+}
+
+#[tauri::command]
+pub async fn get_remote_access() -> Result<RemoteAccess, AppError> {
+    Ok(RemoteAccess {
+        listening: crate::api::http::listening().map(|addr| addr.to_string()),
+        identity: tls::identity(),
+    })
+}
+
+/// A one-time code a remote app trades for an admin token of its own
+#[tauri::command]
+pub async fn create_pairing_code() -> Result<PairingCode, AppError> {
+    ensure_unlocked().await?;
+    if crate::api::http::listening().is_none() {
+        return Err(AppError::InvalidInput(
+            "The API isn't running - start the app with --api or --agent".into(),
+        ));
+    }
+    Ok(pairing::create_code())
+}
+
+/// Replace the self-signed certificate, e.g. after its key leaked. Paired apps pair again.
+#[tauri::command]
+pub async fn regenerate_api_certificate() -> Result<ApiIdentity, AppError> {
+    ensure_unlocked().await?;
+    Ok(tls::regenerate().await?)
+}
@@ -0,0 +1,82 @@
+// Schedule commands - run a server's restarts, backups, console commands and updates on cron
+// expressions
+
+use crate::error::AppError;
+use crate::scheduler::{self, Schedule};
+use crate::security::{can_access, Permission};
+use chrono::{Local, Utc};
+use serverwave_core::server::load_server_config;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// Add a schedule to a server, or replace the one with the same id
+#[tauri::command(rename_all = "camelCase")]
+pub async fn add_schedule(server_id: String, mut schedule: Schedule) -> Result<Schedule, AppError> {
+    load_server_config(&server_id)?;
+    schedule.validate().map_err(AppError::InvalidInput)?;
+    schedule.server_id = server_id;
+    schedule.cron = schedule.cron.trim().to_string();
+    schedule.name = schedule.name.trim().to_string();
+    if schedule.id.is_empty() {
+        schedule.id = Uuid::new_v4().to_string()[..8].to_string();
+    }
+    schedule.next_run = schedule.next_from(&Local::now());
+    if schedule.next_run.is_none() {
+        return Err(AppError::InvalidInput(format!("'{}' never runs", schedule.cron)));
+    }
+
+    let saved = scheduler::modify(|all| {
+        match all.iter_mut().find(|s| s.id == schedule.id) {
+            Some(existing) if existing.server_id != schedule.server_id => {
+                return Err(format!("Schedule '{}' belongs to another server", schedule.id));
+            }
+            Some(existing) => {
+                schedule.last_run = existing.last_run;
+                schedule.last_result = existing.last_result.take();
+                *existing = schedule.clone();
+            }
+            None => all.push(schedule.clone()),
+        }
+        Ok(schedule)
+    })?;
+    Ok(saved)
+}
+
+/// A server's schedules, or every server's the caller can see
+#[tauri::command(rename_all = "camelCase")]
+pub async fn list_schedules(server_id: Option<String>) -> Result<Vec<Schedule>, AppError> {
+    Ok(scheduler::load_schedules()
+        .into_iter()
+        .filter(|s| server_id.as_ref().is_none_or(|id| &s.server_id == id))
+        .filter(|s| can_access(Permission::View, Some(&s.server_id)))
+        .collect())
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_schedule(schedule_id: String) -> Result<(), AppError> {
+    scheduler::modify(|all| {
+        let before = all.len();
+        all.retain(|s| s.id != schedule_id);
+        if all.len() == before {
+            return Err(format!("Schedule '{}' not found", schedule_id));
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Run a schedule's action now, whether or not it's enabled - a restart after its warning
+/// countdown. Its next run stays as it was.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn run_schedule_now(schedule_id: String, app: AppHandle) -> Result<Schedule, AppError> {
+    let schedule = find(&schedule_id)?;
+    scheduler::run_and_record(&app, &schedule, Utc::now() + schedule.countdown(), false).await?;
+    find(&schedule_id)
+}
+
+fn find(schedule_id: &str) -> Result<Schedule, AppError> {
+    scheduler::load_schedules()
+        .into_iter()
+        .find(|s| s.id == schedule_id)
+        .ok_or_else(|| format!("Schedule '{}' not found", schedule_id).into())
+}
@@ -0,0 +1,84 @@
+// Security commands - app lock, API token and audit journal
+
+use crate::api::auth;
+use crate::audit::{self, AuditEntry};
+use crate::error::AppError;
+use crate::security::ensure_unlocked;
+use crate::security::lock::{self, LockStatus};
+use crate::security::read_only;
+use tauri::{AppHandle, Emitter, Window};
+
+#[tauri::command]
+pub async fn get_lock_status() -> Result<LockStatus, AppError> {
+    Ok(lock::status().await)
+}
+
+/// Set, change or remove (empty new password) the app password
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_app_password(
+    current_password: Option<String>,
+    new_password: Option<String>,
+) -> Result<LockStatus, AppError> {
+    lock::set_password(current_password.as_deref(), new_password.as_deref()).await?;
+    Ok(lock::status().await)
+}
+
+#[tauri::command]
+pub async fn unlock_app(password: String) -> Result<LockStatus, AppError> {
+    lock::unlock(&password).await?;
+    Ok(lock::status().await)
+}
+
+#[tauri::command]
+pub async fn lock_app() -> Result<LockStatus, AppError> {
+    lock::lock();
+    Ok(lock::status().await)
+}
+
+/// Whether the calling window is read-only
+#[tauri::command]
+pub async fn get_read_only_mode(window: Window) -> Result<bool, AppError> {
+    Ok(read_only::is_read_only(window.label()))
+}
+
+/// Make the calling window read-only, or leave read-only mode - which needs the app password
+/// when one is set
+#[tauri::command]
+pub async fn set_read_only_mode(enabled: bool, window: Window) -> Result<bool, AppError> {
+    if !enabled {
+        ensure_unlocked().await?;
+    }
+    read_only::set(window.label(), enabled);
+    window.emit_to(window.label(), "read-only-changed", enabled).map_err(|e| e.to_string())?;
+    Ok(enabled)
+}
+
+/// Open another window that can only look, next to the one the owner keeps using
+#[tauri::command]
+pub async fn open_read_only_window(app: AppHandle) -> Result<(), AppError> {
+    let mut config = app.config().app.windows.first().cloned().ok_or("No window configured")?;
+    config.label = format!("{}{}", read_only::WINDOW_PREFIX, &uuid::Uuid::new_v4().to_string()[..8]);
+    config.title = format!("{} (read-only)", config.title);
+    // Marked before it loads, so its first command is already checked
+    read_only::set(&config.label, true);
+    if let Err(e) = tauri::WebviewWindowBuilder::from_config(&app, &config).and_then(|builder| builder.build()) {
+        read_only::set(&config.label, false);
+        return Err(e.to_string().into());
+    }
+    Ok(())
+}
+
+/// The token remote clients and agents authenticate with
+#[tauri::command]
+pub async fn get_api_token() -> Result<String, AppError> {
+    ensure_unlocked().await?;
+    Ok(auth::load_or_create_token().await?)
+}
+
+/// The audit journal, newest first
+#[tauri::command]
+pub async fn get_audit_journal() -> Result<Vec<AuditEntry>, AppError> {
+    let mut journal = audit::load_journal();
+    journal.reverse();
+    Ok(journal)
+}
@@ -1,45 +1,57 @@
+use crate::audit::{self, AuditEntry, DataFate, Tombstone};
+use crate::backup_targets;
+use crate::capacity;
+use crate::crash_loop::{self, CrashReport};
+use crate::database;
+use crate::disk_guard;
+use crate::commands::docker::DockerState;
 use crate::commands::games::GamesState;
-use crate::docker::DockerManager;
-use crate::games::{build_env_vars, GameType};
+use crate::commands::settings::load_app_settings;
+use crate::commands::network::{
+    build_port_allocator, forward_ports, port_mappings_for, server_port_mappings_extra,
+    start_server_tunnel, unforward_ports,
+};
+use crate::docker::{container_command, platform, steam_cache, DockerManager, ScriptOptions};
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, InstallStage, ServerEvent};
+use crate::games::{
+    preview_config_variables, ConfigFileDiff, EnvVar, GameConfig, GameType, InstallNetwork, PlayerLogEvent,
+    PlayerLogRules, PortConfig, DEFAULT_START_TIMEOUT_SECS,
+};
+use crate::gslt;
+use crate::images;
+use crate::install_auth::{self, AuthUpdate};
+use crate::install_queue::{self, InstallActivity};
+use crate::minecraft::properties;
+use crate::network::{egress, firewall, tunnel, MappingProtocol, PortMapping};
+use crate::palworld;
+use crate::port_conflicts;
+use crate::references;
+use crate::scheduler;
+use crate::security::{can_access, Permission};
+use crate::steam;
+use crate::updates;
+use crate::wipes;
 use bollard::container::{LogOutput, LogsOptions};
-use bollard::exec::{CreateExecOptions, StartExecResults};
+use chrono::{DateTime, Local, Utc};
 use futures_util::stream::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
+use serverwave_core::ports;
+use serverwave_core::rcon;
+use serverwave_core::slp::{self, ServerPing};
+use serverwave_core::server::{artifacts, console};
+use serverwave_core::server::lifecycle::{self, Readiness, StopOutcome};
+use serverwave_core::server::{calculate_dir_size, get_servers_dir, sessions, MaintenancePolicy, StartSchedule};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Server {
-    pub id: String,
-    pub name: String,
-    pub game_type: GameType,
-    pub status: ServerStatus,
-    pub container_id: Option<String>,
-    pub port: u16,
-    pub memory_mb: u32,
-    pub data_path: PathBuf,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub config: HashMap<String, String>,
-    #[serde(default)]
-    pub installed: bool,
-    #[serde(default)]
-    pub install_container_id: Option<String>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
-pub enum ServerStatus {
-    Stopped,
-    Starting,
-    Installing,
-    Running,
-    Stopping,
-    Error,
-}
+pub use serverwave_core::server::{Server, ServerStatus};
+pub(crate) use serverwave_core::server::{get_servers_config_dir, load_all_server_configs, load_server_config};
+use serverwave_core::server::ServerConfigs;
 
 #[derive(Debug, Deserialize)]
 pub struct CreateServerRequest {
@@ -48,6 +60,9 @@ pub struct CreateServerRequest {
     pub port: Option<u16>,
     pub config: Option<HashMap<String, String>>,
     pub memory_mb: Option<u32>,
+    /// Create inbound firewall rules for the server's ports (Windows only)
+    #[serde(default)]
+    pub open_firewall: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -63,24 +78,21 @@ pub struct LogsResponse {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct LogEvent {
-    pub server_id: String,
-    pub line: String,
-}
-
 pub struct LogStreamHandle {
     pub cancel_tx: tokio::sync::watch::Sender<bool>,
 }
 
 pub struct ServerState {
     pub streams: Arc<Mutex<HashMap<String, LogStreamHandle>>>,
+    /// Every config change goes through here, so concurrent commands don't overwrite each other
+    pub configs: Arc<ServerConfigs>,
 }
 
 impl Default for ServerState {
     fn default() -> Self {
         Self {
             streams: Arc::new(Mutex::new(HashMap::new())),
+            configs: Arc::new(ServerConfigs::default()),
         }
     }
 }
@@ -88,87 +100,128 @@ impl Default for ServerState {
 #[tauri::command]
 pub async fn create_server(
     request: CreateServerRequest,
+    state: State<'_, ServerState>,
     games_state: State<'_, GamesState>,
-) -> Result<ServerResponse, String> {
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
     tracing::info!("Creating server: {:?}", request.name);
 
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
+    let docker = docker_state.manager()?;
     
     let games_manager = games_state.manager.lock().await;
     let game_config = games_manager.get_game(&request.game_type)
-        .ok_or_else(|| format!("Game type '{}' not found", request.game_type))?;
+        .ok_or_else(|| AppError::GameNotFound(request.game_type.to_string()))?;
     
     let server_id = Uuid::new_v4().to_string()[..8].to_string();
-    
-    let port = request.port.unwrap_or_else(|| {
-        game_config.ports.first()
-            .map(|p| p.container_port)
-            .unwrap_or(25565)
-    });
 
-    let memory_mb = request.memory_mb.unwrap_or(game_config.recommended_ram_mb);
+    // Hold the allocation lock until the server is saved so its ports count as taken
+    let _allocation = ports::ALLOCATION_LOCK.lock().await;
+    let mut allocator = build_port_allocator(&games_manager);
+
+    let port = match request.port {
+        Some(port) => port,
+        None => {
+            let default_port = game_config.ports.first()
+                .map(|p| p.container_port)
+                .unwrap_or(25565);
+            allocator.next_free(default_port)
+                .ok_or("No free port left in the configured port range")?
+        }
+    };
+    allocator.assign(&server_id, &request.name, port, Some("Game port".to_string()))?;
+
+    let mut user_config = request.config.clone().unwrap_or_default();
+    rcon::ensure_password(&mut user_config, &game_config);
+
+    // Extra ports the game reads from a variable can move; fixed ones must be free as-is
+    let mut extra_ports = Vec::new();
+    for extra in game_config.ports.iter().skip(1) {
+        let mut extra = extra.clone();
+        if let Some(var) = extra.env_var.clone().filter(|v| !v.is_empty()) {
+            extra.container_port = match user_config.get(&var).and_then(|v| v.parse().ok()) {
+                Some(chosen) => chosen,
+                None => allocator.next_free(extra.container_port)
+                    .ok_or("No free port left in the configured port range")?,
+            };
+            user_config.insert(var, extra.container_port.to_string());
+        }
+        allocator.assign(&server_id, &request.name, extra.container_port, extra.description.clone())?;
+        extra_ports.push(extra);
+    }
+
+    let memory_mb = request
+        .memory_mb
+        .or(load_app_settings().default_memory_mb)
+        .unwrap_or(game_config.recommended_ram_mb);
 
     let data_path = get_servers_dir()
         .join(request.game_type.to_string())
         .join(&server_id);
 
-    std::fs::create_dir_all(&data_path).map_err(|e| e.to_string())?;
-
-    let user_config = request.config.clone().unwrap_or_default();
-    let env = build_env_vars(&game_config, memory_mb, port, &user_config);
+    std::fs::create_dir_all(&data_path)?;
 
     tracing::info!("Server memory limit: {} MB", memory_mb);
 
-    let extra_ports: Vec<_> = game_config.ports.iter()
-        .skip(1)
-        .cloned()
-        .collect();
-
-    // Get startup command if defined
-    let startup_command = if game_config.startup.is_empty() {
-        None
+    let firewall_mappings = if request.open_firewall {
+        let mut mappings = vec![
+            PortMapping { port, protocol: MappingProtocol::Tcp },
+            PortMapping { port, protocol: MappingProtocol::Udp },
+        ];
+        mappings.extend(server_port_mappings_extra(&extra_ports));
+        Some(mappings)
     } else {
-        // Resolve variables in startup command
-        let mut startup = game_config.startup.clone();
-        for (key, value) in &env {
-            startup = startup.replace(&format!("{{{{{}}}}}", key), value);
-        }
-        Some(startup)
+        None
     };
 
     drop(games_manager);
 
-    let container_id = docker
-        .create_container(
-            &server_id,
-            &game_config.docker_image,
-            port,
-            &data_path,
-            &env,
-            &extra_ports,
-            Some(&game_config.volume_path),
-            Some(memory_mb),
-            startup_command.as_deref(),
-        )
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let server = Server {
+    let mut server = Server {
         id: server_id,
         name: request.name,
         game_type: request.game_type,
         status: ServerStatus::Stopped,
-        container_id: Some(container_id),
+        container_id: None,
         port,
         memory_mb,
         data_path,
         created_at: chrono::Utc::now(),
-        config: user_config,
+        config: user_config.clone(),
         installed: false,
         install_container_id: None,
+        upnp_enabled: false,
+        tunnel: None,
+        firewall_rules: false,
+        network: None,
+        extra_ports,
+        install_state: None,
+        versions: Vec::new(),
+        pinned_version: None,
+        config_outdated: false,
+        docker_image: game_config.select_image(&user_config),
+        env: Vec::new(),
+        maintenance: None,
+        auto_restart: false,
+        database: None,
+        backup_schedule: None,
+        backup_targets: Vec::new(),
+        start_schedule: None,
+        console: None,
+        install_network: None,
     };
 
-    save_server_config(&server)?;
+    let container_id = lifecycle::create_container(&docker, &server, &game_config)
+        .await?;
+    server.container_id = Some(container_id);
+
+    // Firewall rules are best effort - the server is usable locally without them
+    if let Some(mappings) = firewall_mappings {
+        match firewall::add_rules(&server.id, &mappings).await {
+            Ok(()) => server.firewall_rules = true,
+            Err(e) => tracing::warn!("Failed to create firewall rules: {}", e),
+        }
+    }
+
+    state.configs.insert(&server)?;
 
     Ok(ServerResponse {
         success: true,
@@ -177,17 +230,85 @@ pub async fn create_server(
     })
 }
 
+/// A server made before its game took RCON has no port of its own for it, so every such
+/// server would publish the game's default. Give it one, and a password, to be published
+/// when the container is made again.
+async fn ensure_rcon(
+    configs: &ServerConfigs,
+    games_state: &State<'_, GamesState>,
+    mut server: Server,
+    game: &GameConfig,
+) -> Result<Server, AppError> {
+    let Some(settings) = &game.rcon else { return Ok(server) };
+    let mut changed = rcon::ensure_password(&mut server.config, game);
+    let is_rcon = |p: &PortConfig| p.env_var.as_deref() == Some(settings.port_var.as_str());
+    let default = game.ports.iter().skip(1).find(|p| is_rcon(p));
+    let _allocation = ports::ALLOCATION_LOCK.lock().await;
+    if let Some(default) = default.filter(|_| !server.extra_ports.iter().any(is_rcon)) {
+        let allocator = build_port_allocator(&*games_state.manager.lock().await);
+        let port = match allocator.owner(default.container_port) {
+            Some(owner) if owner.server_id == server.id => default.container_port,
+            _ => allocator
+                .next_free(default.container_port)
+                .ok_or("No free port left in the configured port range")?,
+        };
+        if server.extra_ports.is_empty() {
+            server.extra_ports = lifecycle::extra_ports(&server, Some(game));
+        }
+        match server.extra_ports.iter_mut().find(|p| is_rcon(p)) {
+            Some(extra) => extra.container_port = port,
+            None => server.extra_ports.push(PortConfig { container_port: port, ..default.clone() }),
+        }
+        server.config.insert(settings.port_var.clone(), port.to_string());
+        changed = true;
+    }
+    if !changed {
+        return Ok(server);
+    }
+    Ok(configs.update(&server.id, |s| {
+        s.config = server.config.clone();
+        s.extra_ports = server.extra_ports.clone();
+        s.config_outdated |= s.container_id.is_some();
+    })?)
+}
+
+/// Put the server's container on the networks of the servers it references and of its
+/// database, starting the database. Run at each start, and again after the container is made anew.
+async fn attach_networks(
+    docker: &DockerManager,
+    configs: &ServerConfigs,
+    server: Server,
+    linked: &[Server],
+) -> Result<Server, AppError> {
+    references::connect(docker, &server, linked).await?;
+    match database::start(docker, &server).await? {
+        Some(sidecar) => Ok(configs.update(&server.id, |s| s.database = Some(sidecar))?),
+        None => Ok(server),
+    }
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn start_server(
     server_id: String,
+    force: Option<bool>,
+    move_port: Option<bool>,
     app: AppHandle,
     state: State<'_, ServerState>,
     games_state: State<'_, GamesState>,
-) -> Result<ServerResponse, String> {
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
     tracing::info!("Starting server: {}", server_id);
 
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let mut server = load_server_config(&server_id)?;
+    let docker = docker_state.manager()?;
+    let mut server = state.configs.get(&server_id)?;
+
+    // A server that doesn't fit in memory only starts when the user insists
+    if !force.unwrap_or(false) {
+        let capacity = capacity::check(&docker, &server).await;
+        if !capacity.fits() {
+            return Err(AppError::OverCommitted(capacity));
+        }
+    }
 
     // Check if we need to run install first
     if !server.installed {
@@ -203,31 +324,117 @@ pub async fn start_server(
             tracing::info!("Server needs installation, running install script first");
             server = run_install_script_internal(&server_id, &app, &state, &games_state).await?;
         } else {
-            server.installed = true;
-            save_server_config(&server)?;
+            server = state.configs.update(&server_id, |s| s.installed = true)?;
         }
     }
 
-    let container_id = server.container_id.clone().ok_or("No container ID")?;
+    let game = games_state.manager.lock().await.get_game(&server.game_type);
+    if let Some(game) = &game {
+        server = ensure_rcon(&state.configs, &games_state, server, game).await?;
+        let java = slp::Edition::of(&server) == Some(slp::Edition::Java);
+        if let Some((port, password)) = rcon::server_settings(&server, game).filter(|_| java) {
+            if let Err(e) = properties::enable_rcon(&server.data_path, port, &password) {
+                tracing::warn!("Failed to enable RCON for {}: {}", server.name, e);
+            }
+        }
+    }
+    let mut secret_env = match &game {
+        Some(game) => gslt::container_env(&server, game).await,
+        None => HashMap::new(),
+    };
+    if secret_env.values().any(String::is_empty) {
+        events::emit(&app, ServerEvent::alert(
+            &server.id,
+            AlertLevel::Warning,
+            "This game needs a Steam server token to be listed publicly - assign one in Settings",
+        ));
+    }
 
-    docker
-        .start_container(&container_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    // Values taken from other servers aren't saved - they're filled in again on every start
+    let linked = match &game {
+        Some(game) => {
+            let games_manager = games_state.manager.lock().await;
+            let resolved = references::resolve(&server, game, &load_all_server_configs(), &games_manager)
+                .map_err(AppError::InvalidInput)?;
+            secret_env.extend(resolved.env);
+            resolved.linked
+        }
+        None => Vec::new(),
+    };
+    secret_env.extend(database::container_env(&server).await?);
 
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    let palworld_outdated = match &game {
+        Some(game) if server.game_type.0 == palworld::PALWORLD_GAME => {
+            if let Err(e) = palworld::enable_rest_api(&server, game) {
+                tracing::warn!("Failed to enable the Palworld REST API for {}: {}", server.name, e);
+            }
+            palworld::outdated(&docker, &server, game).await
+        }
+        _ => false,
+    };
 
-    let status = docker
-        .get_container_status(&container_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    // Settings or the Steam token changed since the container was created - rebuild its env
+    // and startup command
+    if server.config_outdated || palworld_outdated || gslt::outdated(&docker, &server, &secret_env).await {
+        let game_config = game.clone().ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+        events::emit(&app, ServerEvent::log(&server_id, "[Serverwave] Applying changed settings..."));
+        lifecycle::recreate_container(&docker, &mut server, &game_config, &secret_env).await?;
+        server = save_container(&state.configs, &server)?;
+    }
+    server = attach_networks(&docker, &state.configs, server, &linked).await?;
 
-    if status == ServerStatus::Stopped || status == ServerStatus::Error {
-        return Err("Container failed to start".to_string());
+    if let Err(e) = lifecycle::start(&docker, &mut server).await {
+        // Something else holds one of the server's ports - say what, and move it when allowed
+        let Some(port) = port_conflicts::failed_port(&e) else { return Err(e.into()) };
+        let games_manager = games_state.manager.lock().await;
+        let game_config = match (&game, move_port.unwrap_or(false)) {
+            (Some(game), true) => game,
+            _ => return Err(port_conflicts::port_in_use(&docker, &games_manager, &server, game.as_ref(), port).await),
+        };
+        let _allocation = ports::ALLOCATION_LOCK.lock().await;
+        let free = port_conflicts::move_port(&docker, &games_manager, &mut server, game_config, port).await?;
+        drop(games_manager);
+        state.configs.update(&server_id, |s| {
+            s.port = server.port;
+            s.extra_ports = server.extra_ports.clone();
+            s.config = server.config.clone();
+        })?;
+        events::emit(&app, ServerEvent::log(
+            &server_id,
+            format!("[Serverwave] Port {} is taken - moving the server to port {}", port, free),
+        ));
+        lifecycle::recreate_container(&docker, &mut server, game_config, &secret_env).await?;
+        server = save_container(&state.configs, &server)?;
+        server = attach_networks(&docker, &state.configs, server, &linked).await?;
+        lifecycle::start(&docker, &mut server).await?;
+    }
+    server = state.configs.update(&server_id, |s| s.status = ServerStatus::Starting)?;
+    let container_id = server.container_id.clone().ok_or("No container ID")?;
+    events::emit(&app, ServerEvent::status(&server.id, server.status.clone()));
+
+    watch_start(&app, &state.configs, &server, game.clone());
+
+    // No build of the image for this host - it runs through emulation, if the host has any
+    let game_image = game.map(|g| g.docker_image);
+    let image = server.docker_image.clone().or(game_image).unwrap_or_default();
+    if let Some(platform) = platform::emulated(&image) {
+        events::emit(&app, ServerEvent::alert(
+            &server.id,
+            AlertLevel::Warning,
+            format!(
+                "{} has no build for this computer and runs as {} under emulation - expect it to be slower, \
+                 and to fail with \"exec format error\" where emulation isn't set up",
+                image, platform
+            ),
+        ));
     }
 
-    server.status = status;
-    save_server_config(&server)?;
+    if server.upnp_enabled {
+        let mappings = port_mappings_for(&server, &games_state).await;
+        forward_ports(&app, &server, mappings);
+    }
+
+    start_server_tunnel(&docker, &app, &server).await;
 
     start_log_stream(&server_id, &container_id, app, &state).await;
 
@@ -238,7 +445,63 @@ pub async fn start_server(
     })
 }
 
-async fn start_log_stream(
+/// Save the container a server was just given, keeping the rest of its latest config
+fn save_container(configs: &ServerConfigs, server: &Server) -> Result<Server, AppError> {
+    Ok(configs.update(&server.id, |s| {
+        s.container_id = server.container_id.clone();
+        s.config_outdated = server.config_outdated;
+    })?)
+}
+
+/// Mark a started server Running once it's ready, or say why it isn't
+fn watch_start(app: &AppHandle, configs: &Arc<ServerConfigs>, server: &Server, game: Option<GameConfig>) {
+    let app = app.clone();
+    let configs = configs.clone();
+    let server = server.clone();
+    tauri::async_runtime::spawn(async move {
+        let Ok(docker) = app.state::<DockerState>().manager() else { return };
+        let readiness = lifecycle::wait_until_ready(&docker, &server, game.as_ref()).await;
+        let status = match readiness {
+            Readiness::Ready | Readiness::TimedOut => ServerStatus::Running,
+            Readiness::Exited(_) => ServerStatus::Stopped,
+        };
+        // Stopped or restarted while it was starting - that start isn't ours to report
+        let mut ours = false;
+        let saved = configs.update(&server.id, |current| {
+            if current.status == ServerStatus::Starting && current.container_id == server.container_id {
+                current.status = status.clone();
+                ours = true;
+            }
+        });
+        if let Err(e) = saved {
+            tracing::warn!("Failed to save the status of {}: {}", server.id, e);
+        }
+        if !ours {
+            return;
+        }
+        events::emit(&app, ServerEvent::status(&server.id, status));
+
+        let timeout = game.map(|g| g.start_timeout_secs).unwrap_or(DEFAULT_START_TIMEOUT_SECS);
+        match readiness {
+            Readiness::Ready => {}
+            Readiness::TimedOut => events::emit(&app, ServerEvent::alert(
+                &server.id,
+                AlertLevel::Warning,
+                format!(
+                    "The server isn't answering on port {} after {}s - it may still be loading, or stuck",
+                    server.port, timeout
+                ),
+            )),
+            Readiness::Exited(exit_code) => events::emit(&app, ServerEvent::alert(
+                &server.id,
+                AlertLevel::Error,
+                format!("The server stopped while starting (exit code {}) - check the console", exit_code),
+            )),
+        }
+    });
+}
+
+pub(crate) async fn start_log_stream(
     server_id: &str,
     container_id: &str,
     app: AppHandle,
@@ -262,9 +525,41 @@ async fn start_log_stream(
     let server_id = server_id.to_string();
     let container_id = container_id.to_string();
 
+    let configs = state.configs.clone();
+    let probe = (server_id.clone(), container_id.clone(), app.clone());
     tokio::spawn(async move {
         stream_logs_loop(server_id, container_id, app, cancel_rx).await;
     });
+    tokio::spawn(async move {
+        let (server_id, container_id, app) = probe;
+        remember_console(&configs, &server_id, &container_id, &app).await;
+    });
+}
+
+const CONSOLE_PROBE_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Probe a container that wasn't yet for how commands reach its console, and keep the
+/// result on the server. Waits a little first, for a session the game is started in.
+async fn remember_console(configs: &ServerConfigs, server_id: &str, container_id: &str, app: &AppHandle) {
+    let probed = configs.get(server_id).ok().and_then(|s| s.console).is_some_and(|p| p.container_id == container_id);
+    if probed {
+        return;
+    }
+    tokio::time::sleep(CONSOLE_PROBE_DELAY).await;
+    let Ok(docker) = app.state::<DockerState>().manager() else { return };
+    if docker.get_container_status(container_id).await.ok() != Some(ServerStatus::Running) {
+        return;
+    }
+    let probe = console::probe(&docker, container_id).await;
+    tracing::info!("Console of {}: {:?}", server_id, probe.bridges);
+    let saved = configs.update(server_id, |server| {
+        if server.container_id.as_deref() == Some(container_id) {
+            server.console = Some(probe);
+        }
+    });
+    if let Err(e) = saved {
+        tracing::warn!("Failed to save the console probe of {}: {}", server_id, e);
+    }
 }
 
 async fn stream_logs_loop(
@@ -275,13 +570,18 @@ async fn stream_logs_loop(
 ) {
     let mut reconnect_attempts = 0;
     let max_reconnects = 10;
+    let mut seen_running = false;
+    // Kept across reconnects, which replay the last lines
+    let mut auth_watcher = install_auth::AuthWatcher::default();
+    let player_log = player_log_rules(&app, &server_id).await;
+    let output = events::LogBatch::new(&app, &server_id);
 
     loop {
         if *cancel_rx.borrow() {
             break;
         }
 
-        let docker = match DockerManager::new().await {
+        let docker = match app.state::<DockerState>().manager() {
             Ok(d) => d,
             Err(e) => {
                 tracing::error!("Docker connect failed: {}", e);
@@ -296,17 +596,24 @@ async fn stream_logs_loop(
 
         match docker.get_container_status(&container_id).await {
             Ok(status) if status != ServerStatus::Running && status != ServerStatus::Installing => {
+                if seen_running && !*cancel_rx.borrow() {
+                    report_exit(&docker, &app, &server_id, &container_id, status).await;
+                }
                 break;
             }
-            Err(_) => {}
-            _ => {}
+            Ok(_) => seen_running = true,
+            // Docker may have restarted - connect again on the next try
+            Err(_) => app.state::<DockerState>().reset(),
         }
+        // The tail can reach back into the container's last run - those players are gone
+        let started_at = container_started_at(&docker, &container_id).await;
 
         let options = LogsOptions::<String> {
             follow: true,
             stdout: true,
             stderr: true,
-            timestamps: false,
+            // Each entry's own time, for player sessions - cut off before the line goes out
+            timestamps: true,
             tail: "50".to_string(),
             ..Default::default()
         };
@@ -335,13 +642,14 @@ async fn stream_logs_loop(
                                 LogOutput::StdIn { message } => String::from_utf8_lossy(message).to_string(),
                             };
 
+                            let (logged_at, text) = split_log_time(&text);
                             for line in text.lines() {
                                 if !line.is_empty() {
-                                    let event = LogEvent {
-                                        server_id: server_id.clone(),
-                                        line: line.to_string(),
-                                    };
-                                    let _ = app.emit("server-log", event);
+                                    report_auth(&app, &server_id, auth_watcher.observe(line));
+                                    if let Some(rules) = player_log.as_ref().filter(|_| logged_at >= started_at) {
+                                        track_session(&server_id, rules, line, logged_at);
+                                    }
+                                    output.push(line);
                                 }
                             }
                         }
@@ -362,13 +670,112 @@ async fn stream_logs_loop(
     }
 }
 
+/// How the server's game reports players joining and leaving, if it does
+async fn player_log_rules(app: &AppHandle, server_id: &str) -> Option<PlayerLogRules> {
+    let server = load_server_config(server_id).ok()?;
+    let games_state = app.state::<GamesState>();
+    let game = games_state.manager.lock().await.get_game(&server.game_type)?;
+    game.player_log
+}
+
+async fn container_started_at(docker: &DockerManager, container_id: &str) -> DateTime<Utc> {
+    docker
+        .client()
+        .inspect_container(container_id, None)
+        .await
+        .ok()
+        .and_then(|info| info.state?.started_at)
+        .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// A log entry's Docker timestamp and the text after it - now, when it has none
+fn split_log_time(text: &str) -> (DateTime<Utc>, &str) {
+    text.split_once(' ')
+        .and_then(|(time, rest)| Some((DateTime::parse_from_rfc3339(time).ok()?.with_timezone(&Utc), rest)))
+        .unwrap_or((Utc::now(), text))
+}
+
+/// Record a join or leave the game's console reported
+fn track_session(server_id: &str, rules: &PlayerLogRules, line: &str, at: DateTime<Utc>) {
+    let result = match rules.parse(line) {
+        Some(PlayerLogEvent::Joined(player)) => sessions::record_join(server_id, &player, at),
+        Some(PlayerLogEvent::Left(player)) => sessions::record_leave(server_id, &player, at),
+        None => return,
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to record a player session for {}: {}", server_id, e);
+    }
+}
+
+/// Everyone on a server that stopped has left
+fn end_sessions(server_id: &str) {
+    if let Err(e) = sessions::end_sessions(server_id, Utc::now()) {
+        tracing::warn!("Failed to end player sessions for {}: {}", server_id, e);
+    }
+}
+
+/// Every stop we start cancels the stream first, so a stream that sees the container
+/// gone caught it stopping by itself - a crash unless it exited cleanly
+async fn report_exit(
+    docker: &DockerManager,
+    app: &AppHandle,
+    server_id: &str,
+    container_id: &str,
+    status: ServerStatus,
+) {
+    events::emit(app, ServerEvent::status(server_id, status));
+    end_sessions(server_id);
+    let exit_code = docker
+        .client()
+        .inspect_container(container_id, None)
+        .await
+        .ok()
+        .and_then(|info| info.state)
+        .and_then(|state| state.exit_code)
+        .unwrap_or(0);
+    if exit_code != 0 {
+        tracing::warn!("Server {} exited with code {}", server_id, exit_code);
+        events::emit(app, ServerEvent::Crashed {
+            server_id: server_id.to_string(),
+            exit_code,
+        });
+        crash_loop::report(server_id, container_id, exit_code);
+    }
+}
+
+/// Tell the frontend a login link showed up in the output, or that the login went through.
+/// Hytale's downloader asks during the install and its server on first start.
+fn report_auth(app: &AppHandle, server_id: &str, update: Option<AuthUpdate>) {
+    match update {
+        Some(AuthUpdate::Required(prompt)) => {
+            tracing::info!("Server {} is waiting for a sign-in at {}", server_id, prompt.url);
+            events::emit(app, ServerEvent::InstallAuthRequired {
+                server_id: server_id.to_string(),
+                url: prompt.url,
+                code: prompt.code,
+            });
+        }
+        Some(AuthUpdate::Completed) => {
+            events::emit(app, ServerEvent::InstallAuthCompleted {
+                server_id: server_id.to_string(),
+            });
+        }
+        None => {}
+    }
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn stop_server(
     server_id: String,
+    app: AppHandle,
     state: State<'_, ServerState>,
     games_state: State<'_, GamesState>,
-) -> Result<ServerResponse, String> {
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
     tracing::info!("Stopping server: {}", server_id);
+    crash_loop::forget(&server_id);
 
     {
         let mut streams = state.streams.lock().await;
@@ -377,26 +784,35 @@ pub async fn stop_server(
         }
     }
 
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let mut server = load_server_config(&server_id)?;
+    let docker = docker_state.manager()?;
+    let mut server = state.configs.get(&server_id)?;
 
-    if let Some(container_id) = &server.container_id {
-        let games_manager = games_state.manager.lock().await;
-        if let Some(game_config) = games_manager.get_game(&server.game_type) {
-            if !game_config.stop_command.is_empty() {
-                tracing::info!("Sending stop command: {}", game_config.stop_command);
-                let _ = docker.send_stdin(container_id, &game_config.stop_command).await;
-                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-            }
+    if server.container_id.is_some() {
+        let game_config = {
+            let games_manager = games_state.manager.lock().await;
+            games_manager.get_game(&server.game_type)
+        };
+
+        tunnel::stop_tunnel(&docker, &server_id).await;
+
+        let outcome = lifecycle::stop(&docker, &mut server, game_config.as_ref()).await?;
+        server = state.configs.update(&server_id, |s| s.status = ServerStatus::Stopped)?;
+        if outcome == StopOutcome::Killed {
+            events::emit(&app, ServerEvent::alert(
+                &server.id,
+                AlertLevel::Warning,
+                "The server didn't shut down in time and was killed - anything unsaved is lost",
+            ));
+        }
+        events::emit(&app, ServerEvent::status(&server.id, server.status.clone()));
+        end_sessions(&server_id);
+        if let Err(e) = database::stop(&docker, &server).await {
+            tracing::warn!("Failed to stop the database of {}: {}", server.name, e);
+        }
+
+        if server.upnp_enabled {
+            unforward_ports(port_mappings_for(&server, &games_state).await);
         }
-        drop(games_manager);
-        
-        docker
-            .stop_container(container_id)
-            .await
-            .map_err(|e| e.to_string())?;
-        server.status = ServerStatus::Stopped;
-        save_server_config(&server)?;
     }
 
     Ok(ServerResponse {
@@ -406,13 +822,34 @@ pub async fn stop_server(
     })
 }
 
+/// Delete a server. `confirm_name` must be the server's name, so a stray call can't delete
+/// the wrong one. Its data is deleted, or with `trash` moved to the trash folder, unless
+/// `delete_data` is false. A tombstone goes in the audit journal either way.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn delete_server(
     server_id: String,
+    confirm_name: String,
     delete_data: Option<bool>,
+    trash: Option<bool>,
     state: State<'_, ServerState>,
-) -> Result<ServerResponse, String> {
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
     tracing::info!("Deleting server: {}", server_id);
+    crate::security::ensure_unlocked().await?;
+
+    let server = state.configs.get(&server_id)?;
+    if confirm_name.trim() != server.name.trim() {
+        return Err(AppError::InvalidInput(format!("Type \"{}\" to delete this server", server.name)));
+    }
+    // A folder imported in place from outside the data directory belongs to the user
+    let owned = server.data_path.starts_with(serverwave_core::paths::servers_dir());
+    let fate = match (delete_data.unwrap_or(true), trash.unwrap_or(false)) {
+        (true, _) if !owned => DataFate::Kept,
+        (true, true) => DataFate::Trashed,
+        (true, false) => DataFate::Deleted,
+        (false, _) => DataFate::Kept,
+    };
 
     {
         let mut streams = state.streams.lock().await;
@@ -421,27 +858,34 @@ pub async fn delete_server(
         }
     }
 
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let server = load_server_config(&server_id)?;
+    let docker = docker_state.manager()?;
+    tunnel::stop_tunnel(&docker, &server_id).await;
 
-    if let Some(container_id) = &server.container_id {
-        docker.stop_container(container_id).await.ok();
-        docker.remove_container(container_id).await.ok();
-    }
-    
-    // Also remove install container if it exists
-    if let Some(install_container_id) = &server.install_container_id {
-        docker.remove_install_container(install_container_id).await.ok();
+    if server.upnp_enabled {
+        unforward_ports(port_mappings_for(&server, &games_state).await);
     }
 
-    let config_path = get_server_config_path(&server_id);
-    std::fs::remove_file(config_path).ok();
-
-    if delete_data.unwrap_or(true) {
-        if server.data_path.exists() {
-            std::fs::remove_dir_all(&server.data_path).ok();
-        }
+    let data_path = server.data_path.clone();
+    let data_bytes = tokio::task::spawn_blocking(move || calculate_dir_size(&data_path).unwrap_or(0)).await?;
+    database::remove(&docker, &server, fate == DataFate::Deleted).await?;
+    lifecycle::remove(&docker, &server, fate == DataFate::Deleted).await?;
+    // Moved once the containers are gone, so nothing is still writing to it
+    let trash_path = match fate {
+        DataFate::Trashed => Some(audit::move_to_trash(&server, &database::get_data_dir(&server))?),
+        _ => None,
+    };
+    state.configs.remove(&server_id)?;
+    let tombstone = Tombstone::new(&server, data_bytes, fate, trash_path.as_deref());
+    if let Err(e) = audit::record(AuditEntry::ServerDeleted(tombstone)) {
+        tracing::warn!("Failed to write the audit journal: {}", e);
     }
+    updates::forget(&server_id);
+    images::forget(&server_id);
+    wipes::forget(&server_id);
+    scheduler::forget(&server_id);
+    gslt::forget(&server_id);
+    tunnel::delete_token(&server_id).await;
+    backup_targets::forget(&server).await;
 
     Ok(ServerResponse {
         success: true,
@@ -451,130 +895,144 @@ pub async fn delete_server(
 }
 
 #[tauri::command]
-pub async fn list_servers() -> Result<Vec<Server>, String> {
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let config_dir = get_servers_config_dir();
-
-    if !config_dir.exists() {
-        return Ok(Vec::new());
-    }
-
-    let mut servers = Vec::new();
+pub async fn list_servers(docker_state: State<'_, DockerState>) -> Result<Vec<Server>, AppError> {
+    let docker = docker_state.manager()?;
 
-    for entry in std::fs::read_dir(config_dir).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-
-        if path.extension().map(|e| e == "json").unwrap_or(false) {
-            let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-            let mut server: Server = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    // Newest first. Remote users only see the servers they were given.
+    let servers: Vec<Server> = load_all_server_configs()
+        .into_iter()
+        .filter(|server| can_access(Permission::View, Some(&server.id)))
+        .collect();
 
+    // Inspect all containers at once - one at a time is slow over a remote Docker socket
+    let servers = futures_util::future::join_all(servers.into_iter().map(|mut server| {
+        let docker = &docker;
+        async move {
             // Don't overwrite Installing status - it's managed by the install process
             if server.status != ServerStatus::Installing {
                 if let Some(container_id) = &server.container_id {
-                    server.status = docker
+                    let status = docker
                         .get_container_status(container_id)
                         .await
                         .unwrap_or(ServerStatus::Error);
+                    // A running container is still Starting until it's ready
+                    if !(status == ServerStatus::Running && server.status == ServerStatus::Starting) {
+                        server.status = lifecycle::kept_status(&server, status);
+                    }
                 }
             }
-
-            servers.push(server);
+            server
         }
-    }
+    }))
+    .await;
 
-    servers.sort_by(|a, b| b.created_at.cmp(&a.created_at));
     Ok(servers)
 }
 
+#[derive(Debug, Serialize)]
+pub struct ServerStatusReport {
+    pub status: ServerStatus,
+    /// What a running Minecraft server answers a server list ping with - None for other
+    /// games, and while it isn't answering yet
+    pub ping: Option<ServerPing>,
+}
+
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_server_status(server_id: String) -> Result<ServerStatus, String> {
+pub async fn get_server_status(server_id: String, docker_state: State<'_, DockerState>) -> Result<ServerStatusReport, AppError> {
     let server = load_server_config(&server_id)?;
-    
-    // Don't overwrite Installing status
-    if server.status == ServerStatus::Installing {
-        return Ok(ServerStatus::Installing);
-    }
-    
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-
-    if let Some(container_id) = &server.container_id {
-        docker
-            .get_container_status(container_id)
-            .await
-            .map_err(|e| e.to_string())
-    } else {
-        Ok(ServerStatus::Stopped)
-    }
+    let docker = docker_state.manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    let ping = match slp::Edition::of(&server) {
+        Some(edition) if status == ServerStatus::Running => {
+            let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, server.port));
+            match slp::ping(edition, addr).await {
+                Ok(ping) => Some(ping),
+                Err(e) => {
+                    tracing::debug!("Minecraft ping of {} failed: {}", server_id, e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+    Ok(ServerStatusReport { status, ping })
 }
 
 #[tauri::command(rename_all = "camelCase")]
 pub async fn send_command(
     server_id: String,
     command: String,
-) -> Result<String, String> {
-    tracing::info!("Sending command to {}: {}", server_id, command);
-
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<String, AppError> {
+    // RCON answers with the command's output; the console is the fallback while it's down.
+    // Once the command went out over RCON it isn't sent again, or a slow reply would run it twice.
     let server = load_server_config(&server_id)?;
-    let container_id = server.container_id.ok_or("No container ID")?;
-
-    if docker.send_stdin(&container_id, &command).await.is_ok() {
-        return Ok("Command sent".to_string());
+    let game = games_state.manager.lock().await.get_game(&server.game_type);
+    if let Some(game) = game.filter(|game| rcon::server_settings(&server, game).is_some()) {
+        match rcon::connect(&server, &game).await {
+            Ok(mut client) => return Ok(client.exec(&command).await?),
+            Err(e) => tracing::debug!("RCON to {} failed, using the console: {}", server_id, e),
+        }
     }
+    console_command(&docker_state.manager()?, &server_id, &command).await
+}
 
-    send_via_mc_console(&docker, &container_id, &command).await
+/// Run a command over the server's RCON, for games that take it
+#[tauri::command(rename_all = "camelCase")]
+pub async fn send_rcon_command(
+    server_id: String,
+    command: String,
+    games_state: State<'_, GamesState>,
+) -> Result<String, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game = games_state
+        .manager
+        .lock()
+        .await
+        .get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+    if game.rcon.is_none() {
+        return Err(AppError::InvalidInput(format!("{} doesn't take RCON", game.name)));
+    }
+    Ok(rcon::send(&server, &game, &command).await?)
 }
 
-async fn send_via_mc_console(
-    docker: &DockerManager,
-    container_id: &str,
-    command: &str,
-) -> Result<String, String> {
-    let exec_cmd = vec!["mc-send-to-console".to_string(), command.to_string()];
-    
-    let exec_options = CreateExecOptions {
-        cmd: Some(exec_cmd),
-        attach_stdout: Some(true),
-        attach_stderr: Some(true),
-        ..Default::default()
+/// Type a command into the server's console, for callers that have a Docker manager already.
+/// Each of the container's console bridges is tried in turn until one takes it.
+pub async fn console_command(docker: &DockerManager, server_id: &str, command: &str) -> Result<String, AppError> {
+    tracing::info!("Sending command to {}: {}", server_id, command);
+
+    let server = load_server_config(server_id)?;
+    let container_id = server.container_id.ok_or("No container ID")?;
+    let bridges = match server.console.filter(|probe| probe.container_id == container_id) {
+        Some(probe) => probe.bridges,
+        None => console::probe(docker, &container_id).await.bridges,
     };
 
-    let exec = docker
-        .client()
-        .create_exec(container_id, exec_options)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let mut output = String::new();
-    match docker.client().start_exec(&exec.id, None).await {
-        Ok(StartExecResults::Attached { output: mut out, .. }) => {
-            while let Some(Ok(msg)) = out.next().await {
-                let text = match msg {
-                    LogOutput::StdOut { message } => String::from_utf8_lossy(&message).to_string(),
-                    LogOutput::StdErr { message } => String::from_utf8_lossy(&message).to_string(),
-                    _ => String::new(),
-                };
-                output.push_str(&text);
-            }
+    let mut failures = Vec::new();
+    for bridge in &bridges {
+        match console::send(docker, &container_id, bridge, command).await {
+            Ok(output) => return Ok(output),
+            Err(e) => failures.push(format!("{}: {}", bridge, e)),
         }
-        Ok(StartExecResults::Detached) => {}
-        Err(e) => return Err(e.to_string()),
     }
-    
-    Ok(output)
+    if failures.is_empty() {
+        return Err("This server's container has no console to send commands to".into());
+    }
+    Err(format!("Couldn't reach the console ({})", failures.join("; ")).into())
 }
 
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_server_stats(server_id: String) -> Result<crate::docker::ContainerStats, String> {
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
+pub async fn get_server_stats(server_id: String, docker_state: State<'_, DockerState>) -> Result<crate::docker::ContainerStats, AppError> {
+    let docker = docker_state.manager()?;
     let server = load_server_config(&server_id)?;
 
     if let Some(container_id) = &server.container_id {
         docker
             .get_container_stats(container_id)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(AppError::from)
     } else {
         Ok(crate::docker::ContainerStats {
             cpu_percent: 0.0,
@@ -585,10 +1043,36 @@ pub async fn get_server_stats(server_id: String) -> Result<crate::docker::Contai
     }
 }
 
+/// List processes inside the server container (SteamCMD, wine, game binary, ...)
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_server_logs(server_id: String, lines: Option<u32>) -> Result<LogsResponse, String> {
+pub async fn get_server_processes(
+    server_id: String,
+    docker_state: State<'_, DockerState>,
+) -> Result<Vec<crate::docker::ContainerProcess>, AppError> {
+    let docker = docker_state.manager()?;
     let server = load_server_config(&server_id)?;
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
+
+    let container_id = match &server.container_id {
+        Some(id) => id.clone(),
+        None => return Ok(Vec::new()),
+    };
+
+    // docker top only works on running containers
+    let status = docker.get_container_status(&container_id).await?;
+    if status != ServerStatus::Running {
+        return Ok(Vec::new());
+    }
+
+    docker
+        .get_container_processes(&container_id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_server_logs(server_id: String, lines: Option<u32>, docker_state: State<'_, DockerState>) -> Result<LogsResponse, AppError> {
+    let server = load_server_config(&server_id)?;
+    let docker = docker_state.manager()?;
     
     tracing::info!("get_server_logs: server status = {:?}, install_container_id = {:?}", 
         server.status, server.install_container_id);
@@ -617,8 +1101,7 @@ pub async fn get_server_logs(server_id: String, lines: Option<u32>) -> Result<Lo
     if let Some(container_id) = &server.container_id {
         let logs = docker
             .get_logs(container_id, lines.unwrap_or(500))
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
 
         Ok(LogsResponse { logs, error: None })
     } else {
@@ -634,9 +1117,10 @@ pub async fn attach_server(
     server_id: String,
     app: AppHandle,
     state: State<'_, ServerState>,
-) -> Result<(), String> {
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let server = load_server_config(&server_id)?;
+    docker_state: State<'_, DockerState>,
+) -> Result<(), AppError> {
+    let docker = docker_state.manager()?;
+    let server = state.configs.get(&server_id)?;
 
     // Don't attach if server is installing - the install logs are emitted separately
     if server.status == ServerStatus::Installing {
@@ -649,7 +1133,7 @@ pub async fn attach_server(
         None => return Ok(()),
     };
 
-    let status = docker.get_container_status(&container_id).await.map_err(|e| e.to_string())?;
+    let status = docker.get_container_status(&container_id).await?;
     
     if status != ServerStatus::Running {
         return Ok(());
@@ -660,7 +1144,7 @@ pub async fn attach_server(
 }
 
 #[tauri::command(rename_all = "camelCase")]
-pub async fn detach_server(server_id: String, state: State<'_, ServerState>) -> Result<(), String> {
+pub async fn detach_server(server_id: String, state: State<'_, ServerState>) -> Result<(), AppError> {
     let mut streams = state.streams.lock().await;
     if let Some(handle) = streams.remove(&server_id) {
         let _ = handle.cancel_tx.send(true);
@@ -672,11 +1156,22 @@ pub async fn detach_server(server_id: String, state: State<'_, ServerState>) ->
 pub async fn update_server_config(
     server_id: String,
     config: HashMap<String, String>,
-) -> Result<ServerResponse, String> {
-    let mut server = load_server_config(&server_id)?;
-    server.config = config;
-    save_server_config(&server)?;
-    
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    let game_type = state.configs.get(&server_id)?.game_type;
+    let game = games_state.manager.lock().await.get_game(&game_type);
+    let server = state.configs.update(&server_id, |server| {
+        if server.config != config && server.container_id.is_some() {
+            server.config_outdated = true;
+        }
+        // A different image variant, or Minecraft version that needs another Java
+        if let Some(game) = &game {
+            server.docker_image = game.select_image(&config);
+        }
+        server.config = config;
+    })?;
+
     Ok(ServerResponse {
         success: true,
         server: Some(server),
@@ -684,8 +1179,235 @@ pub async fn update_server_config(
     })
 }
 
+/// Replace the server's own environment variables. Like config changes they reach the
+/// container when it's next created.
 #[tauri::command(rename_all = "camelCase")]
-pub async fn get_server_disk_usage(server_id: String) -> Result<u64, String> {
+pub async fn update_server_env(
+    server_id: String,
+    env: Vec<EnvVar>,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    let game_type = state.configs.get(&server_id)?.game_type;
+    let game_config = games_state.manager.lock().await.get_game(&game_type)
+        .ok_or_else(|| AppError::GameNotFound(game_type.to_string()))?;
+    let env: Vec<EnvVar> = env
+        .into_iter()
+        .map(|var| EnvVar { key: var.key.trim().to_string(), value: var.value })
+        .collect();
+
+    let server = state.configs.try_update(&server_id, |server| {
+        lifecycle::validate_custom_env(&game_config, &lifecycle::extra_ports(server, Some(&game_config)), &env)
+            .map_err(AppError::InvalidInput)?;
+        if server.env != env && server.container_id.is_some() {
+            server.config_outdated = true;
+        }
+        server.env = env;
+        Ok::<_, AppError>(())
+    })?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Set when automation may touch the server, or None to follow the global policy
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_server_maintenance(
+    server_id: String,
+    maintenance: Option<MaintenancePolicy>,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    if let Some(policy) = &maintenance {
+        policy.validate().map_err(AppError::InvalidInput)?;
+    }
+    let server = state.configs.update(&server_id, |s| s.maintenance = maintenance)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Set where the server's install scripts may connect, or None to follow its game
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_server_install_network(
+    server_id: String,
+    install_network: Option<InstallNetwork>,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    let install_network = install_network
+        .map(|network| network.validate())
+        .transpose()
+        .map_err(AppError::InvalidInput)?;
+    let server = state.configs.update(&server_id, |s| s.install_network = install_network)?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Turn restarting the server after a crash on or off
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_server_auto_restart(
+    server_id: String,
+    enabled: bool,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    let server = state.configs.update(&server_id, |s| s.auto_restart = enabled)?;
+    if !enabled {
+        crash_loop::forget(&server_id);
+    }
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Start the server at a time of day, waking the Docker host first when it has Wake-on-LAN -
+/// or stop doing so when None
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_start_schedule(
+    server_id: String,
+    schedule: Option<StartSchedule>,
+    state: State<'_, ServerState>,
+) -> Result<ServerResponse, AppError> {
+    if let Some(schedule) = &schedule {
+        schedule.validate().map_err(AppError::InvalidInput)?;
+    }
+    let server = state.configs.update(&server_id, |s| {
+        let last_run = s.start_schedule.as_ref().and_then(|old| old.last_run);
+        s.start_schedule = schedule.map(|mut schedule| {
+            schedule.last_run = last_run;
+            schedule.next_run = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+            schedule
+        });
+    })?;
+
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// What the server was doing when it was last given up on for crashing over and over
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_crash_report(server_id: String) -> Result<Option<CrashReport>, AppError> {
+    load_server_config(&server_id)?;
+    Ok(crash_loop::load_report(&server_id))
+}
+
+/// Recreate the server's container so changed settings take effect now instead of on the
+/// next start. A running server is restarted; its data folder isn't touched.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn apply_config(
+    server_id: String,
+    app: AppHandle,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
+    let docker = docker_state.manager()?;
+    let server = state.configs.get(&server_id)?;
+    if server.status == ServerStatus::Installing {
+        return Err(AppError::InvalidInput("The server is installing - wait for it to finish".to_string()));
+    }
+    let game_config = games_state.manager.lock().await.get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+
+    let was_running = lifecycle::current_status(&docker, &server).await? == ServerStatus::Running;
+    if was_running {
+        events::emit(&app, ServerEvent::log(&server_id, "[Serverwave] Restarting to apply the new settings..."));
+        stop_server(server_id.clone(), app.clone(), state.clone(), games_state.clone(), docker_state.clone()).await?;
+    }
+
+    let mut server = state.configs.get(&server_id)?;
+    let secret_env = gslt::container_env(&server, &game_config).await;
+    lifecycle::recreate_container(&docker, &mut server, &game_config, &secret_env).await?;
+    let server = save_container(&state.configs, &server)?;
+    events::emit(&app, ServerEvent::log(
+        &server_id,
+        format!("[Serverwave] Recreated the container from {}", lifecycle::image(&server, &game_config)),
+    ));
+
+    if was_running {
+        // It was running a moment ago, so it fits
+        return start_server(server_id, Some(true), None, app, state, games_state, docker_state).await;
+    }
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// The startup command, environment and ports the server's container gets from its current
+/// settings, to check what the variables turn into before starting
+#[tauri::command(rename_all = "camelCase")]
+pub async fn preview_startup(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+) -> Result<lifecycle::StartupPreview, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game_config = games_state.manager.lock().await.get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+    let mut preview = lifecycle::preview(&server, &game_config);
+
+    let resolved = {
+        let games_manager = games_state.manager.lock().await;
+        references::resolve(&server, &game_config, &load_all_server_configs(), &games_manager)
+    };
+    match resolved {
+        Ok(resolved) if !resolved.env.is_empty() => {
+            preview.env.extend(resolved.env);
+            let env: HashMap<String, String> = preview.env.clone().into_iter().collect();
+            preview.startup = lifecycle::resolve_startup_command(&game_config, &env);
+            preview.command = preview
+                .startup
+                .as_deref()
+                .and_then(|s| container_command(&game_config.volume_path, s));
+        }
+        Ok(_) => {}
+        Err(e) => preview.warnings.push(e),
+    }
+    Ok(preview)
+}
+
+/// What the server's variables would change in each of the game's config files, as unified
+/// diffs - the files aren't touched
+#[tauri::command(rename_all = "camelCase")]
+pub async fn preview_config_changes(
+    server_id: String,
+    games_state: State<'_, GamesState>,
+) -> Result<Vec<ConfigFileDiff>, AppError> {
+    let server = load_server_config(&server_id)?;
+    let game_config = games_state.manager.lock().await.get_game(&server.game_type)
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
+    let env = lifecycle::container_env(
+        &game_config,
+        server.memory_mb,
+        server.port,
+        &server.config,
+        &server.env,
+        &lifecycle::extra_ports(&server, Some(&game_config)),
+    );
+    let config_files = game_config.config_files.clone();
+    Ok(tokio::task::spawn_blocking(move || {
+        preview_config_variables(&server.data_path, &config_files, &env)
+    })
+    .await?)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_server_disk_usage(server_id: String) -> Result<u64, AppError> {
     let server = load_server_config(&server_id)?;
     
     if !server.data_path.exists() {
@@ -695,153 +1417,325 @@ pub async fn get_server_disk_usage(server_id: String) -> Result<u64, String> {
     Ok(calculate_dir_size(&server.data_path).unwrap_or(0))
 }
 
+lazy_static::lazy_static! {
+    /// Install scripts running right now, capped by the max_concurrent_installs setting
+    static ref ACTIVE_INSTALLS: std::sync::Mutex<usize> = std::sync::Mutex::new(0);
+    static ref INSTALL_FINISHED: tokio::sync::Notify = tokio::sync::Notify::new();
+}
+
+/// Held while an install script runs - frees the slot when dropped
+struct InstallSlot;
+
+impl Drop for InstallSlot {
+    fn drop(&mut self) {
+        *ACTIVE_INSTALLS.lock().unwrap() -= 1;
+        INSTALL_FINISHED.notify_waiters();
+    }
+}
+
+/// Wait until fewer than max_concurrent_installs installs are running
+async fn acquire_install_slot(app: &AppHandle, server_id: &str) -> InstallSlot {
+    let mut announced = false;
+    loop {
+        let finished = INSTALL_FINISHED.notified();
+        {
+            let mut active = ACTIVE_INSTALLS.lock().unwrap();
+            if *active < load_app_settings().max_concurrent_installs {
+                *active += 1;
+                return InstallSlot;
+            }
+        }
+        if !announced {
+            announced = true;
+            events::emit(app, ServerEvent::install(
+                server_id,
+                InstallStage::Queued,
+                "Waiting for other installs to finish...",
+            ));
+        }
+        finished.await;
+    }
+}
+
+/// Installs running, queued and failed across all servers
+#[tauri::command]
+pub async fn get_install_queue() -> Result<Vec<InstallActivity>, AppError> {
+    Ok(install_queue::snapshot()
+        .into_iter()
+        .filter(|activity| can_access(Permission::View, Some(&activity.server_id)))
+        .collect())
+}
+
 // Internal function for running install script
 async fn run_install_script_internal(
     server_id: &str,
     app: &AppHandle,
-    _state: &State<'_, ServerState>,
+    state: &State<'_, ServerState>,
     games_state: &State<'_, GamesState>,
-) -> Result<Server, String> {
+) -> Result<Server, AppError> {
     tracing::info!("Running install script for server: {}", server_id);
     
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let mut server = load_server_config(server_id)?;
+    let docker = app.state::<DockerState>().manager()?;
+    let server = state.configs.get(server_id)?;
     
     let games_manager = games_state.manager.lock().await;
     let game_config = games_manager.get_game(&server.game_type)
-        .ok_or_else(|| format!("Game type '{}' not found", server.game_type))?;
+        .ok_or_else(|| AppError::GameNotFound(server.game_type.to_string()))?;
     
     let install_script = match &game_config.install_script {
         Some(script) if !script.is_empty() => script.clone(),
         _ => {
             tracing::info!("No install script for game type: {}", server.game_type);
-            server.installed = true;
-            save_server_config(&server)?;
-            return Ok(server);
+            return Ok(state.configs.update(server_id, |s| s.installed = true)?);
         }
     };
     let volume_path = game_config.volume_path.clone();
     let install_image = game_config.install_image.clone()
+        .or_else(|| server.docker_image.clone())
         .unwrap_or_else(|| game_config.docker_image.clone());
+    // Scripts see the same variables the game does, so they install the chosen version
+    let game_env = lifecycle::container_env(
+        &game_config,
+        server.memory_mb,
+        server.port,
+        &server.config,
+        &server.env,
+        &lifecycle::extra_ports(&server, Some(&game_config)),
+    );
+    let install_network = egress::install_network(&server, &game_config)
+        .validate()
+        .map_err(AppError::InvalidInput)?;
     drop(games_manager);
+    disk_guard::ensure_room(disk_guard::Activity::Install).await?;
     
     // Set installing status
-    server.status = ServerStatus::Installing;
-    save_server_config(&server)?;
+    let server = state.configs.update(server_id, |s| s.status = ServerStatus::Installing)?;
+    events::emit(app, ServerEvent::status(server_id, ServerStatus::Installing));
     
-    let _ = app.emit("server-log", LogEvent {
-        server_id: server_id.to_string(),
-        line: "[Serverwave] Starting installation...".to_string(),
-    });
+    let _tracked = install_queue::track(&server);
+    let _install_slot = acquire_install_slot(app, server_id).await;
+    install_queue::started(server_id);
+
+    events::emit(app, ServerEvent::install(server_id, InstallStage::Started, "Starting installation..."));
+
+    // Scripts that log in to Steam get the stored account and stdin for Steam Guard codes
+    let mut script_options = ScriptOptions {
+        env: game_env.iter().map(|(key, value)| format!("{}={}", key, value)).collect(),
+        ..Default::default()
+    };
+    if steam::wanted_by(&install_script) {
+        match steam::install_env().await {
+            Some(env) => {
+                script_options.env.extend(env);
+                script_options.stdin = true;
+            }
+            None => events::emit(
+                app,
+                ServerEvent::alert(
+                    server_id,
+                    AlertLevel::Warning,
+                    "This game logs in to Steam but no Steam account is set - add one in Settings",
+                ),
+            ),
+        }
+    }
+
+    // After a failed attempt the data is kept and the script is told what already finished
+    let previous = server.install_state.clone();
+    if let Some(previous) = &previous {
+        script_options.env.extend(previous.resume_env());
+        let from = previous.failed_step.as_deref().unwrap_or("the start");
+        events::emit(app, ServerEvent::log(
+            server_id,
+            format!("[Serverwave] Resuming the failed install (attempt {}) - it stopped at: {}", previous.attempts + 1, from),
+        ));
+    }
+    let restriction = match egress::prepare(&docker, &server, &install_network).await {
+        Ok(restriction) => restriction,
+        Err(e) => return Err(abort_install(app, state, server_id, e)),
+    };
+    if let Some(restriction) = &restriction {
+        script_options.network = Some(restriction.network.clone());
+        script_options.env.extend(restriction.env.iter().cloned());
+        let reach = match &install_network {
+            InstallNetwork::Restricted { allowed_domains } if !allowed_domains.is_empty() => allowed_domains.join(", "),
+            _ => "nowhere - it runs offline".to_string(),
+        };
+        events::emit(app, ServerEvent::log(server_id, format!("[Serverwave] This install can only reach {}", reach)));
+        if steam_cache::wanted_by(&install_script) {
+            events::emit(
+                app,
+                ServerEvent::alert(
+                    server_id,
+                    AlertLevel::Warning,
+                    "SteamCMD doesn't go through the install proxy, so its downloads fail while installs are restricted",
+                ),
+            );
+        }
+    }
+    let progress = Arc::new(std::sync::Mutex::new(previous.unwrap_or_default()));
+    let progress_clone = progress.clone();
+
+    // A pinned server gets its pinned build rather than the latest
+    if let Some(pinned) = &server.pinned_version {
+        script_options.env.extend(updates::target_env(pinned));
+        events::emit(app, ServerEvent::log(
+            server_id,
+            format!("[Serverwave] Installing the pinned {}", pinned.label()),
+        ));
+    }
+    let report = Arc::new(std::sync::Mutex::new(updates::VersionReport::default()));
+    let report_clone = report.clone();
     
     // Run install script using docker run (temporary container)
     // This avoids issues with the main container's startup command failing
     let app_clone = app.clone();
     let server_id_clone = server_id.to_string();
-    let opened_urls: std::sync::Arc<std::sync::Mutex<std::collections::HashSet<String>>> = 
-        std::sync::Arc::new(std::sync::Mutex::new(std::collections::HashSet::new()));
-    let opened_urls_clone = opened_urls.clone();
+    let mut auth_watcher = install_auth::AuthWatcher::default();
+    let output = events::LogBatch::new(app, server_id);
     
     // Callback to save install container ID for log recovery
     let server_id_for_callback = server_id.to_string();
+    let configs = state.configs.clone();
     let on_container_created = move |container_id: &str| {
-        if let Ok(mut srv) = load_server_config(&server_id_for_callback) {
-            srv.install_container_id = Some(container_id.to_string());
-            let _ = save_server_config(&srv);
+        if configs.update(&server_id_for_callback, |s| s.install_container_id = Some(container_id.to_string())).is_ok() {
             tracing::info!("Saved install container ID: {}", container_id);
         }
     };
     
-    let (exit_code, install_container_id) = docker.run_script(
+    let result = docker.run_script(
         &install_image,
         &server.data_path,
         &volume_path,
         &install_script,
+        script_options,
         on_container_created,
         move |line| {
             tracing::info!("[Install] {}", line);
-            
-            // Check for OAuth URLs and open them in browser (only once per URL)
-            if line.contains("https://") {
-                tracing::info!("[Install] Found line with URL: {}", line);
-                for word in line.split_whitespace() {
-                    if word.starts_with("https://") {
-                        tracing::info!("[Install] Checking URL: {}", word);
-                        if word.contains("oauth") || word.contains("auth") || 
-                           word.contains("login") || word.contains("verify") ||
-                           word.contains("device") {
-                            let url = word.trim_matches(|c| c == '"' || c == '\'' || c == '<' || c == '>').to_string();
-                            
-                            // Only open if we haven't opened this URL before
-                            let mut opened = opened_urls_clone.lock().unwrap();
-                            if !opened.contains(&url) {
-                                tracing::info!("[Install] Opening OAuth URL in browser: {}", url);
-                                // Use platform-specific command to open URL
-                                #[cfg(target_os = "windows")]
-                                let result = std::process::Command::new("cmd")
-                                    .args(["/c", "start", "", &url])
-                                    .spawn();
-                                #[cfg(target_os = "macos")]
-                                let result = std::process::Command::new("open")
-                                    .arg(&url)
-                                    .spawn();
-                                #[cfg(target_os = "linux")]
-                                let result = std::process::Command::new("xdg-open")
-                                    .arg(&url)
-                                    .spawn();
-                                
-                                match result {
-                                    Ok(_) => tracing::info!("[Install] Browser opened successfully"),
-                                    Err(e) => tracing::error!("[Install] Failed to open browser: {}", e),
-                                }
-                                opened.insert(url);
-                            } else {
-                                tracing::info!("[Install] URL already opened, skipping: {}", url);
-                            }
-                        }
-                    }
-                }
+
+            if let Ok(mut progress) = progress_clone.lock() {
+                progress.observe(&line);
             }
-            
-            let _ = app_clone.emit("server-log", LogEvent {
-                server_id: server_id_clone.clone(),
-                line,
-            });
+            if let Ok(mut report) = report_clone.lock() {
+                report.observe(&line);
+            }
+            install_queue::observe(&server_id_clone, &line);
+
+            let guard_prompt = steam::is_guard_prompt(&line);
+            let auth = auth_watcher.observe(&line);
+            output.push(line.clone());
+            // The prompt is in the console before anyone is asked to answer it
+            if guard_prompt || auth.is_some() {
+                output.flush();
+            }
+            if guard_prompt {
+                events::emit(&app_clone, ServerEvent::steam_guard(&server_id_clone, &line));
+            }
+            report_auth(&app_clone, &server_id_clone, auth);
         },
-    ).await.map_err(|e| e.to_string())?;
+    ).await;
+    let (exit_code, install_container_id) = match result {
+        Ok(finished) => finished,
+        Err(e) => {
+            if restriction.is_some() {
+                egress::finish(&docker, server_id).await;
+            }
+            return Err(abort_install(app, state, server_id, e.into()));
+        }
+    };
     
     // Clean up install container
     docker.remove_install_container(&install_container_id).await.ok();
+    if restriction.is_some() {
+        for domain in egress::finish(&docker, server_id).await {
+            events::emit(app, ServerEvent::log(
+                server_id,
+                format!("[Serverwave] The install tried to reach {}, which isn't on its allowed domains", domain),
+            ));
+        }
+    }
+
+    // A script can exit cleanly without putting the game in place - a download that failed
+    // quietly, a wrong version. Say what's missing now rather than at the first start.
+    let missing = match exit_code {
+        0 => artifacts::missing(&server, &game_config),
+        _ => Vec::new(),
+    };
     
-    // Reload and update server status
-    let mut server = load_server_config(server_id)?;
-    
-    if exit_code == 0 {
-        server.installed = true;
-        server.status = ServerStatus::Stopped;
-        server.install_container_id = None;
-        save_server_config(&server)?;
+    if exit_code == 0 && missing.is_empty() {
+        let report = report.lock().map(|r| r.clone()).unwrap_or_default();
+        let version = updates::installed_version(&server.data_path, &report);
+        let server = state.configs.update(server_id, |server| {
+            server.installed = true;
+            server.status = ServerStatus::Stopped;
+            server.install_container_id = None;
+            server.install_state = None;
+            if let Some(version) = version.clone() {
+                updates::record(server, version);
+            }
+        })?;
+        if let Some(version) = version {
+            if server.pinned_version.as_ref().is_some_and(|pinned| !pinned.same_build(&version)) {
+                events::emit(app, ServerEvent::alert(
+                    server_id,
+                    AlertLevel::Warning,
+                    format!("The install put {} in place instead of the pinned build", version.label()),
+                ));
+            }
+        }
         
-        let _ = app.emit("server-log", LogEvent {
-            server_id: server_id.to_string(),
-            line: "[Serverwave] Installation completed successfully!".to_string(),
-        });
+        events::emit(app, ServerEvent::install(
+            server_id,
+            InstallStage::Completed,
+            "Installation completed successfully!",
+        ));
+        events::emit(app, ServerEvent::status(server_id, ServerStatus::Stopped));
         
         Ok(server)
     } else {
-        server.status = ServerStatus::Error;
-        server.install_container_id = None;
-        save_server_config(&server)?;
+        let mut install_state = progress.lock().map(|p| p.clone()).unwrap_or_default();
+        install_state.attempts += 1;
+        install_state.exit_code = Some(exit_code);
+        install_state.failed_at = Some(chrono::Utc::now());
+        let (message, error) = if missing.is_empty() {
+            (format!("Installation failed with exit code: {}", exit_code), AppError::InstallFailed { exit_code })
+        } else {
+            // Whatever SteamCMD reported, the files aren't all there - the retry downloads again
+            install_state.steamcmd_done = false;
+            let error = AppError::InstallIncomplete { missing };
+            (error.to_string(), error)
+        };
+        state.configs.update(server_id, |server| {
+            server.status = ServerStatus::Error;
+            server.install_container_id = None;
+            server.install_state = Some(install_state);
+        })?;
         
-        let _ = app.emit("server-log", LogEvent {
+        events::emit(app, ServerEvent::InstallProgress {
             server_id: server_id.to_string(),
-            line: format!("[Serverwave] Installation failed with exit code: {}", exit_code),
+            stage: InstallStage::Failed,
+            message,
+            exit_code: Some(exit_code),
         });
+        events::emit(app, ServerEvent::status(server_id, ServerStatus::Error));
         
-        Err(format!("Install script failed with exit code: {}", exit_code))
+        Err(error)
     }
 }
 
+/// Take a server out of Installing when its install couldn't run, passing the error on
+fn abort_install(app: &AppHandle, state: &State<'_, ServerState>, server_id: &str, error: AppError) -> AppError {
+    state.configs.update(server_id, |s| s.status = ServerStatus::Error).ok();
+    events::emit(app, ServerEvent::InstallProgress {
+        server_id: server_id.to_string(),
+        stage: InstallStage::Failed,
+        message: error.to_string(),
+        exit_code: None,
+    });
+    events::emit(app, ServerEvent::status(server_id, ServerStatus::Error));
+    error
+}
+
 /// Run install script (can be called manually)
 #[tauri::command(rename_all = "camelCase")]
 pub async fn run_install_script(
@@ -849,7 +1743,31 @@ pub async fn run_install_script(
     app: AppHandle,
     state: State<'_, ServerState>,
     games_state: State<'_, GamesState>,
-) -> Result<ServerResponse, String> {
+) -> Result<ServerResponse, AppError> {
+    let server = run_install_script_internal(&server_id, &app, &state, &games_state).await?;
+    Ok(ServerResponse {
+        success: true,
+        server: Some(server),
+        error: None,
+    })
+}
+
+/// Retry a failed install without deleting data, resuming where it stopped
+#[tauri::command(rename_all = "camelCase")]
+pub async fn retry_install(
+    server_id: String,
+    app: AppHandle,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    let server = load_server_config(&server_id)?;
+    if server.status == ServerStatus::Installing {
+        return Err(AppError::InvalidInput("The server is already installing".into()));
+    }
+    if server.install_state.is_none() {
+        return Err(AppError::InvalidInput("There's no failed install to retry".into()));
+    }
+
     let server = run_install_script_internal(&server_id, &app, &state, &games_state).await?;
     Ok(ServerResponse {
         success: true,
@@ -865,8 +1783,10 @@ pub async fn reinstall_server(
     app: AppHandle,
     state: State<'_, ServerState>,
     games_state: State<'_, GamesState>,
-) -> Result<ServerResponse, String> {
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
     tracing::info!("Reinstalling server: {}", server_id);
+    crate::security::ensure_unlocked().await?;
     
     // Stop log streaming
     {
@@ -876,8 +1796,8 @@ pub async fn reinstall_server(
         }
     }
     
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
-    let mut server = load_server_config(&server_id)?;
+    let docker = docker_state.manager()?;
+    let server = state.configs.get(&server_id)?;
     
     // Stop container
     if let Some(container_id) = &server.container_id {
@@ -886,31 +1806,30 @@ pub async fn reinstall_server(
     
     // Delete all data in server folder
     if server.data_path.exists() {
-        let _ = app.emit("server-log", LogEvent {
-            server_id: server_id.clone(),
-            line: "[Serverwave] Deleting server data...".to_string(),
-        });
+        events::emit(&app, ServerEvent::log(&server_id, "[Serverwave] Deleting server data..."));
         
-        for entry in std::fs::read_dir(&server.data_path).map_err(|e| e.to_string())? {
-            let entry = entry.map_err(|e| e.to_string())?;
+        for entry in std::fs::read_dir(&server.data_path)? {
+            let entry = entry?;
             let path = entry.path();
             if path.is_dir() {
-                std::fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+                std::fs::remove_dir_all(&path)?;
             } else {
-                std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+                std::fs::remove_file(&path)?;
             }
         }
     }
     
-    // Reset installed flag
-    server.installed = false;
-    server.status = ServerStatus::Stopped;
-    save_server_config(&server)?;
+    // Reset installed flag - a fresh install has nothing to resume
+    state.configs.update(&server_id, |server| {
+        server.installed = false;
+        server.status = ServerStatus::Stopped;
+        server.install_state = None;
+    })?;
     
-    let _ = app.emit("server-log", LogEvent {
-        server_id: server_id.clone(),
-        line: "[Serverwave] Server data cleared. Starting reinstallation...".to_string(),
-    });
+    events::emit(&app, ServerEvent::log(
+        &server_id,
+        "[Serverwave] Server data cleared. Starting reinstallation...",
+    ));
     
     // Run install script
     let server = run_install_script_internal(&server_id, &app, &state, &games_state).await?;
@@ -928,7 +1847,8 @@ pub async fn update_server_game(
     app: AppHandle,
     state: State<'_, ServerState>,
     games_state: State<'_, GamesState>,
-) -> Result<ServerResponse, String> {
+    docker_state: State<'_, DockerState>,
+) -> Result<ServerResponse, AppError> {
     tracing::info!("Updating server: {}", server_id);
     
     // Stop log streaming
@@ -939,7 +1859,7 @@ pub async fn update_server_game(
         }
     }
     
-    let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
+    let docker = docker_state.manager()?;
     let server = load_server_config(&server_id)?;
     
     // Stop container
@@ -947,10 +1867,10 @@ pub async fn update_server_game(
         docker.stop_container(container_id).await.ok();
     }
     
-    let _ = app.emit("server-log", LogEvent {
-        server_id: server_id.clone(),
-        line: "[Serverwave] Starting update (running install script)...".to_string(),
-    });
+    events::emit(&app, ServerEvent::log(
+        &server_id,
+        "[Serverwave] Starting update (running install script)...",
+    ));
     
     // Run install script (will overwrite existing files)
     let server = run_install_script_internal(&server_id, &app, &state, &games_state).await?;
@@ -961,12 +1881,84 @@ pub async fn update_server_game(
     })
 }
 
+/// Compare the server's installed build with the latest published one. None when there's
+/// no way to check its game.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_game_updates(server_id: String) -> Result<Option<updates::UpdateCheck>, AppError> {
+    let server = load_server_config(&server_id)?;
+    if !server.installed {
+        return Err(AppError::InvalidInput("The server isn't installed yet".into()));
+    }
+    Ok(updates::check(&server).await)
+}
+
+/// Pin the server to a build from its history so installs fetch it and update checks leave
+/// it alone, or unpin it with None
+#[tauri::command(rename_all = "camelCase")]
+pub async fn pin_game_version(
+    server_id: String,
+    build: Option<String>,
+    state: State<'_, ServerState>,
+) -> Result<Server, AppError> {
+    let server = state.configs.try_update(&server_id, |server| {
+        server.pinned_version = match &build {
+            Some(build) => Some(recorded_version(server, build)?),
+            None => None,
+        };
+        Ok::<_, AppError>(())
+    })?;
+    updates::forget(&server_id);
+    Ok(server)
+}
+
+fn recorded_version(server: &Server, build: &str) -> Result<serverwave_core::server::GameVersion, AppError> {
+    server
+        .versions
+        .iter()
+        .rev()
+        .find(|v| v.build == build)
+        .cloned()
+        .ok_or_else(|| AppError::InvalidInput(format!("Build {} was never installed on this server", build)))
+}
+
+/// Go back to a build installed earlier: pin it and run the installer for it. Steam only
+/// serves a game's current build, so Steam servers can only be rolled back from a backup.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn rollback_game_version(
+    server_id: String,
+    build: String,
+    app: AppHandle,
+    state: State<'_, ServerState>,
+    games_state: State<'_, GamesState>,
+) -> Result<ServerResponse, AppError> {
+    crate::security::ensure_unlocked().await?;
+    let server = state.configs.get(&server_id)?;
+    let version = recorded_version(&server, &build)?;
+    let current = server.versions.last().is_some_and(|last| last.same_build(&version));
+    if version.minecraft_version.is_none() && !current {
+        return Err(AppError::InvalidInput(
+            "Steam only serves the current build - restore a backup from before the update instead".into(),
+        ));
+    }
+    events::emit(&app, ServerEvent::log(&server_id, format!("[Serverwave] Rolling back to {}...", version.label())));
+    state.configs.update(&server_id, |s| s.pinned_version = Some(version))?;
+    updates::forget(&server_id);
+
+    update_server_game(server_id, app.clone(), state, games_state, app.state()).await
+}
+
+/// Servers the background checker found behind the latest build
+#[tauri::command]
+pub async fn get_pending_updates() -> Result<Vec<updates::UpdateCheck>, AppError> {
+    Ok(updates::pending())
+}
+
 /// Check if server needs installation
 #[tauri::command(rename_all = "camelCase")]
 pub async fn check_needs_install(
     server_id: String,
     games_state: State<'_, GamesState>,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     let server = load_server_config(&server_id)?;
     
     if server.installed {
@@ -982,56 +1974,3 @@ pub async fn check_needs_install(
     
     Ok(false)
 }
-
-fn calculate_dir_size(path: &PathBuf) -> Result<u64, std::io::Error> {
-    let mut total = 0;
-    
-    if path.is_file() {
-        return Ok(std::fs::metadata(path)?.len());
-    }
-    
-    for entry in std::fs::read_dir(path)? {
-        let path = entry?.path();
-        if path.is_file() {
-            total += std::fs::metadata(&path)?.len();
-        } else if path.is_dir() {
-            total += calculate_dir_size(&path).unwrap_or(0);
-        }
-    }
-    
-    Ok(total)
-}
-
-fn get_servers_dir() -> PathBuf {
-    directories::UserDirs::new()
-        .map(|d| d.home_dir().to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("ServerWaveAnywhere")
-        .join("servers")
-}
-
-fn get_servers_config_dir() -> PathBuf {
-    directories::UserDirs::new()
-        .map(|d| d.home_dir().to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("ServerWaveAnywhere")
-        .join("config")
-}
-
-fn get_server_config_path(server_id: &str) -> PathBuf {
-    get_servers_config_dir().join(format!("{}.json", server_id))
-}
-
-fn save_server_config(server: &Server) -> Result<(), String> {
-    let config_dir = get_servers_config_dir();
-    std::fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
-    let config_path = get_server_config_path(&server.id);
-    let content = serde_json::to_string_pretty(server).map_err(|e| e.to_string())?;
-    std::fs::write(config_path, content).map_err(|e| e.to_string())
-}
-
-fn load_server_config(server_id: &str) -> Result<Server, String> {
-    let config_path = get_server_config_path(server_id);
-    let content = std::fs::read_to_string(config_path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&content).map_err(|e| e.to_string())
-}
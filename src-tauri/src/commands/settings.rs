@@ -0,0 +1,387 @@
+// App settings commands - global preferences shared by all servers
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::ServerState;
+use crate::disk_guard::{self, DiskSpace};
+use crate::docker::connection::{self, DockerConnection};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use serverwave_core::{atomic, paths};
+use serverwave_core::server::{
+    lifecycle, load_all_server_configs, relocate, MaintenancePolicy, ServerStatus,
+};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    /// Memory for new servers when neither the request nor the game sets one
+    #[serde(default)]
+    pub default_memory_mb: Option<u32>,
+    /// First host port handed out when a server is created without an explicit port
+    #[serde(default = "default_port_range_start")]
+    pub port_range_start: u16,
+    /// Last host port handed out automatically (inclusive)
+    #[serde(default = "default_port_range_end")]
+    pub port_range_end: u16,
+    /// Where servers and config live, None for ~/ServerWaveAnywhere.
+    /// Only changed through `set_data_directory`, which moves the data.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    /// Install scripts allowed to run at the same time - the rest wait their turn
+    #[serde(default = "default_max_concurrent_installs")]
+    pub max_concurrent_installs: usize,
+    #[serde(default)]
+    pub backups: BackupSettings,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    /// Key from console.curseforge.com, needed to search and download from CurseForge
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+    #[serde(default)]
+    pub updates: UpdateSettings,
+    /// How to reach Docker - a socket, or a remote daemon over TCP
+    #[serde(default)]
+    pub docker: DockerConnection,
+    /// When automation may touch servers that don't set their own maintenance policy
+    #[serde(default)]
+    pub maintenance: MaintenancePolicy,
+    /// Open sign-in links an installer asks for (InstallAuthRequired) in the browser right
+    /// away, rather than only showing them
+    #[serde(default = "default_true")]
+    pub open_auth_links: bool,
+    /// Where custom game definitions are shared with other people
+    #[serde(default)]
+    pub game_sync: GameSyncSettings,
+    #[serde(default)]
+    pub disk_guard: DiskGuardSettings,
+}
+
+/// Watching the free space on the disk servers live on - a game writing its world to a full
+/// disk can corrupt it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskGuardSettings {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Less free space than this is low
+    #[serde(default = "default_min_free_gb")]
+    pub min_free_gb: u32,
+    #[serde(default = "default_disk_check_minutes")]
+    pub check_interval_minutes: u32,
+    /// Don't start installs while space is low - they download whole servers
+    #[serde(default = "default_true")]
+    pub pause_installs: bool,
+    #[serde(default = "default_true")]
+    pub pause_backups: bool,
+}
+
+impl Default for DiskGuardSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_free_gb: default_min_free_gb(),
+            check_interval_minutes: default_disk_check_minutes(),
+            pause_installs: true,
+            pause_backups: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSyncSettings {
+    /// A Git repository or gist, over https or ssh - None turns syncing off
+    #[serde(default)]
+    pub repo_url: Option<String>,
+    /// None for the repository's default branch
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Push a custom game to the repository as soon as it's saved
+    #[serde(default = "default_true")]
+    pub push_on_save: bool,
+}
+
+impl Default for GameSyncSettings {
+    fn default() -> Self {
+        Self {
+            repo_url: None,
+            branch: None,
+            push_on_save: true,
+        }
+    }
+}
+
+impl GameSyncSettings {
+    /// The repository, when syncing is set up
+    pub fn repo(&self) -> Option<&str> {
+        self.repo_url.as_deref().map(str::trim).filter(|url| !url.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSettings {
+    /// Look for newer game builds in the background
+    #[serde(default = "default_true")]
+    pub check: bool,
+    /// Update stopped servers as soon as a newer build is found
+    #[serde(default)]
+    pub auto_update: bool,
+    #[serde(default = "default_update_interval_hours")]
+    pub interval_hours: u32,
+    /// Pull newer versions of the app's game images in the background
+    #[serde(default = "default_true")]
+    pub refresh_images: bool,
+    #[serde(default = "default_image_interval_hours")]
+    pub image_interval_hours: u32,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            check: true,
+            auto_update: false,
+            interval_hours: default_update_interval_hours(),
+            refresh_images: true,
+            image_interval_hours: default_image_interval_hours(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSettings {
+    /// Where backups are written, None for a `backups` folder in the data directory
+    #[serde(default)]
+    pub directory: Option<String>,
+    /// Backups kept per server before the oldest are removed
+    #[serde(default = "default_backup_retention")]
+    pub retention_count: u32,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            retention_count: default_backup_retention(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// Show desktop notifications
+    #[serde(default)]
+    pub enabled: bool,
+    /// Also POST each notification as JSON to this URL
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub on_crash: bool,
+    #[serde(default)]
+    pub on_install_finished: bool,
+    #[serde(default)]
+    pub on_low_disk: bool,
+}
+
+fn default_port_range_start() -> u16 {
+    1024
+}
+
+fn default_port_range_end() -> u16 {
+    49151
+}
+
+fn default_max_concurrent_installs() -> usize {
+    2
+}
+
+fn default_backup_retention() -> u32 {
+    5
+}
+
+fn default_min_free_gb() -> u32 {
+    10
+}
+
+fn default_disk_check_minutes() -> u32 {
+    5
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_update_interval_hours() -> u32 {
+    6
+}
+
+fn default_image_interval_hours() -> u32 {
+    24
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            default_memory_mb: None,
+            port_range_start: default_port_range_start(),
+            port_range_end: default_port_range_end(),
+            data_dir: None,
+            max_concurrent_installs: default_max_concurrent_installs(),
+            backups: BackupSettings::default(),
+            notifications: NotificationSettings::default(),
+            curseforge_api_key: None,
+            updates: UpdateSettings::default(),
+            docker: DockerConnection::default(),
+            maintenance: MaintenancePolicy::default(),
+            open_auth_links: true,
+            game_sync: GameSyncSettings::default(),
+            disk_guard: DiskGuardSettings::default(),
+        }
+    }
+}
+
+/// Get the app settings
+#[tauri::command]
+pub async fn get_app_settings() -> Result<AppSettings, AppError> {
+    Ok(load_app_settings())
+}
+
+/// Free space on the disk servers are on, measured now
+#[tauri::command]
+pub async fn get_disk_space(app: AppHandle) -> Result<Option<DiskSpace>, AppError> {
+    Ok(disk_guard::check(&app).await)
+}
+
+/// Replace the app settings and tell every window (and API client) about it
+#[tauri::command]
+pub async fn update_app_settings(mut settings: AppSettings, app: AppHandle) -> Result<AppSettings, AppError> {
+    validate(&settings)?;
+    settings.data_dir = load_app_settings().data_dir;
+    save_app_settings(&settings)?;
+    connection::configure(settings.docker.clone());
+    let _ = app.emit("settings-changed", &settings);
+    Ok(settings)
+}
+
+fn validate(settings: &AppSettings) -> Result<(), AppError> {
+    if settings.port_range_start == 0 || settings.port_range_start > settings.port_range_end {
+        return Err(AppError::InvalidInput(format!(
+            "Invalid port range {}-{}",
+            settings.port_range_start, settings.port_range_end
+        )));
+    }
+    if matches!(settings.default_memory_mb, Some(mb) if mb < 256) {
+        return Err(AppError::InvalidInput("Default memory must be at least 256 MB".into()));
+    }
+    if settings.max_concurrent_installs == 0 {
+        return Err(AppError::InvalidInput("At least one install must be allowed at a time".into()));
+    }
+    if settings.disk_guard.check_interval_minutes == 0 {
+        return Err(AppError::InvalidInput("Disk space checks need an interval of at least 1 minute".into()));
+    }
+    if settings.backups.retention_count == 0 {
+        return Err(AppError::InvalidInput("Keep at least one backup per server".into()));
+    }
+    settings.docker.validate().map_err(AppError::InvalidInput)?;
+    settings.maintenance.validate().map_err(AppError::InvalidInput)?;
+    if let Some(url) = settings.game_sync.repo() {
+        if url.starts_with('-') || url.chars().any(char::is_whitespace) {
+            return Err(AppError::InvalidInput("The game library repository isn't a valid Git URL".into()));
+        }
+    }
+    if let Some(branch) = settings.game_sync.branch.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        if branch.starts_with('-') || branch.chars().any(char::is_whitespace) {
+            return Err(AppError::InvalidInput(format!("\"{}\" isn't a valid branch name", branch)));
+        }
+    }
+    if let Some(url) = settings.notifications.webhook_url.as_deref().filter(|u| !u.is_empty()) {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(AppError::InvalidInput("Webhook URL must start with http:// or https://".into()));
+        }
+    }
+    Ok(())
+}
+
+/// Move servers and configs to another folder (e.g. a bigger drive).
+/// All servers must be stopped; their containers are recreated to mount the new location.
+#[tauri::command]
+pub async fn set_data_directory(
+    path: String,
+    app: AppHandle,
+    games_state: State<'_, GamesState>,
+) -> Result<AppSettings, AppError> {
+    crate::security::ensure_unlocked().await?;
+
+    let new_root = PathBuf::from(path.trim());
+    relocate::check_target(&new_root)?;
+
+    let docker = app.state::<DockerState>().manager()?;
+    for server in load_all_server_configs() {
+        let status = lifecycle::current_status(&docker, &server)
+            .await
+            .unwrap_or(ServerStatus::Error);
+        if matches!(status, ServerStatus::Running | ServerStatus::Starting | ServerStatus::Installing) {
+            return Err(format!(
+                "Stop all servers before moving the data directory ('{}' is {:?})",
+                server.name, status
+            ).into());
+        }
+    }
+
+    tracing::info!("Moving data directory to {}", new_root.display());
+    let target = new_root.clone();
+    tokio::task::spawn_blocking(move || relocate::relocate(&target))
+        .await??;
+
+    let mut settings = load_app_settings();
+    settings.data_dir = (new_root != paths::app_root()).then(|| new_root.to_string_lossy().to_string());
+    save_app_settings(&settings)?;
+
+    // Containers mount the old data path - recreate them against the new one
+    let manager = games_state.manager.lock().await;
+    for mut server in load_all_server_configs() {
+        let old_container = match server.container_id.take() {
+            Some(id) => id,
+            None => continue,
+        };
+        docker.remove_container(&old_container).await.ok();
+        if let Some(install_container_id) = server.install_container_id.take() {
+            docker.remove_install_container(&install_container_id).await.ok();
+        }
+        match manager.get_game(&server.game_type) {
+            Some(game) => match lifecycle::create_container(&docker, &server, &game).await {
+                Ok(container_id) => server.container_id = Some(container_id),
+                Err(e) => tracing::error!("Failed to recreate container for {}: {}", server.name, e),
+            },
+            None => tracing::warn!("Game '{}' not found, {} has no container", server.game_type, server.name),
+        }
+        app.state::<ServerState>().configs.update(&server.id, |s| {
+            s.container_id = server.container_id.clone();
+            s.install_container_id = None;
+        })?;
+    }
+
+    let _ = app.emit("settings-changed", &settings);
+    Ok(settings)
+}
+
+/// Apply the saved data directory and Docker socket - called once at startup before
+/// anything reads paths or connects to Docker
+pub fn apply_startup_settings() {
+    let settings = load_app_settings();
+    paths::set_data_root(settings.data_dir.map(PathBuf::from));
+    connection::configure(settings.docker);
+}
+
+pub(crate) fn load_app_settings() -> AppSettings {
+    atomic::read_json(&get_settings_path()).unwrap_or_default()
+}
+
+pub(crate) fn save_app_settings(settings: &AppSettings) -> Result<(), AppError> {
+    let content = serde_json::to_string_pretty(settings)?;
+    atomic::write_with_backup(&get_settings_path(), content).map_err(AppError::from)
+}
+
+/// Lives in the app folder, not the (movable) config dir
+fn get_settings_path() -> PathBuf {
+    serverwave_core::paths::app_root().join("settings.json")
+}
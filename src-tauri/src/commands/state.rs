@@ -0,0 +1,240 @@
+// App state commands - the panel's whole setup in one file, to move it to another machine
+// or keep a copy. Server data folders, users, nodes and keychain secrets aren't included.
+
+use crate::commands::games::GamesState;
+use crate::commands::server::{load_all_server_configs, Server, ServerState, ServerStatus};
+use crate::commands::settings::{load_app_settings, save_app_settings, AppSettings};
+use crate::error::AppError;
+use crate::games::GameConfig;
+use crate::hooks::{self, Hook};
+use crate::wipes::{self, WipeSchedule};
+use serde::{Deserialize, Serialize};
+use serverwave_core::{atomic, paths};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Bumped when the bundle layout changes in a way older versions can't read
+const STATE_FORMAT: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppStateBundle {
+    pub format: u32,
+    pub app_version: String,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub settings: AppSettings,
+    pub servers: Vec<Server>,
+    /// Custom games and overrides of built-in ones
+    #[serde(default)]
+    pub custom_games: Vec<GameConfig>,
+    #[serde(default)]
+    pub wipe_schedules: Vec<WipeSchedule>,
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct StateImport {
+    pub servers: Vec<String>,
+    /// Servers left alone because one with the same id is already here
+    pub skipped_servers: Vec<String>,
+    /// Imported servers whose data folder isn't on this machine - they install again
+    pub needs_install: Vec<String>,
+    pub custom_games: Vec<String>,
+    pub wipe_schedules: usize,
+    pub hooks: usize,
+}
+
+/// Write the settings, servers, custom games, wipe schedules and hooks to one JSON file
+/// and return its path - `dest`, or a new file in the app folder
+#[tauri::command]
+pub async fn export_app_state(
+    dest: Option<String>,
+    games_state: State<'_, GamesState>,
+) -> Result<String, AppError> {
+    crate::security::ensure_unlocked().await?;
+
+    let custom_games = games_state
+        .manager
+        .lock()
+        .await
+        .get_all_games()
+        .into_iter()
+        .filter(|g| g.is_custom)
+        .collect();
+    let bundle = AppStateBundle {
+        format: STATE_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now(),
+        settings: load_app_settings(),
+        servers: load_all_server_configs(),
+        custom_games,
+        wipe_schedules: wipes::load_schedules(),
+        hooks: hooks::load_hooks(),
+    };
+
+    let path = match dest.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(dest) => PathBuf::from(dest),
+        None => paths::app_root().join("exports").join(format!(
+            "serverwave-state-{}.json",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )),
+    };
+    if path.is_dir() {
+        return Err(AppError::InvalidInput(format!("{} is a folder - give a file name", path.display())));
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    atomic::write_atomic(&path, serde_json::to_string_pretty(&bundle)?)?;
+
+    tracing::info!("Exported app state ({} servers) to {}", bundle.servers.len(), path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Add what an exported state file has and this panel doesn't. Servers and custom games
+/// already here are kept as they are; the settings are replaced, except for the data
+/// directory and Docker connection, which belong to this machine.
+#[tauri::command]
+pub async fn import_app_state(
+    path: String,
+    app: AppHandle,
+    games_state: State<'_, GamesState>,
+) -> Result<StateImport, AppError> {
+    crate::security::ensure_unlocked().await?;
+
+    let content = std::fs::read_to_string(path.trim())?;
+    let bundle: AppStateBundle = serde_json::from_str(&content)
+        .map_err(|e| AppError::InvalidInput(format!("Not a Serverwave state file: {}", e)))?;
+    if bundle.format > STATE_FORMAT {
+        return Err(AppError::InvalidInput(format!(
+            "The file was exported by a newer version ({}) - update the app first",
+            bundle.app_version
+        )));
+    }
+
+    let mut result = StateImport::default();
+
+    {
+        let mut manager = games_state.manager.lock().await;
+        let existing: Vec<String> = manager
+            .get_all_games()
+            .into_iter()
+            .filter(|g| g.is_custom)
+            .map(|g| g.game_type.0)
+            .collect();
+        for game in bundle.custom_games {
+            if existing.contains(&game.game_type.0) {
+                continue;
+            }
+            let id = game.game_type.0.clone();
+            manager.add_game(game)?;
+            result.custom_games.push(id);
+        }
+    }
+
+    let existing: Vec<String> = load_all_server_configs().into_iter().map(|s| s.id).collect();
+    let mut imported_ids = Vec::new();
+    for server in bundle.servers {
+        if existing.contains(&server.id) {
+            result.skipped_servers.push(server.name);
+            continue;
+        }
+        let server = adopt_server(server);
+        if !server.installed {
+            result.needs_install.push(server.name.clone());
+        }
+        app.state::<ServerState>().configs.insert(&server)?;
+        imported_ids.push(server.id);
+        result.servers.push(server.name);
+    }
+
+    // Schedules and hooks come along with the servers they're for
+    let imported = |server_id: Option<&String>| server_id.is_none_or(|id| imported_ids.contains(id));
+
+    let mut schedules = wipes::load_schedules();
+    for schedule in bundle.wipe_schedules {
+        if imported(Some(&schedule.server_id)) && schedules.iter().all(|s| s.server_id != schedule.server_id) {
+            schedules.push(schedule);
+            result.wipe_schedules += 1;
+        }
+    }
+    if result.wipe_schedules > 0 {
+        wipes::save_schedules(&schedules)?;
+    }
+
+    let mut all_hooks = hooks::load_hooks();
+    for hook in bundle.hooks {
+        if imported(hook.server_id.as_ref()) && all_hooks.iter().all(|h| h.id != hook.id) {
+            all_hooks.push(hook);
+            result.hooks += 1;
+        }
+    }
+    if result.hooks > 0 {
+        hooks::save_hooks(&all_hooks)?;
+    }
+
+    let current = load_app_settings();
+    let settings = AppSettings {
+        data_dir: current.data_dir,
+        docker: current.docker,
+        ..bundle.settings
+    };
+    save_app_settings(&settings)?;
+    let _ = app.emit("settings-changed", &settings);
+
+    tracing::info!(
+        "Imported app state: {} servers, {} skipped, {} custom games",
+        result.servers.len(),
+        result.skipped_servers.len(),
+        result.custom_games.len()
+    );
+    Ok(result)
+}
+
+/// A server from another machine, as this one has it: stopped, with no container yet,
+/// its data under this data directory, and not installed unless that folder is here
+fn adopt_server(mut server: Server) -> Server {
+    server.data_path = paths::servers_dir()
+        .join(server.game_type.to_string())
+        .join(&server.id);
+    server.status = ServerStatus::Stopped;
+    server.container_id = None;
+    server.install_container_id = None;
+    server.config_outdated = false;
+    // Rules and Docker networks were made on the other machine
+    server.firewall_rules = false;
+    server.network = None;
+    if !server.data_path.is_dir() {
+        server.installed = false;
+        server.install_state = None;
+        server.versions.clear();
+    }
+    server
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adopted_server_starts_fresh() {
+        let mut server = Server {
+            status: ServerStatus::Running,
+            container_id: Some("deadbeef".into()),
+            data_path: "/old/machine/servers/minecraft-java/ab12cd34".into(),
+            installed: true,
+            firewall_rules: true,
+            network: Some("serverwave-net-1".into()),
+            ..Server::test_fixture("ab12cd34")
+        };
+        server.config.insert("MC_MAXPLAYERS".into(), "20".into());
+
+        let server = adopt_server(server);
+        assert_eq!(server.status, ServerStatus::Stopped);
+        assert!(server.container_id.is_none());
+        assert!(server.network.is_none());
+        assert!(!server.firewall_rules);
+        assert!(server.data_path.ends_with("servers/minecraft-java/ab12cd34"));
+        assert_eq!(server.config.get("MC_MAXPLAYERS").map(String::as_str), Some("20"));
+    }
+}
@@ -0,0 +1,91 @@
+// Steam commands - the Steam account SteamCMD installs log in with, answering Steam Guard,
+// and the server tokens (GSLTs) games need to list publicly
+
+use crate::commands::docker::DockerState;
+use crate::error::AppError;
+use crate::gslt::{self, ServerToken};
+use crate::security::ensure_unlocked;
+use crate::steam;
+use serde::Serialize;
+use serverwave_core::server::{load_server_config, ServerStatus};
+use tauri::State;
+
+#[derive(Debug, Serialize)]
+pub struct ServerTokens {
+    pub tokens: Vec<ServerToken>,
+    /// Checking tokens with Steam needs a Web API key
+    pub has_web_api_key: bool,
+}
+
+/// The stored account's username, never the password
+#[tauri::command]
+pub async fn get_steam_account() -> Result<Option<String>, AppError> {
+    Ok(steam::username().await)
+}
+
+#[tauri::command]
+pub async fn set_steam_account(username: String, password: String) -> Result<(), AppError> {
+    ensure_unlocked().await?;
+    steam::set_account(&username, &password).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_steam_account() -> Result<(), AppError> {
+    steam::clear_account().await;
+    Ok(())
+}
+
+/// Type a Steam Guard code into the install SteamCMD is waiting in
+#[tauri::command(rename_all = "camelCase")]
+pub async fn submit_steam_guard_code(server_id: String, code: String, docker_state: State<'_, DockerState>) -> Result<(), AppError> {
+    let code = steam::validate_code(&code)?;
+    let server = load_server_config(&server_id)?;
+    let container_id = match (&server.status, &server.install_container_id) {
+        (ServerStatus::Installing, Some(container_id)) => container_id.clone(),
+        _ => return Err(AppError::InvalidInput("The server isn't installing".into())),
+    };
+    let docker = docker_state.manager()?;
+    docker.send_stdin(&container_id, code).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_server_tokens() -> Result<ServerTokens, AppError> {
+    Ok(ServerTokens {
+        tokens: gslt::list(),
+        has_web_api_key: gslt::has_web_api_key().await,
+    })
+}
+
+#[tauri::command]
+pub async fn add_server_token(label: String, token: String) -> Result<ServerToken, AppError> {
+    ensure_unlocked().await?;
+    gslt::add(&label, &token).await
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_server_token(token_id: String) -> Result<(), AppError> {
+    gslt::remove(&token_id).await
+}
+
+/// Give a token to a server, or free it with None. It reaches the server on its next start.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn assign_server_token(token_id: String, server_id: Option<String>) -> Result<ServerToken, AppError> {
+    if let Some(server_id) = &server_id {
+        load_server_config(server_id)?;
+    }
+    gslt::assign(&token_id, server_id)
+}
+
+/// Ask Steam whether a token is still valid
+#[tauri::command(rename_all = "camelCase")]
+pub async fn check_server_token(token_id: String) -> Result<ServerToken, AppError> {
+    gslt::check(&token_id).await
+}
+
+#[tauri::command]
+pub async fn set_steam_web_api_key(key: Option<String>) -> Result<(), AppError> {
+    ensure_unlocked().await?;
+    gslt::set_web_api_key(key.as_deref()).await
+}
@@ -0,0 +1,119 @@
+// User commands - remote users with a role and per-server access
+
+use crate::api::auth;
+use crate::error::AppError;
+use crate::security::users::{self, User};
+use crate::security::{current_caller, ensure_unlocked, Caller, Role};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A newly created user with their token - the only time the token is shown
+#[derive(Debug, Clone, Serialize)]
+pub struct NewUser {
+    pub user: User,
+    pub token: String,
+}
+
+/// Who is calling, so the frontend can hide what they can't do
+#[derive(Debug, Clone, Serialize)]
+pub struct CurrentUser {
+    pub name: String,
+    pub role: Role,
+    /// Servers the caller may access, None meaning all of them
+    pub servers: Option<Vec<String>>,
+    pub read_only: bool,
+}
+
+#[tauri::command]
+pub async fn list_users() -> Result<Vec<User>, AppError> {
+    Ok(users::load_users())
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn add_user(
+    name: String,
+    role: Role,
+    servers: Vec<String>,
+    read_only: Option<bool>,
+) -> Result<NewUser, AppError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("User name cannot be empty".into()));
+    }
+
+    let mut all = users::load_users();
+    if all.iter().any(|u| u.name.eq_ignore_ascii_case(&name)) {
+        return Err(AppError::InvalidInput(format!("A user named '{}' already exists", name)));
+    }
+
+    let user = User {
+        id: Uuid::new_v4().to_string()[..8].to_string(),
+        name,
+        role,
+        servers,
+        read_only: read_only.unwrap_or(false),
+        created_at: chrono::Utc::now(),
+    };
+    let token = auth::generate_token();
+    users::set_user_token(&user.id, &token).await?;
+    all.push(user.clone());
+    users::save_users(&all)?;
+
+    Ok(NewUser { user, token })
+}
+
+/// Change a user's role and servers, and read-only when given. Their token stays the same.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn update_user(
+    user_id: String,
+    role: Role,
+    servers: Vec<String>,
+    read_only: Option<bool>,
+) -> Result<User, AppError> {
+    let mut all = users::load_users();
+    let user = all
+        .iter_mut()
+        .find(|u| u.id == user_id)
+        .ok_or_else(|| format!("User '{}' not found", user_id))?;
+    user.role = role;
+    user.servers = servers;
+    if let Some(read_only) = read_only {
+        user.read_only = read_only;
+    }
+    let updated = user.clone();
+    users::save_users(&all)?;
+    Ok(updated)
+}
+
+/// Delete a user - their token stops working immediately
+#[tauri::command(rename_all = "camelCase")]
+pub async fn remove_user(user_id: String) -> Result<(), AppError> {
+    ensure_unlocked().await?;
+    let mut all = users::load_users();
+    let before = all.len();
+    all.retain(|u| u.id != user_id);
+    if all.len() == before {
+        return Err(format!("User '{}' not found", user_id).into());
+    }
+    users::save_users(&all)?;
+    users::delete_user_token(&user_id).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_current_user() -> Result<CurrentUser, AppError> {
+    Ok(match current_caller() {
+        Some(Caller::User(user)) => CurrentUser {
+            servers: (user.role != Role::Admin).then(|| user.servers.clone()),
+            name: user.name,
+            role: user.role,
+            read_only: user.read_only,
+        },
+        _ => CurrentUser {
+            name: "Owner".to_string(),
+            role: Role::Admin,
+            servers: None,
+            read_only: false,
+        },
+    })
+}
@@ -0,0 +1,57 @@
+// Wipe commands - wipe a Rust server now or on a schedule
+
+use crate::error::AppError;
+use crate::wipes::{self, WipeKind, WipeResult, WipeSchedule};
+use chrono::{Local, Utc};
+use tauri::AppHandle;
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn get_wipe_schedule(server_id: String) -> Result<Option<WipeSchedule>, AppError> {
+    Ok(wipes::load_schedules().into_iter().find(|s| s.server_id == server_id))
+}
+
+/// Set a server's wipe schedule, or remove it with None
+#[tauri::command(rename_all = "camelCase")]
+pub async fn set_wipe_schedule(
+    server_id: String,
+    schedule: Option<WipeSchedule>,
+) -> Result<Option<WipeSchedule>, AppError> {
+    wipes::rust_server(&server_id)?;
+    let mut all = wipes::load_schedules();
+    let previous = all.iter().position(|s| s.server_id == server_id).map(|i| all.remove(i));
+
+    let schedule = match schedule {
+        Some(mut schedule) => {
+            if schedule.hour > 23 || schedule.minute > 59 {
+                return Err(AppError::InvalidInput("Pick a time between 00:00 and 23:59".into()));
+            }
+            schedule.server_id = server_id;
+            schedule.last_wipe = previous.and_then(|p| p.last_wipe);
+            schedule.next_wipe = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+            all.push(schedule.clone());
+            Some(schedule)
+        }
+        None => None,
+    };
+    wipes::save_schedules(&all)?;
+    Ok(schedule)
+}
+
+/// Wipe a Rust server now, stopping and restarting it if it runs
+#[tauri::command(rename_all = "camelCase")]
+pub async fn wipe_rust_server(
+    server_id: String,
+    kind: WipeKind,
+    new_seed: bool,
+    hostname_date: bool,
+    app: AppHandle,
+) -> Result<WipeResult, AppError> {
+    crate::security::ensure_unlocked().await?;
+    let result = wipes::wipe(&app, &server_id, kind, new_seed, hostname_date).await?;
+    let mut all = wipes::load_schedules();
+    if let Some(schedule) = all.iter_mut().find(|s| s.server_id == server_id) {
+        schedule.last_wipe = Some(Utc::now());
+        wipes::save_schedules(&all)?;
+    }
+    Ok(result)
+}
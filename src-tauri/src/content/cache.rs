@@ -0,0 +1,103 @@
+// Content cache - downloaded jars stored once by sha1 and hard-linked (or copied) into
+// each server, so a second modded server doesn't download the same files again
+
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheUsage {
+    pub files: usize,
+    pub size_bytes: u64,
+    /// SteamCMD and the game copies kept for SteamCMD installs
+    pub steamcmd_bytes: u64,
+}
+
+fn root() -> PathBuf {
+    serverwave_core::paths::cache_dir().join("content")
+}
+
+fn object_path(sha1: &str) -> Option<PathBuf> {
+    let sha1 = sha1.to_ascii_lowercase();
+    if sha1.len() != 40 || !sha1.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(root().join("sha1").join(&sha1[..2]).join(sha1))
+}
+
+/// Where the sha1 of an immutable URL's content is remembered
+fn url_path(url: &str) -> PathBuf {
+    root().join("urls").join(hex::encode(Sha1::digest(url.as_bytes())))
+}
+
+/// Put a copy of the cached file with this sha1 at `dest`, if there is one
+pub fn fetch(sha1: &str, dest: &Path) -> bool {
+    let Some(object) = object_path(sha1).filter(|p| p.is_file()) else { return false };
+    match link_or_copy(&object, dest) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!("Couldn't use cached {}: {}", object.display(), e);
+            false
+        }
+    }
+}
+
+/// Keep a verified download in the cache
+pub fn store(path: &Path, sha1: &str) {
+    let Some(object) = object_path(sha1) else { return };
+    if object.exists() {
+        return;
+    }
+    if let Err(e) = object
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| link_or_copy(path, &object))
+    {
+        tracing::warn!("Couldn't cache {}: {}", path.display(), e);
+    }
+}
+
+pub fn remember_url(url: &str, sha1: &str) {
+    let path = url_path(url);
+    let _ = path.parent().map(std::fs::create_dir_all);
+    if let Err(e) = std::fs::write(&path, sha1) {
+        tracing::warn!("Couldn't remember the hash of {}: {}", url, e);
+    }
+}
+
+pub fn url_sha1(url: &str) -> Option<String> {
+    std::fs::read_to_string(url_path(url)).ok().map(|s| s.trim().to_string())
+}
+
+/// Hard link when both sides are on one filesystem, copy otherwise. `dest` is replaced.
+fn link_or_copy(from: &Path, dest: &Path) -> std::io::Result<()> {
+    if dest.exists() {
+        std::fs::remove_file(dest)?;
+    }
+    if std::fs::hard_link(from, dest).is_err() {
+        std::fs::copy(from, dest)?;
+    }
+    Ok(())
+}
+
+pub fn usage() -> CacheUsage {
+    let mut usage = CacheUsage { files: 0, size_bytes: 0, steamcmd_bytes: 0 };
+    let Ok(prefixes) = std::fs::read_dir(root().join("sha1")) else { return usage };
+    for prefix in prefixes.flatten() {
+        let Ok(objects) = std::fs::read_dir(prefix.path()) else { continue };
+        for object in objects.flatten() {
+            usage.files += 1;
+            usage.size_bytes += object.metadata().map(|m| m.len()).unwrap_or(0);
+        }
+    }
+    usage
+}
+
+/// Empty the cache. Servers keep their files - hard links outlive the cached copy.
+pub fn clear() -> Result<(), String> {
+    match std::fs::remove_dir_all(root()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Couldn't clear the content cache: {}", e)),
+    }
+}
@@ -0,0 +1,402 @@
+// CurseForge - search mods and modpacks, pick files that fit a server and read pack manifests.
+// Needs an API key from console.curseforge.com (Settings → General).
+
+use super::loader::ContentTarget;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const API_URL: &str = "https://api.curseforge.com/v1";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const SEARCH_LIMIT: u32 = 20;
+
+const MINECRAFT: u32 = 432;
+pub const CLASS_PLUGINS: u32 = 5;
+pub const CLASS_MODS: u32 = 6;
+pub const CLASS_MODPACKS: u32 = 4471;
+
+/// sha1 in a file's `hashes`
+const HASH_SHA1: u32 = 1;
+/// `relationType`s of a file's dependencies
+const RELATION_REQUIRED: u32 = 3;
+const RELATION_INCOMPATIBLE: u32 = 5;
+
+pub struct CurseForge {
+    api_key: String,
+    http: reqwest::Client,
+}
+
+/// A mod or modpack as listed to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct Project {
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    pub summary: String,
+    pub author: String,
+    pub icon_url: Option<String>,
+    pub downloads: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModAttributes {
+    id: u64,
+    name: String,
+    slug: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    download_count: f64,
+    #[serde(default)]
+    logo: Option<Logo>,
+    #[serde(default)]
+    authors: Vec<Author>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Logo {
+    thumbnail_url: String,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct File {
+    pub id: u64,
+    pub mod_id: u64,
+    pub display_name: String,
+    pub file_name: String,
+    /// None when the author doesn't allow downloads outside curseforge.com
+    #[serde(default)]
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub file_date: String,
+    /// Minecraft versions mixed with loader and side names ("1.20.1", "Forge", "Server")
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub hashes: Vec<FileHash>,
+    /// Modpacks: the separate server pack, when the author uploaded one
+    #[serde(default)]
+    pub server_pack_file_id: Option<u64>,
+    #[serde(default)]
+    pub file_fingerprint: u32,
+    #[serde(default)]
+    pub dependencies: Vec<FileDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct FileDependency {
+    pub mod_id: u64,
+    pub relation_type: u32,
+}
+
+impl FileDependency {
+    pub fn required(&self) -> bool {
+        self.relation_type == RELATION_REQUIRED
+    }
+
+    pub fn incompatible(&self) -> bool {
+        self.relation_type == RELATION_INCOMPATIBLE
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+impl File {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes.iter().find(|h| h.algo == HASH_SHA1).map(|h| h.value.as_str())
+    }
+
+    pub fn fits(&self, target: &ContentTarget) -> bool {
+        let loader_ok = match target.loader.curseforge_name() {
+            Some(name) => self.game_versions.iter().any(|v| v == name),
+            None => true,
+        };
+        let version_ok = match &target.game_version {
+            Some(version) => self.game_versions.contains(version),
+            None => true,
+        };
+        loader_ok && version_ok
+    }
+
+    /// Project page, for files that have to be downloaded by hand
+    pub fn website_url(&self) -> String {
+        format!("https://www.curseforge.com/projects/{}", self.mod_id)
+    }
+
+    /// Marked as client-only - no use on a server
+    pub fn client_only(&self) -> bool {
+        self.game_versions.iter().any(|v| v == "Client") && !self.game_versions.iter().any(|v| v == "Server")
+    }
+}
+
+/// manifest.json at the root of a modpack zip
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    pub minecraft: ManifestMinecraft,
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<ManifestLoader>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ManifestLoader {
+    /// e.g. "forge-47.2.0" or "fabric-0.15.7"
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestFile {
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_string()
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl Manifest {
+    pub fn read(archive: &Path) -> Result<Self, String> {
+        let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid modpack: {}", e))?;
+        let entry = zip
+            .by_name("manifest.json")
+            .map_err(|_| "The modpack has no manifest.json".to_string())?;
+        serde_json::from_reader(entry).map_err(|e| format!("Invalid modpack manifest: {}", e))
+    }
+
+    pub fn primary_loader(&self) -> Option<&str> {
+        self.minecraft
+            .mod_loaders
+            .iter()
+            .find(|l| l.primary)
+            .or_else(|| self.minecraft.mod_loaders.first())
+            .map(|l| l.id.as_str())
+    }
+}
+
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<FingerprintMatch>,
+}
+
+#[derive(Deserialize)]
+struct FingerprintMatch {
+    file: File,
+}
+
+impl CurseForge {
+    pub fn new(api_key: Option<&str>) -> Result<Self, String> {
+        let api_key = api_key
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .ok_or("Add a CurseForge API key in Settings first")?;
+        Ok(Self {
+            api_key: api_key.to_string(),
+            http: super::http_client(),
+        })
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, String> {
+        let response = request
+            .header("x-api-key", &self.api_key)
+            .header("Accept", "application/json")
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| format!("CurseForge unreachable: {}", e))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err("CurseForge rejected the API key".to_string());
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err("Not found on CurseForge".to_string());
+        }
+        if !status.is_success() {
+            return Err(format!("CurseForge returned {}", status));
+        }
+        let data: Data<T> = response
+            .json()
+            .await
+            .map_err(|e| format!("Unexpected CurseForge response: {}", e))?;
+        Ok(data.data)
+    }
+
+    /// Search one class (mods, plugins or modpacks), limited to what the target can run
+    pub async fn search(&self, query: &str, class_id: u32, target: Option<&ContentTarget>) -> Result<Vec<Project>, String> {
+        let mut params = vec![
+            ("gameId", MINECRAFT.to_string()),
+            ("classId", class_id.to_string()),
+            ("searchFilter", query.to_string()),
+            ("pageSize", SEARCH_LIMIT.to_string()),
+            // Most downloaded first
+            ("sortField", "6".to_string()),
+            ("sortOrder", "desc".to_string()),
+        ];
+        if let Some(target) = target.filter(|_| class_id != CLASS_MODPACKS) {
+            if let Some(loader_type) = target.loader.curseforge_loader_type() {
+                params.push(("modLoaderType", loader_type.to_string()));
+            }
+            if let Some(version) = &target.game_version {
+                params.push(("gameVersion", version.clone()));
+            }
+        }
+        let mods: Vec<ModAttributes> = self
+            .send(self.http.get(format!("{}/mods/search", API_URL)).query(&params))
+            .await?;
+        Ok(mods
+            .into_iter()
+            .map(|m| Project {
+                id: m.id,
+                name: m.name,
+                slug: m.slug,
+                summary: m.summary,
+                author: m.authors.into_iter().next().map(|a| a.name).unwrap_or_default(),
+                icon_url: m.logo.map(|l| l.thumbnail_url),
+                downloads: m.download_count as u64,
+            })
+            .collect())
+    }
+
+    /// Files of a mod, newest first - only those that fit the target when there is one
+    pub async fn files(&self, mod_id: u64, target: Option<&ContentTarget>) -> Result<Vec<File>, String> {
+        let mut params = Vec::new();
+        if let Some(target) = target {
+            if let Some(loader_type) = target.loader.curseforge_loader_type() {
+                params.push(("modLoaderType", loader_type.to_string()));
+            }
+            if let Some(version) = &target.game_version {
+                params.push(("gameVersion", version.clone()));
+            }
+        }
+        let mut files: Vec<File> = self
+            .send(self.http.get(format!("{}/mods/{}/files", API_URL, mod_id)).query(&params))
+            .await?;
+        if let Some(target) = target {
+            files.retain(|f| f.fits(target));
+        }
+        files.sort_by(|a, b| b.file_date.cmp(&a.file_date));
+        Ok(files)
+    }
+
+    pub async fn file(&self, mod_id: u64, file_id: u64) -> Result<File, String> {
+        self.send(self.http.get(format!("{}/mods/{}/files/{}", API_URL, mod_id, file_id)))
+            .await
+    }
+
+    /// Look up many files at once - how a modpack's mod list is resolved
+    pub async fn files_by_id(&self, file_ids: &[u64]) -> Result<Vec<File>, String> {
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.send(
+            self.http
+                .post(format!("{}/mods/files", API_URL))
+                .json(&json!({ "fileIds": file_ids })),
+        )
+        .await
+    }
+
+    /// Files with these fingerprints, keyed by fingerprint - unknown files are left out
+    pub async fn identify(&self, fingerprints: &[u32]) -> Result<HashMap<u32, File>, String> {
+        if fingerprints.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let matches: FingerprintMatches = self
+            .send(
+                self.http
+                    .post(format!("{}/fingerprints/{}", API_URL, MINECRAFT))
+                    .json(&json!({ "fingerprints": fingerprints })),
+            )
+            .await?;
+        Ok(matches
+            .exact_matches
+            .into_iter()
+            .map(|m| (m.file.file_fingerprint, m.file))
+            .collect())
+    }
+
+    pub async fn project_names(&self, mod_ids: &[u64]) -> Result<HashMap<u64, String>, String> {
+        if mod_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mods: Vec<ModAttributes> = self
+            .send(self.http.post(format!("{}/mods", API_URL)).json(&json!({ "modIds": mod_ids })))
+            .await?;
+        Ok(mods.into_iter().map(|m| (m.id, m.name)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::loader::Loader;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_file_fits_loader_and_game_version() {
+        let file: File = serde_json::from_value(json!({
+            "id": 5000,
+            "modId": 238222,
+            "displayName": "JEI 15.2.0",
+            "fileName": "jei-1.20.1-forge-15.2.0.27.jar",
+            "gameVersions": ["1.20.1", "Forge", "Server"],
+            "hashes": [{ "value": "abc123", "algo": 1 }, { "value": "def456", "algo": 2 }],
+        }))
+        .unwrap();
+        assert_eq!(file.sha1(), Some("abc123"));
+
+        let forge = ContentTarget {
+            loader: Loader::Forge,
+            game_version: Some("1.20.1".to_string()),
+            dir: PathBuf::from("mods"),
+        };
+        assert!(file.fits(&forge));
+        assert!(!file.fits(&ContentTarget { loader: Loader::Fabric, ..forge.clone() }));
+        assert!(!file.fits(&ContentTarget { game_version: Some("1.19.2".to_string()), ..forge }));
+    }
+}
@@ -0,0 +1,171 @@
+// Dependencies - install the libraries a mod requires next to it and point out the ones
+// it can't run with, so a server doesn't crash-loop on a missing or clashing jar
+
+use super::curseforge::{CurseForge, File};
+use super::loader::ContentTarget;
+use super::modrinth::{self, Version};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Stop following chains past this many jars - something is off if a mod needs more
+const MAX_DEPENDENCIES: usize = 32;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Resolution {
+    /// Files downloaded for required dependencies
+    pub installed: Vec<String>,
+    /// Dependencies that couldn't be installed and installed mods that clash
+    pub warnings: Vec<String>,
+}
+
+/// The project a dependency points at and the exact version, when it names one
+async fn modrinth_dependency(dependency: &modrinth::Dependency) -> Result<(String, Option<Version>), String> {
+    if let Some(version_id) = &dependency.version_id {
+        let version = modrinth::version(version_id).await?;
+        return Ok((version.project_id.clone(), Some(version)));
+    }
+    let project = dependency.project_id.clone().ok_or("A dependency names neither project nor version")?;
+    Ok((project, None))
+}
+
+/// Install what `root` requires from Modrinth. `installed` holds the project ids already in
+/// the content folder.
+pub async fn resolve_modrinth(root: &Version, target: &ContentTarget, installed: &HashSet<String>) -> Resolution {
+    let mut resolution = Resolution::default();
+    let mut seen: HashSet<String> = installed.clone();
+    seen.insert(root.project_id.clone());
+    let mut missing: Vec<String> = Vec::new();
+    let mut clashing: Vec<String> = Vec::new();
+    let mut queue: VecDeque<modrinth::Dependency> = root.dependencies.iter().cloned().collect();
+
+    while let Some(dependency) = queue.pop_front() {
+        if resolution.installed.len() >= MAX_DEPENDENCIES {
+            resolution.warnings.push("Stopped following dependencies - there are too many".to_string());
+            break;
+        }
+        let wanted = dependency.dependency_type.as_str();
+        if wanted != "required" && wanted != "incompatible" {
+            continue;
+        }
+        let (project, pinned) = match modrinth_dependency(&dependency).await {
+            Ok(found) => found,
+            Err(e) => {
+                resolution.warnings.push(format!("Couldn't look up a dependency: {}", e));
+                continue;
+            }
+        };
+        if wanted == "incompatible" {
+            if installed.contains(&project) {
+                clashing.push(project);
+            }
+            continue;
+        }
+        if !seen.insert(project.clone()) {
+            continue;
+        }
+
+        let version = match pinned.filter(|v| v.fits(target)) {
+            Some(version) => Some(version),
+            None => modrinth::versions(&project, target).await.ok().and_then(|v| v.into_iter().next()),
+        };
+        let Some(version) = version else {
+            missing.push(project);
+            continue;
+        };
+        let Some(file) = version.primary_file() else {
+            missing.push(project);
+            continue;
+        };
+        match modrinth::download(file, &target.dir).await {
+            Ok(_) => {
+                resolution.installed.push(file.filename.clone());
+                queue.extend(version.dependencies.iter().cloned());
+            }
+            Err(e) => resolution.warnings.push(e),
+        }
+    }
+
+    let mut ids = missing.clone();
+    ids.extend(clashing.iter().cloned());
+    let names = modrinth::project_names(&ids).await.unwrap_or_default();
+    let name = |id: &String| names.get(id).cloned().unwrap_or_else(|| id.clone());
+    for id in &missing {
+        resolution.warnings.push(format!("Required dependency {} has no version for this server", name(id)));
+    }
+    for id in &clashing {
+        resolution.warnings.push(format!("{} is installed but doesn't work together with this mod", name(id)));
+    }
+    resolution
+}
+
+/// Install what `root` requires from CurseForge. `installed` holds the mod ids already in
+/// the content folder.
+pub async fn resolve_curseforge(
+    client: &CurseForge,
+    root: &File,
+    target: &ContentTarget,
+    installed: &HashSet<u64>,
+) -> Resolution {
+    let mut resolution = Resolution::default();
+    let mut seen: HashSet<u64> = installed.clone();
+    seen.insert(root.mod_id);
+    let mut missing: Vec<u64> = Vec::new();
+    let mut clashing: Vec<u64> = Vec::new();
+    let mut manual: HashMap<u64, String> = HashMap::new();
+    let mut queue: VecDeque<_> = root.dependencies.iter().cloned().collect();
+
+    while let Some(dependency) = queue.pop_front() {
+        if resolution.installed.len() >= MAX_DEPENDENCIES {
+            resolution.warnings.push("Stopped following dependencies - there are too many".to_string());
+            break;
+        }
+        if dependency.incompatible() {
+            if installed.contains(&dependency.mod_id) {
+                clashing.push(dependency.mod_id);
+            }
+            continue;
+        }
+        if !dependency.required() || !seen.insert(dependency.mod_id) {
+            continue;
+        }
+
+        let newest = match client.files(dependency.mod_id, Some(target)).await {
+            Ok(files) => files.into_iter().next(),
+            Err(e) => {
+                resolution.warnings.push(format!("Couldn't look up a dependency: {}", e));
+                continue;
+            }
+        };
+        let Some(file) = newest else {
+            missing.push(dependency.mod_id);
+            continue;
+        };
+        let Some(url) = file.download_url.as_deref() else {
+            manual.insert(dependency.mod_id, file.website_url());
+            continue;
+        };
+        match super::download_file(url, &file.file_name, file.sha1(), &target.dir).await {
+            Ok(_) => {
+                resolution.installed.push(file.file_name.clone());
+                queue.extend(file.dependencies.iter().cloned());
+            }
+            Err(e) => resolution.warnings.push(e),
+        }
+    }
+
+    let mut ids = missing.clone();
+    ids.extend(clashing.iter().copied());
+    ids.extend(manual.keys().copied());
+    let names = client.project_names(&ids).await.unwrap_or_default();
+    let name = |id: &u64| names.get(id).cloned().unwrap_or_else(|| id.to_string());
+    for id in &missing {
+        resolution.warnings.push(format!("Required dependency {} has no file for this server", name(id)));
+    }
+    for (id, url) in &manual {
+        resolution.warnings.push(format!("Required dependency {} has to be downloaded by hand: {}", name(id), url));
+    }
+    for id in &clashing {
+        resolution.warnings.push(format!("{} is installed but doesn't work together with this mod", name(id)));
+    }
+    resolution
+}
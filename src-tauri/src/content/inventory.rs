@@ -0,0 +1,90 @@
+// Inventory - the jars in a server's plugins/ or mods/ folder with the hashes Modrinth
+// (sha1) and CurseForge (murmur2 fingerprint) identify files by
+
+use super::loader::ContentTarget;
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct ScannedJar {
+    pub path: PathBuf,
+    pub size: u64,
+    pub sha1: String,
+    pub fingerprint: u32,
+}
+
+impl ScannedJar {
+    pub fn file_name(&self) -> String {
+        self.path.file_name().unwrap_or_default().to_string_lossy().to_string()
+    }
+}
+
+pub fn scan(target: &ContentTarget) -> Vec<ScannedJar> {
+    let Ok(entries) = std::fs::read_dir(&target.dir) else { return Vec::new() };
+    let mut jars: Vec<ScannedJar> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "jar"))
+        .filter_map(|path| match scan_file(&path) {
+            Ok(jar) => Some(jar),
+            Err(e) => {
+                tracing::warn!("Skipping {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect();
+    jars.sort_by_key(|j| j.file_name().to_lowercase());
+    jars
+}
+
+fn scan_file(path: &Path) -> std::io::Result<ScannedJar> {
+    let bytes = std::fs::read(path)?;
+    Ok(ScannedJar {
+        path: path.to_path_buf(),
+        size: bytes.len() as u64,
+        sha1: hex::encode(Sha1::digest(&bytes)),
+        fingerprint: curseforge_fingerprint(&bytes),
+    })
+}
+
+/// CurseForge's file fingerprint: 32-bit MurmurHash2 (seed 1) of the file with all
+/// tabs, newlines, carriage returns and spaces removed
+pub fn curseforge_fingerprint(bytes: &[u8]) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    let data: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, 9 | 10 | 13 | 32))
+        .collect();
+
+    let mut hash: u32 = 1 ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        hash = hash.wrapping_mul(M) ^ k;
+    }
+    let rest = chunks.remainder();
+    if !rest.is_empty() {
+        for (i, byte) in rest.iter().enumerate() {
+            hash ^= (*byte as u32) << (8 * i);
+        }
+        hash = hash.wrapping_mul(M);
+    }
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(M);
+    hash ^ (hash >> 15)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace() {
+        assert_eq!(curseforge_fingerprint(b"hello world"), curseforge_fingerprint(b"hello\r\n\tworld"));
+        assert_ne!(curseforge_fingerprint(b"hello"), curseforge_fingerprint(b"hellp"));
+    }
+}
@@ -0,0 +1,176 @@
+// Loader detection - which mod loader a Minecraft server runs and for which game version,
+// worked out from the files in its data folder
+
+use serde::Serialize;
+use serverwave_core::server::Server;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Loader {
+    /// Paper, Purpur, Spigot and Bukkit - they all load Bukkit plugins
+    Paper,
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+}
+
+impl Loader {
+    /// Folder the loader picks jars up from, relative to the data folder
+    pub fn folder(&self) -> &'static str {
+        match self {
+            Loader::Paper => "plugins",
+            _ => "mods",
+        }
+    }
+
+    /// Modrinth loader names this server can run
+    pub fn modrinth_loaders(&self) -> &'static [&'static str] {
+        match self {
+            Loader::Paper => &["paper", "purpur", "spigot", "bukkit"],
+            Loader::Fabric => &["fabric"],
+            // Quilt runs most Fabric mods
+            Loader::Quilt => &["quilt", "fabric"],
+            Loader::Forge => &["forge"],
+            Loader::NeoForge => &["neoforge"],
+        }
+    }
+
+    pub fn modrinth_project_type(&self) -> &'static str {
+        match self {
+            Loader::Paper => "plugin",
+            _ => "mod",
+        }
+    }
+
+    /// CurseForge `modLoaderType`, None for plugins which aren't tagged with one
+    pub fn curseforge_loader_type(&self) -> Option<u32> {
+        match self {
+            Loader::Paper => None,
+            Loader::Forge => Some(1),
+            Loader::Fabric => Some(4),
+            Loader::Quilt => Some(5),
+            Loader::NeoForge => Some(6),
+        }
+    }
+
+    /// How CurseForge lists the loader among a file's game versions
+    pub fn curseforge_name(&self) -> Option<&'static str> {
+        match self {
+            Loader::Paper => None,
+            Loader::Forge => Some("Forge"),
+            Loader::Fabric => Some("Fabric"),
+            Loader::Quilt => Some("Quilt"),
+            Loader::NeoForge => Some("NeoForge"),
+        }
+    }
+}
+
+/// What content for a server has to match, and where it goes
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentTarget {
+    pub loader: Loader,
+    /// None when the version couldn't be worked out - compatibility isn't checked then
+    pub game_version: Option<String>,
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+impl ContentTarget {
+    pub fn for_server(server: &Server) -> Result<Self, String> {
+        let loader = detect_loader(&server.data_path, &server.game_type.0)
+            .ok_or_else(|| format!("'{}' doesn't run a plugin or mod loader", server.name))?;
+        Ok(Self {
+            loader,
+            game_version: detect_game_version(&server.data_path, server.config.get("MINECRAFT_VERSION")),
+            dir: server.data_path.join(loader.folder()),
+        })
+    }
+}
+
+pub fn detect_loader(data_path: &Path, game_type: &str) -> Option<Loader> {
+    let exists = |path: &str| data_path.join(path).exists();
+
+    if exists("libraries/net/neoforged") {
+        return Some(Loader::NeoForge);
+    }
+    if exists("libraries/net/minecraftforge") {
+        return Some(Loader::Forge);
+    }
+    if exists("quilt-server-launch.jar") || exists("libraries/org/quiltmc") {
+        return Some(Loader::Quilt);
+    }
+    if exists(".fabric") || exists("fabric-server-launch.jar") || exists("libraries/net/fabricmc") {
+        return Some(Loader::Fabric);
+    }
+    if exists("bukkit.yml") || exists("spigot.yml") || exists("config/paper-global.yml") || exists("plugins") {
+        return Some(Loader::Paper);
+    }
+
+    // Nothing installed yet - go by the game
+    let game_type = game_type.to_lowercase();
+    if game_type.contains("neoforge") {
+        Some(Loader::NeoForge)
+    } else if game_type.contains("forge") {
+        Some(Loader::Forge)
+    } else if game_type.contains("quilt") {
+        Some(Loader::Quilt)
+    } else if game_type.contains("fabric") {
+        Some(Loader::Fabric)
+    } else if ["minecraft-java", "paper", "purpur", "spigot"].iter().any(|g| game_type.contains(g)) {
+        Some(Loader::Paper)
+    } else {
+        None
+    }
+}
+
+pub fn detect_game_version(data_path: &Path, configured: Option<&String>) -> Option<String> {
+    if let Some(version) = configured.filter(|v| !v.is_empty() && !v.eq_ignore_ascii_case("latest")) {
+        return Some(version.clone());
+    }
+
+    // Paper: {"currentVersion": "git-Paper-196 (MC: 1.20.4)"}
+    if let Ok(content) = std::fs::read_to_string(data_path.join("version_history.json")) {
+        if let Some(version) = content
+            .split("(MC: ")
+            .nth(1)
+            .and_then(|rest| rest.split(')').next())
+        {
+            return Some(version.trim().to_string());
+        }
+    }
+
+    // The 1.18+ server bundler unpacks into versions/<version>/
+    let entries = std::fs::read_dir(data_path.join("versions")).ok()?;
+    let mut versions: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_digit()))
+        .collect();
+    versions.sort();
+    versions.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_loader_from_files_then_game_type() {
+        let dir = std::env::temp_dir().join(format!("serverwave-loader-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(detect_loader(&dir, "minecraft-java"), Some(Loader::Paper));
+        assert_eq!(detect_loader(&dir, "valheim"), None);
+
+        std::fs::create_dir_all(dir.join(".fabric")).unwrap();
+        assert_eq!(detect_loader(&dir, "minecraft-java"), Some(Loader::Fabric));
+
+        std::fs::write(dir.join("version_history.json"), r#"{"currentVersion":"git-Paper-196 (MC: 1.20.4)"}"#).unwrap();
+        assert_eq!(detect_game_version(&dir, Some(&"latest".to_string())), Some("1.20.4".to_string()));
+        assert_eq!(detect_game_version(&dir, Some(&"1.21.1".to_string())), Some("1.21.1".to_string()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
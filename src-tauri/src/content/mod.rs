@@ -0,0 +1,154 @@
+// Content module - Minecraft mods and plugins: which loader a server runs and where to get jars for it
+
+pub mod cache;
+pub mod curseforge;
+pub mod dependencies;
+pub mod inventory;
+pub mod loader;
+pub mod modrinth;
+pub mod server_jar;
+
+use sha1::{Digest, Sha1};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Both APIs ask for a User-Agent that identifies the app
+pub(crate) fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .user_agent(concat!("serverwave-anywhere/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Download `url` into `dir/file_name`, checking the sha1 when one is known. Goes through
+/// a .part file so a failed download never replaces a working jar. Files with a known sha1
+/// come from the shared cache when another server already downloaded them.
+pub async fn download_file(url: &str, file_name: &str, sha1: Option<&str>, dir: &Path) -> Result<PathBuf, String> {
+    let name = safe_name(file_name)?;
+    if let Some(path) = sha1.and_then(|sha1| from_cache(sha1, &name, dir)) {
+        return Ok(path);
+    }
+    let (path, actual) = fetch(url, &name, sha1, dir).await?;
+    if sha1.is_some() {
+        cache::store(&path, &actual);
+    }
+    Ok(path)
+}
+
+/// Download a URL whose content never changes - a specific build or version - through the
+/// shared cache, which remembers what the URL pointed at
+pub async fn download_immutable(url: &str, file_name: &str, dir: &Path) -> Result<PathBuf, String> {
+    let name = safe_name(file_name)?;
+    if let Some(path) = cache::url_sha1(url).and_then(|sha1| from_cache(&sha1, &name, dir)) {
+        return Ok(path);
+    }
+    let (path, actual) = fetch(url, &name, None, dir).await?;
+    cache::store(&path, &actual);
+    cache::remember_url(url, &actual);
+    Ok(path)
+}
+
+/// Names come from the APIs - never let one escape the folder
+fn safe_name(file_name: &str) -> Result<String, String> {
+    Ok(Path::new(file_name)
+        .file_name()
+        .ok_or_else(|| format!("Invalid file name '{}'", file_name))?
+        .to_string_lossy()
+        .to_string())
+}
+
+fn from_cache(sha1: &str, name: &str, dir: &Path) -> Option<PathBuf> {
+    std::fs::create_dir_all(dir).ok()?;
+    let partial = dir.join(format!("{}.part", name));
+    if !cache::fetch(sha1, &partial) {
+        return None;
+    }
+    let path = dir.join(name);
+    std::fs::rename(&partial, &path).ok()?;
+    tracing::debug!("{} came from the content cache", name);
+    Some(path)
+}
+
+/// Download into `dir/name`, returning the path and the content's sha1
+async fn fetch(url: &str, name: &str, sha1: Option<&str>, dir: &Path) -> Result<(PathBuf, String), String> {
+    let mut response = http_client()
+        .get(url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Download of {} failed: {}", name, e))?;
+
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = dir.join(name);
+    let partial = dir.join(format!("{}.part", name));
+    let mut file = std::fs::File::create(&partial).map_err(|e| e.to_string())?;
+    let mut hasher = Sha1::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download of {} failed: {}", name, e))?
+    {
+        hasher.update(&chunk);
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+    }
+    drop(file);
+
+    let actual = hex::encode(hasher.finalize());
+    if let Some(expected) = sha1 {
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = std::fs::remove_file(&partial);
+            return Err(format!("{} doesn't match its checksum, not installing it", name));
+        }
+    }
+    std::fs::rename(&partial, &path).map_err(|e| e.to_string())?;
+    Ok((path, actual))
+}
+
+/// Unpack the entries of a zip under `prefix` into `dest`, returning how many files were written
+pub fn extract_zip(archive: &Path, prefix: &str, dest: &Path) -> Result<usize, String> {
+    let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    let mut written = 0;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        // enclosed_name rejects absolute paths and ..
+        let Some(name) = entry.enclosed_name() else { continue };
+        let Ok(relative) = name.strip_prefix(prefix) else { continue };
+        if relative.components().all(|c| matches!(c, Component::CurDir)) {
+            continue;
+        }
+        let target = dest.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&target).map_err(|e| e.to_string())?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut out = std::fs::File::create(&target).map_err(|e| e.to_string())?;
+        std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Top-level folder every entry of a zip sits in, if there is one - server packs are
+/// often zipped with their folder
+pub fn zip_root(archive: &Path) -> Result<Option<String>, String> {
+    let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+    let zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    let mut root: Option<String> = None;
+    for name in zip.file_names() {
+        let Some((first, _)) = name.split_once('/') else { return Ok(None) };
+        match &root {
+            Some(existing) if existing != first => return Ok(None),
+            Some(_) => {}
+            None => root = Some(first.to_string()),
+        }
+    }
+    Ok(root)
+}
@@ -0,0 +1,256 @@
+// Modrinth - search projects, pick versions that fit a server and download their jars
+
+use super::loader::ContentTarget;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const API_URL: &str = "https://api.modrinth.com/v2";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub slug: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub downloads: u64,
+    pub project_type: String,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Version {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    #[serde(default)]
+    pub date_published: String,
+    pub files: Vec<VersionFile>,
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dependency {
+    #[serde(default)]
+    pub version_id: Option<String>,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// "required", "optional", "incompatible" or "embedded"
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionFile {
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    pub hashes: HashMap<String, String>,
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl Version {
+    /// The jar to install - the primary file, or the only one
+    pub fn primary_file(&self) -> Option<&VersionFile> {
+        self.files.iter().find(|f| f.primary).or_else(|| self.files.first())
+    }
+
+    pub fn fits(&self, target: &ContentTarget) -> bool {
+        let loader_ok = self
+            .loaders
+            .iter()
+            .any(|l| target.loader.modrinth_loaders().contains(&l.as_str()));
+        let version_ok = match &target.game_version {
+            Some(version) => self.game_versions.contains(version),
+            None => true,
+        };
+        loader_ok && version_ok
+    }
+}
+
+async fn parse<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, String> {
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err("Not found on Modrinth".to_string());
+    }
+    if !status.is_success() {
+        return Err(format!("Modrinth returned {}", status));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected Modrinth response: {}", e))
+}
+
+async fn get<T: DeserializeOwned>(path: &str, query: &[(&str, String)]) -> Result<T, String> {
+    let response = super::http_client()
+        .get(format!("{}{}", API_URL, path))
+        .query(query)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Modrinth unreachable: {}", e))?;
+    parse(response).await
+}
+
+/// Search projects, limited to what the target can load when there is one
+pub async fn search(query: &str, target: Option<&ContentTarget>, limit: u32) -> Result<Vec<SearchHit>, String> {
+    let mut facets: Vec<Vec<String>> = Vec::new();
+    match target {
+        Some(target) => {
+            facets.push(vec![format!("project_type:{}", target.loader.modrinth_project_type())]);
+            facets.push(
+                target
+                    .loader
+                    .modrinth_loaders()
+                    .iter()
+                    .map(|l| format!("categories:{}", l))
+                    .collect(),
+            );
+            if let Some(version) = &target.game_version {
+                facets.push(vec![format!("versions:{}", version)]);
+            }
+        }
+        None => facets.push(vec!["project_type:mod".to_string(), "project_type:plugin".to_string()]),
+    }
+    // Servers can't use client-only mods
+    facets.push(vec!["server_side:required".to_string(), "server_side:optional".to_string()]);
+
+    let response: SearchResponse = get(
+        "/search",
+        &[
+            ("query", query.to_string()),
+            ("facets", json!(facets).to_string()),
+            ("limit", limit.to_string()),
+        ],
+    )
+    .await?;
+    Ok(response.hits)
+}
+
+/// Versions of a project that fit the target, newest first
+pub async fn versions(project: &str, target: &ContentTarget) -> Result<Vec<Version>, String> {
+    let mut query = vec![("loaders", json!(target.loader.modrinth_loaders()).to_string())];
+    if let Some(version) = &target.game_version {
+        query.push(("game_versions", json!([version]).to_string()));
+    }
+    let versions: Vec<Version> = get(&format!("/project/{}/version", project), &query).await?;
+    Ok(versions.into_iter().filter(|v| v.fits(target)).collect())
+}
+
+pub async fn version(id: &str) -> Result<Version, String> {
+    get(&format!("/version/{}", id), &[]).await
+}
+
+/// Versions of the files with these sha1 hashes, keyed by hash - unknown files are left out
+pub async fn identify(hashes: &[String]) -> Result<HashMap<String, Version>, String> {
+    if hashes.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let response = super::http_client()
+        .post(format!("{}/version_files", API_URL))
+        .json(&json!({ "hashes": hashes, "algorithm": "sha1" }))
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Modrinth unreachable: {}", e))?;
+    parse(response).await
+}
+
+/// Newest version that fits the target for each file hash
+pub async fn latest_for(hashes: &[String], target: &ContentTarget) -> Result<HashMap<String, Version>, String> {
+    if hashes.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let mut body = json!({
+        "hashes": hashes,
+        "algorithm": "sha1",
+        "loaders": target.loader.modrinth_loaders(),
+    });
+    if let Some(version) = &target.game_version {
+        body["game_versions"] = json!([version]);
+    }
+    let response = super::http_client()
+        .post(format!("{}/version_files/update", API_URL))
+        .json(&body)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Modrinth unreachable: {}", e))?;
+    parse(response).await
+}
+
+#[derive(Deserialize)]
+struct ProjectTitle {
+    id: String,
+    title: String,
+}
+
+pub async fn project_names(ids: &[String]) -> Result<HashMap<String, String>, String> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let projects: Vec<ProjectTitle> = get("/projects", &[("ids", json!(ids).to_string())]).await?;
+    Ok(projects.into_iter().map(|p| (p.id, p.title)).collect())
+}
+
+/// Download a version's file into `dir`
+pub async fn download(file: &VersionFile, dir: &Path) -> Result<PathBuf, String> {
+    super::download_file(&file.url, &file.filename, file.hashes.get("sha1").map(String::as_str), dir).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::loader::Loader;
+
+    fn version(loaders: &[&str], game_versions: &[&str]) -> Version {
+        Version {
+            id: "v1".to_string(),
+            project_id: "p1".to_string(),
+            name: "Test".to_string(),
+            version_number: "1.0.0".to_string(),
+            game_versions: game_versions.iter().map(|v| v.to_string()).collect(),
+            loaders: loaders.iter().map(|l| l.to_string()).collect(),
+            date_published: String::new(),
+            files: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_version_fits_loader_and_game_version() {
+        let target = ContentTarget {
+            loader: Loader::Paper,
+            game_version: Some("1.20.4".to_string()),
+            dir: PathBuf::from("plugins"),
+        };
+        assert!(version(&["bukkit", "spigot"], &["1.20.4"]).fits(&target));
+        assert!(!version(&["fabric"], &["1.20.4"]).fits(&target));
+        assert!(!version(&["paper"], &["1.19.2"]).fits(&target));
+
+        let unknown_version = ContentTarget { game_version: None, ..target };
+        assert!(version(&["paper"], &["1.19.2"]).fits(&unknown_version));
+    }
+}
@@ -0,0 +1,291 @@
+// Server jars - where to download the Paper, Purpur, Fabric or Forge server for a Minecraft version
+
+use super::loader::Loader;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const PAPER_API: &str = "https://api.papermc.io/v2/projects/paper";
+const PURPUR_API: &str = "https://api.purpurmc.org/v2/purpur";
+const FABRIC_META: &str = "https://meta.fabricmc.net/v2";
+const FORGE_PROMOTIONS: &str = "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+const FORGE_MAVEN: &str = "https://maven.minecraftforge.net/net/minecraftforge/forge";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Flavor {
+    Paper,
+    Purpur,
+    Fabric,
+    Forge,
+}
+
+impl Flavor {
+    pub fn loader(&self) -> Loader {
+        match self {
+            Flavor::Paper | Flavor::Purpur => Loader::Paper,
+            Flavor::Fabric => Loader::Fabric,
+            Flavor::Forge => Loader::Forge,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Flavor::Paper => "Paper",
+            Flavor::Purpur => "Purpur",
+            Flavor::Fabric => "Fabric",
+            Flavor::Forge => "Forge",
+        }
+    }
+}
+
+/// What to download for a flavor and Minecraft version
+#[derive(Debug, Clone)]
+pub struct ServerJar {
+    pub url: String,
+    /// Paper/Purpur build, Fabric loader or Forge version
+    pub build: String,
+}
+
+async fn get<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    let response = super::http_client()
+        .get(url)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("{} unreachable: {}", host(url), e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("Not found on {}", host(url)));
+    }
+    if !status.is_success() {
+        return Err(format!("{} returned {}", host(url), status));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected response from {}: {}", host(url), e))
+}
+
+fn host(url: &str) -> &str {
+    url.split("://").nth(1).and_then(|rest| rest.split('/').next()).unwrap_or(url)
+}
+
+#[derive(Deserialize)]
+struct VersionList {
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct FabricGameVersion {
+    version: String,
+    stable: bool,
+}
+
+#[derive(Deserialize)]
+struct Promotions {
+    promos: HashMap<String, String>,
+}
+
+/// Minecraft versions the flavor has a server for, newest first
+pub async fn versions(flavor: Flavor) -> Result<Vec<String>, String> {
+    let mut versions = match flavor {
+        Flavor::Paper => get::<VersionList>(PAPER_API).await?.versions,
+        Flavor::Purpur => get::<VersionList>(PURPUR_API).await?.versions,
+        Flavor::Fabric => {
+            let all: Vec<FabricGameVersion> = get(&format!("{}/versions/game", FABRIC_META)).await?;
+            // Already newest first
+            return Ok(all.into_iter().filter(|v| v.stable).map(|v| v.version).collect());
+        }
+        Flavor::Forge => {
+            let promotions: Promotions = get(FORGE_PROMOTIONS).await?;
+            let mut versions: Vec<String> = promotions
+                .promos
+                .keys()
+                .filter_map(|key| key.rsplit_once('-').map(|(version, _)| version.to_string()))
+                .collect();
+            versions.sort_by(|a, b| compare_versions(a, b));
+            versions.dedup();
+            versions
+        }
+    };
+    versions.reverse();
+    Ok(versions)
+}
+
+/// Numeric comparison so 1.9 sorts before 1.10
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parts = |v: &str| -> Vec<u32> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    parts(a).cmp(&parts(b))
+}
+
+#[derive(Deserialize)]
+struct PaperBuilds {
+    builds: Vec<PaperBuild>,
+}
+
+#[derive(Deserialize)]
+struct PaperBuild {
+    build: u32,
+    downloads: HashMap<String, PaperDownload>,
+}
+
+#[derive(Deserialize)]
+struct PaperDownload {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct PurpurVersion {
+    builds: PurpurBuilds,
+}
+
+#[derive(Deserialize)]
+struct PurpurBuilds {
+    latest: String,
+}
+
+#[derive(Deserialize)]
+struct FabricLoader {
+    loader: FabricComponent,
+}
+
+#[derive(Deserialize)]
+struct FabricComponent {
+    version: String,
+    #[serde(default)]
+    stable: bool,
+}
+
+/// Latest server for `version` - the newest build, loader or recommended Forge
+pub async fn resolve(flavor: Flavor, version: &str) -> Result<ServerJar, String> {
+    match flavor {
+        Flavor::Paper => {
+            let builds: PaperBuilds = get(&format!("{}/versions/{}/builds", PAPER_API, version)).await?;
+            let build = builds.builds.last().ok_or_else(|| format!("Paper has no builds for {}", version))?;
+            let file = build
+                .downloads
+                .get("application")
+                .ok_or_else(|| format!("Paper build {} has no server jar", build.build))?;
+            Ok(ServerJar {
+                url: format!("{}/versions/{}/builds/{}/downloads/{}", PAPER_API, version, build.build, file.name),
+                build: build.build.to_string(),
+            })
+        }
+        Flavor::Purpur => {
+            let purpur: PurpurVersion = get(&format!("{}/{}", PURPUR_API, version)).await?;
+            Ok(ServerJar {
+                url: format!("{}/{}/{}/download", PURPUR_API, version, purpur.builds.latest),
+                build: purpur.builds.latest,
+            })
+        }
+        Flavor::Fabric => {
+            let loaders: Vec<FabricLoader> = get(&format!("{}/versions/loader/{}", FABRIC_META, version)).await?;
+            let loader = loaders
+                .iter()
+                .map(|l| &l.loader)
+                .find(|l| l.stable)
+                .or_else(|| loaders.first().map(|l| &l.loader))
+                .ok_or_else(|| format!("Fabric doesn't support {} yet", version))?;
+            let installers: Vec<FabricComponent> = get(&format!("{}/versions/installer", FABRIC_META)).await?;
+            let installer = installers
+                .iter()
+                .find(|i| i.stable)
+                .or_else(|| installers.first())
+                .ok_or("Fabric has no installer")?;
+            // The launcher jar fetches the vanilla server itself on first start
+            Ok(ServerJar {
+                url: format!(
+                    "{}/versions/loader/{}/{}/{}/server/jar",
+                    FABRIC_META, version, loader.version, installer.version
+                ),
+                build: loader.version.clone(),
+            })
+        }
+        Flavor::Forge => {
+            let promotions: Promotions = get(FORGE_PROMOTIONS).await?;
+            let forge = forge_build(&promotions.promos, version)
+                .ok_or_else(|| format!("Forge doesn't support {}", version))?;
+            // An installer - it has to run before there's a server to start
+            let full = format!("{}-{}", version, forge);
+            Ok(ServerJar {
+                url: format!("{}/{}/forge-{}-installer.jar", FORGE_MAVEN, full, full),
+                build: forge,
+            })
+        }
+    }
+}
+
+/// The recommended Forge build for a Minecraft version, or the latest when none is recommended
+fn forge_build(promos: &HashMap<String, String>, version: &str) -> Option<String> {
+    promos
+        .get(&format!("{}-recommended", version))
+        .or_else(|| promos.get(&format!("{}-latest", version)))
+        .cloned()
+}
+
+/// The jar the Forge installer left that `java -jar` can start: the shim of newer versions
+/// or the universal jar of old ones. 1.17 to 1.20.2 only start through run.sh.
+pub fn forge_server_jar(data_path: &Path, version: &str, forge: &str) -> Option<String> {
+    let full = format!("{}-{}", version, forge);
+    [format!("forge-{}-shim.jar", full), format!("forge-{}.jar", full), format!("forge-{}-universal.jar", full)]
+        .into_iter()
+        .find(|name| data_path.join(name).is_file())
+}
+
+/// Files only the given loader uses and that it puts back on start - removed when switching
+/// away so loader detection sees the new one. Worlds, configs and mods aren't touched.
+pub fn clear_loader_files(data_path: &Path, keep: Loader) -> Vec<String> {
+    let owned = [
+        (Loader::Fabric, ".fabric"),
+        (Loader::Fabric, "fabric-server-launch.jar"),
+        (Loader::Fabric, "libraries/net/fabricmc"),
+        (Loader::Quilt, "quilt-server-launch.jar"),
+        (Loader::Quilt, "libraries/org/quiltmc"),
+        (Loader::Forge, "libraries/net/minecraftforge"),
+        (Loader::NeoForge, "libraries/net/neoforged"),
+    ];
+    let mut removed = Vec::new();
+    for (loader, relative) in owned {
+        if loader == keep {
+            continue;
+        }
+        let path = data_path.join(relative);
+        let result = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else if path.is_file() {
+            std::fs::remove_file(&path)
+        } else {
+            continue;
+        };
+        match result {
+            Ok(()) => removed.push(relative.to_string()),
+            Err(e) => tracing::warn!("Couldn't remove {}: {}", path.display(), e),
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forge_build_prefers_recommended() {
+        let promos: HashMap<String, String> = [
+            ("1.20.1-latest", "47.3.0"),
+            ("1.20.1-recommended", "47.2.0"),
+            ("1.21-latest", "51.0.33"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(forge_build(&promos, "1.20.1"), Some("47.2.0".to_string()));
+        assert_eq!(forge_build(&promos, "1.21"), Some("51.0.33".to_string()));
+        assert_eq!(forge_build(&promos, "1.7.10"), None);
+        assert!(compare_versions("1.9", "1.10").is_lt());
+    }
+}
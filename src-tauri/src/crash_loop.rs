@@ -0,0 +1,219 @@
+// Crash-loop protection - a server with auto-restart is started again when it crashes, until
+// it crashes CRASH_LIMIT times within CRASH_WINDOW_MINUTES. Then it's left stopped as
+// CrashLooping with a crash report instead of being restarted forever.
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::{start_server, ServerState};
+use crate::commands::settings::load_app_settings;
+use crate::docker::DockerManager;
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::notify;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serverwave_core::server::{Server, ServerStatus};
+use serverwave_core::{atomic, paths};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+const CRASH_LIMIT: usize = 3;
+const CRASH_WINDOW_MINUTES: i64 = 10;
+/// Time for the crashed process to let go of its files before the restart
+const RESTART_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+const REPORT_LOG_LINES: u32 = 200;
+
+lazy_static::lazy_static! {
+    /// Each server's crashes within the window, oldest first
+    static ref CRASHES: Mutex<HashMap<String, Vec<DateTime<Utc>>>> = Mutex::new(HashMap::new());
+    static ref CRASHED: Mutex<Option<UnboundedSender<Crash>>> = Mutex::new(None);
+}
+
+struct Crash {
+    server_id: String,
+    container_id: String,
+    exit_code: i64,
+}
+
+/// What the server was doing when restarts were given up on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub server_id: String,
+    pub server_name: String,
+    pub game_type: String,
+    /// Id of the image the container ran
+    pub image: Option<String>,
+    /// The crashes that counted, oldest first
+    pub crashes: Vec<DateTime<Utc>>,
+    pub exit_code: i64,
+    /// The end of the console output before the last crash
+    pub log: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+fn get_report_path(server_id: &str) -> PathBuf {
+    paths::app_root().join("crash-reports").join(format!("{}.json", server_id))
+}
+
+/// The server's last crash report, if it ever crash-looped
+pub fn load_report(server_id: &str) -> Option<CrashReport> {
+    atomic::read_json(&get_report_path(server_id))
+}
+
+fn save_report(report: &CrashReport) -> Result<(), String> {
+    let path = get_report_path(&report.server_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    atomic::write_atomic(&path, content).map_err(|e| e.to_string())
+}
+
+/// The user took over - earlier crashes no longer count
+pub fn forget(server_id: &str) {
+    CRASHES.lock().unwrap().remove(server_id);
+}
+
+/// Add a crash, dropping the ones that fell out of the window. True once there are too many.
+fn record(history: &mut Vec<DateTime<Utc>>, at: DateTime<Utc>) -> bool {
+    history.retain(|time| at - *time < Duration::minutes(CRASH_WINDOW_MINUTES));
+    history.push(at);
+    history.len() >= CRASH_LIMIT
+}
+
+/// Handle crashes as the log streams notice them. Restarting runs here rather than in the
+/// stream, which the restart replaces.
+pub fn spawn_restarter(app: AppHandle) {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    *CRASHED.lock().unwrap() = Some(tx);
+    tauri::async_runtime::spawn(async move {
+        while let Some(crash) = rx.recv().await {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move { handle_crash(&app, crash).await });
+        }
+    });
+}
+
+/// A server stopped with a non-zero exit code
+pub fn report(server_id: &str, container_id: &str, exit_code: i64) {
+    if let Some(tx) = CRASHED.lock().unwrap().as_ref() {
+        let _ = tx.send(Crash {
+            server_id: server_id.to_string(),
+            container_id: container_id.to_string(),
+            exit_code,
+        });
+    }
+}
+
+/// Restart a server that crashed, if it has auto-restart on and hasn't been crashing over and over
+async fn handle_crash(app: &AppHandle, crash: Crash) {
+    let Crash { server_id, container_id, exit_code } = crash;
+    let state = app.state::<ServerState>();
+    let Ok(server) = state.configs.get(&server_id) else { return };
+    if !server.auto_restart {
+        return;
+    }
+    let Ok(docker) = app.state::<DockerState>().manager() else { return };
+
+    let crashes = {
+        let mut all = CRASHES.lock().unwrap();
+        let history = all.entry(server_id.clone()).or_default();
+        if record(history, Utc::now()) {
+            all.remove(&server_id)
+        } else {
+            events::emit(app, ServerEvent::log(
+                &server_id,
+                format!(
+                    "[Serverwave] Crashed with exit code {} - restarting ({} of {} crashes in {} minutes)",
+                    exit_code,
+                    history.len(),
+                    CRASH_LIMIT,
+                    CRASH_WINDOW_MINUTES
+                ),
+            ));
+            None
+        }
+    };
+    if let Some(crashes) = crashes {
+        give_up(app, &docker, &server, &container_id, exit_code, crashes).await;
+        return;
+    }
+
+    tokio::time::sleep(RESTART_DELAY).await;
+    // Stopped, started or deleted by hand in the meantime
+    let Ok(server) = state.configs.get(&server_id) else { return };
+    if server.status == ServerStatus::Stopped
+        || docker.get_container_status(&container_id).await.ok() == Some(ServerStatus::Running)
+    {
+        return;
+    }
+    // It fit in memory a moment ago
+    if let Err(e) = start_server(server_id.clone(), Some(true), None, app.clone(), app.state(), app.state(), app.state()).await {
+        tracing::warn!("Failed to restart {} after a crash: {}", server.name, e);
+        events::emit(app, ServerEvent::alert(
+            &server_id,
+            AlertLevel::Error,
+            format!("The server crashed and couldn't be restarted: {}", e),
+        ));
+    }
+}
+
+/// Stop restarting the server, keep a crash report and tell the user
+async fn give_up(
+    app: &AppHandle,
+    docker: &DockerManager,
+    server: &Server,
+    container_id: &str,
+    exit_code: i64,
+    crashes: Vec<DateTime<Utc>>,
+) {
+    tracing::warn!("{} crashed {} times in {} minutes, not restarting it", server.name, crashes.len(), CRASH_WINDOW_MINUTES);
+    let report = CrashReport {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        game_type: server.game_type.to_string(),
+        image: docker.container_image_id(container_id).await,
+        crashes,
+        exit_code,
+        log: docker.get_logs(container_id, REPORT_LOG_LINES).await.unwrap_or_default(),
+        created_at: Utc::now(),
+    };
+    if let Err(e) = save_report(&report) {
+        tracing::warn!("Failed to save the crash report for {}: {}", server.name, e);
+    }
+
+    let state = app.state::<ServerState>();
+    if let Err(e) = state.configs.update(&server.id, |s| s.status = ServerStatus::CrashLooping) {
+        tracing::warn!("Failed to mark {} as crash looping: {}", server.name, e);
+    }
+    events::emit(app, ServerEvent::status(&server.id, ServerStatus::CrashLooping));
+
+    let message = format!(
+        "{} crashed {} times in {} minutes and won't be restarted until you start it - see its crash report",
+        server.name,
+        report.crashes.len(),
+        CRASH_WINDOW_MINUTES
+    );
+    events::emit(app, ServerEvent::alert(&server.id, AlertLevel::Error, message.clone()));
+    if load_app_settings().notifications.on_crash {
+        notify::send(app, "Server keeps crashing", message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crashes_outside_the_window_dont_count() {
+        let start = Utc::now();
+        let mut history = Vec::new();
+        assert!(!record(&mut history, start));
+        assert!(!record(&mut history, start + Duration::minutes(4)));
+        // The first crash is out of the window by now
+        assert!(!record(&mut history, start + Duration::minutes(11)));
+        assert_eq!(history.len(), 2);
+        assert!(record(&mut history, start + Duration::minutes(12)));
+    }
+}
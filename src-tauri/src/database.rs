@@ -0,0 +1,220 @@
+// Database sidecars - a MariaDB, MySQL or Redis container next to a game server, for plugins
+// like LuckPerms or CoreProtect. It's on a network of its own with the server's container,
+// starts and stops with the server, and its generated password (kept in the keychain) reaches
+// the server as DB_* or REDIS_* variables.
+
+use crate::docker::{DockerError, DockerManager};
+use crate::error::AppError;
+use crate::security::secrets;
+use chrono::Utc;
+use serde::Serialize;
+use serverwave_core::paths;
+use serverwave_core::server::{DatabaseKind, DatabaseSidecar, Server, ServerStatus};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// The database and user SQL sidecars are made with
+const SQL_NAME: &str = "serverwave";
+const MEMORY_MB: u32 = 512;
+
+/// How the server reaches its database, for the settings page
+#[derive(Debug, Clone, Serialize)]
+pub struct DatabaseInfo {
+    pub kind: DatabaseKind,
+    pub host: String,
+    pub port: u16,
+    /// None for Redis
+    pub database: Option<String>,
+    pub user: Option<String>,
+    pub password: String,
+    /// The variables the server gets
+    pub variables: Vec<String>,
+    pub running: bool,
+}
+
+fn password_secret(server_id: &str) -> String {
+    format!("db-password-{}", server_id)
+}
+
+/// The sidecar's container name - the host the server connects to
+pub fn container_name(server: &Server) -> String {
+    format!("serverwave-db-{}", server.id)
+}
+
+fn network_name(server: &Server) -> String {
+    format!("serverwave-db-net-{}", server.id)
+}
+
+pub fn get_data_dir(server: &Server) -> PathBuf {
+    paths::data_root().join("databases").join(&server.id)
+}
+
+/// The sidecar's password, made the first time it's needed
+async fn password(server: &Server) -> Result<String, String> {
+    if let Some(password) = secrets::get_secret(&password_secret(&server.id)).await {
+        return Ok(password);
+    }
+    let password = Uuid::new_v4().simple().to_string();
+    secrets::set_secret(&password_secret(&server.id), &password).await?;
+    Ok(password)
+}
+
+fn variables(kind: DatabaseKind, host: String, password: String) -> HashMap<String, String> {
+    let port = kind.port().to_string();
+    let vars = match kind {
+        DatabaseKind::Redis => vec![("REDIS_HOST", host), ("REDIS_PORT", port), ("REDIS_PASSWORD", password)],
+        DatabaseKind::Mariadb | DatabaseKind::Mysql => vec![
+            ("DB_HOST", host),
+            ("DB_PORT", port),
+            ("DB_NAME", SQL_NAME.to_string()),
+            ("DB_USER", SQL_NAME.to_string()),
+            ("DB_PASSWORD", password),
+        ],
+    };
+    vars.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+}
+
+/// What the server's container gets about its database, if it has one
+pub async fn container_env(server: &Server) -> Result<HashMap<String, String>, String> {
+    match &server.database {
+        Some(database) => Ok(variables(database.kind, container_name(server), password(server).await?)),
+        None => Ok(HashMap::new()),
+    }
+}
+
+pub async fn info(docker: &DockerManager, server: &Server) -> Result<Option<DatabaseInfo>, String> {
+    let Some(database) = &server.database else { return Ok(None) };
+    let sql = database.kind != DatabaseKind::Redis;
+    let password = password(server).await?;
+    let running = match &database.container_id {
+        Some(id) => docker.get_container_status(id).await.ok() == Some(ServerStatus::Running),
+        None => false,
+    };
+    let mut variables: Vec<String> = variables(database.kind, String::new(), String::new()).into_keys().collect();
+    variables.sort();
+    Ok(Some(DatabaseInfo {
+        kind: database.kind,
+        host: container_name(server),
+        port: database.kind.port(),
+        database: sql.then(|| SQL_NAME.to_string()),
+        user: sql.then(|| SQL_NAME.to_string()),
+        password,
+        variables,
+        running,
+    }))
+}
+
+async fn create_container(docker: &DockerManager, server: &Server, kind: DatabaseKind) -> Result<String, AppError> {
+    let password = password(server).await?;
+    let (cmd, env) = match kind {
+        DatabaseKind::Redis => (
+            Some(vec![
+                "redis-server".to_string(),
+                "--requirepass".to_string(),
+                password,
+                "--appendonly".to_string(),
+                "yes".to_string(),
+            ]),
+            Vec::new(),
+        ),
+        DatabaseKind::Mariadb | DatabaseKind::Mysql => {
+            let prefix = if kind == DatabaseKind::Mariadb { "MARIADB" } else { "MYSQL" };
+            let env = vec![
+                format!("{}_DATABASE={}", prefix, SQL_NAME),
+                format!("{}_USER={}", prefix, SQL_NAME),
+                format!("{}_PASSWORD={}", prefix, password),
+                format!("{}_ROOT_PASSWORD={}", prefix, password),
+            ];
+            (None, env)
+        }
+    };
+
+    let data_dir = get_data_dir(server);
+    std::fs::create_dir_all(&data_dir)?;
+    docker.ensure_network(&network_name(server)).await?;
+    let bind = format!("{}:{}", data_dir.display(), kind.data_path());
+    let id = docker
+        .create_service_container(&container_name(server), kind.image(), cmd, env, bind, &network_name(server), MEMORY_MB)
+        .await?;
+    Ok(id)
+}
+
+/// Give the server a database. The caller saves the returned sidecar on the server.
+pub async fn add(docker: &DockerManager, server: &Server, kind: DatabaseKind) -> Result<DatabaseSidecar, AppError> {
+    if server.database.is_some() {
+        return Err(AppError::InvalidInput("The server already has a database - remove it first".into()));
+    }
+    let container_id = create_container(docker, server, kind).await?;
+    Ok(DatabaseSidecar {
+        kind,
+        container_id: Some(container_id),
+        created_at: Utc::now(),
+    })
+}
+
+/// Start the server's database and put the server's container on its network. Returns the
+/// sidecar when its container had to be made again, for the caller to save.
+pub async fn start(docker: &DockerManager, server: &Server) -> Result<Option<DatabaseSidecar>, AppError> {
+    let Some(database) = &server.database else { return Ok(None) };
+
+    // Gone, e.g. removed by hand or the config came from another machine
+    let exists = match &database.container_id {
+        Some(id) => docker.client().inspect_container(id, None).await.is_ok(),
+        None => false,
+    };
+    let mut recreated = None;
+    let container_id = match (&database.container_id, exists) {
+        (Some(id), true) => id.clone(),
+        _ => {
+            let id = create_container(docker, server, database.kind).await?;
+            recreated = Some(DatabaseSidecar { container_id: Some(id.clone()), ..database.clone() });
+            id
+        }
+    };
+
+    if docker.get_container_status(&container_id).await? != ServerStatus::Running {
+        docker.start_container(&container_id).await?;
+    }
+    if let Some(game_container) = &server.container_id {
+        let network = network_name(server);
+        docker.ensure_network(&network).await?;
+        if !docker.container_networks(game_container).await.contains(&network) {
+            docker.connect_to_network(&network, game_container).await?;
+        }
+    }
+    Ok(recreated)
+}
+
+/// Stop the server's database along with the server
+pub async fn stop(docker: &DockerManager, server: &Server) -> Result<(), DockerError> {
+    let Some(container_id) = server.database.as_ref().and_then(|d| d.container_id.as_deref()) else {
+        return Ok(());
+    };
+    if docker.get_container_status(container_id).await? == ServerStatus::Running {
+        docker.stop_container(container_id).await?;
+    }
+    Ok(())
+}
+
+/// Remove the database container and its network, and its data and password with `delete_data`
+pub async fn remove(docker: &DockerManager, server: &Server, delete_data: bool) -> Result<(), AppError> {
+    let Some(database) = &server.database else { return Ok(()) };
+    if let Some(container_id) = &database.container_id {
+        docker.remove_container(container_id).await.ok();
+    }
+    let network = network_name(server);
+    if let Some(game_container) = &server.container_id {
+        let options = bollard::network::DisconnectNetworkOptions { container: game_container.as_str(), force: true };
+        docker.client().disconnect_network(&network, options).await.ok();
+    }
+    docker.remove_network(&network).await.ok();
+    if delete_data {
+        let data_dir = get_data_dir(server);
+        if data_dir.exists() {
+            std::fs::remove_dir_all(&data_dir)?;
+        }
+        secrets::delete_secret(&password_secret(&server.id)).await;
+    }
+    Ok(())
+}
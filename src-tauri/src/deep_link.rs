@@ -0,0 +1,59 @@
+// Deep links - serverwave://server/<id> focuses the app and opens that server
+
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+pub const SCHEME: &str = "serverwave";
+
+/// Server from the link the app was launched with - the page isn't listening yet then
+static PENDING_SERVER: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, PartialEq)]
+pub enum DeepLink {
+    Server(String),
+}
+
+pub fn parse(url: &str) -> Option<DeepLink> {
+    let rest = url.strip_prefix(SCHEME)?.strip_prefix("://")?;
+    let mut parts = rest.trim_end_matches('/').split('/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("server"), Some(id), None) if !id.is_empty() => Some(DeepLink::Server(id.to_string())),
+        _ => None,
+    }
+}
+
+/// Bring the window forward and act on the link, if it's one we understand
+pub fn handle(app: &AppHandle, url: &str) {
+    crate::tray::show_main_window(app);
+    match parse(url) {
+        Some(DeepLink::Server(id)) => {
+            let _ = app.emit("open-server", id);
+        }
+        None => tracing::warn!("Ignoring unknown link: {}", url),
+    }
+}
+
+/// Remember a launch link until the frontend asks for it with `take_pending_link`
+pub fn set_pending(url: &str) {
+    if let Some(DeepLink::Server(id)) = parse(url) {
+        *PENDING_SERVER.lock().unwrap() = Some(id);
+    }
+}
+
+pub fn take_pending_server() -> Option<String> {
+    PENDING_SERVER.lock().unwrap().take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_link() {
+        assert_eq!(parse("serverwave://server/ab12cd34"), Some(DeepLink::Server("ab12cd34".to_string())));
+        assert_eq!(parse("serverwave://server/ab12cd34/"), Some(DeepLink::Server("ab12cd34".to_string())));
+        assert_eq!(parse("serverwave://server/"), None);
+        assert_eq!(parse("serverwave://games/minecraft"), None);
+        assert_eq!(parse("https://server/ab12cd34"), None);
+    }
+}
@@ -0,0 +1,132 @@
+// Disk guard - a background check of the free space on the disk servers live on. Running low
+// is reported once (and again when it recovers), and installs and backups can be held back
+// until there's room, since a game saving its world to a full disk can corrupt it.
+
+use crate::commands::settings::{load_app_settings, DiskGuardSettings};
+use crate::error::AppError;
+use crate::notify;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serverwave_core::paths;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Sent when space runs low or recovers, on its own channel
+pub const DISK_SPACE_EVENT: &str = "disk-space";
+const GB: u64 = 1024 * 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref LAST: Mutex<Option<DiskSpace>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskSpace {
+    pub path: String,
+    pub free_bytes: u64,
+    pub min_free_bytes: u64,
+    pub low: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Work the guard can hold back
+#[derive(Debug, Clone, Copy)]
+pub enum Activity {
+    Install,
+    Backup,
+}
+
+/// Available bytes on the disk holding `path` - the disk with the longest mount point it's under
+pub fn free_space(path: &Path) -> Option<u64> {
+    // The data folder may not exist yet - use the closest folder that does
+    let existing: PathBuf = path.ancestors().find(|p| p.exists())?.to_path_buf();
+    let path = std::fs::canonicalize(&existing).unwrap_or(existing);
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| d.available_space())
+}
+
+/// The servers disk right now, against the configured minimum
+async fn measure(settings: &DiskGuardSettings) -> Option<DiskSpace> {
+    let root = paths::servers_dir();
+    let path = root.clone();
+    let free_bytes = tokio::task::spawn_blocking(move || free_space(&path)).await.ok()??;
+    let min_free_bytes = u64::from(settings.min_free_gb) * GB;
+    Some(DiskSpace {
+        path: root.to_string_lossy().to_string(),
+        free_bytes,
+        min_free_bytes,
+        low: free_bytes < min_free_bytes,
+        checked_at: Utc::now(),
+    })
+}
+
+/// Check now and report a change between enough and low space
+pub async fn check(app: &AppHandle) -> Option<DiskSpace> {
+    let settings = load_app_settings();
+    let space = measure(&settings.disk_guard).await?;
+    let was_low = LAST.lock().unwrap().replace(space.clone()).is_some_and(|last| last.low);
+
+    if space.low && !was_low {
+        let message = format!(
+            "Only {:.1} GB is free on the disk servers are on ({}), below the {} GB minimum",
+            space.free_bytes as f64 / GB as f64,
+            space.path,
+            settings.disk_guard.min_free_gb
+        );
+        tracing::warn!("{}", message);
+        let _ = app.emit(DISK_SPACE_EVENT, &space);
+        if settings.notifications.on_low_disk {
+            notify::send(app, "Disk space is low", message);
+        }
+    } else if !space.low && was_low {
+        tracing::info!("Disk space for servers is back above the minimum");
+        let _ = app.emit(DISK_SPACE_EVENT, &space);
+    }
+    Some(space)
+}
+
+pub fn spawn_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let settings = load_app_settings().disk_guard;
+            if settings.enabled {
+                check(&app).await;
+            } else {
+                *LAST.lock().unwrap() = None;
+            }
+            tokio::time::sleep(Duration::from_secs(u64::from(settings.check_interval_minutes.max(1)) * 60)).await;
+        }
+    });
+}
+
+/// Refuse to start an install or backup while space is low, when the guard is set to
+pub async fn ensure_room(activity: Activity) -> Result<(), AppError> {
+    let settings = load_app_settings().disk_guard;
+    let paused = match activity {
+        Activity::Install => settings.pause_installs,
+        Activity::Backup => settings.pause_backups,
+    };
+    if !settings.enabled || !paused {
+        return Ok(());
+    }
+    // Measured fresh - a cleanup since the last check should count
+    match measure(&settings).await {
+        Some(space) if space.low => Err(format!(
+            "Not enough disk space: {:.1} GB free, {} GB needed before {} - free some up or lower the minimum in Settings",
+            space.free_bytes as f64 / GB as f64,
+            settings.min_free_gb,
+            match activity {
+                Activity::Install => "installing",
+                Activity::Backup => "backing up",
+            }
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
@@ -1,5 +0,0 @@
-// Docker management module
-
-mod manager;
-
-pub use manager::{ContainerStats, DockerManager};
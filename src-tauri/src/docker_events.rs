@@ -0,0 +1,57 @@
+// Docker events - follows Docker's events stream so the UI hears about a server's container
+// starting or stopping (a crash, `docker stop`, Docker restarting) as it happens rather than
+// on its next poll. Each change goes out as `server-state-changed`.
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::ServerState;
+use crate::docker::ContainerEvent;
+use crate::events;
+use futures_util::stream::StreamExt;
+use serverwave_core::server::{lifecycle, ServerStatus};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Game server containers are named serverwave-<server id>
+const CONTAINER_PREFIX: &str = "serverwave-";
+/// Wait before subscribing again after Docker went away
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let docker_state = app.state::<DockerState>();
+            match docker_state.manager() {
+                Ok(docker) => {
+                    let mut stream = Box::pin(docker.container_events(CONTAINER_PREFIX));
+                    while let Some(event) = stream.next().await {
+                        match event {
+                            Ok(event) => handle(&app, event),
+                            Err(e) => {
+                                tracing::debug!("Docker events stream failed: {}", e);
+                                docker_state.reset();
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => tracing::debug!("Docker events unavailable: {}", e),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+}
+
+fn handle(app: &AppHandle, event: ContainerEvent) {
+    // serverwave-db-<id>, serverwave-<id>-tunnel and others don't name a server
+    let Some(server_id) = event.name.trim_start_matches('/').strip_prefix(CONTAINER_PREFIX) else { return };
+    let Ok(server) = app.state::<ServerState>().configs.get(server_id) else { return };
+    // An install container or one the server has since replaced
+    if server.container_id.as_deref() != Some(event.container_id.as_str()) || server.status == ServerStatus::Installing {
+        return;
+    }
+    // Starting servers are marked Running once they're ready, not when the process starts
+    if event.status == ServerStatus::Running && server.status == ServerStatus::Starting {
+        return;
+    }
+    events::emit_state(app, &server.id, lifecycle::kept_status(&server, event.status));
+}
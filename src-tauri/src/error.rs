@@ -0,0 +1,214 @@
+// Command errors - one serializable error type for every command, so the frontend
+// can branch on `kind` instead of matching message text.
+//
+// Serialized as `{"kind": "ServerNotFound", "message": "Server 'ab12' not found", ...}`
+// with any extra fields of the variant alongside.
+
+use crate::capacity::Capacity;
+use crate::docker::DockerError;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serverwave_core::ports::PortConflict;
+use serverwave_core::a2s::A2sError;
+use serverwave_core::rcon::RconError;
+use serverwave_core::slp::PingError;
+use serverwave_core::server::relocate::RelocateError;
+use serverwave_core::server::{LifecycleError, StoreError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("Docker is not running or can't be reached: {0}")]
+    DockerUnavailable(String),
+
+    #[error("Docker error: {0}")]
+    Docker(String),
+
+    #[error("Server '{0}' not found")]
+    ServerNotFound(String),
+
+    #[error("Game type '{0}' not found")]
+    GameNotFound(String),
+
+    #[error("Port {port} is already used by server '{server_name}'")]
+    PortConflict { port: u16, server_name: String },
+
+    #[error("Port {port} is already in use by {}", .holder.as_deref().unwrap_or("another program on this computer"))]
+    PortInUse { port: u16, holder: Option<String>, free_port: Option<u16> },
+
+    #[error("Install script failed with exit code: {exit_code}")]
+    InstallFailed { exit_code: i64 },
+
+    #[error("Install incomplete: missing {}", .missing.join(", "))]
+    InstallIncomplete { missing: Vec<String> },
+
+    #[error("App is locked - enter the app password to continue")]
+    Locked,
+
+    #[error("This window is read-only - nothing can be changed from it")]
+    ReadOnly,
+
+    #[error("{}", .0.message())]
+    OverCommitted(Capacity),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AppError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::DockerUnavailable(_) => "DockerUnavailable",
+            AppError::Docker(_) => "Docker",
+            AppError::ServerNotFound(_) => "ServerNotFound",
+            AppError::GameNotFound(_) => "GameNotFound",
+            AppError::PortConflict { .. } => "PortConflict",
+            AppError::PortInUse { .. } => "PortInUse",
+            AppError::InstallFailed { .. } => "InstallFailed",
+            AppError::InstallIncomplete { .. } => "InstallIncomplete",
+            AppError::Locked => "Locked",
+            AppError::ReadOnly => "ReadOnly",
+            AppError::OverCommitted(_) => "OverCommitted",
+            AppError::InvalidInput(_) => "InvalidInput",
+            AppError::Io(_) => "Io",
+            AppError::Other(_) => "Other",
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            AppError::ServerNotFound(id) => map.serialize_entry("serverId", id)?,
+            AppError::GameNotFound(game_type) => map.serialize_entry("gameType", game_type)?,
+            AppError::PortConflict { port, server_name } => {
+                map.serialize_entry("port", port)?;
+                map.serialize_entry("serverName", server_name)?;
+            }
+            AppError::PortInUse { port, holder, free_port } => {
+                map.serialize_entry("port", port)?;
+                map.serialize_entry("holder", holder)?;
+                map.serialize_entry("freePort", free_port)?;
+            }
+            AppError::InstallFailed { exit_code } => map.serialize_entry("exitCode", exit_code)?,
+            AppError::InstallIncomplete { missing } => map.serialize_entry("missing", missing)?,
+            AppError::OverCommitted(capacity) => {
+                map.serialize_entry("requestedMb", &capacity.requested_mb)?;
+                map.serialize_entry("availableMb", &capacity.available_mb)?;
+                map.serialize_entry("totalMb", &capacity.total_mb)?;
+                map.serialize_entry("committedMb", &capacity.committed_mb)?;
+                map.serialize_entry("runningServers", &capacity.running)?;
+            }
+            _ => {}
+        }
+        map.end()
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Other(message.to_string())
+    }
+}
+
+impl From<DockerError> for AppError {
+    fn from(e: DockerError) -> Self {
+        match &e {
+            // Docker answered, it just didn't like the request
+            DockerError::ConnectionError(bollard::errors::Error::DockerResponseServerError { .. })
+            | DockerError::ContainerNotFound(_)
+            | DockerError::ImagePullFailed(_)
+            | DockerError::AttachFailed(_) => AppError::Docker(e.to_string()),
+            DockerError::ConnectionError(_) => AppError::DockerUnavailable(e.to_string()),
+        }
+    }
+}
+
+impl From<bollard::errors::Error> for AppError {
+    fn from(e: bollard::errors::Error) -> Self {
+        DockerError::from(e).into()
+    }
+}
+
+impl From<tokio::task::JoinError> for AppError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        AppError::Other(e.to_string())
+    }
+}
+
+impl From<StoreError> for AppError {
+    fn from(e: StoreError) -> Self {
+        match e {
+            StoreError::NotFound(id) => AppError::ServerNotFound(id),
+            StoreError::Io(e) => AppError::Io(e),
+            other => AppError::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<LifecycleError> for AppError {
+    fn from(e: LifecycleError) -> Self {
+        match e {
+            LifecycleError::Docker(e) => e.into(),
+            LifecycleError::Store(e) => e.into(),
+            other => AppError::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<A2sError> for AppError {
+    fn from(e: A2sError) -> Self {
+        AppError::Other(e.to_string())
+    }
+}
+
+impl From<PingError> for AppError {
+    fn from(e: PingError) -> Self {
+        AppError::Other(e.to_string())
+    }
+}
+
+impl From<RconError> for AppError {
+    fn from(e: RconError) -> Self {
+        AppError::Other(e.to_string())
+    }
+}
+
+impl From<PortConflict> for AppError {
+    fn from(e: PortConflict) -> Self {
+        AppError::PortConflict {
+            port: e.port,
+            server_name: e.server_name,
+        }
+    }
+}
+
+impl From<RelocateError> for AppError {
+    fn from(e: RelocateError) -> Self {
+        match e {
+            RelocateError::Store(e) => e.into(),
+            RelocateError::Io { .. } => AppError::Other(e.to_string()),
+            other => AppError::InvalidInput(other.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::InvalidInput(e.to_string())
+    }
+}
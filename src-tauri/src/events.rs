@@ -0,0 +1,321 @@
+// Server events - everything the backend reports about a server goes out on the single
+// `server-event` channel, tagged by `type`:
+//   {"type": "LogLine", "server_id": "ab12cd34", "line": "Done (3.2s)!"}
+// Console and install output comes in batches instead, oldest line first:
+//   {"type": "LogLines", "server_id": "ab12cd34", "lines": ["Loading world", "Done (3.2s)!"]}
+
+use crate::docker::ContainerStats;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serverwave_core::server::ServerStatus;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+pub const SERVER_EVENT: &str = "server-event";
+/// A server's container started or stopped, as Docker reported it - see docker_events
+pub const STATE_CHANGED_EVENT: &str = "server-state-changed";
+/// Notable events kept for the dashboard
+const RECENT_LIMIT: usize = 50;
+/// Batched output goes out this often, or sooner once this many lines are waiting
+const LOG_FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+const LOG_BATCH_LINES: usize = 100;
+
+lazy_static::lazy_static! {
+    static ref RECENT: Mutex<VecDeque<RecentEvent>> = Mutex::new(VecDeque::new());
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallStage {
+    /// Waiting for a free install slot
+    Queued,
+    Started,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertLevel {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    Upload,
+    Download,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum ServerEvent {
+    StatusChanged {
+        server_id: String,
+        status: ServerStatus,
+    },
+    /// One line of console or install output
+    LogLine {
+        server_id: String,
+        line: String,
+    },
+    /// Console or install output gathered by a LogBatch, in the order it was printed
+    LogLines {
+        server_id: String,
+        lines: Vec<String>,
+    },
+    InstallProgress {
+        server_id: String,
+        stage: InstallStage,
+        message: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i64>,
+    },
+    /// The server stopped by itself with a non-zero exit code
+    Crashed {
+        server_id: String,
+        exit_code: i64,
+    },
+    Stats {
+        server_id: String,
+        stats: ContainerStats,
+    },
+    /// Something the user should notice - a failed tunnel, port forward, ...
+    Alert {
+        server_id: String,
+        level: AlertLevel,
+        message: String,
+    },
+    /// SteamCMD is waiting for a Steam Guard code - answer with submit_steam_guard_code
+    SteamGuardRequired {
+        server_id: String,
+        prompt: String,
+    },
+    /// An installer (or a game on its first start) is waiting for the user to log in at
+    /// `url`, entering `code` when given
+    InstallAuthRequired {
+        server_id: String,
+        url: String,
+        code: Option<String>,
+    },
+    /// The login from InstallAuthRequired went through
+    InstallAuthCompleted {
+        server_id: String,
+    },
+    /// Automation was kept off the server by its quiet hours or maintenance windows -
+    /// put off until `deferred_until`, or skipped when that's None
+    AutomationHeld {
+        server_id: String,
+        task: String,
+        message: String,
+        deferred_until: Option<chrono::DateTime<chrono::Utc>>,
+    },
+    BackupDone {
+        server_id: String,
+        path: String,
+        size_bytes: u64,
+    },
+    /// A backup being copied to or from one of the server's backup targets
+    BackupTransfer {
+        server_id: String,
+        backup_id: String,
+        target_id: String,
+        direction: TransferDirection,
+        done_bytes: u64,
+        total_bytes: u64,
+    },
+}
+
+impl ServerEvent {
+    pub fn log(server_id: &str, line: impl Into<String>) -> Self {
+        ServerEvent::LogLine {
+            server_id: server_id.to_string(),
+            line: line.into(),
+        }
+    }
+
+    pub fn alert(server_id: &str, level: AlertLevel, message: impl Into<String>) -> Self {
+        ServerEvent::Alert {
+            server_id: server_id.to_string(),
+            level,
+            message: message.into(),
+        }
+    }
+
+    pub fn install(server_id: &str, stage: InstallStage, message: impl Into<String>) -> Self {
+        ServerEvent::InstallProgress {
+            server_id: server_id.to_string(),
+            stage,
+            message: message.into(),
+            exit_code: None,
+        }
+    }
+
+    pub fn steam_guard(server_id: &str, prompt: &str) -> Self {
+        ServerEvent::SteamGuardRequired {
+            server_id: server_id.to_string(),
+            prompt: prompt.trim().to_string(),
+        }
+    }
+
+    pub fn status(server_id: &str, status: ServerStatus) -> Self {
+        ServerEvent::StatusChanged {
+            server_id: server_id.to_string(),
+            status,
+        }
+    }
+
+    /// Worth listing on the dashboard - not console output, stats or prompts
+    fn notable(&self) -> bool {
+        matches!(
+            self,
+            ServerEvent::StatusChanged { .. }
+                | ServerEvent::Crashed { .. }
+                | ServerEvent::Alert { .. }
+                | ServerEvent::AutomationHeld { .. }
+                | ServerEvent::BackupDone { .. }
+                | ServerEvent::InstallProgress { stage: InstallStage::Completed | InstallStage::Failed, .. }
+        )
+    }
+
+    pub fn server_id(&self) -> &str {
+        match self {
+            ServerEvent::StatusChanged { server_id, .. }
+            | ServerEvent::LogLine { server_id, .. }
+            | ServerEvent::LogLines { server_id, .. }
+            | ServerEvent::InstallProgress { server_id, .. }
+            | ServerEvent::Crashed { server_id, .. }
+            | ServerEvent::Stats { server_id, .. }
+            | ServerEvent::Alert { server_id, .. }
+            | ServerEvent::SteamGuardRequired { server_id, .. }
+            | ServerEvent::InstallAuthRequired { server_id, .. }
+            | ServerEvent::InstallAuthCompleted { server_id }
+            | ServerEvent::AutomationHeld { server_id, .. }
+            | ServerEvent::BackupDone { server_id, .. }
+            | ServerEvent::BackupTransfer { server_id, .. } => server_id,
+        }
+    }
+}
+
+pub fn emit(app: &AppHandle, event: ServerEvent) {
+    crate::hooks::dispatch(app, &event);
+    if event.notable() {
+        let mut recent = RECENT.lock().unwrap();
+        recent.push_front(RecentEvent { event: event.clone(), at: Utc::now() });
+        recent.truncate(RECENT_LIMIT);
+    }
+    let _ = app.emit(SERVER_EVENT, event);
+}
+
+/// Gathers a server's output lines into LogLines events - a game printing thousands of
+/// lines while it boots would otherwise send the frontend an event for each one. What's
+/// left is sent when it's dropped.
+pub struct LogBatch {
+    app: AppHandle,
+    server_id: String,
+    pending: Arc<Mutex<Vec<String>>>,
+}
+
+impl LogBatch {
+    pub fn new(app: &AppHandle, server_id: &str) -> Self {
+        let batch = LogBatch {
+            app: app.clone(),
+            server_id: server_id.to_string(),
+            pending: Arc::new(Mutex::new(Vec::new())),
+        };
+        // Sends quiet output, like a prompt waiting for an answer, until the batch is dropped
+        let (app, server_id, pending) = (app.clone(), server_id.to_string(), Arc::downgrade(&batch.pending));
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(LOG_FLUSH_INTERVAL).await;
+                let Some(pending) = pending.upgrade() else { break };
+                flush_lines(&app, &server_id, &pending);
+            }
+        });
+        batch
+    }
+
+    pub fn push(&self, line: impl Into<String>) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.push(line.into());
+        if pending.len() >= LOG_BATCH_LINES {
+            drop(pending);
+            self.flush();
+        }
+    }
+
+    pub fn flush(&self) {
+        flush_lines(&self.app, &self.server_id, &self.pending);
+    }
+}
+
+impl Drop for LogBatch {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Held while the event goes out, so a timed flush and a full batch can't swap places
+fn flush_lines(app: &AppHandle, server_id: &str, pending: &Mutex<Vec<String>>) {
+    let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+    if !pending.is_empty() {
+        let lines = std::mem::take(&mut *pending);
+        emit(app, ServerEvent::LogLines { server_id: server_id.to_string(), lines });
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentEvent {
+    pub event: ServerEvent,
+    pub at: DateTime<Utc>,
+}
+
+/// The notable events since the app started, newest first
+pub fn recent() -> Vec<RecentEvent> {
+    RECENT.lock().unwrap().iter().cloned().collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StateChanged {
+    pub server_id: String,
+    pub status: ServerStatus,
+}
+
+/// Only for the frontend - hooks already run on the StatusChanged the app itself sends
+pub fn emit_state(app: &AppHandle, server_id: &str, status: ServerStatus) {
+    let _ = app.emit(STATE_CHANGED_EVENT, StateChanged { server_id: server_id.to_string(), status });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_tagged_with_snake_case_fields() {
+        let value = serde_json::to_value(ServerEvent::log("ab12cd34", "Done!")).unwrap();
+        assert_eq!(value["type"], "LogLine");
+        assert_eq!(value["server_id"], "ab12cd34");
+        assert_eq!(value["line"], "Done!");
+
+        let value = serde_json::to_value(ServerEvent::LogLines {
+            server_id: "ab12cd34".to_string(),
+            lines: vec!["Loading world".to_string(), "Done!".to_string()],
+        })
+        .unwrap();
+        assert_eq!(value["type"], "LogLines");
+        assert_eq!(value["lines"], serde_json::json!(["Loading world", "Done!"]));
+
+        let value = serde_json::to_value(ServerEvent::InstallProgress {
+            server_id: "ab12cd34".to_string(),
+            stage: InstallStage::Failed,
+            message: "Installation failed".to_string(),
+            exit_code: Some(1),
+        })
+        .unwrap();
+        assert_eq!(value["stage"], "failed");
+        assert_eq!(value["exit_code"], 1);
+    }
+}
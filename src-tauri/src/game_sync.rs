@@ -0,0 +1,372 @@
+// Game library sync - custom game definitions shared through a Git repository or gist, one
+// file per game under games/, so a community can keep a library together. Saves are pushed
+// as they happen; pulling is up to the user. The git command line does the work, so SSH keys
+// and credential helpers already set up on the machine just work.
+
+use crate::commands::games::GamesState;
+use crate::commands::settings::{load_app_settings, GameSyncSettings};
+use crate::games::{GameConfig, GameType};
+use crate::security::secrets;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serverwave_core::paths;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Sent after each pull or push, on its own channel
+pub const SYNC_EVENT: &str = "game-sync";
+/// Keychain entry for an https access token, for repositories that need one
+pub const TOKEN_KEY: &str = "game-sync-token";
+/// Folder in the repository the definitions live in
+const GAMES_DIR: &str = "games";
+/// Used for commits when git has no identity set up
+const COMMIT_NAME: &str = "Serverwave Anywhere";
+const COMMIT_EMAIL: &str = "anywhere@serverwave.local";
+
+lazy_static::lazy_static! {
+    /// One git operation at a time - they share a working copy
+    static ref SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::new(());
+    static ref LAST: Mutex<Option<GameSyncReport>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncDirection {
+    Pull,
+    Push,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSyncReport {
+    pub direction: SyncDirection,
+    pub at: DateTime<Utc>,
+    /// The repository's commit after the sync
+    pub commit: Option<String>,
+    /// Games brought in or sent out - unchanged ones aren't listed
+    pub games: Vec<String>,
+    /// Files that couldn't be read as a game, with the reason
+    pub failed: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSyncStatus {
+    pub repo_url: Option<String>,
+    pub has_token: bool,
+    pub last: Option<GameSyncReport>,
+}
+
+pub async fn status() -> GameSyncStatus {
+    GameSyncStatus {
+        repo_url: load_app_settings().game_sync.repo().map(str::to_string),
+        has_token: secrets::get_secret(TOKEN_KEY).await.is_some(),
+        last: LAST.lock().unwrap().clone(),
+    }
+}
+
+/// The app's working copy of the repository
+fn checkout_dir() -> PathBuf {
+    paths::app_root().join("games").join("sync")
+}
+
+/// A game's file in the repository - ids are user-chosen, so anything odd becomes `_`
+fn file_name(game_type: &GameType) -> String {
+    let name: String = game_type
+        .0
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}.json", name)
+}
+
+/// The branch `git ls-remote --symref origin HEAD` says the remote defaults to
+fn parse_default_branch(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let target = line.strip_prefix("ref: refs/heads/")?;
+        Some(target.split_whitespace().next()?.to_string())
+    })
+}
+
+struct Git {
+    dir: PathBuf,
+    token: Option<String>,
+}
+
+impl Git {
+    async fn run(&self, args: &[&str]) -> Result<String, String> {
+        let mut command = tokio::process::Command::new("git");
+        command.current_dir(&self.dir).env("GIT_TERMINAL_PROMPT", "0");
+        // The token reaches git through the environment, never the command line
+        if let Some(token) = &self.token {
+            command
+                .args(["-c", "credential.helper="])
+                .args([
+                    "-c",
+                    "credential.helper=!f() { echo username=x-access-token; echo \"password=$SERVERWAVE_GIT_TOKEN\"; }; f",
+                ])
+                .env("SERVERWAVE_GIT_TOKEN", token);
+        }
+        #[cfg(target_os = "windows")]
+        {
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        let output = command.args(args).output().await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "Git isn't installed - it's needed to sync the game library".to_string(),
+            _ => format!("Failed to run git: {}", e),
+        })?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let subcommand = args.iter().find(|a| !a.starts_with('-') && !a.contains('=')).unwrap_or(&"");
+            Err(format!("git {} failed: {}", subcommand, stderr.trim()))
+        }
+    }
+
+    /// Point the working copy at the repository and bring it to the remote branch.
+    /// Returns the branch; a repository or branch with no commits yet starts empty.
+    async fn prepare(&self, settings: &GameSyncSettings, url: &str) -> Result<String, String> {
+        if !self.dir.join(".git").exists() {
+            std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+            self.run(&["init", "-q"]).await?;
+        }
+        self.run(&["remote", "remove", "origin"]).await.ok();
+        self.run(&["remote", "add", "origin", url]).await?;
+
+        let branch = match settings.branch.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+            Some(branch) => branch.to_string(),
+            None => {
+                let heads = self.run(&["ls-remote", "--symref", "origin", "HEAD"]).await?;
+                parse_default_branch(&heads).unwrap_or_else(|| "main".to_string())
+            }
+        };
+
+        let refspec = format!("refs/heads/{}", branch);
+        // Also where a wrong URL or a missing login shows up
+        let found = self.run(&["ls-remote", "origin", &refspec]).await?;
+        if !found.is_empty() {
+            self.run(&["fetch", "-q", "origin", &refspec]).await?;
+            self.run(&["checkout", "-q", "-B", &branch, "FETCH_HEAD"]).await?;
+            self.run(&["reset", "-q", "--hard", "FETCH_HEAD"]).await?;
+            self.run(&["clean", "-q", "-fd"]).await?;
+        } else {
+            // Nothing to build on - the first push creates the branch
+            std::fs::remove_dir_all(&self.dir).map_err(|e| e.to_string())?;
+            std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+            self.run(&["init", "-q"]).await?;
+            self.run(&["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)]).await?;
+            self.run(&["remote", "add", "origin", url]).await?;
+        }
+        Ok(branch)
+    }
+
+    async fn head(&self) -> Option<String> {
+        self.run(&["rev-parse", "--short", "HEAD"]).await.ok()
+    }
+}
+
+async fn open(settings: &GameSyncSettings) -> Result<(Git, String), String> {
+    let url = settings.repo().ok_or("No game library repository is set")?.to_string();
+    let git = Git {
+        dir: checkout_dir(),
+        token: secrets::get_secret(TOKEN_KEY).await,
+    };
+    Ok((git, url))
+}
+
+fn finish(app: &AppHandle, report: GameSyncReport) -> GameSyncReport {
+    *LAST.lock().unwrap() = Some(report.clone());
+    let _ = app.emit(SYNC_EVENT, &report);
+    report
+}
+
+fn failed(direction: SyncDirection, error: String) -> GameSyncReport {
+    GameSyncReport {
+        direction,
+        at: Utc::now(),
+        commit: None,
+        games: Vec::new(),
+        failed: Vec::new(),
+        error: Some(error),
+    }
+}
+
+/// Bring in every game in the repository. The repository wins for games in both; games
+/// only kept here are left alone.
+pub async fn pull(app: &AppHandle) -> GameSyncReport {
+    let _guard = SYNC_LOCK.lock().await;
+    let report = match pull_games(app).await {
+        Ok(report) => report,
+        Err(e) => failed(SyncDirection::Pull, e),
+    };
+    finish(app, report)
+}
+
+async fn pull_games(app: &AppHandle) -> Result<GameSyncReport, String> {
+    let settings = load_app_settings().game_sync;
+    let (git, url) = open(&settings).await?;
+    git.prepare(&settings, &url).await?;
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(git.dir.join(GAMES_DIR))
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    files.retain(|path| path.extension().is_some_and(|ext| ext == "json"));
+    files.sort();
+
+    let games_state = app.state::<GamesState>();
+    let mut manager = games_state.manager.lock().await;
+    let known = manager.get_custom_games();
+    let mut games = Vec::new();
+    let mut failures = Vec::new();
+    for path in files {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let result = std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|json| {
+            let mut game: GameConfig = serde_json::from_str(&json).map_err(|e| format!("Invalid JSON: {}", e))?;
+            game.is_custom = true;
+            let unchanged = known
+                .iter()
+                .any(|k| k.game_type == game.game_type && serde_json::to_value(k).ok() == serde_json::to_value(&game).ok());
+            if unchanged {
+                return Ok(None);
+            }
+            manager.import_game(&json).map(Some)
+        });
+        match result {
+            Ok(Some(game)) => games.push(game.name),
+            Ok(None) => {}
+            Err(e) => failures.push(format!("{}: {}", name, e)),
+        }
+    }
+
+    Ok(GameSyncReport {
+        direction: SyncDirection::Pull,
+        at: Utc::now(),
+        commit: git.head().await,
+        games,
+        failed: failures,
+        error: None,
+    })
+}
+
+/// Send games to the repository and take deleted ones out of it. Whatever else is in the
+/// repository is kept - a push only touches the games it's given.
+pub async fn push(app: &AppHandle, saved: Vec<GameConfig>, removed: Vec<GameType>) -> GameSyncReport {
+    let _guard = SYNC_LOCK.lock().await;
+    let report = match push_games(&saved, &removed).await {
+        Ok(report) => report,
+        Err(e) => failed(SyncDirection::Push, e),
+    };
+    finish(app, report)
+}
+
+/// Every custom game, for sharing a library that was kept locally until now
+pub async fn push_all(app: &AppHandle) -> GameSyncReport {
+    let games = app.state::<GamesState>().manager.lock().await.get_custom_games();
+    push(app, games, Vec::new()).await
+}
+
+/// Push a save in the background, when syncing is set up to
+pub fn push_on_save(app: &AppHandle, saved: Vec<GameConfig>, removed: Vec<GameType>) {
+    let settings = load_app_settings().game_sync;
+    if settings.repo().is_none() || !settings.push_on_save {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let report = push(&app, saved, removed).await;
+        if let Some(error) = &report.error {
+            tracing::warn!("Game library push failed: {}", error);
+        }
+    });
+}
+
+async fn push_games(saved: &[GameConfig], removed: &[GameType]) -> Result<GameSyncReport, String> {
+    let settings = load_app_settings().game_sync;
+    let (git, url) = open(&settings).await?;
+
+    // Someone else pushing in between rejects ours - start again from theirs, once
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let branch = git.prepare(&settings, &url).await?;
+        let games = write_games(&git.dir, saved, removed)?;
+
+        git.run(&["add", "-A", GAMES_DIR]).await?;
+        if git.run(&["diff", "--cached", "--quiet"]).await.is_ok() {
+            return Ok(GameSyncReport {
+                direction: SyncDirection::Push,
+                at: Utc::now(),
+                commit: git.head().await,
+                games: Vec::new(),
+                failed: Vec::new(),
+                error: None,
+            });
+        }
+
+        let message = match (games.as_slice(), removed) {
+            ([game], []) => format!("Update {}", game),
+            ([], [game_type]) => format!("Remove {}", game_type),
+            _ => format!("Update {} game definitions", games.len() + removed.len()),
+        };
+        let mut commit = Vec::new();
+        if git.run(&["config", "user.email"]).await.is_err() {
+            commit.extend(["-c".to_string(), format!("user.name={}", COMMIT_NAME)]);
+            commit.extend(["-c".to_string(), format!("user.email={}", COMMIT_EMAIL)]);
+        }
+        commit.extend(["commit".to_string(), "-q".to_string(), "-m".to_string(), message]);
+        git.run(&commit.iter().map(String::as_str).collect::<Vec<_>>()).await?;
+
+        match git.run(&["push", "-q", "origin", &format!("HEAD:refs/heads/{}", branch)]).await {
+            Ok(_) => {
+                return Ok(GameSyncReport {
+                    direction: SyncDirection::Push,
+                    at: Utc::now(),
+                    commit: git.head().await,
+                    games,
+                    failed: Vec::new(),
+                    error: None,
+                })
+            }
+            Err(e) if attempt == 1 && (e.contains("rejected") || e.contains("fetch first")) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Write the games into the working copy, returning the names of those that changed
+fn write_games(dir: &Path, saved: &[GameConfig], removed: &[GameType]) -> Result<Vec<String>, String> {
+    let games_dir = dir.join(GAMES_DIR);
+    std::fs::create_dir_all(&games_dir).map_err(|e| e.to_string())?;
+
+    let mut changed = Vec::new();
+    for game in saved {
+        let path = games_dir.join(file_name(&game.game_type));
+        let json = serde_json::to_string_pretty(game).map_err(|e| e.to_string())? + "\n";
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(json.as_str()) {
+            std::fs::write(&path, json).map_err(|e| e.to_string())?;
+            changed.push(game.name.clone());
+        }
+    }
+    for game_type in removed {
+        std::fs::remove_file(games_dir.join(file_name(game_type))).ok();
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_names_and_default_branch() {
+        assert_eq!(file_name(&GameType::new("my-game_2")), "my-game_2.json");
+        assert_eq!(file_name(&GameType::new("../etc/passwd")), "___etc_passwd.json");
+
+        let output = "ref: refs/heads/trunk\tHEAD\n3f2a9c1b0d\tHEAD";
+        assert_eq!(parse_default_branch(output), Some("trunk".to_string()));
+        assert_eq!(parse_default_branch(""), None);
+    }
+}
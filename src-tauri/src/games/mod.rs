@@ -1,8 +0,0 @@
-mod config;
-mod config_processor;
-mod manager;
-
-pub use config::{
-    build_env_vars, ConfigFile, ConfigFileFormat, GameConfig, GameType, PortConfig, PortProtocol,
-};
-pub use manager::GamesManager;
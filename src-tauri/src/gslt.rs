@@ -0,0 +1,260 @@
+// Steam server tokens - Game Server Login Tokens for games that need one to list publicly.
+// Tokens are kept in the keychain; which server uses which is kept next to the server configs.
+// A token only works for one running server at a time, so each is given to one server.
+
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use crate::games::GameConfig;
+use crate::security::secrets;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use serverwave_core::server::Server;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use uuid::Uuid;
+
+const QUERY_TOKEN_API: &str = "https://api.steampowered.com/IGameServersService/QueryLoginToken/v1/";
+/// The Steam Web API key checking tokens needs
+const WEB_API_KEY: &str = "steam-web-api-key";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerToken {
+    pub id: String,
+    pub label: String,
+    /// The token's last characters, to tell tokens apart without showing them
+    pub hint: String,
+    #[serde(default)]
+    pub server_id: Option<String>,
+    pub added_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_check: Option<TokenCheck>,
+}
+
+/// What Steam said about a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCheck {
+    pub checked_at: DateTime<Utc>,
+    pub valid: bool,
+    #[serde(default)]
+    pub banned: bool,
+    /// Tokens that go unused for a while expire
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub steam_id: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+fn secret_key(id: &str) -> String {
+    format!("gslt-{}", id)
+}
+
+fn get_tokens_path() -> PathBuf {
+    serverwave_core::paths::config_dir().join("gslt.json")
+}
+
+pub fn list() -> Vec<ServerToken> {
+    atomic::read_json(&get_tokens_path()).unwrap_or_default()
+}
+
+fn save(tokens: &[ServerToken]) -> Result<(), AppError> {
+    let content = serde_json::to_string_pretty(tokens)?;
+    atomic::write_with_backup(&get_tokens_path(), content).map_err(AppError::from)
+}
+
+/// Tokens are 32 hex characters
+fn validate(token: &str) -> Result<&str, AppError> {
+    let token = token.trim();
+    if token.len() == 32 && token.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(token)
+    } else {
+        Err(AppError::InvalidInput(
+            "A Steam server token is 32 letters and digits - copy it from steamcommunity.com/dev/managegameservers".into(),
+        ))
+    }
+}
+
+pub async fn add(label: &str, token: &str) -> Result<ServerToken, AppError> {
+    let token = validate(token)?;
+    let label = label.trim();
+    if label.is_empty() {
+        return Err(AppError::InvalidInput("Give the token a name".into()));
+    }
+    let mut tokens = list();
+    let entry = ServerToken {
+        id: Uuid::new_v4().to_string()[..8].to_string(),
+        label: label.to_string(),
+        hint: token[token.len() - 4..].to_string(),
+        server_id: None,
+        added_at: Utc::now(),
+        last_check: None,
+    };
+    secrets::set_secret(&secret_key(&entry.id), token).await?;
+    tokens.push(entry.clone());
+    save(&tokens)?;
+    Ok(entry)
+}
+
+pub async fn remove(id: &str) -> Result<(), AppError> {
+    let mut tokens = list();
+    tokens.retain(|t| t.id != id);
+    save(&tokens)?;
+    secrets::delete_secret(&secret_key(id)).await;
+    Ok(())
+}
+
+/// Give a token to a server, or take it back with None. A server has at most one token.
+pub fn assign(id: &str, server_id: Option<String>) -> Result<ServerToken, AppError> {
+    let mut tokens = list();
+    if !tokens.iter().any(|t| t.id == id) {
+        return Err(AppError::InvalidInput("That token was removed".into()));
+    }
+    for token in tokens.iter_mut() {
+        if token.id == id {
+            token.server_id = server_id.clone();
+        } else if server_id.is_some() && token.server_id == server_id {
+            token.server_id = None;
+        }
+    }
+    save(&tokens)?;
+    Ok(tokens.into_iter().find(|t| t.id == id).expect("checked above"))
+}
+
+/// Free a deleted server's token for another server
+pub fn forget(server_id: &str) {
+    let mut tokens = list();
+    let mut changed = false;
+    for token in tokens.iter_mut().filter(|t| t.server_id.as_deref() == Some(server_id)) {
+        token.server_id = None;
+        changed = true;
+    }
+    if changed {
+        if let Err(e) = save(&tokens) {
+            tracing::warn!("Failed to save Steam server tokens: {}", e);
+        }
+    }
+}
+
+pub async fn has_web_api_key() -> bool {
+    secrets::get_secret(WEB_API_KEY).await.is_some()
+}
+
+pub async fn set_web_api_key(key: Option<&str>) -> Result<(), AppError> {
+    match key.map(str::trim).filter(|k| !k.is_empty()) {
+        Some(key) => secrets::set_secret(WEB_API_KEY, key).await?,
+        None => secrets::delete_secret(WEB_API_KEY).await,
+    }
+    Ok(())
+}
+
+/// Ask Steam whether a token is valid, banned or about to expire, and remember the answer
+pub async fn check(id: &str) -> Result<ServerToken, AppError> {
+    let token = secrets::get_secret(&secret_key(id))
+        .await
+        .ok_or_else(|| AppError::InvalidInput("The token isn't in the keychain anymore - add it again".into()))?;
+    let key = secrets::get_secret(WEB_API_KEY)
+        .await
+        .ok_or_else(|| AppError::InvalidInput("Add a Steam Web API key to check tokens".into()))?;
+
+    let check = match query(&key, &token).await {
+        Ok(check) => check,
+        Err(e) => TokenCheck {
+            checked_at: Utc::now(),
+            valid: false,
+            banned: false,
+            expires_at: None,
+            steam_id: None,
+            error: Some(e),
+        },
+    };
+
+    let mut tokens = list();
+    let entry = tokens.iter_mut().find(|t| t.id == id).ok_or("That token was removed")?;
+    entry.last_check = Some(check);
+    let entry = entry.clone();
+    save(&tokens)?;
+    Ok(entry)
+}
+
+async fn query(key: &str, token: &str) -> Result<TokenCheck, String> {
+    let response = crate::content::http_client()
+        .get(QUERY_TOKEN_API)
+        .query(&[("key", key), ("login_token", token)])
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Steam unreachable: {}", e))?;
+    match response.status().as_u16() {
+        401 | 403 => return Err("Steam refused the Web API key".to_string()),
+        status if status >= 400 => return Err(format!("Steam returned {}", response.status())),
+        _ => {}
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    Ok(parse_query(&body))
+}
+
+/// Steam answers an unknown token with an empty response
+fn parse_query(body: &serde_json::Value) -> TokenCheck {
+    let response = &body["response"];
+    let steam_id = response["steamid"].as_str().map(str::to_string);
+    let expires_at = response["expires"]
+        .as_i64()
+        .filter(|e| *e > 0)
+        .and_then(|e| Utc.timestamp_opt(e, 0).single());
+    TokenCheck {
+        checked_at: Utc::now(),
+        valid: steam_id.is_some(),
+        banned: response["is_banned"].as_bool().unwrap_or(false),
+        expires_at,
+        steam_id,
+        error: None,
+    }
+}
+
+/// The variable holding the server's token, for its container. Empty when the game doesn't
+/// use one; the variable without a value when no token is assigned.
+pub async fn container_env(server: &Server, game: &GameConfig) -> HashMap<String, String> {
+    let Some(var) = game.gslt_var.as_ref().filter(|v| !v.is_empty()) else {
+        return HashMap::new();
+    };
+    let token = match list().into_iter().find(|t| t.server_id.as_deref() == Some(server.id.as_str())) {
+        Some(entry) => secrets::get_secret(&secret_key(&entry.id)).await.unwrap_or_default(),
+        None => String::new(),
+    };
+    HashMap::from([(var.clone(), token)])
+}
+
+/// Whether the server's container was created with other values for `env` - a token that
+/// was assigned, replaced or taken back since
+pub async fn outdated(docker: &DockerManager, server: &Server, env: &HashMap<String, String>) -> bool {
+    let Some(container_id) = &server.container_id else { return false };
+    for (name, value) in env {
+        if docker.container_env_var(container_id, name).await.unwrap_or_default() != *value {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_and_steam_answers() {
+        assert!(validate(" 0123456789ABCDEF0123456789abcdef ").is_ok());
+        assert!(validate("not-a-token").is_err());
+
+        let valid = parse_query(&serde_json::json!({
+            "response": { "is_banned": false, "expires": 0, "steamid": "85568392920040404" }
+        }));
+        assert!(valid.valid && !valid.banned && valid.expires_at.is_none());
+
+        let unknown = parse_query(&serde_json::json!({ "response": {} }));
+        assert!(!unknown.valid);
+    }
+}
@@ -0,0 +1,219 @@
+// Hooks - user automation that runs when a server event happens. Each hook picks a
+// trigger and an action (console command, HTTP POST or local script); hooks.json in the
+// config folder holds them and every `events::emit` goes through `dispatch`.
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::console_command;
+use crate::events::{InstallStage, ServerEvent};
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use serverwave_core::server::ServerStatus;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Loaded on first use - log lines go through `dispatch` too, so don't read the file each time
+static HOOKS: RwLock<Option<Vec<Hook>>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookTrigger {
+    Started,
+    Stopped,
+    Crashed,
+    InstallFinished,
+    InstallFailed,
+    BackupDone,
+    Alert,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HookAction {
+    /// Send a command to the server console
+    Command { command: String },
+    /// POST the event as JSON
+    Http { url: String },
+    /// Run a program with the event as JSON on stdin
+    Script {
+        path: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hook {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub trigger: HookTrigger,
+    /// Only run for this server, None for all of them
+    #[serde(default)]
+    pub server_id: Option<String>,
+    pub action: HookAction,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl HookTrigger {
+    pub fn for_event(event: &ServerEvent) -> Option<HookTrigger> {
+        match event {
+            ServerEvent::StatusChanged { status: ServerStatus::Running, .. } => Some(HookTrigger::Started),
+            ServerEvent::StatusChanged { status: ServerStatus::Stopped, .. } => Some(HookTrigger::Stopped),
+            ServerEvent::Crashed { .. } => Some(HookTrigger::Crashed),
+            ServerEvent::InstallProgress { stage: InstallStage::Completed, .. } => Some(HookTrigger::InstallFinished),
+            ServerEvent::InstallProgress { stage: InstallStage::Failed, .. } => Some(HookTrigger::InstallFailed),
+            ServerEvent::BackupDone { .. } => Some(HookTrigger::BackupDone),
+            ServerEvent::Alert { .. } => Some(HookTrigger::Alert),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            HookTrigger::Started => "started",
+            HookTrigger::Stopped => "stopped",
+            HookTrigger::Crashed => "crashed",
+            HookTrigger::InstallFinished => "install_finished",
+            HookTrigger::InstallFailed => "install_failed",
+            HookTrigger::BackupDone => "backup_done",
+            HookTrigger::Alert => "alert",
+        }
+    }
+}
+
+impl Hook {
+    fn matches(&self, trigger: HookTrigger, server_id: &str) -> bool {
+        self.enabled
+            && self.trigger == trigger
+            && self.server_id.as_deref().is_none_or(|id| id == server_id)
+    }
+}
+
+pub fn load_hooks() -> Vec<Hook> {
+    if let Some(hooks) = HOOKS.read().unwrap().as_ref() {
+        return hooks.clone();
+    }
+    let hooks: Vec<Hook> = atomic::read_json(&get_hooks_path()).unwrap_or_default();
+    *HOOKS.write().unwrap() = Some(hooks.clone());
+    hooks
+}
+
+pub fn save_hooks(hooks: &[Hook]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(hooks).map_err(|e| e.to_string())?;
+    atomic::write_with_backup(&get_hooks_path(), content).map_err(|e| e.to_string())?;
+    *HOOKS.write().unwrap() = Some(hooks.to_vec());
+    Ok(())
+}
+
+/// Start every hook the event triggers. Hooks run in the background and only log
+/// failures - reporting them as events could trigger more hooks.
+pub fn dispatch(app: &AppHandle, event: &ServerEvent) {
+    let Some(trigger) = HookTrigger::for_event(event) else { return };
+    let hooks: Vec<Hook> = load_hooks()
+        .into_iter()
+        .filter(|h| h.matches(trigger, event.server_id()))
+        .collect();
+    for hook in hooks {
+        let (app, event) = (app.clone(), event.clone());
+        tauri::async_runtime::spawn(async move {
+            match run(&app, &hook, trigger, &event).await {
+                Ok(()) => tracing::info!("Hook '{}' ran for {}", hook.name, event.server_id()),
+                Err(e) => tracing::warn!("Hook '{}' failed for {}: {}", hook.name, event.server_id(), e),
+            }
+        });
+    }
+}
+
+pub async fn run(app: &AppHandle, hook: &Hook, trigger: HookTrigger, event: &ServerEvent) -> Result<(), String> {
+    let payload = serde_json::json!({
+        "hook": hook.name,
+        "trigger": trigger,
+        "event": event,
+    });
+    match &hook.action {
+        HookAction::Command { command } => {
+            let docker = app.state::<DockerState>().manager().map_err(|e| e.to_string())?;
+            console_command(&docker, event.server_id(), command)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+        HookAction::Http { url } => {
+            let response = reqwest::Client::new()
+                .post(url)
+                .timeout(HTTP_TIMEOUT)
+                .json(&payload)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("{} answered {}", url, response.status()));
+            }
+            Ok(())
+        }
+        HookAction::Script { path, args } => {
+            let mut child = tokio::process::Command::new(path)
+                .args(args)
+                .env("SERVERWAVE_TRIGGER", trigger.as_str())
+                .env("SERVERWAVE_SERVER_ID", event.server_id())
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|e| format!("Failed to run {}: {}", path, e))?;
+            if let Some(mut stdin) = child.stdin.take() {
+                // Scripts that don't read stdin close it early, that's fine
+                let _ = stdin.write_all(payload.to_string().as_bytes()).await;
+            }
+            let output = tokio::time::timeout(SCRIPT_TIMEOUT, child.wait_with_output())
+                .await
+                .map_err(|_| format!("{} took longer than {}s", path, SCRIPT_TIMEOUT.as_secs()))?
+                .map_err(|e| e.to_string())?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("{} exited with {}: {}", path, output.status, stderr.trim()));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn get_hooks_path() -> PathBuf {
+    serverwave_core::paths::config_dir().join("hooks.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_match_trigger_and_server() {
+        let hook = Hook {
+            id: "h1".to_string(),
+            name: "Announce".to_string(),
+            enabled: true,
+            trigger: HookTrigger::Crashed,
+            server_id: Some("ab12cd34".to_string()),
+            action: HookAction::Http { url: "http://localhost/hook".to_string() },
+        };
+        let crash = ServerEvent::Crashed { server_id: "ab12cd34".to_string(), exit_code: 1 };
+        let trigger = HookTrigger::for_event(&crash).unwrap();
+        assert!(hook.matches(trigger, "ab12cd34"));
+        assert!(!hook.matches(trigger, "ef56ab78"));
+        assert!(!hook.matches(HookTrigger::Started, "ab12cd34"));
+        assert_eq!(HookTrigger::for_event(&ServerEvent::log("ab12cd34", "Done!")), None);
+    }
+}
@@ -0,0 +1,226 @@
+// Game images - pull newer versions of the serverwave game image tags servers use, on a
+// schedule, and point out servers whose container was created from an older pull. Install
+// images (debian, alpine) only run install scripts and can be removed once no server needs them.
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::settings::load_app_settings;
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use serde::Serialize;
+use serverwave_core::server::{lifecycle, load_all_server_configs, Server};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Only the app's own images are refreshed - custom images are left to whoever built them
+pub const IMAGE_PREFIX: &str = "ghcr.io/serverwavehost/game-images:";
+/// Let the app settle before the first pull
+const FIRST_REFRESH_DELAY: Duration = Duration::from_secs(300);
+
+lazy_static::lazy_static! {
+    /// Servers whose container runs an older pull of its image, from the last refresh
+    static ref STALE: Mutex<HashMap<String, StaleImage>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleImage {
+    pub server_id: String,
+    pub server_name: String,
+    pub image: String,
+    pub found_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImageRefresh {
+    /// Images that were pulled
+    pub pulled: Vec<String>,
+    /// Images that couldn't be pulled, with the reason
+    pub failed: Vec<String>,
+    pub stale: Vec<StaleImage>,
+}
+
+/// An image games run their install scripts in, and the servers that still need it
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallImage {
+    pub image: String,
+    /// Size on disk, None when it isn't pulled
+    pub size_bytes: Option<u64>,
+    /// Names of the servers whose game installs with it - updates run the script again
+    pub servers: Vec<String>,
+    /// Also a server's game image, so it stays regardless
+    pub game_image: bool,
+}
+
+impl InstallImage {
+    pub fn unused(&self) -> bool {
+        self.servers.is_empty() && !self.game_image
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImageCleanup {
+    pub removed: Vec<String>,
+    /// Images Docker wouldn't remove, with the reason
+    pub failed: Vec<String>,
+    pub reclaimed_bytes: u64,
+}
+
+pub fn is_managed(image: &str) -> bool {
+    image.starts_with(IMAGE_PREFIX)
+}
+
+/// The image each server with a container was created from, by server
+async fn server_images(app: &AppHandle) -> Vec<(Server, String)> {
+    let games_state = app.state::<GamesState>();
+    let games = games_state.manager.lock().await;
+    load_all_server_configs()
+        .into_iter()
+        .filter(|s| s.container_id.is_some())
+        .filter_map(|server| {
+            let game = games.get_game(&server.game_type)?;
+            let image = lifecycle::image(&server, &game).to_string();
+            Some((server, image))
+        })
+        .collect()
+}
+
+/// Every install image a game names, with the servers that use it
+pub async fn install_images(app: &AppHandle) -> Result<Vec<InstallImage>, String> {
+    let docker = app.state::<DockerState>().manager().map_err(|e| e.to_string())?;
+    let mut images: HashMap<String, Vec<String>> = HashMap::new();
+    let mut runtime = HashSet::new();
+    {
+        let games_state = app.state::<GamesState>();
+        let games = games_state.manager.lock().await;
+        for game in games.get_all_games() {
+            if let Some(image) = game.install_image {
+                images.entry(image).or_default();
+            }
+        }
+        // Servers without a container yet count too - their install is still to come
+        for server in load_all_server_configs() {
+            let Some(game) = games.get_game(&server.game_type) else { continue };
+            runtime.insert(lifecycle::image(&server, &game).to_string());
+            if let Some(image) = &game.install_image {
+                images.entry(image.clone()).or_default().push(server.name.clone());
+            }
+        }
+    }
+
+    let mut list = Vec::new();
+    for (image, mut servers) in images {
+        servers.sort();
+        let size_bytes = docker.image_size(&image).await;
+        let game_image = runtime.contains(&image);
+        list.push(InstallImage { image, size_bytes, servers, game_image });
+    }
+    list.sort_by(|a, b| a.image.cmp(&b.image));
+    Ok(list)
+}
+
+/// Remove the pulled install images no server needs anymore. Installs pull them again if a
+/// server for that game is added later.
+pub async fn clean_install_images(app: &AppHandle) -> Result<ImageCleanup, String> {
+    let docker = app.state::<DockerState>().manager().map_err(|e| e.to_string())?;
+    let mut result = ImageCleanup::default();
+    for unused in install_images(app).await?.into_iter().filter(InstallImage::unused) {
+        let Some(size) = unused.size_bytes else { continue };
+        match docker.remove_image(&unused.image).await {
+            Ok(()) => {
+                result.reclaimed_bytes += size;
+                result.removed.push(unused.image);
+            }
+            Err(e) => result.failed.push(format!("{}: {}", unused.image, e)),
+        }
+    }
+    Ok(result)
+}
+
+pub fn stale() -> Vec<StaleImage> {
+    let mut list: Vec<StaleImage> = STALE.lock().unwrap().values().cloned().collect();
+    list.sort_by(|a, b| a.server_name.cmp(&b.server_name));
+    list
+}
+
+pub fn forget(server_id: &str) {
+    STALE.lock().unwrap().remove(server_id);
+}
+
+/// Pull every game image servers use and compare each container against the fresh pull.
+/// Servers that just went stale get an alert.
+pub async fn refresh(app: &AppHandle) -> Result<ImageRefresh, String> {
+    let docker = app.state::<DockerState>().manager().map_err(|e| e.to_string())?;
+    let servers = server_images(app).await;
+    let mut result = ImageRefresh::default();
+
+    let images: HashSet<&String> = servers.iter().map(|(_, image)| image).filter(|i| is_managed(i)).collect();
+    for image in images {
+        match docker.pull_image(image).await {
+            Ok(()) => result.pulled.push(image.clone()),
+            Err(e) => result.failed.push(format!("{}: {}", image, e)),
+        }
+    }
+
+    let mut found = HashMap::new();
+    for (server, image) in servers.iter().filter(|(_, image)| is_managed(image)) {
+        let Some(container_id) = &server.container_id else { continue };
+        let (Some(current), Some(used)) = (docker.image_id(image).await, docker.container_image_id(container_id).await)
+        else {
+            continue;
+        };
+        if current != used {
+            found.insert(server.id.clone(), StaleImage {
+                server_id: server.id.clone(),
+                server_name: server.name.clone(),
+                image: image.clone(),
+                found_at: chrono::Utc::now(),
+            });
+        }
+    }
+
+    let mut known = STALE.lock().unwrap();
+    for (server_id, stale) in found.iter_mut() {
+        match known.get(server_id) {
+            // Keep when the server was first found stale
+            Some(previous) => stale.found_at = previous.found_at,
+            None => events::emit(app, ServerEvent::alert(
+                server_id,
+                AlertLevel::Warning,
+                format!("A newer {} image is available - recreate the container to use it", stale.image),
+            )),
+        }
+    }
+    *known = found;
+    result.stale = known.values().cloned().collect();
+    Ok(result)
+}
+
+/// Replace a server's container with one from the current pull of its image. A running
+/// server is restarted. Data lives outside the container.
+pub async fn recreate(app: &AppHandle, server_id: &str) -> Result<Server, AppError> {
+    let response = crate::commands::server::apply_config(server_id.to_string(), app.clone(), app.state(), app.state(), app.state()).await?;
+    forget(server_id);
+    response.server.ok_or_else(|| AppError::Other("The server disappeared".to_string()))
+}
+
+pub fn spawn_refresher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_REFRESH_DELAY).await;
+        loop {
+            let settings = load_app_settings().updates;
+            if settings.refresh_images {
+                match refresh(&app).await {
+                    Ok(result) => {
+                        for failure in &result.failed {
+                            tracing::warn!("Couldn't pull game image {}", failure);
+                        }
+                    }
+                    Err(e) => tracing::warn!("Game image refresh skipped: {}", e),
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(u64::from(settings.image_interval_hours.max(1)) * 3600)).await;
+        }
+    });
+}
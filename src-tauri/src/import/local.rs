@@ -0,0 +1,178 @@
+// Local import - take over a server folder that was run by hand, like a Paper or Rust
+// folder: work out the game, read its settings back into variables and fix up what the
+// managed container starts differently
+
+use crate::content::loader::{detect_game_version, detect_loader, Loader};
+use crate::games::{read_config_variables, GameConfig, GameType};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Files or folders only a given game's server folder has, checked in this order - modded
+/// and Bedrock folders have a server.properties too
+const MARKERS: &[(&str, &[&str])] = &[
+    ("minecraft-modded", &["libraries/net/neoforged", "libraries/net/minecraftforge", "fabric-server-launch.jar", ".fabric"]),
+    ("minecraft-bedrock", &["bedrock_server", "bedrock_server.exe"]),
+    ("velocity", &["velocity.toml"]),
+    ("minecraft-java", &["server.properties", "eula.txt"]),
+    ("rust", &["RustDedicated", "RustDedicated.exe", "RustDedicated_Data"]),
+    ("terraria", &["TerrariaServer.bin.x86_64", "TerrariaServer.exe", "TerrariaServer"]),
+    ("hytale", &["Server/HytaleServer.jar"]),
+    ("palworld", &["PalServer.sh", "PalServer.exe", "Pal/Binaries"]),
+    ("satisfactory", &["FactoryServer.sh", "FactoryServer.exe", "FactoryGame"]),
+    ("project-zomboid", &["ProjectZomboid64", "ProjectZomboid64.json"]),
+    ("sons-of-the-forest", &["SonsOfTheForestDS.exe"]),
+    ("starrupture", &["StarRuptureServerEOS.exe"]),
+];
+
+/// The property Minecraft (Java and Bedrock) reads its port from
+const PORT_PROPERTY: &str = "server-port";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalScan {
+    pub path: String,
+    /// The game the folder looks like, None when nothing matched
+    pub game_type: Option<GameType>,
+    /// Game variables read back from the folder's files
+    pub config: HashMap<String, String>,
+    /// The port the folder's config sets, when it has one
+    pub port: Option<u16>,
+    pub size_bytes: u64,
+}
+
+pub fn detect_game_type(dir: &Path) -> Option<GameType> {
+    MARKERS
+        .iter()
+        .find(|(_, files)| files.iter().any(|file| dir.join(file).exists()))
+        .map(|(game_type, _)| GameType::new(game_type))
+}
+
+/// What the folder holds for `game`'s variables: its config files read back, plus the
+/// Minecraft jar, loader and version. Only variables the game has are returned.
+pub fn read_config(dir: &Path, game: &GameConfig) -> HashMap<String, String> {
+    let mut config = HashMap::new();
+    for config_file in &game.config_files {
+        config.extend(read_config_variables(dir, config_file));
+    }
+    if let Some(jar) = server_jar(dir) {
+        config.insert("SERVER_JARFILE".to_string(), jar);
+    }
+    if let Some(version) = detect_game_version(dir, None) {
+        config.insert("MINECRAFT_VERSION".to_string(), version);
+    }
+    let loader = match detect_loader(dir, "") {
+        Some(Loader::NeoForge) => Some("neoforge"),
+        Some(Loader::Forge) => Some("forge"),
+        Some(Loader::Fabric) => Some("fabric"),
+        _ => None,
+    };
+    if let Some(loader) = loader {
+        config.insert("LOADER".to_string(), loader.to_string());
+    }
+    config.retain(|key, value| !value.is_empty() && game.variables.iter().any(|v| v.env == *key));
+    config
+}
+
+/// The jar a Java server folder starts - server.jar, or the only other one
+fn server_jar(dir: &Path) -> Option<String> {
+    if dir.join("server.jar").is_file() {
+        return Some("server.jar".to_string());
+    }
+    let jars: Vec<String> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".jar") && !name.contains("installer"))
+        .collect();
+    match jars.as_slice() {
+        [jar] => Some(jar.clone()),
+        _ => None,
+    }
+}
+
+/// The port in the folder's server.properties
+pub fn read_port(dir: &Path) -> Option<u16> {
+    let content = std::fs::read_to_string(dir.join("server.properties")).ok()?;
+    content
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .find(|(key, _)| key.trim() == PORT_PROPERTY)
+        .and_then(|(_, value)| value.trim().parse().ok())
+}
+
+/// Make the folder start the way the managed server does: on its assigned port, and for
+/// modded Minecraft with the loader's arguments in unix_args.txt where the startup reads them
+pub fn prepare(dir: &Path, game_type: &GameType, port: u16) -> std::io::Result<Vec<String>> {
+    let mut changes = Vec::new();
+
+    let properties = dir.join("server.properties");
+    if read_port(dir).is_some_and(|current| current != port) {
+        let content = std::fs::read_to_string(&properties)?;
+        let updated: Vec<String> = content
+            .lines()
+            .map(|line| match line.trim().split_once('=') {
+                Some((key, _)) if key.trim() == PORT_PROPERTY => format!("{}={}", PORT_PROPERTY, port),
+                _ => line.to_string(),
+            })
+            .collect();
+        serverwave_core::atomic::write_atomic(&properties, updated.join("\n") + "\n")?;
+        changes.push(format!("Set {} to {} in server.properties", PORT_PROPERTY, port));
+    }
+
+    if game_type.0 == "minecraft-modded" && !dir.join("unix_args.txt").exists() {
+        if let Some(args) = loader_args(dir) {
+            std::fs::write(dir.join("unix_args.txt"), args)?;
+            changes.push("Wrote unix_args.txt for the loader".to_string());
+        }
+    }
+    Ok(changes)
+}
+
+/// The arguments a modded server starts with: Forge/NeoForge's own unix_args.txt (1.17+),
+/// Fabric's launcher or an older Forge's jar
+fn loader_args(dir: &Path) -> Option<String> {
+    for base in ["libraries/net/neoforged/neoforge", "libraries/net/minecraftforge/forge"] {
+        let Ok(versions) = std::fs::read_dir(dir.join(base)) else { continue };
+        let mut args: Vec<_> = versions
+            .flatten()
+            .map(|e| e.path().join("unix_args.txt"))
+            .filter(|p| p.is_file())
+            .collect();
+        args.sort();
+        if let Some(path) = args.pop() {
+            return std::fs::read_to_string(path).ok();
+        }
+    }
+    if dir.join("fabric-server-launch.jar").is_file() {
+        return Some("-jar fabric-server-launch.jar\n".to_string());
+    }
+    server_jar(dir)
+        .filter(|jar| jar.starts_with("forge-"))
+        .map(|jar| format!("-jar {}\n", jar))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_and_prepares_a_modded_folder() {
+        let dir = std::env::temp_dir().join(format!("sw-local-import-{}", uuid::Uuid::new_v4()));
+        let forge = dir.join("libraries/net/minecraftforge/forge/1.20.1-47.3.0");
+        std::fs::create_dir_all(&forge).unwrap();
+        std::fs::write(forge.join("unix_args.txt"), "-DlibraryDirectory=libraries\n").unwrap();
+        std::fs::write(dir.join("server.properties"), "motd=Hi\nserver-port=25570\n").unwrap();
+
+        let game_type = detect_game_type(&dir).unwrap();
+        assert_eq!(game_type.0, "minecraft-modded");
+        assert_eq!(read_port(&dir), Some(25570));
+
+        prepare(&dir, &game_type, 25565).unwrap();
+        assert_eq!(read_port(&dir), Some(25565));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("unix_args.txt")).unwrap(),
+            "-DlibraryDirectory=libraries\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+// Import module - bring servers over from other hosting panels, from folders on this machine,
+// or from archives exported by another copy of the app
+
+pub mod local;
+pub mod portable;
+pub mod pterodactyl;
+pub mod sftp;
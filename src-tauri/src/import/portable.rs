@@ -0,0 +1,138 @@
+// Portable archives - a whole server in one zip, to move it to another machine: its settings
+// in serverwave-server.json and its files under data/. Importing one makes a new server, so
+// the id, the ports that are taken and the container are this machine's.
+
+use crate::backups;
+use crate::games::GameConfig;
+use crate::hooks::Hook;
+use crate::wipes::WipeSchedule;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serverwave_core::server::Server;
+use std::io::Write;
+use std::path::Path;
+
+/// Bumped when the archive layout changes in a way older versions can't read
+pub const ARCHIVE_FORMAT: u32 = 1;
+const MANIFEST: &str = "serverwave-server.json";
+const DATA_DIR: &str = "data";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerManifest {
+    pub format: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub server: Server,
+    /// The game when it's a custom one or an override, which the other machine may not have
+    #[serde(default)]
+    pub game: Option<GameConfig>,
+    #[serde(default)]
+    pub wipe_schedule: Option<WipeSchedule>,
+    /// Hooks that only run for this server
+    #[serde(default)]
+    pub hooks: Vec<Hook>,
+}
+
+/// Write the manifest and the data folder to `archive`, through a .part file so a failed
+/// export doesn't leave a zip that looks complete
+pub fn write(manifest: &ServerManifest, data_path: &Path, archive: &Path) -> Result<(), String> {
+    let partial = archive.with_extension("part");
+    let written = (|| {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&partial).map_err(|e| e.to_string())?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+        zip.start_file(MANIFEST, options).map_err(|e| e.to_string())?;
+        let content = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+        zip.write_all(&content).map_err(|e| e.to_string())?;
+        zip.add_directory(DATA_DIR, options).map_err(|e| e.to_string())?;
+        if data_path.exists() {
+            backups::add_dir(&mut zip, data_path, data_path, options, &format!("{}/", DATA_DIR))?;
+        }
+        zip.finish().map_err(|e| e.to_string())?;
+        std::fs::rename(&partial, archive).map_err(|e| e.to_string())
+    })();
+    if written.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    written
+}
+
+pub fn read_manifest(archive: &Path) -> Result<ServerManifest, String> {
+    let file = std::fs::File::open(archive).map_err(|e| format!("Can't open {}: {}", archive.display(), e))?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    let entry = zip
+        .by_name(MANIFEST)
+        .map_err(|_| "Not a server exported from Serverwave - it has no serverwave-server.json".to_string())?;
+    serde_json::from_reader(entry).map_err(|e| format!("The archive's server details can't be read: {}", e))
+}
+
+/// Put the archive's data folder in place of `data_path`. Unpacked next to it first, so a
+/// broken archive leaves nothing half-written.
+pub fn unpack_data(archive: &Path, data_path: &Path) -> Result<(), String> {
+    let name = data_path.file_name().unwrap_or_default().to_string_lossy();
+    let staging = data_path.with_file_name(format!(".{}-import", name));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).map_err(|e| e.to_string())?;
+    }
+    let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    // extract keeps the files' permissions, so start scripts stay executable
+    if let Err(e) = zip.extract(&staging) {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(format!("Couldn't unpack the archive: {}", e));
+    }
+
+    let data = staging.join(DATA_DIR);
+    std::fs::create_dir_all(&data).map_err(|e| e.to_string())?;
+    if data_path.exists() {
+        std::fs::remove_dir_all(data_path).map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(&data, data_path).map_err(|e| e.to_string())?;
+    std::fs::remove_dir_all(&staging).ok();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_round_trip() {
+        let dir = std::env::temp_dir().join(format!("serverwave-portable-{}", uuid::Uuid::new_v4()));
+        let data = dir.join("ab12cd34");
+        std::fs::create_dir_all(data.join("world")).unwrap();
+        std::fs::write(data.join("world").join("level.dat"), b"level").unwrap();
+        std::fs::write(data.join("server.properties"), b"server-port=25565").unwrap();
+
+        let mut server = Server { data_path: data.clone(), installed: true, ..Server::test_fixture("ab12cd34") };
+        server.config.insert("MC_MAXPLAYERS".into(), "20".into());
+        let manifest = ServerManifest {
+            format: ARCHIVE_FORMAT,
+            app_version: "1.0.0".to_string(),
+            exported_at: Utc::now(),
+            server,
+            game: None,
+            wipe_schedule: None,
+            hooks: Vec::new(),
+        };
+        let archive = dir.join("survival.zip");
+        write(&manifest, &data, &archive).unwrap();
+        assert!(!archive.with_extension("part").exists());
+
+        let read = read_manifest(&archive).unwrap();
+        assert_eq!(read.server.name, "Survival");
+        assert_eq!(read.server.config.get("MC_MAXPLAYERS").map(String::as_str), Some("20"));
+
+        let target = dir.join("ef56ab78");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("placeholder.txt"), b"from create_server").unwrap();
+        unpack_data(&archive, &target).unwrap();
+        assert_eq!(std::fs::read(target.join("world").join("level.dat")).unwrap(), b"level");
+        assert!(target.join("server.properties").exists());
+        assert!(!target.join("placeholder.txt").exists());
+        assert!(!target.join(MANIFEST).exists());
+        assert!(!dir.join(".ef56ab78-import").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
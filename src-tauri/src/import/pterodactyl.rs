@@ -0,0 +1,458 @@
+// Pterodactyl panel - list servers through the client API and rebuild their eggs as games
+
+use crate::games::{
+    FieldType, GameConfig, GameType, PortConfig, PortProtocol, SelectOption, SystemMapping, Variable,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where eggs expect their files - the yolks images run from here
+const VOLUME_PATH: &str = "/home/container";
+
+/// A client API connection (`ptlc_...` key from Account → API Credentials)
+pub struct PanelClient {
+    base_url: String,
+    api_key: String,
+    http: reqwest::Client,
+}
+
+/// A server as listed to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct PanelServer {
+    pub identifier: String,
+    pub name: String,
+    pub description: String,
+    pub egg_name: Option<String>,
+    pub docker_image: String,
+    /// Memory limit in MB, None when unlimited
+    pub memory_mb: Option<u32>,
+    pub port: Option<u16>,
+    pub extra_ports: Vec<u16>,
+    pub sftp_host: String,
+    pub sftp_port: u16,
+    pub is_suspended: bool,
+}
+
+/// An egg variable with this server's value, from the startup endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct StartupVariable {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub env_variable: String,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    #[serde(default)]
+    pub server_value: Option<String>,
+    #[serde(default)]
+    pub is_editable: bool,
+    #[serde(default)]
+    pub rules: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Startup {
+    pub raw_startup_command: String,
+    pub variables: Vec<StartupVariable>,
+}
+
+#[derive(Deserialize)]
+struct Wrapped<T> {
+    attributes: T,
+}
+
+#[derive(Deserialize)]
+struct List<T> {
+    data: Vec<Wrapped<T>>,
+    #[serde(default)]
+    meta: Option<ListMeta>,
+}
+
+#[derive(Deserialize)]
+struct ListMeta {
+    #[serde(default)]
+    pagination: Option<Pagination>,
+    #[serde(default)]
+    raw_startup_command: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Pagination {
+    current_page: u32,
+    total_pages: u32,
+}
+
+#[derive(Deserialize)]
+struct ServerAttributes {
+    identifier: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    docker_image: String,
+    #[serde(default)]
+    is_suspended: bool,
+    limits: Limits,
+    sftp_details: SftpDetails,
+    #[serde(default)]
+    relationships: Relationships,
+}
+
+#[derive(Deserialize)]
+struct Limits {
+    #[serde(default)]
+    memory: u64,
+}
+
+#[derive(Deserialize)]
+struct SftpDetails {
+    ip: String,
+    port: u16,
+}
+
+#[derive(Deserialize, Default)]
+struct Relationships {
+    #[serde(default)]
+    allocations: Option<List<Allocation>>,
+    #[serde(default)]
+    egg: Option<Wrapped<Egg>>,
+}
+
+#[derive(Deserialize)]
+struct Allocation {
+    port: u16,
+    #[serde(default)]
+    is_default: bool,
+}
+
+#[derive(Deserialize)]
+struct Egg {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Account {
+    username: String,
+}
+
+impl PanelClient {
+    pub fn new(base_url: &str, api_key: &str) -> Result<Self, String> {
+        let base_url = base_url.trim().trim_end_matches('/').to_string();
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err("Panel URL must start with http:// or https://".to_string());
+        }
+        if api_key.trim().is_empty() {
+            return Err("API key is required".to_string());
+        }
+        Ok(Self {
+            base_url,
+            api_key: api_key.trim().to_string(),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Host part of the panel URL, for nodes that report 0.0.0.0 as their SFTP address
+    fn panel_host(&self) -> String {
+        self.base_url
+            .split("://")
+            .nth(1)
+            .and_then(|rest| rest.split(['/', ':']).next())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        let response = self
+            .http
+            .get(format!("{}/api/client{}", self.base_url, path))
+            .bearer_auth(&self.api_key)
+            .header("Accept", "application/json")
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| format!("Panel unreachable: {}", e))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(
+                "The panel rejected the API key - use a client key from Account → API Credentials"
+                    .to_string(),
+            );
+        }
+        if !status.is_success() {
+            return Err(format!("Panel returned {}", status));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Unexpected panel response: {}", e))
+    }
+
+    /// Username of the key's owner - SFTP logins are `<username>.<server identifier>`
+    pub async fn account_username(&self) -> Result<String, String> {
+        let account: Wrapped<Account> = self.get("/account").await?;
+        Ok(account.attributes.username)
+    }
+
+    /// Every server the key's owner can access
+    pub async fn list_servers(&self) -> Result<Vec<PanelServer>, String> {
+        let mut servers = Vec::new();
+        let mut page = 1;
+        loop {
+            let list: List<ServerAttributes> = self.get(&format!("?include=egg&page={}", page)).await?;
+            servers.extend(list.data.into_iter().map(|s| self.to_panel_server(s.attributes)));
+
+            match list.meta.and_then(|m| m.pagination) {
+                Some(p) if p.current_page < p.total_pages => page += 1,
+                _ => break,
+            }
+        }
+        Ok(servers)
+    }
+
+    pub async fn get_server(&self, identifier: &str) -> Result<PanelServer, String> {
+        let server: Wrapped<ServerAttributes> = self
+            .get(&format!("/servers/{}?include=egg", identifier))
+            .await?;
+        Ok(self.to_panel_server(server.attributes))
+    }
+
+    pub async fn startup(&self, identifier: &str) -> Result<Startup, String> {
+        let list: List<StartupVariable> = self.get(&format!("/servers/{}/startup", identifier)).await?;
+        Ok(Startup {
+            raw_startup_command: list
+                .meta
+                .and_then(|m| m.raw_startup_command)
+                .unwrap_or_default(),
+            variables: list.data.into_iter().map(|v| v.attributes).collect(),
+        })
+    }
+
+    fn to_panel_server(&self, server: ServerAttributes) -> PanelServer {
+        let allocations = server
+            .relationships
+            .allocations
+            .map(|list| list.data.into_iter().map(|a| a.attributes).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let port = allocations
+            .iter()
+            .find(|a| a.is_default)
+            .or(allocations.first())
+            .map(|a| a.port);
+        let extra_ports = allocations
+            .iter()
+            .map(|a| a.port)
+            .filter(|p| Some(*p) != port)
+            .collect();
+
+        let sftp_host = match server.sftp_details.ip.as_str() {
+            "" | "0.0.0.0" | "127.0.0.1" | "localhost" => self.panel_host(),
+            ip => ip.to_string(),
+        };
+
+        PanelServer {
+            identifier: server.identifier,
+            name: server.name,
+            description: server.description.unwrap_or_default(),
+            egg_name: server.relationships.egg.map(|e| e.attributes.name),
+            docker_image: server.docker_image,
+            memory_mb: (server.limits.memory > 0).then_some(server.limits.memory as u32),
+            port,
+            extra_ports,
+            sftp_host,
+            sftp_port: server.sftp_details.port,
+            is_suspended: server.is_suspended,
+        }
+    }
+}
+
+/// Game id for an imported egg, e.g. "pterodactyl-paper"
+pub fn game_type_for(server: &PanelServer) -> GameType {
+    let name = server.egg_name.as_deref().unwrap_or(&server.identifier);
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    GameType(format!("pterodactyl-{}", slug))
+}
+
+/// Rebuild the server's egg as a custom game. The client API doesn't expose the install
+/// script or stop command, so the game relies on the copied files and a plain container stop.
+pub fn egg_to_game(game_type: GameType, server: &PanelServer, startup: &Startup) -> GameConfig {
+    let mut variables: Vec<Variable> = startup.variables.iter().map(to_variable).collect();
+
+    // Wings injects these itself; here they come from the server's settings
+    let defined = |env: &str| startup.variables.iter().any(|v| v.env_variable == env);
+    if !defined("SERVER_MEMORY") {
+        variables.push(system_variable("SERVER_MEMORY", "Memory", "1024", SystemMapping::Ram));
+    }
+    if !defined("SERVER_PORT") {
+        variables.push(system_variable("SERVER_PORT", "Port", "", SystemMapping::Port));
+    }
+    if !defined("SERVER_IP") {
+        variables.push(system_variable("SERVER_IP", "Bind Address", "0.0.0.0", SystemMapping::None));
+    }
+
+    let mut ports = vec![PortConfig {
+        container_port: server.port.unwrap_or(25565),
+        protocol: PortProtocol::Both,
+        description: Some("Game port".to_string()),
+        env_var: None,
+        local_only: false,
+    }];
+    // Extra allocations usually feed a variable (query port, RCON, ...) - tie them to it
+    // so they can move if the port is taken here
+    for port in &server.extra_ports {
+        let env_var = startup
+            .variables
+            .iter()
+            .find(|v| v.server_value.as_deref() == Some(port.to_string().as_str()))
+            .map(|v| v.env_variable.clone());
+        ports.push(PortConfig {
+            container_port: *port,
+            protocol: PortProtocol::Both,
+            description: env_var.clone().or(Some("Additional allocation".to_string())),
+            env_var,
+            local_only: false,
+        });
+    }
+
+    GameConfig {
+        game_type,
+        name: server.egg_name.clone().unwrap_or_else(|| server.name.clone()),
+        description: format!("Imported from Pterodactyl ({})", server.name),
+        docker_image: server.docker_image.clone(),
+        startup: startup.raw_startup_command.clone(),
+        stop_command: String::new(),
+        variables,
+        ports,
+        volume_path: VOLUME_PATH.to_string(),
+        min_ram_mb: 512,
+        recommended_ram_mb: server.memory_mb.unwrap_or(2048),
+        icon: "🦖".to_string(),
+        is_custom: true,
+        ..Default::default()
+    }
+}
+
+/// The server's current variable values, to use as the imported server's config
+pub fn server_config(startup: &Startup) -> HashMap<String, String> {
+    startup
+        .variables
+        .iter()
+        .filter_map(|v| Some((v.env_variable.clone(), v.server_value.clone()?)))
+        .collect()
+}
+
+fn to_variable(var: &StartupVariable) -> Variable {
+    let rules: Vec<&str> = var.rules.split('|').collect();
+    let options = rules.iter().find_map(|r| r.strip_prefix("in:")).map(|values| {
+        values
+            .split(',')
+            .map(|v| SelectOption {
+                value: v.to_string(),
+                label: v.to_string(),
+            })
+            .collect::<Vec<_>>()
+    });
+    let field_type = if options.is_some() {
+        FieldType::Select
+    } else if rules.iter().any(|r| *r == "numeric" || *r == "integer") {
+        FieldType::Number
+    } else {
+        FieldType::Text
+    };
+
+    Variable {
+        env: var.env_variable.clone(),
+        name: var.name.clone(),
+        description: var.description.clone(),
+        default: var.default_value.clone().unwrap_or_default(),
+        system_mapping: None,
+        user_editable: var.is_editable,
+        options,
+        field_type,
+    }
+}
+
+fn system_variable(env: &str, name: &str, default: &str, mapping: SystemMapping) -> Variable {
+    Variable {
+        env: env.to_string(),
+        name: name.to_string(),
+        description: String::new(),
+        default: default.to_string(),
+        system_mapping: Some(mapping),
+        user_editable: false,
+        options: None,
+        field_type: FieldType::Text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_egg_variables_become_game_variables() {
+        let json = r#"{
+            "object": "list",
+            "data": [
+                {"object": "egg_variable", "attributes": {
+                    "name": "Server Jar File", "description": "", "env_variable": "SERVER_JARFILE",
+                    "default_value": "server.jar", "server_value": "paper.jar",
+                    "is_editable": true, "rules": "required|string|max:20"}},
+                {"object": "egg_variable", "attributes": {
+                    "name": "Difficulty", "description": "", "env_variable": "DIFFICULTY",
+                    "default_value": "normal", "server_value": "hard",
+                    "is_editable": true, "rules": "required|in:easy,normal,hard"}},
+                {"object": "egg_variable", "attributes": {
+                    "name": "Query Port", "description": "", "env_variable": "QUERY_PORT",
+                    "default_value": "27016", "server_value": "27020",
+                    "is_editable": false, "rules": "required|integer"}}
+            ],
+            "meta": {"startup_command": "java -jar paper.jar", "raw_startup_command": "java -jar {{SERVER_JARFILE}}"}
+        }"#;
+        let list: List<StartupVariable> = serde_json::from_str(json).unwrap();
+        let startup = Startup {
+            raw_startup_command: list.meta.and_then(|m| m.raw_startup_command).unwrap(),
+            variables: list.data.into_iter().map(|v| v.attributes).collect(),
+        };
+        let server = PanelServer {
+            identifier: "1a2b3c4d".to_string(),
+            name: "Survival".to_string(),
+            description: String::new(),
+            egg_name: Some("Paper (1.20)".to_string()),
+            docker_image: "ghcr.io/pterodactyl/yolks:java_21".to_string(),
+            memory_mb: Some(4096),
+            port: Some(25565),
+            extra_ports: vec![27020],
+            sftp_host: "node.example.com".to_string(),
+            sftp_port: 2022,
+            is_suspended: false,
+        };
+
+        let game_type = game_type_for(&server);
+        assert_eq!(game_type.0, "pterodactyl-paper-1-20");
+
+        let game = egg_to_game(game_type, &server, &startup);
+        assert_eq!(game.startup, "java -jar {{SERVER_JARFILE}}");
+        let difficulty = game.variables.iter().find(|v| v.env == "DIFFICULTY").unwrap();
+        assert_eq!(difficulty.field_type, FieldType::Select);
+        assert_eq!(difficulty.options.as_ref().unwrap().len(), 3);
+        assert!(game.variables.iter().any(|v| v.system_mapping == Some(SystemMapping::Port)));
+        assert_eq!(game.ports[1].env_var.as_deref(), Some("QUERY_PORT"));
+
+        let config = server_config(&startup);
+        assert_eq!(config.get("SERVER_JARFILE").map(String::as_str), Some("paper.jar"));
+    }
+}
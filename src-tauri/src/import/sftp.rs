@@ -0,0 +1,95 @@
+// SFTP download - copies a remote server's files into a local data folder (blocking, run
+// it on a blocking thread)
+
+use serde::{Deserialize, Serialize};
+use ssh2::{Session, Sftp};
+use std::io;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+const SESSION_TIMEOUT_MS: u32 = 60_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SftpLogin {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DownloadProgress {
+    pub files: u64,
+    pub bytes: u64,
+    pub current: String,
+}
+
+/// Open an SFTP session, failing early on a bad address or password
+pub fn connect(login: &SftpLogin) -> Result<Sftp, String> {
+    let addr = (login.host.as_str(), login.port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Can't resolve {}: {}", login.host, e))?
+        .next()
+        .ok_or_else(|| format!("Can't resolve {}", login.host))?;
+    let tcp = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+        .map_err(|e| format!("Can't reach SFTP at {}:{}: {}", login.host, login.port, e))?;
+
+    let mut session = Session::new().map_err(|e| e.to_string())?;
+    session.set_tcp_stream(tcp);
+    session.set_timeout(SESSION_TIMEOUT_MS);
+    session
+        .handshake()
+        .map_err(|e| format!("SFTP handshake failed: {}", e))?;
+    session
+        .userauth_password(&login.username, &login.password)
+        .map_err(|_| "SFTP login failed - check the username and panel password".to_string())?;
+    session.sftp().map_err(|e| e.to_string())
+}
+
+/// Copy everything under the SFTP root into `dest`. Symlinks are skipped.
+pub fn download_all(
+    sftp: &Sftp,
+    dest: &Path,
+    on_progress: &mut dyn FnMut(&DownloadProgress),
+) -> Result<DownloadProgress, String> {
+    let mut progress = DownloadProgress::default();
+    download_dir(sftp, Path::new("/"), dest, &mut progress, on_progress)?;
+    Ok(progress)
+}
+
+fn download_dir(
+    sftp: &Sftp,
+    remote: &Path,
+    local: &Path,
+    progress: &mut DownloadProgress,
+    on_progress: &mut dyn FnMut(&DownloadProgress),
+) -> Result<(), String> {
+    std::fs::create_dir_all(local).map_err(|e| e.to_string())?;
+
+    let entries = sftp
+        .readdir(remote)
+        .map_err(|e| format!("Failed to list {}: {}", remote.display(), e))?;
+    for (path, stat) in entries {
+        let Some(name) = path.file_name() else { continue };
+        let target = local.join(name);
+
+        if stat.is_dir() {
+            download_dir(sftp, &path, &target, progress, on_progress)?;
+        } else if stat.is_file() {
+            let mut remote_file = sftp
+                .open(&path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            let mut local_file = std::fs::File::create(&target).map_err(|e| e.to_string())?;
+            let copied = io::copy(&mut remote_file, &mut local_file)
+                .map_err(|e| format!("Failed to download {}: {}", path.display(), e))?;
+
+            progress.files += 1;
+            progress.bytes += copied;
+            progress.current = path.to_string_lossy().to_string();
+            on_progress(progress);
+        }
+    }
+    Ok(())
+}
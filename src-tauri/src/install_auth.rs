@@ -0,0 +1,135 @@
+// Install sign-in - spotting an installer (like Hytale's downloader) or a first start that
+// waits for the user to log in through a device-code link, and noticing when it's done.
+// Opening the link is up to the frontend, so it follows the user's settings.
+
+use std::collections::HashSet;
+
+/// Words an auth link has somewhere in it
+const URL_HINTS: &[&str] = &["oauth", "auth", "login", "verify", "device"];
+/// Lines an installer prints once the login went through
+const DONE_HINTS: &[&str] = &[
+    "authentication successful",
+    "successfully authenticated",
+    "authenticated successfully",
+    "logged in successfully",
+    "login successful",
+];
+
+/// A link the user has to open, with the code to enter there when the installer printed one
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthPrompt {
+    pub url: String,
+    pub code: Option<String>,
+}
+
+/// Watches one install's (or server's) output. Each link is reported once; the code may come
+/// with the link (`?user_code=`) or on a line of its own.
+#[derive(Debug, Default)]
+pub struct AuthWatcher {
+    seen: HashSet<String>,
+    pending: Option<AuthPrompt>,
+}
+
+pub enum AuthUpdate {
+    Required(AuthPrompt),
+    Completed,
+}
+
+impl AuthWatcher {
+    pub fn observe(&mut self, line: &str) -> Option<AuthUpdate> {
+        if let Some(url) = auth_url(line) {
+            if !self.seen.insert(url.clone()) {
+                return None;
+            }
+            let code = query_param(&url, "user_code").or_else(|| line_code(line));
+            let prompt = AuthPrompt { url, code };
+            self.pending = Some(prompt.clone());
+            return Some(AuthUpdate::Required(prompt));
+        }
+
+        let pending = self.pending.as_mut()?;
+        if pending.code.is_none() {
+            if let Some(code) = line_code(line) {
+                pending.code = Some(code);
+                return Some(AuthUpdate::Required(pending.clone()));
+            }
+        }
+        let lower = line.to_ascii_lowercase();
+        if DONE_HINTS.iter().any(|hint| lower.contains(hint)) {
+            self.pending = None;
+            return Some(AuthUpdate::Completed);
+        }
+        None
+    }
+}
+
+/// The first https link on the line that looks like a login page
+fn auth_url(line: &str) -> Option<String> {
+    line.split_whitespace()
+        .filter(|word| word.contains("https://"))
+        .map(|word| {
+            let start = word.find("https://").unwrap_or(0);
+            word[start..].trim_end_matches(['"', '\'', '>', ')', ',', '.'])
+        })
+        .find(|url| {
+            let lower = url.to_ascii_lowercase();
+            URL_HINTS.iter().any(|hint| lower.contains(hint))
+        })
+        .map(str::to_string)
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    let (_, query) = url.split_once('?')?;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// A code printed as "code: ABCD-1234" (or "Enter code ABCD-1234")
+fn line_code(line: &str) -> Option<String> {
+    let lower = line.to_ascii_lowercase();
+    let at = lower.rfind("code")? + "code".len();
+    let code = line[at..]
+        .trim_start_matches(|c: char| c == ':' || c.is_whitespace())
+        .split_whitespace()
+        .next()?
+        .trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    let valid = (4..=16).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && code.chars().any(|c| c.is_ascii_digit() || c.is_ascii_uppercase());
+    valid.then(|| code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_reports_links_codes_and_completion() {
+        let mut watcher = AuthWatcher::default();
+        assert!(watcher.observe("[Serverwave] Running Hytale downloader...").is_none());
+
+        let line = "Visit https://oauth.accounts.hytale.com/oauth2/device/verify?user_code=AbC123xy to authenticate";
+        match watcher.observe(line) {
+            Some(AuthUpdate::Required(prompt)) => {
+                assert_eq!(prompt.url, "https://oauth.accounts.hytale.com/oauth2/device/verify?user_code=AbC123xy");
+                assert_eq!(prompt.code.as_deref(), Some("AbC123xy"));
+            }
+            _ => panic!("expected an auth prompt"),
+        }
+        // Printed again while it waits - not a new prompt
+        assert!(watcher.observe(line).is_none());
+        assert!(matches!(watcher.observe("Authentication successful!"), Some(AuthUpdate::Completed)));
+
+        let mut watcher = AuthWatcher::default();
+        assert!(matches!(watcher.observe("Go to https://example.com/device"), Some(AuthUpdate::Required(p)) if p.code.is_none()));
+        match watcher.observe("and enter code: WXYZ-4821") {
+            Some(AuthUpdate::Required(prompt)) => assert_eq!(prompt.code.as_deref(), Some("WXYZ-4821")),
+            _ => panic!("expected the code"),
+        }
+        assert!(watcher.observe("Downloading https://cdn.example.com/server.zip").is_none());
+    }
+}
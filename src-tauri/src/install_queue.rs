@@ -0,0 +1,116 @@
+// Install queue - which servers are installing or waiting for an install slot right now, and
+// which installs failed, so the dashboard can show all install activity in one place
+
+use serde::Serialize;
+use serverwave_core::server::{load_all_server_configs, Server};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    /// Installs in progress, by server
+    static ref ACTIVE: Mutex<HashMap<String, InstallActivity>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallPhase {
+    Installing,
+    /// Waiting for a free install slot
+    Queued,
+    /// The last attempt failed - retry_install picks it up again
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallActivity {
+    pub server_id: String,
+    pub server_name: String,
+    pub game_type: String,
+    pub phase: InstallPhase,
+    /// The last progress message the script printed
+    pub step: Option<String>,
+    /// Failed attempts before this one
+    pub attempts: u32,
+    pub queued_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub started_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub failed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub exit_code: Option<i64>,
+}
+
+/// Held while an install is queued or running - takes it off the list when dropped
+pub struct Tracked(String);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        ACTIVE.lock().unwrap().remove(&self.0);
+    }
+}
+
+/// Put a server on the list as waiting for a slot
+pub fn track(server: &Server) -> Tracked {
+    let activity = InstallActivity {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        game_type: server.game_type.to_string(),
+        phase: InstallPhase::Queued,
+        step: None,
+        attempts: server.install_state.as_ref().map(|s| s.attempts).unwrap_or(0),
+        queued_at: Some(chrono::Utc::now()),
+        started_at: None,
+        failed_at: None,
+        exit_code: None,
+    };
+    ACTIVE.lock().unwrap().insert(server.id.clone(), activity);
+    Tracked(server.id.clone())
+}
+
+/// The install got a slot and its script is running
+pub fn started(server_id: &str) {
+    if let Some(activity) = ACTIVE.lock().unwrap().get_mut(server_id) {
+        activity.phase = InstallPhase::Installing;
+        activity.started_at = Some(chrono::Utc::now());
+    }
+}
+
+/// Follow install output for progress messages
+pub fn observe(server_id: &str, line: &str) {
+    let Some(step) = line.strip_prefix("[Serverwave] ") else { return };
+    if let Some(activity) = ACTIVE.lock().unwrap().get_mut(server_id) {
+        activity.step = Some(step.trim().to_string());
+    }
+}
+
+fn failed(server: &Server) -> Option<InstallActivity> {
+    let state = server.install_state.as_ref()?;
+    Some(InstallActivity {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        game_type: server.game_type.to_string(),
+        phase: InstallPhase::Failed,
+        step: state.failed_step.clone(),
+        attempts: state.attempts,
+        queued_at: None,
+        started_at: None,
+        failed_at: state.failed_at,
+        exit_code: state.exit_code,
+    })
+}
+
+/// Running installs first, then the queue in arrival order, then failed installs
+pub fn snapshot() -> Vec<InstallActivity> {
+    let mut list: Vec<InstallActivity> = ACTIVE.lock().unwrap().values().cloned().collect();
+    for server in load_all_server_configs() {
+        if list.iter().any(|a| a.server_id == server.id) {
+            continue;
+        }
+        list.extend(failed(&server));
+    }
+    list.sort_by(|a, b| {
+        a.phase
+            .cmp(&b.phase)
+            .then_with(|| a.started_at.cmp(&b.started_at))
+            .then_with(|| a.queued_at.cmp(&b.queued_at))
+            .then_with(|| b.failed_at.cmp(&a.failed_at))
+    });
+    list
+}
@@ -1,6 +1,43 @@
 // Serverwave Anywhere Library
 // Shared types and utilities
 
+pub mod api;
+pub mod audit;
+pub mod backup_targets;
+pub mod backups;
+pub mod capacity;
 pub mod commands;
-pub mod docker;
-pub mod games;
+pub mod content;
+pub mod crash_loop;
+pub mod database;
+pub mod deep_link;
+pub mod disk_guard;
+pub mod docker_events;
+pub mod error;
+pub mod events;
+pub mod game_sync;
+pub mod gslt;
+pub mod hooks;
+pub mod images;
+pub mod import;
+pub mod install_auth;
+pub mod install_queue;
+pub mod logging;
+pub mod maintenance;
+pub mod minecraft;
+pub mod network;
+pub mod nodes;
+pub mod notify;
+pub mod palworld;
+pub mod port_conflicts;
+pub mod reconcile;
+pub mod references;
+pub mod scheduler;
+pub mod security;
+pub mod steam;
+pub mod tray;
+pub mod updates;
+pub mod wake;
+pub mod wipes;
+
+pub use serverwave_core::{docker, games};
@@ -0,0 +1,131 @@
+// App log file - tracing output is also written to logs/serverwave.log, rotated by size
+
+use serverwave_core::paths;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing_subscriber::fmt::MakeWriter;
+
+const LOG_FILE: &str = "serverwave.log";
+/// Size at which the current file is rotated out
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the current one (serverwave.log.1 is the newest)
+const KEEP_ROTATED: usize = 3;
+
+pub struct RotatingFile {
+    state: Mutex<Option<(File, u64)>>,
+}
+
+impl RotatingFile {
+    pub fn new() -> Self {
+        std::fs::create_dir_all(paths::logs_dir()).ok();
+        Self {
+            state: Mutex::new(open_current()),
+        }
+    }
+}
+
+impl Default for RotatingFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn open_current() -> Option<(File, u64)> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(current_log_path())
+        .ok()?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    Some((file, size))
+}
+
+fn rotate() {
+    let dir = paths::logs_dir();
+    for i in (1..KEEP_ROTATED).rev() {
+        let from = dir.join(format!("{}.{}", LOG_FILE, i));
+        if from.exists() {
+            std::fs::rename(&from, dir.join(format!("{}.{}", LOG_FILE, i + 1))).ok();
+        }
+    }
+    std::fs::rename(current_log_path(), dir.join(format!("{}.1", LOG_FILE))).ok();
+}
+
+pub fn current_log_path() -> PathBuf {
+    paths::logs_dir().join(LOG_FILE)
+}
+
+/// Log files from oldest to newest, ending with the current one
+pub fn log_files() -> Vec<PathBuf> {
+    let dir = paths::logs_dir();
+    (1..=KEEP_ROTATED)
+        .rev()
+        .map(|i| dir.join(format!("{}.{}", LOG_FILE, i)))
+        .chain(std::iter::once(current_log_path()))
+        .filter(|p| p.exists())
+        .collect()
+}
+
+pub struct LogWriter<'a>(&'a RotatingFile);
+
+impl Write for LogWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.0.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.as_ref().is_some_and(|(_, size)| *size >= MAX_FILE_BYTES) {
+            *state = None;
+            rotate();
+        }
+        if state.is_none() {
+            *state = open_current();
+        }
+        // Logging must never take the app down - drop the line if the file is unavailable
+        if let Some((file, size)) = state.as_mut() {
+            file.write_all(buf)?;
+            *size += buf.len() as u64;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.0.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.as_mut() {
+            Some((file, _)) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = LogWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogWriter(self)
+    }
+}
+
+/// The last `lines` log lines containing `filter` (case-insensitive), oldest first
+pub fn read_logs(filter: Option<&str>, lines: usize) -> Vec<String> {
+    let filter = filter.map(str::to_lowercase).filter(|f| !f.is_empty());
+    let mut matched: Vec<String> = Vec::new();
+    for path in log_files() {
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        matched.extend(
+            content
+                .lines()
+                .filter(|line| {
+                    filter
+                        .as_ref()
+                        .map(|f| line.to_lowercase().contains(f))
+                        .unwrap_or(true)
+                })
+                .map(str::to_string),
+        );
+        // Only the tail is returned - don't hold more than needed
+        if matched.len() > lines {
+            matched.drain(..matched.len() - lines);
+        }
+    }
+    matched
+}
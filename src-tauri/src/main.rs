@@ -3,15 +3,109 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod api;
+mod audit;
+mod backup_targets;
+mod backups;
+mod capacity;
 mod commands;
-mod docker;
-mod games;
+mod content;
+mod crash_loop;
+mod database;
+mod deep_link;
+mod disk_guard;
+mod docker_events;
+mod error;
+mod events;
+mod game_sync;
+mod gslt;
+mod hooks;
+mod images;
+mod import;
+mod install_auth;
+mod install_queue;
+mod logging;
+mod maintenance;
+mod minecraft;
+mod network;
+mod nodes;
+mod notify;
+mod palworld;
+mod port_conflicts;
+mod reconcile;
+mod references;
+mod scheduler;
+mod security;
+mod steam;
+mod tray;
+mod updates;
+mod wake;
+mod wipes;
 
+use serverwave_core::{docker, games};
+
+use api::tls::TlsSource;
+use api::ApiOptions;
 use commands::games::GamesState;
+use commands::nodes::NodesState;
+use commands::docker::DockerState;
 use commands::server::ServerState;
 use tauri::Manager;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tracing_subscriber::prelude::*;
 use tracing_subscriber::EnvFilter;
 
+/// Command line flags: `--headless` runs without a window and serves the API,
+/// `--api` serves it alongside the window, `--agent` is headless and reachable from
+/// other machines, `--api-bind <addr:port>` picks the address. An API reachable from other
+/// machines serves HTTPS with a self-signed certificate, or the PEM files given with
+/// `--api-cert <path> --api-key <path>`; `--api-insecure` keeps it on plain HTTP.
+struct LaunchOptions {
+    headless: bool,
+    api: Option<ApiOptions>,
+}
+
+fn launch_options() -> LaunchOptions {
+    let args: Vec<String> = std::env::args().collect();
+    let value_of = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1));
+    let agent = args.iter().any(|a| a == "--agent");
+    let headless = agent || args.iter().any(|a| a == "--headless");
+    let mut api = (headless || args.iter().any(|a| a == "--api")).then(ApiOptions::default);
+    if agent {
+        api = Some(ApiOptions::public());
+    }
+
+    if let Some(bind) = value_of("--api-bind") {
+        match bind.parse::<std::net::SocketAddr>() {
+            Ok(addr) => {
+                api = Some(ApiOptions {
+                    bind: addr,
+                    tls: (!addr.ip().is_loopback()).then_some(TlsSource::SelfSigned),
+                })
+            }
+            Err(_) => tracing::warn!("Ignoring invalid --api-bind address: {}", bind),
+        }
+    }
+
+    if let Some(options) = api.as_mut() {
+        match (value_of("--api-cert"), value_of("--api-key")) {
+            (Some(cert), Some(key)) => {
+                options.tls = Some(TlsSource::Files {
+                    cert: cert.into(),
+                    key: key.into(),
+                })
+            }
+            (None, None) => {}
+            _ => tracing::warn!("--api-cert and --api-key go together - ignoring them"),
+        }
+        if args.iter().any(|a| a == "--api-insecure") {
+            options.tls = None;
+        }
+    }
+
+    LaunchOptions { headless, api }
+}
+
 fn main() {
     // Configure logging to filter out noisy tao warnings
     let filter = EnvFilter::try_from_default_env()
@@ -21,30 +115,130 @@ fn main() {
                 .add_directive("wry=error".parse().unwrap())
         });
     
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
+    // Console output plus logs/serverwave.log, so errors can be read from inside the app
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(logging::RotatingFile::new()),
+        )
         .init();
 
+    // Print the API token (kept in the OS keychain) for setting up remote clients
+    if std::env::args().any(|a| a == "--print-api-token") {
+        match tauri::async_runtime::block_on(api::auth::load_or_create_token()) {
+            Ok(token) => println!("{}", token),
+            Err(e) => eprintln!("Failed to read API token: {}", e),
+        }
+        return;
+    }
+
+    let launch = launch_options();
+    commands::settings::apply_startup_settings();
+
     tauri::Builder::default()
+        // Must come first: a second launch hands its arguments (and any link) to this
+        // instance and exits, instead of starting its own pollers and log streams
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            tracing::info!("Another launch was redirected to this instance");
+            match argv.iter().find(|a| a.starts_with(deep_link::SCHEME)) {
+                Some(url) => deep_link::handle(app, url),
+                None => tray::show_main_window(app),
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .manage(ServerState::default())
+        .manage(DockerState::default())
         .manage(GamesState::default())
-        .setup(|app| {
+        .manage(NodesState::default())
+        .setup(move |app| {
             let app_data_dir = app.path().app_data_dir().expect("Failed to get app data dir");
             std::fs::create_dir_all(&app_data_dir).ok();
 
-            if let Some(home) = directories::UserDirs::new() {
-                let servers_dir = home.home_dir().join("ServerWaveAnywhere").join("servers");
-                std::fs::create_dir_all(&servers_dir).ok();
+            std::fs::create_dir_all(serverwave_core::paths::servers_dir()).ok();
+            std::fs::create_dir_all(serverwave_core::paths::config_dir()).ok();
+
+            if launch.headless {
+                tracing::info!("Running headless, no window will be opened");
+            } else {
+                let window = app.config().app.windows.first().cloned().ok_or("No window configured")?;
+                tauri::WebviewWindowBuilder::from_config(app.handle(), &window)?.build()?;
+                tray::build(app.handle())?;
+            }
 
-                let config_dir = home.home_dir().join("ServerWaveAnywhere").join("config");
-                std::fs::create_dir_all(&config_dir).ok();
+            // Installed builds register the scheme at install time; dev builds do it here
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                tracing::warn!("Failed to register serverwave:// links: {}", e);
+            }
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    deep_link::handle(&handle, url.as_str());
+                }
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    deep_link::set_pending(url.as_str());
+                }
+            }
+
+            // Before anything saves a server config, which would drop the token unmoved
+            tauri::async_runtime::block_on(network::tunnel::migrate_tokens(&app.state::<ServerState>().configs));
+
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                commands::nodes::start_relays(&handle, &handle.state::<NodesState>()).await;
+            });
+
+            updates::spawn_checker(app.handle().clone());
+            images::spawn_refresher(app.handle().clone());
+            wipes::spawn_scheduler(app.handle().clone());
+            backups::spawn_scheduler(app.handle().clone());
+            wake::spawn_scheduler(app.handle().clone());
+            scheduler::spawn_scheduler(app.handle().clone());
+            crash_loop::spawn_restarter(app.handle().clone());
+            reconcile::spawn(app.handle().clone());
+            disk_guard::spawn_monitor(app.handle().clone());
+            docker_events::spawn_watcher(app.handle().clone());
+
+            if let Some(options) = launch.api.clone() {
+                let handle = app.handle().clone();
+                let headless = launch.headless;
+                tauri::async_runtime::spawn(async move {
+                    // Nobody can open Settings on a headless machine - offer a code to pair with
+                    if headless && options.tls.is_some() {
+                        let pairing = api::pairing::create_code();
+                        tracing::info!(
+                            "Pair a remote app with code {} (valid until {})",
+                            pairing.code,
+                            pairing.expires_at.format("%H:%M UTC")
+                        );
+                    }
+                    if let Err(e) = api::serve(handle, options).await {
+                        tracing::error!("API server stopped: {}", e);
+                    }
+                });
             }
 
             tracing::info!("Serverwave Anywhere initialized");
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .on_window_event(|window, event| match event {
+            // Closing the main window keeps the app in the tray; quit from the tray menu
+            tauri::WindowEvent::CloseRequested { api, .. }
+                if window.label() == "main" && window.app_handle().tray_by_id("main").is_some() =>
+            {
+                api.prevent_close();
+                let _ = window.hide();
+            }
+            tauri::WindowEvent::Destroyed => security::read_only::set(window.label(), false),
+            _ => {}
+        })
+        .invoke_handler(security::read_only::guard(tauri::generate_handler![
             commands::server::create_server,
             commands::server::start_server,
             commands::server::stop_server,
@@ -52,22 +246,175 @@ fn main() {
             commands::server::list_servers,
             commands::server::get_server_status,
             commands::server::send_command,
+            commands::server::send_rcon_command,
             commands::server::get_server_logs,
             commands::server::get_server_stats,
+            commands::server::get_server_processes,
             commands::server::get_server_disk_usage,
             commands::server::attach_server,
             commands::server::detach_server,
             commands::server::update_server_config,
+            commands::server::update_server_env,
+            commands::server::update_server_maintenance,
+            commands::server::update_server_install_network,
+            commands::server::set_server_auto_restart,
+            commands::server::set_start_schedule,
+            commands::server::get_crash_report,
+            commands::server::apply_config,
+            commands::server::preview_startup,
+            commands::server::preview_config_changes,
             commands::server::run_install_script,
+            commands::server::retry_install,
             commands::server::reinstall_server,
             commands::server::update_server_game,
+            commands::server::check_game_updates,
+            commands::server::get_pending_updates,
+            commands::server::get_install_queue,
+            commands::server::pin_game_version,
+            commands::server::rollback_game_version,
             commands::server::check_needs_install,
+            commands::health::get_server_health,
+            commands::dashboard::get_dashboard_summary,
+            commands::backups::list_backups,
+            commands::backups::create_backup,
+            commands::backups::restore_backup,
+            commands::backups::delete_backup,
+            commands::backups::set_backup_schedule,
+            commands::backups::set_backup_target,
+            commands::backups::remove_backup_target,
+            commands::backups::test_backup_target,
+            commands::backups::push_backup,
+            commands::backups::list_remote_backups,
+            commands::backups::pull_backup,
+            commands::network::set_server_upnp,
+            commands::network::test_port_mapping,
+            commands::network::open_firewall_ports,
+            commands::network::close_firewall_ports,
+            commands::network::get_connection_info,
+            commands::network::get_server_network,
+            commands::network::check_port_reachable,
+            commands::network::set_server_tunnel,
+            commands::network::get_tunnel_status,
+            commands::network::get_port_allocations,
+            commands::proxy::create_proxy_network,
+            commands::proxy::list_proxy_networks,
+            commands::proxy::delete_proxy_network,
+            commands::settings::get_app_settings,
+            commands::settings::get_disk_space,
+            commands::settings::update_app_settings,
+            commands::settings::set_data_directory,
+            commands::state::export_app_state,
+            commands::state::import_app_state,
+            commands::links::take_pending_link,
+            commands::nodes::list_nodes,
+            commands::nodes::add_node,
+            commands::nodes::pair_node,
+            commands::nodes::remove_node,
+            commands::nodes::node_invoke,
+            commands::import::list_pterodactyl_servers,
+            commands::import::import_pterodactyl_server,
+            commands::import::scan_local_server,
+            commands::import::import_local_server,
+            commands::import::export_server,
+            commands::import::import_server,
+            commands::security::get_lock_status,
+            commands::security::set_app_password,
+            commands::security::unlock_app,
+            commands::security::lock_app,
+            commands::security::get_read_only_mode,
+            commands::security::set_read_only_mode,
+            commands::security::open_read_only_window,
+            commands::security::get_api_token,
+            commands::security::get_audit_journal,
+            commands::users::list_users,
+            commands::users::add_user,
+            commands::users::update_user,
+            commands::users::remove_user,
+            commands::users::get_current_user,
+            commands::remote::get_remote_access,
+            commands::remote::create_pairing_code,
+            commands::remote::regenerate_api_certificate,
             commands::docker::check_docker_status,
             commands::docker::get_docker_info,
+            commands::docker::detect_docker_sockets,
+            commands::onboarding::run_onboarding_checks,
+            commands::players::get_player_sessions,
+            commands::players::query_server,
+            commands::docker::test_docker_connection,
+            commands::docker::wake_docker_host,
+            commands::docker::refresh_game_images,
+            commands::docker::get_stale_images,
+            commands::docker::get_install_images,
+            commands::docker::clean_install_images,
+            commands::docker::get_startup_reconciliation,
+            commands::docker::recreate_server_container,
+            commands::docker::check_image_platform,
+            commands::diagnostics::export_diagnostics,
+            commands::diagnostics::get_app_logs,
+            commands::content::search_modrinth,
+            commands::content::get_modrinth_versions,
+            commands::content::install_mod,
+            commands::content::check_mod_updates,
+            commands::content::list_installed_content,
+            commands::content::update_all_content,
+            commands::content::search_curseforge,
+            commands::content::install_curseforge_mod,
+            commands::content::install_curseforge_modpack,
+            commands::content::get_server_versions,
+            commands::content::change_server_version,
+            commands::content::get_content_cache,
+            commands::content::clear_content_cache,
+            commands::minecraft::list_worlds,
+            commands::minecraft::set_active_world,
+            commands::minecraft::import_world,
+            commands::minecraft::delete_world,
+            commands::minecraft::list_players,
+            commands::minecraft::add_player,
+            commands::minecraft::remove_player,
+            commands::minecraft::list_datapacks,
+            commands::minecraft::install_datapack,
+            commands::minecraft::remove_datapack,
+            commands::minecraft::get_resource_pack,
+            commands::minecraft::set_resource_pack,
+            commands::minecraft::host_resource_pack,
+            commands::minecraft::clear_resource_pack,
+            commands::actions::list_game_actions,
+            commands::actions::run_game_action,
+            commands::wipes::get_wipe_schedule,
+            commands::wipes::set_wipe_schedule,
+            commands::wipes::wipe_rust_server,
+            commands::schedules::add_schedule,
+            commands::schedules::list_schedules,
+            commands::schedules::remove_schedule,
+            commands::schedules::run_schedule_now,
+            commands::palworld::get_palworld_status,
+            commands::palworld::palworld_announce,
+            commands::palworld::palworld_kick,
+            commands::palworld::palworld_ban,
+            commands::palworld::palworld_unban,
+            commands::palworld::palworld_save,
+            commands::database::get_database,
+            commands::database::add_database,
+            commands::database::remove_database,
+            commands::steam::get_steam_account,
+            commands::steam::set_steam_account,
+            commands::steam::clear_steam_account,
+            commands::steam::submit_steam_guard_code,
+            commands::steam::list_server_tokens,
+            commands::steam::add_server_token,
+            commands::steam::remove_server_token,
+            commands::steam::assign_server_token,
+            commands::steam::check_server_token,
+            commands::steam::set_steam_web_api_key,
+            commands::hooks::list_hooks,
+            commands::hooks::save_hook,
+            commands::hooks::delete_hook,
+            commands::hooks::test_hook,
             commands::games::list_available_games,
             commands::games::get_game_config,
             commands::games::add_custom_game,
             commands::games::update_game,
+            commands::games::lint_install_script,
             commands::games::delete_game,
             commands::games::export_game,
             commands::games::export_all_custom_games,
@@ -75,6 +422,10 @@ fn main() {
             commands::games::import_games,
             commands::games::reset_games_to_defaults,
             commands::games::get_games_config_path,
+            commands::games::get_game_sync_status,
+            commands::games::pull_game_library,
+            commands::games::push_game_library,
+            commands::games::set_game_sync_token,
             commands::files::list_directory,
             commands::files::read_file_text,
             commands::files::write_file_text,
@@ -85,7 +436,8 @@ fn main() {
             commands::files::move_path,
             commands::files::copy_path,
             commands::files::get_file_info,
-        ])
+            commands::files::get_server_roots,
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,57 @@
+// Maintenance windows - keeps automation (scheduled wipes, backups and tasks, automatic
+// updates) off a server during its quiet hours and outside its maintenance windows. A
+// server's own policy replaces the global one from the settings.
+
+use crate::commands::settings::load_app_settings;
+use crate::events::{self, ServerEvent};
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serverwave_core::server::{MaintenancePolicy, Server};
+use tauri::AppHandle;
+
+pub enum Window {
+    Open,
+    /// Automation has to wait until `opens`, or can't run at all when that's None
+    Closed { opens: Option<DateTime<Utc>> },
+}
+
+pub fn policy_for(server: &Server) -> MaintenancePolicy {
+    server.maintenance.clone().unwrap_or_else(|| load_app_settings().maintenance)
+}
+
+/// Whether automation may touch the server right now
+pub fn window(server: &Server) -> Window {
+    window_at(server, Utc::now())
+}
+
+/// Whether automation may touch the server at `at`, like a restart at the end of its countdown
+pub fn window_at(server: &Server, at: DateTime<Utc>) -> Window {
+    let policy = policy_for(server);
+    let at = at.with_timezone(&Local).naive_local();
+    if policy.allows(at) {
+        return Window::Open;
+    }
+    let opens = policy
+        .next_allowed(at)
+        .and_then(|time| Local.from_local_datetime(&time).earliest())
+        .map(|time| time.with_timezone(&Utc));
+    Window::Closed { opens }
+}
+
+/// Tell the user `task` was held back - until `deferred_until`, or skipped when that's None
+pub fn report_held(app: &AppHandle, server_id: &str, task: &str, deferred_until: Option<DateTime<Utc>>) {
+    let message = match deferred_until {
+        Some(until) => format!(
+            "{} put off until {} - it's outside the server's maintenance window",
+            task,
+            until.with_timezone(&Local).format("%b %-d %H:%M")
+        ),
+        None => format!("{} skipped - it's outside the server's maintenance window", task),
+    };
+    tracing::info!("{}: {}", server_id, message);
+    events::emit(app, ServerEvent::AutomationHeld {
+        server_id: server_id.to_string(),
+        task: task.to_string(),
+        message,
+        deferred_until,
+    });
+}
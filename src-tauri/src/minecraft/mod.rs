@@ -0,0 +1,7 @@
+// Minecraft module - the files a server keeps in its data folder: server.properties,
+// worlds, player lists, packs and the like
+
+pub mod packs;
+pub mod players;
+pub mod properties;
+pub mod worlds;
@@ -0,0 +1,188 @@
+// Packs - datapacks in the active world's datapacks/ folder and the resource pack
+// server.properties points players at
+
+use super::{properties, worlds};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Resource packs the app serves itself, relative to the data folder
+pub const HOSTED_DIR: &str = "resourcepacks";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Datapack {
+    pub name: String,
+    /// An unpacked folder rather than a zip
+    pub folder: bool,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourcePack {
+    pub url: String,
+    pub sha1: String,
+    pub required: bool,
+}
+
+pub fn datapacks_dir(data_path: &Path) -> PathBuf {
+    data_path.join(worlds::active_world(data_path)).join("datapacks")
+}
+
+pub fn list_datapacks(data_path: &Path) -> Vec<Datapack> {
+    let Ok(entries) = std::fs::read_dir(datapacks_dir(data_path)) else { return Vec::new() };
+    let mut packs: Vec<Datapack> = entries
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            let name = e.file_name().to_string_lossy().to_string();
+            if path.is_dir() && path.join("pack.mcmeta").is_file() {
+                Some(Datapack { name, folder: true, size_bytes: 0 })
+            } else if path.extension().is_some_and(|ext| ext == "zip") {
+                let size_bytes = e.metadata().map(|m| m.len()).unwrap_or(0);
+                Some(Datapack { name, folder: false, size_bytes })
+            } else {
+                None
+            }
+        })
+        .collect();
+    packs.sort_by_key(|p| p.name.to_lowercase());
+    packs
+}
+
+/// Whether a zip has pack.mcmeta at its top, the only place Minecraft looks for it
+fn zip_is_pack(path: &Path) -> Result<bool, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    let found = zip.file_names().any(|name| name == "pack.mcmeta");
+    Ok(found)
+}
+
+/// Put a datapack zip or folder into the active world. A zip that isn't a pack is removed again.
+pub fn install_datapack(data_path: &Path, source: &Path) -> Result<Datapack, String> {
+    let dir = datapacks_dir(data_path);
+    let name = source
+        .file_name()
+        .ok_or_else(|| format!("{} isn't a datapack", source.display()))?
+        .to_string_lossy()
+        .to_string();
+    let target = dir.join(&name);
+
+    if source.is_dir() {
+        if !source.join("pack.mcmeta").is_file() {
+            return Err("That folder isn't a datapack (no pack.mcmeta)".to_string());
+        }
+        if target.exists() {
+            std::fs::remove_dir_all(&target).map_err(|e| e.to_string())?;
+        }
+        serverwave_core::paths::copy_dir_all(source, &target).map_err(|e| e.to_string())?;
+    } else {
+        if !zip_is_pack(source)? {
+            return Err(format!("{} isn't a datapack (no pack.mcmeta at its top)", name));
+        }
+        if source != target {
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            std::fs::copy(source, &target).map_err(|e| e.to_string())?;
+        }
+    }
+
+    list_datapacks(data_path)
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("{} was copied but isn't a datapack", name))
+}
+
+pub fn remove_datapack(data_path: &Path, name: &str) -> Result<(), String> {
+    worlds::validate_name(name)?;
+    let path = datapacks_dir(data_path).join(name);
+    if path.is_dir() {
+        std::fs::remove_dir_all(&path).map_err(|e| e.to_string())
+    } else if path.is_file() {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())
+    } else {
+        Err(format!("There's no datapack called '{}'", name))
+    }
+}
+
+pub fn sha1_file(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha1::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+pub fn resource_pack(data_path: &Path) -> Option<ResourcePack> {
+    let url = properties::get(data_path, "resource-pack").filter(|url| !url.is_empty())?;
+    Some(ResourcePack {
+        // server.properties escapes the colon in URLs
+        url: url.replace("\\:", ":"),
+        sha1: properties::get(data_path, "resource-pack-sha1").unwrap_or_default(),
+        required: properties::get(data_path, "require-resource-pack").as_deref() == Some("true"),
+    })
+}
+
+pub fn set_resource_pack(data_path: &Path, pack: &ResourcePack) -> Result<(), String> {
+    properties::set(data_path, "resource-pack", &pack.url.replace(':', "\\:"))?;
+    properties::set(data_path, "resource-pack-sha1", &pack.sha1)?;
+    properties::set(data_path, "require-resource-pack", if pack.required { "true" } else { "false" })
+}
+
+pub fn clear_resource_pack(data_path: &Path) -> Result<(), String> {
+    properties::set(data_path, "resource-pack", "")?;
+    properties::set(data_path, "resource-pack-sha1", "")?;
+    properties::set(data_path, "require-resource-pack", "false")
+}
+
+/// A hosted pack's file, only ever a zip straight inside resourcepacks/
+pub fn hosted_pack(data_path: &Path, file_name: &str) -> Option<PathBuf> {
+    if worlds::validate_name(file_name).is_err() || !file_name.ends_with(".zip") {
+        return None;
+    }
+    let path = data_path.join(HOSTED_DIR).join(file_name);
+    path.is_file().then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_datapack_needs_mcmeta_and_resource_pack_roundtrips() {
+        let dir = std::env::temp_dir().join(format!("serverwave-packs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("world")).unwrap();
+
+        let zip_path = dir.join("pack.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        zip.start_file("pack.mcmeta", zip::write::SimpleFileOptions::default()).unwrap();
+        zip.write_all(br#"{"pack":{"pack_format":15}}"#).unwrap();
+        zip.finish().unwrap();
+        assert_eq!(install_datapack(&dir, &zip_path).unwrap().name, "pack.zip");
+        assert!(dir.join("world/datapacks/pack.zip").is_file());
+        assert!(install_datapack(&dir, &dir.join("world")).is_err());
+
+        std::fs::write(dir.join("hello.txt"), b"hello").unwrap();
+        assert_eq!(sha1_file(&dir.join("hello.txt")).unwrap(), "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d");
+
+        let pack = ResourcePack {
+            url: "https://example.com/pack.zip".to_string(),
+            sha1: "abc".to_string(),
+            required: true,
+        };
+        set_resource_pack(&dir, &pack).unwrap();
+        let read = resource_pack(&dir).unwrap();
+        assert_eq!(read.url, pack.url);
+        assert!(read.required);
+        clear_resource_pack(&dir).unwrap();
+        assert!(resource_pack(&dir).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+// Player lists - whitelist.json, ops.json and banned-players.json on Java servers and
+// allowlist.json on Bedrock. Unknown fields in the files are kept as they are.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use std::time::Duration;
+
+const MOJANG_PROFILE_API: &str = "https://api.mojang.com/users/profiles/minecraft";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Level `op` gives without an argument
+const DEFAULT_OP_LEVEL: u8 = 4;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlayerList {
+    Whitelist,
+    Ops,
+    Bans,
+    /// Bedrock's whitelist
+    Allowlist,
+}
+
+impl PlayerList {
+    pub fn file(&self) -> &'static str {
+        match self {
+            PlayerList::Whitelist => "whitelist.json",
+            PlayerList::Ops => "ops.json",
+            PlayerList::Bans => "banned-players.json",
+            PlayerList::Allowlist => "allowlist.json",
+        }
+    }
+
+    pub fn bedrock(&self) -> bool {
+        *self == PlayerList::Allowlist
+    }
+
+    /// What a running server is told so its own copy stays in step with the file
+    pub fn add_command(&self, name: &str, reason: Option<&str>) -> String {
+        match (self, reason) {
+            (PlayerList::Whitelist, _) => format!("whitelist add {}", name),
+            (PlayerList::Ops, _) => format!("op {}", name),
+            (PlayerList::Bans, Some(reason)) if !reason.trim().is_empty() => format!("ban {} {}", name, reason.trim()),
+            (PlayerList::Bans, _) => format!("ban {}", name),
+            (PlayerList::Allowlist, _) => format!("allowlist add {}", name),
+        }
+    }
+
+    pub fn remove_command(&self, name: &str) -> String {
+        match self {
+            PlayerList::Whitelist => format!("whitelist remove {}", name),
+            PlayerList::Ops => format!("deop {}", name),
+            PlayerList::Bans => format!("pardon {}", name),
+            PlayerList::Allowlist => format!("allowlist remove {}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerEntry {
+    pub name: String,
+    /// Java UUID, None on Bedrock
+    pub uuid: Option<String>,
+    /// Op permission level
+    pub level: Option<u8>,
+    /// Ban reason
+    pub reason: Option<String>,
+    /// Ban expiry, "forever" for permanent bans
+    pub expires: Option<String>,
+}
+
+impl PlayerEntry {
+    fn from_value(value: &Value) -> Option<Self> {
+        let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            name: text("name")?,
+            uuid: text("uuid"),
+            level: value.get("level").and_then(Value::as_u64).map(|l| l as u8),
+            reason: text("reason"),
+            expires: text("expires"),
+        })
+    }
+}
+
+/// A Mojang account
+#[derive(Debug, Clone, Deserialize)]
+pub struct Profile {
+    /// UUID without dashes, as Mojang returns it
+    pub id: String,
+    pub name: String,
+}
+
+impl Profile {
+    /// The dashed form the server files use
+    pub fn uuid(&self) -> String {
+        let id = &self.id;
+        if id.len() != 32 {
+            return id.clone();
+        }
+        format!("{}-{}-{}-{}-{}", &id[0..8], &id[8..12], &id[12..16], &id[16..20], &id[20..])
+    }
+}
+
+/// Look up a Java account's UUID by its username
+pub async fn lookup_profile(name: &str) -> Result<Profile, String> {
+    let response = crate::content::http_client()
+        .get(format!("{}/{}", MOJANG_PROFILE_API, name))
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Mojang unreachable: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NO_CONTENT {
+        return Err(format!("There's no Minecraft account called '{}'", name));
+    }
+    if !status.is_success() {
+        return Err(format!("Mojang returned {}", status));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected Mojang response: {}", e))
+}
+
+fn read_values(data_path: &Path, list: PlayerList) -> Result<Vec<Value>, String> {
+    let path = data_path.join(list.file());
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", list.file(), e)),
+    };
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&content).map_err(|e| format!("{} isn't valid JSON: {}", list.file(), e))
+}
+
+fn write_values(data_path: &Path, list: PlayerList, values: &[Value]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(values).map_err(|e| e.to_string())?;
+    serverwave_core::atomic::write_atomic(&data_path.join(list.file()), content)
+        .map_err(|e| format!("Failed to write {}: {}", list.file(), e))
+}
+
+pub fn read(data_path: &Path, list: PlayerList) -> Result<Vec<PlayerEntry>, String> {
+    Ok(read_values(data_path, list)?.iter().filter_map(PlayerEntry::from_value).collect())
+}
+
+fn same_player(value: &Value, name: &str, uuid: Option<&str>) -> bool {
+    let matches = |key: &str, expected: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .is_some_and(|v| v.eq_ignore_ascii_case(expected))
+    };
+    matches("name", name) || uuid.is_some_and(|uuid| matches("uuid", uuid))
+}
+
+/// Add a player to a stopped server's list, replacing an existing entry for them. Java lists
+/// need the account's profile; Bedrock fills in the XUID itself on the player's next join.
+pub fn add(
+    data_path: &Path,
+    list: PlayerList,
+    name: &str,
+    profile: Option<&Profile>,
+    reason: Option<&str>,
+) -> Result<(), String> {
+    let mut entry = Map::new();
+    match (list, profile) {
+        (PlayerList::Allowlist, _) => {
+            entry.insert("ignoresPlayerLimit".into(), json!(false));
+            entry.insert("name".into(), json!(name));
+        }
+        (_, None) => return Err(format!("{} needs the player's UUID", list.file())),
+        (_, Some(profile)) => {
+            entry.insert("uuid".into(), json!(profile.uuid()));
+            entry.insert("name".into(), json!(profile.name));
+        }
+    }
+    match list {
+        PlayerList::Ops => {
+            entry.insert("level".into(), json!(DEFAULT_OP_LEVEL));
+            entry.insert("bypassesPlayerLimit".into(), json!(false));
+        }
+        PlayerList::Bans => {
+            entry.insert(
+                "created".into(),
+                json!(chrono::Local::now().format("%Y-%m-%d %H:%M:%S %z").to_string()),
+            );
+            entry.insert("source".into(), json!("Server"));
+            entry.insert("expires".into(), json!("forever"));
+            let reason = reason.map(str::trim).filter(|r| !r.is_empty()).unwrap_or("Banned by an operator.");
+            entry.insert("reason".into(), json!(reason));
+        }
+        _ => {}
+    }
+
+    let uuid = profile.map(Profile::uuid);
+    let mut values = read_values(data_path, list)?;
+    values.retain(|v| !same_player(v, name, uuid.as_deref()));
+    values.push(Value::Object(entry));
+    write_values(data_path, list, &values)
+}
+
+/// Remove a player from a stopped server's list. Returns whether they were on it.
+pub fn remove(data_path: &Path, list: PlayerList, name: &str) -> Result<bool, String> {
+    let mut values = read_values(data_path, list)?;
+    let before = values.len();
+    values.retain(|v| !same_player(v, name, None));
+    if values.len() == before {
+        return Ok(false);
+    }
+    write_values(data_path, list, &values)?;
+    Ok(true)
+}
+
+/// Java usernames: 3-16 letters, digits and underscores. Bedrock gamertags may also have spaces.
+pub fn validate_name(name: &str, bedrock: bool) -> Result<(), String> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || c == '_' || (bedrock && c == ' ');
+    let valid = (3..=16).contains(&name.len()) && name.chars().all(allowed);
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("'{}' isn't a valid player name", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_and_remove_keep_one_entry_per_player() {
+        let dir = std::env::temp_dir().join(format!("serverwave-players-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let profile = Profile {
+            id: "069a79f444e94726a5befca90e38aaf5".to_string(),
+            name: "Notch".to_string(),
+        };
+        assert_eq!(profile.uuid(), "069a79f4-44e9-4726-a5be-fca90e38aaf5");
+
+        add(&dir, PlayerList::Ops, "notch", Some(&profile), None).unwrap();
+        add(&dir, PlayerList::Ops, "Notch", Some(&profile), None).unwrap();
+        let ops = read(&dir, PlayerList::Ops).unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].level, Some(DEFAULT_OP_LEVEL));
+
+        assert!(add(&dir, PlayerList::Bans, "Notch", None, None).is_err());
+        add(&dir, PlayerList::Allowlist, "Some Gamer", None, None).unwrap();
+        assert!(remove(&dir, PlayerList::Allowlist, "some gamer").unwrap());
+        assert!(!remove(&dir, PlayerList::Allowlist, "some gamer").unwrap());
+
+        assert!(validate_name("Some Gamer", true).is_ok());
+        assert!(validate_name("Some Gamer", false).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
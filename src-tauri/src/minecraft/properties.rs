@@ -0,0 +1,81 @@
+// server.properties - read and change single keys, leaving comments and the order alone
+
+use std::path::Path;
+
+pub const FILE: &str = "server.properties";
+
+pub fn get(data_path: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(data_path.join(FILE)).ok()?;
+    content.lines().find_map(|line| {
+        let (k, v) = split(line)?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+/// Set `key`, appending it when the file doesn't have it yet
+pub fn set(data_path: &Path, key: &str, value: &str) -> Result<(), String> {
+    let path = data_path.join(FILE);
+    let content = std::fs::read_to_string(&path).unwrap_or_default();
+    let mut found = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|line| match split(line) {
+            Some((k, _)) if k == key => {
+                found = true;
+                format!("{}={}", key, value)
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if !found {
+        lines.push(format!("{}={}", key, value));
+    }
+    serverwave_core::atomic::write_atomic(&path, &(lines.join("\n") + "\n"))
+        .map_err(|e| format!("Failed to write {}: {}", FILE, e))
+}
+
+/// Switch RCON on with the server's port and password. Runs while the server is stopped - it
+/// reads the file once at start.
+pub fn enable_rcon(data_path: &Path, port: u16, password: &str) -> Result<(), String> {
+    if !data_path.join(FILE).exists() {
+        // Not installed yet - the install writes the file
+        return Ok(());
+    }
+    let values = [("enable-rcon", "true".to_string()), ("rcon.port", port.to_string()), ("rcon.password", password.to_string())];
+    for (key, value) in values {
+        if get(data_path, key).as_deref() != Some(value.as_str()) {
+            set(data_path, key, &value)?;
+        }
+    }
+    Ok(())
+}
+
+fn split(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('#') || trimmed.starts_with('!') {
+        return None;
+    }
+    let (key, value) = trimmed.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_keeps_other_lines() {
+        let dir = std::env::temp_dir().join(format!("serverwave-props-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(FILE), "#Minecraft server properties\nlevel-name=world\nmotd=hi\n").unwrap();
+
+        set(&dir, "level-name", "creative").unwrap();
+        set(&dir, "max-players", "10").unwrap();
+        assert_eq!(get(&dir, "level-name").as_deref(), Some("creative"));
+        assert_eq!(get(&dir, "max-players").as_deref(), Some("10"));
+        let content = std::fs::read_to_string(dir.join(FILE)).unwrap();
+        assert!(content.starts_with("#Minecraft server properties\nlevel-name=creative\nmotd=hi\n"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,280 @@
+// Worlds - the level folders in a server's data folder, which one server.properties loads,
+// and bringing worlds in or archiving them
+
+use super::properties;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const LEVEL_NAME: &str = "level-name";
+const DEFAULT_LEVEL: &str = "world";
+/// Where archived worlds go, relative to the data folder
+pub const ARCHIVE_DIR: &str = "world-archives";
+/// Bukkit-based servers keep the other dimensions next to the world instead of inside it
+const DIMENSION_SUFFIXES: [&str; 2] = ["_nether", "_the_end"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct World {
+    pub name: String,
+    /// Whether server.properties' level-name points at it
+    pub active: bool,
+    /// The world and its dimension folders together
+    pub size_bytes: u64,
+    /// Sibling folders like world_nether that belong to it
+    pub dimensions: Vec<String>,
+    /// When level.dat was last written, in seconds since the epoch
+    pub last_played: Option<u64>,
+}
+
+pub fn active_world(data_path: &Path) -> String {
+    properties::get(data_path, LEVEL_NAME)
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| DEFAULT_LEVEL.to_string())
+}
+
+fn is_world(dir: &Path) -> bool {
+    dir.join("level.dat").is_file()
+}
+
+pub fn list(data_path: &Path) -> Vec<World> {
+    let active = active_world(data_path);
+    let Ok(entries) = std::fs::read_dir(data_path) else { return Vec::new() };
+    let names: Vec<String> = entries
+        .flatten()
+        .filter(|e| is_world(&e.path()))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    let mut worlds: Vec<World> = names
+        .iter()
+        .filter(|name| !is_dimension_of_listed(name, &names))
+        .map(|name| {
+            let dimensions = dimensions(data_path, name);
+            let size_bytes = std::iter::once(name)
+                .chain(dimensions.iter())
+                .map(|dir| dir_size(&data_path.join(dir)))
+                .sum();
+            let last_played = std::fs::metadata(data_path.join(name).join("level.dat"))
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+            World {
+                name: name.clone(),
+                active: *name == active,
+                size_bytes,
+                dimensions,
+                last_played,
+            }
+        })
+        .collect();
+    worlds.sort_by(|a, b| b.active.cmp(&a.active).then_with(|| a.name.cmp(&b.name)));
+    worlds
+}
+
+fn is_dimension_of_listed(name: &str, names: &[String]) -> bool {
+    DIMENSION_SUFFIXES
+        .iter()
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .any(|base| names.iter().any(|n| n == base))
+}
+
+fn dimensions(data_path: &Path, name: &str) -> Vec<String> {
+    DIMENSION_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{}{}", name, suffix))
+        .filter(|dir| data_path.join(dir).is_dir())
+        .collect()
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else { return 0 };
+    entries
+        .flatten()
+        .map(|e| match e.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&e.path()),
+            _ => e.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
+/// A world name is a single folder in the data folder
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':'])
+        && name.chars().all(|c| !c.is_control());
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("'{}' isn't a valid world name", name))
+    }
+}
+
+fn existing_world(data_path: &Path, name: &str) -> Result<PathBuf, String> {
+    validate_name(name)?;
+    let dir = data_path.join(name);
+    if !is_world(&dir) {
+        return Err(format!("There's no world called '{}'", name));
+    }
+    Ok(dir)
+}
+
+/// Point level-name at another world - the server loads it on its next start
+pub fn set_active(data_path: &Path, name: &str) -> Result<(), String> {
+    existing_world(data_path, name)?;
+    properties::set(data_path, LEVEL_NAME, name)
+}
+
+/// Copy a world folder or unpack a world zip into the data folder as `name`. The level.dat
+/// may sit at the top or inside a single folder, the way worlds usually get zipped.
+pub fn import(data_path: &Path, source: &Path, name: &str) -> Result<World, String> {
+    validate_name(name)?;
+    let dest = data_path.join(name);
+    if dest.exists() {
+        return Err(format!("'{}' already exists", name));
+    }
+
+    if source.is_dir() {
+        let root = find_level_dir(source).ok_or("That folder doesn't contain a Minecraft world (no level.dat)")?;
+        serverwave_core::paths::copy_dir_all(&root, &dest).map_err(|e| e.to_string())?;
+    } else if source.is_file() {
+        let prefix = zip_level_prefix(source)?;
+        if let Err(e) = crate::content::extract_zip(source, &prefix, &dest) {
+            let _ = std::fs::remove_dir_all(&dest);
+            return Err(e);
+        }
+        if !is_world(&dest) {
+            let _ = std::fs::remove_dir_all(&dest);
+            return Err("The zip doesn't contain a Minecraft world".to_string());
+        }
+    } else {
+        return Err(format!("{} doesn't exist", source.display()));
+    }
+
+    list(data_path)
+        .into_iter()
+        .find(|w| w.name == name)
+        .ok_or_else(|| format!("'{}' was copied but isn't a world", name))
+}
+
+fn find_level_dir(source: &Path) -> Option<PathBuf> {
+    if is_world(source) {
+        return Some(source.to_path_buf());
+    }
+    std::fs::read_dir(source)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| is_world(p))
+}
+
+/// The folder inside the zip that holds level.dat, "" when it's at the top
+fn zip_level_prefix(archive: &Path) -> Result<String, String> {
+    let file = std::fs::File::open(archive).map_err(|e| e.to_string())?;
+    let zip = zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip: {}", e))?;
+    zip.file_names()
+        .filter(|name| *name == "level.dat" || name.ends_with("/level.dat"))
+        .map(|name| name.trim_end_matches("level.dat").to_string())
+        .min_by_key(|prefix| prefix.len())
+        .ok_or_else(|| "The zip doesn't contain a Minecraft world (no level.dat)".to_string())
+}
+
+/// Delete a world and its dimension folders, zipping them into world-archives/ first when
+/// `archive` is set. Returns the archive's path. The active world can't be removed.
+pub fn delete(data_path: &Path, name: &str, archive: bool) -> Result<Option<PathBuf>, String> {
+    existing_world(data_path, name)?;
+    if active_world(data_path) == name {
+        return Err(format!("'{}' is the active world - switch to another one first", name));
+    }
+    let mut folders = vec![name.to_string()];
+    folders.extend(dimensions(data_path, name));
+
+    let archived = if archive {
+        let dir = data_path.join(ARCHIVE_DIR);
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let path = dir.join(format!("{}-{}.zip", name, chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        if let Err(e) = zip_folders(data_path, &folders, &path) {
+            let _ = std::fs::remove_file(&path);
+            return Err(format!("Couldn't archive '{}': {}", name, e));
+        }
+        Some(path)
+    } else {
+        None
+    };
+
+    for folder in &folders {
+        std::fs::remove_dir_all(data_path.join(folder)).map_err(|e| e.to_string())?;
+    }
+    Ok(archived)
+}
+
+fn zip_folders(base: &Path, folders: &[String], path: &Path) -> Result<(), String> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path).map_err(|e| e.to_string())?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+    for folder in folders {
+        add_dir(&mut zip, base, &base.join(folder), options)?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn add_dir(
+    zip: &mut zip::ZipWriter<std::fs::File>,
+    base: &Path,
+    dir: &Path,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), String> {
+    for entry in std::fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let name = path
+            .strip_prefix(base)
+            .map_err(|e| e.to_string())?
+            .to_string_lossy()
+            .replace('\\', "/");
+        if path.is_dir() {
+            zip.add_directory(name, options).map_err(|e| e.to_string())?;
+            add_dir(zip, base, &path, options)?;
+        } else {
+            zip.start_file(name, options).map_err(|e| e.to_string())?;
+            let content = std::fs::read(&path).map_err(|e| e.to_string())?;
+            zip.write_all(&content).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_switch_and_archive_worlds() {
+        let dir = std::env::temp_dir().join(format!("serverwave-worlds-{}", uuid::Uuid::new_v4()));
+        for world in ["world", "world_nether", "creative"] {
+            std::fs::create_dir_all(dir.join(world)).unwrap();
+            std::fs::write(dir.join(world).join("level.dat"), b"level").unwrap();
+        }
+        std::fs::write(dir.join(properties::FILE), "level-name=world\n").unwrap();
+
+        let worlds = list(&dir);
+        assert_eq!(worlds.len(), 2);
+        assert!(worlds[0].active && worlds[0].name == "world");
+        assert_eq!(worlds[0].dimensions, vec!["world_nether".to_string()]);
+
+        assert!(delete(&dir, "world", false).is_err());
+        set_active(&dir, "creative").unwrap();
+        let archive = delete(&dir, "world", true).unwrap().unwrap();
+        assert!(archive.is_file());
+        assert!(!dir.join("world").exists() && !dir.join("world_nether").exists());
+
+        let imported = import(&dir, &archive, "restored").unwrap();
+        assert_eq!(imported.name, "restored");
+        assert!(dir.join("restored").join("level.dat").is_file());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+// Install egress - restricted installs run on an internal network of their own, whose only
+// way out is a tinyproxy sidecar letting the allowed domains through. Scripts find it through
+// the usual proxy variables; whatever ignores them can't connect at all.
+
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use serverwave_core::games::{GameConfig, InstallNetwork};
+use serverwave_core::paths;
+use serverwave_core::server::Server;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const PROXY_IMAGE: &str = "alpine:3.20";
+const PROXY_PORT: u16 = 8888;
+const PROXY_MEMORY_MB: u32 = 64;
+const CONFIG_MOUNT: &str = "/etc/serverwave-proxy";
+/// How long the proxy may take to install tinyproxy and start listening
+const PROXY_READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How an install container is run to stay inside its restriction
+pub struct Restriction {
+    pub network: String,
+    /// The proxy variables, empty when the install is offline
+    pub env: Vec<String>,
+}
+
+/// Where a server's install scripts may connect - its own setting, or its game's
+pub fn install_network(server: &Server, game: &GameConfig) -> InstallNetwork {
+    server
+        .install_network
+        .clone()
+        .or_else(|| game.install_network.clone())
+        .unwrap_or(InstallNetwork::Open)
+}
+
+fn network_name(server_id: &str) -> String {
+    format!("serverwave-install-net-{}", server_id)
+}
+
+fn proxy_name(server_id: &str) -> String {
+    format!("serverwave-install-proxy-{}", server_id)
+}
+
+fn config_dir(server_id: &str) -> PathBuf {
+    paths::data_root().join("install-proxy").join(server_id)
+}
+
+fn proxy_config() -> String {
+    format!(
+        "User nobody\n\
+         Group nobody\n\
+         Port {PROXY_PORT}\n\
+         Timeout 600\n\
+         LogLevel Notice\n\
+         ConnectPort 443\n\
+         ConnectPort 80\n\
+         Filter \"{CONFIG_MOUNT}/filter\"\n\
+         FilterURLs Off\n\
+         FilterType ere\n\
+         FilterDefaultDeny Yes\n"
+    )
+}
+
+/// tinyproxy's allow-list: a pattern per domain, matching it and its subdomains
+fn filter(domains: &[String]) -> String {
+    domains.iter().map(|domain| format!("(^|\\.){}$\n", domain.replace('.', "\\."))).collect()
+}
+
+/// The domains the proxy turned away, from its log
+fn refused_domains(lines: &[String]) -> Vec<String> {
+    let mut domains = Vec::new();
+    for line in lines.iter().filter(|line| line.contains("refused on filtered")) {
+        let Some(domain) = line.split('"').nth(1) else { continue };
+        if !domains.iter().any(|d| d == domain) {
+            domains.push(domain.to_string());
+        }
+    }
+    domains
+}
+
+/// Set up the network and proxy for a restricted install, None for an open one
+pub async fn prepare(docker: &DockerManager, server: &Server, network: &InstallNetwork) -> Result<Option<Restriction>, AppError> {
+    let InstallNetwork::Restricted { allowed_domains } = network else { return Ok(None) };
+    if allowed_domains.is_empty() {
+        return Ok(Some(Restriction { network: "none".to_string(), env: Vec::new() }));
+    }
+
+    let dir = config_dir(&server.id);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("tinyproxy.conf"), proxy_config())?;
+    std::fs::write(dir.join("filter"), filter(allowed_domains))?;
+
+    let network = network_name(&server.id);
+    let name = proxy_name(&server.id);
+    docker.ensure_internal_network(&network).await?;
+    let bind = format!("{}:{}:ro", dir.to_string_lossy().replace('\\', "/"), CONFIG_MOUNT);
+    let cmd = format!("apk add --no-cache -q tinyproxy && exec tinyproxy -d -c {}/tinyproxy.conf", CONFIG_MOUNT);
+    let id = docker
+        .create_service_container(&name, PROXY_IMAGE, Some(vec!["sh".into(), "-c".into(), cmd]), Vec::new(), bind, "bridge", PROXY_MEMORY_MB)
+        .await?;
+    docker.connect_to_network(&network, &id).await?;
+    docker.start_container(&id).await?;
+
+    let wait = format!("until nc -z 127.0.0.1 {}; do sleep 1; done", PROXY_PORT);
+    let ready = tokio::time::timeout(PROXY_READY_TIMEOUT, docker.exec_command(&id, vec!["sh", "-c", &wait], None, |_| {})).await;
+    if !matches!(ready, Ok(Ok(0))) {
+        finish(docker, &server.id).await;
+        return Err(AppError::Other("The install proxy didn't start".to_string()));
+    }
+
+    let proxy = format!("http://{}:{}", name, PROXY_PORT);
+    let mut env = Vec::new();
+    for key in ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"] {
+        env.push(format!("{}={}", key, proxy));
+    }
+    env.extend(["NO_PROXY=localhost,127.0.0.1".to_string(), "no_proxy=localhost,127.0.0.1".to_string()]);
+    Ok(Some(Restriction { network, env }))
+}
+
+/// Take a restricted install's proxy and network down once its container is gone, returning
+/// the domains the proxy turned away
+pub async fn finish(docker: &DockerManager, server_id: &str) -> Vec<String> {
+    let name = proxy_name(server_id);
+    let refused = docker.get_logs(&name, 1000).await.map(|lines| refused_domains(&lines)).unwrap_or_default();
+    docker.remove_container(&name).await.ok();
+    docker.remove_network(&network_name(server_id)).await.ok();
+    refused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_matches_subdomains() {
+        assert_eq!(filter(&["github.com".into(), "maven.org".into()]), "(^|\\.)github\\.com$\n(^|\\.)maven\\.org$\n");
+    }
+
+    #[test]
+    fn test_refused_domains() {
+        let lines = [
+            "NOTICE    Oct 16 10:00:01 [7]: Proxying refused on filtered domain \"evil.example\"",
+            "CONNECT   Oct 16 10:00:02 [7]: Connect (file descriptor 5): 172.20.0.3",
+            "NOTICE    Oct 16 10:00:03 [7]: Proxying refused on filtered domain \"evil.example\"",
+            "NOTICE    Oct 16 10:00:04 [7]: Proxying refused on filtered domain \"tracker.example\"",
+        ]
+        .map(String::from);
+        assert_eq!(refused_domains(&lines), vec!["evil.example", "tracker.example"]);
+    }
+}
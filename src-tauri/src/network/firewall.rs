@@ -0,0 +1,78 @@
+// Windows Firewall automation - inbound rules for server ports via an elevated netsh call
+
+use crate::network::PortMapping;
+
+/// All rules for a server share one name so they can be removed together
+#[cfg(target_os = "windows")]
+fn rule_name(server_id: &str) -> String {
+    format!("Serverwave Anywhere ({})", server_id)
+}
+
+/// Create inbound allow rules for the given ports (prompts for elevation once)
+#[cfg(target_os = "windows")]
+pub async fn add_rules(server_id: &str, mappings: &[PortMapping]) -> Result<(), String> {
+    use crate::network::MappingProtocol;
+
+    let name = rule_name(server_id);
+    let mut commands = vec![format!("netsh advfirewall firewall delete rule name=\"{}\"", name)];
+    for mapping in mappings {
+        let protocol = match mapping.protocol {
+            MappingProtocol::Tcp => "TCP",
+            MappingProtocol::Udp => "UDP",
+        };
+        commands.push(format!(
+            "netsh advfirewall firewall add rule name=\"{}\" dir=in action=allow protocol={} localport={}",
+            name, protocol, mapping.port
+        ));
+    }
+    run_elevated(&commands.join(" & ")).await
+}
+
+/// Remove all inbound rules created for a server
+#[cfg(target_os = "windows")]
+pub async fn remove_rules(server_id: &str) -> Result<(), String> {
+    run_elevated(&format!(
+        "netsh advfirewall firewall delete rule name=\"{}\"",
+        rule_name(server_id)
+    ))
+    .await
+}
+
+/// Run a cmd.exe command line through a UAC prompt and wait for it to finish
+#[cfg(target_os = "windows")]
+async fn run_elevated(command_line: &str) -> Result<(), String> {
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let script = format!(
+        "$p = Start-Process -FilePath cmd.exe -ArgumentList '/c {}' -Verb RunAs -WindowStyle Hidden -Wait -PassThru; exit $p.ExitCode",
+        command_line.replace('\'', "''")
+    );
+
+    let output = tokio::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to launch PowerShell: {}", e))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("canceled by the user") {
+            Err("Administrator permission was declined".to_string())
+        } else {
+            Err(format!("Firewall update failed: {}", stderr.trim()))
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub async fn add_rules(_server_id: &str, _mappings: &[PortMapping]) -> Result<(), String> {
+    Err("Firewall automation is only available on Windows".to_string())
+}
+
+#[cfg(not(target_os = "windows"))]
+pub async fn remove_rules(_server_id: &str) -> Result<(), String> {
+    Err("Firewall automation is only available on Windows".to_string())
+}
@@ -0,0 +1,13 @@
+// Network module - router port forwarding, connectivity helpers and install egress
+
+pub mod egress;
+pub mod firewall;
+pub mod public_ip;
+pub mod reachability;
+pub mod tunnel;
+pub mod upnp;
+
+pub use serverwave_core::ports::{PortAllocation, PortAllocator};
+pub use public_ip::get_public_ip;
+pub use tunnel::{TunnelConfig, TunnelStatus};
+pub use upnp::{MappingProtocol, PortMapping, PortMappingResult};
@@ -0,0 +1,56 @@
+// Public IP detection with a short-lived cache
+
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const CACHE_TTL: Duration = Duration::from_secs(600);
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+const LOOKUP_SERVICES: &[&str] = &[
+    "https://api.ipify.org",
+    "https://icanhazip.com",
+    "https://ifconfig.me/ip",
+];
+
+lazy_static::lazy_static! {
+    static ref CACHED_IP: Mutex<Option<(IpAddr, Instant)>> = Mutex::new(None);
+}
+
+/// Detect this machine's public IP, reusing a cached value for a few minutes
+pub async fn get_public_ip(force_refresh: bool) -> Result<IpAddr, String> {
+    if !force_refresh {
+        if let Some((ip, fetched_at)) = *CACHED_IP.lock().unwrap() {
+            if fetched_at.elapsed() < CACHE_TTL {
+                return Ok(ip);
+            }
+        }
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(LOOKUP_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut last_error = String::from("no lookup services configured");
+    for service in LOOKUP_SERVICES {
+        let response = match client.get(*service).send().await {
+            Ok(r) => r,
+            Err(e) => {
+                last_error = e.to_string();
+                continue;
+            }
+        };
+        match response.text().await {
+            Ok(body) => match body.trim().parse::<IpAddr>() {
+                Ok(ip) => {
+                    *CACHED_IP.lock().unwrap() = Some((ip, Instant::now()));
+                    return Ok(ip);
+                }
+                Err(_) => last_error = format!("{} returned an invalid address", service),
+            },
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+
+    Err(format!("Could not detect public IP: {}", last_error))
+}
@@ -0,0 +1,70 @@
+// Port reachability - local probes and checks from outside the LAN
+
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// External service that tries to open a TCP connection to host:port from the internet
+const PORT_CHECK_URL: &str = "https://portchecker.io/api/v1/query";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const EXTERNAL_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Deserialize)]
+struct PortCheckResponse {
+    #[serde(default)]
+    error: bool,
+    #[serde(default)]
+    check: Vec<PortCheckEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PortCheckEntry {
+    port: u16,
+    status: bool,
+}
+
+/// Whether something accepts TCP connections on localhost:port
+pub async fn probe_local_tcp(port: u16) -> bool {
+    let connect = tokio::net::TcpStream::connect(("127.0.0.1", port));
+    matches!(tokio::time::timeout(PROBE_TIMEOUT, connect).await, Ok(Ok(_)))
+}
+
+/// Ask the external check service whether public_ip:port accepts TCP connections
+pub async fn check_external_tcp(public_ip: IpAddr, port: u16) -> Result<bool, String> {
+    let client = reqwest::Client::builder()
+        .timeout(EXTERNAL_CHECK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let response: PortCheckResponse = client
+        .post(PORT_CHECK_URL)
+        .json(&serde_json::json!({
+            "host": public_ip.to_string(),
+            "ports": [port],
+        }))
+        .send()
+        .await
+        .map_err(|e| format!("Port check service unavailable: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected response from port check service: {}", e))?;
+
+    if response.error {
+        return Err("Port check service rejected the request".to_string());
+    }
+
+    response
+        .check
+        .iter()
+        .find(|c| c.port == port)
+        .map(|c| c.status)
+        .ok_or_else(|| "Port check service returned no result".to_string())
+}
+
+/// Whether an address is in a private/LAN range
+pub fn is_private_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        IpAddr::V6(v6) => v6.is_loopback() || (v6.segments()[0] & 0xfe00) == 0xfc00,
+    }
+}
@@ -0,0 +1,136 @@
+// Tunnel sidecars - expose TCP game ports through ngrok or Cloudflare Tunnel. A tunnel's
+// token is kept in the keychain, not in the server's config.
+
+use crate::docker::{DockerError, DockerManager};
+use crate::security::secrets;
+use serde::Serialize;
+use serverwave_core::server::{load_all_server_configs, ServerConfigs, ServerStatus};
+
+pub use serverwave_core::server::{TunnelConfig, TunnelProvider};
+
+const NGROK_IMAGE: &str = "ngrok/ngrok:latest";
+const CLOUDFLARED_IMAGE: &str = "cloudflare/cloudflared:latest";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TunnelStatus {
+    pub provider: TunnelProvider,
+    pub running: bool,
+    pub public_address: Option<String>,
+}
+
+pub fn tunnel_container_name(server_id: &str) -> String {
+    format!("serverwave-{}-tunnel", server_id)
+}
+
+fn token_key(server_id: &str) -> String {
+    format!("tunnel-{}", server_id)
+}
+
+pub async fn load_token(server_id: &str) -> Option<String> {
+    secrets::get_secret(&token_key(server_id)).await
+}
+
+pub async fn save_token(server_id: &str, token: &str) -> Result<(), String> {
+    secrets::set_secret(&token_key(server_id), token).await
+}
+
+pub async fn delete_token(server_id: &str) {
+    secrets::delete_secret(&token_key(server_id)).await;
+}
+
+/// Move the tokens older versions saved in server configs to the keychain, saving the
+/// configs again without them
+pub async fn migrate_tokens(configs: &ServerConfigs) {
+    for server in load_all_server_configs() {
+        let Some(tunnel) = server.tunnel.filter(|t| !t.token.is_empty()) else { continue };
+        if let Err(e) = save_token(&server.id, &tunnel.token).await {
+            tracing::warn!("Couldn't move the tunnel token of {} to the keychain: {}", server.name, e);
+            continue;
+        }
+        let moved = configs.update(&server.id, |s| {
+            if let Some(tunnel) = &mut s.tunnel {
+                tunnel.token.clear();
+                tunnel.has_token = true;
+            }
+        });
+        if let Err(e) = moved {
+            tracing::warn!("Couldn't save {} without its tunnel token: {}", server.name, e);
+        }
+    }
+}
+
+/// Start the tunnel sidecar next to a running game container
+pub async fn start_tunnel(
+    docker: &DockerManager,
+    server_id: &str,
+    game_container_id: &str,
+    port: u16,
+    config: &TunnelConfig,
+    token: &str,
+) -> Result<String, DockerError> {
+    let name = tunnel_container_name(server_id);
+
+    let (image, cmd, env) = match config.provider {
+        TunnelProvider::Ngrok => (
+            NGROK_IMAGE,
+            vec![
+                "tcp".to_string(),
+                format!("localhost:{}", port),
+                "--log".to_string(),
+                "stdout".to_string(),
+                "--log-format".to_string(),
+                "logfmt".to_string(),
+            ],
+            vec![format!("NGROK_AUTHTOKEN={}", token)],
+        ),
+        TunnelProvider::Cloudflared => (
+            CLOUDFLARED_IMAGE,
+            vec![
+                "tunnel".to_string(),
+                "--no-autoupdate".to_string(),
+                "run".to_string(),
+            ],
+            vec![format!("TUNNEL_TOKEN={}", token)],
+        ),
+    };
+
+    let container_id = docker
+        .create_sidecar_container(&name, image, cmd, env, game_container_id)
+        .await?;
+    docker.start_container(&container_id).await?;
+    Ok(container_id)
+}
+
+/// Stop and remove the tunnel sidecar (no-op if it doesn't exist)
+pub async fn stop_tunnel(docker: &DockerManager, server_id: &str) {
+    let name = tunnel_container_name(server_id);
+    docker.remove_container(&name).await.ok();
+}
+
+/// Report whether the sidecar is running and which public address it got
+pub async fn tunnel_status(docker: &DockerManager, server_id: &str, config: &TunnelConfig) -> TunnelStatus {
+    let name = tunnel_container_name(server_id);
+    let running = matches!(docker.get_container_status(&name).await, Ok(ServerStatus::Running));
+
+    let public_address = match config.provider {
+        TunnelProvider::Ngrok if running => docker
+            .get_logs(&name, 200)
+            .await
+            .ok()
+            .and_then(|logs| logs.iter().rev().find_map(|line| parse_ngrok_url(line))),
+        _ => config.hostname.clone(),
+    };
+
+    TunnelStatus {
+        provider: config.provider.clone(),
+        running,
+        public_address,
+    }
+}
+
+/// Extract the public address from an ngrok logfmt line (`... url=tcp://0.tcp.ngrok.io:12345`)
+fn parse_ngrok_url(line: &str) -> Option<String> {
+    line.split_whitespace()
+        .find_map(|field| field.strip_prefix("url=tcp://"))
+        .map(|addr| addr.trim_matches('"').to_string())
+}
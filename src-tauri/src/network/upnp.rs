@@ -0,0 +1,307 @@
+// Router port forwarding - UPnP IGD with a NAT-PMP fallback
+
+use igd_next::aio::tokio::{search_gateway, Tokio};
+use igd_next::aio::Gateway;
+use igd_next::{PortMappingProtocol, SearchOptions};
+use serde::Serialize;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(5);
+const NATPMP_PORT: u16 = 5351;
+const NATPMP_TIMEOUT: Duration = Duration::from_secs(2);
+/// NAT-PMP mappings must have a lifetime; a day covers a long play session
+const NATPMP_LIFETIME_SECS: u32 = 86400;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingProtocol {
+    Tcp,
+    Udp,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingBackend {
+    Upnp,
+    Natpmp,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortMapping {
+    pub port: u16,
+    pub protocol: MappingProtocol,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortMappingResult {
+    pub backend: Option<MappingBackend>,
+    pub external_ip: Option<String>,
+    pub local_ip: Option<String>,
+    pub mapped: Vec<PortMapping>,
+    pub errors: Vec<String>,
+}
+
+enum Router {
+    Upnp(Gateway<Tokio>),
+    NatPmp(Ipv4Addr),
+}
+
+impl Router {
+    fn backend(&self) -> MappingBackend {
+        match self {
+            Router::Upnp(_) => MappingBackend::Upnp,
+            Router::NatPmp(_) => MappingBackend::Natpmp,
+        }
+    }
+
+    fn address(&self) -> IpAddr {
+        match self {
+            Router::Upnp(gateway) => gateway.addr.ip(),
+            Router::NatPmp(ip) => IpAddr::V4(*ip),
+        }
+    }
+}
+
+/// Find a router that accepts mapping requests, preferring UPnP
+async fn discover_router() -> Result<Router, String> {
+    let options = SearchOptions {
+        timeout: Some(SEARCH_TIMEOUT),
+        ..Default::default()
+    };
+
+    let upnp_error = match search_gateway(options).await {
+        Ok(gateway) => return Ok(Router::Upnp(gateway)),
+        Err(e) => e.to_string(),
+    };
+    tracing::debug!("UPnP gateway search failed: {}", upnp_error);
+
+    let gateway = guess_gateway_ip().await.ok_or_else(|| {
+        format!("No UPnP gateway found ({}) and no LAN address for NAT-PMP", upnp_error)
+    })?;
+
+    match natpmp_request(gateway, 0, 0, 0, 0).await {
+        Ok(_) => Ok(Router::NatPmp(gateway)),
+        Err(e) => Err(format!(
+            "Router doesn't support UPnP ({}) or NAT-PMP ({})",
+            upnp_error, e
+        )),
+    }
+}
+
+/// Local address of the interface used to reach `target`
+pub async fn local_ip_towards(target: IpAddr) -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await.ok()?;
+    socket.connect(SocketAddr::new(target, 9)).await.ok()?;
+    socket.local_addr().ok().map(|a| a.ip())
+}
+
+/// This machine's LAN address (the interface used for internet traffic)
+pub async fn local_ip() -> Option<IpAddr> {
+    local_ip_towards(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1))).await
+}
+
+/// Most home routers sit at .1 of the LAN subnet
+async fn guess_gateway_ip() -> Option<Ipv4Addr> {
+    match local_ip().await? {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            Some(Ipv4Addr::new(a, b, c, 1))
+        }
+        IpAddr::V6(_) => None,
+    }
+}
+
+/// Send a NAT-PMP request. Opcode 0 = external address, 1 = map UDP, 2 = map TCP.
+/// Returns the raw response payload after the result code.
+async fn natpmp_request(
+    gateway: Ipv4Addr,
+    opcode: u8,
+    internal_port: u16,
+    external_port: u16,
+    lifetime: u32,
+) -> Result<Vec<u8>, String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await.map_err(|e| e.to_string())?;
+    socket
+        .connect(SocketAddrV4::new(gateway, NATPMP_PORT))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut request = vec![0u8, opcode];
+    if opcode != 0 {
+        request.extend_from_slice(&[0, 0]);
+        request.extend_from_slice(&internal_port.to_be_bytes());
+        request.extend_from_slice(&external_port.to_be_bytes());
+        request.extend_from_slice(&lifetime.to_be_bytes());
+    }
+    socket.send(&request).await.map_err(|e| e.to_string())?;
+
+    let mut buf = [0u8; 16];
+    let len = tokio::time::timeout(NATPMP_TIMEOUT, socket.recv(&mut buf))
+        .await
+        .map_err(|_| "no response from router".to_string())?
+        .map_err(|e| e.to_string())?;
+
+    if len < 8 || buf[1] != opcode + 128 {
+        return Err("malformed NAT-PMP response".to_string());
+    }
+    let result_code = u16::from_be_bytes([buf[2], buf[3]]);
+    if result_code != 0 {
+        return Err(format!("router refused the request (result code {})", result_code));
+    }
+    // Skip version, opcode, result code and epoch
+    Ok(buf[8..len].to_vec())
+}
+
+async fn external_ip(router: &Router) -> Option<IpAddr> {
+    match router {
+        Router::Upnp(gateway) => gateway.get_external_ip().await.ok(),
+        Router::NatPmp(gateway) => {
+            let payload = natpmp_request(*gateway, 0, 0, 0, 0).await.ok()?;
+            let octets: [u8; 4] = payload.get(..4)?.try_into().ok()?;
+            Some(IpAddr::V4(Ipv4Addr::from(octets)))
+        }
+    }
+}
+
+async fn add_mapping(
+    router: &Router,
+    local_ip: IpAddr,
+    mapping: &PortMapping,
+    description: &str,
+) -> Result<(), String> {
+    match router {
+        Router::Upnp(gateway) => {
+            let protocol = match mapping.protocol {
+                MappingProtocol::Tcp => PortMappingProtocol::TCP,
+                MappingProtocol::Udp => PortMappingProtocol::UDP,
+            };
+            gateway
+                .add_port(
+                    protocol,
+                    mapping.port,
+                    SocketAddr::new(local_ip, mapping.port),
+                    0,
+                    description,
+                )
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Router::NatPmp(gateway) => {
+            let opcode = match mapping.protocol {
+                MappingProtocol::Udp => 1,
+                MappingProtocol::Tcp => 2,
+            };
+            let payload =
+                natpmp_request(*gateway, opcode, mapping.port, mapping.port, NATPMP_LIFETIME_SECS)
+                    .await?;
+            let mapped_port = payload
+                .get(2..4)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .unwrap_or(0);
+            if mapped_port != mapping.port {
+                return Err(format!("router assigned port {} instead", mapped_port));
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn remove_mapping(router: &Router, mapping: &PortMapping) -> Result<(), String> {
+    match router {
+        Router::Upnp(gateway) => {
+            let protocol = match mapping.protocol {
+                MappingProtocol::Tcp => PortMappingProtocol::TCP,
+                MappingProtocol::Udp => PortMappingProtocol::UDP,
+            };
+            gateway
+                .remove_port(protocol, mapping.port)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Router::NatPmp(gateway) => {
+            let opcode = match mapping.protocol {
+                MappingProtocol::Udp => 1,
+                MappingProtocol::Tcp => 2,
+            };
+            // A lifetime of 0 deletes the mapping
+            natpmp_request(*gateway, opcode, mapping.port, 0, 0).await.map(|_| ())
+        }
+    }
+}
+
+/// Ask the router to forward the given ports to this machine
+pub async fn map_ports(mappings: &[PortMapping], description: &str) -> PortMappingResult {
+    let mut result = PortMappingResult {
+        backend: None,
+        external_ip: None,
+        local_ip: None,
+        mapped: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let router = match discover_router().await {
+        Ok(r) => r,
+        Err(e) => {
+            result.errors.push(e);
+            return result;
+        }
+    };
+    result.backend = Some(router.backend());
+
+    let local_ip = match local_ip_towards(router.address()).await {
+        Some(ip) => ip,
+        None => {
+            result.errors.push("Could not determine this machine's LAN address".to_string());
+            return result;
+        }
+    };
+    result.local_ip = Some(local_ip.to_string());
+    result.external_ip = external_ip(&router).await.map(|ip| ip.to_string());
+
+    for mapping in mappings {
+        match add_mapping(&router, local_ip, mapping, description).await {
+            Ok(()) => {
+                tracing::info!("Forwarded {:?} port {} via {:?}", mapping.protocol, mapping.port, router.backend());
+                result.mapped.push(mapping.clone());
+            }
+            Err(e) => result.errors.push(format!(
+                "{} {:?}: {}",
+                mapping.port, mapping.protocol, e
+            )),
+        }
+    }
+
+    result
+}
+
+/// Remove previously requested port mappings (best effort)
+pub async fn unmap_ports(mappings: &[PortMapping]) {
+    let router = match discover_router().await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!("Could not remove port mappings: {}", e);
+            return;
+        }
+    };
+
+    for mapping in mappings {
+        if let Err(e) = remove_mapping(&router, mapping).await {
+            tracing::warn!("Failed to remove mapping for port {}: {}", mapping.port, e);
+        }
+    }
+}
+
+/// Map a single port, then remove it again - checks whether the router cooperates
+pub async fn test_mapping(port: u16) -> PortMappingResult {
+    let mapping = PortMapping {
+        port,
+        protocol: MappingProtocol::Tcp,
+    };
+    let result = map_ports(std::slice::from_ref(&mapping), "Serverwave Anywhere test").await;
+    if !result.mapped.is_empty() {
+        unmap_ports(&result.mapped).await;
+    }
+    result
+}
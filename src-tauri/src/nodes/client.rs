@@ -0,0 +1,92 @@
+// Agent client - calls a remote agent's API on behalf of the frontend
+
+use super::{pinning, Node};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+
+fn base_url(node: &Node) -> &str {
+    node.url.trim_end_matches('/')
+}
+
+/// A client that trusts the agent's pinned certificate, or the usual authorities without one
+fn http_client(node: &Node) -> reqwest::Client {
+    match &node.fingerprint {
+        Some(fingerprint) => reqwest::Client::builder()
+            .use_preconfigured_tls(pinning::pinned(fingerprint))
+            .build()
+            .unwrap_or_default(),
+        None => reqwest::Client::new(),
+    }
+}
+
+/// What pairing with an agent gives: a token, and over HTTPS the certificate to pin
+pub struct Pairing {
+    pub token: String,
+    pub fingerprint: Option<String>,
+}
+
+/// Trade a pairing code shown on the agent for a token of our own
+pub async fn pair(url: &str, code: &str, name: &str) -> Result<Pairing, String> {
+    let (tls, seen) = pinning::learning();
+    let client = reqwest::Client::builder()
+        .use_preconfigured_tls(tls)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(format!("{}/api/v1/pair", url.trim_end_matches('/')))
+        .timeout(REQUEST_TIMEOUT)
+        .json(&json!({ "code": code, "name": name }))
+        .send()
+        .await
+        .map_err(|e| format!("Agent unreachable: {}", e))?;
+
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        return Err(body["error"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Agent returned {}", status)));
+    }
+    let token = body["token"].as_str().ok_or("The agent sent no token")?.to_string();
+    let fingerprint = seen.lock().unwrap().take();
+    Ok(Pairing { token, fingerprint })
+}
+
+/// Agent version if it answers its health check
+pub async fn health(node: &Node) -> Result<String, String> {
+    let response = http_client(node)
+        .get(format!("{}/api/v1/health", base_url(node)))
+        .timeout(HEALTH_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Agent unreachable: {}", e))?;
+    let body: Value = response.json().await.map_err(|e| e.to_string())?;
+    Ok(body["version"].as_str().unwrap_or("unknown").to_string())
+}
+
+/// Run a command on the agent, with the same name and arguments as a local invoke
+pub async fn invoke(node: &Node, command: &str, args: Value) -> Result<Value, String> {
+    let response = http_client(node)
+        .post(format!("{}/api/v1/invoke/{}", base_url(node), command))
+        .bearer_auth(&node.token)
+        .timeout(REQUEST_TIMEOUT)
+        .json(&args)
+        .send()
+        .await
+        .map_err(|e| format!("Agent unreachable: {}", e))?;
+
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if status.is_success() {
+        Ok(body)
+    } else {
+        Err(body["error"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Agent returned {}", status)))
+    }
+}
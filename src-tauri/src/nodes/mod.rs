@@ -0,0 +1,8 @@
+// Nodes module - remote agents managed from this app
+
+pub mod client;
+pub mod pinning;
+pub mod registry;
+pub mod relay;
+
+pub use registry::Node;
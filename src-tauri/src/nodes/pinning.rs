@@ -0,0 +1,80 @@
+// Certificate pinning - agents serve a self-signed certificate, so instead of a certificate
+// authority the app trusts the exact certificate it saw when pairing, by its fingerprint
+
+use crate::api::tls::fingerprint;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, Error, SignatureScheme};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct PinnedCert {
+    provider: Arc<CryptoProvider>,
+    /// None accepts any certificate - only while pairing, to learn it
+    expected: Option<String>,
+    seen: Arc<Mutex<Option<String>>>,
+}
+
+impl ServerCertVerifier for PinnedCert {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let actual = fingerprint(end_entity);
+        *self.seen.lock().unwrap() = Some(actual.clone());
+        match &self.expected {
+            Some(expected) if !expected.eq_ignore_ascii_case(&actual) => Err(Error::General(
+                "The agent's certificate changed since pairing - pair it again if that was expected".into(),
+            )),
+            _ => Ok(ServerCertVerified::assertion()),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn build(expected: Option<String>, seen: Arc<Mutex<Option<String>>>) -> ClientConfig {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .expect("ring supports the default protocol versions")
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(PinnedCert { provider, expected, seen }))
+        .with_no_client_auth()
+}
+
+/// TLS settings that only accept the certificate with this fingerprint
+pub fn pinned(fingerprint: &str) -> ClientConfig {
+    build(Some(fingerprint.to_string()), Arc::default())
+}
+
+/// TLS settings that accept any certificate and note its fingerprint, for the pairing request
+pub fn learning() -> (ClientConfig, Arc<Mutex<Option<String>>>) {
+    let seen = Arc::new(Mutex::new(None));
+    (build(None, seen.clone()), seen)
+}
@@ -0,0 +1,78 @@
+// Node registry - remote agents saved in nodes.json, their tokens in the keychain
+
+use crate::security::secrets;
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    pub name: String,
+    /// Agent API address, e.g. https://192.168.1.10:7450
+    pub url: String,
+    /// The agent's self-signed certificate, as seen when pairing - None trusts the usual
+    /// certificate authorities (or the URL is plain HTTP)
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Kept in the keychain - only present in nodes.json written by older versions
+    #[serde(default, skip_serializing)]
+    pub token: String,
+    pub added_at: chrono::DateTime<chrono::Utc>,
+}
+
+pub async fn load_nodes() -> Vec<Node> {
+    let mut nodes: Vec<Node> = atomic::read_json(&get_nodes_path()).unwrap_or_default();
+
+    let mut migrated = false;
+    for node in &mut nodes {
+        if node.token.is_empty() {
+            node.token = secrets::get_secret(&token_key(&node.id)).await.unwrap_or_default();
+        } else if secrets::set_secret(&token_key(&node.id), &node.token).await.is_ok() {
+            migrated = true;
+        }
+    }
+    if migrated {
+        // Rewrite without the plain-text tokens, and don't keep them in a backup either
+        let path = get_nodes_path();
+        if let Ok(content) = serde_json::to_string_pretty(&nodes) {
+            atomic::write_atomic(&path, content).ok();
+            std::fs::remove_file(atomic::backup_path(&path)).ok();
+        }
+    }
+    nodes
+}
+
+/// Save the registry, storing each node's token in the keychain
+pub async fn save_nodes(nodes: &[Node]) -> Result<(), String> {
+    for node in nodes {
+        secrets::set_secret(&token_key(&node.id), &node.token).await?;
+    }
+    write_nodes_file(nodes)
+}
+
+/// Forget a node's token once it's removed from the registry
+pub async fn delete_node_token(node_id: &str) {
+    secrets::delete_secret(&token_key(node_id)).await;
+}
+
+fn write_nodes_file(nodes: &[Node]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(nodes).map_err(|e| e.to_string())?;
+    atomic::write_with_backup(&get_nodes_path(), content).map_err(|e| e.to_string())
+}
+
+pub async fn find_node(node_id: &str) -> Result<Node, String> {
+    load_nodes()
+        .await
+        .into_iter()
+        .find(|n| n.id == node_id)
+        .ok_or_else(|| format!("Node '{}' not found", node_id))
+}
+
+fn token_key(node_id: &str) -> String {
+    format!("node-{}", node_id)
+}
+
+fn get_nodes_path() -> PathBuf {
+    serverwave_core::paths::app_root().join("nodes.json")
+}
@@ -0,0 +1,65 @@
+// Event relay - re-emits an agent's WebSocket events locally, tagged with the node id
+
+use super::{pinning, Node};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::Connector;
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Keep a connection to the agent open until the handle is aborted
+pub fn spawn_relay(app: AppHandle, node: Node) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = relay_once(&app, &node).await {
+                tracing::debug!("Event relay for node {} disconnected: {}", node.name, e);
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    })
+}
+
+async fn relay_once(app: &AppHandle, node: &Node) -> Result<(), String> {
+    let url = format!(
+        "{}/api/v1/ws?token={}",
+        node.url.trim_end_matches('/').replacen("http", "ws", 1),
+        node.token
+    );
+    let connector = node
+        .fingerprint
+        .as_deref()
+        .map(|fingerprint| Connector::Rustls(Arc::new(pinning::pinned(fingerprint))));
+    let (socket, _) = tokio_tungstenite::connect_async_tls_with_config(url.as_str(), None, false, connector)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (mut sink, mut stream) = socket.split();
+
+    let subscribe = json!({ "type": "subscribe", "serverIds": null }).to_string();
+    sink.send(Message::Text(subscribe)).await.map_err(|e| e.to_string())?;
+    tracing::info!("Relaying events from node {}", node.name);
+
+    while let Some(message) = stream.next().await {
+        let text = match message.map_err(|e| e.to_string())? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let data: Value = match serde_json::from_str(&text) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+        if data["type"] != "event" {
+            continue;
+        }
+        if let (Some(event), Value::Object(mut payload)) = (data["event"].as_str(), data["payload"].clone()) {
+            payload.insert("node_id".to_string(), Value::String(node.id.clone()));
+            let _ = app.emit(event, Value::Object(payload));
+        }
+    }
+    Ok(())
+}
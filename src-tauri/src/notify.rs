@@ -0,0 +1,48 @@
+// Notifications - things worth telling the user about even when they aren't looking at the
+// app. The frontend shows them as desktop notifications; a webhook gets each one as JSON.
+
+use crate::commands::settings::load_app_settings;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Sent on its own channel when desktop notifications are on
+pub const NOTIFICATION_EVENT: &str = "notification";
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// Show a notification and post it to the webhook, as the notification settings say.
+/// Callers check their own `on_*` setting first.
+pub fn send(app: &AppHandle, title: impl Into<String>, body: impl Into<String>) {
+    let settings = load_app_settings().notifications;
+    let notification = Notification {
+        title: title.into(),
+        body: body.into(),
+        sent_at: Utc::now(),
+    };
+
+    if settings.enabled {
+        let _ = app.emit(NOTIFICATION_EVENT, &notification);
+    }
+    if let Some(url) = settings.webhook_url.filter(|u| !u.is_empty()) {
+        tauri::async_runtime::spawn(async move {
+            let result = reqwest::Client::new()
+                .post(&url)
+                .timeout(WEBHOOK_TIMEOUT)
+                .json(&notification)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                tracing::warn!("Notification webhook failed: {}", e);
+            }
+        });
+    }
+}
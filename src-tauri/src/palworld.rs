@@ -0,0 +1,292 @@
+// Palworld - the dedicated server's admin REST API: server info and metrics, the player list,
+// announcements, kicks, bans and saves. The API is switched on in PalWorldSettings.ini before
+// each start and published on this machine only; it logs in with the server's admin password.
+
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use crate::games::GameConfig;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use serverwave_core::atomic;
+use serverwave_core::rcon;
+use serverwave_core::server::{lifecycle, load_server_config, Server};
+use std::time::Duration;
+
+pub const PALWORLD_GAME: &str = "palworld";
+const SETTINGS_FILE: &str = "Pal/Saved/Config/LinuxServer/PalWorldSettings.ini";
+const SETTINGS_SECTION: &str = "[/Script/Pal.PalGameWorldSettings]";
+const PORT_VAR: &str = "REST_API_PORT";
+const PASSWORD_VAR: &str = "ADMIN_PASSWORD";
+/// The REST API's fixed user name
+const USER: &str = "admin";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub version: String,
+    pub servername: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub worldguid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Metrics {
+    pub serverfps: u32,
+    pub currentplayernum: u32,
+    /// Milliseconds per frame
+    pub serverframetime: f64,
+    pub maxplayernum: u32,
+    /// Seconds since the server started
+    pub uptime: u64,
+    /// In-game days
+    #[serde(default)]
+    pub days: Option<u32>,
+    #[serde(default)]
+    pub basecampnum: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Player {
+    pub name: String,
+    #[serde(alias = "accountName", default)]
+    pub account_name: String,
+    #[serde(alias = "playerId", default)]
+    pub player_id: String,
+    /// What kicks and bans take, e.g. steam_76561198000000000
+    #[serde(alias = "userId")]
+    pub user_id: String,
+    #[serde(default)]
+    pub ip: String,
+    #[serde(default)]
+    pub ping: f64,
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub location_x: f64,
+    #[serde(default)]
+    pub location_y: f64,
+    #[serde(default)]
+    pub building_count: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct PlayerList {
+    players: Vec<Player>,
+}
+
+pub fn palworld_server(server_id: &str) -> Result<Server, AppError> {
+    let server = load_server_config(server_id)?;
+    if server.game_type.0 != PALWORLD_GAME {
+        return Err(AppError::InvalidInput(format!("'{}' isn't a Palworld server", server.name)));
+    }
+    Ok(server)
+}
+
+/// The REST API's port and password, as the server's container gets them
+fn api_settings(server: &Server, game: &GameConfig) -> (Option<u16>, String) {
+    let extra_ports = lifecycle::extra_ports(server, Some(game));
+    let env = lifecycle::container_env(game, server.memory_mb, server.port, &server.config, &server.env, &extra_ports);
+    let port = env.get(PORT_VAR).and_then(|p| p.parse().ok());
+    (port, env.get(PASSWORD_VAR).cloned().unwrap_or_default())
+}
+
+/// Split `(A=1,B="x, y",C=(D,E))` into its top-level fields
+fn split_fields(tuple: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0, false, 0);
+    for (i, c) in tuple.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                fields.push(&tuple[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < tuple.len() {
+        fields.push(&tuple[start..]);
+    }
+    fields
+}
+
+/// The settings file with `values` set inside its OptionSettings line, which is added when
+/// the file doesn't have one yet
+fn set_options(content: &str, values: &[(&str, String)]) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let index = lines.iter().position(|l| l.trim_start().starts_with("OptionSettings=("));
+    let tuple = index
+        .and_then(|i| lines[i].trim().strip_prefix("OptionSettings=(")?.strip_suffix(')'))
+        .unwrap_or_default()
+        .to_string();
+
+    let mut fields: Vec<String> = split_fields(&tuple).into_iter().map(str::to_string).collect();
+    for (key, value) in values {
+        let field = format!("{}={}", key, value);
+        match fields.iter_mut().find(|f| f.split_once('=').is_some_and(|(k, _)| k == *key)) {
+            Some(existing) => *existing = field,
+            None => fields.push(field),
+        }
+    }
+    let line = format!("OptionSettings=({})", fields.join(","));
+
+    match index {
+        Some(i) => lines[i] = line,
+        None => {
+            if !lines.iter().any(|l| l.trim() == SETTINGS_SECTION) {
+                lines.push(SETTINGS_SECTION.to_string());
+            }
+            lines.push(line);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Switch the REST API on with the server's port and password, and RCON when the server has
+/// a port for it. Runs while the server is stopped - it reads the file once at start.
+pub fn enable_rest_api(server: &Server, game: &GameConfig) -> Result<(), String> {
+    let (Some(port), password) = api_settings(server, game) else {
+        return Ok(());
+    };
+    let path = server.data_path.join(SETTINGS_FILE);
+    if !path.exists() {
+        // Not installed yet - the install writes the file
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", SETTINGS_FILE, e))?;
+
+    let mut values = vec![("RESTAPIEnabled", "True".to_string()), ("RESTAPIPort", port.to_string())];
+    if let Some((rcon_port, _)) = rcon::server_settings(server, game) {
+        values.extend([("RCONEnabled", "True".to_string()), ("RCONPort", rcon_port.to_string())]);
+    }
+    // The file has no way to escape a quote - such a password only comes from the command line
+    if !password.contains('"') {
+        values.push(("AdminPassword", format!("\"{}\"", password)));
+    }
+    let updated = set_options(&content, &values);
+    if updated != content {
+        atomic::write_atomic(&path, updated).map_err(|e| format!("Failed to write {}: {}", SETTINGS_FILE, e))?;
+    }
+    Ok(())
+}
+
+/// Whether the container was made before the REST API port was published and has to be made
+/// again
+pub async fn outdated(docker: &DockerManager, server: &Server, game: &GameConfig) -> bool {
+    let (Some(container_id), (Some(port), _)) = (&server.container_id, api_settings(server, game)) else {
+        return false;
+    };
+    docker.container_env_var(container_id, PORT_VAR).await != Some(port.to_string())
+}
+
+/// The REST API of one running server
+pub struct RestApi {
+    base: String,
+    password: String,
+}
+
+impl RestApi {
+    pub fn new(server: &Server, game: &GameConfig) -> Result<Self, AppError> {
+        match api_settings(server, game) {
+            (Some(port), password) => Ok(Self {
+                base: format!("http://127.0.0.1:{}/v1/api", port),
+                password,
+            }),
+            (None, _) => Err(AppError::InvalidInput(
+                "This server has no REST API port - reset the Palworld game definition to its default".into(),
+            )),
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, AppError> {
+        let response = request
+            .basic_auth(USER, Some(&self.password))
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_connect() {
+                    "The server's REST API isn't answering - it's up a minute after the server starts".to_string()
+                } else {
+                    format!("Palworld REST API: {}", e)
+                }
+            })?;
+        match response.status().as_u16() {
+            401 => Err("The REST API refused the admin password - restart the server after changing it".into()),
+            status if status >= 400 => Err(format!("Palworld REST API returned {}", response.status()).into()),
+            _ => Ok(response),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, AppError> {
+        let response = self.send(reqwest::Client::new().get(format!("{}/{}", self.base, path))).await?;
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Unexpected answer from the REST API: {}", e).into())
+    }
+
+    async fn post(&self, path: &str, body: Value) -> Result<(), AppError> {
+        self.send(reqwest::Client::new().post(format!("{}/{}", self.base, path)).json(&body))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn info(&self) -> Result<ServerInfo, AppError> {
+        self.get("info").await
+    }
+
+    pub async fn metrics(&self) -> Result<Metrics, AppError> {
+        self.get("metrics").await
+    }
+
+    pub async fn players(&self) -> Result<Vec<Player>, AppError> {
+        Ok(self.get::<PlayerList>("players").await?.players)
+    }
+
+    pub async fn announce(&self, message: &str) -> Result<(), AppError> {
+        self.post("announce", json!({ "message": message })).await
+    }
+
+    pub async fn kick(&self, user_id: &str, message: &str) -> Result<(), AppError> {
+        self.post("kick", json!({ "userid": user_id, "message": message })).await
+    }
+
+    pub async fn ban(&self, user_id: &str, message: &str) -> Result<(), AppError> {
+        self.post("ban", json!({ "userid": user_id, "message": message })).await
+    }
+
+    pub async fn unban(&self, user_id: &str) -> Result<(), AppError> {
+        self.post("unban", json!({ "userid": user_id })).await
+    }
+
+    /// Write the world to disk now
+    pub async fn save(&self) -> Result<(), AppError> {
+        self.post("save", json!({})).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_are_set_inside_the_tuple() {
+        let content = "[/Script/Pal.PalGameWorldSettings]\n\
+            OptionSettings=(Difficulty=None,ServerDescription=\"Hi, all\",RESTAPIEnabled=False,RESTAPIPort=8212,CrossplayPlatforms=(Steam,Xbox))\n";
+        let updated = set_options(content, &[("RESTAPIEnabled", "True".into()), ("RESTAPIPort", "8300".into())]);
+        assert_eq!(
+            updated,
+            "[/Script/Pal.PalGameWorldSettings]\n\
+             OptionSettings=(Difficulty=None,ServerDescription=\"Hi, all\",RESTAPIEnabled=True,RESTAPIPort=8300,CrossplayPlatforms=(Steam,Xbox))\n"
+        );
+
+        let created = set_options("", &[("RESTAPIEnabled", "True".into())]);
+        assert_eq!(created, "[/Script/Pal.PalGameWorldSettings]\nOptionSettings=(RESTAPIEnabled=True)\n");
+    }
+}
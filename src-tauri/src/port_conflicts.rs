@@ -0,0 +1,91 @@
+// Port conflicts - a server's container failing to start because something else on this
+// computer holds one of its ports. Finds who holds it and a free port the server could
+// move to, and moves it there when the user agrees.
+
+use crate::commands::network::build_port_allocator;
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use serverwave_core::games::{GameConfig, GamesManager};
+use serverwave_core::ports;
+use serverwave_core::server::{lifecycle, LifecycleError, Server};
+use std::net::{TcpListener, UdpSocket};
+
+/// The host port a start failed on, when it failed for one being taken
+pub fn failed_port(e: &LifecycleError) -> Option<u16> {
+    match e {
+        LifecycleError::Docker(e) => ports::bind_failure_port(&e.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether nothing on this computer listens on the port, over TCP or UDP
+fn host_port_free(port: u16) -> bool {
+    TcpListener::bind(("0.0.0.0", port)).is_ok() && UdpSocket::bind(("0.0.0.0", port)).is_ok()
+}
+
+/// Whether the port can move: the game port, or an extra port the game reads from a variable
+fn movable(server: &Server, game: Option<&GameConfig>, port: u16) -> bool {
+    server.port == port
+        || lifecycle::extra_ports(server, game)
+            .iter()
+            .any(|p| p.container_port == port && p.env_var.as_deref().is_some_and(|v| !v.is_empty()))
+}
+
+/// The lowest port from `port` up that no server is given, no container publishes and nothing
+/// on the host listens on
+async fn next_free_port(docker: &DockerManager, games_manager: &GamesManager, port: u16) -> Option<u16> {
+    let mut allocator = build_port_allocator(games_manager);
+    loop {
+        let candidate = allocator.next_free(port)?;
+        if host_port_free(candidate) && docker.container_on_port(candidate).await.is_none() {
+            return Some(candidate);
+        }
+        allocator.assign("", "", candidate, None).ok()?;
+    }
+}
+
+/// The error for a start that failed on `port`: who holds it, and where the server could go
+pub async fn port_in_use(
+    docker: &DockerManager,
+    games_manager: &GamesManager,
+    server: &Server,
+    game: Option<&GameConfig>,
+    port: u16,
+) -> AppError {
+    let holder = docker.container_on_port(port).await;
+    let free_port = match movable(server, game, port) {
+        true => next_free_port(docker, games_manager, port).await,
+        false => None,
+    };
+    AppError::PortInUse { port, holder, free_port }
+}
+
+/// Give the server a free port in place of `port`, in the config only - the caller saves it
+/// and recreates the container. Returns the new port.
+pub async fn move_port(
+    docker: &DockerManager,
+    games_manager: &GamesManager,
+    server: &mut Server,
+    game: &GameConfig,
+    port: u16,
+) -> Result<u16, AppError> {
+    let not_movable = || AppError::InvalidInput(format!("Port {} is fixed by the game and can't be moved", port));
+    if !movable(server, Some(game), port) {
+        return Err(not_movable());
+    }
+    let free = next_free_port(docker, games_manager, port)
+        .await
+        .ok_or("No free port left in the configured port range")?;
+
+    if server.port == port {
+        server.port = free;
+        return Ok(free);
+    }
+    let mut extra_ports = lifecycle::extra_ports(server, Some(game));
+    let extra = extra_ports.iter_mut().find(|p| p.container_port == port).ok_or_else(not_movable)?;
+    let var = extra.env_var.clone().ok_or_else(not_movable)?;
+    extra.container_port = free;
+    server.config.insert(var, free.to_string());
+    server.extra_ports = extra_ports;
+    Ok(free)
+}
@@ -0,0 +1,213 @@
+// Startup reconciliation - catching up with what Docker did while the app was closed. After a
+// host reboot the daemon can bring containers back on its own, and servers can stop or crash
+// with nobody watching; stored statuses are fixed up and running servers are followed again.
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::network::{forward_ports, port_mappings_for, start_server_tunnel};
+use crate::commands::server::{load_all_server_configs, start_log_stream, ServerState};
+use crate::docker::DockerManager;
+use crate::events::{self, ServerEvent};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serverwave_core::server::{sessions, Server, ServerStatus};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Sent once the startup check is done, on its own channel
+pub const RECONCILED_EVENT: &str = "servers-reconciled";
+
+lazy_static::lazy_static! {
+    /// The startup check's result, for a window that opens after it was sent
+    static ref LAST: Mutex<Option<Reconciliation>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reconciled {
+    /// Came back up with the Docker daemon, like after a reboot
+    Resumed,
+    /// Stopped cleanly while the app was closed
+    Stopped,
+    /// Exited with an error while the app was closed
+    Crashed,
+    /// Was installing when the app closed - the install has to be retried
+    InstallInterrupted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconciledServer {
+    pub server_id: String,
+    pub server_name: String,
+    pub outcome: Reconciled,
+    pub exit_code: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reconciliation {
+    pub checked_at: DateTime<Utc>,
+    /// Servers whose stored status didn't match their container
+    pub servers: Vec<ReconciledServer>,
+    /// Running servers whose logs are followed again
+    pub reattached: usize,
+}
+
+/// What the container is doing now, as far as the stored status cares
+struct ContainerState {
+    running: bool,
+    exit_code: Option<i64>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+async fn container_state(docker: &DockerManager, container_id: &str) -> ContainerState {
+    // A container that's gone entirely stopped with the rest of them
+    let state = docker.client().inspect_container(container_id, None).await.ok().and_then(|info| info.state);
+    ContainerState {
+        running: state.as_ref().and_then(|s| s.running).unwrap_or(false),
+        exit_code: state.as_ref().and_then(|s| s.exit_code),
+        finished_at: state
+            .and_then(|s| s.finished_at)
+            .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+            .map(|time| time.with_timezone(&Utc))
+            // Docker reports year 1 for a container that never stopped
+            .filter(|time| time.timestamp() > 0),
+    }
+}
+
+/// Check every server against its container once the app is up
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        match reconcile(&app).await {
+            Some(result) => {
+                tracing::info!(
+                    "Startup check fixed {} servers and re-attached {}",
+                    result.servers.len(),
+                    result.reattached
+                );
+                *LAST.lock().unwrap() = Some(result.clone());
+                let _ = app.emit(RECONCILED_EVENT, &result);
+            }
+            None => tracing::warn!("Docker isn't reachable - server statuses weren't checked at startup"),
+        }
+    });
+}
+
+/// The startup check's result, once it ran
+pub fn last() -> Option<Reconciliation> {
+    LAST.lock().unwrap().clone()
+}
+
+async fn reconcile(app: &AppHandle) -> Option<Reconciliation> {
+    let docker = app.state::<DockerState>().manager().ok()?;
+    docker.ping().await.ok()?;
+
+    let mut servers = Vec::new();
+    let mut reattached = 0;
+    for mut server in load_all_server_configs() {
+        if server.status == ServerStatus::Installing {
+            servers.push(interrupt_install(&docker, app, &mut server).await);
+            continue;
+        }
+        let Some(container_id) = server.container_id.clone() else {
+            continue;
+        };
+
+        let state = container_state(&docker, &container_id).await;
+        if state.running {
+            if server.status != ServerStatus::Running {
+                servers.push(resume(&docker, app, &mut server).await);
+            }
+            start_log_stream(&server.id, &container_id, app.clone(), &app.state::<ServerState>()).await;
+            reattached += 1;
+        } else if matches!(server.status, ServerStatus::Running | ServerStatus::Starting | ServerStatus::Stopping) {
+            servers.push(settle_exit(app, &mut server, &state));
+        }
+    }
+
+    Some(Reconciliation {
+        checked_at: Utc::now(),
+        servers,
+        reattached,
+    })
+}
+
+fn entry(server: &Server, outcome: Reconciled, exit_code: Option<i64>) -> ReconciledServer {
+    ReconciledServer {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        outcome,
+        exit_code,
+    }
+}
+
+/// Save what the check settled - the status, and the install's outcome
+fn save(app: &AppHandle, server: &Server) {
+    let saved = app.state::<ServerState>().configs.update(&server.id, |s| {
+        s.status = server.status.clone();
+        s.install_container_id = server.install_container_id.clone();
+        s.install_state = server.install_state.clone();
+    });
+    if let Err(e) = saved {
+        tracing::warn!("Failed to save the checked status of {}: {}", server.id, e);
+    }
+}
+
+/// The install script ran in a container the app was following - without it, nothing records
+/// the result, so the install is failed and can be retried from where it stopped
+async fn interrupt_install(docker: &DockerManager, app: &AppHandle, server: &mut Server) -> ReconciledServer {
+    let mut exit_code = None;
+    if let Some(container_id) = server.install_container_id.take() {
+        exit_code = container_state(docker, &container_id).await.exit_code;
+        docker.remove_install_container(&container_id).await.ok();
+    }
+    let mut state = server.install_state.take().unwrap_or_default();
+    state.attempts += 1;
+    state.exit_code = exit_code;
+    state.failed_at = Some(Utc::now());
+    server.install_state = Some(state);
+    server.status = ServerStatus::Error;
+    save(app, server);
+
+    tracing::warn!("Server {} was installing when the app closed", server.id);
+    events::emit(app, ServerEvent::status(&server.id, ServerStatus::Error));
+    entry(server, Reconciled::InstallInterrupted, exit_code)
+}
+
+/// Started without the app - redo what starting it from the app would have
+async fn resume(docker: &DockerManager, app: &AppHandle, server: &mut Server) -> ReconciledServer {
+    server.status = ServerStatus::Running;
+    save(app, server);
+
+    tracing::info!("Server {} came back up while the app was closed", server.id);
+    events::emit(app, ServerEvent::status(&server.id, ServerStatus::Running));
+    if server.upnp_enabled {
+        let mappings = port_mappings_for(server, &app.state::<GamesState>()).await;
+        forward_ports(app, server, mappings);
+    }
+    start_server_tunnel(docker, app, server).await;
+    entry(server, Reconciled::Resumed, None)
+}
+
+fn settle_exit(app: &AppHandle, server: &mut Server, state: &ContainerState) -> ReconciledServer {
+    server.status = ServerStatus::Stopped;
+    save(app, server);
+
+    // Players left when it went down, not now
+    let ended_at = state.finished_at.unwrap_or_else(Utc::now);
+    if let Err(e) = sessions::end_sessions(&server.id, ended_at) {
+        tracing::warn!("Failed to end player sessions for {}: {}", server.id, e);
+    }
+    events::emit(app, ServerEvent::status(&server.id, ServerStatus::Stopped));
+
+    match state.exit_code.filter(|code| *code != 0) {
+        Some(exit_code) => {
+            tracing::warn!("Server {} exited with code {} while the app was closed", server.id, exit_code);
+            events::emit(app, ServerEvent::Crashed {
+                server_id: server.id.clone(),
+                exit_code,
+            });
+            entry(server, Reconciled::Crashed, Some(exit_code))
+        }
+        None => entry(server, Reconciled::Stopped, state.exit_code),
+    }
+}
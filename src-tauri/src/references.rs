@@ -0,0 +1,152 @@
+// Server references - a server's variables can take values from other servers, written as
+// `{{server:<name or id>.<attribute>}}` and filled in each time the server starts. That lets
+// a stack of containers (a proxy and its backends, a game and its database) be set up in
+// the variables alone. Servers whose `host` or `address` is used are put on a shared
+// network with the server using them, so the name reaches their container.
+
+use crate::docker::{DockerError, DockerManager};
+use crate::games::{GameConfig, GamesManager};
+use serverwave_core::server::{lifecycle, Server};
+use std::collections::HashMap;
+
+pub const LINKS_NETWORK: &str = "serverwave-links";
+const OPEN: &str = "{{server:";
+const CLOSE: &str = "}}";
+
+/// Attributes every server has; anything else is one of its variables
+const ATTRIBUTES: &[&str] = &["id", "name", "host", "port", "address"];
+
+/// What a server's references came to
+#[derive(Debug, Default)]
+pub struct Resolved {
+    /// The variables that had references, filled in
+    pub env: HashMap<String, String>,
+    /// Servers that have to share a network with this one
+    pub linked: Vec<Server>,
+}
+
+/// `(placeholder, server, attribute)` for each reference in a value
+fn parse(value: &str) -> Vec<(&str, &str, &str)> {
+    let mut found = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(OPEN) {
+        let inner = &rest[start + OPEN.len()..];
+        let Some(end) = inner.find(CLOSE) else { break };
+        if let Some((server, attribute)) = inner[..end].rsplit_once('.') {
+            let placeholder = &rest[start..start + OPEN.len() + end + CLOSE.len()];
+            found.push((placeholder, server.trim(), attribute.trim()));
+        }
+        rest = &inner[end + CLOSE.len()..];
+    }
+    found
+}
+
+pub fn has_references(value: &str) -> bool {
+    !parse(value).is_empty()
+}
+
+/// The server a reference names - by id, or by name when no other server has it
+fn find<'a>(servers: &'a [Server], name: &str) -> Result<&'a Server, String> {
+    if let Some(server) = servers.iter().find(|s| s.id == name) {
+        return Ok(server);
+    }
+    let mut named = servers.iter().filter(|s| s.name.eq_ignore_ascii_case(name));
+    match (named.next(), named.next()) {
+        (Some(server), None) => Ok(server),
+        (Some(_), Some(_)) => Err(format!("More than one server is called '{}' - refer to it by id", name)),
+        (None, _) => Err(format!("No server is called '{}'", name)),
+    }
+}
+
+/// A server's container name, which other containers on its networks can reach it by
+pub fn container_host(server: &Server) -> String {
+    format!("serverwave-{}", server.id)
+}
+
+fn attribute(target: &Server, games: &GamesManager, name: &str) -> Result<String, String> {
+    match name {
+        "id" => Ok(target.id.clone()),
+        "name" => Ok(target.name.clone()),
+        "host" => Ok(container_host(target)),
+        "port" => Ok(target.port.to_string()),
+        "address" => Ok(format!("{}:{}", container_host(target), target.port)),
+        var => {
+            let game = games.get_game(&target.game_type);
+            let env = match &game {
+                Some(game) => env_of(target, game),
+                None => target.env.iter().map(|v| (v.key.clone(), v.value.clone())).collect(),
+            };
+            let value = env.get(var).ok_or_else(|| {
+                format!("'{}' has no variable {} (or use one of {})", target.name, var, ATTRIBUTES.join(", "))
+            })?;
+            if has_references(value) {
+                return Err(format!(
+                    "{} on '{}' refers to another server itself - refer to that one directly",
+                    var, target.name
+                ));
+            }
+            Ok(value.clone())
+        }
+    }
+}
+
+fn env_of(server: &Server, game: &GameConfig) -> HashMap<String, String> {
+    let extra_ports = lifecycle::extra_ports(server, Some(game));
+    lifecycle::container_env(game, server.memory_mb, server.port, &server.config, &server.env, &extra_ports)
+}
+
+/// Fill in the references in a server's variables from the other servers
+pub fn resolve(server: &Server, game: &GameConfig, servers: &[Server], games: &GamesManager) -> Result<Resolved, String> {
+    let mut resolved = Resolved::default();
+    for (key, value) in env_of(server, game) {
+        let references = parse(&value);
+        if references.is_empty() {
+            continue;
+        }
+        let mut filled = value.clone();
+        for (placeholder, name, attr) in references {
+            let target = find(servers, name).map_err(|e| format!("{}: {}", key, e))?;
+            if target.id == server.id {
+                return Err(format!("{} refers to this server itself", key));
+            }
+            let value = attribute(target, games, attr).map_err(|e| format!("{}: {}", key, e))?;
+            filled = filled.replace(placeholder, &value);
+            if matches!(attr, "host" | "address") && !resolved.linked.iter().any(|s| s.id == target.id) {
+                resolved.linked.push(target.clone());
+            }
+        }
+        resolved.env.insert(key, filled);
+    }
+    Ok(resolved)
+}
+
+/// Put the server and the servers it refers to by host on the links network
+pub async fn connect(docker: &DockerManager, server: &Server, linked: &[Server]) -> Result<(), DockerError> {
+    if linked.is_empty() {
+        return Ok(());
+    }
+    docker.ensure_network(LINKS_NETWORK).await?;
+    for container_id in std::iter::once(server).chain(linked).filter_map(|s| s.container_id.as_deref()) {
+        if !docker.container_networks(container_id).await.iter().any(|n| n == LINKS_NETWORK) {
+            docker.connect_to_network(LINKS_NETWORK, container_id).await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references_are_found_in_values() {
+        assert_eq!(
+            parse("jdbc:mysql://{{server:My DB.address}}/game?user={{server:ab12cd34.DB_USER}}"),
+            vec![
+                ("{{server:My DB.address}}", "My DB", "address"),
+                ("{{server:ab12cd34.DB_USER}}", "ab12cd34", "DB_USER"),
+            ]
+        );
+        assert!(parse("{{SERVER_PORT}} and {{server:no-attribute}}").is_empty());
+    }
+}
@@ -0,0 +1,350 @@
+// Scheduled tasks - a server action (restart, backup, console command or game update) run on
+// a cron expression, like a restart every night at 5. A restart can warn players in the
+// console first; its countdown starts early so the restart itself is on time. Restarts,
+// backups and updates keep to the server's maintenance window.
+// schedules.json in the config folder holds them.
+
+use crate::backups;
+use crate::commands::docker::DockerState;
+use crate::commands::server::{console_command, start_server, stop_server, update_server_game};
+use crate::docker::DockerManager;
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::maintenance::{self, Window};
+use chrono::{DateTime, Duration as ChronoDuration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use serverwave_core::cron::Cron;
+use serverwave_core::server::{lifecycle, load_server_config, Server, ServerStatus};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const TICK: Duration = Duration::from_secs(60);
+/// A run the app wasn't open for is skipped when it's later than this, not run late
+const MISSED_AFTER: ChronoDuration = ChronoDuration::hours(1);
+const BACKUP_NOTE: &str = "Scheduled task";
+/// How long before a restart its first warning may go out
+const MAX_COUNTDOWN_MINUTES: u32 = 60;
+/// A warning whose time passed by more than this (the app was busy or just opened) isn't sent
+const WARNING_GRACE: ChronoDuration = ChronoDuration::seconds(30);
+
+/// Held while schedules.json is read and written back, so a run finishing doesn't undo an edit
+static FILE: Mutex<()> = Mutex::new(());
+
+lazy_static::lazy_static! {
+    /// Schedules being run, so one still counting down or updating isn't started twice
+    static ref RUNNING: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduledAction {
+    /// Stop and start again - a stopped server is left stopped
+    Restart {
+        /// Console commands sent ahead of the restart, like "say Restarting in 5 minutes"
+        #[serde(default)]
+        warnings: Vec<RestartWarning>,
+    },
+    Backup,
+    /// Type a command into the console of the running server
+    Command { command: String },
+    /// Run the install script for the latest build, and start the server again if it was up
+    Update,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartWarning {
+    pub minutes_before: u32,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(default)]
+    pub id: String,
+    pub server_id: String,
+    #[serde(default)]
+    pub name: String,
+    /// Five-field cron expression in local time, like "0 5 * * *"
+    pub cron: String,
+    pub action: ScheduledAction,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub last_run: Option<DateTime<Utc>>,
+    /// What the last run did, or why it failed
+    #[serde(default)]
+    pub last_result: Option<String>,
+    #[serde(default)]
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Schedule {
+    pub fn validate(&self) -> Result<(), String> {
+        Cron::parse(&self.cron)?;
+        match &self.action {
+            ScheduledAction::Command { command } if command.trim().is_empty() => Err("Command is required".to_string()),
+            ScheduledAction::Restart { warnings } => {
+                for warning in warnings {
+                    if warning.command.trim().is_empty() {
+                        return Err("Each warning needs a command".to_string());
+                    }
+                    if warning.minutes_before == 0 || warning.minutes_before > MAX_COUNTDOWN_MINUTES {
+                        return Err(format!("Warnings go out 1 to {} minutes before the restart", MAX_COUNTDOWN_MINUTES));
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The next run after `after`, None when the expression never matches again
+    pub fn next_from(&self, after: &DateTime<Local>) -> Option<DateTime<Utc>> {
+        let cron = Cron::parse(&self.cron).ok()?;
+        cron.next_after(after).map(|t| t.with_timezone(&Utc))
+    }
+
+    /// How long before its time the schedule starts - a restart's warning countdown
+    pub fn countdown(&self) -> ChronoDuration {
+        match &self.action {
+            ScheduledAction::Restart { warnings } => {
+                let minutes = warnings.iter().map(|w| w.minutes_before).max().unwrap_or(0);
+                ChronoDuration::minutes(minutes.into())
+            }
+            _ => ChronoDuration::zero(),
+        }
+    }
+}
+
+pub fn load_schedules() -> Vec<Schedule> {
+    atomic::read_json(&get_schedules_path()).unwrap_or_default()
+}
+
+pub fn save_schedules(schedules: &[Schedule]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(schedules).map_err(|e| e.to_string())?;
+    atomic::write_with_backup(&get_schedules_path(), content).map_err(|e| e.to_string())
+}
+
+/// Load, change and save the schedules as one step
+pub fn modify<T>(change: impl FnOnce(&mut Vec<Schedule>) -> Result<T, String>) -> Result<T, String> {
+    let _file = FILE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut schedules = load_schedules();
+    let result = change(&mut schedules)?;
+    save_schedules(&schedules)?;
+    Ok(result)
+}
+
+/// Drop a deleted server's schedules
+pub fn forget(server_id: &str) {
+    let removed = modify(|schedules| {
+        schedules.retain(|s| s.server_id != server_id);
+        Ok(())
+    });
+    if let Err(e) = removed {
+        tracing::warn!("Failed to save schedules: {}", e);
+    }
+}
+
+/// Run the schedules that are due, once a minute
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            run_due(&app).await;
+        }
+    });
+}
+
+async fn run_due(app: &AppHandle) {
+    let now = Utc::now();
+    // Moved on before running, so a run that's still counting down isn't started again
+    let due = modify(|schedules| {
+        let mut due = Vec::new();
+        for schedule in schedules.iter_mut().filter(|s| s.enabled) {
+            let Some(when) = schedule.next_run.filter(|when| *when - schedule.countdown() <= now) else { continue };
+            schedule.next_run = schedule.next_from(&when.max(now).with_timezone(&Local));
+            if now - when > MISSED_AFTER {
+                let when = when.with_timezone(&Local).format("%b %-d %H:%M");
+                schedule.last_result = Some(format!("Skipped the run planned for {} - the app wasn't running", when));
+            } else {
+                due.push((schedule.clone(), when));
+            }
+        }
+        Ok(due)
+    });
+    let due = match due {
+        Ok(due) => due,
+        Err(e) => {
+            tracing::warn!("Failed to save schedules: {}", e);
+            return;
+        }
+    };
+    // Each on its own, so one counting down doesn't hold up the rest
+    for (schedule, when) in due {
+        tracing::info!("Running schedule {} of {}", schedule.id, schedule.server_id);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = run_and_record(&app, &schedule, when, true).await;
+        });
+    }
+}
+
+/// Run a schedule's action for its time `at` and note how it went. A restart's warnings
+/// go out before `at`. An `automatic` run keeps to the server's maintenance window.
+pub async fn run_and_record(app: &AppHandle, schedule: &Schedule, at: DateTime<Utc>, automatic: bool) -> Result<(), AppError> {
+    if !RUNNING.lock().unwrap().insert(schedule.id.clone()) {
+        return Err(AppError::InvalidInput("This task is already running".into()));
+    }
+    let ran = run(app, schedule, at, automatic).await;
+    RUNNING.lock().unwrap().remove(&schedule.id);
+    let result = match &ran {
+        Ok(done) => done.clone(),
+        Err(e) => {
+            let label = if schedule.name.is_empty() { "Scheduled task" } else { schedule.name.as_str() };
+            events::emit(app, ServerEvent::alert(&schedule.server_id, AlertLevel::Error, format!("{} failed: {}", label, e)));
+            format!("Failed: {}", e)
+        }
+    };
+    let recorded = modify(|schedules| {
+        if let Some(saved) = schedules.iter_mut().find(|s| s.id == schedule.id) {
+            saved.last_run = Some(Utc::now());
+            saved.last_result = Some(result);
+        }
+        Ok(())
+    });
+    if let Err(e) = recorded {
+        tracing::warn!("Failed to save schedules: {}", e);
+    }
+    ran.map(|_| ())
+}
+
+async fn run(app: &AppHandle, schedule: &Schedule, at: DateTime<Utc>, automatic: bool) -> Result<String, AppError> {
+    let server_id = schedule.server_id.clone();
+    let server = load_server_config(&server_id)?;
+    let docker = app.state::<DockerState>().manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if status == ServerStatus::Installing {
+        return Err(AppError::InvalidInput("The server is installing".into()));
+    }
+    let running = status == ServerStatus::Running;
+
+    match &schedule.action {
+        ScheduledAction::Restart { warnings } => {
+            if !running {
+                return Ok("Not running - left stopped".to_string());
+            }
+            // Before the first warning, so players aren't told of a restart that won't happen
+            if let Some(held) = hold(app, schedule, &server, at, automatic) {
+                return Ok(held);
+            }
+            count_down(&docker, &server_id, warnings, at).await;
+            // Someone may have stopped it during the countdown
+            let server = load_server_config(&server_id)?;
+            if lifecycle::current_status(&docker, &server).await? != ServerStatus::Running {
+                return Ok("Stopped during the countdown - left stopped".to_string());
+            }
+            events::emit(app, ServerEvent::log(&server_id, "[Serverwave] Restarting on schedule..."));
+            stop_server(server_id.clone(), app.clone(), app.state(), app.state(), app.state()).await?;
+            start_server(server_id, None, None, app.clone(), app.state(), app.state(), app.state()).await?;
+            Ok("Restarted".to_string())
+        }
+        ScheduledAction::Backup => {
+            if let Some(held) = hold(app, schedule, &server, at, automatic) {
+                return Ok(held);
+            }
+            let backup = backups::create(app, &server_id, Some(BACKUP_NOTE.to_string())).await?;
+            Ok(format!("Backed up ({})", backup.id))
+        }
+        ScheduledAction::Command { command } => {
+            if !running {
+                return Ok("Not running - command not sent".to_string());
+            }
+            console_command(&docker, &server_id, command).await?;
+            Ok(format!("Sent \"{}\"", command))
+        }
+        ScheduledAction::Update => {
+            if let Some(held) = hold(app, schedule, &server, at, automatic) {
+                return Ok(held);
+            }
+            events::emit(app, ServerEvent::log(&server_id, "[Serverwave] Updating on schedule..."));
+            update_server_game(server_id.clone(), app.clone(), app.state(), app.state(), app.state()).await?;
+            if running {
+                start_server(server_id, None, None, app.clone(), app.state(), app.state(), app.state()).await?;
+            }
+            Ok("Updated".to_string())
+        }
+    }
+}
+
+/// Keep an automatic restart, backup or update - which take the server down - out of its
+/// quiet hours. It's put off until the maintenance window opens when that comes before its
+/// next run, and skipped otherwise. Returns what happened instead, None when it may run.
+fn hold(app: &AppHandle, schedule: &Schedule, server: &Server, at: DateTime<Utc>, automatic: bool) -> Option<String> {
+    if !automatic {
+        return None;
+    }
+    let Window::Closed { opens } = maintenance::window_at(server, at) else { return None };
+    let task = match schedule.action {
+        ScheduledAction::Restart { .. } => "Scheduled restart",
+        ScheduledAction::Backup => "Scheduled backup",
+        ScheduledAction::Update => "Scheduled update",
+        ScheduledAction::Command { .. } => "Scheduled command",
+    };
+    let deferred = opens.filter(|opens| {
+        let moved = modify(|schedules| {
+            let Some(saved) = schedules.iter_mut().find(|s| s.id == schedule.id) else { return Ok(false) };
+            if saved.next_run.is_some_and(|next| next <= *opens) {
+                return Ok(false);
+            }
+            saved.next_run = Some(*opens);
+            Ok(true)
+        });
+        moved.unwrap_or_else(|e| {
+            tracing::warn!("Failed to save schedules: {}", e);
+            false
+        })
+    });
+    maintenance::report_held(app, &schedule.server_id, task, deferred);
+    Some(match deferred {
+        Some(until) => format!(
+            "Put off until {} - outside the maintenance window",
+            until.with_timezone(&Local).format("%b %-d %H:%M")
+        ),
+        None => "Skipped - outside the maintenance window".to_string(),
+    })
+}
+
+/// Send each warning at its time before `at`, then wait for `at`
+async fn count_down(docker: &DockerManager, server_id: &str, warnings: &[RestartWarning], at: DateTime<Utc>) {
+    let mut warnings: Vec<&RestartWarning> = warnings.iter().collect();
+    warnings.sort_by_key(|w| std::cmp::Reverse(w.minutes_before));
+    for warning in warnings {
+        let send_at = at - ChronoDuration::minutes(warning.minutes_before.into());
+        if Utc::now() - send_at > WARNING_GRACE {
+            continue;
+        }
+        sleep_until(send_at).await;
+        if let Err(e) = console_command(docker, server_id, &warning.command).await {
+            tracing::warn!("Failed to send the restart warning to {}: {}", server_id, e);
+        }
+    }
+    sleep_until(at).await;
+}
+
+async fn sleep_until(when: DateTime<Utc>) {
+    if let Ok(wait) = (when - Utc::now()).to_std() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+fn get_schedules_path() -> PathBuf {
+    serverwave_core::paths::config_dir().join("schedules.json")
+}
@@ -0,0 +1,145 @@
+// Access control - who is calling a command and what they may do
+//
+// Commands run from the desktop window have full access. API calls run with a
+// `Caller`: the app's API token is an admin, user tokens are limited by role
+// and by the servers they were given.
+
+use super::users::User;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Everything, on every server
+    Admin,
+    /// View, start/stop and use the console of assigned servers
+    Operator,
+    /// Read-only access to assigned servers
+    Viewer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// Status, stats, logs and connection info
+    View,
+    /// Start and stop
+    Control,
+    /// Send console commands
+    Console,
+    /// Anything else - files, config, deletion, games, settings
+    Manage,
+}
+
+impl Role {
+    pub fn allows(self, permission: Permission) -> bool {
+        match self {
+            Role::Admin => true,
+            Role::Operator => permission != Permission::Manage,
+            Role::Viewer => permission == Permission::View,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Caller {
+    /// Authenticated with the app's own API token
+    Admin,
+    User(User),
+}
+
+impl Caller {
+    /// Whether the caller may do `permission` on a server, or globally when `server_id` is None
+    pub fn can(&self, permission: Permission, server_id: Option<&str>) -> bool {
+        let user = match self {
+            Caller::Admin => return true,
+            Caller::User(user) => user,
+        };
+        if user.read_only && permission != Permission::View {
+            return false;
+        }
+        if user.role == Role::Admin {
+            return true;
+        }
+        if !user.role.allows(permission) {
+            return false;
+        }
+        match server_id {
+            Some(id) => user.servers.iter().any(|s| s == id),
+            None => permission == Permission::View,
+        }
+    }
+}
+
+tokio::task_local! {
+    static CALLER: Caller;
+}
+
+/// Run a future on behalf of an API caller
+pub async fn with_caller<F: Future>(caller: Caller, future: F) -> F::Output {
+    CALLER.scope(caller, future).await
+}
+
+/// The API caller running the current command, None for the desktop app
+pub fn current_caller() -> Option<Caller> {
+    CALLER.try_with(|caller| caller.clone()).ok()
+}
+
+/// Permission check for the current caller - the desktop app may do anything
+pub fn can_access(permission: Permission, server_id: Option<&str>) -> bool {
+    current_caller()
+        .map(|caller| caller.can(permission, server_id))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(role: Role, servers: &[&str]) -> Caller {
+        Caller::User(User {
+            id: "u1".to_string(),
+            name: "friend".to_string(),
+            role,
+            servers: servers.iter().map(|s| s.to_string()).collect(),
+            read_only: false,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_operator_limited_to_assigned_servers() {
+        let caller = user(Role::Operator, &["mc"]);
+        assert!(caller.can(Permission::Console, Some("mc")));
+        assert!(caller.can(Permission::Control, Some("mc")));
+        assert!(!caller.can(Permission::Manage, Some("mc")));
+        assert!(!caller.can(Permission::View, Some("rust")));
+        assert!(caller.can(Permission::View, None));
+        assert!(!caller.can(Permission::Manage, None));
+    }
+
+    #[test]
+    fn test_viewer_is_read_only() {
+        let caller = user(Role::Viewer, &["mc"]);
+        assert!(caller.can(Permission::View, Some("mc")));
+        assert!(!caller.can(Permission::Control, Some("mc")));
+        assert!(!caller.can(Permission::Console, Some("mc")));
+    }
+
+    #[test]
+    fn test_admins_can_do_anything() {
+        assert!(Caller::Admin.can(Permission::Manage, None));
+        assert!(user(Role::Admin, &[]).can(Permission::Manage, Some("mc")));
+    }
+
+    #[test]
+    fn test_read_only_user_only_views() {
+        let Caller::User(mut watcher) = user(Role::Admin, &[]) else { unreachable!() };
+        watcher.read_only = true;
+        let caller = Caller::User(watcher);
+        assert!(caller.can(Permission::View, Some("mc")));
+        assert!(caller.can(Permission::View, None));
+        assert!(!caller.can(Permission::Control, Some("mc")));
+        assert!(!caller.can(Permission::Manage, None));
+    }
+}
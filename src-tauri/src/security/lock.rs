@@ -0,0 +1,105 @@
+// App lock - an optional password required before destructive commands
+
+use super::access;
+use crate::error::AppError;
+use super::secrets;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const PASSWORD_KEY: &str = "app-password";
+/// How long one unlock lasts before the password is asked for again
+const UNLOCK_DURATION: Duration = Duration::from_secs(15 * 60);
+
+lazy_static::lazy_static! {
+    static ref UNLOCKED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockStatus {
+    pub password_set: bool,
+    pub unlocked: bool,
+}
+
+fn is_unlocked() -> bool {
+    UNLOCKED_UNTIL
+        .lock()
+        .unwrap()
+        .map(|until| Instant::now() < until)
+        .unwrap_or(false)
+}
+
+/// Fails when an app password is set and the app hasn't been unlocked recently
+pub async fn ensure_unlocked() -> Result<(), AppError> {
+    // API callers are already authenticated by their token
+    if access::current_caller().is_some() || is_unlocked() {
+        return Ok(());
+    }
+    if secrets::get_secret(PASSWORD_KEY).await.is_none() {
+        return Ok(());
+    }
+    Err(AppError::Locked)
+}
+
+pub async fn status() -> LockStatus {
+    let password_set = secrets::get_secret(PASSWORD_KEY).await.is_some();
+    LockStatus {
+        password_set,
+        unlocked: !password_set || is_unlocked(),
+    }
+}
+
+/// Check the password and keep the app unlocked for a while
+pub async fn unlock(password: &str) -> Result<(), String> {
+    let hash = match secrets::get_secret(PASSWORD_KEY).await {
+        Some(hash) => hash,
+        None => return Ok(()),
+    };
+    if !verify_password(password, &hash) {
+        return Err("Wrong password".to_string());
+    }
+    *UNLOCKED_UNTIL.lock().unwrap() = Some(Instant::now() + UNLOCK_DURATION);
+    Ok(())
+}
+
+pub fn lock() {
+    *UNLOCKED_UNTIL.lock().unwrap() = None;
+}
+
+/// Set, change or (with `None`) remove the app password. Changing it needs the current one.
+pub async fn set_password(current: Option<&str>, new: Option<&str>) -> Result<(), String> {
+    if let Some(hash) = secrets::get_secret(PASSWORD_KEY).await {
+        if !verify_password(current.unwrap_or(""), &hash) {
+            return Err("Current password is incorrect".to_string());
+        }
+    }
+
+    match new.filter(|p| !p.is_empty()) {
+        Some(password) => {
+            let hash = hash_password(password)?;
+            secrets::set_secret(PASSWORD_KEY, &hash).await?;
+            *UNLOCKED_UNTIL.lock().unwrap() = Some(Instant::now() + UNLOCK_DURATION);
+        }
+        None => {
+            secrets::delete_secret(PASSWORD_KEY).await;
+            lock();
+        }
+    }
+    Ok(())
+}
+
+fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::encode_b64(uuid::Uuid::new_v4().as_bytes()).map_err(|e| e.to_string())?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn verify_password(password: &str, hash: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
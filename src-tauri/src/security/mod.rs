@@ -0,0 +1,10 @@
+// Security module - keychain secrets, the app lock, read-only windows and remote users
+
+pub mod access;
+pub mod lock;
+pub mod read_only;
+pub mod secrets;
+pub mod users;
+
+pub use access::{can_access, current_caller, with_caller, Caller, Permission, Role};
+pub use lock::ensure_unlocked;
@@ -0,0 +1,122 @@
+// Read-only windows - a window to screen-share or hand to someone watching consoles and
+// stats. Every command it sends that changes something is refused before it runs; API users
+// get the same with their `read_only` flag.
+
+use crate::api::dispatch::required_permission;
+use crate::error::AppError;
+use super::access::Permission;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::ipc::{Invoke, InvokeBody, InvokeMessage};
+use tauri::Runtime;
+
+/// Label prefix of the windows opened read-only
+pub const WINDOW_PREFIX: &str = "read-only-";
+
+/// Commands that only read but aren't open to API viewers, for the app's own pages
+const WINDOW_READS: &[&str] = &[
+    "get_app_settings",
+    "get_read_only_mode",
+    "set_read_only_mode",
+    "take_pending_link",
+    "get_port_allocations",
+    "get_pending_updates",
+    "preview_startup",
+    "preview_config_changes",
+    "list_proxy_networks",
+    "list_remote_backups",
+    "list_installed_content",
+    "get_server_versions",
+    "get_content_cache",
+    "search_modrinth",
+    "get_modrinth_versions",
+    "search_curseforge",
+    "list_worlds",
+    "list_players",
+    "list_datapacks",
+    "get_resource_pack",
+    "list_hooks",
+    "list_nodes",
+    "get_game_sync_status",
+    "get_games_config_path",
+    "get_stale_images",
+    "get_install_images",
+    "get_app_logs",
+    "get_server_roots",
+    "list_directory",
+    "read_file_text",
+    "get_file_info",
+];
+
+lazy_static::lazy_static! {
+    static ref WINDOWS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+pub fn is_read_only(window: &str) -> bool {
+    WINDOWS.lock().unwrap().contains(window)
+}
+
+pub fn set(window: &str, read_only: bool) {
+    let mut windows = WINDOWS.lock().unwrap();
+    if read_only {
+        windows.insert(window.to_string());
+    } else {
+        windows.remove(window);
+    }
+}
+
+/// Whether a read-only window may run `command`. Commands run on an agent are judged by
+/// the command they carry.
+pub fn allows(command: &str, args: &Value) -> bool {
+    if command == "node_invoke" {
+        return match args.get("command").and_then(Value::as_str) {
+            Some(inner) => inner != "node_invoke" && allows(inner, args.get("args").unwrap_or(&Value::Null)),
+            None => false,
+        };
+    }
+    required_permission(command) == Permission::View || WINDOW_READS.contains(&command)
+}
+
+/// Wrap the app's command handler so read-only windows can't run commands that change things
+pub fn guard<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        if refuses(&invoke.message) {
+            invoke.resolver.reject(AppError::ReadOnly);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+fn refuses<R: Runtime>(message: &InvokeMessage<R>) -> bool {
+    if !is_read_only(message.webview_ref().label()) {
+        return false;
+    }
+    let args = match message.payload() {
+        InvokeBody::Json(args) => args,
+        InvokeBody::Raw(_) => &Value::Null,
+    };
+    !allows(message.command(), args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_only_reads_are_allowed() {
+        assert!(allows("get_server_logs", &Value::Null));
+        assert!(allows("read_file_text", &Value::Null));
+        assert!(!allows("delete_server", &Value::Null));
+        assert!(!allows("start_server", &Value::Null));
+        assert!(!allows("write_file_text", &Value::Null));
+
+        assert!(allows("node_invoke", &json!({ "nodeId": "n1", "command": "list_servers" })));
+        assert!(!allows("node_invoke", &json!({ "nodeId": "n1", "command": "delete_server" })));
+        assert!(!allows("node_invoke", &json!({ "nodeId": "n1" })));
+    }
+}
@@ -0,0 +1,82 @@
+// Secret storage - the OS keychain, with a private file fallback when no keychain
+// is available (e.g. a headless Linux box without a secret service)
+
+use std::path::{Path, PathBuf};
+
+const SERVICE: &str = "com.serverwave.anywhere";
+
+fn keychain_get(key: &str) -> Option<String> {
+    keyring::Entry::new(SERVICE, key).ok()?.get_password().ok()
+}
+
+fn keychain_set(key: &str, value: &str) -> Result<(), String> {
+    keyring::Entry::new(SERVICE, key)
+        .and_then(|entry| entry.set_password(value))
+        .map_err(|e| e.to_string())
+}
+
+fn keychain_delete(key: &str) {
+    if let Ok(entry) = keyring::Entry::new(SERVICE, key) {
+        entry.delete_credential().ok();
+    }
+}
+
+/// Read a secret, checking the keychain first
+pub async fn get_secret(key: &str) -> Option<String> {
+    let key = key.to_string();
+    tokio::task::spawn_blocking(move || {
+        keychain_get(&key).or_else(|| {
+            std::fs::read_to_string(get_fallback_path(&key))
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        })
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Store a secret in the keychain, or in a file only this user can read
+pub async fn set_secret(key: &str, value: &str) -> Result<(), String> {
+    let key = key.to_string();
+    let value = value.to_string();
+    tokio::task::spawn_blocking(move || match keychain_set(&key, &value) {
+        Ok(()) => {
+            std::fs::remove_file(get_fallback_path(&key)).ok();
+            Ok(())
+        }
+        Err(e) => {
+            tracing::warn!("Keychain unavailable ({}), storing secret '{}' in a file", e, key);
+            write_private_file(&get_fallback_path(&key), &value)
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+pub async fn delete_secret(key: &str) {
+    let key = key.to_string();
+    let _ = tokio::task::spawn_blocking(move || {
+        keychain_delete(&key);
+        std::fs::remove_file(get_fallback_path(&key)).ok();
+    })
+    .await;
+}
+
+fn write_private_file(path: &Path, value: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(path, value).map_err(|e| e.to_string())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600)).ok();
+    }
+    Ok(())
+}
+
+fn get_fallback_path(key: &str) -> PathBuf {
+    serverwave_core::paths::app_root().join("secrets").join(key)
+}
@@ -0,0 +1,80 @@
+// Users - people given their own API token with a role and a set of servers
+//
+// Users are stored in users.json, their tokens in the keychain.
+
+use super::access::Role;
+use super::secrets;
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+    pub role: Role,
+    /// Servers an operator or viewer may access (ignored for admins)
+    #[serde(default)]
+    pub servers: Vec<String>,
+    /// Only views, whatever the role allows - for someone who just watches
+    #[serde(default)]
+    pub read_only: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+lazy_static::lazy_static! {
+    /// User id -> token, so requests don't hit the keychain every time
+    static ref TOKEN_CACHE: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+}
+
+pub fn load_users() -> Vec<User> {
+    atomic::read_json(&get_users_path()).unwrap_or_default()
+}
+
+pub fn save_users(users: &[User]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(users).map_err(|e| e.to_string())?;
+    atomic::write_with_backup(&get_users_path(), content).map_err(|e| e.to_string())
+}
+
+pub async fn set_user_token(user_id: &str, token: &str) -> Result<(), String> {
+    secrets::set_secret(&token_key(user_id), token).await?;
+    *TOKEN_CACHE.lock().await = None;
+    Ok(())
+}
+
+pub async fn delete_user_token(user_id: &str) {
+    secrets::delete_secret(&token_key(user_id)).await;
+    *TOKEN_CACHE.lock().await = None;
+}
+
+/// Every user with their token
+pub async fn users_with_tokens() -> Vec<(User, String)> {
+    let users = load_users();
+    let mut cache = TOKEN_CACHE.lock().await;
+    let tokens = cache.get_or_insert_with(HashMap::new);
+
+    let mut result = Vec::with_capacity(users.len());
+    for user in users {
+        if !tokens.contains_key(&user.id) {
+            match secrets::get_secret(&token_key(&user.id)).await {
+                Some(token) => {
+                    tokens.insert(user.id.clone(), token);
+                }
+                None => continue,
+            }
+        }
+        let token = tokens[&user.id].clone();
+        result.push((user, token));
+    }
+    result
+}
+
+fn token_key(user_id: &str) -> String {
+    format!("user-{}", user_id)
+}
+
+fn get_users_path() -> PathBuf {
+    serverwave_core::paths::app_root().join("users.json")
+}
@@ -0,0 +1,75 @@
+// Steam account - the login for dedicated servers SteamCMD can't download anonymously, and
+// spotting SteamCMD's Steam Guard prompt in install output so the user can answer it
+
+use crate::security::secrets;
+
+const USERNAME_KEY: &str = "steam-username";
+const PASSWORD_KEY: &str = "steam-password";
+/// Scripts that read this variable log in with the stored account
+pub const USER_VAR: &str = "STEAM_USER";
+pub const PASSWORD_VAR: &str = "STEAM_PASS";
+
+pub async fn username() -> Option<String> {
+    secrets::get_secret(USERNAME_KEY).await
+}
+
+pub async fn set_account(username: &str, password: &str) -> Result<(), String> {
+    let username = username.trim();
+    if username.is_empty() || password.is_empty() {
+        return Err("Enter both the Steam username and password".to_string());
+    }
+    secrets::set_secret(USERNAME_KEY, username).await?;
+    secrets::set_secret(PASSWORD_KEY, password).await
+}
+
+pub async fn clear_account() {
+    secrets::delete_secret(USERNAME_KEY).await;
+    secrets::delete_secret(PASSWORD_KEY).await;
+}
+
+/// Whether an install script logs in with the stored account
+pub fn wanted_by(script: &str) -> bool {
+    script.contains(USER_VAR)
+}
+
+/// Environment giving an install container the account, None when there's none stored
+pub async fn install_env() -> Option<Vec<String>> {
+    let username = username().await?;
+    let password = secrets::get_secret(PASSWORD_KEY).await?;
+    Some(vec![
+        format!("{}={}", USER_VAR, username),
+        format!("{}={}", PASSWORD_VAR, password),
+    ])
+}
+
+/// SteamCMD waiting for an emailed or authenticator code. It prints the prompt without a
+/// newline and blocks on stdin.
+pub fn is_guard_prompt(line: &str) -> bool {
+    let line = line.trim().to_ascii_lowercase();
+    line.ends_with("steam guard code:") || line.ends_with("two-factor code:")
+}
+
+/// A code is 5 letters or digits, typed as shown
+pub fn validate_code(code: &str) -> Result<&str, String> {
+    let code = code.trim();
+    if code.len() == 5 && code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(code)
+    } else {
+        Err("A Steam Guard code is 5 letters or digits".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_prompts_and_codes() {
+        assert!(is_guard_prompt("Steam Guard code:"));
+        assert!(is_guard_prompt("Logging in user 'someone' to Steam Public...Two-factor code:"));
+        assert!(!is_guard_prompt("Logging in user 'someone' to Steam Public...OK"));
+        assert_eq!(validate_code(" 4FX2K ").unwrap(), "4FX2K");
+        assert!(validate_code("12345678").is_err());
+        assert!(wanted_by("./steamcmd.sh +login ${STEAM_USER} ${STEAM_PASS} +quit"));
+    }
+}
@@ -0,0 +1,196 @@
+// System tray - running server count and quick start/stop, so the window can be closed
+// while servers keep running
+
+use crate::commands::docker::DockerState;
+use crate::commands::games::GamesState;
+use crate::commands::server::{self, Server, ServerState, ServerStatus};
+use tauri::menu::{IsMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+const TRAY_ID: &str = "main";
+const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Create the tray icon and keep its menu in step with the servers
+pub fn build(app: &AppHandle) -> tauri::Result<()> {
+    let mut tray = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Serverwave Anywhere")
+        .menu(&build_menu(app, &[])?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.build(app)?;
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        // Only rebuild when something changed - rebuilding closes an open menu
+        let mut last = String::new();
+        loop {
+            if let Ok(servers) = server::list_servers(handle.state()).await {
+                let summary: String = servers
+                    .iter()
+                    .map(|s| format!("{}:{}:{:?};", s.id, s.name, s.status))
+                    .collect();
+                if summary != last {
+                    last = summary;
+                    refresh(&handle, &servers);
+                }
+            }
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+    Ok(())
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn refresh(app: &AppHandle, servers: &[Server]) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    let running = servers.iter().filter(|s| s.status == ServerStatus::Running).count();
+    let _ = tray.set_tooltip(Some(format!("Serverwave Anywhere - {} running", running)));
+    match build_menu(app, servers) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => tracing::warn!("Failed to build tray menu: {}", e),
+    }
+}
+
+fn build_menu(app: &AppHandle, servers: &[Server]) -> tauri::Result<Menu<Wry>> {
+    let running = servers.iter().filter(|s| s.status == ServerStatus::Running).count();
+
+    let show = MenuItem::with_id(app, "show", "Open Serverwave Anywhere", true, None::<&str>)?;
+    let summary = format!("{} of {} servers running", running, servers.len());
+    let count = MenuItem::new(app, summary, false, None::<&str>)?;
+
+    let mut server_menus = Vec::new();
+    for server in servers {
+        let is_running = matches!(server.status, ServerStatus::Running | ServerStatus::Starting);
+        let busy = matches!(server.status, ServerStatus::Installing | ServerStatus::Stopping);
+        let action = if is_running {
+            MenuItem::with_id(app, format!("stop:{}", server.id), "Stop", !busy, None::<&str>)?
+        } else {
+            MenuItem::with_id(app, format!("start:{}", server.id), "Start", !busy, None::<&str>)?
+        };
+        let open = MenuItem::with_id(app, format!("open:{}", server.id), "Open", true, None::<&str>)?;
+        let label = format!("{} ({})", server.name, status_label(&server.status));
+        server_menus.push(Submenu::with_items(app, label, true, &[&action, &open])?);
+    }
+
+    let stop_all = MenuItem::with_id(app, "stop-all-quit", "Stop All and Quit", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit (servers keep running)", true, None::<&str>)?;
+
+    let (sep_top, sep_bottom) = (PredefinedMenuItem::separator(app)?, PredefinedMenuItem::separator(app)?);
+
+    let mut items: Vec<&dyn IsMenuItem<Wry>> = vec![&show, &sep_top, &count];
+    items.extend(server_menus.iter().map(|m| m as &dyn IsMenuItem<Wry>));
+    items.extend([&sep_bottom as &dyn IsMenuItem<Wry>, &stop_all, &quit]);
+
+    Menu::with_items(app, &items)
+}
+
+fn status_label(status: &ServerStatus) -> &'static str {
+    match status {
+        ServerStatus::Stopped => "stopped",
+        ServerStatus::Starting => "starting",
+        ServerStatus::Installing => "installing",
+        ServerStatus::Running => "running",
+        ServerStatus::Stopping => "stopping",
+        ServerStatus::Error => "error",
+        ServerStatus::CrashLooping => "crash looping",
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref().to_string();
+    let app = app.clone();
+    match id.as_str() {
+        "show" => show_main_window(&app),
+        "quit" => app.exit(0),
+        "stop-all-quit" => {
+            tauri::async_runtime::spawn(async move {
+                stop_all(&app).await;
+                app.exit(0);
+            });
+        }
+        _ => {
+            let Some((action, server_id)) = id.split_once(':') else { return };
+            let server_id = server_id.to_string();
+            match action {
+                "open" => {
+                    show_main_window(&app);
+                    let _ = app.emit("open-server", server_id);
+                }
+                "start" | "stop" => {
+                    let action = action.to_string();
+                    tauri::async_runtime::spawn(async move {
+                        let result = if action == "start" {
+                            server::start_server(
+                                server_id.clone(),
+                                None,
+                                None,
+                                app.clone(),
+                                app.state::<ServerState>(),
+                                app.state::<GamesState>(),
+                                app.state::<DockerState>(),
+                            )
+                            .await
+                        } else {
+                            server::stop_server(
+                                server_id.clone(),
+                                app.clone(),
+                                app.state::<ServerState>(),
+                                app.state::<GamesState>(),
+                                app.state::<DockerState>(),
+                            )
+                            .await
+                        };
+                        if let Err(e) = result {
+                            tracing::error!("Tray {} of {} failed: {}", action, server_id, e);
+                        }
+                        if let Ok(servers) = server::list_servers(app.state()).await {
+                            refresh(&app, &servers);
+                        }
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+async fn stop_all(app: &AppHandle) {
+    let Ok(servers) = server::list_servers(app.state()).await else { return };
+    for server in servers.into_iter().filter(|s| s.status == ServerStatus::Running) {
+        tracing::info!("Stopping {} before quitting", server.name);
+        if let Err(e) = server::stop_server(
+            server.id.clone(),
+            app.clone(),
+            app.state::<ServerState>(),
+            app.state::<GamesState>(),
+            app.state::<DockerState>(),
+        )
+        .await
+        {
+            tracing::error!("Failed to stop {}: {}", server.name, e);
+        }
+    }
+}
@@ -0,0 +1,335 @@
+// Game updates - compare the build a server has installed with the latest one published, in
+// the background every few hours, and update stopped servers when the user wants that
+
+use crate::commands::docker::DockerState;
+use crate::commands::settings::load_app_settings;
+use crate::content::server_jar::{self, Flavor};
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::maintenance::{self, Window};
+use serde::{Deserialize, Serialize};
+use serverwave_core::server::{lifecycle, load_all_server_configs, GameVersion, Server, ServerStatus};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const STEAM_INFO_API: &str = "https://api.steamcmd.net/v1/info";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+/// Give the app a moment to settle before the first check
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(120);
+/// Versions remembered per server
+const MAX_VERSIONS: usize = 20;
+const REPORTED_BUILD: &str = "[Serverwave] Installed build: ";
+const REPORTED_MINECRAFT: &str = "[Serverwave] Installed Minecraft version: ";
+
+lazy_static::lazy_static! {
+    /// Latest result per server
+    static ref CHECKS: Mutex<HashMap<String, UpdateCheck>> = Mutex::new(HashMap::new());
+    /// Servers already told their automatic update waits for the maintenance window
+    static ref HELD: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum UpdateSource {
+    /// A SteamCMD install, compared by the public branch's build id
+    Steam { app_id: String },
+    /// A Paper or Purpur jar, compared by build within its Minecraft version
+    Minecraft { flavor: Flavor, minecraft_version: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheck {
+    pub server_id: String,
+    pub source: UpdateSource,
+    pub installed: String,
+    pub latest: Option<String>,
+    pub update_available: bool,
+    /// The server is pinned to its build, so it isn't flagged or updated
+    pub pinned: bool,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+    /// Why the latest build couldn't be looked up
+    pub error: Option<String>,
+}
+
+/// The value of `"key" "value"` in a Steam .acf file
+fn acf_value(content: &str, key: &str) -> Option<String> {
+    let quoted = format!("\"{}\"", key);
+    content.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(&quoted)?;
+        Some(rest.trim().trim_matches('"').to_string())
+    })
+}
+
+/// App id and build id of the game SteamCMD installed into the data folder
+fn installed_steam_build(data_path: &Path) -> Option<(String, String)> {
+    let entries = std::fs::read_dir(data_path.join("steamapps")).ok()?;
+    entries.flatten().find_map(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with("appmanifest_") || !name.ends_with(".acf") {
+            return None;
+        }
+        let content = std::fs::read_to_string(entry.path()).ok()?;
+        Some((acf_value(&content, "appid")?, acf_value(&content, "buildid")?))
+    })
+}
+
+/// Flavor, Minecraft version and build from Paper's version_history.json. Older builds write
+/// "git-Paper-196 (MC: 1.20.1)", newer ones "1.21.4-211-main@3e8e8d5 (2025-03-02T...)".
+fn parse_paper_version(current: &str) -> Option<(Flavor, String, String)> {
+    if let Some(rest) = current.strip_prefix("git-") {
+        let (name, rest) = rest.split_once('-')?;
+        let flavor = match name {
+            "Paper" => Flavor::Paper,
+            "Purpur" => Flavor::Purpur,
+            _ => return None,
+        };
+        let (build, rest) = rest.split_once(' ')?;
+        let version = rest.strip_prefix("(MC: ")?.trim_end_matches(')');
+        return Some((flavor, version.to_string(), build.to_string()));
+    }
+    let mut parts = current.split(['-', ' ']);
+    let version = parts.next()?;
+    let build = parts.next()?;
+    build.parse::<u32>().ok()?;
+    Some((Flavor::Paper, version.to_string(), build.to_string()))
+}
+
+fn installed_paper_build(data_path: &Path) -> Option<(Flavor, String, String)> {
+    let content = std::fs::read_to_string(data_path.join("version_history.json")).ok()?;
+    let history: serde_json::Value = serde_json::from_str(&content).ok()?;
+    parse_paper_version(history.get("currentVersion")?.as_str()?)
+}
+
+/// What the server has installed, None for games there's no way to check
+pub fn installed(data_path: &Path) -> Option<(UpdateSource, String)> {
+    if let Some((app_id, build)) = installed_steam_build(data_path) {
+        return Some((UpdateSource::Steam { app_id }, build));
+    }
+    let (flavor, minecraft_version, build) = installed_paper_build(data_path)?;
+    Some((UpdateSource::Minecraft { flavor, minecraft_version }, build))
+}
+
+/// What the install script said it installed. Paper only writes version_history.json once
+/// the server starts, so its script reports the build itself.
+#[derive(Debug, Clone, Default)]
+pub struct VersionReport {
+    build: Option<String>,
+    minecraft_version: Option<String>,
+}
+
+impl VersionReport {
+    pub fn observe(&mut self, line: &str) {
+        if let Some(build) = line.strip_prefix(REPORTED_BUILD) {
+            self.build = Some(build.trim().to_string());
+        } else if let Some(version) = line.strip_prefix(REPORTED_MINECRAFT) {
+            self.minecraft_version = Some(version.trim().to_string());
+        }
+    }
+}
+
+/// The build an install just put in place - the script's report, else what's on disk
+pub fn installed_version(data_path: &Path, report: &VersionReport) -> Option<GameVersion> {
+    let (build, minecraft_version) = match &report.build {
+        Some(build) => (build.clone(), report.minecraft_version.clone()),
+        None => match installed(data_path)? {
+            (UpdateSource::Steam { .. }, build) => (build, None),
+            (UpdateSource::Minecraft { minecraft_version, .. }, build) => (build, Some(minecraft_version)),
+        },
+    };
+    Some(GameVersion { build, minecraft_version, installed_at: chrono::Utc::now() })
+}
+
+/// Add a build to the server's history unless it's the one already last in it
+pub fn record(server: &mut Server, version: GameVersion) {
+    if server.versions.last().is_some_and(|last| last.same_build(&version)) {
+        return;
+    }
+    server.versions.push(version);
+    let excess = server.versions.len().saturating_sub(MAX_VERSIONS);
+    server.versions.drain(..excess);
+}
+
+/// Environment making the install script fetch a specific build. The Paper script reads
+/// MINECRAFT_VERSION and BUILD_NUMBER; custom scripts can use SERVERWAVE_TARGET_BUILD.
+pub fn target_env(version: &GameVersion) -> Vec<String> {
+    let mut env = vec![format!("SERVERWAVE_TARGET_BUILD={}", version.build)];
+    if let Some(minecraft_version) = &version.minecraft_version {
+        env.push(format!("MINECRAFT_VERSION={}", minecraft_version));
+        env.push(format!("BUILD_NUMBER={}", version.build));
+    }
+    env
+}
+
+async fn latest_steam_build(app_id: &str) -> Result<String, String> {
+    let response = crate::content::http_client()
+        .get(format!("{}/{}", STEAM_INFO_API, app_id))
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Steam info unreachable: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam info returned {}", response.status()));
+    }
+    let info: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    info.pointer(&format!("/data/{}/depots/branches/public/buildid", app_id))
+        .and_then(|b| b.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| format!("No public build listed for app {}", app_id))
+}
+
+async fn latest(source: &UpdateSource) -> Result<String, String> {
+    match source {
+        UpdateSource::Steam { app_id } => latest_steam_build(app_id).await,
+        UpdateSource::Minecraft { flavor, minecraft_version } => {
+            Ok(server_jar::resolve(*flavor, minecraft_version).await?.build)
+        }
+    }
+}
+
+/// Look up the latest build for a server and remember the result. None when the server's
+/// game can't be checked.
+pub async fn check(server: &Server) -> Option<UpdateCheck> {
+    let (source, installed) = installed(&server.data_path)?;
+    let (latest, error) = match latest(&source).await {
+        Ok(latest) => (Some(latest), None),
+        Err(e) => (None, Some(e)),
+    };
+    let pinned = server.pinned_version.is_some();
+    let check = UpdateCheck {
+        server_id: server.id.clone(),
+        update_available: !pinned && latest.as_ref().is_some_and(|latest| *latest != installed),
+        pinned,
+        source,
+        installed,
+        latest,
+        checked_at: chrono::Utc::now(),
+        error,
+    };
+    CHECKS.lock().unwrap().insert(server.id.clone(), check.clone());
+    Some(check)
+}
+
+/// Servers the last checks found behind
+pub fn pending() -> Vec<UpdateCheck> {
+    CHECKS.lock().unwrap().values().filter(|c| c.update_available).cloned().collect()
+}
+
+pub fn forget(server_id: &str) {
+    CHECKS.lock().unwrap().remove(server_id);
+    HELD.lock().unwrap().remove(server_id);
+}
+
+/// Check every installed server on the interval from the settings
+pub fn spawn_checker(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_CHECK_DELAY).await;
+        loop {
+            let settings = load_app_settings().updates;
+            if settings.check {
+                check_all(&app, settings.auto_update).await;
+            }
+            tokio::time::sleep(Duration::from_secs(u64::from(settings.interval_hours.max(1)) * 3600)).await;
+        }
+    });
+}
+
+async fn check_all(app: &AppHandle, auto_update: bool) {
+    for server in load_all_server_configs().into_iter().filter(|s| s.installed) {
+        let already_flagged = CHECKS.lock().unwrap().get(&server.id).is_some_and(|c| c.update_available);
+        let Some(check) = check(&server).await else { continue };
+        if !check.update_available {
+            continue;
+        }
+        let latest = check.latest.clone().unwrap_or_default();
+
+        let stopped = match app.state::<DockerState>().manager() {
+            Ok(docker) => matches!(lifecycle::current_status(&docker, &server).await, Ok(ServerStatus::Stopped)),
+            Err(_) => false,
+        };
+        // Updated at the first check once the window opens - said once, not at every check
+        let window = match auto_update && stopped {
+            true => maintenance::window(&server),
+            false => Window::Open,
+        };
+        let held = match window {
+            Window::Closed { opens } => {
+                if HELD.lock().unwrap().insert(server.id.clone()) {
+                    maintenance::report_held(app, &server.id, "Automatic update", opens);
+                }
+                true
+            }
+            Window::Open => {
+                HELD.lock().unwrap().remove(&server.id);
+                false
+            }
+        };
+        if auto_update && stopped && !held {
+            tracing::info!("Updating {} from build {} to {}", server.id, check.installed, latest);
+            events::emit(app, ServerEvent::log(
+                &server.id,
+                format!("[Serverwave] Updating automatically from build {} to {}...", check.installed, latest),
+            ));
+            let result = crate::commands::server::update_server_game(
+                server.id.clone(),
+                app.clone(),
+                app.state(),
+                app.state(),
+                app.state(),
+            )
+            .await;
+            match result {
+                Ok(_) => forget(&server.id),
+                Err(e) => tracing::warn!("Automatic update of {} failed: {}", server.id, e),
+            }
+        } else if !already_flagged {
+            events::emit(app, ServerEvent::alert(
+                &server.id,
+                AlertLevel::Warning,
+                format!("Game update available: build {} is installed, {} is out", check.installed, latest),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_steam_manifest_and_paper_versions() {
+        let acf = "\"AppState\"\n{\n\t\"appid\"\t\t\"258550\"\n\t\"buildid\"\t\t\"16512345\"\n}\n";
+        assert_eq!(acf_value(acf, "buildid").as_deref(), Some("16512345"));
+        assert_eq!(acf_value(acf, "appid").as_deref(), Some("258550"));
+
+        assert_eq!(
+            parse_paper_version("git-Paper-196 (MC: 1.20.1)"),
+            Some((Flavor::Paper, "1.20.1".to_string(), "196".to_string()))
+        );
+        assert_eq!(
+            parse_paper_version("git-Purpur-2095 (MC: 1.20.1)"),
+            Some((Flavor::Purpur, "1.20.1".to_string(), "2095".to_string()))
+        );
+        assert_eq!(
+            parse_paper_version("1.21.4-211-main@3e8e8d5 (2025-03-02T18:41:19Z)"),
+            Some((Flavor::Paper, "1.21.4".to_string(), "211".to_string()))
+        );
+        assert_eq!(parse_paper_version("git-Spigot-abc (MC: 1.20.1)"), None);
+    }
+
+    #[test]
+    fn test_reported_versions_are_recorded_once() {
+        let mut report = VersionReport::default();
+        report.observe("[Serverwave] Installed Minecraft version: 1.20.1");
+        report.observe("[Serverwave] Installed build: 196");
+        let version = installed_version(Path::new("/nonexistent"), &report).unwrap();
+        assert_eq!(version.label(), "1.20.1 build 196");
+        assert!(target_env(&version).contains(&"BUILD_NUMBER=196".to_string()));
+
+        let mut server = Server::test_fixture("ab12cd34");
+        record(&mut server, version.clone());
+        record(&mut server, version);
+        assert_eq!(server.versions.len(), 1);
+    }
+}
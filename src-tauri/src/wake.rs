@@ -0,0 +1,99 @@
+// Host wake - waking a Docker host that sleeps between play sessions with Wake-on-LAN and
+// waiting for Docker on it to answer, and the servers started at a time of day by
+// `spawn_scheduler`, which wakes the host first.
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::{start_server, ServerState};
+use crate::docker::connection;
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use chrono::{Local, Utc};
+use serverwave_core::server::{lifecycle, load_all_server_configs, load_server_config, ServerStatus};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Time between checks whether Docker answers, and how long each may take
+const POLL: Duration = Duration::from_secs(5);
+const TICK: Duration = Duration::from_secs(60);
+
+/// Whether Docker answers within a poll - a sleeping host may not refuse, only stay silent
+async fn docker_answers(app: &AppHandle) -> bool {
+    let Ok(docker) = app.state::<DockerState>().manager() else { return false };
+    matches!(tokio::time::timeout(POLL, docker.ping()).await, Ok(Ok(())))
+}
+
+/// Wake the Docker host and wait for Docker on it to answer. Nothing is sent when it already
+/// does.
+pub async fn wake_host(app: &AppHandle) -> Result<(), AppError> {
+    let wake = connection::current()
+        .wake
+        .ok_or_else(|| AppError::InvalidInput("Wake-on-LAN isn't set up for the Docker host".into()))?;
+    if docker_answers(app).await {
+        return Ok(());
+    }
+
+    tracing::info!("Waking the Docker host {}", wake.mac);
+    wake.send()?;
+    let deadline = Instant::now() + Duration::from_secs(wake.boot_secs);
+    while Instant::now() < deadline {
+        tokio::time::sleep(POLL).await;
+        // The client may hold connections from before the host slept
+        app.state::<DockerState>().reset();
+        if docker_answers(app).await {
+            tracing::info!("The Docker host is awake");
+            return Ok(());
+        }
+    }
+    Err(format!("The Docker host didn't answer within {} seconds of being woken", wake.boot_secs).into())
+}
+
+/// Start the servers whose start time has come, once a minute
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            run_due(&app).await;
+        }
+    });
+}
+
+async fn run_due(app: &AppHandle) {
+    for server in load_all_server_configs() {
+        let Some(due) = server.start_schedule.filter(|s| s.enabled).and_then(|s| s.next_run) else { continue };
+        let now = Utc::now();
+        if due > now {
+            continue;
+        }
+        // Moved on before starting, so a slow wake isn't started again on the next tick
+        let updated = app.state::<ServerState>().configs.update(&server.id, |s| {
+            if let Some(schedule) = s.start_schedule.as_mut() {
+                schedule.last_run = Some(now);
+                schedule.next_run = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+            }
+        });
+        if let Err(e) = updated {
+            tracing::warn!("Failed to save the start schedule of {}: {}", server.id, e);
+        }
+
+        tracing::info!("Running scheduled start of {}", server.id);
+        if let Err(e) = start_on_schedule(app, &server.id).await {
+            events::emit(app, ServerEvent::alert(&server.id, AlertLevel::Error, format!("Scheduled start failed: {}", e)));
+        }
+    }
+}
+
+async fn start_on_schedule(app: &AppHandle, server_id: &str) -> Result<(), AppError> {
+    if connection::current().wake.is_some() && !docker_answers(app).await {
+        events::emit(app, ServerEvent::log(server_id, "[Serverwave] Waking the Docker host for the scheduled start..."));
+        wake_host(app).await?;
+    }
+    let docker = app.state::<DockerState>().manager()?;
+    let server = load_server_config(server_id)?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if matches!(status, ServerStatus::Running | ServerStatus::Starting | ServerStatus::Installing) {
+        return Ok(());
+    }
+    start_server(server_id.to_string(), None, None, app.clone(), app.state(), app.state(), app.state()).await?;
+    events::emit(app, ServerEvent::log(server_id, "[Serverwave] Started on schedule"));
+    Ok(())
+}
@@ -0,0 +1,364 @@
+// Rust wipes - delete a Rust server's map (and blueprints, for a full wipe), roll a new seed
+// and put the wipe date in the hostname, now or on a weekly, biweekly or monthly schedule.
+// wipes.json in the config folder holds the schedules.
+
+use crate::commands::docker::DockerState;
+use crate::commands::server::{start_server, stop_server, ServerState};
+use crate::error::AppError;
+use crate::events::{self, AlertLevel, ServerEvent};
+use crate::maintenance::{self, Window};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use serverwave_core::atomic;
+use serverwave_core::server::{lifecycle, load_server_config, Server, ServerStatus};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+pub const RUST_GAME: &str = "rust";
+const SEED_VAR: &str = "WORLD_SEED";
+const HOSTNAME_VAR: &str = "HOSTNAME";
+const HOSTNAME_MARKER: &str = " | Wiped ";
+const TICK: Duration = Duration::from_secs(60);
+/// A wipe the app wasn't running for is skipped when it's later than this, not run late
+const MISSED_AFTER: ChronoDuration = ChronoDuration::hours(1);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WipeKind {
+    /// The map and its saves - players keep their blueprints
+    Map,
+    /// The map and every player's blueprints
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WipeFrequency {
+    Weekly,
+    Biweekly,
+    /// The first `weekday` of the month, like Facepunch's forced wipe on the first Thursday
+    Monthly,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WipeSchedule {
+    #[serde(default)]
+    pub server_id: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub kind: WipeKind,
+    pub frequency: WipeFrequency,
+    pub weekday: Weekday,
+    /// Local time of day
+    pub hour: u32,
+    pub minute: u32,
+    /// Roll a new WORLD_SEED so the map changes too
+    #[serde(default = "default_enabled")]
+    pub new_seed: bool,
+    /// End the hostname with " | Wiped <date>"
+    #[serde(default)]
+    pub hostname_date: bool,
+    #[serde(default)]
+    pub last_wipe: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub next_wipe: Option<DateTime<Utc>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WipeResult {
+    /// Files deleted, relative to the data folder
+    pub deleted: Vec<String>,
+    /// The new seed, when it was rolled
+    pub seed: Option<String>,
+    /// The new hostname, when it changed
+    pub hostname: Option<String>,
+}
+
+impl WipeSchedule {
+    /// `date` at this schedule's time of day in `tz`
+    fn at<Tz: TimeZone>(&self, tz: &Tz, date: NaiveDate) -> Option<DateTime<Tz>> {
+        tz.from_local_datetime(&date.and_hms_opt(self.hour, self.minute, 0)?).earliest()
+    }
+
+    /// The first wipe after `after`, in its timezone
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let today = after.date_naive();
+        match self.frequency {
+            WipeFrequency::Weekly | WipeFrequency::Biweekly => (0..=7)
+                .filter_map(|days| today.checked_add_signed(ChronoDuration::days(days)))
+                .filter(|date| date.weekday() == self.weekday)
+                .filter_map(|date| self.at(&tz, date))
+                .find(|time| time > after),
+            WipeFrequency::Monthly => (0..=2)
+                .filter_map(|months| {
+                    let month0 = today.month0() + months;
+                    let year = today.year() + (month0 / 12) as i32;
+                    NaiveDate::from_weekday_of_month_opt(year, month0 % 12 + 1, self.weekday, 1)
+                })
+                .filter_map(|date| self.at(&tz, date))
+                .find(|time| time > after),
+        }
+    }
+
+    /// The next wipe from `now`, two weeks on from the last one for a biweekly schedule
+    pub fn next_from<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let mut from = now.clone();
+        if let Some(last) = self.last_wipe.map(|t| t.with_timezone(&now.timezone())) {
+            if self.frequency == WipeFrequency::Biweekly {
+                from = from.max(last.clone() + ChronoDuration::days(13));
+            }
+            from = from.max(last);
+        }
+        self.next_after(&from)
+    }
+}
+
+/// Whether a save file goes in a wipe of this kind
+fn wiped_file(name: &str, kind: WipeKind) -> bool {
+    let map = name.ends_with(".map")
+        || name.ends_with(".sav")
+        || name.contains(".sav.")
+        || name.starts_with("sv.files.");
+    map || (kind == WipeKind::Full && name.starts_with("player.blueprints."))
+}
+
+/// Delete the wiped files from every server identity (server/<identity>) in the data folder
+fn delete_files(data_path: &Path, kind: WipeKind) -> std::io::Result<Vec<String>> {
+    let mut deleted = Vec::new();
+    let identities = match std::fs::read_dir(data_path.join("server")) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(deleted),
+        Err(e) => return Err(e),
+    };
+    for identity in identities.flatten().filter(|e| e.path().is_dir()) {
+        for file in std::fs::read_dir(identity.path())?.flatten() {
+            let name = file.file_name().to_string_lossy().to_string();
+            if file.path().is_file() && wiped_file(&name, kind) {
+                std::fs::remove_file(file.path())?;
+                let relative = file.path().strip_prefix(data_path).map(Path::to_path_buf).unwrap_or(file.path());
+                deleted.push(relative.to_string_lossy().to_string());
+            }
+        }
+    }
+    Ok(deleted)
+}
+
+/// `hostname` with the wipe date at the end, replacing an earlier one
+fn dated_hostname(hostname: &str, date: &str) -> String {
+    let base = hostname.split(HOSTNAME_MARKER).next().unwrap_or(hostname).trim_end();
+    format!("{}{}{}", base, HOSTNAME_MARKER, date)
+}
+
+fn random_seed() -> String {
+    // Rust takes a positive 32-bit seed
+    (Uuid::new_v4().as_u128() % 2_147_483_646 + 1).to_string()
+}
+
+pub fn rust_server(server_id: &str) -> Result<Server, AppError> {
+    let server = load_server_config(server_id)?;
+    if server.game_type.0 != RUST_GAME {
+        return Err(AppError::InvalidInput(format!("'{}' isn't a Rust server", server.name)));
+    }
+    Ok(server)
+}
+
+/// Wipe a Rust server. A running server is stopped for it and started again after.
+pub async fn wipe(
+    app: &AppHandle,
+    server_id: &str,
+    kind: WipeKind,
+    new_seed: bool,
+    hostname_date: bool,
+) -> Result<WipeResult, AppError> {
+    let server = rust_server(server_id)?;
+    let docker = app.state::<DockerState>().manager()?;
+    let status = lifecycle::current_status(&docker, &server).await?;
+    if status == ServerStatus::Installing {
+        return Err(AppError::InvalidInput("Wait for the install to finish before wiping".into()));
+    }
+    let was_running = matches!(status, ServerStatus::Running | ServerStatus::Starting);
+    if was_running {
+        events::emit(app, ServerEvent::log(server_id, "[Serverwave] Stopping for the wipe..."));
+        stop_server(server_id.to_string(), app.clone(), app.state(), app.state(), app.state()).await?;
+    }
+
+    let data_path = server.data_path.clone();
+    let deleted = tokio::task::spawn_blocking(move || delete_files(&data_path, kind)).await??;
+
+    let mut result = WipeResult { deleted, seed: None, hostname: None };
+    app.state::<ServerState>().configs.update(server_id, |server| {
+        if new_seed {
+            let seed = random_seed();
+            server.config.insert(SEED_VAR.to_string(), seed.clone());
+            result.seed = Some(seed);
+        }
+        if hostname_date {
+            let current = server.config.get(HOSTNAME_VAR).cloned().unwrap_or_default();
+            let hostname = dated_hostname(&current, &Local::now().format("%b %-d").to_string());
+            server.config.insert(HOSTNAME_VAR.to_string(), hostname.clone());
+            result.hostname = Some(hostname);
+        }
+        if (result.seed.is_some() || result.hostname.is_some()) && server.container_id.is_some() {
+            server.config_outdated = true;
+        }
+    })?;
+
+    let what = match kind {
+        WipeKind::Map => "Map",
+        WipeKind::Full => "Map and blueprints",
+    };
+    events::emit(app, ServerEvent::log(
+        server_id,
+        format!("[Serverwave] {} wiped - {} files deleted", what, result.deleted.len()),
+    ));
+    if was_running {
+        start_server(server_id.to_string(), Some(true), None, app.clone(), app.state(), app.state(), app.state()).await?;
+    }
+    Ok(result)
+}
+
+pub fn load_schedules() -> Vec<WipeSchedule> {
+    atomic::read_json(&get_schedules_path()).unwrap_or_default()
+}
+
+pub fn save_schedules(schedules: &[WipeSchedule]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(schedules).map_err(|e| e.to_string())?;
+    atomic::write_with_backup(&get_schedules_path(), content).map_err(|e| e.to_string())
+}
+
+/// Drop a deleted server's schedule
+pub fn forget(server_id: &str) {
+    let mut schedules = load_schedules();
+    let before = schedules.len();
+    schedules.retain(|s| s.server_id != server_id);
+    if schedules.len() != before {
+        if let Err(e) = save_schedules(&schedules) {
+            tracing::warn!("Failed to save wipe schedules: {}", e);
+        }
+    }
+}
+
+/// Run the wipes that are due, once a minute
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            run_due(&app).await;
+        }
+    });
+}
+
+async fn run_due(app: &AppHandle) {
+    let now = Utc::now();
+    let mut schedules = load_schedules();
+    let mut changed = false;
+    for schedule in schedules.iter_mut().filter(|s| s.enabled) {
+        let Some(due) = schedule.next_wipe.filter(|due| *due <= now) else { continue };
+        changed = true;
+        if now - due > MISSED_AFTER {
+            events::emit(app, ServerEvent::alert(
+                &schedule.server_id,
+                AlertLevel::Warning,
+                format!("The wipe planned for {} was skipped - the app wasn't running", due.with_timezone(&Local).format("%b %-d %H:%M")),
+            ));
+        } else if let Window::Closed { opens } = wipe_window(&schedule.server_id) {
+            maintenance::report_held(app, &schedule.server_id, "Scheduled wipe", opens);
+            if opens.is_some() {
+                schedule.next_wipe = opens;
+                continue;
+            }
+        } else {
+            tracing::info!("Running scheduled wipe of {}", schedule.server_id);
+            match wipe(app, &schedule.server_id, schedule.kind, schedule.new_seed, schedule.hostname_date).await {
+                Ok(_) => schedule.last_wipe = Some(now),
+                Err(e) => events::emit(app, ServerEvent::alert(
+                    &schedule.server_id,
+                    AlertLevel::Error,
+                    format!("Scheduled wipe failed: {}", e),
+                )),
+            }
+        }
+        schedule.next_wipe = schedule.next_from(&Local::now()).map(|t| t.with_timezone(&Utc));
+    }
+    if changed {
+        if let Err(e) = save_schedules(&schedules) {
+            tracing::warn!("Failed to save wipe schedules: {}", e);
+        }
+    }
+}
+
+/// A server that can't be loaded is left for `wipe` to report
+fn wipe_window(server_id: &str) -> Window {
+    load_server_config(server_id).map(|server| maintenance::window(&server)).unwrap_or(Window::Open)
+}
+
+fn get_schedules_path() -> PathBuf {
+    serverwave_core::paths::config_dir().join("wipes.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(frequency: WipeFrequency) -> WipeSchedule {
+        WipeSchedule {
+            server_id: "ab12cd34".into(),
+            enabled: true,
+            kind: WipeKind::Map,
+            frequency,
+            weekday: Weekday::Thu,
+            hour: 19,
+            minute: 0,
+            new_seed: true,
+            hostname_date: false,
+            last_wipe: None,
+            next_wipe: None,
+        }
+    }
+
+    #[test]
+    fn test_next_wipe_follows_frequency() {
+        // Wednesday 2026-10-14
+        let now = Utc.with_ymd_and_hms(2026, 10, 14, 12, 0, 0).unwrap();
+        let thursday = Utc.with_ymd_and_hms(2026, 10, 15, 19, 0, 0).unwrap();
+        assert_eq!(schedule(WipeFrequency::Weekly).next_from(&now), Some(thursday));
+
+        let mut biweekly = schedule(WipeFrequency::Biweekly);
+        biweekly.last_wipe = Some(Utc.with_ymd_and_hms(2026, 10, 8, 19, 0, 0).unwrap());
+        assert_eq!(biweekly.next_from(&now), Some(Utc.with_ymd_and_hms(2026, 10, 22, 19, 0, 0).unwrap()));
+
+        // First Thursday of October has passed - November's is next
+        let monthly = schedule(WipeFrequency::Monthly);
+        assert_eq!(monthly.next_from(&now), Some(Utc.with_ymd_and_hms(2026, 11, 5, 19, 0, 0).unwrap()));
+        let december = Utc.with_ymd_and_hms(2026, 12, 20, 0, 0, 0).unwrap();
+        assert_eq!(monthly.next_from(&december), Some(Utc.with_ymd_and_hms(2027, 1, 7, 19, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_wipe_deletes_map_and_keeps_blueprints() {
+        let dir = std::env::temp_dir().join(format!("serverwave-wipe-{}", Uuid::new_v4()));
+        let identity = dir.join("server/rust");
+        std::fs::create_dir_all(&identity).unwrap();
+        for name in ["proceduralmap.3000.42.256.map", "proceduralmap.3000.42.256.sav", "proceduralmap.3000.42.256.sav.1",
+            "sv.files.256.db", "player.blueprints.5.db", "cfg"] {
+            std::fs::write(identity.join(name), "").unwrap();
+        }
+
+        let deleted = delete_files(&dir, WipeKind::Map).unwrap();
+        assert_eq!(deleted.len(), 4);
+        assert!(identity.join("player.blueprints.5.db").exists());
+        let deleted = delete_files(&dir, WipeKind::Full).unwrap();
+        assert_eq!(deleted, vec![Path::new("server/rust/player.blueprints.5.db").to_string_lossy().to_string()]);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(dated_hostname("My Server | Wiped Oct 8", "Oct 15"), "My Server | Wiped Oct 15");
+        assert_eq!(dated_hostname("My Server", "Oct 15"), "My Server | Wiped Oct 15");
+    }
+}
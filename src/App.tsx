@@ -1,5 +1,5 @@
 import { useEffect } from 'react';
-import { BrowserRouter, Routes, Route, Navigate } from 'react-router-dom';
+import { BrowserRouter, Routes, Route, Navigate, useNavigate } from 'react-router-dom';
 import { useDockerStore } from './stores/dockerStore';
 import { useGamesStore } from './stores/gamesStore';
 import { useServerStore } from './stores/serverStore';
@@ -8,12 +8,70 @@ import { Sidebar } from './components/Sidebar';
 import { Home } from './pages/Home';
 import { Servers } from './pages/Servers';
 import { CreateServer } from './pages/CreateServer';
+import { ImportServer } from './pages/ImportServer';
 import { ServerDetail } from './pages/ServerDetail';
 import { GamesPage } from './pages/Games';
 import { Settings } from './pages/Settings';
 import { DockerRequired } from './components/DockerRequired';
+import { CapacityWarningDialog } from './components/CapacityWarningDialog';
+import { PortWarningDialog } from './components/PortWarningDialog';
+import { ReadOnlyBanner } from './components/ReadOnlyMode';
+import { getRemoteBackend, invoke, listen } from './utils/backend';
+import type { AppNotification, ServerStateChanged } from './types';
 import './App.css';
 
+/** Opens a server when the tray or a serverwave:// link asks for it */
+function OpenServerListener() {
+  const navigate = useNavigate();
+
+  useEffect(() => {
+    // A serverwave://server/<id> link the app was launched with
+    if (!getRemoteBackend()) {
+      invoke<string | null>('take_pending_link')
+        .then((serverId) => serverId && navigate(`/servers/${serverId}`))
+        .catch(() => {});
+    }
+    const unlisten = listen<string>('open-server', (event) => navigate(`/servers/${event.payload}`));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [navigate]);
+
+  return null;
+}
+
+/** Shows the backend's notifications on the desktop, once the user allowed them */
+function NotificationListener() {
+  useEffect(() => {
+    if (!('Notification' in window)) return;
+    if (Notification.permission === 'default') Notification.requestPermission();
+    const unlisten = listen<AppNotification>('notification', (event) => {
+      if (Notification.permission === 'granted') new Notification(event.payload.title, { body: event.payload.body });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return null;
+}
+
+/** Keeps server statuses current as Docker reports containers starting and stopping */
+function ServerStateListener() {
+  const setServerStatus = useServerStore((s) => s.setServerStatus);
+
+  useEffect(() => {
+    const unlisten = listen<ServerStateChanged>('server-state-changed', (event) =>
+      setServerStatus(event.payload.server_id, event.payload.status),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [setServerStatus]);
+
+  return null;
+}
+
 function App() {
   const { status, checkStatus } = useDockerStore();
   const { fetchGames } = useGamesStore();
@@ -45,8 +103,14 @@ function App() {
 
   return (
     <BrowserRouter>
+      <OpenServerListener />
+      <NotificationListener />
+      <ServerStateListener />
+      <CapacityWarningDialog />
+      <PortWarningDialog />
       <div className="h-screen flex flex-col bg-slate-900">
         <TitleBar />
+        <ReadOnlyBanner />
         <div className="flex flex-1 overflow-hidden">
           <Sidebar />
           <main className="main-content">
@@ -54,6 +118,7 @@ function App() {
               <Route path="/" element={<Home />} />
               <Route path="/servers" element={<Servers />} />
               <Route path="/servers/create" element={<CreateServer />} />
+              <Route path="/servers/import" element={<ImportServer />} />
               <Route path="/servers/:id" element={<ServerDetail />} />
               <Route path="/games" element={<GamesPage />} />
               <Route path="/settings" element={<Settings />} />
@@ -0,0 +1,67 @@
+import { useEffect, useState } from 'react';
+import { RefreshCw } from 'lucide-react';
+import { invoke } from '../utils/backend';
+
+const LEVELS = ['', 'ERROR', 'WARN', 'INFO'];
+
+export function AppLogs() {
+  const [lines, setLines] = useState<string[]>([]);
+  const [filter, setFilter] = useState('');
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async (query = filter) => {
+    setIsLoading(true);
+    setError(null);
+    try {
+      setLines(await invoke<string[]>('get_app_logs', { filter: query.trim() || null, lines: 300 }));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    load('');
+  }, []);
+
+  return (
+    <section className="card mb-6">
+      <div className="flex items-center justify-between mb-4">
+        <h2 className="text-xl font-semibold">App Logs</h2>
+        <button onClick={() => load()} disabled={isLoading} className="btn btn-secondary text-sm">
+          <RefreshCw size={16} className={isLoading ? 'animate-spin' : ''} />
+        </button>
+      </div>
+      <div className="flex gap-2 mb-3">
+        <select
+          value={LEVELS.includes(filter) ? filter : ''}
+          onChange={(e) => {
+            setFilter(e.target.value);
+            load(e.target.value);
+          }}
+          className="input w-32"
+        >
+          {LEVELS.map((level) => (
+            <option key={level} value={level}>
+              {level || 'All'}
+            </option>
+          ))}
+        </select>
+        <input
+          type="text"
+          value={filter}
+          onChange={(e) => setFilter(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && load()}
+          placeholder="Filter, e.g. docker or a server id"
+          className="input flex-1"
+        />
+      </div>
+      {error && <p className="text-sm text-red-400 mb-2">{error}</p>}
+      <pre className="bg-slate-950 rounded-lg p-3 text-xs text-slate-300 font-mono h-64 overflow-auto whitespace-pre-wrap">
+        {lines.length > 0 ? lines.join('\n') : 'No log lines'}
+      </pre>
+    </section>
+  );
+}
@@ -0,0 +1,89 @@
+import { useState } from 'react';
+import { Download, Upload } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import { useGamesStore } from '../stores/gamesStore';
+import type { StateImport } from '../types';
+
+/** Export the panel's setup to one file, or bring one in from another machine */
+export function AppStateSettings() {
+  const fetchServers = useServerStore((s) => s.fetchServers);
+  const fetchGames = useGamesStore((s) => s.fetchGames);
+  const [dest, setDest] = useState('');
+  const [source, setSource] = useState('');
+  const [busy, setBusy] = useState<'export' | 'import' | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async (key: 'export' | 'import', action: () => Promise<string>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const exportState = () =>
+    run('export', async () => {
+      const path = await invoke<string>('export_app_state', { dest: dest.trim() || null });
+      return `Exported to ${path}`;
+    });
+
+  const importState = () =>
+    run('import', async () => {
+      const result = await invoke<StateImport>('import_app_state', { path: source.trim() });
+      await Promise.all([fetchServers(), fetchGames()]);
+      const parts = [`Imported ${result.servers.length} servers and ${result.custom_games.length} custom games.`];
+      if (result.skipped_servers.length) parts.push(`Already here: ${result.skipped_servers.join(', ')}.`);
+      if (result.needs_install.length) parts.push(`Install again (no data on this machine): ${result.needs_install.join(', ')}.`);
+      return parts.join(' ');
+    });
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Panel Backup</h2>
+      <p className="text-sm text-slate-400 mb-4">
+        Settings, servers, custom games, wipe schedules and hooks in one file - to move the panel to another machine
+        or keep a copy of the setup. Server files, users and linked nodes aren't included.
+      </p>
+      <div className="space-y-3 text-sm">
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={dest}
+            onChange={(e) => setDest(e.target.value)}
+            placeholder="Save to (optional) - a file path, otherwise the app folder's exports/"
+            className="input flex-1"
+          />
+          <button onClick={exportState} disabled={busy !== null} className="btn btn-secondary text-sm">
+            <Download size={16} className={busy === 'export' ? 'animate-pulse' : ''} />
+            Export
+          </button>
+        </div>
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={source}
+            onChange={(e) => setSource(e.target.value)}
+            placeholder="State file to import"
+            className="input flex-1"
+          />
+          <button onClick={importState} disabled={busy !== null || !source.trim()} className="btn btn-secondary text-sm">
+            <Upload size={16} className={busy === 'import' ? 'animate-pulse' : ''} />
+            Import
+          </button>
+        </div>
+      </div>
+      <p className="text-xs text-slate-500 mt-2">
+        Importing adds servers and games this panel doesn't have yet and replaces the settings, except the data
+        location and Docker connection.
+      </p>
+      {message && <p className="text-sm text-green-400 mt-2 break-all">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
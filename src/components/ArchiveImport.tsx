@@ -0,0 +1,60 @@
+import { useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { FileArchive, Loader2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import { useGamesStore } from '../stores/gamesStore';
+import type { ServerResponse } from '../types';
+
+/** Bring in a server exported from Serverwave on another machine */
+export function ArchiveImport({ onError }: { onError: (error: string | null) => void }) {
+  const navigate = useNavigate();
+  const fetchServers = useServerStore((s) => s.fetchServers);
+  const fetchGames = useGamesStore((s) => s.fetchGames);
+  const [path, setPath] = useState('');
+  const [name, setName] = useState('');
+  const [busy, setBusy] = useState(false);
+
+  const runImport = async () => {
+    onError(null);
+    setBusy(true);
+    try {
+      const response = await invoke<ServerResponse>('import_server', { path: path.trim(), name: name.trim() || null });
+      await Promise.all([fetchServers(), fetchGames()]);
+      if (response.server) navigate(`/servers/${response.server.id}`);
+    } catch (e) {
+      onError(String(e));
+    }
+    setBusy(false);
+  };
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-lg font-semibold mb-1">From an Exported Server</h2>
+      <p className="text-sm text-slate-400 mb-4">
+        A zip made with Export in a server's settings on another machine. Its files and settings come along; ports
+        another server here already uses are swapped for free ones.
+      </p>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          placeholder="/home/me/survival-20261016-120000.zip"
+          className="input flex-1"
+        />
+        <input
+          type="text"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          placeholder="Name (optional)"
+          className="input w-48"
+        />
+        <button onClick={runImport} disabled={!path.trim() || busy} className="btn btn-primary">
+          {busy ? <Loader2 size={18} className="animate-spin" /> : <FileArchive size={18} />}
+          {busy ? 'Importing...' : 'Import'}
+        </button>
+      </div>
+    </section>
+  );
+}
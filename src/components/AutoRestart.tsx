@@ -0,0 +1,62 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '../utils/backend';
+import type { CrashReport, Server } from '../types';
+
+interface AutoRestartProps {
+  server: Server;
+  onSaved: () => void;
+}
+
+/** Restarting after crashes, and the report from the last time the server kept crashing */
+export function AutoRestart({ server, onSaved }: AutoRestartProps) {
+  const [report, setReport] = useState<CrashReport | null>(null);
+  const [showLog, setShowLog] = useState(server.status === 'crashlooping');
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<CrashReport | null>('get_crash_report', { serverId: server.id })
+      .then(setReport)
+      .catch(() => setReport(null));
+  }, [server.id, server.status]);
+
+  const toggle = async (enabled: boolean) => {
+    setError(null);
+    try {
+      await invoke('set_server_auto_restart', { serverId: server.id, enabled });
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2">Auto-Restart</h3>
+      <label className="flex items-center gap-2 text-sm">
+        <input type="checkbox" checked={server.auto_restart ?? false} onChange={(e) => toggle(e.target.checked)} />
+        Start the server again when it crashes
+      </label>
+      <p className="text-sm text-zinc-400 mt-2">
+        After 3 crashes within 10 minutes it's left stopped and marked as crash looping until you start it.
+      </p>
+
+      {report && (
+        <div className="mt-4 text-sm">
+          <p className={server.status === 'crashlooping' ? 'text-red-400' : 'text-zinc-400'}>
+            Last crash loop {new Date(report.created_at).toLocaleString()}: {report.crashes.length} crashes, exit code{' '}
+            {report.exit_code}.{' '}
+            <button onClick={() => setShowLog(!showLog)} className="underline">
+              {showLog ? 'Hide' : 'Show'} the console output
+            </button>
+          </p>
+          {showLog && (
+            <pre className="mt-2 p-3 rounded bg-zinc-900 text-xs max-h-80 overflow-auto whitespace-pre-wrap">
+              {report.log.join('\n') || 'No output'}
+            </pre>
+          )}
+        </div>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,176 @@
+import { useEffect, useState } from 'react';
+import { CalendarClock, Save } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { BackupSchedule, BackupTiming, Server, Weekday } from '../types';
+
+interface BackupScheduleSettingsProps {
+  server: Server;
+  onSaved: () => void;
+}
+
+const DAYS: Weekday[] = ['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun'];
+
+const NEW_SCHEDULE: BackupSchedule = {
+  enabled: true,
+  timing: { type: 'daily', days: [], hour: 4, minute: 0 },
+  keep_count: null,
+  max_total_mb: null,
+};
+
+const pad = (n: number) => String(n).padStart(2, '0');
+const optionalNumber = (value: string) => (value === '' ? null : Math.max(0, parseInt(value, 10) || 0));
+
+/** Backing the server up on its own, and how many backups to keep */
+export function BackupScheduleSettings({ server, onSaved }: BackupScheduleSettingsProps) {
+  const [schedule, setSchedule] = useState<BackupSchedule | null>(server.backup_schedule ?? null);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setSchedule(server.backup_schedule ?? null);
+  }, [server.id, server.backup_schedule]);
+
+  const editable = (s: BackupSchedule | null | undefined) =>
+    s && { enabled: s.enabled, timing: s.timing, keep_count: s.keep_count, max_total_mb: s.max_total_mb };
+  const changed = JSON.stringify(editable(schedule)) !== JSON.stringify(editable(server.backup_schedule));
+
+  const set = (changes: Partial<BackupSchedule>) => schedule && setSchedule({ ...schedule, ...changes });
+  const setTiming = (timing: BackupTiming) => set({ timing });
+
+  const toggleDay = (day: Weekday) => {
+    if (schedule?.timing.type !== 'daily') return;
+    const days = schedule.timing.days;
+    setTiming({
+      ...schedule.timing,
+      days: days.includes(day) ? days.filter((d) => d !== day) : DAYS.filter((d) => d === day || days.includes(d)),
+    });
+  };
+
+  const save = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke('set_backup_schedule', { serverId: server.id, schedule });
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+    setSaving(false);
+  };
+
+  const timing = schedule?.timing;
+  const nextRun = server.backup_schedule?.enabled ? server.backup_schedule.next_run : null;
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold flex items-center gap-2">
+          <CalendarClock size={20} /> Automatic Backups
+        </h3>
+        {changed && (
+          <button onClick={save} disabled={saving} className="btn btn-success text-sm">
+            <Save size={16} /> {saving ? 'Saving...' : 'Save'}
+          </button>
+        )}
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        Backs the server up on a schedule while the app is open. The limits below apply to all of its backups.
+      </p>
+      <label className="flex items-center gap-2 text-sm mb-4">
+        <input
+          type="checkbox"
+          checked={schedule?.enabled ?? false}
+          onChange={(e) => setSchedule(schedule ? { ...schedule, enabled: e.target.checked } : NEW_SCHEDULE)}
+        />
+        Back up automatically
+      </label>
+
+      {schedule && timing && (
+        <div className="space-y-3 text-sm">
+          <div className="flex flex-wrap items-center gap-2">
+            <select
+              value={timing.type}
+              onChange={(e) =>
+                setTiming(e.target.value === 'interval' ? { type: 'interval', hours: 6 } : NEW_SCHEDULE.timing)
+              }
+              className="input py-1"
+            >
+              <option value="daily">At a time of day</option>
+              <option value="interval">Every few hours</option>
+            </select>
+            {timing.type === 'interval' ? (
+              <label className="flex items-center gap-2">
+                every
+                <input
+                  type="number"
+                  min={1}
+                  value={timing.hours}
+                  onChange={(e) => setTiming({ type: 'interval', hours: Math.max(1, parseInt(e.target.value, 10) || 1) })}
+                  className="input py-1 w-20"
+                />
+                hours
+              </label>
+            ) : (
+              <>
+                <div className="flex gap-1">
+                  {DAYS.map((day) => (
+                    <button
+                      key={day}
+                      onClick={() => toggleDay(day)}
+                      className={`px-1.5 py-0.5 rounded text-xs ${
+                        timing.days.length === 0 || timing.days.includes(day)
+                          ? 'bg-sky-600 text-white'
+                          : 'bg-slate-700 text-slate-400'
+                      }`}
+                    >
+                      {day}
+                    </button>
+                  ))}
+                </div>
+                <input
+                  type="time"
+                  value={`${pad(timing.hour)}:${pad(timing.minute)}`}
+                  onChange={(e) => {
+                    const [hour, minute] = e.target.value.split(':').map((part) => parseInt(part, 10) || 0);
+                    setTiming({ ...timing, hour, minute });
+                  }}
+                  className="input py-1"
+                />
+              </>
+            )}
+          </div>
+          <div className="flex flex-wrap items-center gap-4">
+            <label className="flex items-center gap-2">
+              Keep
+              <input
+                type="number"
+                min={1}
+                value={schedule.keep_count ?? ''}
+                placeholder="Settings"
+                onChange={(e) => set({ keep_count: optionalNumber(e.target.value) })}
+                className="input py-1 w-24"
+              />
+              backups
+            </label>
+            <label className="flex items-center gap-2">
+              up to
+              <input
+                type="number"
+                min={1}
+                value={schedule.max_total_mb ?? ''}
+                placeholder="No limit"
+                onChange={(e) => set({ max_total_mb: optionalNumber(e.target.value) })}
+                className="input py-1 w-28"
+              />
+              MB in total
+            </label>
+          </div>
+          {nextRun && !changed && (
+            <p className="text-zinc-400">Next backup: {new Date(nextRun).toLocaleString()}</p>
+          )}
+        </div>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
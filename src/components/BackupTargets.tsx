@@ -0,0 +1,305 @@
+import { useEffect, useState } from 'react';
+import { CloudDownload, CloudUpload, Plug, Plus, Trash2 } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { BackupStorage, BackupTarget, RemoteBackup, Server, ServerEvent } from '../types';
+
+interface BackupTargetsProps {
+  server: Server;
+  onSaved: () => void;
+  /** A backup was copied into the local backups */
+  onPulled: () => void;
+}
+
+type Preset = 's3' | 'b2' | 'webdav';
+
+const MB = 1024 * 1024;
+
+const STORAGE: Record<Preset, BackupStorage> = {
+  s3: { kind: 's3', endpoint: 'https://s3.amazonaws.com', region: 'us-east-1', bucket: '', access_key_id: '', path_style: false },
+  b2: {
+    kind: 's3',
+    endpoint: 'https://s3.us-west-004.backblazeb2.com',
+    region: 'us-west-004',
+    bucket: '',
+    access_key_id: '',
+    path_style: false,
+  },
+  webdav: { kind: 'webdav', url: '', username: '' },
+};
+
+const newTarget = (preset: Preset): BackupTarget => ({
+  id: '',
+  name: preset === 'b2' ? 'Backblaze B2' : preset === 'webdav' ? 'WebDAV' : 'S3',
+  storage: STORAGE[preset],
+  prefix: 'serverwave',
+  upload_new: false,
+});
+
+/** S3 buckets and WebDAV folders the server's backups are copied to, and the backups on them */
+export function BackupTargets({ server, onSaved, onPulled }: BackupTargetsProps) {
+  const targets = server.backup_targets ?? [];
+  const [editing, setEditing] = useState<BackupTarget | null>(null);
+  const [secret, setSecret] = useState('');
+  const [browsing, setBrowsing] = useState<string | null>(null);
+  const [remote, setRemote] = useState<RemoteBackup[]>([]);
+  const [progress, setProgress] = useState<Record<string, number>>({});
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<ServerEvent>('server-event', (event) => {
+      const e = event.payload;
+      if (e.type !== 'BackupTransfer' || e.server_id !== server.id || e.direction !== 'download') return;
+      setProgress((p) => ({ ...p, [e.backup_id]: e.total_bytes ? Math.round((e.done_bytes / e.total_bytes) * 100) : 0 }));
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [server.id]);
+
+  const run = async (key: string, action: () => Promise<string | null>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const save = () =>
+    editing &&
+    run('save', async () => {
+      await invoke('set_backup_target', { serverId: server.id, target: editing, secret: secret || null });
+      setEditing(null);
+      setSecret('');
+      onSaved();
+      return 'Target saved';
+    });
+
+  const remove = (target: BackupTarget) => {
+    if (!confirm(`Stop using ${target.name}? The backups on it stay there.`)) return;
+    run(target.id, async () => {
+      await invoke('remove_backup_target', { serverId: server.id, targetId: target.id });
+      if (browsing === target.id) setBrowsing(null);
+      onSaved();
+      return null;
+    });
+  };
+
+  const test = (target: BackupTarget) =>
+    run(target.id, async () => {
+      await invoke('test_backup_target', { serverId: server.id, targetId: target.id });
+      return `${target.name} can be reached`;
+    });
+
+  const browse = (target: BackupTarget) =>
+    run(target.id, async () => {
+      setRemote(await invoke<RemoteBackup[]>('list_remote_backups', { serverId: server.id, targetId: target.id }));
+      setBrowsing(target.id);
+      return null;
+    });
+
+  const pull = (targetId: string, backup: RemoteBackup) =>
+    run(backup.id, async () => {
+      await invoke('pull_backup', { serverId: server.id, targetId, backupId: backup.id });
+      setRemote((list) => list.map((b) => (b.id === backup.id ? { ...b, local: true } : b)));
+      onPulled();
+      return 'Copied to this computer - restore it from the backups above';
+    });
+
+  const setStorage = (changes: Partial<BackupStorage>) =>
+    editing && setEditing({ ...editing, storage: { ...editing.storage, ...changes } as BackupStorage });
+
+  const storage = editing?.storage;
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold flex items-center gap-2">
+          <CloudUpload size={20} /> Off-site Copies
+        </h3>
+        {!editing && (
+          <select
+            value=""
+            onChange={(e) => e.target.value && setEditing(newTarget(e.target.value as Preset))}
+            className="input py-1 text-sm"
+          >
+            <option value="">Add a target...</option>
+            <option value="s3">Amazon S3 or compatible</option>
+            <option value="b2">Backblaze B2</option>
+            <option value="webdav">WebDAV (Nextcloud, NAS)</option>
+          </select>
+        )}
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        Copies backups to a bucket or folder somewhere else, so they survive this computer. Keys and passwords are
+        kept in the system keychain.
+      </p>
+
+      {targets.length === 0 && !editing && <p className="text-sm text-zinc-500">No targets yet</p>}
+      <ul className="divide-y divide-zinc-800">
+        {targets.map((target) => (
+          <li key={target.id} className="py-2 text-sm">
+            <div className="flex items-center gap-2">
+              <div className="flex-1">
+                <div>{target.name}</div>
+                <div className="text-zinc-400">
+                  {target.storage.kind === 's3' ? `${target.storage.bucket} on ${target.storage.endpoint}` : target.storage.url}
+                  {target.upload_new && ' - every new backup'}
+                </div>
+              </div>
+              <button onClick={() => test(target)} disabled={busy !== null} className="btn btn-secondary text-sm" title="Test">
+                <Plug size={16} />
+              </button>
+              <button onClick={() => browse(target)} disabled={busy !== null} className="btn btn-secondary text-sm">
+                {busy === target.id ? 'Working...' : 'Backups'}
+              </button>
+              <button onClick={() => setEditing(target)} disabled={busy !== null} className="btn btn-secondary text-sm">
+                Edit
+              </button>
+              <button onClick={() => remove(target)} disabled={busy !== null} className="btn btn-danger text-sm">
+                <Trash2 size={16} />
+              </button>
+            </div>
+            {browsing === target.id && (
+              <ul className="mt-2 ml-4 space-y-1">
+                {remote.length === 0 && <li className="text-zinc-500">No backups of this server there yet</li>}
+                {remote.map((backup) => (
+                  <li key={backup.id} className="flex items-center gap-4">
+                    <span className="flex-1">
+                      {backup.created_at ? new Date(backup.created_at).toLocaleString() : backup.id}
+                      <span className="text-zinc-400"> - {(backup.size_bytes / MB).toFixed(1)} MB</span>
+                    </span>
+                    {backup.local ? (
+                      <span className="text-zinc-500">On this computer</span>
+                    ) : (
+                      <button
+                        onClick={() => pull(target.id, backup)}
+                        disabled={busy !== null}
+                        className="btn btn-secondary text-sm"
+                      >
+                        <CloudDownload size={16} />
+                        {busy === backup.id ? `${progress[backup.id] ?? 0}%` : 'Copy here'}
+                      </button>
+                    )}
+                  </li>
+                ))}
+              </ul>
+            )}
+          </li>
+        ))}
+      </ul>
+
+      {editing && storage && (
+        <div className="space-y-2 text-sm mt-4">
+          <input
+            value={editing.name}
+            onChange={(e) => setEditing({ ...editing, name: e.target.value })}
+            placeholder="Name"
+            className="input w-full"
+          />
+          {storage.kind === 's3' ? (
+            <>
+              <div className="flex gap-2">
+                <input
+                  value={storage.endpoint}
+                  onChange={(e) => setStorage({ endpoint: e.target.value })}
+                  placeholder="Endpoint"
+                  className="input flex-1"
+                />
+                <input
+                  value={storage.region}
+                  onChange={(e) => setStorage({ region: e.target.value })}
+                  placeholder="Region"
+                  className="input w-36"
+                />
+              </div>
+              <div className="flex gap-2">
+                <input
+                  value={storage.bucket}
+                  onChange={(e) => setStorage({ bucket: e.target.value })}
+                  placeholder="Bucket"
+                  className="input flex-1"
+                />
+                <input
+                  value={storage.access_key_id}
+                  onChange={(e) => setStorage({ access_key_id: e.target.value })}
+                  placeholder="Access key ID"
+                  className="input flex-1"
+                />
+              </div>
+              <label className="flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={storage.path_style}
+                  onChange={(e) => setStorage({ path_style: e.target.checked })}
+                />
+                Path-style addresses (MinIO and other self-hosted stores)
+              </label>
+            </>
+          ) : (
+            <div className="flex gap-2">
+              <input
+                value={storage.url}
+                onChange={(e) => setStorage({ url: e.target.value })}
+                placeholder="https://cloud.example.com/remote.php/dav/files/me"
+                className="input flex-1"
+              />
+              <input
+                value={storage.username}
+                onChange={(e) => setStorage({ username: e.target.value })}
+                placeholder="Username"
+                className="input w-40"
+              />
+            </div>
+          )}
+          <div className="flex gap-2">
+            <input
+              type="password"
+              value={secret}
+              onChange={(e) => setSecret(e.target.value)}
+              placeholder={
+                (storage.kind === 's3' ? 'Secret access key' : 'Password') + (editing.id ? ' (unchanged when empty)' : '')
+              }
+              className="input flex-1"
+            />
+            <input
+              value={editing.prefix}
+              onChange={(e) => setEditing({ ...editing, prefix: e.target.value })}
+              placeholder="Folder"
+              className="input w-40"
+            />
+          </div>
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={editing.upload_new}
+              onChange={(e) => setEditing({ ...editing, upload_new: e.target.checked })}
+            />
+            Copy every new backup here
+          </label>
+          <div className="flex gap-2">
+            <button onClick={save} disabled={busy !== null} className="btn btn-success text-sm">
+              <Plus size={16} /> {busy === 'save' ? 'Saving...' : 'Save Target'}
+            </button>
+            <button
+              onClick={() => {
+                setEditing(null);
+                setSecret('');
+              }}
+              className="btn btn-secondary text-sm"
+            >
+              Cancel
+            </button>
+          </div>
+        </div>
+      )}
+      {message && <p className="text-sm text-emerald-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
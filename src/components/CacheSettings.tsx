@@ -0,0 +1,47 @@
+import { useEffect, useState } from 'react';
+import { Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { CacheUsage } from '../types';
+
+export function CacheSettings() {
+  const [usage, setUsage] = useState<CacheUsage | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () => {
+    invoke<CacheUsage>('get_content_cache').then(setUsage).catch((e) => setError(String(e)));
+  };
+
+  const clear = async () => {
+    setError(null);
+    try {
+      await invoke('clear_content_cache');
+    } catch (e) {
+      setError(String(e));
+    }
+    load();
+  };
+
+  useEffect(load, []);
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Download Cache</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Mods, server jars, SteamCMD and Steam game files are downloaded once and shared between servers.
+        Clearing the cache doesn't touch installed servers.
+      </p>
+      <div className="flex items-center justify-between text-sm">
+        <span>
+          {usage
+            ? `${usage.files} files, ${(usage.size_bytes / 1024 / 1024).toFixed(1)} MB` +
+              ` · SteamCMD ${(usage.steamcmd_bytes / 1024 / 1024).toFixed(1)} MB`
+            : 'Loading...'}
+        </span>
+        <button onClick={clear} disabled={!usage || (usage.files === 0 && usage.steamcmd_bytes === 0)} className="btn btn-secondary text-sm">
+          <Trash2 size={16} /> Clear
+        </button>
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
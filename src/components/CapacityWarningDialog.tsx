@@ -0,0 +1,47 @@
+import { AlertTriangle, X } from 'lucide-react';
+import { useServerStore } from '../stores/serverStore';
+
+/** Shown when a start was refused because the server doesn't fit in memory */
+export function CapacityWarningDialog() {
+  const { capacityWarning, dismissCapacityWarning, startServer, servers } = useServerStore();
+
+  if (!capacityWarning) return null;
+  const { serverId, error } = capacityWarning;
+  const server = servers.find((s) => s.id === serverId);
+  const running = error.runningServers ?? [];
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="absolute inset-0 bg-black/70 backdrop-blur-sm" onClick={dismissCapacityWarning} />
+      <div className="relative bg-zinc-900 border border-zinc-800 rounded-xl shadow-2xl max-w-md w-full mx-4 animate-fade-in">
+        <div className="flex items-center gap-3 p-4 border-b border-zinc-800">
+          <div className="w-10 h-10 rounded-full bg-yellow-500/20 flex items-center justify-center">
+            <AlertTriangle size={20} className="text-yellow-500" />
+          </div>
+          <div className="flex-1">
+            <h3 className="font-semibold text-lg">Not Enough Memory</h3>
+            <p className="text-sm text-zinc-400">{server?.name ?? serverId}</p>
+          </div>
+          <button onClick={dismissCapacityWarning} className="p-2 hover:bg-zinc-800 rounded-lg transition-colors">
+            <X size={20} className="text-zinc-400" />
+          </button>
+        </div>
+        <div className="p-4 space-y-3 text-sm">
+          <p>{error.message}.</p>
+          <p className="text-zinc-400">
+            Starting it anyway can slow the whole computer down or get servers killed when memory runs out.
+            {running.length > 0 && ` Running now: ${running.join(', ')}.`}
+          </p>
+        </div>
+        <div className="flex justify-end gap-2 p-4 border-t border-zinc-800">
+          <button onClick={dismissCapacityWarning} className="btn btn-secondary">
+            Cancel
+          </button>
+          <button onClick={() => startServer(serverId, true)} className="btn btn-primary">
+            Start Anyway
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
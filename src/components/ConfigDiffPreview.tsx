@@ -0,0 +1,75 @@
+import { useState } from 'react';
+import { FileDiff } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { ConfigFileDiff } from '../types';
+
+interface ConfigDiffPreviewProps {
+  serverId: string;
+}
+
+const lineClass = (line: string) => {
+  if (line.startsWith('+++') || line.startsWith('---')) return 'text-zinc-500';
+  if (line.startsWith('@@')) return 'text-sky-400';
+  if (line.startsWith('+')) return 'text-emerald-400';
+  if (line.startsWith('-')) return 'text-red-400';
+  return 'text-zinc-300';
+};
+
+/** What the server's settings would change in the game's config files, before they're written */
+export function ConfigDiffPreview({ serverId }: ConfigDiffPreviewProps) {
+  const [diffs, setDiffs] = useState<ConfigFileDiff[] | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      setDiffs(await invoke<ConfigFileDiff[]>('preview_config_changes', { serverId }));
+    } catch (e) {
+      setError(String(e));
+    }
+    setLoading(false);
+  };
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold">Config File Changes</h3>
+        <button onClick={load} disabled={loading} className="btn btn-secondary text-sm">
+          <FileDiff size={16} />
+          {loading ? 'Loading...' : diffs ? 'Refresh' : 'Preview'}
+        </button>
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        The edits the settings make to the game's own config files, compared with the files as they are now.
+      </p>
+
+      {error && <p className="text-sm text-red-400">{error}</p>}
+      {diffs && diffs.length === 0 && <p className="text-sm text-zinc-400">This game has no config files it edits.</p>}
+
+      {diffs && (
+        <div className="space-y-4 text-sm">
+          {diffs.map((file) => (
+            <div key={file.path}>
+              <div className="font-mono mb-1">{file.path}</div>
+              {file.error ? (
+                <p className="text-red-400">{file.error}</p>
+              ) : !file.exists ? (
+                <p className="text-zinc-500">Not created yet - the game writes it on first start.</p>
+              ) : !file.diff ? (
+                <p className="text-zinc-500">Already matches the settings.</p>
+              ) : (
+                <pre className="bg-zinc-800 rounded-lg px-3 py-2 font-mono text-xs overflow-x-auto">
+                  {file.diff.split('\n').filter(Boolean).map((line, i) => (
+                    <div key={i} className={lineClass(line)}>{line}</div>
+                  ))}
+                </pre>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,89 @@
+import { useEffect, useState } from 'react';
+import { Plus, Save, Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { EnvVar, Server } from '../types';
+
+interface CustomEnvEditorProps {
+  server: Server;
+  onSaved: () => void;
+}
+
+/** The server's own environment variables - image options the game's settings don't cover */
+export function CustomEnvEditor({ server, onSaved }: CustomEnvEditorProps) {
+  const [vars, setVars] = useState<EnvVar[]>(server.env ?? []);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setVars(server.env ?? []);
+  }, [server.id, server.env]);
+
+  const changed = JSON.stringify(vars) !== JSON.stringify(server.env ?? []);
+
+  const update = (index: number, changes: Partial<EnvVar>) =>
+    setVars(vars.map((v, i) => (i === index ? { ...v, ...changes } : v)));
+
+  const save = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke('update_server_env', { serverId: server.id, env: vars.filter((v) => v.key.trim()) });
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+    setSaving(false);
+  };
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold">Environment Variables</h3>
+        <div className="flex items-center gap-2">
+          <button onClick={() => setVars([...vars, { key: '', value: '' }])} className="btn btn-secondary text-sm">
+            <Plus size={16} /> Add
+          </button>
+          {changed && (
+            <button onClick={save} disabled={saving} className="btn btn-success text-sm">
+              <Save size={16} /> {saving ? 'Saving...' : 'Save'}
+            </button>
+          )}
+        </div>
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        Extra variables for the server's container and its installs, for image options that aren't in the game
+        settings. They apply when the container is next created. A value can use another server's settings,
+        like <code>{'{{server:Lobby.address}}'}</code>, filled in at each start.
+      </p>
+      {vars.length === 0 && <p className="text-sm text-zinc-500">None set.</p>}
+      <div className="space-y-2">
+        {vars.map((v, index) => (
+          <div key={index} className="flex gap-2">
+            <input
+              type="text"
+              value={v.key}
+              onChange={(e) => update(index, { key: e.target.value })}
+              placeholder="NAME"
+              className="input w-1/3 font-mono text-sm"
+            />
+            <input
+              type="text"
+              value={v.value}
+              onChange={(e) => update(index, { value: e.target.value })}
+              placeholder="value"
+              className="input flex-1 font-mono text-sm"
+            />
+            <button
+              onClick={() => setVars(vars.filter((_, i) => i !== index))}
+              className="btn btn-secondary text-sm"
+              title="Remove"
+            >
+              <Trash2 size={16} />
+            </button>
+          </div>
+        ))}
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
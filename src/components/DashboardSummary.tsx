@@ -0,0 +1,103 @@
+import { useEffect, useState } from 'react';
+import { ArrowUpCircle, HardDrive, MemoryStick } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import type { DashboardSummary as Summary, RecentEvent } from '../types';
+
+const GB = 1024 * 1024 * 1024;
+const REFRESH_INTERVAL = 30000;
+
+function describe({ event }: RecentEvent): string {
+  switch (event.type) {
+    case 'StatusChanged':
+      return `is ${event.status}`;
+    case 'Crashed':
+      return `crashed (exit code ${event.exit_code})`;
+    case 'InstallProgress':
+    case 'Alert':
+    case 'AutomationHeld':
+      return event.message;
+    case 'BackupDone':
+      return `backed up (${(event.size_bytes / GB).toFixed(2)} GB)`;
+    default:
+      return event.type;
+  }
+}
+
+/** Memory, disk, pending updates and recent events across all servers */
+export function DashboardSummary() {
+  const servers = useServerStore((s) => s.servers);
+  const [summary, setSummary] = useState<Summary | null>(null);
+
+  useEffect(() => {
+    const load = () => invoke<Summary>('get_dashboard_summary').then(setSummary).catch(() => {});
+    load();
+    const interval = setInterval(load, REFRESH_INTERVAL);
+    return () => clearInterval(interval);
+  }, []);
+
+  if (!summary) return null;
+  const name = (id: string) => servers.find((s) => s.id === id)?.name ?? id;
+
+  return (
+    <div className="grid grid-cols-1 md:grid-cols-3 gap-4 mb-8">
+      <div className="card flex items-center gap-4">
+        <div className="p-3 bg-purple-600/20 rounded-lg">
+          <MemoryStick className="text-purple-400" size={24} />
+        </div>
+        <div>
+          <div className="text-2xl font-bold">
+            {(summary.running_memory_mb / 1024).toFixed(1)} / {(summary.allocated_memory_mb / 1024).toFixed(1)} GB
+          </div>
+          <div className="text-sm text-slate-400">
+            Memory running / allocated
+            {summary.host_memory_mb > 0 && `, of ${(summary.host_memory_mb / 1024).toFixed(1)} GB`}
+          </div>
+        </div>
+      </div>
+
+      <div className="card flex items-center gap-4">
+        <div className="p-3 bg-cyan-600/20 rounded-lg">
+          <HardDrive className="text-cyan-400" size={24} />
+        </div>
+        <div>
+          <div className="text-2xl font-bold">{(summary.disk_used_bytes / GB).toFixed(1)} GB</div>
+          <div className="text-sm text-slate-400">
+            Used by servers
+            {summary.disk_free_bytes !== null && `, ${(summary.disk_free_bytes / GB).toFixed(1)} GB free`}
+          </div>
+        </div>
+      </div>
+
+      <div className="card flex items-center gap-4">
+        <div className="p-3 bg-amber-600/20 rounded-lg">
+          <ArrowUpCircle className="text-amber-400" size={24} />
+        </div>
+        <div>
+          <div className="text-2xl font-bold">{summary.pending_updates.length}</div>
+          <div className="text-sm text-slate-400">
+            {summary.pending_updates.length > 0
+              ? `Updates for ${summary.pending_updates.map((u) => name(u.server_id)).join(', ')}`
+              : 'Servers are up to date'}
+          </div>
+        </div>
+      </div>
+
+      {summary.recent_events.length > 0 && (
+        <div className="card md:col-span-3">
+          <h3 className="font-semibold mb-2">Recent Activity</h3>
+          <ul className="text-sm space-y-1">
+            {summary.recent_events.map((e, i) => (
+              <li key={i} className="flex gap-3">
+                <span className="text-slate-500 shrink-0">{new Date(e.at).toLocaleTimeString()}</span>
+                <span>
+                  <span className="font-medium">{name(e.event.server_id)}</span> {describe(e)}
+                </span>
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,71 @@
+import { useEffect, useState } from 'react';
+import { FolderInput } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { AppSettings } from '../types';
+
+const DEFAULT_ROOT = '~/ServerWaveAnywhere';
+
+export function DataLocationSettings() {
+  const [dataDir, setDataDir] = useState<string | null>(null);
+  const [newDir, setNewDir] = useState('');
+  const [isMoving, setIsMoving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<AppSettings>('get_app_settings').then((s) => setDataDir(s.data_dir)).catch(() => {});
+    const unlisten = listen<AppSettings>('settings-changed', (event) => setDataDir(event.payload.data_dir));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const root = dataDir ?? DEFAULT_ROOT;
+
+  const move = async () => {
+    setError(null);
+    setIsMoving(true);
+    try {
+      const settings = await invoke<AppSettings>('set_data_directory', { path: newDir });
+      setDataDir(settings.data_dir);
+      setNewDir('');
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsMoving(false);
+    }
+  };
+
+  return (
+    <section className="card">
+      <h2 className="text-xl font-semibold mb-4">Data Location</h2>
+      <div className="space-y-3 text-sm">
+        <div>
+          <span className="text-slate-500">Server Data:</span>
+          <div className="font-mono text-slate-300 mt-1">{root}/servers/</div>
+        </div>
+        <div>
+          <span className="text-slate-500">Configuration:</span>
+          <div className="font-mono text-slate-300 mt-1">{root}/config/</div>
+        </div>
+      </div>
+      <p className="text-xs text-slate-500 mt-4">
+        World saves and configs persist even when you delete a server.
+      </p>
+      <div className="flex gap-2 mt-4">
+        <input
+          type="text"
+          value={newDir}
+          onChange={(e) => setNewDir(e.target.value)}
+          placeholder="Move to, e.g. D:\\ServerWaveAnywhere"
+          className="input flex-1"
+        />
+        <button onClick={move} disabled={!newDir.trim() || isMoving} className="btn btn-secondary text-sm">
+          <FolderInput size={16} className={isMoving ? 'animate-pulse' : ''} />
+          {isMoving ? 'Moving...' : 'Move'}
+        </button>
+      </div>
+      <p className="text-xs text-slate-500 mt-2">All servers must be stopped. Large servers can take a while to copy.</p>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
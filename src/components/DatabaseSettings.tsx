@@ -0,0 +1,119 @@
+import { useEffect, useState } from 'react';
+import { Database, Eye, EyeOff, Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { DatabaseInfo, DatabaseKind, Server } from '../types';
+
+interface DatabaseSettingsProps {
+  server: Server;
+  onSaved: () => void;
+}
+
+const KINDS: { value: DatabaseKind; label: string }[] = [
+  { value: 'mariadb', label: 'MariaDB' },
+  { value: 'mysql', label: 'MySQL' },
+  { value: 'redis', label: 'Redis' },
+];
+
+/** A database container for the server's plugins, with the details to put in their configs */
+export function DatabaseSettings({ server, onSaved }: DatabaseSettingsProps) {
+  const [info, setInfo] = useState<DatabaseInfo | null>(null);
+  const [kind, setKind] = useState<DatabaseKind>('mariadb');
+  const [showPassword, setShowPassword] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<DatabaseInfo | null>('get_database', { serverId: server.id })
+      .then(setInfo)
+      .catch((e) => setError(String(e)));
+  }, [server.id, server.status, server.database?.kind]);
+
+  const run = async (action: () => Promise<void>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(false);
+  };
+
+  const add = () =>
+    run(async () => {
+      setInfo(await invoke<DatabaseInfo | null>('add_database', { serverId: server.id, kind }));
+    });
+
+  const remove = () => {
+    if (!confirm('Remove the database container?')) return;
+    const deleteData = confirm('Delete its data too? Cancel keeps the data for a database added later.');
+    run(async () => {
+      await invoke('remove_database', { serverId: server.id, deleteData });
+      setInfo(null);
+    });
+  };
+
+  const label = (k: DatabaseKind) => KINDS.find((entry) => entry.value === k)?.label ?? k;
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2 flex items-center gap-2">
+        <Database size={20} /> Database
+      </h3>
+      {!info ? (
+        <>
+          <p className="text-sm text-zinc-400 mb-4">
+            A database container for plugins like LuckPerms or CoreProtect. It starts and stops with the server, and
+            its address and generated password reach the server as variables.
+          </p>
+          <div className="flex gap-2">
+            <select value={kind} onChange={(e) => setKind(e.target.value as DatabaseKind)} className="input text-sm">
+              {KINDS.map((k) => (
+                <option key={k.value} value={k.value}>{k.label}</option>
+              ))}
+            </select>
+            <button onClick={add} disabled={busy} className="btn btn-primary text-sm">
+              {busy ? 'Adding...' : 'Add Database'}
+            </button>
+          </div>
+        </>
+      ) : (
+        <>
+          <p className="text-sm text-zinc-400 mb-4">
+            {label(info.kind)}, {info.running ? 'running' : 'stopped'}. The server gets{' '}
+            {info.variables.map((v) => <code key={v} className="mr-1">{v}</code>)}
+            on its next start.
+          </p>
+          <div className="grid grid-cols-[120px_1fr] gap-y-1 text-sm mb-4">
+            <span className="text-zinc-400">Host</span>
+            <code>{info.host}:{info.port}</code>
+            {info.database && (
+              <>
+                <span className="text-zinc-400">Database</span>
+                <code>{info.database}</code>
+              </>
+            )}
+            {info.user && (
+              <>
+                <span className="text-zinc-400">User</span>
+                <code>{info.user}</code>
+              </>
+            )}
+            <span className="text-zinc-400">Password</span>
+            <span className="flex items-center gap-2">
+              <code>{showPassword ? info.password : '••••••••'}</code>
+              <button onClick={() => setShowPassword(!showPassword)} className="text-zinc-400 hover:text-white">
+                {showPassword ? <EyeOff size={14} /> : <Eye size={14} />}
+              </button>
+            </span>
+          </div>
+          <button onClick={remove} disabled={busy} className="btn btn-danger text-sm">
+            <Trash2 size={16} /> Remove Database
+          </button>
+        </>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
@@ -5,7 +5,7 @@ interface DeleteConfirmDialogProps {
   isOpen: boolean;
   serverName: string;
   dataPath: string;
-  onConfirm: (deleteData: boolean) => void;
+  onConfirm: (confirmName: string, deleteData: boolean, trash: boolean) => void;
   onCancel: () => void;
 }
 
@@ -17,6 +17,8 @@ export function DeleteConfirmDialog({
   onCancel 
 }: DeleteConfirmDialogProps) {
   const [deleteData, setDeleteData] = useState(true);
+  const [trash, setTrash] = useState(true);
+  const [confirmName, setConfirmName] = useState('');
 
   if (!isOpen) return null;
 
@@ -37,7 +39,9 @@ export function DeleteConfirmDialog({
           </div>
           <div className="flex-1">
             <h3 className="font-semibold text-lg">Delete Server</h3>
-            <p className="text-sm text-zinc-400">This action cannot be undone</p>
+            <p className="text-sm text-zinc-400">
+              {deleteData && !trash ? 'This action cannot be undone' : 'The server is removed from the app'}
+            </p>
           </div>
           <button 
             onClick={onCancel}
@@ -80,14 +84,37 @@ export function DeleteConfirmDialog({
                   Delete world data
                 </div>
                 <p className="text-sm text-zinc-400 mt-1">
-                  {deleteData 
-                    ? 'All server files will be permanently deleted' 
-                    : 'Server files will be preserved for later use'
+                  {!deleteData
+                    ? 'Server files will be preserved for later use'
+                    : trash
+                      ? 'Server files will be moved to the trash folder'
+                      : 'All server files will be permanently deleted'
                   }
                 </p>
               </div>
             </div>
           </div>
+
+          {deleteData && (
+            <label className="flex items-center gap-2 text-sm text-zinc-300 cursor-pointer">
+              <input type="checkbox" checked={trash} onChange={(e) => setTrash(e.target.checked)} />
+              Move to the trash instead of deleting
+            </label>
+          )}
+
+          {/* Name confirmation */}
+          <div>
+            <label className="block text-sm text-zinc-400 mb-1">
+              Type <span className="font-semibold text-white">{serverName}</span> to confirm
+            </label>
+            <input
+              type="text"
+              value={confirmName}
+              onChange={(e) => setConfirmName(e.target.value)}
+              className="input w-full"
+              autoFocus
+            />
+          </div>
           
           {/* Data path info */}
           <div className="flex items-start gap-2 p-3 bg-zinc-800/50 rounded-lg">
@@ -108,11 +135,12 @@ export function DeleteConfirmDialog({
             Cancel
           </button>
           <button 
-            onClick={() => onConfirm(deleteData)}
+            onClick={() => onConfirm(confirmName, deleteData, trash)}
+            disabled={confirmName.trim() !== serverName.trim()}
             className="btn btn-danger flex-1"
           >
             <Trash2 size={18} />
-            {deleteData ? 'Delete Everything' : 'Delete Server'}
+            {deleteData && !trash ? 'Delete Everything' : 'Delete Server'}
           </button>
         </div>
       </div>
@@ -0,0 +1,48 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '../utils/backend';
+import type { AuditEntry, DataFate } from '../types';
+
+const formatSize = (bytes: number) =>
+  bytes >= 1024 ** 3 ? `${(bytes / 1024 ** 3).toFixed(1)} GB` : `${(bytes / 1024 ** 2).toFixed(0)} MB`;
+
+const FATE_LABELS: Record<DataFate, string> = {
+  kept: 'files kept',
+  trashed: 'files in the trash',
+  deleted: 'files deleted',
+};
+
+/** Tombstones of deleted servers from the audit journal */
+export function DeletedServers() {
+  const [entries, setEntries] = useState<AuditEntry[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<AuditEntry[]>('get_audit_journal').then(setEntries).catch((e) => setError(String(e)));
+  }, []);
+
+  if (entries.length === 0 && !error) return null;
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Deleted Servers</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Servers deleted from this app, newest first. Trashed files stay in the trash folder until you remove them.
+      </p>
+      <div className="space-y-2 text-sm">
+        {entries.map((entry) => (
+          <div key={`${entry.server_id}-${entry.deleted_at}`} className="py-2 border-b border-zinc-700">
+            <div>
+              {entry.name} <span className="text-zinc-500">({entry.game_type})</span>
+            </div>
+            <div className="text-xs text-zinc-500">
+              {new Date(entry.deleted_at).toLocaleString()} by {entry.deleted_by} · {formatSize(entry.data_bytes)},{' '}
+              {FATE_LABELS[entry.data]}
+            </div>
+            <code className="text-xs text-zinc-400 break-all">{entry.trash_path ?? entry.data_path}</code>
+          </div>
+        ))}
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
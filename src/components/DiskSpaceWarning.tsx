@@ -0,0 +1,36 @@
+import { useEffect, useState } from 'react';
+import { HardDrive } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { DiskSpace } from '../types';
+
+const GB = 1024 * 1024 * 1024;
+
+/** Shown while the disk servers are on has less free space than the minimum in Settings */
+export function DiskSpaceWarning() {
+  const [space, setSpace] = useState<DiskSpace | null>(null);
+
+  useEffect(() => {
+    invoke<DiskSpace | null>('get_disk_space').then(setSpace).catch(() => {});
+    const unlisten = listen<DiskSpace>('disk-space', (event) => setSpace(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!space?.low) return null;
+
+  return (
+    <div className="card mb-8 flex items-center gap-4 border border-amber-500/50">
+      <div className="p-3 bg-amber-500/20 rounded-lg">
+        <HardDrive className="text-amber-400" size={24} />
+      </div>
+      <div className="text-sm">
+        <div className="font-semibold text-amber-400">Disk space is low</div>
+        <div className="text-slate-400">
+          {(space.free_bytes / GB).toFixed(1)} GB free on the disk with {space.path}, below the{' '}
+          {Math.round(space.min_free_bytes / GB)} GB minimum. Free some up before a server runs out mid-save.
+        </div>
+      </div>
+    </div>
+  );
+}
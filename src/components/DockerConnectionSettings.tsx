@@ -0,0 +1,194 @@
+import { useEffect, useState } from 'react';
+import { Plug, Power, Save } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { AppSettings, DetectedSocket, DockerConnection, DockerStatus, WakeOnLan } from '../types';
+
+const NEW_WAKE: WakeOnLan = { mac: '', broadcast: '255.255.255.255', port: 9, boot_secs: 180 };
+
+/** Where Docker is - a socket on this computer, or a daemon on another one over TCP */
+export function DockerConnectionSettings({ onSaved }: { onSaved?: () => void }) {
+  const [settings, setSettings] = useState<AppSettings | null>(null);
+  const [sockets, setSockets] = useState<DetectedSocket[]>([]);
+  const [test, setTest] = useState<DockerStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [waking, setWaking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [saved, setSaved] = useState(false);
+  // Waking uses the saved settings, so not while there are changes
+  const [dirty, setDirty] = useState(false);
+
+  useEffect(() => {
+    invoke<AppSettings>('get_app_settings').then(setSettings).catch((e) => setError(String(e)));
+    invoke<DetectedSocket[]>('detect_docker_sockets').then(setSockets).catch(() => {});
+    const unlisten = listen<AppSettings>('settings-changed', (event) => setSettings(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!settings) return null;
+  const connection = settings.docker;
+
+  const update = (changes: Partial<DockerConnection>) => {
+    setSaved(false);
+    setDirty(true);
+    setTest(null);
+    setSettings({ ...settings, docker: { ...connection, ...changes } });
+  };
+
+  const runTest = async () => {
+    setBusy(true);
+    setError(null);
+    try {
+      setTest(await invoke<DockerStatus>('test_docker_connection', { connection }));
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(false);
+  };
+
+  const setWake = (changes: Partial<WakeOnLan>) => connection.wake && update({ wake: { ...connection.wake, ...changes } });
+
+  const wakeNow = async () => {
+    setWaking(true);
+    setError(null);
+    try {
+      setTest(await invoke<DockerStatus>('wake_docker_host'));
+    } catch (e) {
+      setError(String(e));
+    }
+    setWaking(false);
+  };
+
+  const save = async () => {
+    setError(null);
+    try {
+      setSettings(await invoke<AppSettings>('update_app_settings', { settings }));
+      setSaved(true);
+      setDirty(false);
+      onSaved?.();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const text = (key: 'socket' | 'url' | 'tls_ca' | 'tls_cert' | 'tls_key', label: string, placeholder: string) => (
+    <label className="block">
+      <span className="input-label">{label}</span>
+      <input
+        type="text"
+        list={key === 'socket' ? 'docker-sockets' : undefined}
+        value={connection[key] ?? ''}
+        onChange={(e) => update({ [key]: e.target.value || null })}
+        placeholder={placeholder}
+        className="input w-full"
+      />
+    </label>
+  );
+
+  const detected = sockets[0] ? `Automatic (${sockets[0].runtime}: ${sockets[0].path})` : 'Automatic';
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Docker Connection</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Leave everything empty to use Docker on this computer. Set an address to manage containers on another
+        machine's Docker daemon - with TLS files when it's exposed with <code>--tlsverify</code>.
+      </p>
+      <div className="grid grid-cols-2 gap-4 text-sm">
+        {text('socket', 'Socket', detected)}
+        <datalist id="docker-sockets">
+          {sockets.map((s) => (
+            <option key={s.path} value={s.path}>{s.runtime}</option>
+          ))}
+        </datalist>
+        {text('url', 'Address', 'tcp://192.168.1.20:2376')}
+        {connection.url && (
+          <>
+            {text('tls_ca', 'TLS CA file', 'ca.pem')}
+            {text('tls_cert', 'TLS certificate file', 'cert.pem')}
+            {text('tls_key', 'TLS key file', 'key.pem')}
+          </>
+        )}
+        <label className="block">
+          <span className="input-label">API timeout (seconds)</span>
+          <input
+            type="number"
+            min={1}
+            value={connection.timeout_secs}
+            onChange={(e) => update({ timeout_secs: e.target.value === '' ? 0 : parseInt(e.target.value, 10) })}
+            className="input w-full"
+          />
+        </label>
+      </div>
+      {connection.url && (
+        <div className="mt-4 text-sm">
+          <label className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={!!connection.wake}
+              onChange={(e) => update({ wake: e.target.checked ? NEW_WAKE : null })}
+            />
+            Wake the host with Wake-on-LAN when it's asleep
+          </label>
+          {connection.wake && (
+            <div className="grid grid-cols-3 gap-4 mt-2">
+              <label className="block">
+                <span className="input-label">MAC address</span>
+                <input
+                  type="text"
+                  value={connection.wake.mac}
+                  onChange={(e) => setWake({ mac: e.target.value })}
+                  placeholder="00:11:22:aa:bb:cc"
+                  className="input w-full"
+                />
+              </label>
+              <label className="block">
+                <span className="input-label">Broadcast address</span>
+                <input
+                  type="text"
+                  value={connection.wake.broadcast}
+                  onChange={(e) => setWake({ broadcast: e.target.value })}
+                  className="input w-full"
+                />
+              </label>
+              <label className="block">
+                <span className="input-label">Boot wait (seconds)</span>
+                <input
+                  type="number"
+                  min={1}
+                  value={connection.wake.boot_secs}
+                  onChange={(e) => setWake({ boot_secs: e.target.value === '' ? 0 : parseInt(e.target.value, 10) })}
+                  className="input w-full"
+                />
+              </label>
+            </div>
+          )}
+        </div>
+      )}
+      <div className="flex items-center gap-3 mt-4">
+        <button onClick={runTest} disabled={busy} className="btn btn-secondary text-sm">
+          <Plug size={16} />
+          {busy ? 'Testing...' : 'Test'}
+        </button>
+        {connection.wake && !dirty && (
+          <button onClick={wakeNow} disabled={waking} className="btn btn-secondary text-sm">
+            <Power size={16} />
+            {waking ? 'Waking...' : 'Wake now'}
+          </button>
+        )}
+        <button onClick={save} className="btn btn-primary text-sm">
+          <Save size={16} />
+          Save
+        </button>
+        {saved && <span className="text-sm text-emerald-500">Saved</span>}
+        {test && (
+          <span className={`text-sm ${test.running ? 'text-emerald-500' : 'text-red-400'}`}>
+            {test.running ? `Connected to Docker ${test.version ?? ''} at ${test.endpoint ?? 'the default socket'}` : test.error}
+          </span>
+        )}
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
@@ -1,5 +1,7 @@
 import { RefreshCw, Download } from 'lucide-react';
 import type { DockerStatus } from '../types';
+import { DockerConnectionSettings } from './DockerConnectionSettings';
+import { SetupChecklist } from './SetupChecklist';
 
 interface Props {
   status: DockerStatus;
@@ -47,6 +49,15 @@ export function DockerRequired({ status, onRetry }: Props) {
           </button>
         </div>
 
+        <div className="mt-8">
+          <SetupChecklist onReady={onRetry} />
+        </div>
+
+        {/* Docker may be elsewhere - another socket, or another machine */}
+        <div className="mt-8 text-left">
+          <DockerConnectionSettings onSaved={onRetry} />
+        </div>
+
         <div className="mt-8 p-4 bg-slate-800 rounded-lg text-left">
           <h4 className="font-medium mb-2 text-sm">Why Docker?</h4>
           <ul className="text-xs text-slate-400 space-y-1">
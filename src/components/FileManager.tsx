@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import type { ReactNode } from 'react';
+import { invoke } from '../utils/backend';
 import { open } from '@tauri-apps/plugin-shell';
 import {
   Folder,
@@ -25,7 +26,10 @@ import {
   Database,
   Settings,
   Check,
+  Globe,
+  ScrollText,
 } from 'lucide-react';
+import type { ServerRoot, ServerRootKind } from '../types';
 
 interface FileEntry {
   name: string;
@@ -45,8 +49,17 @@ interface DirectoryContents {
 interface FileManagerProps {
   rootPath: string;
   serverName?: string;
+  /** Shows shortcuts to the server's config files, worlds and logs */
+  serverId?: string;
 }
 
+const ROOT_ICONS: Record<ServerRootKind, ReactNode> = {
+  data: <Home size={14} className="text-zinc-400" />,
+  config: <Settings size={14} className="text-purple-400" />,
+  save: <Globe size={14} className="text-green-400" />,
+  logs: <ScrollText size={14} className="text-zinc-400" />,
+};
+
 // File icons based on extension
 const getFileIcon = (entry: FileEntry) => {
   if (entry.is_dir) {
@@ -134,9 +147,10 @@ const formatDate = (timestamp: number | null): string => {
   return date.toLocaleDateString() + ' ' + date.toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
 };
 
-export function FileManager({ rootPath, serverName }: FileManagerProps) {
+export function FileManager({ rootPath, serverName, serverId }: FileManagerProps) {
   const [currentPath, setCurrentPath] = useState(rootPath);
   const [contents, setContents] = useState<DirectoryContents | null>(null);
+  const [roots, setRoots] = useState<ServerRoot[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   
@@ -178,6 +192,13 @@ export function FileManager({ rootPath, serverName }: FileManagerProps) {
     loadDirectory(rootPath);
   }, [rootPath, loadDirectory]);
 
+  useEffect(() => {
+    if (!serverId) return;
+    invoke<ServerRoot[]>('get_server_roots', { serverId })
+      .then((all) => setRoots(all.filter((r) => r.kind !== 'data')))
+      .catch(() => setRoots([]));
+  }, [serverId]);
+
   // Auto-refresh every 3 seconds for live updates
   useEffect(() => {
     const interval = setInterval(() => {
@@ -241,6 +262,22 @@ export function FileManager({ rootPath, serverName }: FileManagerProps) {
     }
   };
 
+  // Jump to a shortcut - a config file opens in the editor from its folder
+  const openRoot = async (root: ServerRoot) => {
+    if (root.is_dir) {
+      navigateTo(root.path);
+      return;
+    }
+    await loadDirectory(root.path.replace(/[\\/][^\\/]*$/, ''));
+    const name = root.path.split(/[\\/]/).pop() ?? root.label;
+    try {
+      const content = await invoke<string>('read_file_text', { path: root.path });
+      setEditingFile({ path: root.path, name, content, original: content });
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
   // Handle selection
   const handleSelect = (entry: FileEntry, e: React.MouseEvent) => {
     if (e.ctrlKey || e.metaKey) {
@@ -567,6 +604,23 @@ export function FileManager({ rootPath, serverName }: FileManagerProps) {
         ))}
       </div>
       
+      {/* Shortcuts */}
+      {roots.length > 0 && (
+        <div className="flex items-center gap-1 px-3 py-1.5 bg-zinc-800/60 border-b border-zinc-700 text-xs overflow-x-auto">
+          {roots.map((root) => (
+            <button
+              key={root.path}
+              onClick={() => openRoot(root)}
+              className="flex items-center gap-1 px-2 py-0.5 rounded hover:bg-zinc-700 text-zinc-300 whitespace-nowrap"
+              title={root.path}
+            >
+              {ROOT_ICONS[root.kind]}
+              {root.label}
+            </button>
+          ))}
+        </div>
+      )}
+
       {/* Error */}
       {error && (
         <div className="px-3 py-2 bg-red-500/10 border-b border-red-500/30 text-sm text-red-400 flex items-center justify-between">
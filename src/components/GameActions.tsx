@@ -0,0 +1,51 @@
+import { useEffect, useState } from 'react';
+import { Zap } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { GameAction } from '../types';
+
+interface GameActionsProps {
+  serverId: string;
+}
+
+/** Buttons for the actions the server's game declares, like "Save World" */
+export function GameActions({ serverId }: GameActionsProps) {
+  const [actions, setActions] = useState<GameAction[]>([]);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<GameAction[]>('list_game_actions', { serverId }).then(setActions).catch(() => setActions([]));
+  }, [serverId]);
+
+  if (actions.length === 0) return null;
+
+  const run = async (action: GameAction) => {
+    if (action.confirm && !confirm(`${action.label}?`)) return;
+    setBusy(action.id);
+    setError(null);
+    try {
+      await invoke('run_game_action', { serverId, action: action.id });
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  return (
+    <div className="flex flex-wrap items-center gap-2 px-4 py-2 border-b border-zinc-800">
+      <Zap size={14} className="text-zinc-500" />
+      {actions.map((action) => (
+        <button
+          key={action.id}
+          onClick={() => run(action)}
+          disabled={busy !== null}
+          title={action.description ?? action.command ?? undefined}
+          className="console-toolbar-btn"
+        >
+          {busy === action.id ? `${action.label}...` : action.label}
+        </button>
+      ))}
+      {error && <span className="text-xs text-red-400">{error}</span>}
+    </div>
+  );
+}
@@ -0,0 +1,140 @@
+import { useEffect, useState } from 'react';
+import { RefreshCw, Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { ImageCleanup, ImageRefresh, InstallImage, StaleImage } from '../types';
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(0)} MB`;
+  return `${(bytes / 1024 / 1024 / 1024).toFixed(2)} GB`;
+}
+
+const isUnused = (image: InstallImage) => image.servers.length === 0 && !image.game_image;
+
+/** Pull newer game images and recreate the containers still on an older one */
+export function GameImageSettings() {
+  const [stale, setStale] = useState<StaleImage[]>([]);
+  const [installImages, setInstallImages] = useState<InstallImage[]>([]);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<StaleImage[]>('get_stale_images').then(setStale).catch((e) => setError(String(e)));
+    loadInstallImages();
+  }, []);
+
+  const loadInstallImages = () => {
+    invoke<InstallImage[]>('get_install_images').then(setInstallImages).catch(() => {});
+  };
+
+  const cleanInstallImages = async () => {
+    setBusy('clean');
+    setError(null);
+    setMessage(null);
+    try {
+      const result = await invoke<ImageCleanup>('clean_install_images');
+      setMessage(
+        `Removed ${result.removed.length} install image${result.removed.length === 1 ? '' : 's'}, freeing ${formatSize(result.reclaimed_bytes)}`
+      );
+      if (result.failed.length > 0) setError(result.failed.join('\n'));
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+    loadInstallImages();
+  };
+
+  const removable = installImages.filter((i) => isUnused(i) && i.size_bytes !== null);
+
+  const refresh = async () => {
+    setBusy('refresh');
+    setError(null);
+    setMessage(null);
+    try {
+      const result = await invoke<ImageRefresh>('refresh_game_images');
+      setStale(result.stale);
+      setMessage(`Pulled ${result.pulled.length} image${result.pulled.length === 1 ? '' : 's'}`);
+      if (result.failed.length > 0) setError(result.failed.join('\n'));
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const recreate = async (serverId: string) => {
+    setBusy(serverId);
+    setError(null);
+    try {
+      await invoke('recreate_server_container', { serverId });
+      setStale((list) => list.filter((s) => s.server_id !== serverId));
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Game Images</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Newer versions of the Serverwave game images are pulled in the background. A server keeps running on the
+        image its container was created from until the container is recreated - its data isn't touched.
+      </p>
+      {stale.length > 0 && (
+        <div className="space-y-2 mb-4">
+          {stale.map((s) => (
+            <div key={s.server_id} className="flex items-center justify-between text-sm">
+              <span>
+                {s.server_name}
+                <span className="text-zinc-500 ml-2">{s.image}</span>
+              </span>
+              <button onClick={() => recreate(s.server_id)} disabled={busy !== null} className="btn btn-secondary text-sm">
+                {busy === s.server_id ? 'Recreating...' : 'Recreate'}
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+      <div className="flex items-center gap-3">
+        <button onClick={refresh} disabled={busy !== null} className="btn btn-secondary text-sm">
+          <RefreshCw size={16} className={busy === 'refresh' ? 'animate-spin' : ''} />
+          Pull now
+        </button>
+        {message && <span className="text-sm text-emerald-500">{message}</span>}
+        {stale.length === 0 && !message && <span className="text-sm text-zinc-400">All containers use the current images</span>}
+      </div>
+      {installImages.length > 0 && (
+        <div className="mt-6">
+          <h3 className="font-medium mb-1">Install Images</h3>
+          <p className="text-sm text-zinc-400 mb-3">
+            Install scripts run in these images. They're kept while a server's game uses them, since updates run the
+            script again, and pulled back if they're needed later.
+          </p>
+          <div className="space-y-1 mb-3">
+            {installImages.map((i) => (
+              <div key={i.image} className="flex items-center justify-between gap-4 text-sm">
+                <span>
+                  {i.image}
+                  {i.size_bytes !== null && <span className="text-zinc-500 ml-2">{formatSize(i.size_bytes)}</span>}
+                </span>
+                <span className="text-zinc-400 truncate">
+                  {i.size_bytes === null
+                    ? 'Not pulled'
+                    : i.servers.length > 0
+                      ? i.servers.join(', ')
+                      : i.game_image
+                        ? 'Also a game image'
+                        : 'Unused'}
+                </span>
+              </div>
+            ))}
+          </div>
+          <button onClick={cleanInstallImages} disabled={busy !== null || removable.length === 0} className="btn btn-secondary text-sm">
+            <Trash2 size={16} /> {busy === 'clean' ? 'Removing...' : 'Remove unused'}
+          </button>
+        </div>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2 whitespace-pre-line">{error}</p>}
+    </section>
+  );
+}
@@ -0,0 +1,149 @@
+import { useEffect, useState } from 'react';
+import { Download, Save, Upload } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import { useGamesStore } from '../stores/gamesStore';
+import type { AppSettings, GameSyncReport, GameSyncStatus } from '../types';
+
+function describe(report: GameSyncReport): string {
+  const when = new Date(report.at).toLocaleString();
+  const verb = report.direction === 'pull' ? 'Pulled' : 'Pushed';
+  const games = report.games.length === 0 ? 'nothing new' : report.games.join(', ');
+  return `${verb} ${games} at ${when}${report.commit ? ` (${report.commit})` : ''}`;
+}
+
+/** Custom game definitions shared through a Git repository or gist */
+export function GameLibrarySettings() {
+  const fetchGames = useGamesStore((s) => s.fetchGames);
+  const [settings, setSettings] = useState<AppSettings | null>(null);
+  const [status, setStatus] = useState<GameSyncStatus | null>(null);
+  const [token, setToken] = useState('');
+  const [busy, setBusy] = useState<'pull' | 'push' | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadStatus = () => {
+    invoke<GameSyncStatus>('get_game_sync_status').then(setStatus).catch((e) => setError(String(e)));
+  };
+
+  useEffect(() => {
+    invoke<AppSettings>('get_app_settings').then(setSettings).catch((e) => setError(String(e)));
+    loadStatus();
+    const unlistenSettings = listen<AppSettings>('settings-changed', (event) => setSettings(event.payload));
+    const unlistenSync = listen<GameSyncReport>('game-sync', () => loadStatus());
+    return () => {
+      unlistenSettings.then((fn) => fn());
+      unlistenSync.then((fn) => fn());
+    };
+  }, []);
+
+  if (!settings) return null;
+  const sync = settings.game_sync;
+
+  const save = async () => {
+    setError(null);
+    try {
+      setSettings(await invoke<AppSettings>('update_app_settings', { settings }));
+      if (token) {
+        await invoke('set_game_sync_token', { token });
+        setToken('');
+      }
+      loadStatus();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const removeToken = async () => {
+    setError(null);
+    try {
+      await invoke('set_game_sync_token', { token: null });
+      loadStatus();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const run = async (direction: 'pull' | 'push') => {
+    setBusy(direction);
+    setError(null);
+    try {
+      const report = await invoke<GameSyncReport>(direction === 'pull' ? 'pull_game_library' : 'push_game_library');
+      if (report.error) setError(report.error);
+      if (direction === 'pull') await fetchGames();
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+    loadStatus();
+  };
+
+  const last = status?.last;
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Game Library</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Share custom games through a Git repository or gist, one file per game. Saved games are pushed right away and
+        everyone pulls when they want the latest - the library's version wins over local edits. SSH keys and Git
+        credentials already set up on this computer are used; for https, an access token can be kept in the system
+        keychain instead.
+      </p>
+      <div className="grid grid-cols-2 gap-4 text-sm">
+        <label className="block">
+          <span className="input-label">Repository</span>
+          <input
+            value={sync.repo_url ?? ''}
+            onChange={(e) => setSettings({ ...settings, game_sync: { ...sync, repo_url: e.target.value || null } })}
+            placeholder="https://github.com/you/game-library.git"
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Branch</span>
+          <input
+            value={sync.branch ?? ''}
+            onChange={(e) => setSettings({ ...settings, game_sync: { ...sync, branch: e.target.value || null } })}
+            placeholder="The repository's default"
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Access token {status?.has_token && '(saved)'}</span>
+          <input
+            type="password"
+            value={token}
+            onChange={(e) => setToken(e.target.value)}
+            placeholder={status?.has_token ? 'Enter a new one to replace it' : 'Only for private https repositories'}
+            className="input w-full"
+          />
+        </label>
+        <label className="flex items-center gap-2 self-end pb-2">
+          <input
+            type="checkbox"
+            checked={sync.push_on_save}
+            onChange={(e) => setSettings({ ...settings, game_sync: { ...sync, push_on_save: e.target.checked } })}
+          />
+          Push games when they're saved
+        </label>
+      </div>
+      <div className="flex items-center gap-3 mt-4">
+        <button onClick={save} className="btn btn-primary text-sm">
+          <Save size={16} /> Save
+        </button>
+        {status?.has_token && (
+          <button onClick={removeToken} className="btn btn-secondary text-sm">
+            Remove token
+          </button>
+        )}
+        <button onClick={() => run('pull')} disabled={busy !== null || !status?.repo_url} className="btn btn-secondary text-sm">
+          <Download size={16} /> {busy === 'pull' ? 'Pulling...' : 'Pull'}
+        </button>
+        <button onClick={() => run('push')} disabled={busy !== null || !status?.repo_url} className="btn btn-secondary text-sm">
+          <Upload size={16} /> {busy === 'push' ? 'Pushing...' : 'Push all'}
+        </button>
+      </div>
+      {last && !last.error && <p className="text-sm text-zinc-400 mt-2">{describe(last)}</p>}
+      {last && last.failed.length > 0 && <p className="text-sm text-amber-400 mt-2 whitespace-pre-line">{last.failed.join('\n')}</p>}
+      {(error ?? last?.error) && <p className="text-sm text-red-400 mt-2">{error ?? last?.error}</p>}
+    </section>
+  );
+}
@@ -0,0 +1,89 @@
+import { useState } from 'react';
+import { Pin, PinOff, History } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { GameVersion, Server } from '../types';
+
+interface GameVersionsProps {
+  server: Server;
+  onChanged: () => void;
+}
+
+function label(version: GameVersion) {
+  return version.minecraft_version ? `${version.minecraft_version} build ${version.build}` : `build ${version.build}`;
+}
+
+function same(a: GameVersion | null | undefined, b: GameVersion) {
+  return !!a && a.build === b.build && a.minecraft_version === b.minecraft_version;
+}
+
+/** Builds the server had installed, to pin one or go back to it */
+export function GameVersions({ server, onChanged }: GameVersionsProps) {
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const versions = [...(server.versions ?? [])].reverse();
+  const current = server.versions?.[server.versions.length - 1];
+
+  const run = async (command: string, args: Record<string, unknown>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await invoke(command, { serverId: server.id, ...args });
+      onChanged();
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(false);
+  };
+
+  if (versions.length === 0) return null;
+
+  return (
+    <div className="p-4 bg-zinc-800/50 rounded-lg">
+      <div className="font-medium flex items-center gap-2 mb-2">
+        <History size={16} /> Installed Versions
+      </div>
+      {server.pinned_version && (
+        <p className="text-sm text-yellow-400 mb-2">
+          Pinned to {label(server.pinned_version)} - installs fetch it and update checks skip this server
+        </p>
+      )}
+      <div className="space-y-1">
+        {versions.map((version) => {
+          const pinned = same(server.pinned_version, version);
+          const isCurrent = current && same(current, version);
+          // Steam only serves the current build
+          const canRollBack = !isCurrent && version.minecraft_version !== null;
+          return (
+            <div key={`${version.minecraft_version}-${version.build}-${version.installed_at}`} className="flex items-center justify-between text-sm">
+              <span>
+                {label(version)}
+                <span className="text-zinc-500 ml-2">{new Date(version.installed_at).toLocaleDateString()}</span>
+                {isCurrent && <span className="ml-2 text-xs text-emerald-400">Installed</span>}
+              </span>
+              <span className="flex gap-2">
+                {canRollBack && (
+                  <button
+                    onClick={() => run('rollback_game_version', { build: version.build })}
+                    disabled={busy || server.status !== 'stopped'}
+                    className="btn btn-secondary text-xs"
+                  >
+                    Roll back
+                  </button>
+                )}
+                <button
+                  onClick={() => run('pin_game_version', { build: pinned ? null : version.build })}
+                  disabled={busy}
+                  className="btn btn-secondary text-xs"
+                  title={pinned ? 'Unpin' : 'Pin this build'}
+                >
+                  {pinned ? <PinOff size={14} /> : <Pin size={14} />}
+                </button>
+              </span>
+            </div>
+          );
+        })}
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
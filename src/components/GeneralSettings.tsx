@@ -0,0 +1,253 @@
+import { useEffect, useState } from 'react';
+import { Save } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { AppSettings } from '../types';
+import { MaintenancePolicyEditor } from './MaintenancePolicyEditor';
+
+const NOTIFICATION_LABELS = {
+  enabled: 'Desktop notifications',
+  on_crash: 'Notify on crash',
+  on_install_finished: 'Notify when installs finish',
+  on_low_disk: 'Notify when disk space runs low',
+} as const;
+
+export function GeneralSettings() {
+  const [settings, setSettings] = useState<AppSettings | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [saved, setSaved] = useState(false);
+
+  useEffect(() => {
+    invoke<AppSettings>('get_app_settings').then(setSettings).catch((e) => setError(String(e)));
+    const unlisten = listen<AppSettings>('settings-changed', (event) => setSettings(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (!settings) return null;
+
+  const update = (changes: Partial<AppSettings>) => {
+    setSaved(false);
+    setSettings({ ...settings, ...changes });
+  };
+
+  const save = async () => {
+    setError(null);
+    try {
+      setSettings(await invoke<AppSettings>('update_app_settings', { settings }));
+      setSaved(true);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const numberValue = (value: string) => (value === '' ? 0 : parseInt(value, 10));
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-4">General</h2>
+      <div className="grid grid-cols-2 gap-4 text-sm">
+        <label className="block">
+          <span className="input-label">Default memory (MB)</span>
+          <input
+            type="number"
+            value={settings.default_memory_mb ?? ''}
+            onChange={(e) => update({ default_memory_mb: e.target.value ? numberValue(e.target.value) : null })}
+            placeholder="Game recommendation"
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Concurrent installs</span>
+          <input
+            type="number"
+            min={1}
+            value={settings.max_concurrent_installs}
+            onChange={(e) => update({ max_concurrent_installs: numberValue(e.target.value) })}
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Port range start</span>
+          <input
+            type="number"
+            value={settings.port_range_start}
+            onChange={(e) => update({ port_range_start: numberValue(e.target.value) })}
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Port range end</span>
+          <input
+            type="number"
+            value={settings.port_range_end}
+            onChange={(e) => update({ port_range_end: numberValue(e.target.value) })}
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Backups kept per server</span>
+          <input
+            type="number"
+            min={1}
+            value={settings.backups.retention_count}
+            onChange={(e) => update({ backups: { ...settings.backups, retention_count: numberValue(e.target.value) } })}
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Notification webhook</span>
+          <input
+            type="text"
+            value={settings.notifications.webhook_url ?? ''}
+            onChange={(e) => update({ notifications: { ...settings.notifications, webhook_url: e.target.value || null } })}
+            placeholder="https://..."
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">CurseForge API key</span>
+          <input
+            type="password"
+            value={settings.curseforge_api_key ?? ''}
+            onChange={(e) => update({ curseforge_api_key: e.target.value || null })}
+            placeholder="From console.curseforge.com"
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Check for game updates every (hours)</span>
+          <input
+            type="number"
+            min={1}
+            value={settings.updates.interval_hours}
+            onChange={(e) => update({ updates: { ...settings.updates, interval_hours: numberValue(e.target.value) } })}
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Refresh game images every (hours)</span>
+          <input
+            type="number"
+            min={1}
+            value={settings.updates.image_interval_hours}
+            onChange={(e) => update({ updates: { ...settings.updates, image_interval_hours: numberValue(e.target.value) } })}
+            className="input w-full"
+          />
+        </label>
+      </div>
+      <div className="flex flex-wrap gap-4 text-sm mt-4">
+        {(['enabled', 'on_crash', 'on_install_finished', 'on_low_disk'] as const).map((key) => (
+          <label key={key} className="flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={settings.notifications[key]}
+              onChange={(e) => update({ notifications: { ...settings.notifications, [key]: e.target.checked } })}
+            />
+            {NOTIFICATION_LABELS[key]}
+          </label>
+        ))}
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.updates.check}
+            onChange={(e) => update({ updates: { ...settings.updates, check: e.target.checked } })}
+          />
+          Check for game updates
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.updates.auto_update}
+            onChange={(e) => update({ updates: { ...settings.updates, auto_update: e.target.checked } })}
+          />
+          Update stopped servers automatically
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.updates.refresh_images}
+            onChange={(e) => update({ updates: { ...settings.updates, refresh_images: e.target.checked } })}
+          />
+          Pull newer game images
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.open_auth_links}
+            onChange={(e) => update({ open_auth_links: e.target.checked })}
+          />
+          Open sign-in links from installers
+        </label>
+      </div>
+      <h3 className="font-semibold mt-6 mb-2">Disk Space</h3>
+      <p className="text-sm text-slate-400 mb-3">
+        A game saving its world to a full disk can corrupt it. Free space on the disk servers are on is checked in the
+        background, with a warning when it drops below the minimum.
+      </p>
+      <div className="grid grid-cols-2 gap-4 text-sm">
+        <label className="block">
+          <span className="input-label">Minimum free space (GB)</span>
+          <input
+            type="number"
+            min={0}
+            value={settings.disk_guard.min_free_gb}
+            onChange={(e) => update({ disk_guard: { ...settings.disk_guard, min_free_gb: numberValue(e.target.value) } })}
+            className="input w-full"
+          />
+        </label>
+        <label className="block">
+          <span className="input-label">Check every (minutes)</span>
+          <input
+            type="number"
+            min={1}
+            value={settings.disk_guard.check_interval_minutes}
+            onChange={(e) =>
+              update({ disk_guard: { ...settings.disk_guard, check_interval_minutes: numberValue(e.target.value) } })
+            }
+            className="input w-full"
+          />
+        </label>
+      </div>
+      <div className="flex flex-wrap gap-4 text-sm mt-4">
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.disk_guard.enabled}
+            onChange={(e) => update({ disk_guard: { ...settings.disk_guard, enabled: e.target.checked } })}
+          />
+          Watch free space
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.disk_guard.pause_installs}
+            onChange={(e) => update({ disk_guard: { ...settings.disk_guard, pause_installs: e.target.checked } })}
+          />
+          Hold installs while space is low
+        </label>
+        <label className="flex items-center gap-2">
+          <input
+            type="checkbox"
+            checked={settings.disk_guard.pause_backups}
+            onChange={(e) => update({ disk_guard: { ...settings.disk_guard, pause_backups: e.target.checked } })}
+          />
+          Hold backups while space is low
+        </label>
+      </div>
+      <h3 className="font-semibold mt-6 mb-2">Automation Hours</h3>
+      <p className="text-sm text-slate-400 mb-3">
+        For every server without its own hours (set in the server's settings). Times are this machine's local time.
+      </p>
+      <MaintenancePolicyEditor policy={settings.maintenance} onChange={(maintenance) => update({ maintenance })} />
+      <div className="flex items-center gap-3 mt-4">
+        <button onClick={save} className="btn btn-primary text-sm">
+          <Save size={16} />
+          Save
+        </button>
+        {saved && <span className="text-sm text-emerald-500">Saved</span>}
+        {error && <span className="text-sm text-red-400">{error}</span>}
+      </div>
+    </section>
+  );
+}
@@ -0,0 +1,209 @@
+import { useEffect, useState } from 'react';
+import { Play, Plus, Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import type { Hook, HookAction, HookTrigger } from '../types';
+
+const TRIGGER_LABELS: Record<HookTrigger, string> = {
+  started: 'Server started',
+  stopped: 'Server stopped',
+  crashed: 'Server crashed',
+  install_finished: 'Install finished',
+  install_failed: 'Install failed',
+  backup_done: 'Backup finished',
+  alert: 'Alert',
+};
+
+const ACTION_LABELS: Record<HookAction['type'], string> = {
+  command: 'Console command',
+  http: 'HTTP POST',
+  script: 'Run script',
+};
+
+function describeAction(action: HookAction): string {
+  switch (action.type) {
+    case 'command':
+      return `Console: ${action.command}`;
+    case 'http':
+      return `POST ${action.url}`;
+    case 'script':
+      return `Run ${[action.path, ...action.args].join(' ')}`;
+  }
+}
+
+export function HooksSettings() {
+  const { servers, fetchServers } = useServerStore();
+  const [hooks, setHooks] = useState<Hook[]>([]);
+  const [name, setName] = useState('');
+  const [trigger, setTrigger] = useState<HookTrigger>('crashed');
+  const [serverId, setServerId] = useState('');
+  const [actionType, setActionType] = useState<HookAction['type']>('http');
+  const [target, setTarget] = useState('');
+  const [error, setError] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const loadHooks = () => invoke<Hook[]>('list_hooks').then(setHooks).catch((e) => setError(String(e)));
+
+  useEffect(() => {
+    loadHooks();
+    fetchServers();
+  }, []);
+
+  const buildAction = (): HookAction => {
+    switch (actionType) {
+      case 'command':
+        return { type: 'command', command: target };
+      case 'http':
+        return { type: 'http', url: target.trim() };
+      case 'script': {
+        const [path, ...args] = target.trim().split(/\s+/);
+        return { type: 'script', path, args };
+      }
+    }
+  };
+
+  const addHook = async () => {
+    setError(null);
+    setMessage(null);
+    try {
+      const hook: Hook = {
+        id: '',
+        name: name.trim(),
+        enabled: true,
+        trigger,
+        server_id: serverId || null,
+        action: buildAction(),
+      };
+      await invoke('save_hook', { hook });
+      setName('');
+      setTarget('');
+      loadHooks();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const toggleHook = async (hook: Hook) => {
+    setError(null);
+    try {
+      await invoke('save_hook', { hook: { ...hook, enabled: !hook.enabled } });
+      loadHooks();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const deleteHook = async (hookId: string) => {
+    setError(null);
+    try {
+      await invoke('delete_hook', { hookId });
+      loadHooks();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const testHook = async (hook: Hook) => {
+    const testServer = hook.server_id ?? servers[0]?.id;
+    if (!testServer) return;
+    setError(null);
+    setMessage(null);
+    try {
+      await invoke('test_hook', { hookId: hook.id, serverId: testServer });
+      setMessage(`'${hook.name}' ran successfully`);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const serverName = (id: string | null) => (id ? servers.find((s) => s.id === id)?.name ?? id : 'all servers');
+
+  const placeholder = {
+    command: 'say Server is back!',
+    http: 'https://example.com/hook',
+    script: '/home/me/notify.sh --loud',
+  }[actionType];
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Hooks</h2>
+      <p className="text-slate-400 text-sm mb-4">
+        Run a console command, call a URL or start a script when something happens to a server. URLs get the
+        event as JSON; scripts get it on stdin.
+      </p>
+
+      {hooks.length > 0 && (
+        <div className="mb-4">
+          {hooks.map((hook) => (
+            <div key={hook.id} className="flex items-center justify-between py-2 border-b border-slate-700">
+              <label className="flex items-center gap-3">
+                <input type="checkbox" checked={hook.enabled} onChange={() => toggleHook(hook)} />
+                <div>
+                  <div>{hook.name}</div>
+                  <div className="text-xs text-slate-500">
+                    {TRIGGER_LABELS[hook.trigger]} on {serverName(hook.server_id)} · {describeAction(hook.action)}
+                  </div>
+                </div>
+              </label>
+              <div className="flex gap-2">
+                <button onClick={() => testHook(hook)} className="btn btn-secondary text-sm" title="Run now">
+                  <Play size={16} />
+                </button>
+                <button onClick={() => deleteHook(hook.id)} className="btn btn-secondary text-sm">
+                  <Trash2 size={16} />
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="space-y-3">
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name"
+            className="input flex-1"
+          />
+          <select value={trigger} onChange={(e) => setTrigger(e.target.value as HookTrigger)} className="input">
+            {(Object.keys(TRIGGER_LABELS) as HookTrigger[]).map((t) => (
+              <option key={t} value={t}>{TRIGGER_LABELS[t]}</option>
+            ))}
+          </select>
+          <select value={serverId} onChange={(e) => setServerId(e.target.value)} className="input">
+            <option value="">All servers</option>
+            {servers.map((server) => (
+              <option key={server.id} value={server.id}>{server.name}</option>
+            ))}
+          </select>
+        </div>
+        <div className="flex gap-2">
+          <select
+            value={actionType}
+            onChange={(e) => setActionType(e.target.value as HookAction['type'])}
+            className="input"
+          >
+            {(Object.keys(ACTION_LABELS) as HookAction['type'][]).map((a) => (
+              <option key={a} value={a}>{ACTION_LABELS[a]}</option>
+            ))}
+          </select>
+          <input
+            type="text"
+            value={target}
+            onChange={(e) => setTarget(e.target.value)}
+            placeholder={placeholder}
+            className="input flex-1"
+          />
+        </div>
+        <button onClick={addHook} disabled={!name.trim() || !target.trim()} className="btn btn-primary text-sm">
+          <Plus size={16} />
+          Add Hook
+        </button>
+        {message && <p className="text-sm text-green-400">{message}</p>}
+        {error && <p className="text-sm text-red-400">{error}</p>}
+      </div>
+    </section>
+  );
+}
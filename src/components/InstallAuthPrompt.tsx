@@ -0,0 +1,99 @@
+import { useEffect, useRef, useState } from 'react';
+import { Check, Copy, ExternalLink, Key } from 'lucide-react';
+import { open } from '@tauri-apps/plugin-shell';
+import { invoke, listen } from '../utils/backend';
+import type { AppSettings, ServerEvent } from '../types';
+
+interface InstallAuthPromptProps {
+  serverId: string;
+}
+
+/** Shows the sign-in link (and code) an installer is waiting on, like Hytale's downloader */
+export function InstallAuthPrompt({ serverId }: InstallAuthPromptProps) {
+  const [prompt, setPrompt] = useState<{ url: string; code: string | null } | null>(null);
+  const [copied, setCopied] = useState(false);
+  const openedUrl = useRef<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<ServerEvent>('server-event', (event) => {
+      const payload = event.payload;
+      if (payload.server_id !== serverId) return;
+      if (payload.type === 'InstallAuthRequired') {
+        setPrompt({ url: payload.url, code: payload.code });
+        // The code for a link can arrive on a later line - only open the link once
+        if (openedUrl.current !== payload.url) {
+          openedUrl.current = payload.url;
+          openIfWanted(payload.url);
+        }
+        setCopied(false);
+      } else if (
+        payload.type === 'InstallAuthCompleted' ||
+        (payload.type === 'InstallProgress' && payload.stage !== 'started') ||
+        (payload.type === 'StatusChanged' && (payload.status === 'stopped' || payload.status === 'error'))
+      ) {
+        setPrompt(null);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [serverId]);
+
+  const openIfWanted = async (url: string) => {
+    try {
+      const settings = await invoke<AppSettings>('get_app_settings');
+      if (settings.open_auth_links) await open(url);
+    } catch (e) {
+      console.error('Failed to open sign-in link:', e);
+    }
+  };
+
+  const copyCode = () => {
+    if (!prompt?.code) return;
+    navigator.clipboard.writeText(prompt.code);
+    setCopied(true);
+    setTimeout(() => setCopied(false), 2000);
+  };
+
+  if (prompt === null) return null;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="absolute inset-0 bg-black/70 backdrop-blur-sm" onClick={() => setPrompt(null)} />
+      <div className="relative bg-zinc-900 border border-indigo-500/50 rounded-xl shadow-2xl max-w-md w-full mx-4 p-6 space-y-4">
+        <div className="flex items-center gap-3">
+          <div className="w-12 h-12 rounded-lg bg-indigo-500/20 flex items-center justify-center">
+            <Key size={24} className="text-indigo-400" />
+          </div>
+          <div>
+            <h3 className="text-lg font-semibold">Sign-in Required</h3>
+            <p className="text-sm text-zinc-400">The server is waiting for you to log in</p>
+          </div>
+        </div>
+        <p className="text-xs text-zinc-500 font-mono break-all">{prompt.url}</p>
+        {prompt.code && (
+          <div className="p-3 bg-zinc-800 rounded-lg">
+            <div className="text-xs text-zinc-500 mb-1">Code</div>
+            <div className="flex items-center justify-between">
+              <code className="text-2xl font-mono font-bold text-white tracking-wider">{prompt.code}</code>
+              <button onClick={copyCode} className="p-2 hover:bg-zinc-700 rounded transition-colors">
+                {copied ? <Check size={16} className="text-green-500" /> : <Copy size={16} className="text-zinc-400" />}
+              </button>
+            </div>
+          </div>
+        )}
+        <div className="flex items-center gap-3">
+          <button onClick={() => open(prompt.url)} className="btn btn-primary flex-1">
+            <ExternalLink size={16} /> Open Sign-in Page
+          </button>
+          <button onClick={() => setPrompt(null)} className="btn btn-secondary">
+            Dismiss
+          </button>
+        </div>
+        <p className="text-xs text-zinc-500 text-center">
+          Once you've logged in, the server carries on by itself and this closes.
+        </p>
+      </div>
+    </div>
+  );
+}
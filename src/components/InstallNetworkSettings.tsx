@@ -0,0 +1,93 @@
+import { useEffect, useState } from 'react';
+import { Save } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { InstallNetwork, Server } from '../types';
+
+interface InstallNetworkSettingsProps {
+  server: Server;
+  /** The game's own setting, used while the server has none */
+  gameDefault: InstallNetwork | null;
+  onSaved: () => void;
+}
+
+function describe(network: InstallNetwork | null): string {
+  if (!network || network.mode === 'open') return 'anywhere';
+  if (network.allowed_domains.length === 0) return 'nowhere - offline';
+  return network.allowed_domains.join(', ');
+}
+
+/** Where the server's install scripts may connect - anywhere, a list of domains, or nowhere */
+export function InstallNetworkSettings({ server, gameDefault, onSaved }: InstallNetworkSettingsProps) {
+  const [network, setNetwork] = useState<InstallNetwork | null>(server.install_network ?? null);
+  const [domains, setDomains] = useState('');
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const saved = server.install_network ?? null;
+    setNetwork(saved);
+    setDomains(saved?.mode === 'restricted' ? saved.allowed_domains.join('\n') : '');
+  }, [server.id, server.install_network]);
+
+  const edited: InstallNetwork | null =
+    network?.mode === 'restricted'
+      ? { mode: 'restricted', allowed_domains: domains.split(/[\s,]+/).filter(Boolean) }
+      : network;
+  const changed = JSON.stringify(edited) !== JSON.stringify(server.install_network ?? null);
+
+  const save = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke('update_server_install_network', { serverId: server.id, installNetwork: edited });
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+    setSaving(false);
+  };
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold">Install Network</h3>
+        {changed && (
+          <button onClick={save} disabled={saving} className="btn btn-success text-sm">
+            <Save size={16} /> {saving ? 'Saving...' : 'Save'}
+          </button>
+        )}
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        Keeps install and update scripts from reaching the whole internet. Restricted installs go through a proxy that
+        only lets the listed domains and their subdomains through; SteamCMD can't use it.
+      </p>
+      <div className="flex gap-2 mb-4">
+        {[
+          { label: `Game default (${describe(gameDefault)})`, value: null },
+          { label: 'Anywhere', value: { mode: 'open' } as InstallNetwork },
+          { label: 'Only these domains', value: { mode: 'restricted', allowed_domains: [] } as InstallNetwork },
+        ].map((option) => (
+          <button
+            key={option.label}
+            onClick={() => setNetwork(option.value)}
+            className={`btn text-sm ${(network?.mode ?? null) === (option.value?.mode ?? null) ? 'btn-primary' : 'btn-secondary'}`}
+          >
+            {option.label}
+          </button>
+        ))}
+      </div>
+      {network?.mode === 'restricted' && (
+        <>
+          <textarea
+            value={domains}
+            onChange={(e) => setDomains(e.target.value)}
+            className="input font-mono text-sm h-28"
+            placeholder={'github.com\nobjects.githubusercontent.com'}
+          />
+          <p className="text-xs text-zinc-500 mt-1">One per line. Leave it empty to run installs offline.</p>
+        </>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,85 @@
+import { useEffect, useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { Download, Clock, AlertTriangle } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { InstallActivity, ServerEvent } from '../types';
+
+/** How often the current steps are refreshed while something is installing */
+const POLL_INTERVAL = 5000;
+
+function since(timestamp: string | null) {
+  return timestamp ? new Date(timestamp).toLocaleTimeString() : '';
+}
+
+/** Installs running, queued and failed across all servers */
+export function InstallQueue() {
+  const navigate = useNavigate();
+  const [queue, setQueue] = useState<InstallActivity[]>([]);
+
+  const refresh = () => {
+    invoke<InstallActivity[]>('get_install_queue').then(setQueue).catch(() => {});
+  };
+
+  useEffect(() => {
+    refresh();
+    const unlisten = listen<ServerEvent>('server-event', (event) => {
+      if (event.payload.type === 'InstallProgress') refresh();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const busy = queue.some((a) => a.phase !== 'failed');
+  useEffect(() => {
+    if (!busy) return;
+    const interval = window.setInterval(refresh, POLL_INTERVAL);
+    return () => window.clearInterval(interval);
+  }, [busy]);
+
+  if (queue.length === 0) return null;
+
+  return (
+    <section className="mb-8">
+      <h2 className="text-xl font-semibold mb-4 flex items-center gap-2">
+        <Download size={20} />
+        Installs
+      </h2>
+      <div className="card space-y-3">
+        {queue.map((activity) => (
+          <div
+            key={activity.server_id}
+            onClick={() => navigate(`/servers/${activity.server_id}`)}
+            className="flex items-center justify-between gap-4 cursor-pointer"
+          >
+            <div className="min-w-0">
+              <div className="font-medium">{activity.server_name}</div>
+              <div className="text-sm text-slate-400 truncate">
+                {activity.step ?? (activity.phase === 'queued' ? 'Waiting for other installs to finish' : activity.game_type)}
+              </div>
+            </div>
+            <div className="text-sm text-right shrink-0">
+              {activity.phase === 'installing' && (
+                <span className="text-emerald-400">Installing since {since(activity.started_at)}</span>
+              )}
+              {activity.phase === 'queued' && (
+                <span className="text-slate-400 flex items-center gap-1">
+                  <Clock size={14} /> Queued at {since(activity.queued_at)}
+                </span>
+              )}
+              {activity.phase === 'failed' && (
+                <span className="text-red-400 flex items-center gap-1">
+                  <AlertTriangle size={14} />
+                  {`Failed${activity.exit_code !== null ? ` (exit ${activity.exit_code})` : ''}${activity.failed_at ? ` at ${since(activity.failed_at)}` : ''}`}
+                </span>
+              )}
+              {activity.attempts > 0 && (
+                <div className="text-xs text-slate-500">{activity.attempts} failed attempt{activity.attempts === 1 ? '' : 's'}</div>
+              )}
+            </div>
+          </div>
+        ))}
+      </div>
+    </section>
+  );
+}
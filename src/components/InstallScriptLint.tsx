@@ -0,0 +1,56 @@
+import { useState } from 'react';
+import { FileCheck } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { ScriptLint } from '../types';
+
+interface InstallScriptLintProps {
+  script: string;
+  image: string;
+}
+
+/** Checks an install script's syntax in its image before anyone sits through an install */
+export function InstallScriptLint({ script, image }: InstallScriptLintProps) {
+  const [lint, setLint] = useState<ScriptLint | null>(null);
+  const [checking, setChecking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const check = async () => {
+    setChecking(true);
+    setError(null);
+    setLint(null);
+    try {
+      setLint(await invoke<ScriptLint>('lint_install_script', { script, image }));
+    } catch (e) {
+      setError(String(e));
+    }
+    setChecking(false);
+  };
+
+  return (
+    <div className="mt-2">
+      <button onClick={check} disabled={checking || !script.trim() || !image.trim()} className="btn btn-secondary text-sm">
+        <FileCheck size={16} /> {checking ? 'Checking...' : 'Check Script'}
+      </button>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+      {lint && (
+        <div className="mt-2 text-sm space-y-1">
+          <p className={lint.syntax_ok ? 'text-green-400' : 'text-red-400'}>
+            {lint.syntax_ok ? `No syntax errors (${lint.shell})` : `Syntax errors (${lint.shell}):`}
+          </p>
+          {lint.errors.map((line, i) => (
+            <p key={i} className="font-mono text-xs text-red-300">{line}</p>
+          ))}
+          {lint.shellcheck === null ? (
+            <p className="text-xs text-slate-500">shellcheck isn't in this image - only the syntax was checked</p>
+          ) : lint.shellcheck.length === 0 ? (
+            <p className="text-green-400">shellcheck found nothing</p>
+          ) : (
+            lint.shellcheck.map((line, i) => (
+              <p key={i} className="font-mono text-xs text-yellow-300">{line}</p>
+            ))
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
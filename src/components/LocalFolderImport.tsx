@@ -0,0 +1,166 @@
+import { useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { FolderInput, Loader2, Search } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import { useGamesStore } from '../stores/gamesStore';
+import type { LocalImportMode, LocalImportRequest, LocalScan, ServerResponse } from '../types';
+
+const formatSize = (bytes: number) =>
+  bytes >= 1024 ** 3 ? `${(bytes / 1024 ** 3).toFixed(1)} GB` : `${(bytes / 1024 ** 2).toFixed(0)} MB`;
+
+/** Take over a server folder that was run by hand on this machine */
+export function LocalFolderImport({ onError }: { onError: (error: string | null) => void }) {
+  const navigate = useNavigate();
+  const fetchServers = useServerStore((s) => s.fetchServers);
+  const games = useGamesStore((s) => s.games);
+
+  const [path, setPath] = useState('');
+  const [scan, setScan] = useState<LocalScan | null>(null);
+  const [gameType, setGameType] = useState('');
+  const [name, setName] = useState('');
+  const [port, setPort] = useState('');
+  const [mode, setMode] = useState<LocalImportMode>('copy');
+  const [config, setConfig] = useState<Record<string, string>>({});
+  const [busy, setBusy] = useState<'scan' | 'import' | null>(null);
+
+  const game = games.find((g) => g.game_type === gameType);
+
+  const runScan = async (asGame?: string) => {
+    onError(null);
+    setBusy('scan');
+    try {
+      const result = await invoke<LocalScan>('scan_local_server', { path: path.trim(), gameType: asGame ?? null });
+      setScan(result);
+      setGameType(result.game_type ?? '');
+      setConfig(result.config);
+      setPort(result.port ? String(result.port) : '');
+      if (!name) setName(result.path.split(/[\\/]/).filter(Boolean).pop() ?? '');
+    } catch (e) {
+      onError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const runImport = async () => {
+    onError(null);
+    setBusy('import');
+    const request: LocalImportRequest = {
+      path: path.trim(),
+      name: name.trim(),
+      gameType,
+      mode,
+      port: port ? parseInt(port, 10) : undefined,
+      config,
+    };
+    try {
+      const response = await invoke<ServerResponse>('import_local_server', { request });
+      await fetchServers();
+      if (response.server) navigate(`/servers/${response.server.id}`);
+    } catch (e) {
+      onError(String(e));
+    }
+    setBusy(null);
+  };
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-lg font-semibold mb-1">From a Folder on This Machine</h2>
+      <p className="text-sm text-slate-400 mb-4">
+        A server you've been running by hand, like a Paper or Rust folder. Its game and settings are read from the files.
+      </p>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          placeholder="/home/me/minecraft-server"
+          className="input flex-1"
+        />
+        <button onClick={() => runScan()} disabled={!path.trim() || busy !== null} className="btn btn-secondary">
+          {busy === 'scan' ? <Loader2 size={18} className="animate-spin" /> : <Search size={18} />}
+          Scan
+        </button>
+      </div>
+
+      {scan && (
+        <div className="space-y-4 mt-4 text-sm">
+          <p className="text-slate-400">
+            {formatSize(scan.size_bytes)} in {scan.path}
+            {!scan.game_type && ' - the game couldn\'t be recognised, pick it below'}
+          </p>
+          <div className="grid grid-cols-2 gap-4">
+            <label className="block">
+              <span className="input-label">Game</span>
+              <select
+                value={gameType}
+                onChange={(e) => {
+                  setGameType(e.target.value);
+                  if (e.target.value) runScan(e.target.value);
+                }}
+                className="input w-full"
+              >
+                <option value="">Choose a game...</option>
+                {games.map((g) => (
+                  <option key={g.game_type} value={g.game_type}>{g.name}</option>
+                ))}
+              </select>
+            </label>
+            <label className="block">
+              <span className="input-label">Name</span>
+              <input type="text" value={name} onChange={(e) => setName(e.target.value)} className="input w-full" />
+            </label>
+            <label className="block">
+              <span className="input-label">Port</span>
+              <input
+                type="number"
+                value={port}
+                onChange={(e) => setPort(e.target.value)}
+                placeholder="Next free port"
+                className="input w-full"
+              />
+            </label>
+          </div>
+
+          <div className="space-y-1">
+            <label className="flex items-center gap-2">
+              <input type="radio" checked={mode === 'copy'} onChange={() => setMode('copy')} />
+              Copy the files into the app's data folder and leave the original alone
+            </label>
+            <label className="flex items-center gap-2">
+              <input type="radio" checked={mode === 'inplace'} onChange={() => setMode('inplace')} />
+              Run it from where it is - deleting the server later keeps the folder
+            </label>
+          </div>
+
+          {game && Object.keys(config).length > 0 && (
+            <div>
+              <span className="input-label">Settings found</span>
+              <div className="space-y-2">
+                {Object.entries(config).map(([key, value]) => (
+                  <div key={key} className="flex items-center gap-2">
+                    <span className="w-48 text-slate-400 truncate">
+                      {game.variables.find((v) => v.env === key)?.name ?? key}
+                    </span>
+                    <input
+                      type="text"
+                      value={value}
+                      onChange={(e) => setConfig({ ...config, [key]: e.target.value })}
+                      className="input flex-1"
+                    />
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+
+          <p className="text-xs text-slate-500">Stop the server first so its files are consistent.</p>
+          <button onClick={runImport} disabled={!gameType || !name.trim() || busy !== null} className="btn btn-primary">
+            {busy === 'import' ? <Loader2 size={18} className="animate-spin" /> : <FolderInput size={18} />}
+            {busy === 'import' ? 'Importing...' : 'Import Server'}
+          </button>
+        </div>
+      )}
+    </section>
+  );
+}
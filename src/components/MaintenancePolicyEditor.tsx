@@ -0,0 +1,105 @@
+import { Plus, Trash2 } from 'lucide-react';
+import type { MaintenancePolicy, TimeWindow, Weekday } from '../types';
+
+const DAYS: Weekday[] = ['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun'];
+
+const NEW_WINDOW: TimeWindow = { days: [], start_hour: 18, start_minute: 0, end_hour: 23, end_minute: 0 };
+
+const pad = (n: number) => String(n).padStart(2, '0');
+const timeValue = (hour: number, minute: number) => `${pad(hour)}:${pad(minute)}`;
+const parseTime = (value: string) => value.split(':').map((part) => parseInt(part, 10) || 0);
+
+interface WindowListProps {
+  title: string;
+  hint: string;
+  windows: TimeWindow[];
+  onChange: (windows: TimeWindow[]) => void;
+}
+
+function WindowList({ title, hint, windows, onChange }: WindowListProps) {
+  const set = (index: number, changes: Partial<TimeWindow>) =>
+    onChange(windows.map((w, i) => (i === index ? { ...w, ...changes } : w)));
+
+  const toggleDay = (index: number, day: Weekday) => {
+    const days = windows[index].days;
+    set(index, { days: days.includes(day) ? days.filter((d) => d !== day) : DAYS.filter((d) => d === day || days.includes(d)) });
+  };
+
+  return (
+    <div>
+      <div className="flex items-center justify-between mb-1">
+        <span className="input-label mb-0">{title}</span>
+        <button onClick={() => onChange([...windows, NEW_WINDOW])} className="btn btn-secondary text-xs py-1">
+          <Plus size={14} /> Add
+        </button>
+      </div>
+      <p className="text-xs text-slate-500 mb-2">{hint}</p>
+      <div className="space-y-2">
+        {windows.map((w, index) => (
+          <div key={index} className="flex flex-wrap items-center gap-2">
+            <div className="flex gap-1">
+              {DAYS.map((day) => (
+                <button
+                  key={day}
+                  onClick={() => toggleDay(index, day)}
+                  className={`px-1.5 py-0.5 rounded text-xs ${
+                    w.days.length === 0 || w.days.includes(day) ? 'bg-sky-600 text-white' : 'bg-slate-700 text-slate-400'
+                  }`}
+                >
+                  {day}
+                </button>
+              ))}
+            </div>
+            <input
+              type="time"
+              value={timeValue(w.start_hour, w.start_minute)}
+              onChange={(e) => {
+                const [start_hour, start_minute] = parseTime(e.target.value);
+                set(index, { start_hour, start_minute });
+              }}
+              className="input py-1"
+            />
+            <span className="text-slate-500">to</span>
+            <input
+              type="time"
+              value={timeValue(w.end_hour, w.end_minute)}
+              onChange={(e) => {
+                const [end_hour, end_minute] = parseTime(e.target.value);
+                set(index, { end_hour, end_minute });
+              }}
+              className="input py-1"
+            />
+            <button onClick={() => onChange(windows.filter((_, i) => i !== index))} className="text-slate-400 hover:text-red-400">
+              <Trash2 size={16} />
+            </button>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
+
+interface MaintenancePolicyEditorProps {
+  policy: MaintenancePolicy;
+  onChange: (policy: MaintenancePolicy) => void;
+}
+
+/** Quiet hours and maintenance windows, in the machine's local time */
+export function MaintenancePolicyEditor({ policy, onChange }: MaintenancePolicyEditorProps) {
+  return (
+    <div className="space-y-4 text-sm">
+      <WindowList
+        title="Quiet hours"
+        hint="Scheduled wipes and automatic updates never run during these, like peak play hours."
+        windows={policy.quiet_hours}
+        onChange={(quiet_hours) => onChange({ ...policy, quiet_hours })}
+      />
+      <WindowList
+        title="Maintenance windows"
+        hint="When set, automation only runs inside one of these. A wipe that falls outside waits for the next one."
+        windows={policy.windows}
+        onChange={(windows) => onChange({ ...policy, windows })}
+      />
+    </div>
+  );
+}
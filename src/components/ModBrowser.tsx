@@ -0,0 +1,228 @@
+import { useEffect, useState } from 'react';
+import { Download, RefreshCw, Search } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type {
+  ContentUpdateResult,
+  CurseForgeProject,
+  InstalledContent,
+  InstalledMod,
+  ModpackInstall,
+  ModrinthProject,
+} from '../types';
+
+interface ModBrowserProps {
+  serverId: string;
+}
+
+type Source = 'modrinth' | 'curseforge' | 'modpacks';
+
+interface Result {
+  id: string;
+  title: string;
+  description: string;
+  author: string;
+  icon_url: string | null;
+}
+
+const SOURCE_LABELS: Record<Source, string> = {
+  modrinth: 'Modrinth',
+  curseforge: 'CurseForge',
+  modpacks: 'CurseForge Modpacks',
+};
+
+export function ModBrowser({ serverId }: ModBrowserProps) {
+  const [query, setQuery] = useState('');
+  const [source, setSource] = useState<Source>('modrinth');
+  const [results, setResults] = useState<Result[]>([]);
+  const [installed, setInstalled] = useState<InstalledContent[]>([]);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const search = async (from: Source = source) => {
+    setBusy('search');
+    setError(null);
+    try {
+      if (from === 'modrinth') {
+        const hits = await invoke<ModrinthProject[]>('search_modrinth', { query, serverId });
+        setResults(hits.map((p) => ({ ...p, id: p.project_id })));
+      } else {
+        const projects = await invoke<CurseForgeProject[]>('search_curseforge', {
+          query,
+          serverId,
+          modpacks: from === 'modpacks',
+        });
+        setResults(projects.map((p) => ({ ...p, id: String(p.id), title: p.name, description: p.summary })));
+      }
+    } catch (e) {
+      setResults([]);
+      setError(String(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const loadInstalled = async () => {
+    setBusy('installed');
+    try {
+      setInstalled(await invoke<InstalledContent[]>('list_installed_content', { serverId }));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const updateAll = async () => {
+    setBusy('update-all');
+    setError(null);
+    setMessage(null);
+    try {
+      const results = await invoke<ContentUpdateResult[]>('update_all_content', { serverId });
+      const updated = results.filter((r) => r.outcome === 'updated');
+      const failed = results.filter((r) => r.outcome === 'failed');
+      setMessage(`Updated ${updated.length} of ${results.length}. Restart the server to load the new versions.`);
+      if (failed.length > 0) {
+        setError(failed.map((r) => `${r.file_name}: ${r.message}`).join('\n'));
+      }
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+      loadInstalled();
+    }
+  };
+
+  const install = async (project: string, version: string | null = null, from: Source = 'modrinth') => {
+    setBusy(project);
+    setError(null);
+    setMessage(null);
+    try {
+      if (from === 'modpacks') {
+        const pack = await invoke<ModpackInstall>('install_curseforge_modpack', { serverId, modId: Number(project) });
+        const loader = pack.loader ? ` It needs ${pack.loader} on Minecraft ${pack.minecraft_version ?? '?'}.` : '';
+        const manual =
+          pack.manual_downloads.length > 0 ? ` Download these by hand into mods/: ${pack.manual_downloads.join(', ')}` : '';
+        setMessage(`Installed ${pack.name} ${pack.version} (${pack.files} files).${loader}${manual}`);
+        return;
+      }
+      const result =
+        from === 'curseforge'
+          ? await invoke<InstalledMod>('install_curseforge_mod', { serverId, modId: Number(project) })
+          : await invoke<InstalledMod>('install_mod', { serverId, project, version });
+      const replaced = result.replaced.length > 0 ? `, replacing ${result.replaced.join(', ')}` : '';
+      const deps = result.dependencies.length > 0 ? ` with ${result.dependencies.join(', ')}` : '';
+      setMessage(`Installed ${result.file_name}${deps}${replaced}. Restart the server to load it.`);
+      if (result.warnings.length > 0) {
+        setError(result.warnings.join('\n'));
+      }
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+      loadInstalled();
+    }
+  };
+
+  useEffect(() => {
+    search();
+    loadInstalled();
+  }, [serverId]);
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-4">
+        <h3 className="text-lg font-semibold">Mods &amp; Plugins</h3>
+        <div className="flex gap-2">
+          <button onClick={loadInstalled} disabled={busy !== null} className="btn btn-secondary text-sm">
+            <RefreshCw size={16} className={busy === 'installed' ? 'animate-spin' : ''} />
+          </button>
+          <button
+            onClick={updateAll}
+            disabled={busy !== null || !installed.some((c) => c.update_available)}
+            className="btn btn-primary text-sm"
+          >
+            Update All
+          </button>
+        </div>
+      </div>
+
+      {installed.length > 0 && (
+        <div className="mb-4">
+          {installed.map((item) => (
+            <div key={item.file_name} className="flex items-center justify-between py-2 border-b border-zinc-800 text-sm">
+              <div className="min-w-0">
+                <div className="truncate">{item.name ?? item.file_name}</div>
+                <div className="text-xs text-zinc-500">
+                  {item.source ? `${item.source} · ${item.version ?? '?'}` : 'Not on Modrinth or CurseForge'}
+                </div>
+              </div>
+              {item.update_available && (
+                <span className="text-xs text-emerald-400">Update: {item.latest_version}</span>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="flex gap-2 mb-4">
+        <select
+          value={source}
+          onChange={(e) => {
+            const next = e.target.value as Source;
+            setSource(next);
+            search(next);
+          }}
+          className="input"
+        >
+          {(Object.keys(SOURCE_LABELS) as Source[]).map((s) => (
+            <option key={s} value={s}>{SOURCE_LABELS[s]}</option>
+          ))}
+        </select>
+        <input
+          type="text"
+          value={query}
+          onChange={(e) => setQuery(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && search()}
+          placeholder={`Search ${SOURCE_LABELS[source]}`}
+          className="input flex-1"
+        />
+        <button onClick={() => search()} disabled={busy !== null} className="btn btn-secondary">
+          <Search size={16} />
+        </button>
+      </div>
+
+      {message && <p className="text-sm text-green-400 mb-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mb-2 whitespace-pre-line">{error}</p>}
+
+      <div className="space-y-2">
+        {results.map((project) => (
+          <div key={project.id} className="flex items-center gap-3 py-2 border-b border-zinc-800">
+            {project.icon_url ? (
+              <img src={project.icon_url} alt="" className="w-10 h-10 rounded" />
+            ) : (
+              <div className="w-10 h-10 rounded bg-zinc-800" />
+            )}
+            <div className="flex-1 min-w-0">
+              <div className="font-medium">
+                {project.title} <span className="text-xs text-zinc-500">by {project.author}</span>
+              </div>
+              <div className="text-sm text-zinc-400 truncate">{project.description}</div>
+            </div>
+            <button
+              onClick={() => install(project.id, null, source)}
+              disabled={busy !== null}
+              className="btn btn-primary text-sm"
+            >
+              <Download size={16} className={busy === project.id ? 'animate-pulse' : ''} />
+              Install
+            </button>
+          </div>
+        ))}
+        {results.length === 0 && busy !== 'search' && (
+          <p className="text-sm text-zinc-500">Nothing found for this server's loader and version</p>
+        )}
+      </div>
+    </div>
+  );
+}
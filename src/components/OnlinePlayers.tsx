@@ -0,0 +1,69 @@
+import { useEffect, useState } from 'react';
+import { RefreshCw } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { ServerQuery } from '../types';
+
+const POLL_MS = 30_000;
+
+/** Who's on now, from the server's answer to a Steam query or Minecraft server list ping */
+export function OnlinePlayers({ serverId, running }: { serverId: string; running: boolean }) {
+  const [query, setQuery] = useState<ServerQuery | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    setLoading(true);
+    try {
+      setQuery(await invoke<ServerQuery>('query_server', { serverId }));
+      setError(null);
+    } catch (e) {
+      setError(String(e));
+    }
+    setLoading(false);
+  };
+
+  useEffect(() => {
+    setQuery(null);
+    if (!running) return;
+    load();
+    const timer = setInterval(load, POLL_MS);
+    return () => clearInterval(timer);
+  }, [serverId, running]);
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-4">
+        <div className="min-w-0">
+          <h3 className="font-semibold">Online</h3>
+          {query && (
+            <p className="text-xs text-zinc-500 truncate">
+              {query.name}
+              {query.map && ` · ${query.map}`}
+            </p>
+          )}
+        </div>
+        <div className="flex items-center gap-3">
+          {query && (
+            <span className="text-lg font-semibold">
+              {query.players}
+              <span className="text-zinc-500"> / {query.max_players}</span>
+            </span>
+          )}
+          <button onClick={load} disabled={loading || !running} className="btn btn-secondary text-sm">
+            <RefreshCw size={16} className={loading ? 'animate-spin' : ''} />
+          </button>
+        </div>
+      </div>
+      {!running && <p className="text-sm text-zinc-500">Start the server to see who's on</p>}
+      {query?.player_names.map((name) => (
+        <div key={name} className="py-2 border-b border-zinc-800 text-sm truncate">
+          {name}
+        </div>
+      ))}
+      {query && query.players > 0 && query.player_names.length === 0 && (
+        <p className="text-sm text-zinc-500">The server doesn't list who they are</p>
+      )}
+      {running && error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
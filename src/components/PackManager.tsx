@@ -0,0 +1,157 @@
+import { useEffect, useState } from 'react';
+import { Plus, X } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { Datapack, ResourcePack } from '../types';
+
+interface PackManagerProps {
+  serverId: string;
+}
+
+export function PackManager({ serverId }: PackManagerProps) {
+  const [datapacks, setDatapacks] = useState<Datapack[]>([]);
+  const [datapackSource, setDatapackSource] = useState('');
+  const [pack, setPack] = useState<ResourcePack | null>(null);
+  const [packSource, setPackSource] = useState('');
+  const [publicUrl, setPublicUrl] = useState('');
+  const [required, setRequired] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const isUrl = /^https?:\/\//.test(packSource.trim());
+
+  const load = async () => {
+    try {
+      setDatapacks(await invoke<Datapack[]>('list_datapacks', { serverId }));
+      setPack(await invoke<ResourcePack | null>('get_resource_pack', { serverId }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const run = async (action: () => Promise<string>) => {
+    setBusy(true);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+      load();
+    }
+  };
+
+  const installDatapack = () =>
+    run(async () => {
+      const installed = await invoke<Datapack>('install_datapack', { serverId, source: datapackSource });
+      setDatapackSource('');
+      return `Added ${installed.name}. Run /reload or restart the server to load it.`;
+    });
+
+  const removeDatapack = (name: string) =>
+    run(async () => {
+      await invoke('remove_datapack', { serverId, name });
+      return `Removed ${name}.`;
+    });
+
+  const applyPack = () =>
+    run(async () => {
+      const result = isUrl
+        ? await invoke<ResourcePack>('set_resource_pack', { serverId, url: packSource, required })
+        : await invoke<ResourcePack>('host_resource_pack', { serverId, path: packSource, publicUrl, required });
+      setPackSource('');
+      return `Players get ${result.url} (SHA1 ${result.sha1}) after a restart.`;
+    });
+
+  const clearPack = () =>
+    run(async () => {
+      await invoke('clear_resource_pack', { serverId });
+      return 'Removed the resource pack. Restart the server to apply.';
+    });
+
+  useEffect(() => {
+    load();
+  }, [serverId]);
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-4">Datapacks</h3>
+      <div className="mb-4">
+        {datapacks.map((datapack) => (
+          <div key={datapack.name} className="flex items-center justify-between py-2 border-b border-zinc-800 text-sm">
+            <span className="truncate">
+              {datapack.name}
+              {datapack.folder && <span className="ml-2 text-xs text-zinc-500">folder</span>}
+            </span>
+            <button onClick={() => removeDatapack(datapack.name)} disabled={busy} className="btn btn-secondary text-sm">
+              <X size={16} />
+            </button>
+          </div>
+        ))}
+        {datapacks.length === 0 && <p className="text-sm text-zinc-500">The active world has no datapacks</p>}
+      </div>
+      <div className="flex gap-2 mb-6">
+        <input
+          type="text"
+          value={datapackSource}
+          onChange={(e) => setDatapackSource(e.target.value)}
+          placeholder="Datapack .zip, folder or download URL"
+          className="input flex-1"
+        />
+        <button onClick={installDatapack} disabled={busy || !datapackSource} className="btn btn-primary">
+          <Plus size={16} /> Add
+        </button>
+      </div>
+
+      <h3 className="text-lg font-semibold mb-2">Resource Pack</h3>
+      {pack ? (
+        <div className="flex items-center justify-between mb-4 text-sm">
+          <div className="min-w-0">
+            <div className="truncate">{pack.url}</div>
+            <div className="text-xs text-zinc-500">
+              SHA1 {pack.sha1 || 'not set'}
+              {pack.required && ' · required'}
+            </div>
+          </div>
+          <button onClick={clearPack} disabled={busy} className="btn btn-secondary text-sm">
+            <X size={16} />
+          </button>
+        </div>
+      ) : (
+        <p className="text-sm text-zinc-500 mb-4">No resource pack</p>
+      )}
+      <div className="space-y-2">
+        <input
+          type="text"
+          value={packSource}
+          onChange={(e) => setPackSource(e.target.value)}
+          placeholder="Pack URL, or a .zip on this computer to host"
+          className="input w-full"
+        />
+        {packSource && !isUrl && (
+          <input
+            type="text"
+            value={publicUrl}
+            onChange={(e) => setPublicUrl(e.target.value)}
+            placeholder="Address players reach this app's API at, e.g. http://203.0.113.5:7450"
+            className="input w-full"
+          />
+        )}
+        <div className="flex items-center justify-between">
+          <label className="flex items-center gap-2 text-sm">
+            <input type="checkbox" checked={required} onChange={(e) => setRequired(e.target.checked)} />
+            Players must accept it
+          </label>
+          <button onClick={applyPack} disabled={busy || !packSource || (!isUrl && !publicUrl)} className="btn btn-primary">
+            {busy ? 'Working...' : 'Set Pack'}
+          </button>
+        </div>
+      </div>
+
+      {message && <p className="text-sm text-green-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
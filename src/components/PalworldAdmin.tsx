@@ -0,0 +1,191 @@
+import { useEffect, useState } from 'react';
+import { Ban, Megaphone, RefreshCw, Save, UserX } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { PalworldPlayer, PalworldStatus } from '../types';
+
+interface PalworldAdminProps {
+  serverId: string;
+  running: boolean;
+}
+
+const formatUptime = (seconds: number) => {
+  const hours = Math.floor(seconds / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  return hours > 0 ? `${hours}h ${minutes}m` : `${minutes}m`;
+};
+
+export function PalworldAdmin({ serverId, running }: PalworldAdminProps) {
+  const [status, setStatus] = useState<PalworldStatus | null>(null);
+  const [announcement, setAnnouncement] = useState('');
+  const [unbanId, setUnbanId] = useState('');
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () => {
+    if (!running) return;
+    invoke<PalworldStatus>('get_palworld_status', { serverId })
+      .then((s) => {
+        setStatus(s);
+        setError(null);
+      })
+      .catch((e) => setError(String(e)));
+  };
+
+  useEffect(() => {
+    load();
+    const interval = setInterval(load, 15000);
+    return () => clearInterval(interval);
+  }, [serverId, running]);
+
+  const run = async (key: string, action: () => Promise<string>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const announce = () =>
+    run('announce', async () => {
+      await invoke('palworld_announce', { serverId, message: announcement });
+      setAnnouncement('');
+      return 'Announced.';
+    });
+
+  const kick = (player: PalworldPlayer) =>
+    run(`kick-${player.user_id}`, async () => {
+      await invoke('palworld_kick', { serverId, userId: player.user_id, message: null });
+      return `Kicked ${player.name}.`;
+    });
+
+  const ban = (player: PalworldPlayer) => {
+    if (!confirm(`Ban ${player.name}? They can't join again until unbanned.`)) return;
+    run(`ban-${player.user_id}`, async () => {
+      await invoke('palworld_ban', { serverId, userId: player.user_id, message: null });
+      return `Banned ${player.name} (${player.user_id}).`;
+    });
+  };
+
+  const unban = () =>
+    run('unban', async () => {
+      await invoke('palworld_unban', { serverId, userId: unbanId.trim() });
+      setUnbanId('');
+      return `Unbanned ${unbanId.trim()}.`;
+    });
+
+  const save = () =>
+    run('save', async () => {
+      await invoke('palworld_save', { serverId });
+      return 'World saved.';
+    });
+
+  if (!running) {
+    return (
+      <div className="card">
+        <h3 className="text-lg font-semibold mb-2">Palworld</h3>
+        <p className="text-sm text-zinc-400">Start the server to see who's online and manage it.</p>
+      </div>
+    );
+  }
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold">Palworld</h3>
+        <div className="flex gap-2">
+          <button onClick={load} disabled={busy !== null} className="btn btn-secondary text-sm">
+            <RefreshCw size={16} />
+          </button>
+          <button onClick={save} disabled={busy !== null} className="btn btn-primary text-sm">
+            <Save size={16} className={busy === 'save' ? 'animate-pulse' : ''} />
+            Save World
+          </button>
+        </div>
+      </div>
+
+      {status && (
+        <p className="text-sm text-zinc-400 mb-4">
+          {status.info.servername} {status.info.version} - {status.metrics.currentplayernum}/
+          {status.metrics.maxplayernum} players, {status.metrics.serverfps} FPS (
+          {status.metrics.serverframetime.toFixed(1)} ms), up {formatUptime(status.metrics.uptime)}
+          {status.metrics.days !== null && `, day ${status.metrics.days}`}
+        </p>
+      )}
+
+      <div className="flex gap-2 mb-4">
+        <input
+          value={announcement}
+          onChange={(e) => setAnnouncement(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && announcement.trim() && announce()}
+          placeholder="Message everyone on the server"
+          className="input flex-1 text-sm"
+        />
+        <button onClick={announce} disabled={busy !== null || !announcement.trim()} className="btn btn-secondary text-sm">
+          <Megaphone size={16} />
+          Announce
+        </button>
+      </div>
+
+      {status && status.players.length === 0 && <p className="text-sm text-zinc-400">Nobody's online.</p>}
+      {status && status.players.length > 0 && (
+        <table className="w-full text-sm">
+          <thead className="text-left text-zinc-400">
+            <tr>
+              <th className="py-1">Player</th>
+              <th>Level</th>
+              <th>Ping</th>
+              <th>User ID</th>
+              <th />
+            </tr>
+          </thead>
+          <tbody>
+            {status.players.map((player) => (
+              <tr key={player.user_id} className="border-t border-zinc-800">
+                <td className="py-1">
+                  {player.name}
+                  {player.account_name && player.account_name !== player.name && (
+                    <span className="text-zinc-500"> ({player.account_name})</span>
+                  )}
+                </td>
+                <td>{player.level}</td>
+                <td>{Math.round(player.ping)} ms</td>
+                <td className="font-mono text-xs">{player.user_id}</td>
+                <td className="text-right whitespace-nowrap">
+                  <button onClick={() => kick(player)} disabled={busy !== null} className="btn btn-secondary text-xs mr-1">
+                    <UserX size={14} />
+                    Kick
+                  </button>
+                  <button onClick={() => ban(player)} disabled={busy !== null} className="btn btn-secondary text-xs text-red-400">
+                    <Ban size={14} />
+                    Ban
+                  </button>
+                </td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+
+      <div className="flex gap-2 mt-4">
+        <input
+          value={unbanId}
+          onChange={(e) => setUnbanId(e.target.value)}
+          placeholder="User ID to unban, e.g. steam_76561198000000000"
+          className="input flex-1 text-sm font-mono"
+        />
+        <button onClick={unban} disabled={busy !== null || !unbanId.trim()} className="btn btn-secondary text-sm">
+          Unban
+        </button>
+      </div>
+
+      {message && <p className="text-sm text-green-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
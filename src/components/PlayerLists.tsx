@@ -0,0 +1,120 @@
+import { useEffect, useState } from 'react';
+import { Plus, X } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { PlayerEntry, PlayerList } from '../types';
+
+interface PlayerListsProps {
+  serverId: string;
+  bedrock: boolean;
+}
+
+const LIST_LABELS: Record<PlayerList, string> = {
+  whitelist: 'Whitelist',
+  ops: 'Operators',
+  bans: 'Bans',
+  allowlist: 'Allowlist',
+};
+
+export function PlayerLists({ serverId, bedrock }: PlayerListsProps) {
+  const lists: PlayerList[] = bedrock ? ['allowlist'] : ['whitelist', 'ops', 'bans'];
+  const [list, setList] = useState<PlayerList>(lists[0]);
+  const [players, setPlayers] = useState<PlayerEntry[]>([]);
+  const [name, setName] = useState('');
+  const [reason, setReason] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const change = async (action: () => Promise<PlayerEntry[]>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      setPlayers(await action());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const add = () =>
+    change(async () => {
+      const result = await invoke<PlayerEntry[]>('add_player', {
+        serverId,
+        list,
+        name,
+        reason: list === 'bans' && reason ? reason : null,
+      });
+      setName('');
+      setReason('');
+      return result;
+    });
+
+  const remove = (player: string) => change(() => invoke<PlayerEntry[]>('remove_player', { serverId, list, name: player }));
+
+  useEffect(() => {
+    change(() => invoke<PlayerEntry[]>('list_players', { serverId, list }));
+  }, [serverId, list]);
+
+  return (
+    <div className="card">
+      <div className="flex gap-2 mb-4">
+        {lists.map((l) => (
+          <button
+            key={l}
+            onClick={() => setList(l)}
+            className={`btn text-sm ${l === list ? 'btn-primary' : 'btn-secondary'}`}
+          >
+            {LIST_LABELS[l]}
+          </button>
+        ))}
+      </div>
+
+      <div className="mb-4">
+        {players.map((player) => (
+          <div key={player.uuid ?? player.name} className="flex items-center justify-between py-2 border-b border-zinc-800 text-sm">
+            <div className="min-w-0">
+              <div className="truncate">{player.name}</div>
+              <div className="text-xs text-zinc-500">
+                {player.uuid}
+                {player.level !== null && ` · level ${player.level}`}
+                {player.reason && ` · ${player.reason}`}
+                {player.expires && player.expires !== 'forever' && ` · until ${player.expires}`}
+              </div>
+            </div>
+            <button onClick={() => remove(player.name)} disabled={busy} className="btn btn-secondary text-sm">
+              <X size={16} />
+            </button>
+          </div>
+        ))}
+        {players.length === 0 && <p className="text-sm text-zinc-500">Nobody on the {LIST_LABELS[list].toLowerCase()}</p>}
+      </div>
+
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && name && add()}
+          placeholder={bedrock ? 'Gamertag' : 'Username'}
+          className="input flex-1"
+        />
+        {list === 'bans' && (
+          <input
+            type="text"
+            value={reason}
+            onChange={(e) => setReason(e.target.value)}
+            placeholder="Reason (optional)"
+            className="input flex-1"
+          />
+        )}
+        <button onClick={add} disabled={busy || !name} className="btn btn-primary">
+          <Plus size={16} /> Add
+        </button>
+      </div>
+      <p className="text-xs text-zinc-500 mt-2">
+        While the server runs, changes go through its console so it doesn't overwrite them.
+      </p>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,88 @@
+import { useEffect, useState } from 'react';
+import { RefreshCw } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { PlayerSession, SessionRange } from '../types';
+
+const RANGES = [
+  { label: '24 hours', hours: 24 },
+  { label: '7 days', hours: 24 * 7 },
+  { label: '30 days', hours: 24 * 30 },
+  { label: 'All', hours: null },
+] as const;
+
+function formatDuration(secs: number): string {
+  const hours = Math.floor(secs / 3600);
+  const minutes = Math.floor((secs % 3600) / 60);
+  if (hours > 0) return `${hours}h ${minutes}m`;
+  return minutes > 0 ? `${minutes}m` : `${secs}s`;
+}
+
+/** Who played when, from the joins and leaves in the server's console */
+export function PlayerSessions({ serverId }: { serverId: string }) {
+  const [hours, setHours] = useState<number | null>(24);
+  const [sessions, setSessions] = useState<PlayerSession[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    setLoading(true);
+    setError(null);
+    const range: SessionRange = {
+      from: hours === null ? null : new Date(Date.now() - hours * 3600 * 1000).toISOString(),
+      to: null,
+    };
+    try {
+      setSessions(await invoke<PlayerSession[]>('get_player_sessions', { serverId, range }));
+    } catch (e) {
+      setError(String(e));
+    }
+    setLoading(false);
+  };
+
+  useEffect(() => {
+    load();
+  }, [serverId, hours]);
+
+  const players = new Set(sessions.map((s) => s.player)).size;
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-4">
+        <div>
+          <h3 className="font-semibold">Sessions</h3>
+          <p className="text-xs text-zinc-500">
+            {sessions.length} sessions from {players} {players === 1 ? 'player' : 'players'}
+          </p>
+        </div>
+        <div className="flex gap-2">
+          {RANGES.map((range) => (
+            <button
+              key={range.label}
+              onClick={() => setHours(range.hours)}
+              className={`btn text-sm ${range.hours === hours ? 'btn-primary' : 'btn-secondary'}`}
+            >
+              {range.label}
+            </button>
+          ))}
+          <button onClick={load} disabled={loading} className="btn btn-secondary text-sm">
+            <RefreshCw size={16} className={loading ? 'animate-spin' : ''} />
+          </button>
+        </div>
+      </div>
+      {sessions.map((session) => (
+        <div key={`${session.player}-${session.joined_at}`} className="flex items-center justify-between py-2 border-b border-zinc-800 text-sm">
+          <div className="min-w-0">
+            <div className="truncate">{session.player}</div>
+            <div className="text-xs text-zinc-500">
+              {new Date(session.joined_at).toLocaleString()}
+              {session.left_at ? ` – ${new Date(session.left_at).toLocaleTimeString()}` : ' · online'}
+            </div>
+          </div>
+          <span className="text-zinc-400">{formatDuration(session.duration_secs)}</span>
+        </div>
+      ))}
+      {sessions.length === 0 && !loading && <p className="text-sm text-zinc-500">Nobody played in this time</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,49 @@
+import { Network, X } from 'lucide-react';
+import { useServerStore } from '../stores/serverStore';
+
+/** Shown when a start failed because something else on this computer holds one of the server's ports */
+export function PortWarningDialog() {
+  const { portWarning, dismissPortWarning, startServer, servers } = useServerStore();
+
+  if (!portWarning) return null;
+  const { serverId, error } = portWarning;
+  const server = servers.find((s) => s.id === serverId);
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="absolute inset-0 bg-black/70 backdrop-blur-sm" onClick={dismissPortWarning} />
+      <div className="relative bg-zinc-900 border border-zinc-800 rounded-xl shadow-2xl max-w-md w-full mx-4 animate-fade-in">
+        <div className="flex items-center gap-3 p-4 border-b border-zinc-800">
+          <div className="w-10 h-10 rounded-full bg-yellow-500/20 flex items-center justify-center">
+            <Network size={20} className="text-yellow-500" />
+          </div>
+          <div className="flex-1">
+            <h3 className="font-semibold text-lg">Port Already In Use</h3>
+            <p className="text-sm text-zinc-400">{server?.name ?? serverId}</p>
+          </div>
+          <button onClick={dismissPortWarning} className="p-2 hover:bg-zinc-800 rounded-lg transition-colors">
+            <X size={20} className="text-zinc-400" />
+          </button>
+        </div>
+        <div className="p-4 space-y-3 text-sm">
+          <p>{error.message}.</p>
+          <p className="text-zinc-400">
+            {error.freePort
+              ? `Stop it and start again, or move this server to port ${error.freePort}. Players will need the new address, and port forwards made by hand need updating.`
+              : 'Stop whatever holds it and start the server again. The game needs this exact port, so the server can\'t move.'}
+          </p>
+        </div>
+        <div className="flex justify-end gap-2 p-4 border-t border-zinc-800">
+          <button onClick={dismissPortWarning} className="btn btn-secondary">
+            Cancel
+          </button>
+          {error.freePort && (
+            <button onClick={() => startServer(serverId, false, true)} className="btn btn-primary">
+              Move to {error.freePort} and Start
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,110 @@
+import { useEffect, useState } from 'react';
+import { AppWindow, Eye } from 'lucide-react';
+import { getRemoteBackend, invoke, isAppError, listen } from '../utils/backend';
+import type { CurrentUser } from '../types';
+
+/** Says the window can only look, with a way out for the owner */
+export function ReadOnlyBanner() {
+  const remote = getRemoteBackend() !== null;
+  const [readOnly, setReadOnly] = useState(false);
+  const [askPassword, setAskPassword] = useState(false);
+  const [password, setPassword] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (remote) {
+      invoke<CurrentUser>('get_current_user')
+        .then((user) => setReadOnly(user.read_only))
+        .catch(() => {});
+      return;
+    }
+    invoke<boolean>('get_read_only_mode').then(setReadOnly).catch(() => {});
+    const unlisten = listen<boolean>('read-only-changed', (event) => setReadOnly(event.payload));
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [remote]);
+
+  if (!readOnly) return null;
+
+  const leave = async () => {
+    setError(null);
+    try {
+      if (askPassword) await invoke('unlock_app', { password });
+      await invoke('set_read_only_mode', { enabled: false });
+      setAskPassword(false);
+      setPassword('');
+    } catch (e) {
+      if (isAppError(e, 'Locked')) setAskPassword(true);
+      else setError(String(e));
+    }
+  };
+
+  return (
+    <div className="flex items-center gap-3 px-4 py-2 bg-sky-500/10 border-b border-sky-500/30 text-sm text-sky-300">
+      <Eye size={16} />
+      <span className="flex-1">
+        Read-only - consoles and stats can be watched, but nothing can be started, changed or deleted
+        {remote ? ' with this login' : ' from this window'}
+      </span>
+      {error && <span className="text-red-400">{error}</span>}
+      {!remote && askPassword && (
+        <input
+          type="password"
+          value={password}
+          onChange={(e) => setPassword(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && leave()}
+          placeholder="App password"
+          className="input py-1 text-sm"
+          autoFocus
+        />
+      )}
+      {!remote && (
+        <button onClick={leave} className="btn btn-secondary text-sm py-1">
+          Leave read-only
+        </button>
+      )}
+    </div>
+  );
+}
+
+/** Turning this window read-only, or opening one that is, before handing the app over */
+export function ReadOnlySettings() {
+  const [error, setError] = useState<string | null>(null);
+
+  if (getRemoteBackend()) return null;
+
+  const run = async (action: () => Promise<unknown>) => {
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Read-only Mode</h2>
+      <p className="text-slate-400 text-sm mb-4">
+        For screen-sharing or letting someone watch: a read-only window shows consoles, stats and settings, but
+        anything that would start, stop, change or delete is refused. With an app password set, leaving read-only
+        mode asks for it.
+      </p>
+      <div className="flex gap-2">
+        <button
+          onClick={() => run(() => invoke('set_read_only_mode', { enabled: true }))}
+          className="btn btn-secondary text-sm"
+        >
+          <Eye size={16} />
+          Make This Window Read-only
+        </button>
+        <button onClick={() => run(() => invoke('open_read_only_window'))} className="btn btn-secondary text-sm">
+          <AppWindow size={16} />
+          Open a Read-only Window
+        </button>
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
@@ -0,0 +1,94 @@
+import { useEffect, useState } from 'react';
+import { Link2, RefreshCw, ShieldCheck, ShieldAlert } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { PairingCode, RemoteAccess } from '../types';
+
+export function RemoteAccessSettings() {
+  const [access, setAccess] = useState<RemoteAccess | null>(null);
+  const [pairing, setPairing] = useState<PairingCode | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () => {
+    invoke<RemoteAccess>('get_remote_access').then(setAccess).catch((e) => setError(String(e)));
+  };
+
+  useEffect(load, []);
+
+  const createCode = async () => {
+    setError(null);
+    try {
+      setPairing(await invoke<PairingCode>('create_pairing_code'));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const regenerate = async () => {
+    if (!confirm('Make a new certificate? Apps paired with this machine will have to pair again.')) return;
+    setError(null);
+    try {
+      await invoke('regenerate_api_certificate');
+      setPairing(null);
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (!access) return null;
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Remote Access</h2>
+      {!access.listening ? (
+        <p className="text-sm text-zinc-400">
+          The API is off. Start the app with <code>--api</code>, or <code>--agent</code> to manage it from other
+          machines over HTTPS.
+        </p>
+      ) : (
+        <>
+          <div className="text-sm mb-4 space-y-1">
+            <p>
+              API on <code>{access.listening}</code>
+            </p>
+            {access.identity ? (
+              <p className="flex items-center gap-2 text-zinc-400">
+                <ShieldCheck size={16} className="text-green-400" />
+                HTTPS, {access.identity.self_signed ? 'self-signed' : 'certificate files'} - fingerprint{' '}
+                <code className="text-xs break-all">{access.identity.fingerprint}</code>
+              </p>
+            ) : (
+              <p className="flex items-center gap-2 text-zinc-400">
+                <ShieldAlert size={16} className="text-yellow-400" />
+                Plain HTTP - fine on this computer, but tokens cross the network unencrypted
+              </p>
+            )}
+          </div>
+
+          {pairing && (
+            <div className="mb-4 p-3 rounded bg-zinc-800 text-sm">
+              <p className="text-2xl font-mono tracking-widest mb-1">{pairing.code}</p>
+              <p className="text-zinc-400">
+                Enter it in the other app within 10 minutes (until{' '}
+                {new Date(pairing.expires_at).toLocaleTimeString()}). It can be used once, and the app pairs as an
+                admin user you can remove under Users.
+              </p>
+            </div>
+          )}
+
+          <div className="flex gap-2">
+            <button onClick={createCode} className="btn btn-primary text-sm">
+              <Link2 size={16} /> Pair an App
+            </button>
+            {access.identity?.self_signed && (
+              <button onClick={regenerate} className="btn btn-secondary text-sm">
+                <RefreshCw size={16} /> New Certificate
+              </button>
+            )}
+          </div>
+        </>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
@@ -0,0 +1,178 @@
+import { useEffect, useState } from 'react';
+import { Eraser, Save } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { WipeFrequency, WipeKind, WipeResult, WipeSchedule, Weekday } from '../types';
+
+interface RustWipesProps {
+  serverId: string;
+}
+
+const WEEKDAYS: { value: Weekday; label: string }[] = [
+  { value: 'Mon', label: 'Monday' },
+  { value: 'Tue', label: 'Tuesday' },
+  { value: 'Wed', label: 'Wednesday' },
+  { value: 'Thu', label: 'Thursday' },
+  { value: 'Fri', label: 'Friday' },
+  { value: 'Sat', label: 'Saturday' },
+  { value: 'Sun', label: 'Sunday' },
+];
+
+const DEFAULT_SCHEDULE: WipeSchedule = {
+  server_id: '',
+  enabled: true,
+  kind: 'map',
+  frequency: 'monthly',
+  weekday: 'Thu',
+  hour: 19,
+  minute: 0,
+  new_seed: true,
+  hostname_date: true,
+  last_wipe: null,
+  next_wipe: null,
+};
+
+const pad = (n: number) => n.toString().padStart(2, '0');
+
+export function RustWipes({ serverId }: RustWipesProps) {
+  const [schedule, setSchedule] = useState<WipeSchedule | null>(null);
+  const [draft, setDraft] = useState<WipeSchedule>(DEFAULT_SCHEDULE);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<WipeSchedule | null>('get_wipe_schedule', { serverId })
+      .then((s) => {
+        setSchedule(s);
+        if (s) setDraft(s);
+      })
+      .catch((e) => setError(String(e)));
+  }, [serverId]);
+
+  const update = (changes: Partial<WipeSchedule>) => setDraft({ ...draft, ...changes });
+
+  const run = async (key: string, action: () => Promise<string>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const saveSchedule = (remove: boolean) =>
+    run('save', async () => {
+      const saved = await invoke<WipeSchedule | null>('set_wipe_schedule', { serverId, schedule: remove ? null : draft });
+      setSchedule(saved);
+      if (saved) setDraft(saved);
+      return saved ? 'Schedule saved.' : 'Schedule removed.';
+    });
+
+  const wipeNow = (kind: WipeKind) => {
+    const what = kind === 'full' ? 'the map and all blueprints' : 'the map';
+    if (!confirm(`Wipe ${what} now? A running server is restarted.`)) return;
+    run(kind, async () => {
+      const result = await invoke<WipeResult>('wipe_rust_server', {
+        serverId,
+        kind,
+        newSeed: draft.new_seed,
+        hostnameDate: draft.hostname_date,
+      });
+      return `Deleted ${result.deleted.length} files${result.seed ? `, new seed ${result.seed}` : ''}.`;
+    });
+  };
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2">Wipes</h3>
+      <p className="text-sm text-zinc-400 mb-4">
+        A map wipe deletes the map and its saves; a full wipe deletes players' blueprints too.
+        {schedule?.next_wipe && ` Next wipe: ${new Date(schedule.next_wipe).toLocaleString()}.`}
+        {schedule?.last_wipe && ` Last wipe: ${new Date(schedule.last_wipe).toLocaleString()}.`}
+      </p>
+
+      <div className="grid grid-cols-2 gap-4 text-sm">
+        <label className="block">
+          <span className="input-label">Wipe</span>
+          <select value={draft.kind} onChange={(e) => update({ kind: e.target.value as WipeKind })} className="input w-full">
+            <option value="map">Map</option>
+            <option value="full">Map and blueprints</option>
+          </select>
+        </label>
+        <label className="block">
+          <span className="input-label">Every</span>
+          <select
+            value={draft.frequency}
+            onChange={(e) => update({ frequency: e.target.value as WipeFrequency })}
+            className="input w-full"
+          >
+            <option value="weekly">Week</option>
+            <option value="biweekly">Two weeks</option>
+            <option value="monthly">Month (first weekday of the month)</option>
+          </select>
+        </label>
+        <label className="block">
+          <span className="input-label">On</span>
+          <select value={draft.weekday} onChange={(e) => update({ weekday: e.target.value as Weekday })} className="input w-full">
+            {WEEKDAYS.map((d) => (
+              <option key={d.value} value={d.value}>{d.label}</option>
+            ))}
+          </select>
+        </label>
+        <label className="block">
+          <span className="input-label">At</span>
+          <input
+            type="time"
+            value={`${pad(draft.hour)}:${pad(draft.minute)}`}
+            onChange={(e) => {
+              const [hour, minute] = e.target.value.split(':').map((n) => parseInt(n, 10));
+              if (!isNaN(hour) && !isNaN(minute)) update({ hour, minute });
+            }}
+            className="input w-full"
+          />
+        </label>
+      </div>
+
+      <div className="flex flex-wrap gap-4 text-sm mt-4">
+        <label className="flex items-center gap-2">
+          <input type="checkbox" checked={draft.new_seed} onChange={(e) => update({ new_seed: e.target.checked })} />
+          New random seed
+        </label>
+        <label className="flex items-center gap-2">
+          <input type="checkbox" checked={draft.hostname_date} onChange={(e) => update({ hostname_date: e.target.checked })} />
+          Put the wipe date in the hostname
+        </label>
+        <label className="flex items-center gap-2">
+          <input type="checkbox" checked={draft.enabled} onChange={(e) => update({ enabled: e.target.checked })} />
+          Schedule enabled
+        </label>
+      </div>
+
+      <div className="flex flex-wrap gap-2 mt-4">
+        <button onClick={() => saveSchedule(false)} disabled={busy !== null} className="btn btn-primary text-sm">
+          <Save size={16} />
+          Save schedule
+        </button>
+        {schedule && (
+          <button onClick={() => saveSchedule(true)} disabled={busy !== null} className="btn btn-secondary text-sm">
+            Remove schedule
+          </button>
+        )}
+        <button onClick={() => wipeNow('map')} disabled={busy !== null} className="btn btn-secondary text-sm">
+          <Eraser size={16} className={busy === 'map' ? 'animate-pulse' : ''} />
+          Wipe map now
+        </button>
+        <button onClick={() => wipeNow('full')} disabled={busy !== null} className="btn btn-secondary text-sm text-red-400">
+          <Eraser size={16} className={busy === 'full' ? 'animate-pulse' : ''} />
+          Full wipe now
+        </button>
+      </div>
+
+      {message && <p className="text-sm text-green-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
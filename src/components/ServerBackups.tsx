@@ -0,0 +1,149 @@
+import { useEffect, useState } from 'react';
+import { Archive, RotateCcw, Trash2 } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { Backup, BackupTarget, ServerEvent } from '../types';
+
+interface ServerBackupsProps {
+  serverId: string;
+  /** Where a backup can be copied to */
+  targets?: BackupTarget[];
+}
+
+const MB = 1024 * 1024;
+
+/** Zips of the server's files - make one, restore one or delete old ones */
+export function ServerBackups({ serverId, targets = [] }: ServerBackupsProps) {
+  const [backups, setBackups] = useState<Backup[]>([]);
+  const [uploaded, setUploaded] = useState<number | null>(null);
+  const [note, setNote] = useState('');
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () =>
+    invoke<Backup[]>('list_backups', { serverId })
+      .then(setBackups)
+      .catch((e) => setError(String(e)));
+
+  useEffect(() => {
+    load();
+    const unlisten = listen<ServerEvent>('server-event', (event) => {
+      const e = event.payload;
+      if (e.type !== 'BackupTransfer' || e.server_id !== serverId || e.direction !== 'upload') return;
+      setUploaded(e.total_bytes ? Math.round((e.done_bytes / e.total_bytes) * 100) : 0);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [serverId]);
+
+  const run = async (key: string, action: () => Promise<string>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+      await load();
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+  };
+
+  const create = () =>
+    run('create', async () => {
+      const backup = await invoke<Backup>('create_backup', { serverId, note: note || null });
+      setNote('');
+      return `Backed up (${(backup.size_bytes / MB).toFixed(1)} MB)`;
+    });
+
+  const restore = (backup: Backup) => {
+    if (!confirm('Replace the server\'s files with this backup? Changes since then are lost.')) return;
+    run(backup.id, async () => {
+      await invoke('restore_backup', { serverId, backupId: backup.id });
+      return 'Backup restored';
+    });
+  };
+
+  const push = (backup: Backup, target: BackupTarget) => {
+    setUploaded(0);
+    run(backup.id, async () => {
+      await invoke('push_backup', { serverId, backupId: backup.id, targetId: target.id });
+      return `Copied to ${target.name}`;
+    });
+  };
+
+  const remove = (backup: Backup) => {
+    if (!confirm('Delete this backup?')) return;
+    run(backup.id, async () => {
+      await invoke('delete_backup', { serverId, backupId: backup.id });
+      return 'Backup deleted';
+    });
+  };
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2 flex items-center gap-2">
+        <Archive size={20} /> Backups
+      </h3>
+      <p className="text-sm text-zinc-400 mb-4">
+        A zip of all the server's files. Running servers save their world first when the game supports it. Restoring
+        stops the server and starts it again after.
+      </p>
+      <div className="flex gap-2 mb-4">
+        <input
+          value={note}
+          onChange={(e) => setNote(e.target.value)}
+          placeholder="Note (optional)"
+          className="input text-sm flex-1"
+        />
+        <button onClick={create} disabled={busy !== null} className="btn btn-primary text-sm">
+          {busy === 'create' ? 'Backing up...' : 'Back Up Now'}
+        </button>
+      </div>
+
+      {backups.length === 0 ? (
+        <p className="text-sm text-zinc-500">No backups yet</p>
+      ) : (
+        <ul className="divide-y divide-zinc-800">
+          {backups.map((backup) => (
+            <li key={backup.id} className="py-2 flex items-center gap-4 text-sm">
+              <div className="flex-1">
+                <div>{new Date(backup.created_at).toLocaleString()}</div>
+                <div className="text-zinc-400">
+                  {(backup.size_bytes / MB).toFixed(1)} MB{backup.note && ` - ${backup.note}`}
+                </div>
+              </div>
+              {targets.length > 0 && (
+                <select
+                  value=""
+                  onChange={(e) => {
+                    const target = targets.find((t) => t.id === e.target.value);
+                    if (target) push(backup, target);
+                  }}
+                  disabled={busy !== null}
+                  className="input py-1 text-sm"
+                >
+                  <option value="">{busy === backup.id && uploaded !== null ? `Copying ${uploaded}%` : 'Copy to...'}</option>
+                  {targets.map((target) => (
+                    <option key={target.id} value={target.id}>
+                      {target.name}
+                    </option>
+                  ))}
+                </select>
+              )}
+              <button onClick={() => restore(backup)} disabled={busy !== null} className="btn btn-secondary text-sm">
+                <RotateCcw size={16} /> {busy === backup.id ? 'Working...' : 'Restore'}
+              </button>
+              <button onClick={() => remove(backup)} disabled={busy !== null} className="btn btn-danger text-sm">
+                <Trash2 size={16} />
+              </button>
+            </li>
+          ))}
+        </ul>
+      )}
+      {message && <p className="text-sm text-emerald-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
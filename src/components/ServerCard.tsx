@@ -29,8 +29,9 @@ export function ServerCard({ server }: Props) {
 
   const handleDelete = async (e: React.MouseEvent) => {
     e.stopPropagation();
-    if (confirm(`Delete server "${server.name}"? Your world data will be preserved.`)) {
-      await deleteServer(server.id);
+    const typed = prompt(`Type "${server.name}" to delete this server. Your world data will be preserved.`);
+    if (typed !== null) {
+      await deleteServer(server.id, typed, false);
     }
   };
 
@@ -41,6 +42,7 @@ export function ServerCard({ server }: Props) {
     stopping: 'bg-yellow-500',
     installing: 'bg-blue-500',
     error: 'bg-red-500',
+    crashlooping: 'bg-red-500',
   };
 
   const statusDot = statusColors[server.status] || statusColors.stopped;
@@ -76,7 +78,7 @@ export function ServerCard({ server }: Props) {
       </div>
 
       <div className="flex items-center gap-2 opacity-0 group-hover:opacity-100 transition-opacity">
-        {server.status === 'stopped' ? (
+        {server.status === 'stopped' || server.status === 'crashlooping' ? (
           <button
             onClick={handleStart}
             disabled={isLoading}
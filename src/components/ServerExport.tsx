@@ -0,0 +1,54 @@
+import { useState } from 'react';
+import { FileArchive } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { Server } from '../types';
+
+/** Save the server with its files as one zip, to import it on another machine */
+export function ServerExport({ server }: { server: Server }) {
+  const [dest, setDest] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const stopped = server.status === 'stopped' || server.status === 'error' || server.status === 'crashlooping';
+
+  const runExport = async () => {
+    setBusy(true);
+    setError(null);
+    setMessage(null);
+    try {
+      const path = await invoke<string>('export_server', { serverId: server.id, dest: dest.trim() || null });
+      setMessage(`Exported to ${path}`);
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(false);
+  };
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2 flex items-center gap-2">
+        <FileArchive size={20} /> Move to Another Machine
+      </h3>
+      <p className="text-sm text-zinc-400 mb-4">
+        The server's files and settings in one zip - import it on the other machine from Import a Server. Backups,
+        the database and tunnel stay here.
+      </p>
+      <div className="flex gap-2 text-sm">
+        <input
+          type="text"
+          value={dest}
+          onChange={(e) => setDest(e.target.value)}
+          placeholder="Save to (optional) - a file path, otherwise the app folder's exports/"
+          className="input flex-1"
+        />
+        <button onClick={runExport} disabled={busy || !stopped} className="btn btn-secondary text-sm">
+          {busy ? 'Exporting...' : 'Export'}
+        </button>
+      </div>
+      {!stopped && <p className="text-xs text-zinc-500 mt-2">Stop the server to export it.</p>}
+      {message && <p className="text-sm text-emerald-400 mt-2 break-all">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
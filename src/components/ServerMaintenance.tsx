@@ -0,0 +1,63 @@
+import { useEffect, useState } from 'react';
+import { Save } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { MaintenancePolicy, Server } from '../types';
+import { MaintenancePolicyEditor } from './MaintenancePolicyEditor';
+
+interface ServerMaintenanceProps {
+  server: Server;
+  onSaved: () => void;
+}
+
+const EMPTY_POLICY: MaintenancePolicy = { quiet_hours: [], windows: [] };
+
+/** When automation may stop or change this server - its own hours, or the global ones */
+export function ServerMaintenance({ server, onSaved }: ServerMaintenanceProps) {
+  const [policy, setPolicy] = useState<MaintenancePolicy | null>(server.maintenance ?? null);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setPolicy(server.maintenance ?? null);
+  }, [server.id, server.maintenance]);
+
+  const changed = JSON.stringify(policy) !== JSON.stringify(server.maintenance ?? null);
+
+  const save = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke('update_server_maintenance', { serverId: server.id, maintenance: policy });
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+    setSaving(false);
+  };
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold">Automation Hours</h3>
+        {changed && (
+          <button onClick={save} disabled={saving} className="btn btn-success text-sm">
+            <Save size={16} /> {saving ? 'Saving...' : 'Save'}
+          </button>
+        )}
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        Keeps scheduled wipes and automatic updates away from the times players are on.
+      </p>
+      <label className="flex items-center gap-2 text-sm mb-4">
+        <input
+          type="checkbox"
+          checked={policy === null}
+          onChange={(e) => setPolicy(e.target.checked ? null : server.maintenance ?? EMPTY_POLICY)}
+        />
+        Use the hours from Settings
+      </label>
+      {policy && <MaintenancePolicyEditor policy={policy} onChange={setPolicy} />}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
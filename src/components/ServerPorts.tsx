@@ -0,0 +1,76 @@
+import { useEffect, useState } from 'react';
+import { Network } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { ServerNetwork, ServerStatus } from '../types';
+
+interface ServerPortsProps {
+  serverId: string;
+  status: ServerStatus;
+}
+
+const isLoopback = (ip: string) => ip === '127.0.0.1' || ip === '::1';
+
+/** Every port the container publishes, as Docker bound them */
+export function ServerPorts({ serverId, status }: ServerPortsProps) {
+  const [network, setNetwork] = useState<ServerNetwork | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<ServerNetwork>('get_server_network', { serverId })
+      .then((n) => {
+        setNetwork(n);
+        setError(null);
+      })
+      .catch((e) => setError(String(e)));
+  }, [serverId, status]);
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2 flex items-center gap-2">
+        <Network size={20} /> Published Ports
+      </h3>
+      <p className="text-sm text-zinc-400 mb-4">
+        {network && !network.from_container
+          ? 'The ports the server will publish once its container is created.'
+          : 'The ports Docker publishes for this server. Ones on 127.0.0.1 can only be reached from this computer.'}
+      </p>
+      {network && (
+        <table className="w-full text-sm">
+          <thead>
+            <tr className="text-left text-xs text-zinc-500 uppercase tracking-wide">
+              <th className="pb-2">Port</th>
+              <th className="pb-2">Protocol</th>
+              <th className="pb-2">Host address</th>
+              <th className="pb-2">Used for</th>
+            </tr>
+          </thead>
+          <tbody>
+            {network.ports.map((port) => (
+              <tr
+                key={`${port.host_ip}:${port.host_port}/${port.protocol}`}
+                className="border-t border-zinc-800"
+              >
+                <td className="py-2 font-mono">
+                  {port.host_port}
+                  {port.host_port !== port.container_port && (
+                    <span className="text-zinc-500"> → {port.container_port}</span>
+                  )}
+                </td>
+                <td className="py-2 uppercase">{port.protocol}</td>
+                <td className="py-2 font-mono">
+                  {port.host_ip}
+                  {isLoopback(port.host_ip) && <span className="text-xs text-zinc-500 font-sans"> (this computer only)</span>}
+                </td>
+                <td className="py-2 text-zinc-400">{port.description ?? '-'}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+      {network && network.networks.length > 0 && (
+        <p className="text-xs text-zinc-500 mt-3">Docker networks: {network.networks.join(', ')}</p>
+      )}
+      {error && <p className="text-sm text-red-400">{error}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,265 @@
+import { useEffect, useState } from 'react';
+import { Play, Plus, Trash2, X } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { RestartWarning, Schedule, ScheduledAction } from '../types';
+
+interface ServerSchedulesProps {
+  serverId: string;
+}
+
+const ACTION_LABELS: Record<ScheduledAction['type'], string> = {
+  restart: 'Restart',
+  backup: 'Back up',
+  command: 'Console command',
+  update: 'Update the game',
+};
+
+const EXAMPLES = [
+  { cron: '0 5 * * *', label: 'every day at 05:00' },
+  { cron: '*/30 * * * *', label: 'every 30 minutes' },
+  { cron: '0 20 * * fri', label: 'Fridays at 20:00' },
+];
+
+const DEFAULT_WARNINGS: RestartWarning[] = [
+  { minutes_before: 5, command: 'say Restarting in 5 minutes' },
+  { minutes_before: 1, command: 'say Restarting in 1 minute' },
+];
+
+function describeAction(action: ScheduledAction): string {
+  switch (action.type) {
+    case 'command':
+      return `Console: ${action.command}`;
+    case 'restart':
+      return action.warnings.length > 0
+        ? `Restart, warning ${action.warnings.map((w) => w.minutes_before).join(', ')} min before`
+        : ACTION_LABELS.restart;
+    default:
+      return ACTION_LABELS[action.type];
+  }
+}
+
+export function ServerSchedules({ serverId }: ServerSchedulesProps) {
+  const [schedules, setSchedules] = useState<Schedule[]>([]);
+  const [name, setName] = useState('');
+  const [cron, setCron] = useState('0 5 * * *');
+  const [actionType, setActionType] = useState<ScheduledAction['type']>('restart');
+  const [command, setCommand] = useState('');
+  const [warnings, setWarnings] = useState<RestartWarning[]>(DEFAULT_WARNINGS);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const loadSchedules = () =>
+    invoke<Schedule[]>('list_schedules', { serverId }).then(setSchedules).catch((e) => setError(String(e)));
+
+  useEffect(() => {
+    loadSchedules();
+  }, [serverId]);
+
+  const run = async (key: string, action: () => Promise<string | null>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setError(String(e));
+    }
+    setBusy(null);
+    loadSchedules();
+  };
+
+  const addSchedule = () =>
+    run('add', async () => {
+      const action: ScheduledAction =
+        actionType === 'command'
+          ? { type: 'command', command }
+          : actionType === 'restart'
+            ? { type: 'restart', warnings }
+            : { type: actionType };
+      const schedule = { id: '', server_id: serverId, name: name.trim(), cron, action, enabled: true };
+      await invoke<Schedule>('add_schedule', { serverId, schedule });
+      setName('');
+      setCommand('');
+      return null;
+    });
+
+  const toggleSchedule = (schedule: Schedule) =>
+    run(schedule.id, async () => {
+      await invoke<Schedule>('add_schedule', { serverId, schedule: { ...schedule, enabled: !schedule.enabled } });
+      return null;
+    });
+
+  const runNow = (schedule: Schedule) =>
+    run(schedule.id, async () => {
+      const ran = await invoke<Schedule>('run_schedule_now', { scheduleId: schedule.id });
+      return ran.last_result ?? 'Done.';
+    });
+
+  const removeSchedule = (schedule: Schedule) => {
+    if (!confirm(`Remove '${schedule.name || describeAction(schedule.action)}'?`)) return;
+    run(schedule.id, async () => {
+      await invoke('remove_schedule', { scheduleId: schedule.id });
+      return null;
+    });
+  };
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-2">Scheduled Tasks</h3>
+      <p className="text-sm text-zinc-400 mb-4">
+        Restart, back up, send a console command or update the game on a cron expression in this computer's time -
+        minute, hour, day of month, month and day of week. Restarts and commands are skipped while the server is
+        stopped. A restart's warnings are sent in the console ahead of it, so it still happens on time.
+      </p>
+
+      {schedules.length > 0 && (
+        <div className="mb-4">
+          {schedules.map((schedule) => (
+            <div key={schedule.id} className="flex items-center justify-between py-2 border-b border-zinc-700">
+              <label className="flex items-center gap-3">
+                <input type="checkbox" checked={schedule.enabled} onChange={() => toggleSchedule(schedule)} />
+                <div>
+                  <div>
+                    {schedule.name || describeAction(schedule.action)}{' '}
+                    <code className="text-xs text-zinc-400">{schedule.cron}</code>
+                  </div>
+                  <div className="text-xs text-zinc-500">
+                    {schedule.name && `${describeAction(schedule.action)} · `}
+                    {schedule.enabled && schedule.next_run
+                      ? `Next: ${new Date(schedule.next_run).toLocaleString()}`
+                      : 'Paused'}
+                    {schedule.last_result && ` · Last: ${schedule.last_result}`}
+                  </div>
+                </div>
+              </label>
+              <div className="flex gap-2">
+                <button
+                  onClick={() => runNow(schedule)}
+                  disabled={busy !== null}
+                  className="btn btn-secondary text-sm"
+                  title="Run now"
+                >
+                  <Play size={16} />
+                </button>
+                <button
+                  onClick={() => removeSchedule(schedule)}
+                  disabled={busy !== null}
+                  className="btn btn-secondary text-sm"
+                  title="Remove"
+                >
+                  <Trash2 size={16} />
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="space-y-3 text-sm">
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name (optional)"
+            className="input flex-1"
+          />
+          <input
+            type="text"
+            value={cron}
+            onChange={(e) => setCron(e.target.value)}
+            placeholder="0 5 * * *"
+            className="input w-40 font-mono"
+          />
+        </div>
+        <div className="text-xs text-zinc-500">
+          {EXAMPLES.map((example, i) => (
+            <span key={example.cron}>
+              {i > 0 && ' · '}
+              <button onClick={() => setCron(example.cron)} className="underline hover:text-zinc-300">
+                {example.label}
+              </button>
+            </span>
+          ))}
+        </div>
+        <div className="flex gap-2">
+          <select
+            value={actionType}
+            onChange={(e) => setActionType(e.target.value as ScheduledAction['type'])}
+            className="input"
+          >
+            {(Object.keys(ACTION_LABELS) as ScheduledAction['type'][]).map((a) => (
+              <option key={a} value={a}>{ACTION_LABELS[a]}</option>
+            ))}
+          </select>
+          {actionType === 'command' && (
+            <input
+              type="text"
+              value={command}
+              onChange={(e) => setCommand(e.target.value)}
+              placeholder="say Restarting in 5 minutes"
+              className="input flex-1"
+            />
+          )}
+        </div>
+        {actionType === 'restart' && (
+          <div className="space-y-2">
+            {warnings.map((warning, index) => (
+              <div key={index} className="flex gap-2 items-center">
+                <input
+                  type="number"
+                  min={1}
+                  max={60}
+                  value={warning.minutes_before}
+                  onChange={(e) => {
+                    const minutes_before = parseInt(e.target.value, 10) || 1;
+                    setWarnings(warnings.map((w, i) => (i === index ? { ...w, minutes_before } : w)));
+                  }}
+                  className="input w-20"
+                />
+                <span className="text-zinc-400">min before</span>
+                <input
+                  type="text"
+                  value={warning.command}
+                  onChange={(e) => setWarnings(warnings.map((w, i) => (i === index ? { ...w, command: e.target.value } : w)))}
+                  placeholder="say Restarting soon"
+                  className="input flex-1"
+                />
+                <button
+                  onClick={() => setWarnings(warnings.filter((_, i) => i !== index))}
+                  className="btn btn-secondary text-sm"
+                  title="Remove warning"
+                >
+                  <X size={16} />
+                </button>
+              </div>
+            ))}
+            <button
+              onClick={() => setWarnings([...warnings, { minutes_before: 10, command: 'say Restarting in 10 minutes' }])}
+              className="text-xs text-zinc-400 underline hover:text-zinc-300"
+            >
+              Add a warning
+            </button>
+          </div>
+        )}
+        <button
+          onClick={addSchedule}
+          disabled={
+            busy !== null ||
+            !cron.trim() ||
+            (actionType === 'command' && !command.trim()) ||
+            (actionType === 'restart' && warnings.some((w) => !w.command.trim()))
+          }
+          className="btn btn-primary text-sm flex items-center gap-2"
+        >
+          <Plus size={16} />
+          Add task
+        </button>
+      </div>
+
+      {message && <p className="text-sm text-green-400 mt-3">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-3">{error}</p>}
+    </div>
+  );
+}
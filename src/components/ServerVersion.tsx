@@ -0,0 +1,77 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '../utils/backend';
+import type { ServerFlavor, VersionChange } from '../types';
+
+interface ServerVersionProps {
+  serverId: string;
+  running: boolean;
+}
+
+const FLAVOR_LABELS: Record<ServerFlavor, string> = {
+  paper: 'Paper',
+  purpur: 'Purpur',
+  fabric: 'Fabric',
+  forge: 'Forge',
+};
+
+export function ServerVersion({ serverId, running }: ServerVersionProps) {
+  const [flavor, setFlavor] = useState<ServerFlavor>('paper');
+  const [versions, setVersions] = useState<string[]>([]);
+  const [version, setVersion] = useState('');
+  const [busy, setBusy] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setVersions([]);
+    invoke<string[]>('get_server_versions', { flavor })
+      .then((list) => {
+        setVersions(list);
+        setVersion(list[0] ?? '');
+      })
+      .catch((e) => setError(String(e)));
+  }, [flavor]);
+
+  const change = async () => {
+    setBusy(true);
+    setError(null);
+    setMessage(null);
+    try {
+      const result = await invoke<VersionChange>('change_server_version', { serverId, flavor, version });
+      const backup = result.backup ? ` The previous jar is kept as ${result.backup}.` : '';
+      setMessage(`Installed ${FLAVOR_LABELS[result.flavor]} ${result.minecraft_version} (${result.build}).${backup}`);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="card">
+      <h3 className="text-lg font-semibold mb-1">Server Version</h3>
+      <p className="text-sm text-zinc-400 mb-4">
+        Swaps only the server jar - worlds, configs, plugins and mods stay. Back up the world before moving to an older
+        version.
+      </p>
+      <div className="flex gap-2">
+        <select value={flavor} onChange={(e) => setFlavor(e.target.value as ServerFlavor)} className="input">
+          {(Object.keys(FLAVOR_LABELS) as ServerFlavor[]).map((f) => (
+            <option key={f} value={f}>{FLAVOR_LABELS[f]}</option>
+          ))}
+        </select>
+        <select value={version} onChange={(e) => setVersion(e.target.value)} className="input flex-1">
+          {versions.map((v) => (
+            <option key={v} value={v}>{v}</option>
+          ))}
+        </select>
+        <button onClick={change} disabled={busy || running || !version} className="btn btn-primary">
+          {busy ? 'Switching...' : 'Switch'}
+        </button>
+      </div>
+      {running && <p className="text-xs text-zinc-500 mt-2">Stop the server to change its version</p>}
+      {message && <p className="text-sm text-green-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
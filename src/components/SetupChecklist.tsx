@@ -0,0 +1,63 @@
+import { useEffect, useState } from 'react';
+import type { ReactNode } from 'react';
+import { AlertTriangle, CheckCircle2, MinusCircle, RefreshCw, XCircle } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { CheckStatus, OnboardingChecks } from '../types';
+
+const ICONS: Record<CheckStatus, ReactNode> = {
+  ok: <CheckCircle2 size={18} className="text-emerald-500 shrink-0" />,
+  warning: <AlertTriangle size={18} className="text-yellow-400 shrink-0" />,
+  failed: <XCircle size={18} className="text-red-400 shrink-0" />,
+  skipped: <MinusCircle size={18} className="text-slate-500 shrink-0" />,
+};
+
+/** What this machine still needs before servers can run, from the backend's own probes */
+export function SetupChecklist({ onReady }: { onReady?: () => void }) {
+  const [result, setResult] = useState<OnboardingChecks | null>(null);
+  const [running, setRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async () => {
+    setRunning(true);
+    setError(null);
+    try {
+      const checks = await invoke<OnboardingChecks>('run_onboarding_checks');
+      setResult(checks);
+      if (checks.ready) onReady?.();
+    } catch (e) {
+      setError(String(e));
+    }
+    setRunning(false);
+  };
+
+  useEffect(() => {
+    run();
+  }, []);
+
+  return (
+    <section className="card text-left">
+      <div className="flex items-center justify-between mb-3">
+        <h3 className="font-semibold">Setup Checklist</h3>
+        <button onClick={run} disabled={running} className="btn btn-secondary text-sm">
+          <RefreshCw size={16} className={running ? 'animate-spin' : ''} />
+          {running ? 'Checking...' : 'Check again'}
+        </button>
+      </div>
+      {error && <p className="text-sm text-red-400">{error}</p>}
+      {result && (
+        <ul className="space-y-2">
+          {result.checks.map((check) => (
+            <li key={check.id} className="flex items-start gap-2 text-sm">
+              {ICONS[check.status]}
+              <div>
+                <div className="font-medium">{check.label}</div>
+                <div className="text-xs text-slate-400 break-all">{check.detail}</div>
+                {check.fix && check.status !== 'ok' && <div className="text-xs text-slate-300 mt-0.5">{check.fix}</div>}
+              </div>
+            </li>
+          ))}
+        </ul>
+      )}
+    </section>
+  );
+}
@@ -0,0 +1,113 @@
+import { useEffect, useState } from 'react';
+import { AlarmClock, Save } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { Server, StartSchedule, Weekday } from '../types';
+
+interface StartScheduleSettingsProps {
+  server: Server;
+  onSaved: () => void;
+}
+
+const DAYS: Weekday[] = ['Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat', 'Sun'];
+
+const NEW_SCHEDULE: StartSchedule = { enabled: true, days: [], hour: 19, minute: 0 };
+
+const pad = (n: number) => String(n).padStart(2, '0');
+
+/** Starting the server at a time of day, like an evening play session */
+export function StartScheduleSettings({ server, onSaved }: StartScheduleSettingsProps) {
+  const [schedule, setSchedule] = useState<StartSchedule | null>(server.start_schedule ?? null);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setSchedule(server.start_schedule ?? null);
+  }, [server.id, server.start_schedule]);
+
+  const editable = (s: StartSchedule | null | undefined) =>
+    s && { enabled: s.enabled, days: s.days, hour: s.hour, minute: s.minute };
+  const changed = JSON.stringify(editable(schedule)) !== JSON.stringify(editable(server.start_schedule));
+
+  const toggleDay = (day: Weekday) => {
+    if (!schedule) return;
+    const days = schedule.days;
+    setSchedule({
+      ...schedule,
+      days: days.includes(day) ? days.filter((d) => d !== day) : DAYS.filter((d) => d === day || days.includes(d)),
+    });
+  };
+
+  const save = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke('set_start_schedule', { serverId: server.id, schedule });
+      onSaved();
+    } catch (e) {
+      setError(String(e));
+    }
+    setSaving(false);
+  };
+
+  const nextRun = server.start_schedule?.enabled ? server.start_schedule.next_run : null;
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold flex items-center gap-2">
+          <AlarmClock size={20} /> Scheduled Start
+        </h3>
+        {changed && (
+          <button onClick={save} disabled={saving} className="btn btn-success text-sm">
+            <Save size={16} /> {saving ? 'Saving...' : 'Save'}
+          </button>
+        )}
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        Starts the server at a time of day while the app is open. When the Docker host has Wake-on-LAN set up in
+        Settings, it's woken first.
+      </p>
+      <label className="flex items-center gap-2 text-sm mb-4">
+        <input
+          type="checkbox"
+          checked={schedule?.enabled ?? false}
+          onChange={(e) => setSchedule(schedule ? { ...schedule, enabled: e.target.checked } : NEW_SCHEDULE)}
+        />
+        Start automatically
+      </label>
+
+      {schedule && (
+        <div className="space-y-3 text-sm">
+          <div className="flex flex-wrap items-center gap-2">
+            <div className="flex gap-1">
+              {DAYS.map((day) => (
+                <button
+                  key={day}
+                  onClick={() => toggleDay(day)}
+                  className={`px-1.5 py-0.5 rounded text-xs ${
+                    schedule.days.length === 0 || schedule.days.includes(day)
+                      ? 'bg-sky-600 text-white'
+                      : 'bg-slate-700 text-slate-400'
+                  }`}
+                >
+                  {day}
+                </button>
+              ))}
+            </div>
+            <input
+              type="time"
+              value={`${pad(schedule.hour)}:${pad(schedule.minute)}`}
+              onChange={(e) => {
+                const [hour, minute] = e.target.value.split(':').map((part) => parseInt(part, 10) || 0);
+                setSchedule({ ...schedule, hour, minute });
+              }}
+              className="input py-1"
+            />
+          </div>
+          {nextRun && !changed && <p className="text-zinc-400">Next start: {new Date(nextRun).toLocaleString()}</p>}
+        </div>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
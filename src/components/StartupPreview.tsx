@@ -0,0 +1,117 @@
+import { useState } from 'react';
+import { AlertTriangle, Eye, EyeOff, FileSearch } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { StartupPreview as Preview } from '../types';
+
+interface StartupPreviewProps {
+  serverId: string;
+}
+
+const isSecret = (key: string) => key.toLowerCase().includes('password') || key.toLowerCase().includes('secret');
+
+/** What the container gets from the current settings - checked before starting it */
+export function StartupPreview({ serverId }: StartupPreviewProps) {
+  const [preview, setPreview] = useState<Preview | null>(null);
+  const [showSecrets, setShowSecrets] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      setPreview(await invoke<Preview>('preview_startup', { serverId }));
+    } catch (e) {
+      setError(String(e));
+    }
+    setLoading(false);
+  };
+
+  // Secret values are hidden everywhere they appear, the command line included
+  const secrets = preview
+    ? Object.entries(preview.env).filter(([key, value]) => isSecret(key) && value).map(([, value]) => value)
+    : [];
+  const mask = (text: string) => (showSecrets ? text : secrets.reduce((t, s) => t.split(s).join('••••••••'), text));
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-2">
+        <h3 className="text-lg font-semibold">Startup Preview</h3>
+        <div className="flex items-center gap-2">
+          {preview && secrets.length > 0 && (
+            <button onClick={() => setShowSecrets(!showSecrets)} className="btn btn-secondary text-sm">
+              {showSecrets ? <EyeOff size={16} /> : <Eye size={16} />}
+              {showSecrets ? 'Hide secrets' : 'Show secrets'}
+            </button>
+          )}
+          <button onClick={load} disabled={loading} className="btn btn-secondary text-sm">
+            <FileSearch size={16} />
+            {loading ? 'Loading...' : preview ? 'Refresh' : 'Preview'}
+          </button>
+        </div>
+      </div>
+      <p className="text-sm text-zinc-400 mb-4">
+        The command, environment and ports the container gets from the saved settings.
+      </p>
+
+      {error && <p className="text-sm text-red-400">{error}</p>}
+
+      {preview && (
+        <div className="space-y-4 text-sm">
+          {preview.container_outdated && (
+            <p className="text-yellow-400">The current container was made from older settings - this is what it gets when it's recreated.</p>
+          )}
+          {preview.warnings.map((warning) => (
+            <div key={warning} className="flex items-start gap-2 text-yellow-400">
+              <AlertTriangle size={16} className="mt-0.5 shrink-0" />
+              <span className="font-mono">{mask(warning)}</span>
+            </div>
+          ))}
+
+          <div>
+            <div className="text-xs text-zinc-500 uppercase tracking-wide mb-1">Image</div>
+            <div className="font-mono">{preview.image}</div>
+          </div>
+
+          <div>
+            <div className="text-xs text-zinc-500 uppercase tracking-wide mb-1">Command</div>
+            <pre className="bg-zinc-800 rounded-lg px-3 py-2 font-mono whitespace-pre-wrap break-all">
+              {preview.command ? mask(preview.command.join(' ')) : "The image's own command"}
+            </pre>
+          </div>
+
+          <div>
+            <div className="text-xs text-zinc-500 uppercase tracking-wide mb-1">Ports</div>
+            <div className="font-mono">
+              {preview.ports.map((p) => (
+                <div key={`${p.container_port}/${p.protocol}`}>
+                  {p.host_ip}:{p.host_port} → {p.container_port}/{p.protocol}
+                </div>
+              ))}
+            </div>
+          </div>
+
+          <div>
+            <div className="text-xs text-zinc-500 uppercase tracking-wide mb-1">Mounts</div>
+            <div className="font-mono break-all">
+              {preview.binds.map((b) => <div key={b}>{b}</div>)}
+            </div>
+          </div>
+
+          <div>
+            <div className="text-xs text-zinc-500 uppercase tracking-wide mb-1">
+              Environment · {preview.memory_mb} MB memory limit
+            </div>
+            <div className="bg-zinc-800 rounded-lg px-3 py-2 font-mono space-y-0.5 break-all">
+              {Object.entries(preview.env).map(([key, value]) => (
+                <div key={key}>
+                  <span className="text-zinc-400">{key}</span>={isSecret(key) && !showSecrets ? '••••••••' : value}
+                </div>
+              ))}
+            </div>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
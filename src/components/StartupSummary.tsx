@@ -0,0 +1,68 @@
+import { useEffect, useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { RotateCcw, X } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import type { Reconciled, Reconciliation } from '../types';
+
+const OUTCOMES: Record<Reconciled, { label: string; className: string }> = {
+  resumed: { label: 'Came back up with Docker', className: 'text-emerald-400' },
+  stopped: { label: 'Stopped', className: 'text-slate-400' },
+  crashed: { label: 'Crashed', className: 'text-red-400' },
+  install_interrupted: { label: 'Install interrupted - retry it', className: 'text-amber-400' },
+};
+
+/** What changed with the servers while the app was closed, like after a reboot */
+export function StartupSummary() {
+  const navigate = useNavigate();
+  const fetchServers = useServerStore((s) => s.fetchServers);
+  const [result, setResult] = useState<Reconciliation | null>(null);
+  const [dismissed, setDismissed] = useState(false);
+
+  useEffect(() => {
+    invoke<Reconciliation | null>('get_startup_reconciliation').then(setResult).catch(() => {});
+    const unlisten = listen<Reconciliation>('servers-reconciled', (event) => {
+      setResult(event.payload);
+      fetchServers();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  if (dismissed || !result || result.servers.length === 0) return null;
+
+  return (
+    <section className="mb-8">
+      <div className="card">
+        <div className="flex items-center justify-between mb-3">
+          <h2 className="font-semibold flex items-center gap-2">
+            <RotateCcw size={18} />
+            While the app was closed
+          </h2>
+          <button onClick={() => setDismissed(true)} className="p-1 hover:bg-slate-700 rounded" title="Dismiss">
+            <X size={16} />
+          </button>
+        </div>
+        <div className="space-y-2">
+          {result.servers.map((server) => {
+            const outcome = OUTCOMES[server.outcome];
+            return (
+              <div
+                key={server.server_id}
+                onClick={() => navigate(`/servers/${server.server_id}`)}
+                className="flex items-center justify-between gap-4 cursor-pointer text-sm"
+              >
+                <span className="font-medium truncate">{server.server_name}</span>
+                <span className={outcome.className}>
+                  {outcome.label}
+                  {server.outcome === 'crashed' && server.exit_code !== null && ` (exit code ${server.exit_code})`}
+                </span>
+              </div>
+            );
+          })}
+        </div>
+      </div>
+    </section>
+  );
+}
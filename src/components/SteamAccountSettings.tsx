@@ -0,0 +1,79 @@
+import { useEffect, useState } from 'react';
+import { KeyRound, Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+
+export function SteamAccountSettings() {
+  const [account, setAccount] = useState<string | null>(null);
+  const [username, setUsername] = useState('');
+  const [password, setPassword] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () => {
+    invoke<string | null>('get_steam_account').then(setAccount).catch((e) => setError(String(e)));
+  };
+
+  useEffect(load, []);
+
+  const save = async () => {
+    setError(null);
+    try {
+      await invoke('set_steam_account', { username, password });
+      setUsername('');
+      setPassword('');
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const remove = async () => {
+    setError(null);
+    try {
+      await invoke('clear_steam_account');
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Steam Account</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Some dedicated servers can only be downloaded with a Steam account. Install scripts that use{' '}
+        <code>STEAM_USER</code> and <code>STEAM_PASS</code> log in with it, and you'll be asked for a Steam Guard
+        code when Steam wants one. The password is kept in the system keychain.
+      </p>
+      {account ? (
+        <div className="flex items-center justify-between text-sm">
+          <span className="flex items-center gap-2">
+            <KeyRound size={16} /> {account}
+          </span>
+          <button onClick={remove} className="btn btn-secondary text-sm">
+            <Trash2 size={16} /> Remove
+          </button>
+        </div>
+      ) : (
+        <div className="flex gap-2">
+          <input
+            className="input flex-1"
+            placeholder="Username"
+            value={username}
+            onChange={(e) => setUsername(e.target.value)}
+          />
+          <input
+            className="input flex-1"
+            type="password"
+            placeholder="Password"
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+          />
+          <button onClick={save} disabled={!username || !password} className="btn btn-primary text-sm">
+            Save
+          </button>
+        </div>
+      )}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
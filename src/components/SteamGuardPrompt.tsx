@@ -0,0 +1,73 @@
+import { useEffect, useState } from 'react';
+import { ShieldCheck } from 'lucide-react';
+import { invoke, listen } from '../utils/backend';
+import type { ServerEvent } from '../types';
+
+interface SteamGuardPromptProps {
+  serverId: string;
+}
+
+/** Asks for the Steam Guard code when SteamCMD stops for one during an install */
+export function SteamGuardPrompt({ serverId }: SteamGuardPromptProps) {
+  const [prompt, setPrompt] = useState<string | null>(null);
+  const [code, setCode] = useState('');
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<ServerEvent>('server-event', (event) => {
+      const payload = event.payload;
+      if (payload.server_id !== serverId) return;
+      if (payload.type === 'SteamGuardRequired') {
+        setPrompt(payload.prompt);
+        setCode('');
+        setError(null);
+      } else if (payload.type === 'InstallProgress' && payload.stage !== 'started') {
+        setPrompt(null);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [serverId]);
+
+  const submit = async () => {
+    try {
+      await invoke('submit_steam_guard_code', { serverId, code });
+      setPrompt(null);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  if (prompt === null) return null;
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      <div className="absolute inset-0 bg-black/70 backdrop-blur-sm" />
+      <div className="relative bg-zinc-900 border border-zinc-800 rounded-xl shadow-2xl max-w-md w-full mx-4 p-4 space-y-4">
+        <div className="flex items-center gap-3">
+          <ShieldCheck size={20} className="text-emerald-400" />
+          <h3 className="font-semibold text-lg">Steam Guard</h3>
+        </div>
+        <p className="text-sm text-zinc-400">
+          SteamCMD is waiting for the code from your Steam Guard email or authenticator app.
+        </p>
+        <p className="text-xs text-zinc-500 font-mono">{prompt}</p>
+        <input
+          className="input w-full font-mono uppercase"
+          maxLength={5}
+          autoFocus
+          value={code}
+          onChange={(e) => setCode(e.target.value)}
+          onKeyDown={(e) => e.key === 'Enter' && code.length === 5 && submit()}
+        />
+        {error && <p className="text-sm text-red-400">{error}</p>}
+        <div className="flex justify-end">
+          <button onClick={submit} disabled={code.length !== 5} className="btn btn-primary">
+            Send code
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
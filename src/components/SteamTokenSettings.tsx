@@ -0,0 +1,160 @@
+import { useEffect, useState } from 'react';
+import { ShieldCheck, Trash2 } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import type { ServerToken, ServerTokens } from '../types';
+
+function checkLabel(token: ServerToken): { text: string; className: string } | null {
+  const check = token.last_check;
+  if (!check) return null;
+  if (check.error) return { text: check.error, className: 'text-yellow-400' };
+  if (check.banned) return { text: 'Banned', className: 'text-red-400' };
+  if (!check.valid) return { text: 'Not valid - it may have expired', className: 'text-red-400' };
+  return {
+    text: check.expires_at ? `Valid until ${new Date(check.expires_at).toLocaleDateString()}` : 'Valid',
+    className: 'text-green-400',
+  };
+}
+
+export function SteamTokenSettings() {
+  const { servers, fetchServers } = useServerStore();
+  const [tokens, setTokens] = useState<ServerToken[]>([]);
+  const [hasApiKey, setHasApiKey] = useState(false);
+  const [label, setLabel] = useState('');
+  const [token, setToken] = useState('');
+  const [apiKey, setApiKey] = useState('');
+  const [checking, setChecking] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = () => {
+    invoke<ServerTokens>('list_server_tokens')
+      .then((result) => {
+        setTokens(result.tokens);
+        setHasApiKey(result.has_web_api_key);
+      })
+      .catch((e) => setError(String(e)));
+  };
+
+  useEffect(() => {
+    load();
+    fetchServers();
+  }, []);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setError(null);
+    try {
+      await action();
+      load();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const add = () =>
+    run(async () => {
+      await invoke('add_server_token', { label, token });
+      setLabel('');
+      setToken('');
+    });
+
+  const check = async (tokenId: string) => {
+    setChecking(tokenId);
+    await run(() => invoke('check_server_token', { tokenId }));
+    setChecking(null);
+  };
+
+  const saveApiKey = (key: string | null) =>
+    run(async () => {
+      await invoke('set_steam_web_api_key', { key });
+      setApiKey('');
+    });
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Steam Server Tokens</h2>
+      <p className="text-sm text-zinc-400 mb-4">
+        Games like Counter-Strike 2 only list publicly with a Game Server Login Token from{' '}
+        <code>steamcommunity.com/dev/managegameservers</code>. A token works for one server at a time - it's
+        passed to the server it's assigned to when the server starts. Tokens are kept in the system keychain.
+      </p>
+
+      {tokens.length > 0 && (
+        <div className="space-y-2 mb-4">
+          {tokens.map((t) => {
+            const status = checkLabel(t);
+            return (
+              <div key={t.id} className="flex items-center gap-2 text-sm">
+                <span className="flex-1">
+                  {t.label} <span className="text-zinc-500">…{t.hint}</span>
+                  {status && <span className={`ml-2 text-xs ${status.className}`}>{status.text}</span>}
+                </span>
+                <select
+                  value={t.server_id ?? ''}
+                  onChange={(e) => run(() => invoke('assign_server_token', { tokenId: t.id, serverId: e.target.value || null }))}
+                  className="input w-48"
+                >
+                  <option value="">Not assigned</option>
+                  {servers.map((s) => (
+                    <option key={s.id} value={s.id}>
+                      {s.name}
+                    </option>
+                  ))}
+                </select>
+                <button
+                  onClick={() => check(t.id)}
+                  disabled={!hasApiKey || checking === t.id}
+                  title={hasApiKey ? 'Check with Steam' : 'Add a Steam Web API key to check tokens'}
+                  className="btn btn-secondary text-sm"
+                >
+                  <ShieldCheck size={16} />
+                </button>
+                <button onClick={() => run(() => invoke('remove_server_token', { tokenId: t.id }))} className="btn btn-secondary text-sm">
+                  <Trash2 size={16} />
+                </button>
+              </div>
+            );
+          })}
+        </div>
+      )}
+
+      <div className="flex gap-2 mb-4">
+        <input className="input w-40" placeholder="Name" value={label} onChange={(e) => setLabel(e.target.value)} />
+        <input
+          className="input flex-1"
+          type="password"
+          placeholder="Token"
+          value={token}
+          onChange={(e) => setToken(e.target.value)}
+        />
+        <button onClick={add} disabled={!label || !token} className="btn btn-primary text-sm">
+          Add
+        </button>
+      </div>
+
+      <div className="flex items-center gap-2 text-sm">
+        <span className="text-zinc-400 flex-1">
+          Steam Web API key, to check tokens: {hasApiKey ? 'saved' : 'not set'}
+        </span>
+        {hasApiKey ? (
+          <button onClick={() => saveApiKey(null)} className="btn btn-secondary text-sm">
+            <Trash2 size={16} /> Remove
+          </button>
+        ) : (
+          <>
+            <input
+              className="input w-64"
+              type="password"
+              placeholder="Web API key"
+              value={apiKey}
+              onChange={(e) => setApiKey(e.target.value)}
+            />
+            <button onClick={() => saveApiKey(apiKey)} disabled={!apiKey} className="btn btn-secondary text-sm">
+              Save
+            </button>
+          </>
+        )}
+      </div>
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </section>
+  );
+}
@@ -0,0 +1,137 @@
+import { useEffect, useState } from 'react';
+import { Trash2, UserPlus } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import type { NewUser, User, UserRole } from '../types';
+
+const ROLE_LABELS: Record<UserRole, string> = {
+  admin: 'Admin - everything',
+  operator: 'Operator - start, stop and console',
+  viewer: 'Viewer - read only',
+};
+
+export function UsersSettings() {
+  const { servers, fetchServers } = useServerStore();
+  const [users, setUsers] = useState<User[]>([]);
+  const [name, setName] = useState('');
+  const [role, setRole] = useState<UserRole>('operator');
+  const [selectedServers, setSelectedServers] = useState<string[]>([]);
+  const [readOnly, setReadOnly] = useState(false);
+  const [created, setCreated] = useState<NewUser | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadUsers = () => invoke<User[]>('list_users').then(setUsers).catch((e) => setError(String(e)));
+
+  useEffect(() => {
+    loadUsers();
+    fetchServers();
+  }, []);
+
+  const toggleServer = (id: string) => {
+    setSelectedServers((prev) => (prev.includes(id) ? prev.filter((s) => s !== id) : [...prev, id]));
+  };
+
+  const addUser = async () => {
+    setError(null);
+    try {
+      const result = await invoke<NewUser>('add_user', { name, role, servers: selectedServers, readOnly });
+      setCreated(result);
+      setName('');
+      setSelectedServers([]);
+      setReadOnly(false);
+      loadUsers();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const removeUser = async (userId: string) => {
+    setError(null);
+    try {
+      await invoke('remove_user', { userId });
+      loadUsers();
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const serverName = (id: string) => servers.find((s) => s.id === id)?.name ?? id;
+
+  return (
+    <section className="card mb-6">
+      <h2 className="text-xl font-semibold mb-2">Users</h2>
+      <p className="text-slate-400 text-sm mb-4">
+        Give someone their own API token to connect from their copy of the app (Settings → Remote
+        Backend), limited to the servers you pick.
+      </p>
+
+      {users.length > 0 && (
+        <div className="mb-4">
+          {users.map((user) => (
+            <div key={user.id} className="flex items-center justify-between py-2 border-b border-slate-700">
+              <div>
+                <div>{user.name}</div>
+                <div className="text-xs text-slate-500">
+                  {ROLE_LABELS[user.role]}
+                  {user.read_only && ' · watch only'}
+                  {user.role !== 'admin' && ` · ${user.servers.map(serverName).join(', ') || 'no servers'}`}
+                </div>
+              </div>
+              <button onClick={() => removeUser(user.id)} className="btn btn-secondary text-sm">
+                <Trash2 size={16} />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="space-y-3">
+        <div className="flex gap-2">
+          <input
+            type="text"
+            value={name}
+            onChange={(e) => setName(e.target.value)}
+            placeholder="Name"
+            className="input flex-1"
+          />
+          <select value={role} onChange={(e) => setRole(e.target.value as UserRole)} className="input">
+            {(Object.keys(ROLE_LABELS) as UserRole[]).map((r) => (
+              <option key={r} value={r}>{ROLE_LABELS[r]}</option>
+            ))}
+          </select>
+        </div>
+        {role !== 'admin' && servers.length > 0 && (
+          <div className="flex flex-wrap gap-3 text-sm">
+            {servers.map((server) => (
+              <label key={server.id} className="flex items-center gap-1">
+                <input
+                  type="checkbox"
+                  checked={selectedServers.includes(server.id)}
+                  onChange={() => toggleServer(server.id)}
+                />
+                {server.name}
+              </label>
+            ))}
+          </div>
+        )}
+        {role !== 'viewer' && (
+          <label className="flex items-center gap-2 text-sm">
+            <input type="checkbox" checked={readOnly} onChange={(e) => setReadOnly(e.target.checked)} />
+            Watch only - can see everything the role gives, but not change anything
+          </label>
+        )}
+        <button onClick={addUser} disabled={!name.trim()} className="btn btn-primary text-sm">
+          <UserPlus size={16} />
+          Add User
+        </button>
+        {created && (
+          <p className="text-sm text-slate-300">
+            Token for {created.user.name} (shown once):{' '}
+            <code className="text-xs break-all">{created.token}</code>
+          </p>
+        )}
+        {error && <p className="text-sm text-red-400">{error}</p>}
+      </div>
+    </section>
+  );
+}
@@ -0,0 +1,162 @@
+import { useEffect, useState } from 'react';
+import { Archive, Check, RefreshCw, Trash2, Upload } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import type { MinecraftWorld } from '../types';
+
+interface WorldManagerProps {
+  serverId: string;
+  running: boolean;
+}
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(0)} KB`;
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  return `${(bytes / 1024 / 1024 / 1024).toFixed(2)} GB`;
+}
+
+export function WorldManager({ serverId, running }: WorldManagerProps) {
+  const [worlds, setWorlds] = useState<MinecraftWorld[]>([]);
+  const [importPath, setImportPath] = useState('');
+  const [importName, setImportName] = useState('');
+  const [busy, setBusy] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const load = async () => {
+    try {
+      setWorlds(await invoke<MinecraftWorld[]>('list_worlds', { serverId }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const run = async (key: string, action: () => Promise<string>) => {
+    setBusy(key);
+    setError(null);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+      load();
+    }
+  };
+
+  const activate = (name: string) =>
+    run(name, async () => {
+      await invoke('set_active_world', { serverId, name });
+      return `The server loads ${name} on its next start.`;
+    });
+
+  const remove = (name: string, archive: boolean) => {
+    const prompt = archive ? `Archive and delete ${name}?` : `Delete ${name} permanently?`;
+    if (!confirm(prompt)) return;
+    run(name, async () => {
+      const path = await invoke<string | null>('delete_world', { serverId, name, archive });
+      return path ? `Archived ${name} to ${path}.` : `Deleted ${name}.`;
+    });
+  };
+
+  const importWorld = () =>
+    run('import', async () => {
+      const world = await invoke<MinecraftWorld>('import_world', { serverId, path: importPath, name: importName });
+      setImportPath('');
+      setImportName('');
+      return `Imported ${world.name}. Make it active to play it.`;
+    });
+
+  useEffect(() => {
+    load();
+  }, [serverId]);
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-4">
+        <h3 className="text-lg font-semibold">Worlds</h3>
+        <button onClick={load} disabled={busy !== null} className="btn btn-secondary text-sm">
+          <RefreshCw size={16} />
+        </button>
+      </div>
+
+      {running && <p className="text-sm text-yellow-400 mb-4">Stop the server to switch, import or delete worlds.</p>}
+
+      <div className="mb-4">
+        {worlds.map((world) => (
+          <div key={world.name} className="flex items-center justify-between py-2 border-b border-zinc-800 text-sm">
+            <div className="min-w-0">
+              <div className="truncate">
+                {world.name}
+                {world.active && <span className="ml-2 text-xs text-emerald-400">Active</span>}
+              </div>
+              <div className="text-xs text-zinc-500">
+                {formatSize(world.size_bytes)}
+                {world.dimensions.length > 0 && ` · with ${world.dimensions.join(', ')}`}
+                {world.last_played && ` · last saved ${new Date(world.last_played * 1000).toLocaleString()}`}
+              </div>
+            </div>
+            {!world.active && (
+              <div className="flex gap-2">
+                <button
+                  onClick={() => activate(world.name)}
+                  disabled={running || busy !== null}
+                  className="btn btn-secondary text-sm"
+                  title="Make active"
+                >
+                  <Check size={16} />
+                </button>
+                <button
+                  onClick={() => remove(world.name, true)}
+                  disabled={running || busy !== null}
+                  className="btn btn-secondary text-sm"
+                  title="Archive and delete"
+                >
+                  <Archive size={16} />
+                </button>
+                <button
+                  onClick={() => remove(world.name, false)}
+                  disabled={running || busy !== null}
+                  className="btn btn-secondary text-sm text-red-400"
+                  title="Delete"
+                >
+                  <Trash2 size={16} />
+                </button>
+              </div>
+            )}
+          </div>
+        ))}
+        {worlds.length === 0 && <p className="text-sm text-zinc-500">No worlds yet - the server creates one on its first start</p>}
+      </div>
+
+      <label className="input-label">Import a world folder or .zip from this computer</label>
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={importPath}
+          onChange={(e) => setImportPath(e.target.value)}
+          placeholder="/path/to/world.zip"
+          className="input flex-1"
+        />
+        <input
+          type="text"
+          value={importName}
+          onChange={(e) => setImportName(e.target.value)}
+          placeholder="World name"
+          className="input w-40"
+        />
+        <button
+          onClick={importWorld}
+          disabled={running || busy !== null || !importPath || !importName}
+          className="btn btn-primary"
+        >
+          <Upload size={16} className={busy === 'import' ? 'animate-pulse' : ''} />
+          Import
+        </button>
+      </div>
+
+      {message && <p className="text-sm text-green-400 mt-2">{message}</p>}
+      {error && <p className="text-sm text-red-400 mt-2">{error}</p>}
+    </div>
+  );
+}
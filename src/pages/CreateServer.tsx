@@ -4,7 +4,9 @@ import { ArrowLeft, Loader2, AlertTriangle } from 'lucide-react';
 import { useServerStore } from '../stores/serverStore';
 import { useGamesStore } from '../stores/gamesStore';
 import { GameIcon } from '../components/GameIcon';
-import type { GameType, CreateServerRequest, Variable } from '../types';
+import { invoke } from '../utils/backend';
+import type { GameType, CreateServerRequest, ImagePlatform, Variable } from '../types';
+import { IMAGE_VARIANT_VAR } from '../types';
 
 const RAM_OPTIONS = [
   { value: 1024, label: '1 GB' },
@@ -31,8 +33,11 @@ export function CreateServer() {
   const [port, setPort] = useState<number | undefined>(undefined);
   const [memoryMb, setMemoryMb] = useState<number>(2048);
   const [config, setConfig] = useState<Record<string, string>>({});
+  const [imagePlatform, setImagePlatform] = useState<ImagePlatform | null>(null);
 
   const gameConfig = selectedGame ? games.find((g) => g.game_type === selectedGame) : null;
+  const variant = gameConfig?.image_variants?.find((v) => v.id === config[IMAGE_VARIANT_VAR]);
+  const image = variant?.image ?? gameConfig?.docker_image;
 
   useEffect(() => {
     if (gameConfig) {
@@ -55,6 +60,15 @@ export function CreateServer() {
     }
   }, [gameConfig]);
 
+  // Warn before creating a server whose image has no build for this computer's architecture
+  useEffect(() => {
+    setImagePlatform(null);
+    if (!image) return;
+    invoke<ImagePlatform>('check_image_platform', { image })
+      .then(setImagePlatform)
+      .catch(() => setImagePlatform(null));
+  }, [image]);
+
   useEffect(() => {
     return () => clearError();
   }, []);
@@ -193,6 +207,17 @@ export function CreateServer() {
             </button>
           </div>
 
+          {imagePlatform && !imagePlatform.native && (
+            <div className="card mb-6 flex items-start gap-3 text-yellow-400 text-sm">
+              <AlertTriangle size={16} className="mt-0.5 shrink-0" />
+              <span>
+                This game's image has no {imagePlatform.host} build ({imagePlatform.platforms.join(', ') || 'none listed'}).
+                It will run under emulation, which is slower, and fails with "exec format error" if Docker on this computer
+                has no emulation set up.
+              </span>
+            </div>
+          )}
+
           <div className="card mb-6">
             <h3 className="text-lg font-semibold mb-4">Basic Settings</h3>
 
@@ -5,9 +5,10 @@ import {
   RotateCcw, ExternalLink, FolderOpen, FileCode, ArrowRight
 } from 'lucide-react';
 import { useGamesStore } from '../stores/gamesStore';
-import { GameConfig, DEFAULT_GAME_CONFIG, Variable, PortConfig, ConfigFile, SystemMapping, FieldType, ConfigFileFormat } from '../types';
+import { GameConfig, DEFAULT_GAME_CONFIG, Variable, PortConfig, ConfigFile, SystemMapping, FieldType, ConfigFileFormat, StopConfig, StopMethod, ImageVariant, IMAGE_VARIANT_VAR } from '../types';
 import { open } from '@tauri-apps/plugin-shell';
 import { GameIcon } from '../components/GameIcon';
+import { InstallScriptLint } from '../components/InstallScriptLint';
 
 type EditMode = 'none' | 'create' | 'edit';
 
@@ -51,7 +52,8 @@ export function GamesPage() {
   };
 
   const handleStartEdit = (game: GameConfig) => {
-    setEditingGame({ ...game, is_custom: true });
+    // The image setting is made from the variants, so it's edited there
+    setEditingGame({ ...game, is_custom: true, variables: game.variables.filter((v) => v.env !== IMAGE_VARIANT_VAR) });
     setEditMode('edit');
   };
 
@@ -123,6 +125,10 @@ export function GamesPage() {
   const updateEditingGame = (updates: Partial<GameConfig>) => {
     if (editingGame) setEditingGame({ ...editingGame, ...updates });
   };
+  const stop: StopConfig = editingGame?.stop ?? { method: 'command', timeout_secs: 30 };
+  const variants: ImageVariant[] = editingGame?.image_variants ?? [];
+  const updateVariant = (index: number, updates: Partial<ImageVariant>) =>
+    updateEditingGame({ image_variants: variants.map((v, i) => (i === index ? { ...v, ...updates } : v)) });
 
   // Variable management
   const addVariable = () => {
@@ -355,6 +361,34 @@ export function GamesPage() {
                   onChange={(e) => updateEditingGame({ docker_image: e.target.value })}
                   className="input" placeholder="username/image:tag" />
               </div>
+              <div>
+                <label className="block text-sm text-slate-400 mb-1">Image Variants</label>
+                {variants.map((variant, index) => (
+                  <div key={index} className="grid grid-cols-[100px_120px_1fr_32px] gap-2 items-center mb-2">
+                    <input type="text" value={variant.id}
+                      onChange={(e) => updateVariant(index, { id: e.target.value })}
+                      className="input font-mono text-xs py-1.5" placeholder="java_17" />
+                    <input type="text" value={variant.label}
+                      onChange={(e) => updateVariant(index, { label: e.target.value })}
+                      className="input text-xs py-1.5" placeholder="Java 17" />
+                    <input type="text" value={variant.image}
+                      onChange={(e) => updateVariant(index, { image: e.target.value })}
+                      className="input text-xs py-1.5" placeholder="username/image:java_17" />
+                    <button onClick={() => updateEditingGame({ image_variants: variants.filter((_, i) => i !== index) })}
+                      className="p-1.5 text-red-400 hover:bg-red-500/20 rounded justify-self-center">
+                      <X size={14} />
+                    </button>
+                  </div>
+                ))}
+                <button onClick={() => updateEditingGame({ image_variants: [...variants, { id: '', label: '', image: '' }] })}
+                  className="btn btn-secondary">
+                  <Plus size={16} /> Add Variant
+                </button>
+                <p className="text-xs text-slate-500 mt-1">
+                  Other images a server can run on, like another Java or Proton instead of Wine - picked per server
+                  with the Image setting
+                </p>
+              </div>
               <div>
                 <label className="block text-sm text-slate-400 mb-1">Startup Command</label>
                 <input type="text" value={editingGame.startup}
@@ -362,11 +396,115 @@ export function GamesPage() {
                   className="input font-mono text-sm" placeholder="java -Xmx{{SERVER_MEMORY}}M -jar server.jar" />
                 <p className="text-xs text-slate-500 mt-1">Use {'{{VAR}}'} for placeholders</p>
               </div>
+              <div className="grid grid-cols-3 gap-4">
+                <div>
+                  <label className="block text-sm text-slate-400 mb-1">Stop With</label>
+                  <select value={stop.method}
+                    onChange={(e) => updateEditingGame({ stop: { ...stop, method: e.target.value as StopMethod } })}
+                    className="input">
+                    <option value="command">Console command</option>
+                    <option value="sigint">SIGINT (Ctrl+C)</option>
+                    <option value="sigterm">SIGTERM</option>
+                  </select>
+                </div>
+                <div>
+                  <label className="block text-sm text-slate-400 mb-1">Stop Command</label>
+                  <input type="text" value={editingGame.stop_command}
+                    onChange={(e) => updateEditingGame({ stop_command: e.target.value })}
+                    disabled={stop.method !== 'command'}
+                    className="input" placeholder="stop" />
+                </div>
+                <div>
+                  <label className="block text-sm text-slate-400 mb-1">Kill After (s)</label>
+                  <input type="number" min={1} value={stop.timeout_secs}
+                    onChange={(e) => updateEditingGame({ stop: { ...stop, timeout_secs: parseInt(e.target.value) || 30 } })}
+                    className="input" />
+                </div>
+              </div>
+              <p className="text-xs text-slate-500 -mt-2">
+                A stop command of ^C sends SIGINT. Servers still running after the wait get SIGTERM, then are killed.
+              </p>
+              <div>
+                <label className="block text-sm text-slate-400 mb-1">Start Timeout (s)</label>
+                <input type="number" min={1} value={editingGame.start_timeout_secs ?? 120}
+                  onChange={(e) => updateEditingGame({ start_timeout_secs: parseInt(e.target.value) || 120 })}
+                  className="input" />
+                <p className="text-xs text-slate-500 mt-1">
+                  How long a started server has to answer on its port (or pass its image's health check) before it's
+                  flagged - heavy modpacks and games like Rust need minutes
+                </p>
+              </div>
+              <div>
+                <label className="block text-sm text-slate-400 mb-1">Steam Token Variable</label>
+                <input type="text" value={editingGame.gslt_var || ''}
+                  onChange={(e) => updateEditingGame({ gslt_var: e.target.value || undefined })}
+                  className="input" placeholder="STEAM_GSLT" />
+                <p className="text-xs text-slate-500 mt-1">
+                  For games that need a Steam server token to list publicly - the token assigned to a server in
+                  Settings is passed in this variable when it starts
+                </p>
+              </div>
+              <div>
+                <label className="block text-sm text-slate-400 mb-1">RCON Variables</label>
+                <div className="flex gap-2">
+                  <input type="text" value={editingGame.rcon?.port_var || ''}
+                    onChange={(e) => updateEditingGame({
+                      rcon: e.target.value || editingGame.rcon?.password_var
+                        ? { port_var: e.target.value, password_var: editingGame.rcon?.password_var || '' }
+                        : null,
+                    })}
+                    className="input" placeholder="RCON_PORT" />
+                  <input type="text" value={editingGame.rcon?.password_var || ''}
+                    onChange={(e) => updateEditingGame({
+                      rcon: e.target.value || editingGame.rcon?.port_var
+                        ? { port_var: editingGame.rcon?.port_var || '', password_var: e.target.value }
+                        : null,
+                    })}
+                    className="input" placeholder="RCON_PASSWORD" />
+                </div>
+                <p className="text-xs text-slate-500 mt-1">
+                  For games with Source RCON - the variables holding its port and password. Console commands go
+                  over RCON to 127.0.0.1 on that port, so give it a port that's published on this machine
+                </p>
+              </div>
+              <div>
+                <label className="block text-sm text-slate-400 mb-1">Steam Query</label>
+                <div className="flex items-center gap-2">
+                  <input type="checkbox" checked={Boolean(editingGame.steam_query)}
+                    onChange={(e) => updateEditingGame({ steam_query: e.target.checked ? { port_var: null } : null })} />
+                  <input type="text" value={editingGame.steam_query?.port_var || ''}
+                    disabled={!editingGame.steam_query}
+                    onChange={(e) => updateEditingGame({ steam_query: { port_var: e.target.value || null } })}
+                    className="input" placeholder="Game port" />
+                </div>
+                <p className="text-xs text-slate-500 mt-1">
+                  For games that answer Steam's A2S queries - the variable holding the query port, or blank when
+                  it's the game port. Shows who's on in the Players tab
+                </p>
+              </div>
               <div>
-                <label className="block text-sm text-slate-400 mb-1">Stop Command</label>
-                <input type="text" value={editingGame.stop_command}
-                  onChange={(e) => updateEditingGame({ stop_command: e.target.value })}
-                  className="input" placeholder="stop" />
+                <label className="block text-sm text-slate-400 mb-1">Install Network</label>
+                <label className="flex items-center gap-2 text-sm mb-2">
+                  <input type="checkbox" checked={editingGame.install_network?.mode === 'restricted'}
+                    onChange={(e) => updateEditingGame({
+                      install_network: e.target.checked ? { mode: 'restricted', allowed_domains: [] } : null,
+                    })} />
+                  Only let install scripts reach these domains
+                </label>
+                {editingGame.install_network?.mode === 'restricted' && (
+                  <input type="text" value={editingGame.install_network.allowed_domains.join(', ')}
+                    onChange={(e) => updateEditingGame({
+                      install_network: {
+                        mode: 'restricted',
+                        allowed_domains: e.target.value.split(',').map((d) => d.trim()).filter(Boolean),
+                      },
+                    })}
+                    className="input" placeholder="github.com, maven.org" />
+                )}
+                <p className="text-xs text-slate-500 mt-1">
+                  Subdomains are let through too, and no domains at all runs installs offline. Servers can change it in
+                  their settings
+                </p>
               </div>
               <div>
                 <label className="block text-sm text-slate-400 mb-1">Volume Path</label>
@@ -407,6 +545,10 @@ export function GamesPage() {
               <textarea value={editingGame.install_script || ''}
                 onChange={(e) => updateEditingGame({ install_script: e.target.value || undefined })}
                 className="input font-mono text-sm min-h-[100px]" placeholder="#!/bin/bash&#10;curl -o server.jar https://..." />
+              <InstallScriptLint
+                script={editingGame.install_script || ''}
+                image={editingGame.install_image || editingGame.docker_image}
+              />
             </div>
           </div>
 
@@ -471,17 +613,18 @@ export function GamesPage() {
             
             <div className="max-w-2xl">
               {/* Header row */}
-              <div className="grid grid-cols-[80px_80px_1fr_120px_32px] gap-2 text-xs text-slate-500 mb-2 px-2">
+              <div className="grid grid-cols-[80px_80px_1fr_120px_48px_32px] gap-2 text-xs text-slate-500 mb-2 px-2">
                 <span>Port</span>
                 <span>Protocol</span>
                 <span>Description</span>
                 <span>ENV Variable</span>
+                <span title="Published on this machine only - for admin interfaces, never forwarded">Local</span>
                 <span></span>
               </div>
               
               <div className="space-y-2 mb-4">
                 {editingGame.ports.map((p, i) => (
-                  <div key={i} className="grid grid-cols-[80px_80px_1fr_120px_32px] gap-2 items-center bg-slate-800/50 p-2 rounded-lg">
+                  <div key={i} className="grid grid-cols-[80px_80px_1fr_120px_48px_32px] gap-2 items-center bg-slate-800/50 p-2 rounded-lg">
                     <input type="number" value={p.container_port} 
                       onChange={(e) => updatePort(i, { container_port: parseInt(e.target.value) || 0 })}
                       className="input font-mono text-xs py-1.5" placeholder="25565" />
@@ -502,6 +645,9 @@ export function GamesPage() {
                         <option key={v.env} value={v.env}>{v.env}</option>
                       ))}
                     </select>
+                    <input type="checkbox" checked={Boolean(p.local_only)} disabled={i === 0}
+                      onChange={(e) => updatePort(i, { local_only: e.target.checked || undefined })}
+                      className="justify-self-center" />
                     <button onClick={() => removePort(i)} className="p-1.5 text-red-400 hover:bg-red-500/20 rounded justify-self-center">
                       <X size={14} />
                     </button>
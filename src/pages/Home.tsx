@@ -4,6 +4,11 @@ import { useServerStore } from '../stores/serverStore';
 import { useGamesStore } from '../stores/gamesStore';
 import { ServerCard } from '../components/ServerCard';
 import { GameIcon } from '../components/GameIcon';
+import { InstallQueue } from '../components/InstallQueue';
+import { StartupSummary } from '../components/StartupSummary';
+import { DiskSpaceWarning } from '../components/DiskSpaceWarning';
+import { DashboardSummary } from '../components/DashboardSummary';
+import { SetupChecklist } from '../components/SetupChecklist';
 
 export function Home() {
   const navigate = useNavigate();
@@ -55,6 +60,14 @@ export function Home() {
         </div>
       </div>
 
+      {servers.length > 0 && <DashboardSummary />}
+
+      <DiskSpaceWarning />
+
+      <StartupSummary />
+
+      <InstallQueue />
+
       {/* Running Servers */}
       {runningServers.length > 0 && (
         <section className="mb-8">
@@ -72,7 +85,8 @@ export function Home() {
 
       {/* Quick Create */}
       {servers.length === 0 && (
-        <section className="mb-8">
+        <section className="mb-8 space-y-4">
+          <SetupChecklist />
           <div className="card text-center py-12">
             <div className="text-5xl mb-4">🎮</div>
             <h2 className="text-xl font-semibold mb-2">No servers yet</h2>
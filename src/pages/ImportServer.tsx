@@ -0,0 +1,196 @@
+import { useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import { ArrowLeft, Loader2, AlertTriangle, Download } from 'lucide-react';
+import { invoke } from '../utils/backend';
+import { useServerStore } from '../stores/serverStore';
+import { useGamesStore } from '../stores/gamesStore';
+import type { PterodactylServer, PterodactylImportRequest, ServerResponse } from '../types';
+import { LocalFolderImport } from '../components/LocalFolderImport';
+import { ArchiveImport } from '../components/ArchiveImport';
+
+export function ImportServer() {
+  const navigate = useNavigate();
+  const { fetchServers } = useServerStore();
+  const { fetchGames } = useGamesStore();
+
+  const [panelUrl, setPanelUrl] = useState('');
+  const [apiKey, setApiKey] = useState('');
+  const [servers, setServers] = useState<PterodactylServer[] | null>(null);
+  const [selected, setSelected] = useState<PterodactylServer | null>(null);
+  const [sftpUsername, setSftpUsername] = useState('');
+  const [sftpPassword, setSftpPassword] = useState('');
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const connect = async () => {
+    setError(null);
+    setIsLoading(true);
+    try {
+      setServers(await invoke<PterodactylServer[]>('list_pterodactyl_servers', { panelUrl, apiKey }));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const runImport = async () => {
+    if (!selected) return;
+    setError(null);
+    setIsLoading(true);
+    const request: PterodactylImportRequest = {
+      panelUrl,
+      apiKey,
+      identifier: selected.identifier,
+      sftpUsername: sftpUsername.trim() || undefined,
+      sftpPassword,
+    };
+    try {
+      const response = await invoke<ServerResponse>('import_pterodactyl_server', { request });
+      await Promise.all([fetchServers(), fetchGames()]);
+      if (response.server) {
+        navigate(`/servers/${response.server.id}`);
+      }
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="animate-fade-in max-w-3xl">
+      <button
+        onClick={() => navigate(-1)}
+        className="flex items-center gap-2 text-slate-400 hover:text-white mb-6 transition-colors"
+      >
+        <ArrowLeft size={20} />
+        Back
+      </button>
+
+      <header className="mb-8">
+        <h1 className="text-3xl font-bold">Import a Server</h1>
+        <p className="text-slate-400 mt-2">
+          Bring a server exported from another machine or one you already run here, or copy one from a Pterodactyl
+          panel with its files
+        </p>
+      </header>
+
+      {error && (
+        <div className="card mb-6 border-red-500/50 bg-red-500/10 flex items-center gap-3">
+          <AlertTriangle size={20} className="text-red-400 shrink-0" />
+          <p className="text-sm text-red-300">{error}</p>
+        </div>
+      )}
+
+      <ArchiveImport onError={setError} />
+      <LocalFolderImport onError={setError} />
+
+      <section className="card mb-6">
+        <h2 className="text-lg font-semibold mb-4">From a Pterodactyl Panel</h2>
+        <div className="space-y-4">
+          <div>
+            <label className="block text-sm font-medium mb-2">Panel URL</label>
+            <input
+              type="text"
+              value={panelUrl}
+              onChange={(e) => setPanelUrl(e.target.value)}
+              placeholder="https://panel.example.com"
+              className="input"
+            />
+          </div>
+          <div>
+            <label className="block text-sm font-medium mb-2">Client API Key</label>
+            <input
+              type="password"
+              value={apiKey}
+              onChange={(e) => setApiKey(e.target.value)}
+              placeholder="ptlc_..."
+              className="input"
+            />
+            <p className="text-xs text-slate-500 mt-1">Create one under Account → API Credentials on the panel.</p>
+          </div>
+          <button
+            onClick={connect}
+            disabled={!panelUrl.trim() || !apiKey.trim() || isLoading}
+            className="btn btn-secondary"
+          >
+            {isLoading && !selected ? <Loader2 size={18} className="animate-spin" /> : null}
+            List Servers
+          </button>
+        </div>
+      </section>
+
+      {servers && (
+        <section className="card mb-6">
+          <h2 className="text-lg font-semibold mb-4">Servers</h2>
+          {servers.length === 0 ? (
+            <p className="text-sm text-slate-400">This key has no servers.</p>
+          ) : (
+            <div className="space-y-2">
+              {servers.map((server) => (
+                <div
+                  key={server.identifier}
+                  onClick={() => setSelected(server)}
+                  className={`p-3 rounded-lg border cursor-pointer transition-colors ${
+                    selected?.identifier === server.identifier
+                      ? 'border-blue-500 bg-blue-500/10'
+                      : 'border-slate-700 hover:border-slate-500'
+                  }`}
+                >
+                  <div className="flex items-center justify-between">
+                    <span className="font-medium">{server.name}</span>
+                    <span className="text-xs text-slate-500 font-mono">{server.identifier}</span>
+                  </div>
+                  <div className="text-xs text-slate-500 mt-1">
+                    {server.egg_name ?? 'Unknown egg'}
+                    {server.port ? ` · port ${server.port}` : ''}
+                    {server.memory_mb ? ` · ${server.memory_mb} MB` : ''}
+                    {server.is_suspended ? ' · suspended' : ''}
+                  </div>
+                </div>
+              ))}
+            </div>
+          )}
+        </section>
+      )}
+
+      {selected && (
+        <section className="card mb-6">
+          <h2 className="text-lg font-semibold mb-4">SFTP Login</h2>
+          <div className="space-y-4">
+            <div>
+              <label className="block text-sm font-medium mb-2">Username</label>
+              <input
+                type="text"
+                value={sftpUsername}
+                onChange={(e) => setSftpUsername(e.target.value)}
+                placeholder={`<panel username>.${selected.identifier}`}
+                className="input"
+              />
+              <p className="text-xs text-slate-500 mt-1">
+                Leave empty to use your panel username. Files are copied from {selected.sftp_host}:{selected.sftp_port}.
+              </p>
+            </div>
+            <div>
+              <label className="block text-sm font-medium mb-2">Panel Password</label>
+              <input
+                type="password"
+                value={sftpPassword}
+                onChange={(e) => setSftpPassword(e.target.value)}
+                className="input"
+              />
+            </div>
+            <p className="text-xs text-slate-500">
+              Stop the server on the panel first so its files are consistent. Large servers can take a while to copy.
+            </p>
+            <button onClick={runImport} disabled={!sftpPassword || isLoading} className="btn btn-primary">
+              {isLoading ? <Loader2 size={18} className="animate-spin" /> : <Download size={18} />}
+              {isLoading ? 'Importing...' : 'Import Server'}
+            </button>
+          </div>
+        </section>
+      )}
+    </div>
+  );
+}
@@ -3,20 +3,48 @@ import { useParams, useNavigate } from 'react-router-dom';
 import { 
   ArrowLeft, Play, Square, Trash2, RefreshCw, Send, Folder, 
   Cpu, HardDrive, Terminal, Settings, RotateCcw, Copy, 
-  Clock, Network, FolderOpen, Check, Save, Globe, Wifi, ExternalLink, Key
+  Clock, Network, FolderOpen, Check, Save, Globe, Wifi, ExternalLink, Puzzle, Mountain, Users, Eraser, Archive
 } from 'lucide-react';
 import { useServerStore } from '../stores/serverStore';
 import { useGamesStore } from '../stores/gamesStore';
 import { findGameConfig } from '../utils/gameTypes';
-import { invoke } from '@tauri-apps/api/core';
-import { listen } from '@tauri-apps/api/event';
+import { invoke, listen } from '../utils/backend';
+import { serverEventLines } from '../types';
+import type { ServerEvent, UpdateCheck } from '../types';
 import { open } from '@tauri-apps/plugin-shell';
 import { ConsoleOutput } from '../components/ConsoleOutput';
 import { DeleteConfirmDialog } from '../components/DeleteConfirmDialog';
 import { GameIcon } from '../components/GameIcon';
 import { FileManager } from '../components/FileManager';
+import { ModBrowser } from '../components/ModBrowser';
+import { ServerVersion } from '../components/ServerVersion';
+import { WorldManager } from '../components/WorldManager';
+import { PalworldAdmin } from '../components/PalworldAdmin';
+import { PlayerLists } from '../components/PlayerLists';
+import { PlayerSessions } from '../components/PlayerSessions';
+import { OnlinePlayers } from '../components/OnlinePlayers';
+import { ServerPorts } from '../components/ServerPorts';
+import { PackManager } from '../components/PackManager';
+import { SteamGuardPrompt } from '../components/SteamGuardPrompt';
+import { InstallAuthPrompt } from '../components/InstallAuthPrompt';
+import { GameVersions } from '../components/GameVersions';
+import { RustWipes } from '../components/RustWipes';
+import { GameActions } from '../components/GameActions';
+import { StartupPreview } from '../components/StartupPreview';
+import { CustomEnvEditor } from '../components/CustomEnvEditor';
+import { ConfigDiffPreview } from '../components/ConfigDiffPreview';
+import { ServerMaintenance } from '../components/ServerMaintenance';
+import { InstallNetworkSettings } from '../components/InstallNetworkSettings';
+import { AutoRestart } from '../components/AutoRestart';
+import { StartScheduleSettings } from '../components/StartScheduleSettings';
+import { ServerSchedules } from '../components/ServerSchedules';
+import { DatabaseSettings } from '../components/DatabaseSettings';
+import { ServerBackups } from '../components/ServerBackups';
+import { BackupScheduleSettings } from '../components/BackupScheduleSettings';
+import { BackupTargets } from '../components/BackupTargets';
+import { ServerExport } from '../components/ServerExport';
 
-type TabType = 'console' | 'files' | 'network' | 'settings';
+type TabType = 'console' | 'files' | 'content' | 'worlds' | 'players' | 'wipes' | 'backups' | 'network' | 'settings';
 
 export function ServerDetail() {
   const { id } = useParams<{ id: string }>();
@@ -34,11 +62,14 @@ export function ServerDetail() {
   const sendCommand = useServerStore((s) => s.sendCommand);
   const updateServerConfig = useServerStore((s) => s.updateServerConfig);
   const reinstallServer = useServerStore((s) => s.reinstallServer);
+  const retryInstall = useServerStore((s) => s.retryInstall);
   const updateServerGame = useServerStore((s) => s.updateServerGame);
   
   const { games } = useGamesStore();
 
   const [activeTab, setActiveTab] = useState<TabType>('console');
+  /** Bumped to reload the backups list after one is copied back from a target */
+  const [backupsVersion, setBackupsVersion] = useState(0);
   const [command, setCommand] = useState('');
   const [logs, setLogs] = useState<string[]>([]);
   const [isStreaming, setIsStreaming] = useState(false);
@@ -53,11 +84,11 @@ export function ServerDetail() {
   const [isEditingConfig, setIsEditingConfig] = useState(false);
   const [showDeleteDialog, setShowDeleteDialog] = useState(false);
   const [configSaved, setConfigSaved] = useState(false);
+  const [applyingConfig, setApplyingConfig] = useState(false);
   const [publicIP, setPublicIP] = useState<string>('');
-  const [oauthUrl, setOauthUrl] = useState<string | null>(null);
-  const [oauthCode, setOauthCode] = useState<string | null>(null);
-  const [oauthDismissed, setOauthDismissed] = useState(false);
   const [diskUsage, setDiskUsage] = useState<number>(0);
+  /** Last update check, or a note when there's nothing to compare */
+  const [updateCheck, setUpdateCheck] = useState<UpdateCheck | string | null>(null);
   
   const consoleRef = useRef<HTMLDivElement>(null);
   const consoleEndRef = useRef<HTMLDivElement>(null);
@@ -111,9 +142,11 @@ export function ServerDetail() {
     
     const setupStreaming = async () => {
       try {
-        const unlisten = await listen<{ server_id: string; line: string }>('server-log', (event) => {
-          if (event.payload.server_id === id) {
-            setLogs((prev) => [...prev, event.payload.line]);
+        const unlisten = await listen<ServerEvent>('server-event', (event) => {
+          if (event.payload.server_id !== id) return;
+          const lines = serverEventLines(event.payload);
+          if (lines.length > 0) {
+            setLogs((prev) => [...prev, ...lines]);
           }
         });
         
@@ -197,46 +230,6 @@ export function ServerDetail() {
     lastLogCountRef.current = logs.length;
   }, [logs.length, autoScroll]);
 
-  // Detect OAuth URLs in logs (for Hytale and similar games)
-  useEffect(() => {
-    // Don't detect if server is stopped or user dismissed or we already have a URL
-    if (server?.status === 'stopped' || server?.status === 'error') {
-      if (oauthUrl) {
-        setOauthUrl(null);
-        setOauthCode(null);
-      }
-      // Reset dismissed flag when server stops so it can show again on next start
-      setOauthDismissed(false);
-      return;
-    }
-    
-    // Don't re-detect if user dismissed or already detected
-    if (oauthDismissed || oauthUrl) return;
-    
-    // Search from newest to oldest log lines
-    const recentLogs = logs.slice(-30).reverse();
-    for (const line of recentLogs) {
-      // Strip ANSI codes and any special characters
-      const cleanLine = line
-        .replace(/\x1b\[[0-9;]*m/g, '')
-        .replace(/\u001b\[[0-9;]*m/g, '')
-        .replace(/[\x00-\x1F]/g, ''); // Remove control characters
-      
-      // Look for the specific Hytale OAuth URL with user_code parameter
-      // The URL format is: https://oauth.accounts.hytale.com/oauth2/device/verify?user_code=XXXXX
-      const urlRegex = /https:\/\/oauth\.accounts\.hytale\.com\/oauth2\/device\/verify\?user_code=([A-Za-z0-9]+)/;
-      const match = cleanLine.match(urlRegex);
-      
-      if (match) {
-        const detectedUrl = match[0];
-        const detectedCode = match[1];
-        setOauthUrl(detectedUrl);
-        setOauthCode(detectedCode);
-        return;
-      }
-    }
-  }, [logs, oauthUrl, oauthDismissed, server?.status]);
-
   const handleScroll = () => {
     if (consoleRef.current) {
       const { scrollTop, scrollHeight, clientHeight } = consoleRef.current;
@@ -287,6 +280,15 @@ export function ServerDetail() {
     );
   }
 
+  // Java servers load plugins or mods; the backend works out which from the files
+  const isMinecraft = server.game_type.includes('minecraft');
+  const supportsMods = isMinecraft && !server.game_type.includes('bedrock');
+  const isRust = server.game_type === 'rust';
+  const isPalworld = server.game_type === 'palworld';
+  const tracksSessions = Boolean(gameConfig?.player_log);
+  const answersQuery = isMinecraft || Boolean(gameConfig?.steam_query);
+  const hasPlayersTab = isMinecraft || isPalworld || tracksSessions || answersQuery;
+
   const handleSendCommand = async () => {
     if (!command.trim()) return;
     const cmd = command.trim();
@@ -324,16 +326,11 @@ export function ServerDetail() {
     setLogs([]);
     userScrolledRef.current = false;
     setAutoScroll(true);
-    setOauthUrl(null);
-    setOauthCode(null);
-    setOauthDismissed(false); // Reset so OAuth can be detected again
     await startServer(server.id);
   };
 
   const handleStop = async () => {
     setLogs(['Stopping server...']);
-    setOauthUrl(null);
-    setOauthCode(null);
     await stopServer(server.id);
   };
 
@@ -345,9 +342,9 @@ export function ServerDetail() {
     }, 2000);
   };
 
-  const handleDelete = async (deleteData: boolean) => {
+  const handleDelete = async (confirmName: string, deleteData: boolean, trash: boolean) => {
     setShowDeleteDialog(false);
-    await deleteServer(server.id, deleteData);
+    await deleteServer(server.id, confirmName, deleteData, trash);
     navigate('/servers');
   };
 
@@ -365,9 +362,6 @@ export function ServerDetail() {
     setLogs([]);
     userScrolledRef.current = false;
     setAutoScroll(true);
-    setOauthUrl(null);
-    setOauthCode(null);
-    setOauthDismissed(false); // Reset so OAuth can be detected again
   };
   
   const handleSaveConfig = async () => {
@@ -379,6 +373,17 @@ export function ServerDetail() {
     }
   };
 
+  const handleApplyConfig = async () => {
+    setApplyingConfig(true);
+    try {
+      await invoke('apply_config', { serverId: server.id });
+    } catch (e) {
+      console.error('Failed to apply config:', e);
+    }
+    setApplyingConfig(false);
+    fetchServers();
+  };
+
   // Get friendly label for config key
   const getConfigLabel = (key: string): string => {
     const labels: Record<string, string> = {
@@ -424,6 +429,7 @@ export function ServerDetail() {
     installing: { bg: 'bg-blue-500/10', border: 'border-blue-500/30', text: 'text-blue-500', dot: 'bg-blue-500' },
     stopping: { bg: 'bg-yellow-500/10', border: 'border-yellow-500/30', text: 'text-yellow-500', dot: 'bg-yellow-500' },
     error: { bg: 'bg-red-500/10', border: 'border-red-500/30', text: 'text-red-500', dot: 'bg-red-500' },
+    crashlooping: { bg: 'bg-red-500/10', border: 'border-red-500/30', text: 'text-red-500', dot: 'bg-red-500' },
   };
 
   // Format bytes to human readable
@@ -436,6 +442,9 @@ export function ServerDetail() {
   };
 
   const status = statusColors[server.status] || statusColors.stopped;
+  // The probe of the running container found no way to send it commands
+  const noConsoleBridge =
+    server.console?.container_id === server.container_id && server.console.bridges.length === 0;
   const serverAddress = `localhost:${server.port}`;
   const publicAddress = publicIP ? `${publicIP}:${server.port}` : '';
 
@@ -484,7 +493,9 @@ export function ServerDetail() {
           {/* Status Badge */}
           <div className={`px-4 py-2 rounded-lg ${status.bg} ${status.border} border flex items-center gap-2`}>
             <span className={`w-2 h-2 rounded-full ${status.dot} ${server.status === 'running' || server.status === 'starting' ? 'animate-pulse' : ''}`}></span>
-            <span className={`font-medium capitalize ${status.text}`}>{server.status}</span>
+            <span className={`font-medium capitalize ${status.text}`}>
+              {server.status === 'crashlooping' ? 'Crash looping' : server.status}
+            </span>
           </div>
         </div>
 
@@ -555,6 +566,16 @@ export function ServerDetail() {
           </div>
         )}
 
+        {server.status === 'crashlooping' && (
+          <div className="mt-6 p-4 rounded-lg bg-red-500/10 border border-red-500/30 text-sm text-red-400">
+            The server kept crashing, so it isn't restarted any more. Check the crash report under{' '}
+            <button onClick={() => setActiveTab('settings')} className="underline">
+              Settings
+            </button>{' '}
+            and start it again when it's fixed.
+          </div>
+        )}
+
         {/* Disk Usage - Always visible when server is NOT running */}
         {server.status !== 'running' && (
           <div className="mt-6 p-4 bg-zinc-800/50 rounded-lg">
@@ -572,7 +593,7 @@ export function ServerDetail() {
 
         {/* Action Buttons */}
         <div className="flex items-center gap-3 mt-6 pt-6 border-t border-zinc-800">
-          {server.status === 'stopped' ? (
+          {server.status === 'stopped' || server.status === 'crashlooping' ? (
             <button onClick={handleStart} disabled={isLoading} className="btn btn-success">
               <Play size={18} /> Start Server
             </button>
@@ -619,65 +640,8 @@ export function ServerDetail() {
         onCancel={() => setShowDeleteDialog(false)}
       />
 
-      {/* OAuth Authentication Popup (for Hytale etc.) */}
-      {oauthUrl && (
-        <div className="fixed inset-0 z-50 flex items-center justify-center">
-          <div className="absolute inset-0 bg-black/70 backdrop-blur-sm" onClick={() => { setOauthUrl(null); setOauthDismissed(true); }} />
-          <div className="relative bg-zinc-900 border border-indigo-500/50 rounded-xl shadow-2xl max-w-md w-full mx-4 p-6 animate-fade-in">
-            <div className="flex items-center gap-3 mb-4">
-              <div className="w-12 h-12 rounded-lg bg-indigo-500/20 flex items-center justify-center">
-                <Key size={24} className="text-indigo-400" />
-              </div>
-              <div>
-                <h3 className="text-lg font-semibold">Authentication Required</h3>
-                <p className="text-sm text-zinc-400">Hytale needs you to log in</p>
-              </div>
-            </div>
-            
-            <p className="text-zinc-300 mb-4">
-              This server requires authentication with your Hytale account. Click the button below to open the authentication page.
-            </p>
-            
-            {oauthCode && (
-              <div className="mb-4 p-3 bg-zinc-800 rounded-lg">
-                <div className="text-xs text-zinc-500 mb-1">Authorization Code</div>
-                <div className="flex items-center justify-between">
-                  <code className="text-2xl font-mono font-bold text-white tracking-wider">{oauthCode}</code>
-                  <button 
-                    onClick={() => {
-                      navigator.clipboard.writeText(oauthCode);
-                      setCopied('oauth');
-                      setTimeout(() => setCopied(null), 2000);
-                    }}
-                    className="p-2 hover:bg-zinc-700 rounded transition-colors"
-                  >
-                    {copied === 'oauth' ? <Check size={16} className="text-green-500" /> : <Copy size={16} className="text-zinc-400" />}
-                  </button>
-                </div>
-              </div>
-            )}
-            
-            <div className="flex items-center gap-3">
-              <button
-                onClick={() => oauthUrl && open(oauthUrl)}
-                className="btn btn-primary flex-1"
-              >
-                <ExternalLink size={16} /> Open Authentication Page
-              </button>
-              <button
-                onClick={() => { setOauthUrl(null); setOauthDismissed(true); }}
-                className="btn btn-secondary"
-              >
-                Dismiss
-              </button>
-            </div>
-            
-            <p className="text-xs text-zinc-500 mt-4 text-center">
-              After authenticating, the server will continue starting automatically.
-            </p>
-          </div>
-        </div>
-      )}
+      <SteamGuardPrompt serverId={server.id} />
+      <InstallAuthPrompt serverId={server.id} />
 
       {/* Tabs */}
       <div className="flex items-center gap-1 mb-4">
@@ -701,6 +665,64 @@ export function ServerDetail() {
         >
           <Folder size={18} /> Files
         </button>
+        {supportsMods && (
+          <button
+            onClick={() => setActiveTab('content')}
+            className={`px-4 py-2 rounded-lg font-medium transition-colors flex items-center gap-2 ${
+              activeTab === 'content' 
+                ? 'bg-zinc-800 text-white' 
+                : 'text-zinc-400 hover:text-white hover:bg-zinc-800/50'
+            }`}
+          >
+            <Puzzle size={18} /> Mods
+          </button>
+        )}
+        {supportsMods && (
+          <button
+            onClick={() => setActiveTab('worlds')}
+            className={`px-4 py-2 rounded-lg font-medium transition-colors flex items-center gap-2 ${
+              activeTab === 'worlds' 
+                ? 'bg-zinc-800 text-white' 
+                : 'text-zinc-400 hover:text-white hover:bg-zinc-800/50'
+            }`}
+          >
+            <Mountain size={18} /> Worlds
+          </button>
+        )}
+        {hasPlayersTab && (
+          <button
+            onClick={() => setActiveTab('players')}
+            className={`px-4 py-2 rounded-lg font-medium transition-colors flex items-center gap-2 ${
+              activeTab === 'players' 
+                ? 'bg-zinc-800 text-white' 
+                : 'text-zinc-400 hover:text-white hover:bg-zinc-800/50'
+            }`}
+          >
+            <Users size={18} /> Players
+          </button>
+        )}
+        {isRust && (
+          <button
+            onClick={() => setActiveTab('wipes')}
+            className={`px-4 py-2 rounded-lg font-medium transition-colors flex items-center gap-2 ${
+              activeTab === 'wipes' 
+                ? 'bg-zinc-800 text-white' 
+                : 'text-zinc-400 hover:text-white hover:bg-zinc-800/50'
+            }`}
+          >
+            <Eraser size={18} /> Wipes
+          </button>
+        )}
+        <button
+          onClick={() => setActiveTab('backups')}
+          className={`px-4 py-2 rounded-lg font-medium transition-colors flex items-center gap-2 ${
+            activeTab === 'backups' 
+              ? 'bg-zinc-800 text-white' 
+              : 'text-zinc-400 hover:text-white hover:bg-zinc-800/50'
+          }`}
+        >
+          <Archive size={18} /> Backups
+        </button>
         <button
           onClick={() => setActiveTab('network')}
           className={`px-4 py-2 rounded-lg font-medium transition-colors flex items-center gap-2 ${
@@ -758,6 +780,8 @@ export function ServerDetail() {
             )}
           </div>
 
+          {server.status === 'running' && <GameActions serverId={server.id} />}
+
           <ConsoleOutput
             logs={logs}
             consoleRef={consoleRef}
@@ -772,7 +796,7 @@ export function ServerDetail() {
             }
           />
 
-          {server.status === 'running' && gameConfig?.console && (
+          {server.status === 'running' && gameConfig?.console && !noConsoleBridge && (
             <div className="console-input-wrapper">
               <span className="text-green-500">❯</span>
               <input
@@ -794,10 +818,12 @@ export function ServerDetail() {
             </div>
           )}
           
-          {server.status === 'running' && !gameConfig?.console && (
+          {server.status === 'running' && (!gameConfig?.console || noConsoleBridge) && (
             <div className="px-4 py-3 border-t border-zinc-800 text-sm text-zinc-500 flex items-center gap-2">
               <Terminal size={16} />
-              Console commands not supported for this game
+              {gameConfig?.console
+                ? "This server's image has no console that takes commands"
+                : 'Console commands not supported for this game'}
             </div>
           )}
         </div>
@@ -808,111 +834,148 @@ export function ServerDetail() {
           <FileManager 
             rootPath={String(server.data_path)} 
             serverName={server.name}
+            serverId={server.id}
           />
         </div>
       )}
 
+      {activeTab === 'content' && supportsMods && (
+        <div className="space-y-4">
+          <ServerVersion serverId={server.id} running={server.status !== 'stopped' && server.status !== 'error'} />
+          <ModBrowser serverId={server.id} />
+        </div>
+      )}
+
+      {activeTab === 'worlds' && supportsMods && (
+        <div className="space-y-4">
+          <WorldManager serverId={server.id} running={server.status !== 'stopped' && server.status !== 'error'} />
+          <PackManager serverId={server.id} />
+        </div>
+      )}
+
+      {activeTab === 'players' && hasPlayersTab && (
+        <div className="space-y-4">
+          {isPalworld && <PalworldAdmin serverId={server.id} running={server.status === 'running'} />}
+          {answersQuery && <OnlinePlayers serverId={server.id} running={server.status === 'running'} />}
+          {tracksSessions && <PlayerSessions serverId={server.id} />}
+          {isMinecraft && <PlayerLists serverId={server.id} bedrock={server.game_type.includes('bedrock')} />}
+        </div>
+      )}
+
+      {activeTab === 'wipes' && isRust && <RustWipes serverId={server.id} />}
+
+      {activeTab === 'backups' && (
+        <div className="space-y-4">
+          <ServerBackups key={backupsVersion} serverId={server.id} targets={server.backup_targets} />
+          <BackupScheduleSettings server={server} onSaved={fetchServers} />
+          <BackupTargets server={server} onSaved={fetchServers} onPulled={() => setBackupsVersion((v) => v + 1)} />
+        </div>
+      )}
+
       {activeTab === 'network' && (
-        <div className="card">
-          <h3 className="text-lg font-semibold mb-4 flex items-center gap-2">
-            <Globe size={20} /> Network & Public Access
-          </h3>
+        <div className="space-y-4">
+          <div className="card">
+            <h3 className="text-lg font-semibold mb-4 flex items-center gap-2">
+              <Globe size={20} /> Network & Public Access
+            </h3>
           
-          <div className="space-y-4">
-            {/* Local Address */}
-            <div className="flex items-center justify-between p-4 bg-zinc-800/50 rounded-lg">
-              <div className="flex items-center gap-3">
-                <div className="w-10 h-10 rounded-lg bg-cyan-500/20 flex items-center justify-center">
-                  <Wifi size={20} className="text-cyan-400" />
-                </div>
-                <div>
-                  <div className="font-medium">Local Network</div>
-                  <div className="text-sm text-zinc-400">Players on your network can join</div>
-                </div>
-              </div>
-              <div className="flex items-center gap-2">
-                <code className="px-3 py-1.5 bg-zinc-900 rounded font-mono">{serverAddress}</code>
-                <button 
-                  onClick={() => copyToClipboard(serverAddress, 'local2')}
-                  className="p-2 hover:bg-zinc-700 rounded transition-colors"
-                >
-                  {copied === 'local2' ? <Check size={16} className="text-green-500" /> : <Copy size={16} className="text-zinc-400" />}
-                </button>
-              </div>
-            </div>
-            
-            {/* Public Address */}
-            <div className="p-4 bg-zinc-800/50 rounded-lg border border-zinc-700">
-              <div className="flex items-center justify-between">
+            <div className="space-y-4">
+              {/* Local Address */}
+              <div className="flex items-center justify-between p-4 bg-zinc-800/50 rounded-lg">
                 <div className="flex items-center gap-3">
-                  <div className="w-10 h-10 rounded-lg bg-green-500/20 flex items-center justify-center">
-                    <Globe size={20} className="text-green-400" />
+                  <div className="w-10 h-10 rounded-lg bg-cyan-500/20 flex items-center justify-center">
+                    <Wifi size={20} className="text-cyan-400" />
                   </div>
                   <div>
-                    <div className="font-medium">Public Internet</div>
-                    <div className="text-sm text-zinc-400">Allow anyone to join from the internet</div>
+                    <div className="font-medium">Local Network</div>
+                    <div className="text-sm text-zinc-400">Players on your network can join</div>
                   </div>
                 </div>
+                <div className="flex items-center gap-2">
+                  <code className="px-3 py-1.5 bg-zinc-900 rounded font-mono">{serverAddress}</code>
+                  <button 
+                    onClick={() => copyToClipboard(serverAddress, 'local2')}
+                    className="p-2 hover:bg-zinc-700 rounded transition-colors"
+                  >
+                    {copied === 'local2' ? <Check size={16} className="text-green-500" /> : <Copy size={16} className="text-zinc-400" />}
+                  </button>
+                </div>
               </div>
-              
-              {publicIP && (
-                <div className="mt-4 p-3 bg-zinc-900 rounded-lg">
-                  <div className="flex items-center justify-between mb-2">
-                    <span className="text-sm text-zinc-400">Your Public Address</span>
-                    <button 
-                      onClick={() => copyToClipboard(publicAddress, 'public')}
-                      className="text-xs text-indigo-400 hover:text-indigo-300 flex items-center gap-1"
-                    >
-                      {copied === 'public' ? <Check size={12} /> : <Copy size={12} />}
-                      Copy
-                    </button>
+            
+              {/* Public Address */}
+              <div className="p-4 bg-zinc-800/50 rounded-lg border border-zinc-700">
+                <div className="flex items-center justify-between">
+                  <div className="flex items-center gap-3">
+                    <div className="w-10 h-10 rounded-lg bg-green-500/20 flex items-center justify-center">
+                      <Globe size={20} className="text-green-400" />
+                    </div>
+                    <div>
+                      <div className="font-medium">Public Internet</div>
+                      <div className="text-sm text-zinc-400">Allow anyone to join from the internet</div>
+                    </div>
                   </div>
-                  <code className="text-lg font-mono text-white">{publicAddress}</code>
                 </div>
-              )}
               
-              <div className="mt-4 p-3 bg-yellow-500/10 border border-yellow-500/30 rounded-lg">
-                <div className="flex items-start gap-2 text-sm text-yellow-400">
-                  <span className="text-yellow-500 mt-0.5">⚠️</span>
-                  <div>
-                    <div className="font-medium mb-1">Port Forwarding Required</div>
-                    <p className="text-yellow-400/80">
-                      To make your server public, you need to forward port <strong>{server.port}</strong> (TCP/UDP) in your router settings.
-                    </p>
-                    <button 
-                      onClick={() => open('https://portforward.com/')}
-                      className="mt-2 text-yellow-300 hover:text-yellow-200 flex items-center gap-1"
-                    >
-                      Learn how to port forward <ExternalLink size={12} />
-                    </button>
+                {publicIP && (
+                  <div className="mt-4 p-3 bg-zinc-900 rounded-lg">
+                    <div className="flex items-center justify-between mb-2">
+                      <span className="text-sm text-zinc-400">Your Public Address</span>
+                      <button 
+                        onClick={() => copyToClipboard(publicAddress, 'public')}
+                        className="text-xs text-indigo-400 hover:text-indigo-300 flex items-center gap-1"
+                      >
+                        {copied === 'public' ? <Check size={12} /> : <Copy size={12} />}
+                        Copy
+                      </button>
+                    </div>
+                    <code className="text-lg font-mono text-white">{publicAddress}</code>
                   </div>
-                </div>
-              </div>
+                )}
               
-              <div className="mt-4 p-4 bg-gradient-to-r from-indigo-500/10 to-purple-500/10 border border-indigo-500/30 rounded-lg">
-                <div className="flex items-start gap-3">
-                  <div className="w-10 h-10 rounded-lg bg-indigo-500/20 flex items-center justify-center flex-shrink-0">
-                    <span className="text-xl">🌊</span>
-                  </div>
-                  <div className="flex-1">
-                    <div className="font-semibold text-white mb-1">Want hassle-free public hosting?</div>
-                    <p className="text-sm text-zinc-400 mb-3">
-                      Skip port forwarding and router configs. Serverwave provides instant public game servers with DDoS protection, automatic backups, and 24/7 uptime.
-                    </p>
-                    <div className="flex items-center gap-3">
+                <div className="mt-4 p-3 bg-yellow-500/10 border border-yellow-500/30 rounded-lg">
+                  <div className="flex items-start gap-2 text-sm text-yellow-400">
+                    <span className="text-yellow-500 mt-0.5">⚠️</span>
+                    <div>
+                      <div className="font-medium mb-1">Port Forwarding Required</div>
+                      <p className="text-yellow-400/80">
+                        To make your server public, you need to forward port <strong>{server.port}</strong> (TCP/UDP) in your router settings.
+                      </p>
                       <button 
-                        onClick={() => open('https://serverwave.com/')}
-                        className="btn btn-primary text-sm"
+                        onClick={() => open('https://portforward.com/')}
+                        className="mt-2 text-yellow-300 hover:text-yellow-200 flex items-center gap-1"
                       >
-                        Try Serverwave <ExternalLink size={14} />
+                        Learn how to port forward <ExternalLink size={12} />
                       </button>
-                      <span className="text-xs text-zinc-500">Free tier available</span>
+                    </div>
+                  </div>
+                </div>
+              
+                <div className="mt-4 p-4 bg-gradient-to-r from-indigo-500/10 to-purple-500/10 border border-indigo-500/30 rounded-lg">
+                  <div className="flex items-start gap-3">
+                    <div className="w-10 h-10 rounded-lg bg-indigo-500/20 flex items-center justify-center flex-shrink-0">
+                      <span className="text-xl">🌊</span>
+                    </div>
+                    <div className="flex-1">
+                      <div className="font-semibold text-white mb-1">Want hassle-free public hosting?</div>
+                      <p className="text-sm text-zinc-400 mb-3">
+                        Skip port forwarding and router configs. Serverwave provides instant public game servers with DDoS protection, automatic backups, and 24/7 uptime.
+                      </p>
+                      <div className="flex items-center gap-3">
+                        <button 
+                          onClick={() => open('https://serverwave.com/')}
+                          className="btn btn-primary text-sm"
+                        >
+                          Try Serverwave <ExternalLink size={14} />
+                        </button>
+                        <span className="text-xs text-zinc-500">Free tier available</span>
+                      </div>
                     </div>
                   </div>
                 </div>
               </div>
             </div>
           </div>
+          <ServerPorts serverId={server.id} status={server.status} />
         </div>
       )}
 
@@ -953,6 +1016,15 @@ export function ServerDetail() {
               </div>
             )}
             
+            {server.config_outdated && !isEditingConfig && (
+              <div className="mb-4 p-3 bg-yellow-500/10 border border-yellow-500/30 rounded-lg text-sm text-yellow-400 flex items-center justify-between gap-2">
+                <span>The server was created with older settings - they're applied when it next starts.</span>
+                <button onClick={handleApplyConfig} disabled={applyingConfig} className="btn btn-secondary text-sm">
+                  {applyingConfig ? 'Applying...' : server.status === 'running' ? 'Restart & apply' : 'Apply now'}
+                </button>
+              </div>
+            )}
+
             {isEditingConfig && (
               <div className="mb-4 p-3 bg-blue-500/10 border border-blue-500/30 rounded-lg text-sm text-blue-400">
                 💡 Changes will be applied on next server restart
@@ -1008,6 +1080,26 @@ export function ServerDetail() {
             </div>
           </div>
           
+          <CustomEnvEditor server={server} onSaved={fetchServers} />
+
+          <AutoRestart server={server} onSaved={fetchServers} />
+
+          <StartScheduleSettings server={server} onSaved={fetchServers} />
+
+          <ServerSchedules serverId={server.id} />
+
+          <DatabaseSettings server={server} onSaved={fetchServers} />
+
+          <ServerMaintenance server={server} onSaved={fetchServers} />
+
+          {gameConfig?.install_script && (
+            <InstallNetworkSettings server={server} gameDefault={gameConfig.install_network ?? null} onSaved={fetchServers} />
+          )}
+
+          <StartupPreview serverId={server.id} />
+
+          <ConfigDiffPreview serverId={server.id} />
+
           {/* Maintenance */}
           {gameConfig?.install_script && (
             <div className="card">
@@ -1021,10 +1113,35 @@ export function ServerDetail() {
                   <div>
                     <div className="font-medium">Update Server</div>
                     <div className="text-sm text-zinc-400">Run install script again to update game files (keeps your data)</div>
+                    {typeof updateCheck === 'string' && <div className="text-sm mt-1 text-zinc-500">{updateCheck}</div>}
+                    {updateCheck && typeof updateCheck !== 'string' && (
+                      <div className={`text-sm mt-1 ${updateCheck.update_available ? 'text-yellow-400' : 'text-zinc-500'}`}>
+                        {updateCheck.error
+                          ? `Couldn't check: ${updateCheck.error}`
+                          : updateCheck.update_available
+                            ? `Build ${updateCheck.latest} is out (installed: ${updateCheck.installed})`
+                            : `Up to date (build ${updateCheck.installed})`}
+                      </div>
+                    )}
                   </div>
+                  <button
+                    onClick={async () => {
+                      try {
+                        const check = await invoke<UpdateCheck | null>('check_game_updates', { serverId: server.id });
+                        setUpdateCheck(check ?? "This game's updates can't be checked - run Update to get the latest");
+                      } catch (e) {
+                        setUpdateCheck(String(e));
+                      }
+                    }}
+                    disabled={!server.installed || server.status === 'installing'}
+                    className="btn btn-secondary mr-2"
+                  >
+                    Check
+                  </button>
                   <button 
                     onClick={async () => {
                       setLogs([]);
+                      setUpdateCheck(null);
                       await updateServerGame(server.id);
                     }}
                     disabled={isLoading || server.status === 'running' || server.status === 'installing'}
@@ -1034,6 +1151,30 @@ export function ServerDetail() {
                   </button>
                 </div>
                 
+                <GameVersions server={server} onChanged={fetchServers} />
+
+                {/* Retry Button - only after a failed install */}
+                {server.install_state && (
+                  <div className="flex items-center justify-between p-4 bg-zinc-800/50 rounded-lg">
+                    <div>
+                      <div className="font-medium">Retry Install</div>
+                      <div className="text-sm text-zinc-400">
+                        {`Failed${server.install_state.attempts > 1 ? ` ${server.install_state.attempts} times` : ''}`}
+                        {server.install_state.failed_step ? ` at: ${server.install_state.failed_step}` : ''}. Resumes
+                        without deleting what was already downloaded
+                        {server.install_state.steamcmd_done ? ' and skips SteamCMD' : ''}.
+                      </div>
+                    </div>
+                    <button
+                      onClick={() => retryInstall(server.id)}
+                      disabled={isLoading || server.status === 'installing'}
+                      className="btn btn-secondary"
+                    >
+                      <RefreshCw size={16} /> Retry
+                    </button>
+                  </div>
+                )}
+
                 {/* Reinstall Button */}
                 <div className="flex items-center justify-between p-4 bg-yellow-500/5 border border-yellow-500/20 rounded-lg">
                   <div>
@@ -1057,6 +1198,8 @@ export function ServerDetail() {
             </div>
           )}
           
+          <ServerExport server={server} />
+
           {/* Danger Zone */}
           <div className="card border-red-900/30">
             <h4 className="font-medium text-red-400 mb-3">Danger Zone</h4>
@@ -1,5 +1,5 @@
 import { useNavigate } from 'react-router-dom';
-import { Plus, RefreshCw } from 'lucide-react';
+import { Download, Plus, RefreshCw } from 'lucide-react';
 import { useServerStore } from '../stores/serverStore';
 import { ServerCard } from '../components/ServerCard';
 
@@ -24,6 +24,13 @@ export function Servers() {
           >
             <RefreshCw size={20} className={isLoading ? 'animate-spin' : ''} />
           </button>
+          <button
+            onClick={() => navigate('/servers/import')}
+            className="btn btn-secondary"
+          >
+            <Download size={20} />
+            Import
+          </button>
           <button
             onClick={() => navigate('/servers/create')}
             className="btn btn-primary"
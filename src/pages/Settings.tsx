@@ -1,13 +1,77 @@
-import { useEffect } from 'react';
+import { useEffect, useState } from 'react';
 import { useDockerStore } from '../stores/dockerStore';
-import { RefreshCw, ExternalLink } from 'lucide-react';
+import { getRemoteBackend, invoke, setRemoteBackend } from '../utils/backend';
+import { RefreshCw, ExternalLink, Lock, Unlock, FileArchive } from 'lucide-react';
+import type { LockStatus } from '../types';
+import { UsersSettings } from '../components/UsersSettings';
+import { ReadOnlySettings } from '../components/ReadOnlyMode';
+import { DockerConnectionSettings } from '../components/DockerConnectionSettings';
+import { GeneralSettings } from '../components/GeneralSettings';
+import { DataLocationSettings } from '../components/DataLocationSettings';
+import { AppStateSettings } from '../components/AppStateSettings';
+import { AppLogs } from '../components/AppLogs';
+import { DeletedServers } from '../components/DeletedServers';
+import { HooksSettings } from '../components/HooksSettings';
+import { CacheSettings } from '../components/CacheSettings';
+import { GameImageSettings } from '../components/GameImageSettings';
+import { GameLibrarySettings } from '../components/GameLibrarySettings';
+import { SteamAccountSettings } from '../components/SteamAccountSettings';
+import { SteamTokenSettings } from '../components/SteamTokenSettings';
+import { RemoteAccessSettings } from '../components/RemoteAccessSettings';
 
 export function Settings() {
   const { status, info, checkStatus, fetchInfo, isChecking } = useDockerStore();
+  const [remoteUrl, setRemoteUrl] = useState(getRemoteBackend()?.url ?? '');
+  const [remoteToken, setRemoteToken] = useState(getRemoteBackend()?.token ?? '');
+
+  const saveRemote = () => {
+    setRemoteBackend(remoteUrl.trim() ? { url: remoteUrl.trim(), token: remoteToken.trim() } : null);
+    window.location.reload();
+  };
+
+  const [lockStatus, setLockStatus] = useState<LockStatus | null>(null);
+  const [currentPassword, setCurrentPassword] = useState('');
+  const [newPassword, setNewPassword] = useState('');
+  const [apiToken, setApiToken] = useState<string | null>(null);
+  const [lockError, setLockError] = useState<string | null>(null);
+  const [diagnosticsPath, setDiagnosticsPath] = useState<string | null>(null);
+  const [isExporting, setIsExporting] = useState(false);
+
+  const exportDiagnostics = async () => {
+    setIsExporting(true);
+    try {
+      setDiagnosticsPath(await invoke<string>('export_diagnostics'));
+    } catch (e) {
+      setDiagnosticsPath(`Export failed: ${String(e)}`);
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const runLockAction = async (action: () => Promise<LockStatus>) => {
+    setLockError(null);
+    try {
+      setLockStatus(await action());
+      setCurrentPassword('');
+      setNewPassword('');
+    } catch (e) {
+      setLockError(String(e));
+    }
+  };
+
+  const showApiToken = async () => {
+    setLockError(null);
+    try {
+      setApiToken(await invoke<string>('get_api_token'));
+    } catch (e) {
+      setLockError(String(e));
+    }
+  };
 
   useEffect(() => {
     checkStatus();
     fetchInfo();
+    invoke<LockStatus>('get_lock_status').then(setLockStatus).catch(() => {});
   }, []);
 
   return (
@@ -41,6 +105,13 @@ export function Settings() {
             </span>
           </div>
 
+          {status?.endpoint && (
+            <div className="flex items-center justify-between py-2 border-b border-slate-700">
+              <span className="text-slate-400">Endpoint</span>
+              <span className="font-mono text-sm">{status.endpoint}</span>
+            </div>
+          )}
+
           {info && (
             <>
               <div className="flex items-center justify-between py-2 border-b border-slate-700">
@@ -72,6 +143,148 @@ export function Settings() {
         </div>
       </section>
 
+      {/* Docker Connection */}
+      <DockerConnectionSettings onSaved={() => { checkStatus(); fetchInfo(); }} />
+
+      {/* General */}
+      <GeneralSettings />
+
+      {/* Remote Backend */}
+      <section className="card mb-6">
+        <h2 className="text-xl font-semibold mb-2">Remote Backend</h2>
+        <p className="text-slate-400 text-sm mb-4">
+          Manage servers on another machine running <code>serverwave-anywhere --headless</code>.
+          Leave the address empty to use this computer.
+        </p>
+        <div className="space-y-3">
+          <input
+            type="text"
+            value={remoteUrl}
+            onChange={(e) => setRemoteUrl(e.target.value)}
+            placeholder="http://192.168.1.10:7450"
+            className="input w-full"
+          />
+          <input
+            type="password"
+            value={remoteToken}
+            onChange={(e) => setRemoteToken(e.target.value)}
+            placeholder="API token (serverwave-anywhere --print-api-token on the host)"
+            className="input w-full"
+          />
+          <button onClick={saveRemote} className="btn btn-primary text-sm">
+            Save &amp; Reconnect
+          </button>
+        </div>
+      </section>
+
+      <RemoteAccessSettings />
+
+      {/* App Lock */}
+      {lockStatus && (
+        <section className="card mb-6">
+          <div className="flex items-center justify-between mb-2">
+            <h2 className="text-xl font-semibold">App Lock</h2>
+            {lockStatus.password_set && (
+              <span className={lockStatus.unlocked ? 'text-emerald-500' : 'text-amber-500'}>
+                {lockStatus.unlocked ? '● Unlocked' : '● Locked'}
+              </span>
+            )}
+          </div>
+          <p className="text-slate-400 text-sm mb-4">
+            Ask for a password before deleting or reinstalling servers, deleting files or games and
+            removing nodes. The password is stored in your system keychain.
+          </p>
+          <div className="space-y-3">
+            {lockStatus.password_set && !lockStatus.unlocked && (
+              <div className="flex gap-2">
+                <input
+                  type="password"
+                  value={currentPassword}
+                  onChange={(e) => setCurrentPassword(e.target.value)}
+                  placeholder="Password"
+                  className="input flex-1"
+                />
+                <button
+                  onClick={() => runLockAction(() => invoke<LockStatus>('unlock_app', { password: currentPassword }))}
+                  className="btn btn-primary text-sm"
+                >
+                  <Unlock size={16} />
+                  Unlock
+                </button>
+              </div>
+            )}
+            {lockStatus.password_set && lockStatus.unlocked && (
+              <button
+                onClick={() => runLockAction(() => invoke<LockStatus>('lock_app'))}
+                className="btn btn-secondary text-sm"
+              >
+                <Lock size={16} />
+                Lock Now
+              </button>
+            )}
+            {lockStatus.password_set && lockStatus.unlocked && (
+              <input
+                type="password"
+                value={currentPassword}
+                onChange={(e) => setCurrentPassword(e.target.value)}
+                placeholder="Current password"
+                className="input w-full"
+              />
+            )}
+            <div className="flex gap-2">
+              <input
+                type="password"
+                value={newPassword}
+                onChange={(e) => setNewPassword(e.target.value)}
+                placeholder={lockStatus.password_set ? 'New password (empty to remove)' : 'New password'}
+                className="input flex-1"
+              />
+              <button
+                onClick={() =>
+                  runLockAction(() =>
+                    invoke<LockStatus>('set_app_password', {
+                      currentPassword: currentPassword || null,
+                      newPassword: newPassword || null,
+                    })
+                  )
+                }
+                className="btn btn-secondary text-sm"
+              >
+                {lockStatus.password_set ? 'Change' : 'Set Password'}
+              </button>
+            </div>
+            <div className="flex items-center gap-2 pt-2">
+              <button onClick={showApiToken} className="btn btn-secondary text-sm">
+                Show API Token
+              </button>
+              {apiToken && <code className="text-xs text-slate-300 break-all">{apiToken}</code>}
+            </div>
+            {lockError && <p className="text-sm text-red-400">{lockError}</p>}
+          </div>
+        </section>
+      )}
+
+      <ReadOnlySettings />
+
+      {/* Users */}
+      <UsersSettings />
+
+      <HooksSettings />
+
+      <SteamAccountSettings />
+
+      <SteamTokenSettings />
+
+      <CacheSettings />
+
+      <GameImageSettings />
+
+      <GameLibrarySettings />
+
+      <DeletedServers />
+
+      <AppLogs />
+
       {/* About */}
       <section className="card mb-6">
         <h2 className="text-xl font-semibold mb-4">About Serverwave Anywhere</h2>
@@ -93,28 +306,25 @@ export function Settings() {
               <ExternalLink size={16} />
               Serverwave
             </a>
+            <button onClick={exportDiagnostics} disabled={isExporting} className="btn btn-secondary text-sm">
+              <FileArchive size={16} className={isExporting ? 'animate-pulse' : ''} />
+              {isExporting ? 'Collecting...' : 'Export Diagnostics'}
+            </button>
             <span className="text-sm text-slate-600">v0.1.0</span>
           </div>
+          {diagnosticsPath && (
+            <p className="text-xs text-slate-500 font-mono break-all">{diagnosticsPath}</p>
+          )}
+          <p className="text-xs text-slate-500">
+            Diagnostics bundle logs, Docker info and server configs (passwords removed) into a zip for bug reports.
+          </p>
         </div>
       </section>
 
+      <AppStateSettings />
+
       {/* Data Location */}
-      <section className="card">
-        <h2 className="text-xl font-semibold mb-4">Data Location</h2>
-        <div className="space-y-3 text-sm">
-          <div>
-            <span className="text-slate-500">Server Data:</span>
-            <div className="font-mono text-slate-300 mt-1">~/ServerWaveAnywhere/servers/</div>
-          </div>
-          <div>
-            <span className="text-slate-500">Configuration:</span>
-            <div className="font-mono text-slate-300 mt-1">~/ServerWaveAnywhere/config/</div>
-          </div>
-        </div>
-        <p className="text-xs text-slate-500 mt-4">
-          World saves and configs persist even when you delete a server.
-        </p>
-      </section>
+      <DataLocationSettings />
     </div>
   );
 }
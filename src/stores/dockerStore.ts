@@ -1,7 +1,7 @@
 // Docker status store
 
 import { create } from 'zustand';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/backend';
 import type { DockerStatus, DockerInfo } from '../types';
 
 interface DockerState {
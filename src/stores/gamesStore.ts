@@ -1,7 +1,7 @@
 // Games configuration store
 
 import { create } from 'zustand';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '../utils/backend';
 import type { GameConfig, GameType } from '../types';
 
 interface GamesState {
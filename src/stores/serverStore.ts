@@ -1,26 +1,18 @@
 // Server store using Zustand
 
 import { create } from 'zustand';
-import { invoke } from '@tauri-apps/api/core';
-import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import { invoke, isAppError, listen, type AppError } from '../utils/backend';
 import type {
   Server,
   CreateServerRequest,
   ServerResponse,
   LogsResponse,
+  ContainerStats,
+  ServerEvent,
+  ServerStatus,
 } from '../types';
-
-interface LogEvent {
-  server_id: string;
-  line: string;
-}
-
-interface ContainerStats {
-  cpu_percent: number;
-  memory_usage_mb: number;
-  memory_limit_mb: number;
-  memory_percent: number;
-}
+import { serverEventLines } from '../types';
 
 interface ServerState {
   servers: Server[];
@@ -32,14 +24,22 @@ interface ServerState {
   logUnlisten: UnlistenFn | null;
   statsInterval: number | null;
   isStreaming: boolean;
+  /** A start refused because the server doesn't fit in memory, until started anyway or dismissed */
+  capacityWarning: { serverId: string; error: AppError } | null;
+  /** A start that failed because something else holds one of the server's ports */
+  portWarning: { serverId: string; error: AppError } | null;
 
   fetchServers: () => Promise<void>;
+  setServerStatus: (serverId: string, status: ServerStatus) => void;
   createServer: (request: CreateServerRequest) => Promise<Server | null>;
-  startServer: (serverId: string) => Promise<void>;
+  startServer: (serverId: string, force?: boolean, movePort?: boolean) => Promise<void>;
+  dismissCapacityWarning: () => void;
+  dismissPortWarning: () => void;
   stopServer: (serverId: string) => Promise<void>;
-  deleteServer: (serverId: string, deleteData?: boolean) => Promise<void>;
+  deleteServer: (serverId: string, confirmName: string, deleteData?: boolean, trash?: boolean) => Promise<void>;
   updateServerConfig: (serverId: string, config: Record<string, string>) => Promise<boolean>;
   reinstallServer: (serverId: string) => Promise<void>;
+  retryInstall: (serverId: string) => Promise<void>;
   updateServerGame: (serverId: string) => Promise<void>;
   checkNeedsInstall: (serverId: string) => Promise<boolean>;
   selectServer: (server: Server | null) => void;
@@ -64,6 +64,8 @@ export const useServerStore = create<ServerState>((set, get) => ({
   logUnlisten: null,
   statsInterval: null,
   isStreaming: false,
+  capacityWarning: null,
+  portWarning: null,
 
   fetchServers: async () => {
     set({ isLoading: true, error: null });
@@ -81,6 +83,14 @@ export const useServerStore = create<ServerState>((set, get) => ({
     }
   },
 
+  setServerStatus: (serverId, status) => {
+    set((state) => ({
+      servers: state.servers.map((s) => (s.id === serverId ? { ...s, status } : s)),
+      selectedServer:
+        state.selectedServer?.id === serverId ? { ...state.selectedServer, status } : state.selectedServer,
+    }));
+  },
+
   createServer: async (request) => {
     set({ isLoading: true, error: null });
     try {
@@ -99,20 +109,33 @@ export const useServerStore = create<ServerState>((set, get) => ({
     }
   },
 
-  startServer: async (serverId) => {
-    set({ isLoading: true, error: null, logs: [] });
+  startServer: async (serverId, force = false, movePort = false) => {
+    set({ isLoading: true, error: null, logs: [], capacityWarning: null, portWarning: null });
     try {
       await get().attachToServer(serverId);
-      await invoke<ServerResponse>('start_server', { serverId });
+      await invoke<ServerResponse>('start_server', { serverId, force, movePort });
       await get().fetchServers();
       get().startStatsPolling(serverId);
       set({ isLoading: false });
     } catch (error) {
       console.error('[Store] startServer error:', error);
+      if (isAppError(error, 'OverCommitted')) {
+        set({ capacityWarning: { serverId, error }, isLoading: false });
+        return;
+      }
+      if (isAppError(error, 'PortInUse')) {
+        await get().fetchServers();
+        set({ portWarning: { serverId, error }, isLoading: false });
+        return;
+      }
       set({ error: String(error), isLoading: false });
     }
   },
 
+  dismissCapacityWarning: () => set({ capacityWarning: null }),
+
+  dismissPortWarning: () => set({ portWarning: null }),
+
   stopServer: async (serverId) => {
     set({ isLoading: true, error: null });
     try {
@@ -126,12 +149,12 @@ export const useServerStore = create<ServerState>((set, get) => ({
     }
   },
 
-  deleteServer: async (serverId, deleteData = true) => {
+  deleteServer: async (serverId, confirmName, deleteData = true, trash = false) => {
     set({ isLoading: true, error: null });
     try {
       get().stopStatsPolling();
       await get().detachFromServer(serverId);
-      await invoke<ServerResponse>('delete_server', { serverId, deleteData });
+      await invoke<ServerResponse>('delete_server', { serverId, confirmName, deleteData, trash });
       const selected = get().selectedServer;
       if (selected?.id === serverId) set({ selectedServer: null });
       await get().fetchServers();
@@ -169,6 +192,19 @@ export const useServerStore = create<ServerState>((set, get) => ({
     }
   },
 
+  retryInstall: async (serverId) => {
+    set({ isLoading: true, error: null });
+    try {
+      await get().attachToServer(serverId);
+      await invoke<ServerResponse>('retry_install', { serverId });
+      await get().fetchServers();
+      set({ isLoading: false });
+    } catch (error) {
+      console.error('[Store] retryInstall error:', error);
+      set({ error: String(error), isLoading: false });
+    }
+  },
+
   updateServerGame: async (serverId) => {
     set({ isLoading: true, error: null, logs: [] });
     try {
@@ -232,9 +268,18 @@ export const useServerStore = create<ServerState>((set, get) => ({
     }
 
     try {
-      const unlisten = await listen<LogEvent>('server-log', (event) => {
-        if (event.payload.server_id === serverId) {
-          set((state) => ({ logs: [...state.logs, event.payload.line] }));
+      const unlisten = await listen<ServerEvent>('server-event', (event) => {
+        if (event.payload.server_id !== serverId) return;
+        if (event.payload.type === 'StatusChanged') {
+          const status = event.payload.status;
+          set((state) => ({
+            servers: state.servers.map((s) => (s.id === serverId ? { ...s, status } : s)),
+          }));
+          return;
+        }
+        const lines = serverEventLines(event.payload);
+        if (lines.length > 0) {
+          set((state) => ({ logs: [...state.logs, ...lines] }));
         }
       });
 
@@ -8,7 +8,9 @@ export type ServerStatus =
   | 'installing'
   | 'running'
   | 'stopping'
-  | 'error';
+  | 'error'
+  /** Crashed too often to be restarted again, until started by hand */
+  | 'crashlooping';
 
 export interface Server {
   id: string;
@@ -23,6 +25,107 @@ export interface Server {
   config: Record<string, string>;
   installed: boolean;
   install_container_id?: string;
+  upnp_enabled?: boolean;
+  tunnel?: TunnelConfig | null;
+  firewall_rules?: boolean;
+  network?: string | null;
+  extra_ports?: PortConfig[];
+  install_state?: InstallState | null;
+  versions?: GameVersion[];
+  pinned_version?: GameVersion | null;
+  /** Settings changed since the container was created - applied on the next start */
+  config_outdated?: boolean;
+  /** Image used instead of the game's, like an older Java for an older Minecraft version */
+  docker_image?: string | null;
+  /** Extra environment variables beyond the game's settings */
+  env?: EnvVar[];
+  /** When automation may touch the server, null to follow the global policy */
+  maintenance?: MaintenancePolicy | null;
+  /** Start the server again when it crashes, until it keeps crashing */
+  auto_restart?: boolean;
+  /** Database container that starts and stops with the server */
+  database?: DatabaseSidecar | null;
+  /** Automatic backups, null when the server is only backed up by hand */
+  backup_schedule?: BackupSchedule | null;
+  /** Storage off this computer the backups can be copied to */
+  backup_targets?: BackupTarget[];
+  /** Started at a time of day, after waking the Docker host */
+  start_schedule?: StartSchedule | null;
+  /** How commands reach the console of the running container */
+  console?: ConsoleProbe | null;
+  /** Where install scripts may connect, null to follow the game */
+  install_network?: InstallNetwork | null;
+}
+
+/** Restricted installs only reach the allowed domains (and their subdomains), through a proxy */
+export type InstallNetwork =
+  | { mode: 'open' }
+  | { mode: 'restricted'; allowed_domains: string[] };
+
+export type ConsoleBridge =
+  | { type: 'screen'; session: string }
+  | { type: 'tmux'; session: string }
+  | { type: 'stdin' }
+  | { type: 'mc_send_to_console' }
+  | { type: 'rcon_cli' };
+
+export interface ConsoleProbe {
+  container_id: string;
+  /** Tried in order until one takes the command */
+  bridges: ConsoleBridge[];
+}
+
+export type DatabaseKind = 'mariadb' | 'mysql' | 'redis';
+
+export interface DatabaseSidecar {
+  kind: DatabaseKind;
+  container_id: string | null;
+  created_at: string;
+}
+
+/** How a server reaches its database */
+export interface DatabaseInfo {
+  kind: DatabaseKind;
+  host: string;
+  port: number;
+  database: string | null;
+  user: string | null;
+  password: string;
+  /** The variables the server gets */
+  variables: string[];
+  running: boolean;
+}
+
+export interface EnvVar {
+  key: string;
+  value: string;
+}
+
+/** A game build an install put in place */
+export interface GameVersion {
+  build: string;
+  minecraft_version: string | null;
+  installed_at: string;
+}
+
+/** What a failed install got through - a retry picks up from there */
+export interface InstallState {
+  attempts: number;
+  failed_step: string | null;
+  steamcmd_done: boolean;
+  exit_code: number | null;
+  failed_at?: string | null;
+}
+
+export type TunnelProvider = 'ngrok' | 'cloudflared';
+
+export interface TunnelConfig {
+  provider: TunnelProvider;
+  /** Only sent when setting it - the token is kept in the keychain */
+  token?: string;
+  has_token?: boolean;
+  enabled: boolean;
+  hostname?: string | null;
 }
 
 export type PortProtocol = 'tcp' | 'udp' | 'both';
@@ -32,6 +135,8 @@ export interface PortConfig {
   protocol: PortProtocol;
   description?: string;
   env_var?: string; // Environment variable that maps to this port
+  /** Published on this machine only, for admin APIs the app talks to */
+  local_only?: boolean;
 }
 
 export type SystemMapping = 'none' | 'ram' | 'port';
@@ -68,6 +173,9 @@ export interface GameConfig {
   docker_image: string;
   startup: string;
   stop_command: string;
+  stop?: StopConfig;
+  /** Seconds a started server gets to answer on its port before a warning */
+  start_timeout_secs?: number;
   variables: Variable[];
   ports: PortConfig[];
   volume_path: string;
@@ -80,12 +188,95 @@ export interface GameConfig {
   config_files: ConfigFile[];
   is_custom: boolean;
   console: boolean;
+  join_instructions?: string | null;
+  actions?: GameAction[];
+  player_log?: PlayerLogRules | null;
+  /** Variable the assigned Steam server token is passed in */
+  gslt_var?: string | null;
+  /** Folders holding the worlds or saves, relative to the data folder */
+  save_folders?: string[];
+  /** Images a server can switch to with its IMAGE_VARIANT setting */
+  image_variants?: ImageVariant[];
+  /** Variables holding the Source RCON port and password, for commands sent over RCON */
+  rcon?: RconSettings | null;
+  /** Where the game answers Steam's server queries, for its player count */
+  steam_query?: SteamQuery | null;
+  /** Where install scripts may connect, null for anywhere */
+  install_network?: InstallNetwork | null;
+}
+
+export interface SteamQuery {
+  /** null when the query port is the game port */
+  port_var?: string | null;
+}
+
+export interface RconSettings {
+  port_var: string;
+  password_var: string;
+}
+
+/** The setting holding a server's image variant, listed with the game's variables */
+export const IMAGE_VARIANT_VAR = 'IMAGE_VARIANT';
+
+export interface ImageVariant {
+  id: string;
+  label: string;
+  image: string;
+}
+
+export type StopMethod = 'command' | 'sigint' | 'sigterm';
+
+/** How a server is asked to stop, and how long it gets before it's killed */
+export interface StopConfig {
+  method: StopMethod;
+  timeout_secs: number;
+}
+
+/** Console line patterns with a {player} placeholder */
+export interface PlayerLogRules {
+  joined: string[];
+  left: string[];
+}
+
+export interface GameAction {
+  id: string;
+  label: string;
+  description: string | null;
+  command: string | null;
+  script: string | null;
+  confirm: boolean;
 }
 
 export interface DockerStatus {
   available: boolean;
   running: boolean;
   error: string | null;
+  endpoint: string | null;
+  version: string | null;
+}
+
+export interface DockerConnection {
+  socket: string | null;
+  url: string | null;
+  tls_ca: string | null;
+  tls_cert: string | null;
+  tls_key: string | null;
+  timeout_secs: number;
+  /** Powering on a remote host that sleeps, when set */
+  wake?: WakeOnLan | null;
+}
+
+export interface WakeOnLan {
+  mac: string;
+  broadcast: string;
+  port: number;
+  /** How long the host may take to boot before Docker answers */
+  boot_secs: number;
+}
+
+export interface DetectedSocket {
+  runtime: string;
+  path: string;
 }
 
 export interface DockerInfo {
@@ -96,6 +287,53 @@ export interface DockerInfo {
   containers_running: number;
   containers_total: number;
   images: number;
+  memory_bytes: number;
+}
+
+export interface PterodactylServer {
+  identifier: string;
+  name: string;
+  description: string;
+  egg_name: string | null;
+  docker_image: string;
+  memory_mb: number | null;
+  port: number | null;
+  extra_ports: number[];
+  sftp_host: string;
+  sftp_port: number;
+  is_suspended: boolean;
+}
+
+export interface PterodactylImportRequest {
+  panelUrl: string;
+  apiKey: string;
+  identifier: string;
+  sftpUsername?: string;
+  sftpPassword: string;
+  name?: string;
+  openFirewall?: boolean;
+}
+
+/** What a server folder on this machine was found to hold */
+export interface LocalScan {
+  path: string;
+  game_type: string | null;
+  config: Record<string, string>;
+  port: number | null;
+  size_bytes: number;
+}
+
+export type LocalImportMode = 'copy' | 'inplace';
+
+export interface LocalImportRequest {
+  path: string;
+  name: string;
+  gameType: string;
+  mode: LocalImportMode;
+  port?: number;
+  memoryMb?: number;
+  config?: Record<string, string>;
+  openFirewall?: boolean;
 }
 
 export interface ServerResponse {
@@ -115,6 +353,794 @@ export interface CreateServerRequest {
   port?: number;
   config?: Record<string, string>;
   memory_mb?: number;
+  open_firewall?: boolean;
+}
+
+// A remote agent (another machine running `serverwave-anywhere --agent`)
+export interface NodeInfo {
+  id: string;
+  name: string;
+  url: string;
+  online: boolean;
+  version: string | null;
+  error: string | null;
+}
+
+export interface AppSettings {
+  default_memory_mb: number | null;
+  port_range_start: number;
+  port_range_end: number;
+  data_dir: string | null;
+  max_concurrent_installs: number;
+  backups: {
+    directory: string | null;
+    retention_count: number;
+  };
+  notifications: {
+    enabled: boolean;
+    webhook_url: string | null;
+    on_crash: boolean;
+    on_install_finished: boolean;
+    on_low_disk: boolean;
+  };
+  curseforge_api_key: string | null;
+  updates: {
+    check: boolean;
+    auto_update: boolean;
+    interval_hours: number;
+    refresh_images: boolean;
+    image_interval_hours: number;
+  };
+  docker: DockerConnection;
+  maintenance: MaintenancePolicy;
+  open_auth_links: boolean;
+  game_sync: GameSyncSettings;
+  disk_guard: DiskGuardSettings;
+}
+
+export interface DiskGuardSettings {
+  enabled: boolean;
+  min_free_gb: number;
+  check_interval_minutes: number;
+  pause_installs: boolean;
+  pause_backups: boolean;
+}
+
+export interface DiskSpace {
+  path: string;
+  free_bytes: number;
+  min_free_bytes: number;
+  low: boolean;
+  checked_at: string;
+}
+
+export interface AppNotification {
+  title: string;
+  body: string;
+  sent_at: string;
+}
+
+export interface GameSyncSettings {
+  repo_url: string | null;
+  branch: string | null;
+  push_on_save: boolean;
+}
+
+export interface GameSyncReport {
+  direction: 'pull' | 'push';
+  at: string;
+  commit: string | null;
+  games: string[];
+  failed: string[];
+  error: string | null;
+}
+
+export interface GameSyncStatus {
+  repo_url: string | null;
+  has_token: boolean;
+  last: GameSyncReport | null;
+}
+
+export type UserRole = 'admin' | 'operator' | 'viewer';
+
+export interface User {
+  id: string;
+  name: string;
+  role: UserRole;
+  servers: string[];
+  /** Only views, whatever the role allows */
+  read_only?: boolean;
+  created_at: string;
+}
+
+export interface CurrentUser {
+  name: string;
+  role: UserRole;
+  /** Servers the caller may access, null meaning all of them */
+  servers: string[] | null;
+  read_only: boolean;
+}
+
+export interface NewUser {
+  user: User;
+  token: string;
+}
+
+export interface LockStatus {
+  password_set: boolean;
+  unlocked: boolean;
+}
+
+export interface NetworkPort {
+  container_port: number;
+  host_port: number;
+  protocol: 'tcp' | 'udp';
+  host_ip: string;
+  description: string | null;
+}
+
+export interface ServerNetwork {
+  /** False when there's no container yet - the ports are the ones it will publish */
+  from_container: boolean;
+  ports: NetworkPort[];
+  networks: string[];
+}
+
+/** What became of a deleted server's files */
+export type DataFate = 'kept' | 'trashed' | 'deleted';
+
+export interface Tombstone {
+  server_id: string;
+  name: string;
+  game_type: string;
+  deleted_at: string;
+  deleted_by: string;
+  data_path: string;
+  data_bytes: number;
+  data: DataFate;
+  trash_path: string | null;
+}
+
+export type AuditEntry = { type: 'server_deleted' } & Tombstone;
+
+export type AppErrorKind =
+  | 'DockerUnavailable'
+  | 'Docker'
+  | 'ServerNotFound'
+  | 'GameNotFound'
+  | 'PortConflict'
+  | 'PortInUse'
+  | 'InstallFailed'
+  | 'InstallIncomplete'
+  | 'Locked'
+  | 'ReadOnly'
+  | 'OverCommitted'
+  | 'InvalidInput'
+  | 'Io'
+  | 'Other'
+  // Remote API only
+  | 'UnknownCommand'
+  | 'InvalidArgs'
+  | 'Forbidden';
+
+/** Error shape every command rejects with */
+export interface AppErrorPayload {
+  kind: AppErrorKind;
+  message: string;
+  serverId?: string;
+  gameType?: string;
+  port?: number;
+  serverName?: string;
+  exitCode?: number;
+  /** InstallIncomplete - startup files the install didn't leave behind */
+  missing?: string[];
+  // PortInUse - the container holding the port, and a free one the server could move to
+  holder?: string | null;
+  freePort?: number | null;
+  // OverCommitted
+  requestedMb?: number;
+  availableMb?: number;
+  totalMb?: number;
+  committedMb?: number;
+  runningServers?: string[];
+}
+
+export interface ContainerStats {
+  cpu_percent: number;
+  memory_usage_mb: number;
+  memory_limit_mb: number;
+  memory_percent: number;
+}
+
+export type InstallStage = 'queued' | 'started' | 'completed' | 'failed';
+
+/** Payload of `server-state-changed` - a server's container started or stopped */
+export interface ServerStateChanged {
+  server_id: string;
+  status: ServerStatus;
+}
+
+/** Payload of the `server-event` channel, tagged by `type` */
+export type ServerEvent =
+  | { type: 'StatusChanged'; server_id: string; status: ServerStatus }
+  | { type: 'LogLine'; server_id: string; line: string }
+  /** Console or install output sent in batches, oldest line first */
+  | { type: 'LogLines'; server_id: string; lines: string[] }
+  | { type: 'InstallProgress'; server_id: string; stage: InstallStage; message: string; exit_code?: number }
+  | { type: 'Crashed'; server_id: string; exit_code: number }
+  | { type: 'Stats'; server_id: string; stats: ContainerStats }
+  | { type: 'Alert'; server_id: string; level: 'warning' | 'error'; message: string }
+  | { type: 'SteamGuardRequired'; server_id: string; prompt: string }
+  | { type: 'InstallAuthRequired'; server_id: string; url: string; code: string | null }
+  | { type: 'InstallAuthCompleted'; server_id: string }
+  | { type: 'AutomationHeld'; server_id: string; task: string; message: string; deferred_until: string | null }
+  | { type: 'BackupDone'; server_id: string; path: string; size_bytes: number }
+  | {
+      type: 'BackupTransfer';
+      server_id: string;
+      backup_id: string;
+      target_id: string;
+      direction: 'upload' | 'download';
+      done_bytes: number;
+      total_bytes: number;
+    };
+
+/** Console lines for an event - none when it doesn't belong in the console */
+export function serverEventLines(event: ServerEvent): string[] {
+  switch (event.type) {
+    case 'LogLine':
+      return [event.line];
+    case 'LogLines':
+      return event.lines;
+    case 'InstallProgress':
+    case 'Alert':
+    case 'AutomationHeld':
+      return [`[Serverwave] ${event.message}`];
+    case 'Crashed':
+      return [`[Serverwave] Server stopped unexpectedly (exit code ${event.exit_code})`];
+    default:
+      return [];
+  }
+}
+
+export type HookTrigger =
+  | 'started'
+  | 'stopped'
+  | 'crashed'
+  | 'install_finished'
+  | 'install_failed'
+  | 'backup_done'
+  | 'alert';
+
+export type HookAction =
+  | { type: 'command'; command: string }
+  | { type: 'http'; url: string }
+  | { type: 'script'; path: string; args: string[] };
+
+export interface Hook {
+  id: string;
+  name: string;
+  enabled: boolean;
+  trigger: HookTrigger;
+  server_id: string | null;
+  action: HookAction;
+}
+
+export interface ModrinthProject {
+  project_id: string;
+  slug: string;
+  title: string;
+  description: string;
+  author: string;
+  icon_url: string | null;
+  downloads: number;
+  project_type: string;
+}
+
+export interface CurseForgeProject {
+  id: number;
+  name: string;
+  slug: string;
+  summary: string;
+  author: string;
+  icon_url: string | null;
+  downloads: number;
+}
+
+export interface ModpackInstall {
+  name: string;
+  version: string;
+  minecraft_version: string | null;
+  loader: string | null;
+  files: number;
+  manual_downloads: string[];
+}
+
+export interface InstalledMod {
+  project_id: string;
+  version_id: string;
+  version_number: string;
+  file_name: string;
+  replaced: string[];
+  dependencies: string[];
+  warnings: string[];
+}
+
+export interface InstalledContent {
+  file_name: string;
+  size_bytes: number;
+  source: 'modrinth' | 'curseforge' | null;
+  project_id: string | null;
+  name: string | null;
+  version: string | null;
+  latest_version: string | null;
+  update_available: boolean;
+}
+
+export interface ContentUpdateResult {
+  file_name: string;
+  outcome: 'updated' | 'up_to_date' | 'unknown' | 'failed';
+  message: string | null;
+  new_file: string | null;
+}
+
+export type ServerFlavor = 'paper' | 'purpur' | 'fabric' | 'forge';
+
+export interface VersionChange {
+  flavor: ServerFlavor;
+  minecraft_version: string;
+  build: string;
+  jar_file: string;
+  backup: string | null;
+}
+
+export interface MinecraftWorld {
+  name: string;
+  active: boolean;
+  size_bytes: number;
+  dimensions: string[];
+  last_played: number | null;
+}
+
+export type PlayerList = 'whitelist' | 'ops' | 'bans' | 'allowlist';
+
+export interface PlayerSession {
+  player: string;
+  joined_at: string;
+  /** null while they're still on */
+  left_at: string | null;
+  duration_secs: number;
+}
+
+/** What a Minecraft server answers a server list ping with */
+export interface ServerPing {
+  motd: string;
+  version: string;
+  players: number;
+  max_players: number;
+  /** Some of who's on - Java servers list up to a dozen, Bedrock servers nobody */
+  player_names: string[];
+}
+
+export interface ServerStatusReport {
+  status: ServerStatus;
+  /** null for other games, and while the server isn't answering yet */
+  ping: ServerPing | null;
+}
+
+/** What a running server says about itself when queried */
+export interface ServerQuery {
+  name: string;
+  map: string | null;
+  players: number;
+  max_players: number;
+  /** Empty when the server keeps its player list to itself */
+  player_names: string[];
+}
+
+export interface SessionRange {
+  from: string | null;
+  to: string | null;
+}
+
+export interface PlayerEntry {
+  name: string;
+  uuid: string | null;
+  level: number | null;
+  reason: string | null;
+  expires: string | null;
+}
+
+export interface Datapack {
+  name: string;
+  folder: boolean;
+  size_bytes: number;
+}
+
+export interface ResourcePack {
+  url: string;
+  sha1: string;
+  required: boolean;
+}
+
+export interface CacheUsage {
+  files: number;
+  size_bytes: number;
+  steamcmd_bytes: number;
+}
+
+/** Result of checking an install script without running it */
+export interface ScriptLint {
+  shell: string;
+  syntax_ok: boolean;
+  errors: string[];
+  shellcheck: string[] | null;
+}
+
+export type UpdateSource =
+  | { kind: 'steam'; app_id: string }
+  | { kind: 'minecraft'; flavor: ServerFlavor; minecraft_version: string };
+
+/** Installed build compared with the latest published one */
+export interface UpdateCheck {
+  server_id: string;
+  source: UpdateSource;
+  installed: string;
+  latest: string | null;
+  update_available: boolean;
+  checked_at: string;
+  error: string | null;
+}
+
+/** A zip of a server's files, from list_backups */
+export interface Backup {
+  id: string;
+  server_id: string;
+  server_name: string;
+  game_type: string;
+  path: string;
+  size_bytes: number;
+  note: string | null;
+  created_at: string;
+}
+
+export type ServerRootKind = 'data' | 'config' | 'save' | 'logs';
+
+/** A place in a server's files the file manager can jump to */
+export interface ServerRoot {
+  kind: ServerRootKind;
+  label: string;
+  path: string;
+  is_dir: boolean;
+}
+
+export type BackupTiming =
+  | { type: 'interval'; hours: number }
+  | { type: 'daily'; days: Weekday[]; hour: number; minute: number };
+
+export interface BackupSchedule {
+  enabled: boolean;
+  timing: BackupTiming;
+  /** Backups kept, the Settings retention count when null */
+  keep_count: number | null;
+  /** Oldest backups are removed past this many MB in total */
+  max_total_mb: number | null;
+  last_run?: string | null;
+  next_run?: string | null;
+}
+
+export interface StartSchedule {
+  enabled: boolean;
+  /** Every day when empty */
+  days: Weekday[];
+  hour: number;
+  minute: number;
+  last_run?: string | null;
+  next_run?: string | null;
+}
+
+/** A console command sent ahead of a scheduled restart */
+export interface RestartWarning {
+  minutes_before: number;
+  command: string;
+}
+
+export type ScheduledAction =
+  | { type: 'restart'; warnings: RestartWarning[] }
+  | { type: 'backup' }
+  | { type: 'command'; command: string }
+  | { type: 'update' };
+
+/** A server action run on a cron expression */
+export interface Schedule {
+  id: string;
+  server_id: string;
+  name: string;
+  /** Five fields in local time, like "0 5 * * *" */
+  cron: string;
+  action: ScheduledAction;
+  enabled: boolean;
+  last_run?: string | null;
+  last_result?: string | null;
+  next_run?: string | null;
+}
+
+export type BackupStorage =
+  | { kind: 's3'; endpoint: string; region: string; bucket: string; access_key_id: string; path_style: boolean }
+  | { kind: 'webdav'; url: string; username: string };
+
+/** An S3 bucket or WebDAV folder backups are copied to - its secret is kept in the keychain */
+export interface BackupTarget {
+  id: string;
+  name: string;
+  storage: BackupStorage;
+  prefix: string;
+  /** Copy every new backup here */
+  upload_new: boolean;
+}
+
+/** A backup on a target, from list_remote_backups */
+export interface RemoteBackup {
+  id: string;
+  size_bytes: number;
+  created_at: string | null;
+  /** Also in the local backups */
+  local: boolean;
+}
+
+export interface RecentEvent {
+  event: ServerEvent;
+  at: string;
+}
+
+/** Everything the home screen shows, from get_dashboard_summary */
+export interface DashboardSummary {
+  servers: number;
+  by_status: Partial<Record<ServerStatus, number>>;
+  allocated_memory_mb: number;
+  running_memory_mb: number;
+  /** 0 when unknown */
+  host_memory_mb: number;
+  host_free_memory_mb: number | null;
+  disk_used_bytes: number;
+  disk_free_bytes: number | null;
+  recent_events: RecentEvent[];
+  pending_updates: UpdateCheck[];
+}
+
+export type InstallPhase = 'installing' | 'queued' | 'failed';
+
+/** One server in the install queue */
+export interface InstallActivity {
+  server_id: string;
+  server_name: string;
+  game_type: string;
+  phase: InstallPhase;
+  step: string | null;
+  attempts: number;
+  queued_at: string | null;
+  started_at: string | null;
+  failed_at: string | null;
+  exit_code: number | null;
+}
+
+/** A server whose container was created from an older pull of its game image */
+export interface StaleImage {
+  server_id: string;
+  server_name: string;
+  image: string;
+  found_at: string;
+}
+
+export interface ImagePlatform {
+  host: string | null;
+  platforms: string[];
+  native: boolean;
+}
+
+export interface ImageRefresh {
+  pulled: string[];
+  failed: string[];
+  stale: StaleImage[];
+}
+
+export interface InstallImage {
+  image: string;
+  size_bytes: number | null;
+  servers: string[];
+  game_image: boolean;
+}
+
+/** The certificate the API serves over HTTPS */
+export interface ApiIdentity {
+  fingerprint: string;
+  self_signed: boolean;
+}
+
+export interface RemoteAccess {
+  listening: string | null;
+  identity: ApiIdentity | null;
+}
+
+/** A one-time code a remote app trades for its own token */
+export interface PairingCode {
+  code: string;
+  expires_at: string;
+  identity: ApiIdentity | null;
+}
+
+/** A Steam Game Server Login Token - the token itself stays in the keychain */
+export interface ServerToken {
+  id: string;
+  label: string;
+  hint: string;
+  server_id: string | null;
+  added_at: string;
+  last_check: TokenCheck | null;
+}
+
+export interface TokenCheck {
+  checked_at: string;
+  valid: boolean;
+  banned: boolean;
+  expires_at: string | null;
+  steam_id: string | null;
+  error: string | null;
+}
+
+export interface ServerTokens {
+  tokens: ServerToken[];
+  has_web_api_key: boolean;
+}
+
+export interface ImageCleanup {
+  removed: string[];
+  failed: string[];
+  reclaimed_bytes: number;
+}
+
+export type Reconciled = 'resumed' | 'stopped' | 'crashed' | 'install_interrupted';
+
+export interface ReconciledServer {
+  server_id: string;
+  server_name: string;
+  outcome: Reconciled;
+  exit_code: number | null;
+}
+
+export interface Reconciliation {
+  checked_at: string;
+  servers: ReconciledServer[];
+  reattached: number;
+}
+
+export type WipeKind = 'map' | 'full';
+export type WipeFrequency = 'weekly' | 'biweekly' | 'monthly';
+export type Weekday = 'Mon' | 'Tue' | 'Wed' | 'Thu' | 'Fri' | 'Sat' | 'Sun';
+
+/** Local time on some days (every day when `days` is empty); an end before the start runs past midnight */
+export interface TimeWindow {
+  days: Weekday[];
+  start_hour: number;
+  start_minute: number;
+  end_hour: number;
+  end_minute: number;
+}
+
+/** Automation never runs in quiet hours, and only inside a window when any are set */
+export interface MaintenancePolicy {
+  quiet_hours: TimeWindow[];
+  windows: TimeWindow[];
+}
+
+export interface WipeSchedule {
+  server_id: string;
+  enabled: boolean;
+  kind: WipeKind;
+  frequency: WipeFrequency;
+  weekday: Weekday;
+  hour: number;
+  minute: number;
+  new_seed: boolean;
+  hostname_date: boolean;
+  last_wipe: string | null;
+  next_wipe: string | null;
+}
+
+export interface WipeResult {
+  deleted: string[];
+  seed: string | null;
+  hostname: string | null;
+}
+
+/** What a server was doing when it was given up on for crashing over and over */
+export interface CrashReport {
+  server_id: string;
+  server_name: string;
+  game_type: string;
+  image: string | null;
+  crashes: string[];
+  exit_code: number;
+  log: string[];
+  created_at: string;
+}
+
+export interface PalworldPlayer {
+  name: string;
+  account_name: string;
+  player_id: string;
+  /** What kicks and bans take, e.g. steam_76561198000000000 */
+  user_id: string;
+  ip: string;
+  ping: number;
+  level: number;
+  location_x: number;
+  location_y: number;
+  building_count: number | null;
+}
+
+export interface PalworldStatus {
+  info: { version: string; servername: string; description: string; worldguid: string };
+  metrics: {
+    serverfps: number;
+    currentplayernum: number;
+    serverframetime: number;
+    maxplayernum: number;
+    uptime: number;
+    days: number | null;
+    basecampnum: number | null;
+  };
+  players: PalworldPlayer[];
+}
+
+export interface PublishedPort {
+  container_port: number;
+  host_port: number;
+  protocol: 'tcp' | 'udp';
+  host_ip: string;
+}
+
+export interface StartupPreview {
+  image: string;
+  startup: string | null;
+  command: string[] | null;
+  env: Record<string, string>;
+  ports: PublishedPort[];
+  binds: string[];
+  memory_mb: number;
+  container_outdated: boolean;
+  warnings: string[];
+}
+
+export interface ConfigFileDiff {
+  path: string;
+  exists: boolean;
+  diff: string;
+  error: string | null;
+}
+
+export interface StateImport {
+  servers: string[];
+  skipped_servers: string[];
+  needs_install: string[];
+  custom_games: string[];
+  wipe_schedules: number;
+  hooks: number;
+}
+
+export type CheckStatus = 'ok' | 'warning' | 'failed' | 'skipped';
+
+export interface OnboardingCheck {
+  id: string;
+  label: string;
+  status: CheckStatus;
+  detail: string;
+  fix: string | null;
+}
+
+export interface OnboardingChecks {
+  ready: boolean;
+  checks: OnboardingCheck[];
 }
 
 export const DEFAULT_GAME_CONFIG: GameConfig = {
@@ -0,0 +1,154 @@
+// Command transport - the local Tauri backend, or a headless daemon over HTTP/WebSocket
+
+import { invoke as tauriInvoke, type InvokeArgs } from '@tauri-apps/api/core';
+import { listen as tauriListen, type EventCallback, type UnlistenFn } from '@tauri-apps/api/event';
+import type { AppErrorKind, AppErrorPayload } from '../types';
+
+export interface RemoteBackend {
+  url: string;
+  token: string;
+}
+
+const REMOTE_KEY = 'serverwave.remoteBackend';
+
+export function getRemoteBackend(): RemoteBackend | null {
+  const raw = localStorage.getItem(REMOTE_KEY);
+  if (!raw) return null;
+  try {
+    return JSON.parse(raw) as RemoteBackend;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Point the app at a daemon started with `--headless` (null switches back to local)
+ */
+export function setRemoteBackend(remote: RemoteBackend | null) {
+  if (remote) {
+    localStorage.setItem(REMOTE_KEY, JSON.stringify(remote));
+  } else {
+    localStorage.removeItem(REMOTE_KEY);
+  }
+}
+
+/**
+ * A failed command. Stringifies to its message, so `String(error)` still reads well.
+ */
+export class AppError extends Error implements AppErrorPayload {
+  kind: AppErrorKind;
+  serverId?: string;
+  gameType?: string;
+  port?: number;
+  serverName?: string;
+  exitCode?: number;
+  requestedMb?: number;
+  availableMb?: number;
+  totalMb?: number;
+  committedMb?: number;
+  runningServers?: string[];
+
+  constructor(payload: AppErrorPayload) {
+    super(payload.message);
+    this.name = 'AppError';
+    Object.assign(this, payload);
+    this.kind = payload.kind;
+  }
+
+  toString() {
+    return this.message;
+  }
+}
+
+export function isAppError(error: unknown, kind?: AppErrorKind): error is AppError {
+  return error instanceof AppError && (kind === undefined || error.kind === kind);
+}
+
+function toAppError(error: unknown): AppError {
+  if (error instanceof AppError) return error;
+  if (error && typeof error === 'object' && 'kind' in error) {
+    const payload = error as Partial<AppErrorPayload> & { error?: string };
+    return new AppError({
+      ...payload,
+      kind: payload.kind ?? 'Other',
+      message: payload.message ?? payload.error ?? 'Unknown error',
+    } as AppErrorPayload);
+  }
+  return new AppError({ kind: 'Other', message: String(error) });
+}
+
+/**
+ * Drop-in replacement for Tauri's invoke that honours the remote backend setting.
+ * Errors are thrown as `AppError`, whether the command ran locally or remotely.
+ */
+export async function invoke<T>(command: string, args?: InvokeArgs): Promise<T> {
+  const remote = getRemoteBackend();
+  if (!remote) {
+    try {
+      return await tauriInvoke<T>(command, args);
+    } catch (error) {
+      throw toAppError(error);
+    }
+  }
+
+  const response = await fetch(`${remote.url.replace(/\/$/, '')}/api/v1/invoke/${command}`, {
+    method: 'POST',
+    headers: {
+      'Content-Type': 'application/json',
+      Authorization: `Bearer ${remote.token}`,
+    },
+    body: JSON.stringify(args ?? {}),
+  });
+  const body = await response.json().catch(() => null);
+  if (!response.ok) {
+    throw toAppError(body ?? `Request failed with status ${response.status}`);
+  }
+  return body as T;
+}
+
+type RemoteHandler = (payload: unknown) => void;
+
+let socket: WebSocket | null = null;
+const remoteHandlers = new Map<string, Set<RemoteHandler>>();
+
+function ensureSocket(remote: RemoteBackend) {
+  if (socket && socket.readyState <= WebSocket.OPEN) return;
+
+  const url = `${remote.url.replace(/\/$/, '').replace(/^http/, 'ws')}/api/v1/ws?token=${encodeURIComponent(remote.token)}`;
+  socket = new WebSocket(url);
+  socket.onopen = () => socket?.send(JSON.stringify({ type: 'subscribe', serverIds: null }));
+  socket.onmessage = (message) => {
+    const data = JSON.parse(message.data);
+    if (data.type !== 'event') return;
+    remoteHandlers.get(data.event)?.forEach((handler) => handler(data.payload));
+  };
+  socket.onclose = () => {
+    socket = null;
+    // Reconnect while anyone is still listening
+    if (remoteHandlers.size > 0) {
+      setTimeout(() => ensureSocket(remote), 3000);
+    }
+  };
+}
+
+/**
+ * Drop-in replacement for Tauri's listen; remote events arrive over the daemon's WebSocket
+ */
+export async function listen<T>(event: string, handler: EventCallback<T>): Promise<UnlistenFn> {
+  const remote = getRemoteBackend();
+  if (!remote) {
+    return tauriListen<T>(event, handler);
+  }
+
+  const wrapped: RemoteHandler = (payload) =>
+    handler({ event, id: 0, payload: payload as T } as Parameters<EventCallback<T>>[0]);
+  if (!remoteHandlers.has(event)) remoteHandlers.set(event, new Set());
+  remoteHandlers.get(event)!.add(wrapped);
+  ensureSocket(remote);
+
+  return () => {
+    const handlers = remoteHandlers.get(event);
+    handlers?.delete(wrapped);
+    if (handlers && handlers.size === 0) remoteHandlers.delete(event);
+  };
+}